@@ -0,0 +1,77 @@
+//! Chunking benchmarks: the hierarchical and hybrid chunkers on documents
+//! of growing size
+//!
+//! ```bash
+//! cargo bench --bench chunking
+//! ```
+//!
+//! The hybrid chunker counts tokens with the approximate tokenizer, so no
+//! model is downloaded; `hybrid_split` measures the split pass on sections
+//! far over the token limit.
+
+mod common;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use docling_rs::chunking::{BaseChunker, HierarchicalChunker, HybridChunker};
+use docling_rs::DoclingDocument;
+
+/// Number of text bytes of `doc`
+fn text_bytes(doc: &DoclingDocument) -> u64 {
+    doc.nodes()
+        .iter()
+        .filter_map(|node| node.text_content())
+        .map(|text| text.len() as u64)
+        .sum()
+}
+
+/// Benchmark `chunker` on each of `docs`, labelled by size
+fn bench_chunker(
+    c: &mut Criterion,
+    name: &str,
+    chunker: &dyn BaseChunker,
+    docs: &[(usize, DoclingDocument)],
+) {
+    let mut group = c.benchmark_group(name);
+    for (size, doc) in docs {
+        group.throughput(Throughput::Bytes(text_bytes(doc)));
+        group.bench_with_input(BenchmarkId::from_parameter(size), doc, |b, doc| {
+            b.iter(|| chunker.chunk(doc).count())
+        });
+    }
+    group.finish();
+}
+
+fn documents() -> Vec<(usize, DoclingDocument)> {
+    [10, 100, 1000]
+        .into_iter()
+        .map(|sections| (sections, common::document(sections)))
+        .collect()
+}
+
+fn hierarchical(c: &mut Criterion) {
+    bench_chunker(c, "hierarchical", &HierarchicalChunker::new(), &documents());
+}
+
+fn hybrid(c: &mut Criterion) {
+    let chunker = HybridChunker::builder()
+        .max_tokens(256)
+        .build()
+        .expect("hybrid chunker");
+    bench_chunker(c, "hybrid", &chunker, &documents());
+}
+
+fn hybrid_split(c: &mut Criterion) {
+    let chunker = HybridChunker::builder()
+        .max_tokens(128)
+        .build()
+        .expect("hybrid chunker");
+    // Sections of about 1k, 10k and 100k tokens
+    let docs: Vec<_> = [60, 600, 6000]
+        .into_iter()
+        .map(|sentences| (sentences, common::long_sections(4, sentences)))
+        .collect();
+    bench_chunker(c, "hybrid_split", &chunker, &docs);
+}
+
+criterion_group!(benches, hierarchical, hybrid, hybrid_split);
+criterion_main!(benches);
@@ -0,0 +1,208 @@
+//! Generated fixtures for the benchmarks
+//!
+//! Documents are built from a fixed word list with a fixed seed, so every run
+//! measures the same input. Sizes are in sections (Markdown, HTML), rows
+//! (CSV) or pages (PDF); a section holds a heading, paragraphs, a list and,
+//! every few sections, a code block and a table, like technical documentation.
+
+#![allow(dead_code)]
+
+use docling_rs::backend::{Backend, MarkdownBackend};
+use docling_rs::datamodel::InputDocument;
+use docling_rs::{DoclingDocument, InputFormat};
+
+/// Vocabulary of the generated text
+const WORDS: &str = "the document model converts pages into structured nodes with headings \
+     tables and lists while layout analysis keeps reading order for retrieval pipelines that \
+     split text chunks by tokens embedding search results quarterly revenue grew across regions";
+
+/// Deterministic word generator (linear congruential)
+struct Words {
+    state: u64,
+    vocabulary: Vec<&'static str>,
+}
+
+impl Words {
+    fn new() -> Self {
+        Self {
+            state: 0x5eed,
+            vocabulary: WORDS.split_whitespace().collect(),
+        }
+    }
+
+    fn next(&mut self) -> &'static str {
+        self.state = self
+            .state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        self.vocabulary[(self.state >> 33) as usize % self.vocabulary.len()]
+    }
+
+    /// A sentence of `len` words
+    fn sentence(&mut self, len: usize) -> String {
+        let words: Vec<_> = (0..len).map(|_| self.next()).collect();
+        let mut sentence = words.join(" ");
+        sentence[..1].make_ascii_uppercase();
+        sentence.push('.');
+        sentence
+    }
+
+    /// A paragraph of `sentences` sentences
+    fn paragraph(&mut self, sentences: usize) -> String {
+        (0..sentences)
+            .map(|i| self.sentence(8 + i % 9))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Markdown document of `sections` sections
+pub fn markdown(sections: usize) -> Vec<u8> {
+    let mut words = Words::new();
+    let mut out = String::from("# Benchmark Report\n\n");
+    for i in 0..sections {
+        let level = if i % 4 == 0 { "##" } else { "###" };
+        out.push_str(&format!("{} Section {}\n\n", level, i + 1));
+        for _ in 0..3 {
+            out.push_str(&words.paragraph(4));
+            out.push_str("\n\n");
+        }
+        for _ in 0..4 {
+            out.push_str(&format!("- {}\n", words.sentence(6)));
+        }
+        out.push('\n');
+        if i % 3 == 0 {
+            out.push_str("```rust\nfn main() {\n    println!(\"section\");\n}\n```\n\n");
+        }
+        if i % 5 == 0 {
+            out.push_str("| Region | Quarter | Revenue |\n|---|---|---|\n");
+            for row in 0..6 {
+                out.push_str(&format!(
+                    "| {} | Q{} | {} |\n",
+                    words.next(),
+                    row % 4 + 1,
+                    row * 7
+                ));
+            }
+            out.push('\n');
+        }
+    }
+    out.into_bytes()
+}
+
+/// HTML page of `sections` sections
+pub fn html(sections: usize) -> Vec<u8> {
+    let mut words = Words::new();
+    let mut out = String::from(
+        "<!DOCTYPE html>\n<html><head><title>Benchmark Report</title></head><body>\n\
+         <nav><a href=\"/\">Home</a> <a href=\"/docs\">Docs</a></nav>\n<main>\n\
+         <h1>Benchmark Report</h1>\n",
+    );
+    for i in 0..sections {
+        let tag = if i % 4 == 0 { "h2" } else { "h3" };
+        out.push_str(&format!("<section><{0}>Section {1}</{0}>\n", tag, i + 1));
+        for _ in 0..3 {
+            out.push_str(&format!("<p>{}</p>\n", words.paragraph(4)));
+        }
+        out.push_str("<ul>\n");
+        for _ in 0..4 {
+            out.push_str(&format!("<li>{}</li>\n", words.sentence(6)));
+        }
+        out.push_str("</ul>\n");
+        if i % 3 == 0 {
+            out.push_str("<pre><code>fn main() {\n    println!(\"section\");\n}</code></pre>\n");
+        }
+        if i % 5 == 0 {
+            out.push_str("<table><tr><th>Region</th><th>Quarter</th><th>Revenue</th></tr>\n");
+            for row in 0..6 {
+                out.push_str(&format!(
+                    "<tr><td>{}</td><td>Q{}</td><td>{}</td></tr>\n",
+                    words.next(),
+                    row % 4 + 1,
+                    row * 7
+                ));
+            }
+            out.push_str("</table>\n");
+        }
+        out.push_str("</section>\n");
+    }
+    out.push_str("</main></body></html>\n");
+    out.into_bytes()
+}
+
+/// CSV table of `rows` rows
+pub fn csv(rows: usize) -> Vec<u8> {
+    let mut words = Words::new();
+    let mut out = String::from("id,region,quarter,revenue,notes\n");
+    for row in 0..rows {
+        out.push_str(&format!(
+            "{},{},Q{},{}.{:02},\"{}\"\n",
+            row,
+            words.next(),
+            row % 4 + 1,
+            row * 13 % 9973,
+            row % 100,
+            words.sentence(6)
+        ));
+    }
+    out.into_bytes()
+}
+
+/// PDF of `pages` pages of text
+pub fn pdf(pages: usize) -> Vec<u8> {
+    use printpdf::{BuiltinFont, Mm, PdfDocument};
+
+    let mut words = Words::new();
+    let (doc, first_page, first_layer) =
+        PdfDocument::new("Benchmark Report", Mm(210.0), Mm(297.0), "Layer 1");
+    let font = doc
+        .add_builtin_font(BuiltinFont::Helvetica)
+        .expect("builtin font");
+    for page in 0..pages {
+        let layer = if page == 0 {
+            doc.get_page(first_page).get_layer(first_layer)
+        } else {
+            let (page, layer) = doc.add_page(Mm(210.0), Mm(297.0), "Layer 1");
+            doc.get_page(page).get_layer(layer)
+        };
+        layer.use_text(
+            format!("Section {}", page + 1),
+            16.0,
+            Mm(20.0),
+            Mm(275.0),
+            &font,
+        );
+        let mut y = 262.0;
+        while y > 20.0 {
+            layer.use_text(words.sentence(11), 10.0, Mm(20.0), Mm(y), &font);
+            y -= 5.5;
+        }
+    }
+    doc.save_to_bytes().expect("PDF fixture")
+}
+
+/// The Markdown fixture of `sections` sections, converted
+pub fn document(sections: usize) -> DoclingDocument {
+    let input = InputDocument::from_bytes(markdown(sections), "bench.md", InputFormat::Markdown);
+    MarkdownBackend::new()
+        .convert(&input)
+        .expect("Markdown fixture converts")
+}
+
+/// A document of `sections` sections with a single long paragraph each,
+/// which token-aware chunkers have to split
+pub fn long_sections(sections: usize, sentences: usize) -> DoclingDocument {
+    let mut words = Words::new();
+    let mut out = String::new();
+    for i in 0..sections {
+        out.push_str(&format!(
+            "## Part {}\n\n{}\n\n",
+            i + 1,
+            words.paragraph(sentences)
+        ));
+    }
+    let input = InputDocument::from_bytes(out.into_bytes(), "long.md", InputFormat::Markdown);
+    MarkdownBackend::new()
+        .convert(&input)
+        .expect("Markdown fixture converts")
+}
@@ -0,0 +1,80 @@
+//! Conversion benchmarks: every backend on documents of growing size
+//!
+//! ```bash
+//! cargo bench --bench conversion
+//! cargo bench --bench conversion -- markdown   # one backend
+//! ```
+//!
+//! The PDF cases need the `pdf` feature and a pdfium library; they are
+//! skipped when pdfium cannot be loaded.
+
+mod common;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use docling_rs::backend::{Backend, CsvBackend, HtmlBackend, MarkdownBackend};
+use docling_rs::datamodel::InputDocument;
+use docling_rs::InputFormat;
+
+/// Benchmark `backend` on `fixture` at each of `sizes`
+fn bench_backend(
+    c: &mut Criterion,
+    name: &str,
+    backend: &dyn Backend,
+    format: InputFormat,
+    fixture: fn(usize) -> Vec<u8>,
+    sizes: &[usize],
+) {
+    let mut group = c.benchmark_group(name);
+    for &size in sizes {
+        let data = fixture(size);
+        group.throughput(Throughput::Bytes(data.len() as u64));
+        let input =
+            InputDocument::from_bytes(data, format!("bench.{}", format.extension()), format);
+        if let Err(e) = backend.convert(&input) {
+            eprintln!("Skipping {}/{}: {}", name, size, e);
+            continue;
+        }
+        group.bench_with_input(BenchmarkId::from_parameter(size), &input, |b, input| {
+            b.iter(|| backend.convert(input).expect("conversion"))
+        });
+    }
+    group.finish();
+}
+
+fn markdown(c: &mut Criterion) {
+    let backend = MarkdownBackend::new();
+    let sizes = [10, 100, 1000];
+    bench_backend(
+        c,
+        "markdown",
+        &backend,
+        InputFormat::Markdown,
+        common::markdown,
+        &sizes,
+    );
+}
+
+fn html(c: &mut Criterion) {
+    let backend = HtmlBackend::new();
+    let sizes = [10, 100, 1000];
+    bench_backend(c, "html", &backend, InputFormat::Html, common::html, &sizes);
+}
+
+fn csv(c: &mut Criterion) {
+    let backend = CsvBackend::new();
+    let sizes = [100, 1000, 10_000];
+    bench_backend(c, "csv", &backend, InputFormat::Csv, common::csv, &sizes);
+}
+
+#[cfg(feature = "pdf")]
+fn pdf(c: &mut Criterion) {
+    let backend = docling_rs::backend::PdfBackend::new();
+    let sizes = [1, 10, 50];
+    bench_backend(c, "pdf", &backend, InputFormat::PDF, common::pdf, &sizes);
+}
+
+#[cfg(not(feature = "pdf"))]
+fn pdf(_c: &mut Criterion) {}
+
+criterion_group!(benches, markdown, html, csv, pdf);
+criterion_main!(benches);
@@ -10,6 +10,11 @@ fn main() {
     println!("cargo:rerun-if-changed=build.rs");
     println!("cargo:rerun-if-changed=pdfium/");
 
+    // Without the `pdf` feature nothing loads pdfium
+    if env::var_os("CARGO_FEATURE_PDF").is_none() {
+        return;
+    }
+
     // Get the target triple
     let target = env::var("TARGET").unwrap();
 
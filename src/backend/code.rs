@@ -0,0 +1,467 @@
+//! Source code backend implementation
+//!
+//! Uses lightweight line-based parsing to split a source file into sections:
+//! every top-level function, class, struct, etc. becomes a heading (its
+//! signature) followed by a code block with the full definition. Code outside
+//! of any definition (imports, module docs, scripts) is kept as standalone
+//! code blocks.
+
+use crate::backend::Backend;
+use crate::datamodel::{
+    DoclingDocument, DocumentMetadata, DocumentNode, InputDocument, NodeType, OffsetMap,
+    SourcePosition,
+};
+use crate::error::ConversionError;
+use crate::InputFormat;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Programming languages recognized by the code backend
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum CodeLanguage {
+    Rust,
+    Python,
+    JavaScript,
+    TypeScript,
+    Go,
+    Java,
+    C,
+    Cpp,
+    Ruby,
+    Shell,
+}
+
+impl CodeLanguage {
+    /// Detect language from file extension
+    pub fn from_extension(ext: &str) -> Option<Self> {
+        match ext.to_lowercase().as_str() {
+            "rs" => Some(Self::Rust),
+            "py" | "pyi" => Some(Self::Python),
+            "js" | "jsx" | "mjs" | "cjs" => Some(Self::JavaScript),
+            "ts" | "tsx" => Some(Self::TypeScript),
+            "go" => Some(Self::Go),
+            "java" => Some(Self::Java),
+            "c" | "h" => Some(Self::C),
+            "cpp" | "cc" | "cxx" | "hpp" | "hh" => Some(Self::Cpp),
+            "rb" => Some(Self::Ruby),
+            "sh" | "bash" | "zsh" => Some(Self::Shell),
+            _ => None,
+        }
+    }
+
+    /// Detect language from a file name or path
+    pub fn from_path(path: &Path) -> Option<Self> {
+        path.extension()
+            .and_then(|ext| ext.to_str())
+            .and_then(Self::from_extension)
+    }
+
+    /// Language identifier (as used in Markdown code fences)
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Rust => "rust",
+            Self::Python => "python",
+            Self::JavaScript => "javascript",
+            Self::TypeScript => "typescript",
+            Self::Go => "go",
+            Self::Java => "java",
+            Self::C => "c",
+            Self::Cpp => "cpp",
+            Self::Ruby => "ruby",
+            Self::Shell => "bash",
+        }
+    }
+
+    /// Whether blocks are delimited by indentation rather than braces
+    fn uses_indentation(&self) -> bool {
+        matches!(self, Self::Python | Self::Ruby)
+    }
+
+    /// Whether a line starts a comment, attribute or decorator that belongs
+    /// to the following definition
+    fn is_leading_annotation(&self, line: &str) -> bool {
+        match self {
+            Self::Rust => line.starts_with("//") || line.starts_with("#["),
+            Self::Python => line.starts_with('#') || line.starts_with('@'),
+            Self::Ruby | Self::Shell => line.starts_with('#'),
+            Self::Java => {
+                line.starts_with("//")
+                    || line.starts_with("/*")
+                    || line.starts_with('*')
+                    || line.starts_with('@')
+            }
+            _ => line.starts_with("//") || line.starts_with("/*") || line.starts_with('*'),
+        }
+    }
+
+    /// Check whether a top-level line starts a definition
+    fn is_definition(&self, line: &str) -> bool {
+        match self {
+            Self::Rust => {
+                let rest = strip_modifiers(
+                    line,
+                    &[
+                        "pub(crate) ",
+                        "pub(super) ",
+                        "pub ",
+                        "async ",
+                        "unsafe ",
+                        "const ",
+                        "extern \"C\" ",
+                    ],
+                );
+                has_keyword(
+                    rest,
+                    &[
+                        "fn ",
+                        "struct ",
+                        "enum ",
+                        "trait ",
+                        "impl ",
+                        "impl<",
+                        "mod ",
+                        "union ",
+                        "macro_rules!",
+                    ],
+                ) && !rest.ends_with(';')
+            }
+            Self::Python => has_keyword(line, &["def ", "async def ", "class "]),
+            Self::JavaScript | Self::TypeScript => {
+                let rest = strip_modifiers(
+                    line,
+                    &["export ", "default ", "declare ", "abstract ", "async "],
+                );
+                has_keyword(
+                    rest,
+                    &["function ", "function* ", "class ", "interface ", "enum "],
+                ) || (has_keyword(rest, &["const ", "let "]) && rest.contains("=>"))
+            }
+            Self::Go => has_keyword(line, &["func ", "type "]),
+            Self::Java => {
+                let rest = strip_modifiers(
+                    line,
+                    &[
+                        "public ",
+                        "private ",
+                        "protected ",
+                        "static ",
+                        "final ",
+                        "abstract ",
+                        "sealed ",
+                    ],
+                );
+                has_keyword(
+                    rest,
+                    &["class ", "interface ", "enum ", "record ", "@interface "],
+                )
+            }
+            Self::C | Self::Cpp => {
+                let is_type = has_keyword(
+                    line,
+                    &[
+                        "struct ",
+                        "class ",
+                        "namespace ",
+                        "enum ",
+                        "union ",
+                        "template",
+                    ],
+                );
+                // Function definitions: a signature with parentheses that isn't a declaration
+                let is_function = line.contains('(') && !self.is_leading_annotation(line);
+                (is_type || is_function) && !line.starts_with('#') && !line.ends_with(';')
+            }
+            Self::Ruby => has_keyword(line, &["def ", "class ", "module "]),
+            Self::Shell => line.starts_with("function ") || line.replace(' ', "").contains("(){"),
+        }
+    }
+}
+
+/// Strip any sequence of leading modifier keywords
+fn strip_modifiers<'a>(mut line: &'a str, modifiers: &[&str]) -> &'a str {
+    while let Some(rest) = modifiers.iter().find_map(|m| line.strip_prefix(m)) {
+        line = rest;
+    }
+    line
+}
+
+fn has_keyword(line: &str, keywords: &[&str]) -> bool {
+    keywords.iter().any(|k| line.starts_with(k))
+}
+
+/// Source code backend
+pub struct CodeBackend {}
+
+impl CodeBackend {
+    /// Create a new source code backend
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    fn get_content(input: &InputDocument) -> Result<String, ConversionError> {
+        match input.source() {
+            crate::datamodel::DocumentSource::FilePath(path)
+            | crate::datamodel::DocumentSource::Remote { path, .. } => {
+                std::fs::read_to_string(path).map_err(ConversionError::Io)
+            }
+            crate::datamodel::DocumentSource::Bytes { data, .. } => String::from_utf8(data.clone())
+                .map_err(|e| ConversionError::InvalidFile(format!("Invalid UTF-8: {}", e))),
+        }
+    }
+
+    /// Parse source content into heading + code block nodes
+    fn parse(&self, content: &str, language: CodeLanguage) -> Vec<DocumentNode> {
+        let lines: Vec<SourceLine> = index_lines(content);
+        let mut nodes = Vec::new();
+        let mut loose_start = 0;
+        let mut i = 0;
+
+        while i < lines.len() {
+            let line = lines[i].text;
+            let is_top_level = !line.starts_with([' ', '\t']) && !line.trim().is_empty();
+
+            if is_top_level && language.is_definition(line.trim_end()) {
+                // Attach preceding comments/attributes/decorators to this definition
+                let mut start = i;
+                while start > loose_start && language.is_leading_annotation(lines[start - 1].text) {
+                    start -= 1;
+                }
+                let end = find_definition_end(&lines, i, language);
+
+                push_code(&mut nodes, &lines[loose_start..start]);
+                nodes.push(
+                    DocumentNode::new(NodeType::Heading, signature(line))
+                        .with_level(1)
+                        .with_position(span(&lines[i..=i])),
+                );
+                push_code(&mut nodes, &lines[start..=end]);
+
+                i = end + 1;
+                loose_start = i;
+            } else {
+                i += 1;
+            }
+        }
+        push_code(&mut nodes, &lines[loose_start..]);
+
+        nodes
+    }
+}
+
+impl Default for CodeBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Backend for CodeBackend {
+    fn convert(&self, input: &InputDocument) -> Result<DoclingDocument, ConversionError> {
+        let content = Self::get_content(input)?;
+
+        // Get document name from input
+        let name = match input.source() {
+            crate::datamodel::DocumentSource::FilePath(path)
+            | crate::datamodel::DocumentSource::Remote { path, .. } => path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("unknown")
+                .to_string(),
+            crate::datamodel::DocumentSource::Bytes { name, .. } => name.clone(),
+        };
+
+        let language = CodeLanguage::from_path(Path::new(&name)).ok_or_else(|| {
+            ConversionError::UnsupportedFormat(format!("Unknown source language: {}", name))
+        })?;
+
+        let nodes = self.parse(&content, language);
+
+        Ok(DoclingDocument::new(name)
+            .with_document_metadata(
+                DocumentMetadata::from_input(input).with_programming_language(language.name()),
+            )
+            .with_nodes(nodes))
+    }
+
+    fn supports_format(&self, format: InputFormat) -> bool {
+        format == InputFormat::Code
+    }
+}
+
+/// A source line with its byte range and 1-based line number
+struct SourceLine<'a> {
+    text: &'a str,
+    start: usize,
+    end: usize,
+    line: usize,
+}
+
+fn index_lines(content: &str) -> Vec<SourceLine<'_>> {
+    let mut offset = 0;
+    content
+        .split_inclusive('\n')
+        .enumerate()
+        .map(|(i, raw)| {
+            let text = raw.trim_end_matches(['\n', '\r']);
+            let line = SourceLine {
+                text,
+                start: offset,
+                end: offset + text.len(),
+                line: i + 1,
+            };
+            offset += raw.len();
+            line
+        })
+        .collect()
+}
+
+/// Find the index of the last line belonging to the definition starting at `start`
+fn find_definition_end(lines: &[SourceLine], start: usize, language: CodeLanguage) -> usize {
+    if language.uses_indentation() {
+        // The body continues while lines are blank or indented
+        let mut end = start;
+        for (i, line) in lines.iter().enumerate().skip(start + 1) {
+            let text = line.text;
+            if text.trim().is_empty() {
+                continue;
+            }
+            if text.starts_with([' ', '\t']) || text.starts_with([')', ']', '}']) {
+                end = i;
+            } else {
+                // Ruby closes definitions with a top-level `end`
+                if language == CodeLanguage::Ruby && text.trim() == "end" {
+                    end = i;
+                }
+                break;
+            }
+        }
+        return end;
+    }
+
+    // Brace languages: track nesting depth until the opening brace is closed
+    let mut depth: i64 = 0;
+    let mut opened = false;
+    for (i, line) in lines.iter().enumerate().skip(start) {
+        // A new top-level statement before any brace means a body-less definition
+        if !opened && i > start && is_top_level_statement(line.text) {
+            return i - 1;
+        }
+
+        let code = strip_line_comment(line.text, language);
+        for c in code.chars() {
+            match c {
+                '{' => {
+                    depth += 1;
+                    opened = true;
+                }
+                '}' => depth -= 1,
+                _ => {}
+            }
+        }
+        if opened && depth <= 0 {
+            return i;
+        }
+        if !opened && code.trim_end().ends_with(';') {
+            return i;
+        }
+    }
+    lines.len() - 1
+}
+
+/// Check whether a line starts a new top-level statement rather than
+/// continuing a multi-line signature
+fn is_top_level_statement(text: &str) -> bool {
+    const CONTINUATIONS: [&str; 7] = ["{", ")", "]", ">", "where", "->", ":"];
+    !text.trim().is_empty()
+        && !text.starts_with([' ', '\t'])
+        && !CONTINUATIONS.iter().any(|c| text.starts_with(c))
+}
+
+/// Remove a trailing `//` comment and string literals so braces inside them are ignored
+///
+/// In Rust a `'` only opens a char literal (`'{'`, `'\n'`), so lifetimes such
+/// as `'a` are kept; elsewhere it delimits a string like `"`.
+fn strip_line_comment(line: &str, language: CodeLanguage) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut in_string: Option<char> = None;
+    let mut escaped = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match in_string {
+            Some(quote) => {
+                if escaped {
+                    escaped = false;
+                } else if c == '\\' {
+                    escaped = true;
+                } else if c == quote {
+                    in_string = None;
+                }
+            }
+            None => {
+                if c == '"' || c == '`' {
+                    in_string = Some(c);
+                } else if c == '\'' && language == CodeLanguage::Rust {
+                    if !skip_char_literal(&mut chars) {
+                        out.push(c);
+                    }
+                } else if c == '\'' {
+                    in_string = Some(c);
+                } else if c == '/' && chars.peek() == Some(&'/') {
+                    break;
+                } else {
+                    out.push(c);
+                }
+            }
+        }
+    }
+    out
+}
+
+/// Consume the rest of a Rust char literal after its opening `'`
+///
+/// Returns `false`, consuming nothing, when the quote starts a lifetime.
+fn skip_char_literal(chars: &mut std::iter::Peekable<std::str::Chars>) -> bool {
+    let mut ahead = chars.clone();
+    let literal = match ahead.next() {
+        Some('\\') => ahead.next().is_some() && ahead.any(|c| c == '\''),
+        Some(_) => ahead.next() == Some('\''),
+        None => false,
+    };
+    if literal {
+        *chars = ahead;
+    }
+    literal
+}
+
+/// Short signature for a definition line (trailing `{` / `:` removed)
+fn signature(line: &str) -> String {
+    line.trim()
+        .trim_end_matches('{')
+        .trim_end_matches(':')
+        .trim_end()
+        .to_string()
+}
+
+fn span(lines: &[SourceLine]) -> SourcePosition {
+    let first = &lines[0];
+    let last = &lines[lines.len() - 1];
+    SourcePosition::new(first.start, last.end, first.line, last.line)
+}
+
+/// Push a code block for the given lines, trimming surrounding blank lines
+fn push_code(nodes: &mut Vec<DocumentNode>, lines: &[SourceLine]) {
+    let first = lines.iter().position(|l| !l.text.trim().is_empty());
+    let last = lines.iter().rposition(|l| !l.text.trim().is_empty());
+    let (Some(first), Some(last)) = (first, last) else {
+        return;
+    };
+    let lines = &lines[first..=last];
+
+    let text = lines.iter().map(|l| l.text).collect::<Vec<_>>().join("\n");
+    let offsets = OffsetMap::from_lines(lines.iter().map(|l| (l.start, l.text.len())), 1);
+    nodes.push(
+        DocumentNode::new(NodeType::CodeBlock, text)
+            .with_position(span(lines))
+            .with_offsets(offsets),
+    );
+}
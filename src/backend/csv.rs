@@ -1,12 +1,18 @@
 //! CSV backend implementation
 
 use crate::backend::Backend;
-use crate::datamodel::{DoclingDocument, InputDocument};
+use crate::datamodel::{
+    DoclingDocument, DocumentMetadata, DocumentNode, InputDocument, NodeType, TableCell, TableData,
+    TableRow,
+};
 use crate::error::ConversionError;
 use crate::InputFormat;
 use csv::ReaderBuilder;
 
 /// CSV backend
+///
+/// The file becomes a single table whose first record is the header row.
+/// Records may have different lengths.
 pub struct CsvBackend {}
 
 impl CsvBackend {
@@ -17,7 +23,8 @@ impl CsvBackend {
 
     fn get_bytes(input: &InputDocument) -> Result<Vec<u8>, ConversionError> {
         match input.source() {
-            crate::datamodel::DocumentSource::FilePath(path) => {
+            crate::datamodel::DocumentSource::FilePath(path)
+            | crate::datamodel::DocumentSource::Remote { path, .. } => {
                 std::fs::read(path).map_err(ConversionError::Io)
             }
             crate::datamodel::DocumentSource::Bytes { data, .. } => Ok(data.clone()),
@@ -37,7 +44,8 @@ impl Backend for CsvBackend {
 
         // Get document name from input
         let name = match input.source() {
-            crate::datamodel::DocumentSource::FilePath(path) => path
+            crate::datamodel::DocumentSource::FilePath(path)
+            | crate::datamodel::DocumentSource::Remote { path, .. } => path
                 .file_name()
                 .and_then(|n| n.to_str())
                 .unwrap_or("unknown")
@@ -45,20 +53,28 @@ impl Backend for CsvBackend {
             crate::datamodel::DocumentSource::Bytes { name, .. } => name.clone(),
         };
 
-        // Parse CSV
+        // Parse CSV, keeping the header as the first row of the table
         let mut reader = ReaderBuilder::new()
-            .has_headers(true)
+            .has_headers(false)
+            .flexible(true)
             .from_reader(bytes.as_slice());
 
-        // Verify parsing works by reading headers
-        let _headers = reader
-            .headers()
-            .map_err(|e| ConversionError::ParseError(format!("CSV parse error: {}", e)))?;
+        let mut table = TableData::new();
+        for record in reader.records() {
+            let record = record
+                .map_err(|e| ConversionError::ParseError(format!("CSV parse error: {}", e)))?;
+            table = table.with_row(TableRow::new(record.iter().map(TableCell::new).collect()));
+        }
 
         // Create document
-        let doc = DoclingDocument::new(name);
+        let mut doc =
+            DoclingDocument::new(name).with_document_metadata(DocumentMetadata::from_input(input));
+        if !table.rows().is_empty() {
+            let table = table.with_header_rows(1);
+            let text = table.to_markdown().trim_end().to_string();
+            doc.add_node(DocumentNode::new(NodeType::Table, text).with_table(table));
+        }
 
-        // Full CSV table parsing will be added in REFACTOR phase
         Ok(doc)
     }
 
@@ -1,12 +1,27 @@
 //! DOCX backend implementation
 
 use crate::backend::Backend;
-use crate::datamodel::{DoclingDocument, InputDocument};
+use crate::datamodel::metadata::iso639_3;
+use crate::datamodel::{
+    DoclingDocument, DocumentMetadata, DocumentNode, InputDocument, NodeId, NodeType,
+    TableCell as CellData, TableData, TableRow as RowData,
+};
 use crate::error::ConversionError;
+use crate::pipeline::{LimitGuard, NoProgress, ProgressObserver};
 use crate::InputFormat;
+use chrono::{DateTime, Utc};
 use docx_rs::*;
+use regex::Regex;
+use std::collections::HashMap;
+use std::io::{Cursor, Read};
 
 /// DOCX backend
+///
+/// Paragraphs are read by their style: `Title`, `Heading 1` to `Heading 9`
+/// and `Caption` become title, heading and caption nodes, numbered paragraphs
+/// become list items, and the rest body paragraphs. Content is nested under
+/// the heading it follows, as with Markdown. The title, authors, dates and
+/// language of the document properties become the document metadata.
 pub struct DocxBackend {}
 
 impl DocxBackend {
@@ -17,7 +32,8 @@ impl DocxBackend {
 
     fn get_bytes(input: &InputDocument) -> Result<Vec<u8>, ConversionError> {
         match input.source() {
-            crate::datamodel::DocumentSource::FilePath(path) => {
+            crate::datamodel::DocumentSource::FilePath(path)
+            | crate::datamodel::DocumentSource::Remote { path, .. } => {
                 std::fs::read(path).map_err(ConversionError::Io)
             }
             crate::datamodel::DocumentSource::Bytes { data, .. } => Ok(data.clone()),
@@ -33,11 +49,24 @@ impl Default for DocxBackend {
 
 impl Backend for DocxBackend {
     fn convert(&self, input: &InputDocument) -> Result<DoclingDocument, ConversionError> {
+        self.convert_with_limits(input, &NoProgress, &LimitGuard::unlimited())
+    }
+
+    fn convert_with_limits(
+        &self,
+        input: &InputDocument,
+        _progress: &dyn ProgressObserver,
+        limits: &LimitGuard,
+    ) -> Result<DoclingDocument, ConversionError> {
         let bytes = Self::get_bytes(input)?;
 
+        // DOCX files are zip archives: refuse zip bombs before parsing
+        limits.check_zip(&bytes)?;
+
         // Get document name from input
         let name = match input.source() {
-            crate::datamodel::DocumentSource::FilePath(path) => path
+            crate::datamodel::DocumentSource::FilePath(path)
+            | crate::datamodel::DocumentSource::Remote { path, .. } => path
                 .file_name()
                 .and_then(|n| n.to_str())
                 .unwrap_or("unknown")
@@ -46,17 +75,245 @@ impl Backend for DocxBackend {
         };
 
         // Parse DOCX
-        let _docx = read_docx(&bytes)
+        let docx = read_docx(&bytes)
             .map_err(|e| ConversionError::ParseError(format!("DOCX parse error: {}", e)))?;
 
         // Create document
-        let doc = DoclingDocument::new(name);
+        let mut reader = Reader {
+            doc: DoclingDocument::new(name).with_document_metadata(core_properties(
+                &bytes,
+                DocumentMetadata::from_input(input),
+            )),
+            styles: docx
+                .styles
+                .styles
+                .iter()
+                .map(|style| (style.style_id.as_str(), &style.name))
+                .collect(),
+            sections: Vec::new(),
+            lists: Vec::new(),
+        };
+        for child in &docx.document.children {
+            match child {
+                DocumentChild::Paragraph(paragraph) => reader.paragraph(paragraph),
+                DocumentChild::Table(table) => reader.table(table),
+                _ => {}
+            }
+        }
 
-        // Full DOCX content parsing will be added in REFACTOR phase
-        Ok(doc)
+        Ok(reader.doc)
     }
 
     fn supports_format(&self, format: InputFormat) -> bool {
         format == InputFormat::Docx
     }
 }
+
+/// What a paragraph is, from its style
+enum Kind {
+    Title,
+    Heading(usize),
+    Caption,
+    Body,
+}
+
+/// Reads the body of a DOCX document into document nodes
+struct Reader<'a> {
+    doc: DoclingDocument,
+    /// Style names by style id
+    styles: HashMap<&'a str, &'a Name>,
+    /// Open sections as (heading level, heading node), outermost first
+    sections: Vec<(usize, NodeId)>,
+    /// Open lists by nesting level, with their last item
+    lists: Vec<(NodeId, Option<NodeId>)>,
+}
+
+impl Reader<'_> {
+    fn paragraph(&mut self, paragraph: &Paragraph) {
+        let text = paragraph.raw_text();
+        let text = text.trim();
+        if text.is_empty() {
+            return;
+        }
+
+        // Numbered paragraphs are list items, nested by their level
+        if let Some(numbering) = paragraph
+            .property
+            .numbering_property
+            .as_ref()
+            .filter(|n| n.id.as_ref().is_some_and(|id| id.id != 0))
+        {
+            let level = numbering.level.as_ref().map_or(0, |l| l.val);
+            self.list_item(level, DocumentNode::new(NodeType::ListItem, text));
+            return;
+        }
+        self.lists.clear();
+
+        match self.kind(paragraph) {
+            Kind::Title => {
+                self.doc.add_node(DocumentNode::new(NodeType::Title, text));
+            }
+            Kind::Heading(level) => {
+                while self.sections.last().is_some_and(|(l, _)| *l >= level) {
+                    self.sections.pop();
+                }
+                let node = DocumentNode::new(NodeType::Heading, text).with_level(level);
+                if let Some(id) = self.add(node) {
+                    self.sections.push((level, id));
+                }
+            }
+            Kind::Caption => {
+                self.add(DocumentNode::new(NodeType::Caption, text));
+            }
+            Kind::Body => {
+                self.add(DocumentNode::new(NodeType::Paragraph, text));
+            }
+        }
+    }
+
+    /// Kind of a paragraph, from the name of its style or else its id
+    fn kind(&self, paragraph: &Paragraph) -> Kind {
+        let Some(id) = paragraph.property.style.as_ref().map(|s| s.val.as_str()) else {
+            return Kind::Body;
+        };
+        if let Some(name) = self.styles.get(id) {
+            if name.is_heading() {
+                return Kind::Heading(name.get_heading_number().unwrap_or(1).max(1));
+            }
+            if name.starts_with("Title") {
+                return Kind::Title;
+            }
+            if name.starts_with("caption") || name.starts_with("Caption") {
+                return Kind::Caption;
+            }
+        }
+        match id {
+            "Title" => Kind::Title,
+            "Caption" => Kind::Caption,
+            _ => match id.strip_prefix("Heading").and_then(|n| n.parse().ok()) {
+                Some(level) => Kind::Heading(usize::max(level, 1)),
+                None => Kind::Body,
+            },
+        }
+    }
+
+    /// Add a list item at `level`, opening the lists it is nested in
+    fn list_item(&mut self, level: usize, node: DocumentNode) {
+        self.lists.truncate(level + 1);
+        while self.lists.len() <= level {
+            let list = DocumentNode::new(NodeType::List, "");
+            let list = match self.lists.last().and_then(|&(_, item)| item) {
+                Some(item) => self.doc.add_child(item, list),
+                None => self.add(list),
+            };
+            let Some(list) = list else {
+                return;
+            };
+            self.lists.push((list, None));
+        }
+        if let Some((list, last)) = self.lists.last_mut() {
+            *last = self.doc.add_child(*list, node);
+        }
+    }
+
+    /// Read a table into a table node; the first row is the header
+    fn table(&mut self, table: &Table) {
+        self.lists.clear();
+        let mut data = TableData::new();
+        for TableChild::TableRow(row) in &table.rows {
+            let cells = row
+                .cells
+                .iter()
+                .map(|TableRowChild::TableCell(cell)| {
+                    let text: Vec<String> = cell
+                        .children
+                        .iter()
+                        .filter_map(|content| match content {
+                            TableCellContent::Paragraph(p) => Some(p.raw_text()),
+                            _ => None,
+                        })
+                        .filter(|t| !t.trim().is_empty())
+                        .collect();
+                    CellData::new(text.join("\n"))
+                })
+                .collect();
+            data = data.with_row(RowData::new(cells));
+        }
+        if data.rows().is_empty() {
+            return;
+        }
+
+        let data = data.with_header_rows(1);
+        let text = data.to_markdown().trim_end().to_string();
+        self.add(DocumentNode::new(NodeType::Table, text).with_table(data));
+    }
+
+    /// Add a node under the open section
+    fn add(&mut self, node: DocumentNode) -> Option<NodeId> {
+        match self.sections.last() {
+            Some(&(_, parent)) => self.doc.add_child(parent, node),
+            None => Some(self.doc.add_node(node)),
+        }
+    }
+}
+
+/// Read the title, authors, dates and language of the document properties
+/// (`docProps/core.xml`)
+///
+/// docx-rs parses the part but keeps its values private, so it is read from
+/// the archive. A missing or unreadable part leaves the metadata as it is.
+fn core_properties(bytes: &[u8], mut metadata: DocumentMetadata) -> DocumentMetadata {
+    let mut xml = String::new();
+    let read = zip::ZipArchive::new(Cursor::new(bytes))
+        .ok()
+        .and_then(|mut archive| {
+            let mut part = archive.by_name("docProps/core.xml").ok()?;
+            part.read_to_string(&mut xml).ok()
+        });
+    if read.is_none() {
+        return metadata;
+    }
+
+    let Ok(element) =
+        Regex::new(r"<(?:\w+:)?(title|creator|created|modified|language)\b[^>/]*>([^<]*)</")
+    else {
+        return metadata;
+    };
+    for captures in element.captures_iter(&xml) {
+        let value = unescape_xml(captures[2].trim());
+        if value.is_empty() {
+            continue;
+        }
+        metadata = match &captures[1] {
+            "title" => metadata.with_title(value),
+            // Word separates several authors with semicolons
+            "creator" => value
+                .split(';')
+                .map(str::trim)
+                .filter(|author| !author.is_empty())
+                .fold(metadata, |metadata, author| metadata.with_author(author)),
+            "created" => match DateTime::parse_from_rfc3339(&value) {
+                Ok(date) => metadata.with_created(date.with_timezone(&Utc)),
+                Err(_) => metadata,
+            },
+            "modified" => match DateTime::parse_from_rfc3339(&value) {
+                Ok(date) => metadata.with_modified(date.with_timezone(&Utc)),
+                Err(_) => metadata,
+            },
+            _ => match iso639_3(&value) {
+                Some(language) => metadata.with_language(language),
+                None => metadata,
+            },
+        };
+    }
+    metadata
+}
+
+/// Replace the predefined XML entities
+fn unescape_xml(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
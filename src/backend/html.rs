@@ -1,23 +1,113 @@
 //! HTML backend implementation
 
 use crate::backend::Backend;
-use crate::datamodel::{DoclingDocument, InputDocument};
+use crate::datamodel::metadata::iso639_3;
+use crate::datamodel::{
+    DoclingDocument, DocumentMetadata, DocumentNode, InputDocument, NodeId, NodeType, PictureItem,
+    TableCell, TableData, TableRow,
+};
 use crate::error::ConversionError;
+use crate::pipeline::{LimitGuard, NoProgress, ProgressObserver};
 use crate::InputFormat;
-use scraper::{Html, Selector};
+use scraper::node::Node;
+use scraper::{ElementRef, Html, Selector};
+
+/// Elements whose content is not part of the document text
+const SKIPPED: &[&str] = &["head", "script", "style", "noscript", "template"];
+
+/// Elements that start a block of their own instead of flowing with the text
+/// around them
+const BLOCKS: &[&str] = &[
+    "address",
+    "article",
+    "aside",
+    "blockquote",
+    "body",
+    "caption",
+    "dd",
+    "details",
+    "div",
+    "dl",
+    "dt",
+    "fieldset",
+    "figcaption",
+    "figure",
+    "footer",
+    "form",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "hr",
+    "img",
+    "li",
+    "main",
+    "nav",
+    "ol",
+    "p",
+    "pre",
+    "section",
+    "summary",
+    "table",
+    "ul",
+];
+
+/// Options of the HTML backend
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HtmlConfig {
+    /// Read the title, language and authors from the document head
+    pub extract_metadata: bool,
+}
+
+impl Default for HtmlConfig {
+    fn default() -> Self {
+        Self {
+            extract_metadata: true,
+        }
+    }
+}
+
+impl HtmlConfig {
+    /// Enable or disable reading metadata from the document head
+    pub fn extract_metadata(mut self, enable: bool) -> Self {
+        self.extract_metadata = enable;
+        self
+    }
+}
 
 /// HTML backend
-pub struct HtmlBackend {}
+///
+/// The `<title>` of the page becomes the document title, and the body is read
+/// into headings, paragraphs, lists, tables (with their `<caption>`), figures
+/// (with their `<figcaption>`) and code blocks. Content is nested under the
+/// heading it follows, as with Markdown.
+pub struct HtmlBackend {
+    config: HtmlConfig,
+}
 
 impl HtmlBackend {
     /// Create a new HTML backend
     pub fn new() -> Self {
-        Self {}
+        Self::with_config(HtmlConfig::default())
+    }
+
+    /// Create an HTML backend with custom options
+    pub fn with_config(config: HtmlConfig) -> Self {
+        Self { config }
+    }
+
+    /// The options of this backend
+    pub fn config(&self) -> &HtmlConfig {
+        &self.config
     }
 
     fn get_content(input: &InputDocument) -> Result<String, ConversionError> {
         match input.source() {
-            crate::datamodel::DocumentSource::FilePath(path) => {
+            crate::datamodel::DocumentSource::FilePath(path)
+            | crate::datamodel::DocumentSource::Remote { path, .. } => {
                 std::fs::read_to_string(path).map_err(ConversionError::Io)
             }
             crate::datamodel::DocumentSource::Bytes { data, .. } => String::from_utf8(data.clone())
@@ -26,6 +116,55 @@ impl HtmlBackend {
     }
 }
 
+impl HtmlBackend {
+    /// Read the title, language and authors from the document head
+    fn metadata(document: &Html, mut metadata: DocumentMetadata) -> DocumentMetadata {
+        if let Ok(selector) = Selector::parse("head > title") {
+            let title = document
+                .select(&selector)
+                .next()
+                .map(|t| t.text().collect::<String>().trim().to_string())
+                .filter(|t| !t.is_empty());
+            if let Some(title) = title {
+                metadata = metadata.with_title(title);
+            }
+        }
+
+        if let Some(lang) = document
+            .root_element()
+            .value()
+            .attr("lang")
+            .and_then(iso639_3)
+        {
+            metadata = metadata.with_language(lang);
+        }
+
+        if let Ok(selector) = Selector::parse(r#"meta[name="author"]"#) {
+            for author in document
+                .select(&selector)
+                .filter_map(|m| m.value().attr("content"))
+                .map(str::trim)
+                .filter(|a| !a.is_empty())
+            {
+                metadata = metadata.with_author(author);
+            }
+        }
+
+        metadata
+    }
+
+    /// Nesting depth of the deepest node, without recursing
+    fn depth(document: &Html) -> usize {
+        let mut deepest = 0;
+        let mut stack = vec![(document.tree.root(), 0)];
+        while let Some((node, depth)) = stack.pop() {
+            deepest = deepest.max(depth);
+            stack.extend(node.children().map(|child| (child, depth + 1)));
+        }
+        deepest
+    }
+}
+
 impl Default for HtmlBackend {
     fn default() -> Self {
         Self::new()
@@ -34,11 +173,21 @@ impl Default for HtmlBackend {
 
 impl Backend for HtmlBackend {
     fn convert(&self, input: &InputDocument) -> Result<DoclingDocument, ConversionError> {
+        self.convert_with_limits(input, &NoProgress, &LimitGuard::unlimited())
+    }
+
+    fn convert_with_limits(
+        &self,
+        input: &InputDocument,
+        _progress: &dyn ProgressObserver,
+        limits: &LimitGuard,
+    ) -> Result<DoclingDocument, ConversionError> {
         let content = Self::get_content(input)?;
 
         // Get document name from input
         let name = match input.source() {
-            crate::datamodel::DocumentSource::FilePath(path) => path
+            crate::datamodel::DocumentSource::FilePath(path)
+            | crate::datamodel::DocumentSource::Remote { path, .. } => path
                 .file_name()
                 .and_then(|n| n.to_str())
                 .unwrap_or("unknown")
@@ -46,20 +195,355 @@ impl Backend for HtmlBackend {
             crate::datamodel::DocumentSource::Bytes { name, .. } => name.clone(),
         };
 
-        // Parse HTML
-        let _document = Html::parse_document(&content);
-
-        // Verify parsing works by selecting body
-        let _body_selector = Selector::parse("body").unwrap();
+        // Parse HTML, refusing deeply nested markup before walking it
+        let document = Html::parse_document(&content);
+        limits.check_depth(Self::depth(&document))?;
 
         // Create document
-        let doc = DoclingDocument::new(name);
+        let mut metadata = DocumentMetadata::from_input(input);
+        if self.config.extract_metadata {
+            metadata = Self::metadata(&document, metadata);
+        }
+        let mut walker = Walker {
+            doc: DoclingDocument::new(name).with_document_metadata(metadata),
+            sections: Vec::new(),
+        };
+
+        if let Some(title) = Selector::parse("head > title")
+            .ok()
+            .and_then(|selector| document.select(&selector).next())
+            .map(|title| normalize(&text_of(title, &[])))
+            .filter(|title| !title.is_empty())
+        {
+            walker
+                .doc
+                .add_node(DocumentNode::new(NodeType::Title, title));
+        }
+        let body = Selector::parse("body")
+            .ok()
+            .and_then(|selector| document.select(&selector).next());
+        if let Some(body) = body {
+            walker.walk(body);
+        }
 
-        // Full parsing logic will be added in REFACTOR phase
-        Ok(doc)
+        Ok(walker.doc)
     }
 
     fn supports_format(&self, format: InputFormat) -> bool {
         format == InputFormat::Html
     }
 }
+
+/// A piece of the body waiting to be read
+enum Item<'a> {
+    /// An element, read into nodes under `parent` (the open section if `None`)
+    Element(ElementRef<'a>, Option<NodeId>),
+    /// Text outside of any block element, read into a paragraph
+    Text(String, Option<NodeId>),
+}
+
+/// Reads the body of a page into document nodes, in document order
+struct Walker {
+    doc: DoclingDocument,
+    /// Open sections as (heading level, heading node), outermost first
+    sections: Vec<(usize, NodeId)>,
+}
+
+impl Walker {
+    /// Read `root` and everything in it, without recursing
+    fn walk(&mut self, root: ElementRef) {
+        let mut stack = vec![Item::Element(root, None)];
+        while let Some(item) = stack.pop() {
+            let (element, parent) = match item {
+                Item::Text(text, parent) => {
+                    self.add(parent, DocumentNode::new(NodeType::Paragraph, text));
+                    continue;
+                }
+                Item::Element(element, parent) => (element, parent),
+            };
+
+            match element.value().name() {
+                "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => self.heading(element),
+                "p" | "dt" | "dd" | "summary" => {
+                    let text = normalize(&text_of(element, &[]));
+                    if !text.is_empty() {
+                        self.add(parent, DocumentNode::new(NodeType::Paragraph, text));
+                    }
+                }
+                "caption" | "figcaption" => {
+                    let text = normalize(&text_of(element, &[]));
+                    if !text.is_empty() {
+                        self.add(parent, DocumentNode::new(NodeType::Caption, text));
+                    }
+                }
+                "ul" | "ol" => {
+                    let list = DocumentNode::new(NodeType::List, "")
+                        .with_ordered(element.value().name() == "ol");
+                    if let Some(list) = self.add(parent, list) {
+                        let items = children(element).filter(|e| e.value().name() == "li");
+                        for item in items.rev() {
+                            stack.push(Item::Element(item, Some(list)));
+                        }
+                    }
+                }
+                "li" => {
+                    let text = normalize(&text_of(element, &["ul", "ol"]));
+                    let node = DocumentNode::new(NodeType::ListItem, text);
+                    if let Some(item) = self.add(parent, node) {
+                        // Nested lists belong to the item
+                        let lists =
+                            children(element).filter(|e| matches!(e.value().name(), "ul" | "ol"));
+                        for list in lists.collect::<Vec<_>>().into_iter().rev() {
+                            stack.push(Item::Element(list, Some(item)));
+                        }
+                    }
+                }
+                "blockquote" => {
+                    if let Some(quote) = self.add(parent, DocumentNode::new(NodeType::Quote, "")) {
+                        let items = blocks(element, Some(quote));
+                        stack.extend(items.into_iter().rev());
+                    }
+                }
+                "table" => {
+                    if let Some(node) = table(element) {
+                        self.add(parent, node);
+                    }
+                }
+                "figure" if first_image(element).is_some() => {
+                    if let Some(node) = picture(element) {
+                        self.add(parent, node);
+                    }
+                }
+                "img" => {
+                    if let Some(node) = picture(element) {
+                        self.add(parent, node);
+                    }
+                }
+                "pre" => {
+                    let text = text_of(element, &[]);
+                    let text = text.strip_prefix('\n').unwrap_or(&text).trim_end();
+                    if !text.trim().is_empty() {
+                        let node = DocumentNode::new(NodeType::CodeBlock, text);
+                        self.add(
+                            parent,
+                            match code_language(element) {
+                                Some(language) => node.with_code_language(language),
+                                None => node,
+                            },
+                        );
+                    }
+                }
+                "hr" | "br" => {}
+                // Anything else holds blocks: read its content in order
+                _ => {
+                    let items = blocks(element, parent);
+                    stack.extend(items.into_iter().rev());
+                }
+            }
+        }
+    }
+
+    /// Add a heading and open its section
+    fn heading(&mut self, element: ElementRef) {
+        let text = normalize(&text_of(element, &[]));
+        if text.is_empty() {
+            return;
+        }
+        let level = element.value().name()[1..].parse().unwrap_or(1);
+        while self.sections.last().is_some_and(|(l, _)| *l >= level) {
+            self.sections.pop();
+        }
+        let node = DocumentNode::new(NodeType::Heading, text).with_level(level);
+        if let Some(id) = self.add(None, node) {
+            self.sections.push((level, id));
+        }
+    }
+
+    /// Add a node under `parent`, or else under the open section
+    fn add(&mut self, parent: Option<NodeId>, node: DocumentNode) -> Option<NodeId> {
+        match parent.or_else(|| self.sections.last().map(|&(_, id)| id)) {
+            Some(parent) => self.doc.add_child(parent, node),
+            None => Some(self.doc.add_node(node)),
+        }
+    }
+}
+
+/// Element children of `element`
+fn children<'a>(element: ElementRef<'a>) -> impl DoubleEndedIterator<Item = ElementRef<'a>> {
+    element.children().filter_map(ElementRef::wrap)
+}
+
+/// First `<img>` inside `element`
+fn first_image(element: ElementRef) -> Option<ElementRef> {
+    element
+        .descendants()
+        .filter_map(ElementRef::wrap)
+        .find(|e| e.value().name() == "img")
+}
+
+/// Programming language of a `<pre>`, from the `language-*` (or `lang-*`)
+/// class of the element or of its `<code>`
+fn code_language<'a>(pre: ElementRef<'a>) -> Option<&'a str> {
+    std::iter::once(pre)
+        .chain(children(pre).filter(|child| child.value().name() == "code"))
+        .flat_map(|element| element.value().classes())
+        .find_map(|class| {
+            class
+                .strip_prefix("language-")
+                .or_else(|| class.strip_prefix("lang-"))
+        })
+        .filter(|language| !language.is_empty())
+}
+
+/// Split the content of `element` into block elements and the runs of text
+/// between them
+fn blocks<'a>(element: ElementRef<'a>, parent: Option<NodeId>) -> Vec<Item<'a>> {
+    let mut items = Vec::new();
+    let mut run = String::new();
+    let flush = |run: &mut String, items: &mut Vec<Item<'a>>| {
+        let text = normalize(run);
+        if !text.is_empty() {
+            items.push(Item::Text(text, parent));
+        }
+        run.clear();
+    };
+
+    for child in element.children() {
+        match child.value() {
+            Node::Text(text) => run.push_str(text),
+            Node::Element(e) if SKIPPED.contains(&e.name()) => {}
+            Node::Element(e) => {
+                let Some(child) = ElementRef::wrap(child) else {
+                    continue;
+                };
+                let holds_blocks = children(child).any(|c| BLOCKS.contains(&c.value().name()));
+                if BLOCKS.contains(&e.name()) || holds_blocks {
+                    flush(&mut run, &mut items);
+                    items.push(Item::Element(child, parent));
+                } else if e.name() == "br" {
+                    run.push(' ');
+                } else {
+                    run.push_str(&text_of(child, &[]));
+                }
+            }
+            _ => {}
+        }
+    }
+    flush(&mut run, &mut items);
+    items
+}
+
+/// Text of `element`, leaving out scripts, styles and the elements named in
+/// `skip`, without recursing
+fn text_of(element: ElementRef, skip: &[&str]) -> String {
+    let mut text = String::new();
+    let mut stack: Vec<_> = element.children().rev().collect();
+    while let Some(node) = stack.pop() {
+        match node.value() {
+            Node::Text(t) => text.push_str(t),
+            Node::Element(e) if SKIPPED.contains(&e.name()) || skip.contains(&e.name()) => {}
+            Node::Element(e) if e.name() == "br" => text.push('\n'),
+            Node::Element(_) => stack.extend(node.children().rev()),
+            _ => {}
+        }
+    }
+    text
+}
+
+/// Collapse runs of whitespace into single spaces, as browsers render text
+fn normalize(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Read a `<table>` into a table node, with its `<caption>`
+fn table(element: ElementRef) -> Option<DocumentNode> {
+    // Rows of the table itself, not of tables nested in its cells
+    let mut rows = Vec::new();
+    for child in children(element) {
+        match child.value().name() {
+            "tr" => rows.push((child, false)),
+            group @ ("thead" | "tbody" | "tfoot") => rows.extend(
+                children(child)
+                    .filter(|r| r.value().name() == "tr")
+                    .map(|r| (r, group == "thead")),
+            ),
+            _ => {}
+        }
+    }
+
+    let mut data = TableData::new();
+    let mut header_rows = 0;
+    for (index, (row, in_head)) in rows.into_iter().enumerate() {
+        let cells: Vec<_> = children(row)
+            .filter(|c| matches!(c.value().name(), "td" | "th"))
+            .collect();
+        let header =
+            in_head || (!cells.is_empty() && cells.iter().all(|c| c.value().name() == "th"));
+        if header && header_rows == index {
+            header_rows += 1;
+        }
+        // Out-of-range spans are clamped by the cell
+        let span = |cell: &ElementRef, name| {
+            cell.value()
+                .attr(name)
+                .and_then(|v| v.trim().parse::<usize>().ok())
+                .unwrap_or(1)
+        };
+        data = data.with_row(TableRow::new(
+            cells
+                .iter()
+                .map(|cell| {
+                    TableCell::new(normalize(&text_of(*cell, &[])))
+                        .with_col_span(span(cell, "colspan"))
+                        .with_row_span(span(cell, "rowspan"))
+                })
+                .collect(),
+        ));
+    }
+    if data.rows().is_empty() {
+        return None;
+    }
+
+    let data = data.with_header_rows(header_rows);
+    let text = data.to_markdown().trim_end().to_string();
+    let mut node = DocumentNode::new(NodeType::Table, text).with_table(data);
+    let caption = children(element)
+        .find(|c| c.value().name() == "caption")
+        .map(|c| normalize(&text_of(c, &[])))
+        .filter(|c| !c.is_empty());
+    if let Some(caption) = caption {
+        node = node.with_caption(caption);
+    }
+    Some(node)
+}
+
+/// Read an `<img>`, or a `<figure>` holding one, into a picture node; the
+/// `<figcaption>` of a figure is its caption
+fn picture(element: ElementRef) -> Option<DocumentNode> {
+    let image = match element.value().name() {
+        "img" => element,
+        _ => first_image(element)?,
+    };
+    let alt = normalize(image.value().attr("alt").unwrap_or_default());
+    let source = image
+        .value()
+        .attr("src")
+        .map(str::trim)
+        .filter(|s| !s.is_empty());
+    if alt.is_empty() && source.is_none() {
+        return None;
+    }
+
+    let mut node = DocumentNode::new(NodeType::Picture, alt);
+    if let Some(source) = source {
+        node = node.with_picture(PictureItem::file(source));
+    }
+    let caption = children(element)
+        .find(|c| c.value().name() == "figcaption")
+        .map(|c| normalize(&text_of(c, &[])))
+        .filter(|c| !c.is_empty());
+    if let Some(caption) = caption {
+        node = node.with_caption(caption);
+    }
+    Some(node)
+}
@@ -1,9 +1,23 @@
 //! Markdown backend implementation
+//!
+//! Documents are parsed as CommonMark with the GitHub extensions (pipe tables,
+//! strikethrough, task lists and footnotes). Lists keep their nesting, with
+//! each nested list a child of its item, and block quotes become quote nodes
+//! holding the quoted blocks. Inline markup is removed from node text and
+//! kept as formatting spans. Display math in `$$` delimiters is read into
+//! formula nodes.
 
 use crate::backend::Backend;
-use crate::datamodel::{DoclingDocument, DocumentNode, InputDocument, NodeType};
+use crate::datamodel::ConversionWarning;
+use crate::datamodel::{
+    DoclingDocument, DocumentMetadata, DocumentNode, Formatting, InputDocument, NodeId, NodeType,
+    OffsetMap, PictureItem, SourcePosition, TableCell, TableData, TableRow, TextSpan,
+};
 use crate::error::ConversionError;
+use crate::pipeline::{NoProgress, ProgressEvent, ProgressObserver};
 use crate::InputFormat;
+use pulldown_cmark::{CodeBlockKind, CowStr, Event, Options, Parser, Tag};
+use std::ops::Range;
 
 /// Markdown backend
 pub struct MarkdownBackend {}
@@ -16,7 +30,8 @@ impl MarkdownBackend {
 
     fn get_content(input: &InputDocument) -> Result<String, ConversionError> {
         match input.source() {
-            crate::datamodel::DocumentSource::FilePath(path) => {
+            crate::datamodel::DocumentSource::FilePath(path)
+            | crate::datamodel::DocumentSource::Remote { path, .. } => {
                 std::fs::read_to_string(path).map_err(ConversionError::Io)
             }
             crate::datamodel::DocumentSource::Bytes { data, .. } => String::from_utf8(data.clone())
@@ -33,11 +48,20 @@ impl Default for MarkdownBackend {
 
 impl Backend for MarkdownBackend {
     fn convert(&self, input: &InputDocument) -> Result<DoclingDocument, ConversionError> {
+        self.convert_with_progress(input, &NoProgress)
+    }
+
+    fn convert_with_progress(
+        &self,
+        input: &InputDocument,
+        progress: &dyn ProgressObserver,
+    ) -> Result<DoclingDocument, ConversionError> {
         let content = Self::get_content(input)?;
 
         // Get document name from input
         let name = match input.source() {
-            crate::datamodel::DocumentSource::FilePath(path) => path
+            crate::datamodel::DocumentSource::FilePath(path)
+            | crate::datamodel::DocumentSource::Remote { path, .. } => path
                 .file_name()
                 .and_then(|n| n.to_str())
                 .unwrap_or("unknown")
@@ -45,35 +69,529 @@ impl Backend for MarkdownBackend {
             crate::datamodel::DocumentSource::Bytes { name, .. } => name.clone(),
         };
 
-        // For now, create simple nodes from the content
-        // Full parsing logic will be enhanced later
-        let mut doc = DoclingDocument::new(name);
-
-        // Create basic nodes from paragraphs
-        // This is a minimal implementation to support chunking
-        for line in content.lines() {
-            let trimmed = line.trim();
-            if !trimmed.is_empty() {
-                // Determine node type based on markdown syntax
-                let node_type = if trimmed.starts_with('#') {
-                    NodeType::Heading
-                } else if trimmed.starts_with('-')
-                    || trimmed.starts_with('*')
-                    || trimmed.starts_with('+')
-                {
-                    NodeType::ListItem
+        let doc =
+            DoclingDocument::new(name).with_document_metadata(DocumentMetadata::from_input(input));
+        let mut reader = Reader::new(&content, doc);
+        let mut options = Options::empty();
+        options.insert(Options::ENABLE_TABLES);
+        options.insert(Options::ENABLE_STRIKETHROUGH);
+        options.insert(Options::ENABLE_TASKLISTS);
+        options.insert(Options::ENABLE_FOOTNOTES);
+        for (event, range) in Parser::new_ext(&content, options).into_offset_iter() {
+            reader.event(event, range);
+        }
+
+        for warning in reader.warnings {
+            progress.on_progress(input.name(), &ProgressEvent::Warning(warning));
+        }
+        Ok(reader.doc)
+    }
+
+    fn supports_format(&self, format: InputFormat) -> bool {
+        format == InputFormat::Markdown
+    }
+}
+
+/// Reads parser events into document nodes, in document order
+struct Reader<'a> {
+    content: &'a str,
+    /// Byte offset of the start of each line
+    line_starts: Vec<usize>,
+    doc: DoclingDocument,
+    /// Open sections as (heading level, heading node), outermost first;
+    /// content is nested under the innermost one
+    sections: Vec<(usize, NodeId)>,
+    /// Open lists, list items, quotes and footnotes, outermost first
+    containers: Vec<Container>,
+    /// Block whose inline content is being read
+    leaf: Option<Leaf<'a>>,
+    /// Inline content of the current block
+    inline: Inline,
+    /// Table being read, with the cells of its current row
+    table: Option<Table>,
+    warnings: Vec<ConversionWarning>,
+}
+
+/// A block holding other blocks
+enum Container {
+    List(Option<NodeId>),
+    /// A list item in `list`, with its node once its own text has been read
+    Item {
+        list: Option<NodeId>,
+        node: Option<NodeId>,
+    },
+    Quote(Option<NodeId>),
+    /// A footnote definition; its paragraphs are footnote nodes
+    Footnote,
+}
+
+/// A pipe table being read
+struct Table {
+    data: TableData,
+    /// Cells of the row being read
+    cells: Vec<TableCell>,
+    /// Number of columns, set by the header row
+    columns: Option<usize>,
+    range: Range<usize>,
+}
+
+/// A block made of inline content
+enum Leaf<'a> {
+    Paragraph(Range<usize>),
+    /// The first paragraph of a list item, which is the item text
+    ItemText,
+    Heading(usize),
+    Code {
+        language: Option<&'a str>,
+        range: Range<usize>,
+    },
+}
+
+/// Text of a block with its mapping back to the source
+#[derive(Default)]
+struct Inline {
+    text: String,
+    offsets: OffsetMap,
+    /// Source range covered by the inline content
+    range: Option<Range<usize>>,
+    /// Images as (text range of the alt text, source, title)
+    images: Vec<(Range<usize>, String, String)>,
+    /// Emphasis, strikethrough, links and code spans over the text
+    spans: Vec<TextSpan>,
+    /// Spans still open, with their start in the text
+    open: Vec<(usize, Formatting)>,
+}
+
+impl Inline {
+    /// Append text read from `range` of `content`
+    fn push(&mut self, text: &str, range: Range<usize>, content: &str) {
+        // Markup around the text (code span backticks) is not part of it
+        if let Some(at) = content
+            .get(range.clone())
+            .and_then(|source| source.find(text))
+        {
+            self.offsets
+                .push(self.text.len(), range.start + at, text.len());
+        }
+        self.text.push_str(text);
+        self.range = Some(match self.range.take() {
+            Some(r) => r.start.min(range.start)..r.end.max(range.end),
+            None => range,
+        });
+    }
+
+    fn open(&mut self, formatting: Formatting) {
+        self.open.push((self.text.len(), formatting));
+    }
+
+    fn close(&mut self) {
+        if let Some((start, formatting)) = self.open.pop() {
+            self.spans
+                .push(TextSpan::new(start, self.text.len(), formatting));
+        }
+    }
+
+    /// Check whether the content is a single image and nothing else
+    fn is_image(&self) -> bool {
+        match self.images.as_slice() {
+            [(alt, _, _)] => {
+                self.text[..alt.start].trim().is_empty() && self.text[alt.end..].trim().is_empty()
+            }
+            _ => false,
+        }
+    }
+}
+
+impl<'a> Reader<'a> {
+    fn new(content: &'a str, doc: DoclingDocument) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(content.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        Self {
+            content,
+            line_starts,
+            doc,
+            sections: Vec::new(),
+            containers: Vec::new(),
+            leaf: None,
+            inline: Inline::default(),
+            table: None,
+            warnings: Vec::new(),
+        }
+    }
+
+    fn event(&mut self, event: Event<'a>, range: Range<usize>) {
+        match event {
+            Event::Start(tag) => self.start(tag, range),
+            Event::End(tag) => self.end(tag),
+            Event::Text(text) => self.inline.push(&text, range, self.content),
+            Event::Code(text) => {
+                self.inline.open(Formatting::code());
+                self.inline.push(&text, range, self.content);
+                self.inline.close();
+            }
+            Event::SoftBreak => self.inline.text.push(' '),
+            Event::HardBreak => self.inline.text.push('\n'),
+            // Raw HTML, rules, footnote references and task markers carry no text
+            _ => {}
+        }
+    }
+
+    fn start(&mut self, tag: Tag<'a>, range: Range<usize>) {
+        match tag {
+            Tag::Paragraph => {
+                let item_text = matches!(
+                    self.containers.last(),
+                    Some(Container::Item { node: None, .. })
+                ) && self.inline.text.is_empty();
+                if item_text {
+                    self.leaf = Some(Leaf::ItemText);
                 } else {
-                    NodeType::Paragraph
+                    self.open_item();
+                    self.leaf = Some(Leaf::Paragraph(range));
+                }
+            }
+            Tag::Heading(level, _, _) => {
+                self.open_item();
+                self.leaf = Some(Leaf::Heading(level as usize));
+            }
+            Tag::CodeBlock(kind) => {
+                self.open_item();
+                let language = match kind {
+                    // The info string starts with the language of the code
+                    CodeBlockKind::Fenced(info) => match info {
+                        CowStr::Borrowed(info) => info.split_whitespace().next(),
+                        _ => None,
+                    },
+                    CodeBlockKind::Indented => None,
                 };
+                self.leaf = Some(Leaf::Code { language, range });
+            }
+            Tag::List(start) => {
+                self.open_item();
+                let list = DocumentNode::new(NodeType::List, "").with_ordered(start.is_some());
+                let id = self.add(list);
+                self.containers.push(Container::List(id));
+            }
+            Tag::Item => {
+                self.inline = Inline::default();
+                let list = match self.containers.last() {
+                    Some(Container::List(list)) => *list,
+                    _ => None,
+                };
+                self.containers.push(Container::Item { list, node: None });
+            }
+            Tag::BlockQuote => {
+                self.open_item();
+                let id = self.add(DocumentNode::new(NodeType::Quote, ""));
+                self.containers.push(Container::Quote(id));
+            }
+            Tag::FootnoteDefinition(_) => self.containers.push(Container::Footnote),
+            Tag::Table(_) => {
+                self.open_item();
+                self.table = Some(Table {
+                    data: TableData::new().with_header_rows(1),
+                    cells: Vec::new(),
+                    columns: None,
+                    range,
+                });
+            }
+            Tag::TableCell => self.inline = Inline::default(),
+            Tag::Emphasis => self.inline.open(Formatting::italic()),
+            Tag::Strong => self.inline.open(Formatting::bold()),
+            Tag::Strikethrough => self.inline.open(Formatting::strikethrough()),
+            Tag::Link(_, url, _) => self.inline.open(Formatting::link(url.to_string())),
+            Tag::Image(_, source, title) => {
+                let at = self.inline.text.len();
+                self.inline
+                    .images
+                    .push((at..at, source.to_string(), title.to_string()));
+            }
+            _ => {}
+        }
+    }
 
-                doc.add_node(DocumentNode::new(node_type, trimmed));
+    fn end(&mut self, tag: Tag<'a>) {
+        match tag {
+            Tag::Paragraph => match self.leaf.take() {
+                Some(Leaf::ItemText) => self.open_item(),
+                Some(Leaf::Paragraph(range)) => self.paragraph(range),
+                _ => {}
+            },
+            Tag::Heading(..) => {
+                if let Some(Leaf::Heading(level)) = self.leaf.take() {
+                    self.heading(level);
+                }
+            }
+            Tag::CodeBlock(_) => {
+                if let Some(Leaf::Code { language, range }) = self.leaf.take() {
+                    self.code(language, range);
+                }
+            }
+            Tag::Item => {
+                self.open_item();
+                self.containers.pop();
+            }
+            Tag::List(_) | Tag::BlockQuote | Tag::FootnoteDefinition(_) => {
+                self.containers.pop();
+            }
+            Tag::TableCell => {
+                let text = std::mem::take(&mut self.inline).text;
+                if let Some(table) = self.table.as_mut() {
+                    table.cells.push(TableCell::new(text.trim()));
+                }
+            }
+            Tag::TableHead | Tag::TableRow => {
+                if let Some(mut table) = self.table.take() {
+                    let mut cells = std::mem::take(&mut table.cells);
+                    // Rows are padded or cut to the header width, as in
+                    // GitHub Flavored Markdown
+                    let columns = *table.columns.get_or_insert(cells.len());
+                    cells.resize_with(columns, || TableCell::new(""));
+                    table.data = table.data.with_row(TableRow::new(cells));
+                    self.table = Some(table);
+                }
+            }
+            Tag::Table(_) => {
+                if let Some(table) = self.table.take() {
+                    self.table(table);
+                }
+            }
+            Tag::Emphasis | Tag::Strong | Tag::Strikethrough | Tag::Link(..) => self.inline.close(),
+            Tag::Image(..) => {
+                let end = self.inline.text.len();
+                if let Some((alt, _, _)) = self.inline.images.last_mut() {
+                    alt.end = end;
+                }
             }
         }
+    }
 
-        Ok(doc)
+    /// Add the node of a list item whose text has been read
+    fn open_item(&mut self) {
+        let Some(&Container::Item { list, node: None }) = self.containers.last() else {
+            return;
+        };
+        let node = self
+            .text_node(NodeType::ListItem)
+            .unwrap_or_else(|| DocumentNode::new(NodeType::ListItem, ""));
+        let id = match list {
+            Some(list) => self.doc.add_child(list, node),
+            None => self.add(node),
+        };
+        if let Some(Container::Item { node, .. }) = self.containers.last_mut() {
+            *node = id;
+        }
     }
 
-    fn supports_format(&self, format: InputFormat) -> bool {
-        format == InputFormat::Markdown
+    fn paragraph(&mut self, range: Range<usize>) {
+        if self.content[range.clone()].trim_start().starts_with("$$") {
+            self.inline = Inline::default();
+            return self.formula(range);
+        }
+        if self.inline.is_image() {
+            let (_, source, title) = self.inline.images[0].clone();
+            if let Some(mut node) = self.text_node(NodeType::Picture) {
+                node = node.with_picture(PictureItem::file(source));
+                // The image title is the figure caption
+                if !title.is_empty() {
+                    node = node.with_caption(title);
+                }
+                self.add(node);
+            }
+            return;
+        }
+        let node_type = match self.containers.last() {
+            Some(Container::Footnote) => NodeType::Footnote,
+            _ => NodeType::Paragraph,
+        };
+        if let Some(node) = self.text_node(node_type) {
+            self.add(node);
+        }
     }
+
+    fn heading(&mut self, level: usize) {
+        let Some(node) = self.text_node(NodeType::Heading) else {
+            return;
+        };
+        let node = node.with_level(level);
+        // Headings inside lists and quotes do not open sections
+        if !self.containers.is_empty() {
+            self.add(node);
+            return;
+        }
+        while self.sections.last().is_some_and(|(l, _)| *l >= level) {
+            self.sections.pop();
+        }
+        if let Some(id) = self.add(node) {
+            self.sections.push((level, id));
+        }
+    }
+
+    fn code(&mut self, language: Option<&str>, range: Range<usize>) {
+        let source = &self.content[range.clone()];
+        let fenced = source.trim_start().starts_with(['`', '~']);
+        // An unterminated fence runs to the end of its container
+        if fenced && !is_closed_fence(source) {
+            self.warnings.push(ConversionWarning::new(format!(
+                "Code block opened on line {} is never closed",
+                self.line(range.start)
+            )));
+        }
+
+        let mut inline = std::mem::take(&mut self.inline);
+        // The last line break ends the code rather than belonging to it
+        if inline.text.ends_with('\n') {
+            inline.text.pop();
+        }
+        if inline.text.trim().is_empty() {
+            return;
+        }
+        let (start, end) = inline
+            .offsets
+            .source_range(0, inline.text.len())
+            .unwrap_or((range.start, range.end));
+        let offsets = inline.offsets.slice(0, inline.text.len());
+        let mut node = DocumentNode::new(NodeType::CodeBlock, inline.text)
+            .with_position(self.position(start..end))
+            .with_offsets(offsets);
+        if let Some(language) = language {
+            node = node.with_code_language(language);
+        }
+        self.add(node);
+    }
+
+    /// Read display math (`$$ ... $$`) in a paragraph into a formula node
+    fn formula(&mut self, range: Range<usize>) {
+        let source = &self.content[range.clone()];
+        let start = range.start + (source.len() - source.trim_start().len());
+        let body = &source.trim()[2..];
+        let (body, closed) = match body.trim_end().strip_suffix("$$") {
+            Some(body) => (body, true),
+            None => (body, false),
+        };
+        if !closed {
+            self.warnings.push(ConversionWarning::new(format!(
+                "Formula block opened on line {} is never closed",
+                self.line(start)
+            )));
+        }
+        let start = start + 2 + (body.len() - body.trim_start().len());
+        let body = body.trim();
+        if body.is_empty() {
+            return;
+        }
+
+        // Lines are joined with `\n` whatever the source line endings
+        let mut lines = Vec::new();
+        let mut offset = start;
+        for line in body.split('\n') {
+            lines.push((offset, line.trim_end_matches('\r')));
+            offset += line.len() + 1;
+        }
+        let text = lines
+            .iter()
+            .map(|(_, line)| *line)
+            .collect::<Vec<_>>()
+            .join("\n");
+        let offsets = OffsetMap::from_lines(lines.iter().map(|&(at, line)| (at, line.len())), 1);
+        let node = DocumentNode::new(NodeType::Formula, text)
+            .with_position(self.position(start..start + body.len()));
+        self.add(if offsets.is_identity(start, body.len()) {
+            node
+        } else {
+            node.with_offsets(offsets)
+        });
+    }
+
+    fn table(&mut self, table: Table) {
+        let source = &self.content[table.range.clone()];
+        let range = table.range.start..table.range.start + source.trim_end().len();
+        let node = DocumentNode::new(NodeType::Table, table.data.to_markdown().trim_end())
+            .with_position(self.position(range))
+            .with_table(table.data);
+        self.add(node);
+    }
+
+    /// Build a node from the inline content read, or `None` if it has no text
+    fn text_node(&mut self, node_type: NodeType) -> Option<DocumentNode> {
+        let inline = std::mem::take(&mut self.inline);
+        let text = inline.text.trim();
+        let range = inline.range?;
+        if text.is_empty() {
+            return None;
+        }
+        let lead = inline.text.len() - inline.text.trim_start().len();
+        let offsets = inline.offsets.slice(lead, lead + text.len());
+        let mut node =
+            DocumentNode::new(node_type, text).with_position(self.position(range.clone()));
+        // Spans are kept over what is left of the text once trimmed
+        for span in inline.spans {
+            let start = span.start.saturating_sub(lead).min(text.len());
+            let end = span.end.saturating_sub(lead).min(text.len());
+            if start < end {
+                node = node.with_span(TextSpan::new(start, end, span.formatting));
+            }
+        }
+        Some(if offsets.is_identity(range.start, text.len()) {
+            node
+        } else {
+            node.with_offsets(offsets)
+        })
+    }
+
+    /// Add a node to the innermost open container, or else to the innermost
+    /// open section, or at the top level
+    fn add(&mut self, node: DocumentNode) -> Option<NodeId> {
+        let container = self
+            .containers
+            .iter()
+            .rev()
+            .find_map(|container| match container {
+                Container::List(id) | Container::Quote(id) => Some(*id),
+                Container::Item { node, .. } => Some(*node),
+                Container::Footnote => None,
+            });
+        match container
+            .flatten()
+            .or_else(|| self.sections.last().map(|&(_, id)| id))
+        {
+            Some(parent) => self.doc.add_child(parent, node),
+            None => Some(self.doc.add_node(node)),
+        }
+    }
+
+    /// 1-based line of a byte offset
+    fn line(&self, offset: usize) -> usize {
+        self.line_starts.partition_point(|&start| start <= offset)
+    }
+
+    fn position(&self, range: Range<usize>) -> SourcePosition {
+        let last = range.end.saturating_sub(1).max(range.start);
+        SourcePosition::new(
+            range.start,
+            range.end,
+            self.line(range.start),
+            self.line(last),
+        )
+    }
+}
+
+/// Check whether a fenced code block ends with a closing fence
+fn is_closed_fence(block: &str) -> bool {
+    // Lines inside block quotes keep their `>` markers
+    let fence = |line: &'_ str| {
+        line.trim_start_matches(|c: char| c == '>' || c.is_whitespace())
+            .trim_end()
+            .to_string()
+    };
+    let mut lines = block.lines().map(fence);
+    let Some(open) = lines.next() else {
+        return false;
+    };
+    let marker = open.chars().next().unwrap_or('`');
+    let len = open.chars().take_while(|&c| c == marker).count();
+    lines
+        .next_back()
+        .is_some_and(|close| close.chars().count() >= len && close.chars().all(|c| c == marker))
 }
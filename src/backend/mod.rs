@@ -1,16 +1,20 @@
 //! Backend implementations for different document formats
 
+pub mod code;
 pub mod csv;
 pub mod docx;
 pub mod html;
 pub mod markdown;
 pub mod pdf;
+pub mod text;
 pub mod traits;
 
 // Re-exports
+pub use code::{CodeBackend, CodeLanguage};
 pub use csv::CsvBackend;
 pub use docx::DocxBackend;
-pub use html::HtmlBackend;
+pub use html::{HtmlBackend, HtmlConfig};
 pub use markdown::MarkdownBackend;
 pub use pdf::PdfBackend;
+pub use text::TextBackend;
 pub use traits::{Backend, DeclarativeBackend};
@@ -1,66 +1,70 @@
 //! PDF backend implementation.
 
 use super::config::PdfConfig;
+use super::image::ImageRegion;
 use super::image_extractor::{ImageExtractor, PdfiumImageExtractor};
+use super::layout_analyzer::{LayoutAnalyzer, RuleBasedLayoutAnalyzer};
+use super::pdfium;
+use super::text_extractor::TextExtractor;
 use crate::backend::Backend;
-use crate::datamodel::{DoclingDocument, DocumentNode, DocumentSource, InputDocument, NodeType};
+use crate::datamodel::{
+    ConversionWarning, DoclingDocument, DocumentMetadata, DocumentNode, DocumentSource, ImageStore,
+    InputDocument, NodeMetadata, NodeType, PictureItem, SourcePosition, TimedStage,
+};
 use crate::error::ConversionError;
+use crate::pipeline::{LimitGuard, NoProgress, ProgressEvent, ProgressObserver};
 use crate::InputFormat;
+use chrono::{DateTime, NaiveDate, TimeZone, Utc};
 use pdfium_render::prelude::*;
+use std::time::{Duration, Instant};
 
 // OCR engine imports (conditional on feature flag)
 #[cfg(feature = "ocr")]
 use super::ocr_engine::TesseractOcr;
 
-// Note: text_extractor with detailed position tracking is available but not used in basic implementation
-// It will be integrated in future iterations for advanced layout analysis
-
 /// PDF backend for document conversion.
+///
+/// Backends are cheap to create: they all use the same pdfium bindings, bound
+/// on the first PDF read, and convert one PDF at a time.
 pub struct PdfBackend {
     config: PdfConfig,
-    pdfium: Option<Pdfium>,
 }
 
 impl PdfBackend {
     /// Create a new PDF backend with default configuration.
     pub fn new() -> Self {
-        // Use bundled pdfium library (configured via build.rs)
-        let pdfium = Pdfium::default();
-
         Self {
             config: PdfConfig::default(),
-            pdfium: Some(pdfium),
         }
     }
 
     /// Create a new PDF backend with custom configuration.
     pub fn with_config(config: PdfConfig) -> Self {
-        // Use bundled pdfium library (configured via build.rs)
-        let pdfium = Pdfium::default();
-
-        Self {
-            config,
-            pdfium: Some(pdfium),
-        }
+        Self { config }
     }
 
-    /// Get the pdfium instance, returning an error if not available.
-    fn get_pdfium(&self) -> Result<&Pdfium, ConversionError> {
-        self.pdfium.as_ref().ok_or_else(|| {
-            ConversionError::ParseError(
-                "Pdfium library not available. Please install pdfium-render library.".to_string(),
-            )
-        })
+    /// The configuration of this backend.
+    pub fn config(&self) -> &PdfConfig {
+        &self.config
     }
 
     /// Load and convert a PDF document.
-    fn convert_pdf(&self, input: &InputDocument) -> Result<DoclingDocument, ConversionError> {
-        // Get pdfium instance
-        let pdfium = self.get_pdfium()?;
+    ///
+    /// With `emit`, every page is handed over as a document of its own as
+    /// soon as it is read, and the returned document has no nodes.
+    fn convert_pdf(
+        &self,
+        input: &InputDocument,
+        progress: &dyn ProgressObserver,
+        limits: &LimitGuard,
+        mut emit: Option<&mut dyn FnMut(DoclingDocument) -> Result<(), ConversionError>>,
+    ) -> Result<DoclingDocument, ConversionError> {
+        // Hold the shared pdfium instance until the document is read
+        let pdfium = pdfium::lock()?;
 
         // Load PDF using pdfium
         let pdf = match input.source() {
-            DocumentSource::FilePath(path) => {
+            DocumentSource::FilePath(path) | DocumentSource::Remote { path, .. } => {
                 if let Some(password) = &self.config.password {
                     pdfium
                         .load_pdf_from_file(path, Some(password))
@@ -91,10 +95,28 @@ impl PdfBackend {
             }
         };
 
+        let doc_name = match input.source() {
+            DocumentSource::FilePath(path) | DocumentSource::Remote { path, .. } => path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("document.pdf")
+                .to_string(),
+            DocumentSource::Bytes { name, .. } => name.clone(),
+        };
+
         // Extract text from all pages
         let page_count = pdf.pages().len() as usize;
-        let mut full_text = String::new();
-        let mut all_images = Vec::new();
+        let metadata =
+            Self::metadata(&pdf, DocumentMetadata::from_input(input)).with_page_count(page_count);
+        let mut page_nodes = Vec::new();
+        // Images of the pictures not yet handed to a document
+        let mut images = ImageStore::new();
+        let mut text_offset = 0;
+        let mut text_line = 1;
+        // Time spent in layout analysis and OCR, reported once all pages are done
+        let mut layout_time = Duration::ZERO;
+        #[cfg(feature = "ocr")]
+        let mut ocr_time = None;
 
         // Initialize image extractor if enabled
         let image_extractor = if self.config.enable_images {
@@ -113,78 +135,205 @@ impl PdfBackend {
 
         // Determine page range
         let range = self.config.page_range.clone().unwrap_or(0..page_count);
+        let total = range.end.min(page_count).saturating_sub(range.start);
+        limits.check_pages(total)?;
 
-        for page_index in range {
+        for (done, page_index) in range.enumerate() {
+            let _span = tracing::debug_span!("page", page = page_index + 1).entered();
             if page_index >= page_count {
                 break;
             }
 
-            let page = pdf.pages().get(page_index as u16).map_err(|e| {
-                ConversionError::ParseError(format!("Failed to get page {}: {}", page_index, e))
-            })?;
-
-            // Extract text
-            let text_page = page.text().map_err(|e| {
-                ConversionError::ParseError(format!(
-                    "Failed to get text from page {}: {}",
-                    page_index, e
-                ))
-            })?;
-
-            let page_text = text_page.all();
-
-            // If no text and OCR is enabled, try OCR (indicates scanned PDF)
-            #[cfg(feature = "ocr")]
-            if page_text.trim().is_empty() && self.config.enable_ocr {
-                if let Some(ref _ocr) = ocr_engine {
-                    // TODO: Implement actual OCR here
-                    // This requires:
-                    // 1. Rendering the page to an image
-                    // 2. Passing image to OCR engine
-                    // 3. Extracting text from OCR result
-                    // For now, we just log that OCR would be attempted
-                    // page_text = perform_ocr(&page, ocr)?;
+            let mut nodes = Vec::new();
+            'page: {
+                // A page that cannot be read is left out, with a warning
+                let warn = |message: String| {
+                    progress.on_progress(
+                        input.name(),
+                        &ProgressEvent::Warning(
+                            ConversionWarning::new(message).with_page(page_index + 1),
+                        ),
+                    );
+                };
+                let page = match pdf.pages().get(page_index as u16) {
+                    Ok(page) => page,
+                    Err(e) => {
+                        warn(format!("Failed to get page: {}", e));
+                        break 'page;
+                    }
+                };
+
+                // Extract text
+                let text_page = match page.text() {
+                    Ok(text_page) => text_page,
+                    Err(e) => {
+                        warn(format!("Failed to get text: {}", e));
+                        break 'page;
+                    }
+                };
+
+                // Split the text into blocks and put them in reading order
+                let layout_started = Instant::now();
+                let page_text = TextExtractor::new().extract(&text_page);
+                let layout = RuleBasedLayoutAnalyzer::new().analyze(
+                    &page_text.blocks,
+                    page.width().value as f64,
+                    page.height().value as f64,
+                );
+                layout_time += layout_started.elapsed();
+
+                // If no text and OCR is enabled, try OCR (indicates scanned PDF)
+                #[cfg(feature = "ocr")]
+                if page_text.text.trim().is_empty() && self.config.enable_ocr {
+                    let ocr_started = Instant::now();
+                    if let Some(ref _ocr) = ocr_engine {
+                        // TODO: Implement actual OCR here
+                        // This requires:
+                        // 1. Rendering the page to an image
+                        // 2. Passing image to OCR engine
+                        // 3. Extracting text from OCR result
+                        // For now, we just log that OCR would be attempted
+                        // page_text = perform_ocr(&page, ocr)?;
+                    }
+                    *ocr_time.get_or_insert(Duration::ZERO) += ocr_started.elapsed();
                 }
-            }
 
-            if !page_text.is_empty() {
-                full_text.push_str(&page_text);
-                full_text.push('\n');
+                // Position in the extracted text: pages in order, each followed by a newline
+                let mut positions = Vec::with_capacity(page_text.ranges.len());
+                let (mut offset, mut line) = (0, text_line);
+                for range in &page_text.ranges {
+                    line += page_text.text[offset..range.start].matches('\n').count();
+                    let end_line = line + page_text.text[range.clone()].matches('\n').count();
+                    positions.push(SourcePosition::new(
+                        text_offset + range.start,
+                        text_offset + range.end,
+                        line,
+                        end_line,
+                    ));
+                    (offset, line) = (range.end, end_line);
+                }
+                text_offset += page_text.text.len() + 1;
+                text_line += page_text.text.matches('\n').count() + 1;
+
+                // One text node per block, so chunks keep the block bounding boxes
+                for index in layout.reading_order {
+                    let block = &page_text.blocks[index];
+                    let metadata = NodeMetadata::new()
+                        .with_position(positions[index].clone())
+                        .with_page(page_index + 1)
+                        .with_bbox(block.bbox);
+                    nodes.push(
+                        DocumentNode::new(NodeType::Text, block.text.clone())
+                            .with_metadata(metadata),
+                    );
+                }
+
+                // One picture node per image, after the text of the page
+                if let Some(ref extractor) = image_extractor {
+                    for region in extractor.extract_images(&page) {
+                        nodes.push(Self::picture(&mut images, region, page_index + 1));
+                    }
+                }
             }
 
-            // Extract images if enabled
-            if let Some(ref extractor) = image_extractor {
-                let images = extractor.extract_images(&page);
-                all_images.extend(images);
+            if !nodes.is_empty() {
+                match emit.as_mut() {
+                    Some(emit) => {
+                        let mut page_doc = DoclingDocument::new(doc_name.clone())
+                            .with_document_metadata(metadata.clone())
+                            .with_nodes(nodes);
+                        *page_doc.images_mut() = std::mem::take(&mut images);
+                        emit(page_doc)?
+                    }
+                    None => {
+                        if !page_nodes.is_empty() {
+                            page_nodes.push(
+                                DocumentNode::new(NodeType::PageBreak, "")
+                                    .with_page(page_index + 1),
+                            );
+                        }
+                        page_nodes.extend(nodes);
+                    }
+                }
             }
+
+            progress.on_progress(
+                input.name(),
+                &ProgressEvent::Page {
+                    page: page_index + 1,
+                    done: done + 1,
+                    total,
+                },
+            );
+            limits.check_time()?;
         }
 
-        // Create DoclingDocument
-        let doc_name = match input.source() {
-            DocumentSource::FilePath(path) => path
-                .file_name()
-                .and_then(|n| n.to_str())
-                .unwrap_or("document.pdf")
-                .to_string(),
-            DocumentSource::Bytes { name, .. } => name.clone(),
+        let timing = |stage, elapsed| {
+            progress.on_progress(input.name(), &ProgressEvent::Timing { stage, elapsed });
         };
+        timing(TimedStage::Layout, layout_time);
+        #[cfg(feature = "ocr")]
+        if let Some(elapsed) = ocr_time {
+            timing(TimedStage::Ocr, elapsed);
+        }
 
-        let mut doc = DoclingDocument::new(doc_name);
+        // Create DoclingDocument
+        let mut doc = DoclingDocument::new(doc_name)
+            .with_document_metadata(metadata)
+            .with_nodes(page_nodes);
+        *doc.images_mut() = images;
 
-        // Create a single text node with all content
-        if !full_text.trim().is_empty() {
-            let node = DocumentNode::new(NodeType::Text, full_text);
-            doc.add_node(node);
+        Ok(doc)
+    }
+
+    /// Picture node of an image on page `page`, its bytes put in `images`.
+    fn picture(images: &mut ImageStore, region: ImageRegion, page: usize) -> DocumentNode {
+        let node = DocumentNode::new(NodeType::Picture, "")
+            .with_page(page)
+            .with_bbox(region.bbox);
+        let Some(data) = region.bitmap else {
+            return node;
+        };
+        let digest = images.insert(data);
+        let mut picture = PictureItem::stored(digest.as_str())
+            .with_size(region.metadata.width, region.metadata.height);
+        if let Some(mimetype) = images.get(&digest).and_then(|image| image.mimetype()) {
+            picture = picture.with_mimetype(mimetype);
         }
+        node.with_picture(picture)
+    }
+
+    /// Read title, authors and dates from the PDF info dictionary.
+    fn metadata(pdf: &PdfDocument, mut metadata: DocumentMetadata) -> DocumentMetadata {
+        let tags = pdf.metadata();
+        let tag = |tag_type| {
+            tags.get(tag_type)
+                .map(|t| t.value().trim().to_string())
+                .filter(|v| !v.is_empty())
+        };
 
-        // Add image count as metadata
-        if !all_images.is_empty() {
-            doc = doc.with_metadata("image_count", all_images.len());
-            // TODO: In a future phase, add actual Image nodes to the document
-            // For now, we've successfully extracted and classified the images
+        if let Some(title) = tag(PdfDocumentMetadataTagType::Title) {
+            metadata = metadata.with_title(title);
+        }
+        if let Some(authors) = tag(PdfDocumentMetadataTagType::Author) {
+            for author in authors.split(';').map(str::trim).filter(|a| !a.is_empty()) {
+                metadata = metadata.with_author(author);
+            }
+        }
+        if let Some(created) = tag(PdfDocumentMetadataTagType::CreationDate)
+            .as_deref()
+            .and_then(parse_pdf_date)
+        {
+            metadata = metadata.with_created(created);
+        }
+        if let Some(modified) = tag(PdfDocumentMetadataTagType::ModificationDate)
+            .as_deref()
+            .and_then(parse_pdf_date)
+        {
+            metadata = metadata.with_modified(modified);
         }
 
-        Ok(doc)
+        metadata
     }
 }
 
@@ -196,6 +345,23 @@ impl Default for PdfBackend {
 
 impl Backend for PdfBackend {
     fn convert(&self, input: &InputDocument) -> Result<DoclingDocument, ConversionError> {
+        self.convert_with_progress(input, &NoProgress)
+    }
+
+    fn convert_with_progress(
+        &self,
+        input: &InputDocument,
+        progress: &dyn ProgressObserver,
+    ) -> Result<DoclingDocument, ConversionError> {
+        self.convert_with_limits(input, progress, &LimitGuard::unlimited())
+    }
+
+    fn convert_with_limits(
+        &self,
+        input: &InputDocument,
+        progress: &dyn ProgressObserver,
+        limits: &LimitGuard,
+    ) -> Result<DoclingDocument, ConversionError> {
         // Verify input format
         if input.format() != InputFormat::PDF {
             return Err(ConversionError::UnsupportedFormat(format!(
@@ -204,10 +370,82 @@ impl Backend for PdfBackend {
             )));
         }
 
-        self.convert_pdf(input)
+        self.convert_pdf(input, progress, limits, None)
+    }
+
+    fn convert_streaming(
+        &self,
+        input: &InputDocument,
+        progress: &dyn ProgressObserver,
+        limits: &LimitGuard,
+        emit: &mut dyn FnMut(DoclingDocument) -> Result<(), ConversionError>,
+    ) -> Result<(), ConversionError> {
+        if input.format() != InputFormat::PDF {
+            return Err(ConversionError::UnsupportedFormat(format!(
+                "Expected PDF format, got {:?}",
+                input.format()
+            )));
+        }
+
+        self.convert_pdf(input, progress, limits, Some(emit))
+            .map(|_| ())
     }
 
     fn supports_format(&self, format: InputFormat) -> bool {
         matches!(format, InputFormat::PDF)
     }
 }
+
+/// Parse a PDF date string (`D:YYYYMMDDHHmmSSOHH'mm'`) into UTC.
+///
+/// Every field after the year is optional; a missing timezone means UTC.
+fn parse_pdf_date(value: &str) -> Option<DateTime<Utc>> {
+    let value = value.strip_prefix("D:").unwrap_or(value);
+    let digits: String = value.chars().take_while(|c| c.is_ascii_digit()).collect();
+    let field = |start: usize, default: u32| match digits.get(start..start + 2) {
+        Some(f) => f.parse().ok(),
+        None => Some(default),
+    };
+
+    let year = digits.get(..4)?.parse().ok()?;
+    let local = NaiveDate::from_ymd_opt(year, field(4, 1)?, field(6, 1)?)?.and_hms_opt(
+        field(8, 0)?,
+        field(10, 0)?,
+        field(12, 0)?,
+    )?;
+
+    let zone = &value[digits.len()..];
+    let offset_minutes = match zone.chars().next() {
+        Some(sign @ ('+' | '-')) => {
+            let tz: String = zone.chars().filter(|c| c.is_ascii_digit()).collect();
+            let hours: i64 = tz.get(..2).and_then(|h| h.parse().ok()).unwrap_or(0);
+            let minutes: i64 = tz.get(2..4).and_then(|m| m.parse().ok()).unwrap_or(0);
+            let offset = hours * 60 + minutes;
+            if sign == '-' {
+                -offset
+            } else {
+                offset
+            }
+        }
+        _ => 0,
+    };
+
+    Some(Utc.from_utc_datetime(&local) - chrono::Duration::minutes(offset_minutes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_pdf_date() {
+        let date = parse_pdf_date("D:20230415103000+02'00'").unwrap();
+        assert_eq!(date.to_rfc3339(), "2023-04-15T08:30:00+00:00");
+
+        let date = parse_pdf_date("D:20230415").unwrap();
+        assert_eq!(date.to_rfc3339(), "2023-04-15T00:00:00+00:00");
+
+        assert!(parse_pdf_date("D:20231345").is_none());
+        assert!(parse_pdf_date("garbage").is_none());
+    }
+}
@@ -0,0 +1,55 @@
+//! PDF backend stand-in for builds without the `pdf` feature.
+//!
+//! Without pdfium there is nothing to read PDFs with, so every conversion
+//! fails with [`ConversionError::UnsupportedFormat`].
+
+use super::config::PdfConfig;
+use crate::backend::Backend;
+use crate::datamodel::{DoclingDocument, InputDocument};
+use crate::error::ConversionError;
+use crate::InputFormat;
+
+/// PDF backend for document conversion.
+///
+/// This build has no PDF support: enable the `pdf` feature to convert PDFs.
+pub struct PdfBackend {
+    config: PdfConfig,
+}
+
+impl PdfBackend {
+    /// Create a new PDF backend with default configuration.
+    pub fn new() -> Self {
+        Self {
+            config: PdfConfig::default(),
+        }
+    }
+
+    /// Create a new PDF backend with custom configuration.
+    pub fn with_config(config: PdfConfig) -> Self {
+        Self { config }
+    }
+
+    /// The configuration of this backend.
+    pub fn config(&self) -> &PdfConfig {
+        &self.config
+    }
+}
+
+impl Default for PdfBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Backend for PdfBackend {
+    fn convert(&self, input: &InputDocument) -> Result<DoclingDocument, ConversionError> {
+        Err(ConversionError::UnsupportedFormat(format!(
+            "Cannot convert {}: PDF support requires docling-rs to be built with the `pdf` feature",
+            input.name()
+        )))
+    }
+
+    fn supports_format(&self, format: InputFormat) -> bool {
+        matches!(format, InputFormat::PDF)
+    }
+}
@@ -12,7 +12,7 @@ pub struct ImageRegion {
     /// Type classification of the image
     pub image_type: ImageType,
 
-    /// Optional raw bitmap data (RGBA format)
+    /// Encoded image bytes (JPEG as stored in the PDF, otherwise PNG)
     pub bitmap: Option<Vec<u8>>,
 
     /// Image metadata
@@ -197,12 +197,12 @@ mod tests {
 
     #[test]
     fn test_image_region_creation() {
-        let bbox = BoundingBox::new(100.0, 200.0, 300.0, 400.0);
+        let bbox = BoundingBox::new(100.0, 400.0, 300.0, 200.0);
         let metadata = ImageMetadata::new(800, 600, ImageFormat::Jpeg);
 
         let region = ImageRegion::new(bbox, metadata);
 
-        assert_eq!(region.bbox.x, 100.0);
+        assert_eq!(region.bbox.left, 100.0);
         assert_eq!(region.image_type, ImageType::Unknown);
         assert!(region.bitmap.is_none());
         assert_eq!(region.metadata.width, 800);
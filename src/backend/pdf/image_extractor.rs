@@ -3,6 +3,7 @@
 use super::image::{ImageFormat, ImageMetadata, ImageRegion, ImageType};
 use super::types::BoundingBox;
 use pdfium_render::prelude::*;
+use std::io::Cursor;
 
 /// Trait for image extraction implementations.
 pub trait ImageExtractor {
@@ -30,7 +31,6 @@ pub struct PdfiumImageExtractor {
     min_image_size: u32,
 
     /// Whether to extract bitmap data
-    #[allow(dead_code)]
     extract_bitmaps: bool,
 
     /// Whether to perform image classification
@@ -80,14 +80,24 @@ impl PdfiumImageExtractor {
                 let right = bounds.right().value as f64;
                 let top = bounds.top().value as f64;
 
-                let bbox = BoundingBox::new(left, bottom, right - left, top - bottom);
-
-                // Get image dimensions (convert from PDF points to approximate pixels)
-                let width = bbox.width as u32;
-                let height = bbox.height as u32;
-
-                // For now, we don't extract bitmap data (would require rendering)
-                let format = ImageFormat::Unknown;
+                let bbox = BoundingBox::new(left, top, right, bottom);
+
+                // Pixel size of the image, or its size on the page in points
+                let width = image_obj
+                    .width()
+                    .map_or(bbox.width() as u32, |w| w.max(0) as u32);
+                let height = image_obj
+                    .height()
+                    .map_or(bbox.height() as u32, |h| h.max(0) as u32);
+
+                let bitmap = if self.extract_bitmaps {
+                    self.encode_image(image_obj)
+                } else {
+                    None
+                };
+                let format = bitmap
+                    .as_deref()
+                    .map_or(ImageFormat::Unknown, |data| self.detect_format(data));
 
                 let mut metadata = ImageMetadata::new(width, height, format);
 
@@ -98,6 +108,7 @@ impl PdfiumImageExtractor {
 
                 // Create image region
                 let mut region = ImageRegion::new(bbox, metadata);
+                region.bitmap = bitmap;
 
                 // Classify if enabled
                 if self.classify_images {
@@ -115,8 +126,25 @@ impl PdfiumImageExtractor {
         regions
     }
 
+    /// Encode the pixels of an image object.
+    ///
+    /// JPEG streams are kept as they are; other images are decoded by pdfium
+    /// and encoded as PNG.
+    fn encode_image(&self, image_obj: &PdfPageImageObject) -> Option<Vec<u8>> {
+        if let Ok(data) = image_obj.get_raw_image_data() {
+            if self.detect_format(&data) == ImageFormat::Jpeg {
+                return Some(data);
+            }
+        }
+        let image = image_obj.get_raw_image().ok()?;
+        let mut png = Vec::new();
+        image
+            .write_to(&mut Cursor::new(&mut png), image::ImageFormat::Png)
+            .ok()?;
+        Some(png)
+    }
+
     /// Detect the format of an extracted image.
-    #[allow(dead_code)]
     fn detect_format(&self, bitmap_data: &[u8]) -> ImageFormat {
         // Check magic bytes to identify format
         if bitmap_data.len() < 4 {
@@ -190,7 +218,7 @@ impl PdfiumImageExtractor {
     fn estimate_dpi(&self, bbox: &BoundingBox, width_px: u32) -> Option<u32> {
         // DPI = pixels / inches
         // bbox width is in points (1 point = 1/72 inch)
-        let width_inches = bbox.width / 72.0;
+        let width_inches = bbox.width() / 72.0;
         if width_inches > 0.0 {
             let dpi = (width_px as f64 / width_inches) as u32;
             Some(dpi)
@@ -284,7 +312,7 @@ mod tests {
     fn test_dpi_estimation() {
         let extractor = PdfiumImageExtractor::new();
         // 7.2 inch width at 72 points/inch = 518.4 points
-        let bbox = BoundingBox::new(0.0, 0.0, 518.4, 100.0);
+        let bbox = BoundingBox::new(0.0, 100.0, 518.4, 0.0);
         let width_px = 1440; // pixels
 
         let dpi = extractor.estimate_dpi(&bbox, width_px);
@@ -60,19 +60,19 @@ impl RuleBasedLayoutAnalyzer {
     }
 
     /// Detect columns based on horizontal gaps in text blocks.
-    fn detect_columns(&self, text_blocks: &[TextBlock], page_width: f64) -> Vec<Column> {
+    fn detect_columns(
+        &self,
+        text_blocks: &[TextBlock],
+        page_width: f64,
+        page_height: f64,
+    ) -> Vec<Column> {
         if text_blocks.is_empty() {
             return Vec::new();
         }
 
         // Sort blocks by x-position
         let mut sorted_blocks: Vec<(usize, &TextBlock)> = text_blocks.iter().enumerate().collect();
-        sorted_blocks.sort_by(|a, b| {
-            a.1.bbox
-                .x
-                .partial_cmp(&b.1.bbox.x)
-                .unwrap_or(std::cmp::Ordering::Equal)
-        });
+        sorted_blocks.sort_by(|a, b| a.1.bbox.left.total_cmp(&b.1.bbox.left));
 
         let mut columns = Vec::new();
         let min_gap = page_width * self.column_gap_threshold;
@@ -84,7 +84,7 @@ impl RuleBasedLayoutAnalyzer {
             let prev_block = sorted_blocks[i - 1].1;
             let curr_block = sorted_blocks[i].1;
 
-            let gap = curr_block.bbox.x - (prev_block.bbox.x + prev_block.bbox.width);
+            let gap = curr_block.bbox.left - prev_block.bbox.right;
 
             if gap > min_gap {
                 // Start new column
@@ -107,7 +107,7 @@ impl RuleBasedLayoutAnalyzer {
 
         // If only one column detected and it doesn't cover most of page, treat as single column
         if columns.len() == 1 || columns.is_empty() {
-            return self.create_single_column(text_blocks, page_width);
+            return self.create_single_column(text_blocks, page_width, page_height);
         }
 
         columns
@@ -124,32 +124,12 @@ impl RuleBasedLayoutAnalyzer {
             return Column::new(id, BoundingBox::new(0.0, 0.0, 0.0, 0.0));
         }
 
-        // Calculate bounding box for the column
-        let min_x = blocks
-            .iter()
-            .map(|(_, b)| b.bbox.x)
-            .min_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
-            .unwrap_or(0.0);
-
-        let max_x = blocks
-            .iter()
-            .map(|(_, b)| b.bbox.x + b.bbox.width)
-            .max_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
-            .unwrap_or(0.0);
-
-        let min_y = blocks
+        // The column covers all of its blocks
+        let bbox = blocks
             .iter()
-            .map(|(_, b)| b.bbox.y)
-            .min_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
-            .unwrap_or(0.0);
-
-        let max_y = blocks
-            .iter()
-            .map(|(_, b)| b.bbox.y + b.bbox.height)
-            .max_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
-            .unwrap_or(0.0);
-
-        let bbox = BoundingBox::new(min_x, min_y, max_x - min_x, max_y - min_y);
+            .map(|(_, b)| b.bbox)
+            .reduce(|a, b| a.union(&b))
+            .unwrap_or(BoundingBox::new(0.0, 0.0, 0.0, 0.0));
 
         let mut column = Column::new(id, bbox);
         for (idx, _) in blocks {
@@ -160,12 +140,17 @@ impl RuleBasedLayoutAnalyzer {
     }
 
     /// Create a single column containing all text blocks.
-    fn create_single_column(&self, text_blocks: &[TextBlock], page_width: f64) -> Vec<Column> {
+    fn create_single_column(
+        &self,
+        text_blocks: &[TextBlock],
+        page_width: f64,
+        page_height: f64,
+    ) -> Vec<Column> {
         if text_blocks.is_empty() {
             return Vec::new();
         }
 
-        let mut column = Column::new(0, BoundingBox::new(0.0, 0.0, page_width, 0.0));
+        let mut column = Column::new(0, BoundingBox::new(0.0, page_height, page_width, 0.0));
         for (idx, _) in text_blocks.iter().enumerate() {
             column.add_text_block(idx);
         }
@@ -189,13 +174,8 @@ impl RuleBasedLayoutAnalyzer {
                 .map(|&idx| (idx, &text_blocks[idx]))
                 .collect();
 
-            // Sort by y-position (top to bottom)
-            column_blocks.sort_by(|a, b| {
-                a.1.bbox
-                    .y
-                    .partial_cmp(&b.1.bbox.y)
-                    .unwrap_or(std::cmp::Ordering::Equal)
-            });
+            // Sort by y-position (top to bottom; y grows upwards in PDF coordinates)
+            column_blocks.sort_by(|a, b| b.1.bbox.top.total_cmp(&a.1.bbox.top));
 
             // Add to reading order
             for (idx, _) in column_blocks {
@@ -222,7 +202,7 @@ impl LayoutAnalyzer for RuleBasedLayoutAnalyzer {
         }
 
         // Detect columns
-        let columns = self.detect_columns(text_blocks, page_width);
+        let columns = self.detect_columns(text_blocks, page_width, page_height);
 
         // Determine reading order
         let reading_order = self.determine_reading_order(&columns, text_blocks);
@@ -243,10 +223,11 @@ mod tests {
     use crate::backend::pdf::page::TextBlockType;
     use crate::backend::pdf::types::FontInfo;
 
+    /// Block at `y` points from the top of an 800 points high page
     fn create_test_block(text: &str, x: f64, y: f64, width: f64, height: f64) -> TextBlock {
         TextBlock {
             text: text.to_string(),
-            bbox: BoundingBox::new(x, y, width, height),
+            bbox: BoundingBox::new(x, 800.0 - y, x + width, 800.0 - y - height),
             font_info: FontInfo {
                 name: "Arial".to_string(),
                 size: 12.0,
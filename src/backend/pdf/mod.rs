@@ -8,6 +8,9 @@
 //! - OCR for scanned PDFs
 //! - Content enrichment (code blocks, formulas, lists)
 //!
+//! Reading PDFs needs the `pdf` feature (on by default), which binds pdfium.
+//! Without it, [`PdfBackend`] rejects every PDF as an unsupported format.
+//!
 //! # Example
 //!
 //! ```rust,ignore
@@ -24,23 +27,33 @@
 //! ```
 
 // Re-export main types
+#[cfg(feature = "pdf")]
 pub use backend::PdfBackend;
 pub use config::PdfConfig;
+#[cfg(not(feature = "pdf"))]
+pub use disabled::PdfBackend;
 pub use document::PdfDocument;
 pub use page::PdfPage;
 
 // Module declarations
+#[cfg(feature = "pdf")]
 mod backend;
 mod config;
+#[cfg(not(feature = "pdf"))]
+mod disabled;
 mod document;
 pub mod image; // Public for Phase 3d
+#[cfg(feature = "pdf")]
 pub mod image_extractor; // Public for Phase 3d
 pub mod layout; // Public for Phase 3b
 pub mod layout_analyzer; // Public for Phase 3b
 pub mod ocr; // Public for Phase 3e
 pub mod ocr_engine;
 pub mod page; // Public for Phase 3b (TextBlock, etc.)
+#[cfg(feature = "pdf")]
+mod pdfium;
 pub mod table; // Public for Phase 3c
-pub mod table_detector; // Public for Phase 3c // Public for Phase 3e
-                        // mod text_extractor;  // TODO: Fix pdfium API compatibility issues
+pub mod table_detector; // Public for Phase 3c
+#[cfg(feature = "pdf")]
+pub mod text_extractor;
 pub mod types; // Public for Phase 3b (BoundingBox, FontInfo, etc.)
@@ -81,7 +81,7 @@ impl TesseractOcr {
                 // Estimate bounding box based on word position
                 // In real implementation, tesseract provides actual coordinates
                 let x = (i as f64) * 50.0;
-                let bbox = BoundingBox::new(x, 0.0, 45.0, 20.0);
+                let bbox = BoundingBox::new(x, 20.0, x + 45.0, 0.0);
 
                 OcrWord::new(word.to_string(), confidence, bbox)
             })
@@ -0,0 +1,39 @@
+//! Pdfium bindings shared by every PDF backend.
+//!
+//! pdfium is not thread-safe, and pdfium-render serializes access to it by
+//! letting one bound instance exist at a time: binding again blocks until the
+//! previous instance is dropped. Backends therefore share a single instance,
+//! bound the first time a PDF is read and kept for the life of the process.
+//! Conversions lock it for as long as they read their document, so those
+//! running on worker threads take turns instead of racing.
+
+use crate::error::ConversionError;
+use pdfium_render::prelude::*;
+use std::sync::{Mutex, MutexGuard, OnceLock, PoisonError};
+
+/// The shared instance, or why pdfium could not be bound
+static PDFIUM: OnceLock<Result<Mutex<Pdfium>, String>> = OnceLock::new();
+
+/// Exclusive access to the shared pdfium instance, binding it on first use.
+///
+/// Fails if the pdfium library cannot be loaded; the attempt is not repeated.
+pub(super) fn lock() -> Result<MutexGuard<'static, Pdfium>, ConversionError> {
+    match PDFIUM.get_or_init(|| bind().map(Mutex::new)) {
+        // A conversion that panicked leaves pdfium usable
+        Ok(pdfium) => Ok(pdfium.lock().unwrap_or_else(PoisonError::into_inner)),
+        Err(e) => Err(ConversionError::ParseError(format!(
+            "Pdfium library not available: {}",
+            e
+        ))),
+    }
+}
+
+/// Bind the pdfium library of the working directory (where the build
+/// script places the bundled one), or else the system library.
+fn bind() -> Result<Pdfium, String> {
+    let bindings = Pdfium::bind_to_library(Pdfium::pdfium_platform_library_name_at_path("./"))
+        .or_else(|_| Pdfium::bind_to_system_library())
+        .map_err(|e| e.to_string())?;
+    tracing::debug!("Bound pdfium");
+    Ok(Pdfium::new(bindings))
+}
@@ -2,7 +2,6 @@
 
 use super::page::TextBlock;
 use super::table::{Table, TableCell, TableStructure};
-use super::types::BoundingBox;
 
 /// Trait for table detection implementations.
 pub trait TableDetector {
@@ -82,13 +81,13 @@ impl GridBasedTableDetector {
         let mut row_groups: Vec<Vec<usize>> = Vec::new();
 
         for (idx, block) in blocks.iter().enumerate() {
-            let block_y = block.bbox.y;
+            let block_y = block.bbox.top;
 
             // Find existing row group that this block aligns with
             let mut found_group = false;
             for group in &mut row_groups {
                 if let Some(&first_idx) = group.first() {
-                    let first_y = blocks[first_idx].bbox.y;
+                    let first_y = blocks[first_idx].bbox.top;
                     if (block_y - first_y).abs() < self.alignment_tolerance {
                         group.push(idx);
                         found_group = true;
@@ -102,22 +101,12 @@ impl GridBasedTableDetector {
             }
         }
 
-        // Sort row groups by y-position
-        row_groups.sort_by(|a, b| {
-            let y_a = blocks[a[0]].bbox.y;
-            let y_b = blocks[b[0]].bbox.y;
-            y_a.partial_cmp(&y_b).unwrap_or(std::cmp::Ordering::Equal)
-        });
+        // Sort row groups top to bottom (y grows upwards in PDF coordinates)
+        row_groups.sort_by(|a, b| blocks[b[0]].bbox.top.total_cmp(&blocks[a[0]].bbox.top));
 
         // Sort blocks within each row by x-position
         for group in &mut row_groups {
-            group.sort_by(|&a, &b| {
-                blocks[a]
-                    .bbox
-                    .x
-                    .partial_cmp(&blocks[b].bbox.x)
-                    .unwrap_or(std::cmp::Ordering::Equal)
-            });
+            group.sort_by(|&a, &b| blocks[a].bbox.left.total_cmp(&blocks[b].bbox.left));
         }
 
         row_groups
@@ -154,38 +143,12 @@ impl GridBasedTableDetector {
         let rows = row_groups.len();
         let cols = row_groups.iter().map(|row| row.len()).max().unwrap_or(0);
 
-        // Calculate table bounding box
-        let all_indices: Vec<usize> = row_groups
+        // The table covers all of its cells
+        let table_bbox = row_groups
             .iter()
-            .flat_map(|row| row.iter())
-            .copied()
-            .collect();
-
-        let min_x = all_indices
-            .iter()
-            .map(|&idx| blocks[idx].bbox.x)
-            .min_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
-            .unwrap_or(0.0);
-
-        let max_x = all_indices
-            .iter()
-            .map(|&idx| blocks[idx].bbox.x + blocks[idx].bbox.width)
-            .max_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
-            .unwrap_or(0.0);
-
-        let min_y = all_indices
-            .iter()
-            .map(|&idx| blocks[idx].bbox.y)
-            .min_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
-            .unwrap_or(0.0);
-
-        let max_y = all_indices
-            .iter()
-            .map(|&idx| blocks[idx].bbox.y + blocks[idx].bbox.height)
-            .max_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
-            .unwrap_or(0.0);
-
-        let table_bbox = BoundingBox::new(min_x, min_y, max_x - min_x, max_y - min_y);
+            .flatten()
+            .map(|&idx| blocks[idx].bbox)
+            .reduce(|a, b| a.union(&b))?;
         let structure = TableStructure::new(rows, cols);
         let mut table = Table::new(table_bbox, structure).with_header_rows(1); // Assume first row is header
 
@@ -239,12 +202,13 @@ impl TableDetector for GridBasedTableDetector {
 mod tests {
     use super::*;
     use crate::backend::pdf::page::TextBlockType;
-    use crate::backend::pdf::types::FontInfo;
+    use crate::backend::pdf::types::{BoundingBox, FontInfo};
 
+    /// Block at `y` points from the top of an 800 points high page
     fn create_test_block(text: &str, x: f64, y: f64, width: f64, height: f64) -> TextBlock {
         TextBlock {
             text: text.to_string(),
-            bbox: BoundingBox::new(x, y, width, height),
+            bbox: BoundingBox::new(x, 800.0 - y, x + width, 800.0 - y - height),
             font_info: FontInfo {
                 name: "Arial".to_string(),
                 size: 12.0,
@@ -2,10 +2,38 @@
 
 use super::page::{TextBlock, TextBlockType};
 use super::types::{BoundingBox, FontInfo};
-use crate::error::ConversionError;
 use pdfium_render::prelude::*;
+use std::ops::Range;
+
+/// Gap between two lines, as a fraction of the height of the lower line,
+/// above which the lower line starts a new block.
+const BLOCK_GAP: f64 = 0.5;
+
+/// Text of a page, split into blocks.
+#[derive(Debug, Clone, Default)]
+pub struct PageText {
+    /// Text of the page, character by character.
+    pub text: String,
+
+    /// Text blocks, in the order of the page content.
+    pub blocks: Vec<TextBlock>,
+
+    /// Byte range of each block in `text`.
+    pub ranges: Vec<Range<usize>>,
+}
+
+/// A line of visible text on a page.
+struct Line {
+    range: Range<usize>,
+    bbox: BoundingBox,
+    font_size: f64,
+}
 
 /// Extracts text with positions from a PDF page.
+///
+/// Lines follow the line breaks pdfium puts in the page text. Consecutive
+/// lines go into one block unless a blank line, a gap of more than half a
+/// line, or a jump back up the page (a new column) separates them.
 pub struct TextExtractor;
 
 impl TextExtractor {
@@ -14,132 +42,104 @@ impl TextExtractor {
         Self
     }
 
-    /// Extract text blocks from a pdfium page.
-    pub fn extract_from_page(
-        &self,
-        page: &PdfPage,
-        _page_number: usize,
-    ) -> Result<Vec<TextBlock>, ConversionError> {
-        let mut text_blocks = Vec::new();
-        let mut reading_order = 0;
-
-        // Get all text objects on the page
-        let text_page = page
-            .text()
-            .map_err(|e| ConversionError::ParseError(format!("Failed to get text page: {}", e)))?;
-
-        // Extract text with character-level positions
-        let char_count = text_page.chars().len();
-
-        if char_count == 0 {
-            return Ok(text_blocks);
-        }
-
-        // Group characters into text blocks
-        // For now, we'll create one text block per line
-        let mut current_text = String::new();
-        let mut current_bounds: Option<PdfRect> = None;
-        let mut current_font_size = 0.0;
-
-        for char_index in 0..char_count {
-            if let Ok(text_char) = text_page.chars().get(char_index) {
-                let char_str = text_char.text();
-                let bounds = text_char.loose_bounds();
-
-                // Check if this is a newline or if we should start a new block
-                if char_str == "\n" || char_str == "\r" {
-                    if !current_text.is_empty() {
-                        if let Some(bbox) = current_bounds {
-                            text_blocks.push(self.create_text_block(
-                                current_text.clone(),
-                                bbox,
-                                current_font_size,
-                                reading_order,
-                            ));
-                            reading_order += 1;
-                        }
-                        current_text.clear();
-                        current_bounds = None;
-                    }
-                    continue;
+    /// Extract the text blocks of a page.
+    pub fn extract(&self, text_page: &PdfPageText) -> PageText {
+        let mut page = PageText::default();
+        let mut lines: Vec<Option<Line>> = Vec::new();
+        let mut current: Option<Line> = None;
+
+        for text_char in text_page.chars().iter() {
+            let Some(ch) = text_char.unicode_char() else {
+                continue;
+            };
+            if ch == '\n' || ch == '\r' {
+                // Blank lines are kept as `None` to separate blocks
+                if ch == '\n' {
+                    lines.push(current.take());
                 }
+                page.text.push(ch);
+                continue;
+            }
 
-                // Add character to current block
-                current_text.push_str(&char_str);
-
-                // Update bounding box
-                if let Some(existing_bounds) = current_bounds {
-                    current_bounds = Some(self.merge_bounds(existing_bounds, bounds));
-                } else {
-                    current_bounds = Some(bounds);
+            let start = page.text.len();
+            page.text.push(ch);
+            if ch.is_whitespace() {
+                continue;
+            }
+            let Ok(rect) = text_char.loose_bounds() else {
+                continue;
+            };
+            let bbox = BoundingBox::new(
+                rect.left().value as f64,
+                rect.top().value as f64,
+                rect.right().value as f64,
+                rect.bottom().value as f64,
+            );
+            match current.as_mut() {
+                Some(line) => {
+                    line.range.end = page.text.len();
+                    line.bbox = line.bbox.union(&bbox);
+                }
+                None => {
+                    current = Some(Line {
+                        range: start..page.text.len(),
+                        bbox,
+                        font_size: rect.height().value as f64,
+                    })
                 }
-
-                // Estimate font size from character height
-                current_font_size = bounds.height().value;
             }
         }
-
-        // Add final block if any
-        if !current_text.is_empty() {
-            if let Some(bbox) = current_bounds {
-                text_blocks.push(self.create_text_block(
-                    current_text,
-                    bbox,
-                    current_font_size,
-                    reading_order,
-                ));
+        lines.push(current);
+
+        let mut block: Option<Line> = None;
+        for line in lines {
+            let Some(line) = line else {
+                self.push_block(&mut page, block.take());
+                continue;
+            };
+            match block.as_mut() {
+                Some(open) if Self::continues(open, &line) => {
+                    open.range.end = line.range.end;
+                    open.bbox = open.bbox.union(&line.bbox);
+                }
+                _ => {
+                    self.push_block(&mut page, block.take());
+                    block = Some(line);
+                }
             }
         }
+        self.push_block(&mut page, block);
 
-        Ok(text_blocks)
+        page
     }
 
-    /// Create a text block from extracted data.
-    fn create_text_block(
-        &self,
-        text: String,
-        bounds: PdfRect,
-        font_size: f32,
-        reading_order: usize,
-    ) -> TextBlock {
-        let bbox = BoundingBox::new(
-            bounds.left().value as f64,
-            bounds.top().value as f64,
-            bounds.width().value as f64,
-            bounds.height().value as f64,
-        );
+    /// Whether `line` continues the block ending with `block`.
+    fn continues(block: &Line, line: &Line) -> bool {
+        let gap = block.bbox.bottom - line.bbox.top;
+        line.bbox.top <= block.bbox.top && gap <= line.bbox.height() * BLOCK_GAP
+    }
 
+    /// Close a block of lines.
+    fn push_block(&self, page: &mut PageText, block: Option<Line>) {
+        let Some(block) = block else {
+            return;
+        };
         let font_info = FontInfo {
             name: "Unknown".to_string(), // pdfium-render doesn't easily expose font names
-            size: font_size as f64,
+            size: block.font_size,
             bold: false, // Would need more analysis to detect
             italic: false,
         };
-
-        TextBlock {
-            text,
-            bbox,
+        page.blocks.push(TextBlock {
+            text: page.text[block.range.clone()].to_string(),
+            bbox: block.bbox,
             font_info,
-            reading_order,
+            reading_order: page.blocks.len(),
             column_id: None,
             block_type: TextBlockType::Paragraph,
             confidence: None,
-        }
-    }
-
-    /// Merge two bounding boxes.
-    fn merge_bounds(&self, a: PdfRect, b: PdfRect) -> PdfRect {
-        let left = a.left().value.min(b.left().value);
-        let top = a.top().value.min(b.top().value);
-        let right = a.right().value.max(b.right().value);
-        let bottom = a.bottom().value.max(b.bottom().value);
-
-        PdfRect::new(
-            PdfPoints::new(left),
-            PdfPoints::new(top),
-            PdfPoints::new(right),
-            PdfPoints::new(bottom),
-        )
+        });
+        page.ranges.push(block.range);
     }
 }
 
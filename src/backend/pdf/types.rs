@@ -2,40 +2,11 @@
 
 use serde::{Deserialize, Serialize};
 
-/// Bounding box with coordinates.
-#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
-pub struct BoundingBox {
-    /// Left x coordinate.
-    pub x: f64,
-    /// Top y coordinate.
-    pub y: f64,
-    /// Width.
-    pub width: f64,
-    /// Height.
-    pub height: f64,
-}
-
-impl BoundingBox {
-    /// Create a new bounding box.
-    pub fn new(x: f64, y: f64, width: f64, height: f64) -> Self {
-        Self {
-            x,
-            y,
-            width,
-            height,
-        }
-    }
-
-    /// Get right x coordinate.
-    pub fn right(&self) -> f64 {
-        self.x + self.width
-    }
-
-    /// Get bottom y coordinate.
-    pub fn bottom(&self) -> f64 {
-        self.y + self.height
-    }
-}
+/// Rectangle on a page, in PDF points.
+///
+/// The same type as the node bounding boxes of the data model, so boxes found
+/// by layout analysis end up in the converted document as they are.
+pub use crate::datamodel::BoundingBox;
 
 /// Page dimensions.
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
@@ -0,0 +1,191 @@
+//! Plain text backend implementation
+
+use crate::backend::Backend;
+use crate::datamodel::{
+    DoclingDocument, DocumentMetadata, DocumentNode, InputDocument, NodeType, OffsetMap,
+    SourcePosition,
+};
+use crate::error::ConversionError;
+use crate::InputFormat;
+
+/// Maximum length of a line that can be promoted to a heading
+const MAX_HEADING_LEN: usize = 80;
+
+/// Plain text backend
+///
+/// Splits the input on blank lines into paragraphs. When heading inference is
+/// enabled (the default), single ALL-CAPS lines and lines underlined with
+/// `===` or `---` become headings.
+pub struct TextBackend {
+    infer_headings: bool,
+}
+
+impl TextBackend {
+    /// Create a new plain text backend with heading inference enabled
+    pub fn new() -> Self {
+        Self {
+            infer_headings: true,
+        }
+    }
+
+    /// Create a new plain text backend with custom heading inference setting
+    pub fn with_infer_headings(infer: bool) -> Self {
+        Self {
+            infer_headings: infer,
+        }
+    }
+
+    fn get_content(input: &InputDocument) -> Result<String, ConversionError> {
+        // Plain text files (logs in particular) are often not clean UTF-8,
+        // so invalid sequences are replaced rather than rejected
+        match input.source() {
+            crate::datamodel::DocumentSource::FilePath(path)
+            | crate::datamodel::DocumentSource::Remote { path, .. } => std::fs::read(path)
+                .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+                .map_err(ConversionError::Io),
+            crate::datamodel::DocumentSource::Bytes { data, .. } => {
+                Ok(String::from_utf8_lossy(data).into_owned())
+            }
+        }
+    }
+
+    /// Parse text content into document nodes
+    fn parse(&self, content: &str) -> Vec<DocumentNode> {
+        let mut nodes = Vec::new();
+        let mut block: Vec<Line> = Vec::new();
+        let mut offset = 0;
+
+        for (line_no, raw) in content.split_inclusive('\n').enumerate() {
+            let text = raw.trim_end_matches(['\n', '\r']);
+            if text.trim().is_empty() {
+                self.flush_block(&mut block, &mut nodes);
+            } else {
+                block.push(Line {
+                    text,
+                    start: offset,
+                    end: offset + text.len(),
+                    line: line_no + 1,
+                });
+            }
+            offset += raw.len();
+        }
+        self.flush_block(&mut block, &mut nodes);
+
+        nodes
+    }
+
+    /// Turn a block of consecutive non-blank lines into nodes
+    fn flush_block(&self, block: &mut Vec<Line>, nodes: &mut Vec<DocumentNode>) {
+        if block.is_empty() {
+            return;
+        }
+
+        let mut lines: &[Line] = block;
+
+        if self.infer_headings {
+            // Setext-style heading: a line followed by an underline
+            if lines.len() >= 2 && is_underline(lines[1].text) {
+                // `===` marks a top-level heading, `---` a second-level one
+                let level = if lines[1].text.trim().starts_with('=') {
+                    1
+                } else {
+                    2
+                };
+                nodes.push(make_node(NodeType::Heading, &lines[..1]).with_level(level));
+                lines = &lines[2..];
+            } else if lines.len() == 1 && is_caps_heading(lines[0].text) {
+                nodes.push(make_node(NodeType::Heading, lines).with_level(1));
+                lines = &[];
+            }
+        }
+
+        if !lines.is_empty() {
+            nodes.push(make_node(NodeType::Paragraph, lines));
+        }
+
+        block.clear();
+    }
+}
+
+impl Default for TextBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Backend for TextBackend {
+    fn convert(&self, input: &InputDocument) -> Result<DoclingDocument, ConversionError> {
+        let content = Self::get_content(input)?;
+
+        // Get document name from input
+        let name = match input.source() {
+            crate::datamodel::DocumentSource::FilePath(path)
+            | crate::datamodel::DocumentSource::Remote { path, .. } => path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("unknown")
+                .to_string(),
+            crate::datamodel::DocumentSource::Bytes { name, .. } => name.clone(),
+        };
+
+        Ok(DoclingDocument::new(name)
+            .with_document_metadata(DocumentMetadata::from_input(input))
+            .with_nodes(self.parse(&content)))
+    }
+
+    fn supports_format(&self, format: InputFormat) -> bool {
+        format == InputFormat::Text
+    }
+}
+
+/// A non-blank source line with its byte range and 1-based line number
+struct Line<'a> {
+    text: &'a str,
+    start: usize,
+    end: usize,
+    line: usize,
+}
+
+/// Build a node spanning the given lines, preserving line breaks
+fn make_node(node_type: NodeType, lines: &[Line]) -> DocumentNode {
+    let text = lines
+        .iter()
+        .map(|l| l.text.trim())
+        .collect::<Vec<_>>()
+        .join("\n");
+    let first = &lines[0];
+    let last = &lines[lines.len() - 1];
+    // Trimmed lines joined with `\n` drift from the source offsets
+    let offsets = OffsetMap::from_lines(
+        lines.iter().map(|l| {
+            let trimmed = l.text.trim();
+            let indent = trimmed.as_ptr() as usize - l.text.as_ptr() as usize;
+            (l.start + indent, trimmed.len())
+        }),
+        1,
+    );
+
+    DocumentNode::new(node_type, text)
+        .with_position(SourcePosition::new(
+            first.start,
+            last.end,
+            first.line,
+            last.line,
+        ))
+        .with_offsets(offsets)
+}
+
+/// Check whether a line consists only of `=` or `-` (at least 3)
+fn is_underline(line: &str) -> bool {
+    let trimmed = line.trim();
+    trimmed.len() >= 3 && (trimmed.chars().all(|c| c == '=') || trimmed.chars().all(|c| c == '-'))
+}
+
+/// Check whether a line looks like an ALL-CAPS heading
+fn is_caps_heading(line: &str) -> bool {
+    let trimmed = line.trim();
+    trimmed.len() <= MAX_HEADING_LEN
+        && !trimmed.ends_with('.')
+        && trimmed.chars().any(|c| c.is_alphabetic())
+        && !trimmed.chars().any(|c| c.is_lowercase())
+}
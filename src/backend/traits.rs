@@ -1,7 +1,8 @@
 //! Backend trait definitions
 
-use crate::datamodel::{DoclingDocument, InputDocument};
+use crate::datamodel::{DoclingDocument, InputDocument, NodeType};
 use crate::error::ConversionError;
+use crate::pipeline::{LimitGuard, ProgressObserver};
 use crate::InputFormat;
 
 /// Backend trait for document conversion
@@ -9,6 +10,61 @@ pub trait Backend {
     /// Convert an input document to a DoclingDocument
     fn convert(&self, input: &InputDocument) -> Result<DoclingDocument, ConversionError>;
 
+    /// Convert an input document, reporting progress as it goes
+    ///
+    /// Backends that work in steps, such as pages, override this to report
+    /// each step; the default converts in one go without reporting anything.
+    fn convert_with_progress(
+        &self,
+        input: &InputDocument,
+        progress: &dyn ProgressObserver,
+    ) -> Result<DoclingDocument, ConversionError> {
+        let _ = progress;
+        self.convert(input)
+    }
+
+    /// Convert an input document under resource limits, reporting progress
+    ///
+    /// The pipeline checks `limits` on the converted document; backends that
+    /// work in steps override this to check them between steps and give up
+    /// early. The default converts with [`convert_with_progress`](Self::convert_with_progress).
+    fn convert_with_limits(
+        &self,
+        input: &InputDocument,
+        progress: &dyn ProgressObserver,
+        limits: &LimitGuard,
+    ) -> Result<DoclingDocument, ConversionError> {
+        let _ = limits;
+        self.convert_with_progress(input, progress)
+    }
+
+    /// Convert an input document, handing it to `emit` in parts
+    ///
+    /// Backends that can produce parts while parsing, such as pages,
+    /// override this to keep memory low. The default converts the whole
+    /// document, then emits it one section at a time, split at the
+    /// shallowest heading level. See [`stream`](crate::pipeline::stream).
+    fn convert_streaming(
+        &self,
+        input: &InputDocument,
+        progress: &dyn ProgressObserver,
+        limits: &LimitGuard,
+        emit: &mut dyn FnMut(DoclingDocument) -> Result<(), ConversionError>,
+    ) -> Result<(), ConversionError> {
+        let doc = self.convert_with_limits(input, progress, limits)?;
+        let level = doc
+            .nodes()
+            .iter()
+            .filter(|node| node.node_type() == NodeType::Heading)
+            .filter_map(|node| node.level())
+            .min()
+            .unwrap_or(1);
+        for part in doc.split_sections(level) {
+            emit(part)?;
+        }
+        Ok(())
+    }
+
     /// Check if this backend supports the given format
     fn supports_format(&self, format: InputFormat) -> bool;
 }
@@ -1,40 +1,102 @@
 //! docling-rs CLI binary
 //!
-//! Command-line interface for document conversion.
+//! Command-line interface for document conversion. See
+//! [`exit`](docling_rs::cli::exit) for its exit codes.
 
-use clap::Parser;
-use docling_rs::cli::{CliArgs, Converter};
+use clap::FromArgMatches;
+use docling_rs::cli::exit::ExitCode;
+use docling_rs::cli::{bench, completions, logging, mcp, serve, CliArgs, Converter};
 use std::process;
 
+/// Exit for a clap error: 0 for --help and --version, invalid arguments
+/// otherwise
+fn exit_on_clap_error(e: clap::Error) -> ! {
+    // Handle --version and --help which should exit successfully
+    if e.kind() == clap::error::ErrorKind::DisplayHelp
+        || e.kind() == clap::error::ErrorKind::DisplayVersion
+    {
+        print!("{}", e);
+        process::exit(ExitCode::Success.code());
+    }
+    eprintln!("{}", e);
+    process::exit(ExitCode::InvalidArguments.code());
+}
+
+/// Exit with the code of a subcommand run
+fn exit_with(outcome: anyhow::Result<()>) -> ! {
+    if let Err(e) = outcome {
+        eprintln!("Error: {:#}", e);
+        process::exit(ExitCode::of(&e).code());
+    }
+    process::exit(ExitCode::Success.code());
+}
+
 fn main() {
     // Parse CLI arguments, handle clap errors to return exit code 1
-    let args = match CliArgs::try_parse() {
-        Ok(args) => args,
-        Err(e) => {
-            // Handle --version and --help which should exit successfully
-            if e.kind() == clap::error::ErrorKind::DisplayHelp
-                || e.kind() == clap::error::ErrorKind::DisplayVersion
-            {
-                print!("{}", e);
-                process::exit(0);
-            }
-            // Print clap error and exit with code 1 (not 2)
-            eprintln!("{}", e);
-            process::exit(1);
+    let matches = match completions::command().try_get_matches() {
+        Ok(matches) => matches,
+        Err(e) => exit_on_clap_error(e),
+    };
+    if let Some(shell) = completions::requested_shell(&matches) {
+        if let Err(e) = completions::generate(shell, &mut std::io::stdout()) {
+            eprintln!("Error: {}", e);
+            process::exit(ExitCode::ConversionFailed.code());
         }
+        process::exit(ExitCode::Success.code());
+    }
+    if let Some(serve) = serve::requested(&matches) {
+        let serve = match serve {
+            Ok(serve) => serve,
+            Err(e) => exit_on_clap_error(e),
+        };
+        logging::init(serve.log_level, serve.log_format, None);
+        exit_with(serve::run(&serve));
+    }
+    if let Some(mcp) = mcp::requested(&matches) {
+        let mcp = match mcp {
+            Ok(mcp) => mcp,
+            Err(e) => exit_on_clap_error(e),
+        };
+        // stdout carries the protocol, log messages go to stderr
+        logging::init(mcp.log_level, mcp.log_format, None);
+        exit_with(mcp::run(&mcp));
+    }
+    if let Some(bench) = bench::requested(&matches) {
+        let bench = match bench {
+            Ok(bench) => bench,
+            Err(e) => exit_on_clap_error(e),
+        };
+        logging::init(bench.log_level, bench.log_format, None);
+        exit_with(bench::run(&bench));
+    }
+    let mut args = match CliArgs::from_arg_matches(&matches) {
+        Ok(args) => args,
+        Err(e) => exit_on_clap_error(e),
     };
 
+    // Fill options not given on the command line from the config files
+    let applied = args.load_config().and_then(|config| match config {
+        Some(config) => args.apply_config(&config, &matches),
+        None => Ok(()),
+    });
+    if let Err(e) = applied {
+        eprintln!("Error: {}", e);
+        process::exit(ExitCode::InvalidArguments.code());
+    }
+
     // Create converter
     let converter = Converter::new(args);
+    converter.init_logging();
 
     // Run conversion
     match converter.run() {
         Ok(()) => {
-            process::exit(0);
+            process::exit(ExitCode::Success.code());
         }
         Err(e) => {
-            eprintln!("Error: {}", e);
-            process::exit(1);
+            // With its causes, such as the limit an archive went over
+            eprintln!("Error: {:#}", e);
+            process::exit(ExitCode::of(&e).code());
         }
     }
 }
@@ -0,0 +1,106 @@
+//! On-disk cache of conversion results
+//!
+//! A [`ConversionCache`] keeps serialized [`ConversionResult`]s in a
+//! directory, keyed by a hash of the source content, its name and format,
+//! and the converter configuration. A converter with a cache (see
+//! [`DocumentConverter::with_cache`](crate::DocumentConverter::with_cache))
+//! returns the stored result when the same source is converted again with
+//! the same settings, so re-running a pipeline over a mostly unchanged corpus
+//! only converts what changed.
+//!
+//! Entries are never expired; changing the source, the settings or the
+//! docling-rs version produces a new key. Use [`clear`](ConversionCache::clear)
+//! to reclaim space.
+
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use sha2::{Digest, Sha256};
+use tempfile::NamedTempFile;
+
+use crate::datamodel::ConversionResult;
+use crate::error::ConversionError;
+use crate::InputFormat;
+
+/// Directory of cached conversion results
+#[derive(Debug, Clone)]
+pub struct ConversionCache {
+    dir: PathBuf,
+}
+
+impl ConversionCache {
+    /// Use `dir` as the cache directory, created when the first entry is stored
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Get the cache directory
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Key of a source converted with the configuration `config`
+    ///
+    /// `config` is any string describing the settings that affect the result;
+    /// the docling-rs version is always part of the key.
+    pub fn key(&self, content: &[u8], name: &str, format: InputFormat, config: &str) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(Sha256::digest(content));
+        for part in [
+            name,
+            &format!("{:?}", format),
+            config,
+            env!("CARGO_PKG_VERSION"),
+        ] {
+            hasher.update((part.len() as u64).to_le_bytes());
+            hasher.update(part.as_bytes());
+        }
+        format!("{:x}", hasher.finalize())
+    }
+
+    /// Get the result stored under `key`
+    ///
+    /// Missing and unreadable entries are both reported as `None`.
+    pub fn get(&self, key: &str) -> Option<ConversionResult> {
+        let json = fs::read_to_string(self.path(key)).ok()?;
+        serde_json::from_str(&json).ok()
+    }
+
+    /// Store a result under `key`
+    ///
+    /// The entry is written to a uniquely named temporary file first and
+    /// renamed into place, so concurrent readers never see a partial entry and
+    /// concurrent writers, in this process or another, never share a file.
+    pub fn put(&self, key: &str, result: &ConversionResult) -> Result<(), ConversionError> {
+        fs::create_dir_all(&self.dir)?;
+        let mut temp = NamedTempFile::new_in(&self.dir)?;
+        temp.write_all(&serde_json::to_vec(result)?)?;
+        // The temporary file is removed if it cannot be renamed
+        temp.persist(self.path(key))
+            .map_err(|e| ConversionError::Io(e.error))?;
+        Ok(())
+    }
+
+    /// Remove all entries, returning how many were removed
+    pub fn clear(&self) -> Result<usize, ConversionError> {
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+            Err(e) => return Err(e.into()),
+        };
+        let mut removed = 0;
+        for entry in entries {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == "json") {
+                fs::remove_file(path)?;
+                removed += 1;
+            }
+        }
+        Ok(removed)
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", key))
+    }
+}
@@ -2,6 +2,7 @@
 
 use super::metadata::ChunkMetadata;
 use crate::datamodel::DoclingDocument;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 /// Error type for chunking operations
@@ -21,6 +22,16 @@ pub enum ChunkingError {
 
     #[error("Serialization error: {0}")]
     SerializationError(#[from] serde_json::Error),
+
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Sink error: {0}")]
+    Sink(String),
+
+    #[cfg(feature = "parquet")]
+    #[error("Parquet error: {0}")]
+    Parquet(#[from] parquet::errors::ParquetError),
 }
 
 /// A single chunk of document text with metadata
@@ -42,10 +53,11 @@ pub enum ChunkingError {
 ///         start_offset: 0,
 ///         end_offset: 20,
 ///         index: 0,
+///         ..Default::default()
 ///     },
 /// };
 /// ```
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct BaseChunk {
     /// The text content of this chunk
     pub text: String,
@@ -54,6 +66,15 @@ pub struct BaseChunk {
     pub meta: ChunkMetadata,
 }
 
+impl BaseChunk {
+    /// Set `meta.chunk_id` from the chunk's current content
+    pub fn with_chunk_id(mut self) -> Self {
+        self.meta.chunk_id =
+            super::metadata::chunk_id(&self.meta.doc_name, &self.meta.headings, &self.text);
+        self
+    }
+}
+
 /// Abstract interface for all chunking strategies
 ///
 /// This trait defines the contract that all chunking implementations must follow.
@@ -67,7 +88,11 @@ pub struct BaseChunk {
 /// - Iterator is lazy (no upfront allocation)
 /// - `contextualize()` is deterministic
 /// - All document content is represented in chunks
-pub trait BaseChunker {
+/// - `meta.chunk_id` is set from the final chunk text (see [`BaseChunk::with_chunk_id`])
+///
+/// Chunkers are shared across threads, like converters, so they must be
+/// `Send` and `Sync`.
+pub trait BaseChunker: Send + Sync {
     /// Generate chunks from a document
     ///
     /// Returns a lazy iterator to avoid loading all chunks into memory.
@@ -97,3 +122,13 @@ pub trait BaseChunker {
     /// A string with metadata-prefixed chunk text
     fn contextualize(&self, chunk: &BaseChunk) -> String;
 }
+
+impl<T: BaseChunker + ?Sized> BaseChunker for Box<T> {
+    fn chunk<'a>(&'a self, doc: &'a DoclingDocument) -> Box<dyn Iterator<Item = BaseChunk> + 'a> {
+        (**self).chunk(doc)
+    }
+
+    fn contextualize(&self, chunk: &BaseChunk) -> String {
+        (**self).contextualize(chunk)
+    }
+}
@@ -0,0 +1,83 @@
+//! Chunk export for embedding pipelines
+
+use super::base::{BaseChunk, BaseChunker, ChunkingError};
+use super::metadata::ChunkMetadata;
+use super::tokenizer::Tokenizer;
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+
+/// A chunk prepared for embedding
+///
+/// Carries both the raw chunk text and the contextualized text (headings and
+/// caption prepended) that is normally sent to the embedding model, along with
+/// the token count of the contextualized text.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChunkRecord {
+    /// Raw chunk text
+    pub text: String,
+
+    /// Text with hierarchical context, as produced by `BaseChunker::contextualize`
+    pub contextualized_text: String,
+
+    /// Token count of `contextualized_text`
+    pub token_count: usize,
+
+    /// Chunk metadata
+    pub meta: ChunkMetadata,
+
+    /// Embedding of `contextualized_text`, once computed (see
+    /// [`embeddings`](crate::embeddings))
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub embedding: Option<Vec<f32>>,
+}
+
+impl ChunkRecord {
+    /// Build a record for `chunk`, contextualized by `chunker`
+    pub fn new(chunk: BaseChunk, chunker: &dyn BaseChunker, tokenizer: &dyn Tokenizer) -> Self {
+        let contextualized_text = chunker.contextualize(&chunk);
+        Self {
+            token_count: tokenizer.count_tokens(&contextualized_text),
+            contextualized_text,
+            text: chunk.text,
+            meta: chunk.meta,
+            embedding: None,
+        }
+    }
+}
+
+/// Write chunks as JSON Lines, one [`ChunkRecord`] per line
+///
+/// # Examples
+///
+/// ```ignore
+/// use docling_rs::chunking::{export, ApproxTokenizer, BaseChunker, HierarchicalChunker};
+///
+/// let chunker = HierarchicalChunker::new();
+/// let mut out = std::fs::File::create("chunks.jsonl")?;
+/// export::write_jsonl(&mut out, chunker.chunk(&doc), &chunker, &ApproxTokenizer::new())?;
+/// ```
+pub fn write_jsonl<W: Write>(
+    writer: &mut W,
+    chunks: impl IntoIterator<Item = BaseChunk>,
+    chunker: &dyn BaseChunker,
+    tokenizer: &dyn Tokenizer,
+) -> Result<(), ChunkingError> {
+    for chunk in chunks {
+        let record = ChunkRecord::new(chunk, chunker, tokenizer);
+        serde_json::to_writer(&mut *writer, &record)?;
+        writer.write_all(b"\n")?;
+    }
+    Ok(())
+}
+
+/// Render chunks as a JSON Lines string
+pub fn to_jsonl(
+    chunks: impl IntoIterator<Item = BaseChunk>,
+    chunker: &dyn BaseChunker,
+    tokenizer: &dyn Tokenizer,
+) -> Result<String, ChunkingError> {
+    let mut buffer = Vec::new();
+    write_jsonl(&mut buffer, chunks, chunker, tokenizer)?;
+    // serde_json only emits valid UTF-8
+    Ok(String::from_utf8_lossy(&buffer).into_owned())
+}
@@ -0,0 +1,217 @@
+//! Chunk filtering and postprocessing
+
+use super::base::{BaseChunk, BaseChunker, ChunkingError};
+use crate::datamodel::DoclingDocument;
+use regex::Regex;
+
+/// A postprocessing step applied to each chunk
+///
+/// Returning `None` drops the chunk; returning `Some` keeps it, possibly with
+/// modified text. Closures of type `Fn(BaseChunk) -> Option<BaseChunk>` implement
+/// this trait.
+pub trait ChunkFilter: Send + Sync {
+    /// Transform or drop a chunk
+    fn apply(&self, chunk: BaseChunk) -> Option<BaseChunk>;
+}
+
+impl<F> ChunkFilter for F
+where
+    F: Fn(BaseChunk) -> Option<BaseChunk> + Send + Sync,
+{
+    fn apply(&self, chunk: BaseChunk) -> Option<BaseChunk> {
+        self(chunk)
+    }
+}
+
+/// Drops chunks with fewer than `n` non-whitespace characters
+#[derive(Debug, Clone)]
+pub struct MinLength(pub usize);
+
+impl ChunkFilter for MinLength {
+    fn apply(&self, chunk: BaseChunk) -> Option<BaseChunk> {
+        let len = chunk.text.chars().filter(|c| !c.is_whitespace()).count();
+        (len >= self.0).then_some(chunk)
+    }
+}
+
+/// Drops chunks whose text matches any of the patterns
+#[derive(Debug, Clone)]
+pub struct DropPatterns(Vec<Regex>);
+
+impl DropPatterns {
+    /// Compile the given regular expressions
+    pub fn new<I, S>(patterns: I) -> Result<Self, ChunkingError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        patterns
+            .into_iter()
+            .map(|p| {
+                Regex::new(p.as_ref()).map_err(|e| {
+                    ChunkingError::InvalidConfig(format!("invalid pattern '{}': {}", p.as_ref(), e))
+                })
+            })
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+}
+
+impl ChunkFilter for DropPatterns {
+    fn apply(&self, chunk: BaseChunk) -> Option<BaseChunk> {
+        (!self.0.iter().any(|re| re.is_match(&chunk.text))).then_some(chunk)
+    }
+}
+
+/// Removes common boilerplate lines and drops chunks left empty
+///
+/// Removed lines: bare page numbers (`12`, `- 12 -`, `Page 3 of 10`), copyright
+/// notices and "all rights reserved" lines.
+#[derive(Debug, Clone)]
+pub struct RemoveBoilerplate {
+    patterns: Vec<Regex>,
+}
+
+impl RemoveBoilerplate {
+    /// Create a filter with the default boilerplate patterns
+    pub fn new() -> Self {
+        let patterns = [
+            r"^[-–—\s]*\d{1,4}[-–—\s]*$",
+            r"(?i)^page\s+\d+(\s+of\s+\d+)?$",
+            r"(?i)^(copyright\s|©|\(c\)\s).*",
+            r"(?i)^all rights reserved\.?$",
+        ];
+        Self {
+            patterns: patterns.iter().filter_map(|p| Regex::new(p).ok()).collect(),
+        }
+    }
+
+    fn is_boilerplate(&self, line: &str) -> bool {
+        let line = line.trim();
+        !line.is_empty() && self.patterns.iter().any(|re| re.is_match(line))
+    }
+}
+
+impl Default for RemoveBoilerplate {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ChunkFilter for RemoveBoilerplate {
+    fn apply(&self, mut chunk: BaseChunk) -> Option<BaseChunk> {
+        if chunk.text.lines().any(|line| self.is_boilerplate(line)) {
+            chunk.text = chunk
+                .text
+                .lines()
+                .filter(|line| !self.is_boilerplate(line))
+                .collect::<Vec<_>>()
+                .join("\n")
+                .trim()
+                .to_string();
+        }
+        (!chunk.text.trim().is_empty()).then_some(chunk)
+    }
+}
+
+/// Wraps any chunker with a pipeline of filters
+///
+/// Filters run in the order they were added, before chunks are yielded. Surviving
+/// chunks are re-indexed and their `chunk_id` is recomputed from the final text.
+///
+/// # Examples
+///
+/// ```ignore
+/// use docling_rs::chunking::{BaseChunker, ChunkerExt, HierarchicalChunker};
+///
+/// let chunker = HierarchicalChunker::new()
+///     .filtered()
+///     .remove_boilerplate()
+///     .min_length(20)
+///     .drop_patterns(["(?i)^table of contents"])?
+///     .with_filter(|mut chunk: BaseChunk| {
+///         chunk.text = chunk.text.replace('\u{00ad}', "");
+///         Some(chunk)
+///     });
+///
+/// let chunks: Vec<_> = chunker.chunk(&doc).collect();
+/// ```
+pub struct FilteredChunker<C> {
+    inner: C,
+    filters: Vec<Box<dyn ChunkFilter>>,
+}
+
+impl<C: BaseChunker> FilteredChunker<C> {
+    /// Wrap a chunker with no filters
+    pub fn new(inner: C) -> Self {
+        Self {
+            inner,
+            filters: Vec::new(),
+        }
+    }
+
+    /// Append a filter
+    pub fn with_filter(mut self, filter: impl ChunkFilter + 'static) -> Self {
+        self.filters.push(Box::new(filter));
+        self
+    }
+
+    /// Drop chunks with fewer than `n` non-whitespace characters
+    pub fn min_length(self, n: usize) -> Self {
+        self.with_filter(MinLength(n))
+    }
+
+    /// Drop chunks matching any of the regular expressions
+    pub fn drop_patterns<I, S>(self, patterns: I) -> Result<Self, ChunkingError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Ok(self.with_filter(DropPatterns::new(patterns)?))
+    }
+
+    /// Remove page numbers, copyright lines and similar boilerplate
+    pub fn remove_boilerplate(self) -> Self {
+        self.with_filter(RemoveBoilerplate::new())
+    }
+
+    /// The wrapped chunker
+    pub fn inner(&self) -> &C {
+        &self.inner
+    }
+}
+
+impl<C: BaseChunker> BaseChunker for FilteredChunker<C> {
+    fn chunk<'a>(&'a self, doc: &'a DoclingDocument) -> Box<dyn Iterator<Item = BaseChunk> + 'a> {
+        let chunks = self
+            .inner
+            .chunk(doc)
+            .filter_map(move |chunk| {
+                self.filters
+                    .iter()
+                    .try_fold(chunk, |chunk, filter| filter.apply(chunk))
+            })
+            .enumerate()
+            .map(|(i, mut chunk)| {
+                chunk.meta.index = i;
+                // Filters may have rewritten the text
+                chunk.with_chunk_id()
+            });
+
+        Box::new(chunks)
+    }
+
+    fn contextualize(&self, chunk: &BaseChunk) -> String {
+        self.inner.contextualize(chunk)
+    }
+}
+
+/// Adds [`FilteredChunker`] construction to every chunker
+pub trait ChunkerExt: BaseChunker + Sized {
+    /// Wrap this chunker in a [`FilteredChunker`]
+    fn filtered(self) -> FilteredChunker<Self> {
+        FilteredChunker::new(self)
+    }
+}
+
+impl<C: BaseChunker> ChunkerExt for C {}
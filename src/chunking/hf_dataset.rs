@@ -0,0 +1,282 @@
+//! Hugging Face datasets export
+//!
+//! Writes the chunks of any number of documents as a dataset that
+//! `datasets.load_dataset` reads as is, for fine-tuning or evaluation:
+//!
+//! ```text
+//! corpus/
+//! ├── dataset_infos.json
+//! └── data/
+//!     ├── train-00000-of-00002.parquet
+//!     └── train-00001-of-00002.parquet
+//! ```
+//!
+//! ```python
+//! from datasets import load_dataset
+//!
+//! dataset = load_dataset("corpus", split="train")
+//! ```
+//!
+//! Rows have the columns of the [Parquet export](super::parquet). Every
+//! shard carries their `datasets` features in its schema, so `headings`
+//! loads as a sequence of strings and `page` as `uint32`, and
+//! `dataset_infos.json` records the features and the size of each split.
+//! Requires the `parquet` feature.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use arrow_schema::SchemaRef;
+use serde_json::{json, Map, Value};
+use tempfile::TempPath;
+
+use super::base::{BaseChunk, BaseChunker, ChunkingError};
+use super::export::ChunkRecord;
+use super::parquet::{schema, write_rows, Row};
+use super::tokenizer::Tokenizer;
+
+/// Name of the file describing the dataset
+const INFOS: &str = "dataset_infos.json";
+
+/// Directory of the shards
+const DATA: &str = "data";
+
+/// Name of the only configuration of the dataset
+const CONFIG: &str = "default";
+
+/// Default number of rows per shard
+const DEFAULT_SHARD_ROWS: usize = 100_000;
+
+/// Writes chunks into a Hugging Face dataset directory
+///
+/// Rows are buffered and written a shard at a time; shards get their final
+/// `<split>-00000-of-00002.parquet` names, replacing those of an earlier
+/// export of the same split, and `dataset_infos.json` is written when the
+/// writer is [finished](DatasetWriter::finish). Other splits written to the
+/// same directory are kept, so train and test sets can come from separate
+/// runs.
+///
+/// ```ignore
+/// use docling_rs::chunking::hf_dataset::DatasetWriter;
+///
+/// let mut dataset = DatasetWriter::create("corpus")?;
+/// for doc in &docs {
+///     dataset.write_chunks(chunker.chunk(doc), &chunker, &tokenizer)?;
+/// }
+/// let rows = dataset.finish()?;
+/// ```
+pub struct DatasetWriter {
+    dir: PathBuf,
+    split: String,
+    shard_rows: usize,
+    /// Rows of the shard being filled
+    rows: Vec<Row>,
+    /// Shards written so far, under temporary names
+    shards: Vec<TempPath>,
+    num_examples: usize,
+    num_bytes: u64,
+}
+
+impl DatasetWriter {
+    /// Start a dataset in `dir`, creating it if `dir` does not exist or is
+    /// empty
+    ///
+    /// Fails on directories holding other files and no `dataset_infos.json`,
+    /// so shards are never mixed into an unrelated directory.
+    pub fn create(dir: impl Into<PathBuf>) -> Result<Self, ChunkingError> {
+        let dir = dir.into();
+        if !dir.join(INFOS).exists() && dir.exists() && fs::read_dir(&dir)?.next().is_some() {
+            return Err(ChunkingError::Sink(format!(
+                "{} is not empty and holds no {}, so it is not a dataset",
+                dir.display(),
+                INFOS
+            )));
+        }
+        fs::create_dir_all(dir.join(DATA))?;
+        Ok(Self {
+            dir,
+            split: "train".to_string(),
+            shard_rows: DEFAULT_SHARD_ROWS,
+            rows: Vec::new(),
+            shards: Vec::new(),
+            num_examples: 0,
+            num_bytes: 0,
+        })
+    }
+
+    /// Write rows to the split `split` (default: `train`)
+    pub fn with_split(mut self, split: impl Into<String>) -> Self {
+        self.split = split.into();
+        self
+    }
+
+    /// Put at most `rows` rows in each shard (default: 100,000)
+    pub fn with_shard_rows(mut self, rows: usize) -> Self {
+        self.shard_rows = rows.max(1);
+        self
+    }
+
+    /// Directory of the dataset
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Number of rows written so far
+    pub fn len(&self) -> usize {
+        self.num_examples + self.rows.len()
+    }
+
+    /// Whether no row was written
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Add one row per chunk, with token counts of the contextualized text
+    /// as in [`ChunkRecord`]
+    pub fn write_chunks(
+        &mut self,
+        chunks: impl IntoIterator<Item = BaseChunk>,
+        chunker: &dyn BaseChunker,
+        tokenizer: &dyn Tokenizer,
+    ) -> Result<(), ChunkingError> {
+        for chunk in chunks {
+            let row = Row::from_record(ChunkRecord::new(chunk, chunker, tokenizer));
+            self.num_bytes += row.num_bytes();
+            self.rows.push(row);
+            if self.rows.len() == self.shard_rows {
+                self.write_shard()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Write the last shard, name the shards and describe the dataset,
+    /// returning the number of rows of the split
+    ///
+    /// A split without rows still gets one empty shard, so the dataset
+    /// loads with its features.
+    pub fn finish(mut self) -> Result<usize, ChunkingError> {
+        if !self.rows.is_empty() || self.shards.is_empty() {
+            self.write_shard()?;
+        }
+
+        let data = self.dir.join(DATA);
+        let prefix = format!("{}-", self.split);
+        for entry in fs::read_dir(&data)? {
+            let path = entry?.path();
+            let earlier = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with(&prefix) && name.ends_with(".parquet"));
+            if earlier {
+                fs::remove_file(path)?;
+            }
+        }
+        let total = self.shards.len();
+        for (i, shard) in std::mem::take(&mut self.shards).into_iter().enumerate() {
+            let path = data.join(format!("{}-{:05}-of-{:05}.parquet", self.split, i, total));
+            shard.persist(path).map_err(|e| e.error)?;
+        }
+
+        self.write_infos()?;
+        Ok(self.num_examples)
+    }
+
+    /// Write the buffered rows to a new shard
+    fn write_shard(&mut self) -> Result<(), ChunkingError> {
+        let (file, path) = tempfile::Builder::new()
+            .prefix(".shard-")
+            .suffix(".parquet")
+            .tempfile_in(self.dir.join(DATA))?
+            .into_parts();
+        self.num_examples += self.rows.len();
+        write_rows(file, hf_schema(), self.rows.drain(..))?;
+        self.shards.push(path);
+        Ok(())
+    }
+
+    /// Update `dataset_infos.json` with the split written
+    fn write_infos(&self) -> Result<(), ChunkingError> {
+        let path = self.dir.join(INFOS);
+        let mut infos: Map<String, Value> = match fs::read(&path) {
+            Ok(content) => serde_json::from_slice(&content)?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Map::new(),
+            Err(e) => return Err(e.into()),
+        };
+        let info = infos
+            .entry(CONFIG)
+            .or_insert_with(|| json!({}))
+            .as_object_mut()
+            .ok_or_else(|| ChunkingError::Sink(format!("{} is not valid", path.display())))?;
+
+        let mut splits = match info.remove("splits") {
+            Some(Value::Object(splits)) => splits,
+            _ => Map::new(),
+        };
+        splits.insert(
+            self.split.clone(),
+            json!({
+                "name": self.split,
+                "num_bytes": self.num_bytes,
+                "num_examples": self.num_examples,
+                "dataset_name": null,
+            }),
+        );
+        let dataset_size: u64 = splits
+            .values()
+            .filter_map(|split| split["num_bytes"].as_u64())
+            .sum();
+        let mut download_size = 0;
+        for entry in fs::read_dir(self.dir.join(DATA))? {
+            let entry = entry?;
+            if entry.path().extension().and_then(|e| e.to_str()) == Some("parquet") {
+                download_size += entry.metadata()?.len();
+            }
+        }
+
+        info.insert("description".to_string(), "".into());
+        info.insert("citation".to_string(), "".into());
+        info.insert("homepage".to_string(), "".into());
+        info.insert("license".to_string(), "".into());
+        info.insert("features".to_string(), features());
+        info.insert("builder_name".to_string(), "parquet".into());
+        info.insert("config_name".to_string(), CONFIG.into());
+        info.insert("splits".to_string(), Value::Object(splits));
+        info.insert("download_size".to_string(), download_size.into());
+        info.insert("dataset_size".to_string(), dataset_size.into());
+        info.insert(
+            "size_in_bytes".to_string(),
+            (download_size + dataset_size).into(),
+        );
+
+        let mut temp = tempfile::NamedTempFile::new_in(&self.dir)?;
+        serde_json::to_writer_pretty(&mut temp, &infos)?;
+        temp.persist(path).map_err(|e| e.error)?;
+        Ok(())
+    }
+}
+
+/// `datasets` features of the columns
+fn features() -> Value {
+    let value = |dtype: &str| json!({ "dtype": dtype, "_type": "Value" });
+    json!({
+        "id": value("string"),
+        "document": value("string"),
+        "text": value("string"),
+        "headings": { "feature": value("string"), "_type": "Sequence" },
+        "page": value("uint32"),
+        "token_count": value("uint64"),
+    })
+}
+
+/// The Parquet schema, with the features `datasets` reads from shards
+fn hf_schema() -> SchemaRef {
+    let metadata = json!({ "info": { "features": features() } });
+    let schema = schema().as_ref().clone().with_metadata(HashMap::from([(
+        "huggingface".to_string(),
+        metadata.to_string(),
+    )]));
+    Arc::new(schema)
+}
@@ -1,14 +1,21 @@
 //! Hierarchical (structure-based) chunker implementation
 
 use super::base::{BaseChunk, BaseChunker};
-use super::metadata::ChunkMetadata;
-use crate::datamodel::DoclingDocument;
+use super::metadata::{ChunkMetadata, PageBoundingBox};
+use crate::datamodel::{DoclingDocument, NodeType};
+use std::collections::HashMap;
 
 /// Creates chunks based on document structure elements
 ///
 /// `HierarchicalChunker` preserves the hierarchical structure of the document by creating
-/// one chunk per document element (paragraph, section, table, etc.). It maintains metadata
-/// about headings and hierarchical context.
+/// one chunk per document element (paragraph, section, table, etc.). Headings do not
+/// produce chunks of their own; instead the active heading path (e.g.
+/// `["Chapter 1", "Section 1.1"]`) is recorded in each chunk's metadata. Captions of
+/// tables and figures are carried into the chunk's `caption` and included by
+/// [`contextualize`](BaseChunker::contextualize). A table or figure without a
+/// caption of its own takes the `Caption` node next to it (before a table,
+/// after a figure, as documents usually place them), which then is not a
+/// chunk of its own.
 ///
 /// # Examples
 ///
@@ -62,71 +69,109 @@ impl Default for HierarchicalChunker {
 impl BaseChunker for HierarchicalChunker {
     fn chunk<'a>(&'a self, doc: &'a DoclingDocument) -> Box<dyn Iterator<Item = BaseChunk> + 'a> {
         let doc_name = doc.name().to_string();
-        let nodes = doc.nodes().to_vec();
 
-        // Create chunks from nodes that have text content
+        // Create chunks from nodes that have text content, borrowing the nodes
+        // and building each chunk as it is pulled
         // Track current offset for sequential positioning
         let mut current_offset = 0;
         let mut chunk_index = 0;
 
-        let chunks: Vec<BaseChunk> = nodes
-            .into_iter()
-            .filter_map(|node| {
-                // Extract text content from node
-                let text = node.text_content()?.to_string();
-
-                // Skip empty text nodes
-                if text.trim().is_empty() {
-                    return None;
-                }
-
-                // Extract position if available, otherwise calculate sequential offsets
-                let (start_offset, end_offset) = if let Some(pos) = node.position() {
-                    let start = pos.start_offset();
-                    let end = pos.end_offset();
-                    current_offset = end;
-                    (start, end)
-                } else {
-                    let start = current_offset;
-                    let end = current_offset + text.len();
-                    current_offset = end + 1; // +1 for newline/separator
-                    (start, end)
-                };
-
-                let chunk = BaseChunk {
-                    text,
-                    meta: ChunkMetadata {
-                        doc_name: doc_name.clone(),
-                        headings: vec![],
-                        caption: None,
-                        start_offset,
-                        end_offset,
-                        index: chunk_index,
-                    },
-                };
-
-                chunk_index += 1;
-                Some(chunk)
-            })
-            .collect();
-
-        Box::new(chunks.into_iter())
+        // Active heading ancestry as (level, text), outermost first
+        let mut heading_stack: Vec<(usize, String)> = Vec::new();
+
+        // Caption nodes folded into the table or figure next to them
+        let captions = sibling_captions(doc);
+
+        Box::new(
+            doc.nodes()
+                .iter()
+                .enumerate()
+                .filter_map(move |(index, node)| {
+                    if node.node_type() == NodeType::Caption && captions.contains_key(&index) {
+                        return None;
+                    }
+
+                    // Extract text content from node
+                    let text = node.text_content()?.to_string();
+
+                    // Skip empty text nodes
+                    if text.trim().is_empty() {
+                        return None;
+                    }
+
+                    // Extract position if available, otherwise calculate sequential offsets
+                    let (start_offset, end_offset) = if let Some(pos) = node.position() {
+                        let start = pos.start_offset();
+                        let end = pos.end_offset();
+                        current_offset = end;
+                        (start, end)
+                    } else {
+                        let start = current_offset;
+                        let end = current_offset + text.len();
+                        current_offset = end + 1; // +1 for newline/separator
+                        (start, end)
+                    };
+
+                    // Headings and the title update the context of the chunks that
+                    // follow them instead of becoming chunks of their own
+                    if matches!(node.node_type(), NodeType::Heading | NodeType::Title) {
+                        // The title sits above every heading level
+                        let level = match node.node_type() {
+                            NodeType::Title => 0,
+                            _ => node.level().unwrap_or(1),
+                        };
+                        while heading_stack.last().is_some_and(|(l, _)| *l >= level) {
+                            heading_stack.pop();
+                        }
+                        heading_stack.push((level, text));
+                        return None;
+                    }
+
+                    let chunk = BaseChunk {
+                        text,
+                        meta: ChunkMetadata {
+                            doc_name: doc_name.clone(),
+                            headings: heading_stack.iter().map(|(_, h)| h.clone()).collect(),
+                            caption: node
+                                .caption()
+                                .or_else(|| {
+                                    let caption = captions.get(&index)?;
+                                    doc.nodes()[*caption].text_content()
+                                })
+                                .map(str::to_string),
+                            start_offset,
+                            end_offset,
+                            index: chunk_index,
+                            page_start: node.page(),
+                            page_end: node.page(),
+                            is_code: node.node_type() == NodeType::CodeBlock,
+                            continuation: false,
+                            chunk_id: String::new(),
+                            bboxes: node
+                                .page()
+                                .zip(node.bbox())
+                                .map(|(page, bbox)| PageBoundingBox { page, bbox: *bbox })
+                                .into_iter()
+                                .collect(),
+                            languages: node.language().map(str::to_string).into_iter().collect(),
+                            offsets: node.offsets().cloned(),
+                        },
+                    }
+                    .with_chunk_id();
+
+                    chunk_index += 1;
+                    Some(chunk)
+                }),
+        )
     }
 
     fn contextualize(&self, chunk: &BaseChunk) -> String {
-        let mut result = String::new();
-
-        // Add headings
-        for heading in &chunk.meta.headings {
-            result.push_str(heading);
-            result.push('\n');
-        }
-
-        // Add caption if present
-        if let Some(caption) = &chunk.meta.caption {
-            result.push_str(caption);
-            result.push('\n');
-        }
+        let mut result = String::with_capacity(chunk.text.len() + 64);
+        push_context(
+            &mut result,
+            &chunk.meta.headings,
+            chunk.meta.caption.as_deref(),
+        );
 
         // Add chunk text
         result.push_str(&chunk.text);
@@ -134,3 +179,58 @@ impl BaseChunker for HierarchicalChunker {
         result
     }
 }
+
+/// Append the headings and caption that [`HierarchicalChunker::contextualize`]
+/// places before the text of a chunk, one per line
+pub(super) fn push_context(out: &mut String, headings: &[String], caption: Option<&str>) {
+    // Add headings
+    for heading in headings {
+        out.push_str(heading);
+        out.push('\n');
+    }
+
+    // Add caption if present
+    if let Some(caption) = caption {
+        out.push_str(caption);
+        out.push('\n');
+    }
+}
+
+/// Pair each table or figure without a caption with a `Caption` sibling next to it
+///
+/// Tables take the caption before them first, figures the caption after them.
+/// The map goes both ways: from the table or figure to its caption, and from
+/// the caption to the table or figure.
+fn sibling_captions(doc: &DoclingDocument) -> HashMap<usize, usize> {
+    let nodes = doc.nodes();
+    let mut siblings: HashMap<Option<usize>, Vec<usize>> = HashMap::new();
+    for (index, node) in nodes.iter().enumerate() {
+        siblings
+            .entry(node.parent().map(|parent| parent.index()))
+            .or_default()
+            .push(index);
+    }
+
+    let mut pairs = HashMap::new();
+    for group in siblings.values() {
+        for (k, &index) in group.iter().enumerate() {
+            let node = &nodes[index];
+            let candidates = match node.node_type() {
+                _ if node.caption().is_some() => continue,
+                NodeType::Table => [k.checked_sub(1), Some(k + 1)],
+                NodeType::Picture => [Some(k + 1), k.checked_sub(1)],
+                _ => continue,
+            };
+            let caption = candidates.into_iter().flatten().find_map(|k| {
+                let caption = *group.get(k)?;
+                (nodes[caption].node_type() == NodeType::Caption && !pairs.contains_key(&caption))
+                    .then_some(caption)
+            });
+            if let Some(caption) = caption {
+                pairs.insert(index, caption);
+                pairs.insert(caption, index);
+            }
+        }
+    }
+    pairs
+}
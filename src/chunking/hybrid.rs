@@ -1,10 +1,15 @@
 //! Hybrid (structure + tokenization-aware) chunker implementation
 
 use super::base::{BaseChunk, BaseChunker, ChunkingError};
-use super::hierarchical::HierarchicalChunker;
+use super::hierarchical::{push_context, HierarchicalChunker};
 use super::metadata::ChunkMetadata;
-use super::tokenizer::Tokenizer;
+use super::tokenizer::{ApproxTokenizer, Tokenizer};
 use crate::datamodel::DoclingDocument;
+use rayon::prelude::*;
+use std::ops::Range;
+
+/// Default separator between sections merged across headings
+const DEFAULT_MERGE_SEPARATOR: &str = "\n\n";
 
 /// Advanced chunker combining structure + tokenization awareness
 ///
@@ -13,6 +18,12 @@ use crate::datamodel::DoclingDocument;
 /// 1. **Hierarchical Pass**: Generate initial chunks based on document structure
 /// 2. **Split Pass**: Split oversized chunks that exceed `max_tokens`
 /// 3. **Merge Pass**: Optionally merge undersized peer chunks with same metadata
+///    (passes 2 and 3 can run per section in parallel, see [`HybridChunkerBuilder::parallel`])
+/// 4. **Overlap Pass**: Optionally prefix each chunk with the tail of the previous one
+///
+/// Code chunks are kept atomic: they are never merged with peers or overlapped, and
+/// an oversized code block is split on line boundaries, with every piece after the
+/// first marked as a `continuation`.
 ///
 /// This approach ensures chunks respect both document structure and token limits,
 /// making them ideal for embedding models with context window constraints.
@@ -27,6 +38,7 @@ use crate::datamodel::DoclingDocument;
 ///     .tokenizer(tokenizer)
 ///     .max_tokens(512)
 ///     .merge_peers(true)
+///     .overlap_tokens(32)
 ///     .build()?;
 ///
 /// let chunks: Vec<_> = chunker.chunk(&doc).collect();
@@ -35,6 +47,10 @@ pub struct HybridChunker {
     tokenizer: Box<dyn Tokenizer>,
     max_tokens: usize,
     merge_peers: bool,
+    overlap_tokens: usize,
+    parallel: bool,
+    merge_across_headings: bool,
+    merge_separator: String,
     hierarchical: HierarchicalChunker,
 }
 
@@ -50,12 +66,19 @@ impl HybridChunker {
     /// A `HybridChunker` with:
     /// - `max_tokens` = tokenizer.max_tokens()
     /// - `merge_peers` = true
+    /// - `overlap_tokens` = 0
+    /// - `parallel` = false
+    /// - `merge_across_headings` = false
     pub fn new(tokenizer: Box<dyn Tokenizer>) -> Self {
         let max_tokens = tokenizer.max_tokens();
         Self {
             tokenizer,
             max_tokens,
             merge_peers: true,
+            overlap_tokens: 0,
+            parallel: false,
+            merge_across_headings: false,
+            merge_separator: DEFAULT_MERGE_SEPARATOR.to_string(),
             hierarchical: HierarchicalChunker::new(),
         }
     }
@@ -73,6 +96,10 @@ pub struct HybridChunkerBuilder {
     tokenizer: Option<Box<dyn Tokenizer>>,
     max_tokens: Option<usize>,
     merge_peers: bool,
+    overlap_tokens: usize,
+    parallel: bool,
+    merge_across_headings: bool,
+    merge_separator: String,
 }
 
 impl HybridChunkerBuilder {
@@ -81,10 +108,16 @@ impl HybridChunkerBuilder {
             tokenizer: None,
             max_tokens: None,
             merge_peers: true,
+            overlap_tokens: 0,
+            parallel: false,
+            merge_across_headings: false,
+            merge_separator: DEFAULT_MERGE_SEPARATOR.to_string(),
         }
     }
 
     /// Set the tokenizer for token counting
+    ///
+    /// Defaults to [`ApproxTokenizer`], which needs no model files.
     pub fn tokenizer(mut self, tokenizer: Box<dyn Tokenizer>) -> Self {
         self.tokenizer = Some(tokenizer);
         self
@@ -102,11 +135,52 @@ impl HybridChunkerBuilder {
         self
     }
 
+    /// Set the number of tokens shared between consecutive chunks
+    ///
+    /// Each chunk (except the first) is prefixed with up to `overlap` tokens taken
+    /// from the end of the previous chunk. The overlap counts towards `max_tokens`,
+    /// so chunk content is limited to `max_tokens - overlap`. Default: 0 (no overlap).
+    pub fn overlap_tokens(mut self, overlap: usize) -> Self {
+        self.overlap_tokens = overlap;
+        self
+    }
+
+    /// Set whether undersized chunks of sibling sections may be merged
+    ///
+    /// Sibling sections share the same parent heading path (e.g. "1.1" and "1.2"
+    /// under "Chapter 1"). When merged, each section's title is placed at the start
+    /// of its text, parts are joined with the merge separator, and the chunk's
+    /// headings become the shared parent path. Merging still respects
+    /// `max_tokens`. Has no effect unless `merge_peers` is enabled. Default: false.
+    pub fn merge_across_headings(mut self, merge: bool) -> Self {
+        self.merge_across_headings = merge;
+        self
+    }
+
+    /// Set the separator placed between sections merged across headings
+    ///
+    /// Default: `"\n\n"`.
+    pub fn merge_separator(mut self, separator: impl Into<String>) -> Self {
+        self.merge_separator = separator.into();
+        self
+    }
+
+    /// Set whether sections are split and merged in parallel
+    ///
+    /// Runs of chunks sharing a heading path never interact during the split and
+    /// merge passes, so they are processed on the rayon thread pool. Output is
+    /// identical to sequential chunking. Worthwhile for large documents, where
+    /// token counting dominates runtime. Default: false.
+    pub fn parallel(mut self, parallel: bool) -> Self {
+        self.parallel = parallel;
+        self
+    }
+
     /// Build the HybridChunker
     pub fn build(self) -> Result<HybridChunker, ChunkingError> {
         let tokenizer = self
             .tokenizer
-            .ok_or_else(|| ChunkingError::InvalidConfig("tokenizer is required".to_string()))?;
+            .unwrap_or_else(|| Box::new(ApproxTokenizer::new()));
 
         let max_tokens = self.max_tokens.unwrap_or_else(|| tokenizer.max_tokens());
 
@@ -116,94 +190,150 @@ impl HybridChunkerBuilder {
             ));
         }
 
+        if self.overlap_tokens >= max_tokens {
+            return Err(ChunkingError::InvalidConfig(format!(
+                "overlap_tokens ({}) must be less than max_tokens ({})",
+                self.overlap_tokens, max_tokens
+            )));
+        }
+
         Ok(HybridChunker {
             tokenizer,
             max_tokens,
             merge_peers: self.merge_peers,
+            overlap_tokens: self.overlap_tokens,
+            parallel: self.parallel,
+            merge_across_headings: self.merge_across_headings,
+            merge_separator: self.merge_separator,
             hierarchical: HierarchicalChunker::new(),
         })
     }
 }
 
 impl HybridChunker {
+    /// Token budget for chunk content before overlap is added
+    ///
+    /// Overlap windows are reserved up front so that every chunk, once prefixed
+    /// with its predecessor's tail, still fits within `max_tokens`.
+    fn content_budget(&self) -> usize {
+        self.max_tokens - self.overlap_tokens
+    }
+
     /// Split a chunk that exceeds max_tokens
     fn split_oversized_chunk(&self, chunk: BaseChunk) -> Vec<BaseChunk> {
         let contextualized = self.contextualize(&chunk);
         let token_count = self.tokenizer.count_tokens(&contextualized);
 
         // If chunk fits within max_tokens, return as-is
-        if token_count <= self.max_tokens {
+        if token_count <= self.content_budget() {
             return vec![chunk];
         }
 
-        // Split the chunk text into smaller pieces
-        // Simple implementation: split by sentences or words
-        let text = &chunk.text;
-        let words: Vec<&str> = text.split_whitespace().collect();
+        if chunk.meta.is_code {
+            return self.split_code_chunk(chunk);
+        }
 
+        // Split the chunk text on word boundaries, tracking byte ranges so each
+        // piece is an exact slice of the original text
+        let words = word_ranges(&chunk.text);
         if words.is_empty() {
             return vec![chunk];
         }
+        self.split_units(&chunk, &words, false)
+    }
+
+    /// Split an oversized code chunk on line boundaries
+    ///
+    /// Lines are never broken, so a single line longer than the budget becomes a
+    /// chunk of its own even though it exceeds `max_tokens`.
+    fn split_code_chunk(&self, chunk: BaseChunk) -> Vec<BaseChunk> {
+        let lines = line_ranges(&chunk.text);
+        if lines.is_empty() {
+            return vec![chunk];
+        }
+        self.split_units(&chunk, &lines, true)
+    }
+
+    /// Split `chunk` into pieces of consecutive `units` (byte ranges of its
+    /// text) that fit in the content budget once contextualized
+    ///
+    /// Every piece holds at least one unit. With `continuation`, pieces after
+    /// the first are marked as continuations.
+    fn split_units(
+        &self,
+        chunk: &BaseChunk,
+        units: &[Range<usize>],
+        continuation: bool,
+    ) -> Vec<BaseChunk> {
+        // The context is the same for every piece, so it is written once and
+        // each candidate piece is appended to it in place
+        let mut buffer = String::new();
+        push_context(
+            &mut buffer,
+            &chunk.meta.headings,
+            chunk.meta.caption.as_deref(),
+        );
+        let context_len = buffer.len();
 
         let mut result = Vec::new();
-        let mut current_text = String::new();
-        let mut current_start = chunk.meta.start_offset;
-        let mut chunk_index = chunk.meta.index;
+        let mut rest = units;
+        while !rest.is_empty() {
+            let count = self.fitting_units(&chunk.text, rest, &mut buffer, context_len);
+            let range = rest[0].start..rest[count - 1].end;
+            let mut piece = slice_chunk(chunk, range, chunk.meta.index + result.len());
+            if continuation {
+                piece.meta.continuation = !result.is_empty();
+            }
+            result.push(piece);
+            rest = &rest[count..];
+        }
 
-        for word in words {
-            let test_text = if current_text.is_empty() {
-                word.to_string()
-            } else {
-                format!("{} {}", current_text, word)
-            };
-
-            // Create test chunk to check token count
-            let test_chunk = BaseChunk {
-                text: test_text.clone(),
-                meta: chunk.meta.clone(),
-            };
-            let test_contextualized = self.contextualize(&test_chunk);
-            let test_tokens = self.tokenizer.count_tokens(&test_contextualized);
-
-            if test_tokens > self.max_tokens && !current_text.is_empty() {
-                // Save current chunk and start a new one
-                let end_offset = current_start + current_text.len();
-                result.push(BaseChunk {
-                    text: current_text.clone(),
-                    meta: ChunkMetadata {
-                        doc_name: chunk.meta.doc_name.clone(),
-                        headings: chunk.meta.headings.clone(),
-                        caption: chunk.meta.caption.clone(),
-                        start_offset: current_start,
-                        end_offset,
-                        index: chunk_index,
-                    },
-                });
-                chunk_index += 1;
-                current_text = word.to_string();
-                current_start = end_offset + 1; // +1 for space
+        result
+    }
+
+    /// Number of leading `units` of `text` that fit in the content budget
+    /// after the context in `buffer[..context_len]`; at least one
+    ///
+    /// Token counts grow with the text, so rather than counting every prefix,
+    /// the number of units is doubled until the text no longer fits, then
+    /// bisected: pieces of `n` units take `O(log n)` counts.
+    fn fitting_units(
+        &self,
+        text: &str,
+        units: &[Range<usize>],
+        buffer: &mut String,
+        context_len: usize,
+    ) -> usize {
+        let mut fits = |count: usize| {
+            buffer.truncate(context_len);
+            buffer.push_str(&text[units[0].start..units[count - 1].end]);
+            self.tokenizer.count_tokens(buffer) <= self.content_budget()
+        };
+
+        // `low` units fit (the first is taken regardless), `high` do not
+        let mut low = 1;
+        let mut high = units.len() + 1;
+        let mut step = 1;
+        while low < units.len() {
+            let count = (low + step).min(units.len());
+            if fits(count) {
+                low = count;
+                step *= 2;
             } else {
-                current_text = test_text;
+                high = count;
+                break;
             }
         }
-
-        // Add remaining text
-        if !current_text.is_empty() {
-            let end_offset = current_start + current_text.len();
-            result.push(BaseChunk {
-                text: current_text,
-                meta: ChunkMetadata {
-                    doc_name: chunk.meta.doc_name.clone(),
-                    headings: chunk.meta.headings.clone(),
-                    caption: chunk.meta.caption.clone(),
-                    start_offset: current_start,
-                    end_offset,
-                    index: chunk_index,
-                },
-            });
+        while high - low > 1 {
+            let count = low + (high - low) / 2;
+            if fits(count) {
+                low = count;
+            } else {
+                high = count;
+            }
         }
 
-        result
+        low
     }
 
     /// Merge consecutive chunks with same metadata if they fit within max_tokens
@@ -214,40 +344,81 @@ impl HybridChunker {
 
         let mut result = Vec::new();
         let mut current: Option<BaseChunk> = None;
+        // Headings of the last chunk absorbed into `current`; differs from
+        // `current.meta.headings` once sibling sections have been merged
+        let mut current_headings: Vec<String> = Vec::new();
+        // Contextualized text of the merge being tried, reused across merges
+        let mut buffer = String::new();
 
         for chunk in chunks {
             match current.take() {
                 None => {
+                    current_headings = chunk.meta.headings.clone();
                     current = Some(chunk);
                 }
                 Some(mut prev) => {
-                    // Check if chunks can be merged (same headings and caption)
-                    let can_merge = prev.meta.headings == chunk.meta.headings
-                        && prev.meta.caption == chunk.meta.caption;
-
-                    if can_merge {
-                        // Try merging
-                        let merged_text = format!("{} {}", prev.text, chunk.text);
-                        let test_chunk = BaseChunk {
-                            text: merged_text.clone(),
-                            meta: prev.meta.clone(),
+                    // Check if chunks can be merged (same headings and caption);
+                    // code is kept atomic
+                    let compatible = prev.meta.caption == chunk.meta.caption
+                        && !prev.meta.is_code
+                        && !chunk.meta.is_code;
+                    let same_section = current_headings == chunk.meta.headings;
+                    let sibling_section = self.merge_across_headings
+                        && is_sibling(&current_headings, &chunk.meta.headings);
+
+                    if compatible && (same_section || sibling_section) {
+                        // Try merging: the merged chunk is contextualized into
+                        // the buffer, and its text only built if it fits
+                        let merged_headings = if same_section {
+                            &prev.meta.headings[..]
+                        } else {
+                            &current_headings[..current_headings.len() - 1]
                         };
-                        let contextualized = self.contextualize(&test_chunk);
-                        let token_count = self.tokenizer.count_tokens(&contextualized);
+                        buffer.clear();
+                        push_context(&mut buffer, merged_headings, prev.meta.caption.as_deref());
+                        let text_start = buffer.len();
+                        if same_section {
+                            buffer.push_str(&prev.text);
+                            buffer.push(' ');
+                            buffer.push_str(&chunk.text);
+                        } else {
+                            // Section titles move into the text; the chunk keeps
+                            // the shared parent path as its headings
+                            if prev.meta.headings == current_headings {
+                                push_titled(&mut buffer, &current_headings, &prev.text);
+                            } else {
+                                buffer.push_str(&prev.text);
+                            }
+                            buffer.push_str(&self.merge_separator);
+                            push_titled(&mut buffer, &chunk.meta.headings, &chunk.text);
+                        }
+                        let token_count = self.tokenizer.count_tokens(&buffer);
 
-                        if token_count <= self.max_tokens {
+                        if token_count <= self.content_budget() {
                             // Merge successful
-                            prev.text = merged_text;
+                            prev.text.clear();
+                            prev.text.push_str(&buffer[text_start..]);
+                            if !same_section {
+                                current_headings.pop();
+                                prev.meta.headings = current_headings;
+                            }
                             prev.meta.end_offset = chunk.meta.end_offset;
+                            prev.meta.merge_provenance(&chunk.meta);
+                            // The merged text holds separators and headings
+                            // that are not in the source
+                            prev.meta.offsets = None;
+                            current_headings = chunk.meta.headings;
                             current = Some(prev);
                         } else {
                             // Can't merge, save previous and keep current
                             result.push(prev);
+                            current_headings = chunk.meta.headings.clone();
                             current = Some(chunk);
                         }
                     } else {
                         // Different metadata, can't merge
                         result.push(prev);
+                        current_headings = chunk.meta.headings.clone();
                         current = Some(chunk);
                     }
                 }
@@ -259,12 +430,89 @@ impl HybridChunker {
             result.push(chunk);
         }
 
-        // Re-index chunks
-        for (i, chunk) in result.iter_mut().enumerate() {
-            chunk.meta.index = i;
+        result
+    }
+
+    /// Run the split and merge passes over a run of chunks
+    fn split_and_merge(&self, chunks: Vec<BaseChunk>) -> Vec<BaseChunk> {
+        let split_chunks = chunks
+            .into_iter()
+            .flat_map(|chunk| self.split_oversized_chunk(chunk))
+            .collect();
+        self.merge_undersized_peers(split_chunks)
+    }
+
+    /// Prefix each chunk with the trailing window of its predecessor
+    fn apply_overlap(&self, mut chunks: Vec<BaseChunk>) -> Vec<BaseChunk> {
+        if self.overlap_tokens == 0 || chunks.len() < 2 {
+            return chunks;
         }
 
-        result
+        // Work from the back so each window is taken from the un-overlapped text
+        for i in (1..chunks.len()).rev() {
+            let (before, after) = chunks.split_at_mut(i);
+            let prev = &before[i - 1];
+            let chunk = &mut after[0];
+
+            // Joining code with a space would corrupt its line structure
+            if prev.meta.is_code || chunk.meta.is_code {
+                continue;
+            }
+
+            if let Some(window_start) = self.overlap_window(prev, chunk) {
+                let window = prev.text[window_start..].trim_end();
+                chunk.text = format!("{} {}", window, chunk.text);
+                chunk.meta.start_offset = prev.meta.source_offset(window_start);
+                chunk.meta.offsets = None;
+            }
+        }
+
+        chunks
+    }
+
+    /// Find the byte index in `prev.text` where the overlap window starts
+    ///
+    /// The window is the longest run of trailing words that fits in `overlap_tokens`
+    /// and keeps `chunk` within `max_tokens` once prepended.
+    fn overlap_window(&self, prev: &BaseChunk, chunk: &BaseChunk) -> Option<usize> {
+        let word_starts: Vec<usize> = prev
+            .text
+            .char_indices()
+            .filter(|&(i, c)| {
+                !c.is_whitespace()
+                    && prev.text[..i]
+                        .chars()
+                        .next_back()
+                        .map_or(true, char::is_whitespace)
+            })
+            .map(|(i, _)| i)
+            .collect();
+
+        // Grow the window one word at a time while it fits in the overlap budget
+        let mut candidates = Vec::new();
+        for &start in word_starts.iter().rev() {
+            let window = prev.text[start..].trim_end();
+            if self.tokenizer.count_tokens(window) > self.overlap_tokens {
+                break;
+            }
+            candidates.push(start);
+        }
+
+        // Take the largest window that keeps the chunk within max_tokens
+        let mut buffer = String::new();
+        push_context(
+            &mut buffer,
+            &chunk.meta.headings,
+            chunk.meta.caption.as_deref(),
+        );
+        let context_len = buffer.len();
+        candidates.into_iter().rev().find(|&start| {
+            buffer.truncate(context_len);
+            buffer.push_str(prev.text[start..].trim_end());
+            buffer.push(' ');
+            buffer.push_str(&chunk.text);
+            self.tokenizer.count_tokens(&buffer) <= self.max_tokens
+        })
     }
 }
 
@@ -274,16 +522,45 @@ impl BaseChunker for HybridChunker {
         let hierarchical_chunks: Vec<BaseChunk> = self.hierarchical.chunk(doc).collect();
 
         // Pass 2: Split oversized chunks
-        let mut split_chunks = Vec::new();
-        for chunk in hierarchical_chunks {
-            let mut chunks = self.split_oversized_chunk(chunk);
-            split_chunks.append(&mut chunks);
+        // Pass 3: Merge undersized peers (if enabled)
+        let mut merged_chunks = if self.parallel {
+            // Merging never crosses a change of headings (or of parent headings
+            // when sibling sections may merge), so those runs are independent
+            let across = self.merge_across_headings;
+            let mut sections: Vec<Vec<BaseChunk>> = Vec::new();
+            for chunk in hierarchical_chunks {
+                match sections.last_mut() {
+                    Some(section)
+                        if merge_key(&section[section.len() - 1].meta.headings, across)
+                            == merge_key(&chunk.meta.headings, across) =>
+                    {
+                        section.push(chunk)
+                    }
+                    _ => sections.push(vec![chunk]),
+                }
+            }
+
+            sections
+                .into_par_iter()
+                .map(|section| self.split_and_merge(section))
+                .collect::<Vec<_>>()
+                .into_iter()
+                .flatten()
+                .collect()
+        } else {
+            self.split_and_merge(hierarchical_chunks)
+        };
+
+        // Re-index chunks
+        for (i, chunk) in merged_chunks.iter_mut().enumerate() {
+            chunk.meta.index = i;
         }
 
-        // Pass 3: Merge undersized peers (if enabled)
-        let final_chunks = self.merge_undersized_peers(split_chunks);
+        // Pass 4: Share a window of tokens between consecutive chunks (if enabled)
+        let final_chunks = self.apply_overlap(merged_chunks);
 
-        Box::new(final_chunks.into_iter())
+        // IDs reflect the final text, after splitting, merging and overlap
+        Box::new(final_chunks.into_iter().map(BaseChunk::with_chunk_id))
     }
 
     fn contextualize(&self, chunk: &BaseChunk) -> String {
@@ -291,3 +568,89 @@ impl BaseChunker for HybridChunker {
         self.hierarchical.contextualize(chunk)
     }
 }
+
+/// Check whether two heading paths are distinct sections under the same parent
+fn is_sibling(a: &[String], b: &[String]) -> bool {
+    match (a.split_last(), b.split_last()) {
+        (Some((a_title, a_parent)), Some((b_title, b_parent))) => {
+            a_parent == b_parent && a_title != b_title
+        }
+        _ => false,
+    }
+}
+
+/// Heading path shared by chunks that may merge: the whole path, or its parent
+/// when sibling sections may merge
+fn merge_key(headings: &[String], across_headings: bool) -> &[String] {
+    if across_headings {
+        &headings[..headings.len().saturating_sub(1)]
+    } else {
+        headings
+    }
+}
+
+/// Append text prefixed with the last heading of its path
+fn push_titled(out: &mut String, headings: &[String], text: &str) {
+    if let Some(title) = headings.last() {
+        out.push_str(title);
+        out.push('\n');
+    }
+    out.push_str(text);
+}
+
+/// Byte ranges of the whitespace-separated words in `text`
+fn word_ranges(text: &str) -> Vec<Range<usize>> {
+    let mut words = Vec::new();
+    let mut start = None;
+
+    for (i, c) in text.char_indices() {
+        match (start, c.is_whitespace()) {
+            (None, false) => start = Some(i),
+            (Some(s), true) => {
+                words.push(s..i);
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        words.push(s..text.len());
+    }
+
+    words
+}
+
+/// Byte ranges of the lines in `text`, excluding line terminators
+fn line_ranges(text: &str) -> Vec<Range<usize>> {
+    let mut lines = Vec::new();
+    let mut start = 0;
+
+    for line in text.split_inclusive('\n') {
+        let content = line.trim_end_matches(['\n', '\r']);
+        lines.push(start..start + content.len());
+        start += line.len();
+    }
+
+    lines
+}
+
+/// Build the chunk covering `range` of `chunk.text`
+///
+/// Offsets are those of the slice within the source document, mapped through
+/// [`ChunkMetadata::source_offset`].
+fn slice_chunk(chunk: &BaseChunk, range: Range<usize>, index: usize) -> BaseChunk {
+    BaseChunk {
+        text: chunk.text[range.clone()].to_string(),
+        meta: ChunkMetadata {
+            start_offset: chunk.meta.source_offset(range.start),
+            end_offset: chunk.meta.source_offset(range.end),
+            index,
+            offsets: chunk
+                .meta
+                .offsets
+                .as_ref()
+                .map(|offsets| offsets.slice(range.start, range.end)),
+            ..chunk.meta.clone()
+        },
+    }
+}
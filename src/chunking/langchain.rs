@@ -0,0 +1,112 @@
+//! LangChain document export
+//!
+//! Turns chunks into LangChain `Document` objects, `{"page_content": ...,
+//! "metadata": {...}}`, so they load into LangChain without adapter code:
+//!
+//! ```python
+//! from langchain_core.documents import Document
+//!
+//! docs = [Document(**d) for d in json.load(open("report.json"))]
+//! ```
+//!
+//! As in docling's own LangChain integration, the page content is the
+//! contextualized text of the chunk (headings and caption prepended), which
+//! is what gets embedded. The metadata is flat, so vector stores can filter
+//! on it:
+//!
+//! | key           | value                                              |
+//! |---------------|----------------------------------------------------|
+//! | `source`      | document name                                      |
+//! | `chunk_id`    | [`chunk_id`](crate::chunking::chunk_id)            |
+//! | `chunk_index` | position of the chunk in the document              |
+//! | `start_index` | offset of the chunk in the document text           |
+//! | `headings`    | heading path, when the chunk is under a heading    |
+//! | `caption`     | caption of the table or picture, if any            |
+//! | `page`        | first page, for paginated formats                  |
+//! | `page_end`    | last page, for paginated formats                   |
+//! | `languages`   | languages of the chunk, when detected              |
+//! | `is_code`     | `true` for code blocks                             |
+
+use super::base::{BaseChunk, BaseChunker, ChunkingError};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// A chunk as a LangChain `Document`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LangChainDocument {
+    /// Identifier of the document, the chunk ID
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+
+    /// Text of the document: the contextualized chunk text
+    pub page_content: String,
+
+    /// Flat metadata (see the [module documentation](self))
+    pub metadata: Map<String, Value>,
+}
+
+impl LangChainDocument {
+    /// Build the document for `chunk`, contextualized by `chunker`
+    pub fn new(chunk: &BaseChunk, chunker: &dyn BaseChunker) -> Self {
+        let meta = &chunk.meta;
+        let mut metadata = Map::new();
+        metadata.insert("source".to_string(), meta.doc_name.clone().into());
+        metadata.insert("chunk_id".to_string(), meta.chunk_id.clone().into());
+        metadata.insert("chunk_index".to_string(), meta.index.into());
+        metadata.insert("start_index".to_string(), meta.start_offset.into());
+        if !meta.headings.is_empty() {
+            metadata.insert("headings".to_string(), meta.headings.clone().into());
+        }
+        if let Some(caption) = &meta.caption {
+            metadata.insert("caption".to_string(), caption.clone().into());
+        }
+        if let Some(page) = meta.page_start {
+            metadata.insert("page".to_string(), page.into());
+        }
+        if let Some(page) = meta.page_end {
+            metadata.insert("page_end".to_string(), page.into());
+        }
+        if !meta.languages.is_empty() {
+            metadata.insert("languages".to_string(), meta.languages.clone().into());
+        }
+        if meta.is_code {
+            metadata.insert("is_code".to_string(), true.into());
+        }
+
+        Self {
+            id: Some(meta.chunk_id.clone()).filter(|id| !id.is_empty()),
+            page_content: chunker.contextualize(chunk),
+            metadata,
+        }
+    }
+}
+
+/// Convert chunks to LangChain documents, in order
+///
+/// # Examples
+///
+/// ```ignore
+/// use docling_rs::chunking::{langchain, BaseChunker, HierarchicalChunker};
+///
+/// let chunker = HierarchicalChunker::new();
+/// let documents = langchain::to_documents(chunker.chunk(&doc), &chunker);
+/// ```
+pub fn to_documents(
+    chunks: impl IntoIterator<Item = BaseChunk>,
+    chunker: &dyn BaseChunker,
+) -> Vec<LangChainDocument> {
+    chunks
+        .into_iter()
+        .map(|chunk| LangChainDocument::new(&chunk, chunker))
+        .collect()
+}
+
+/// Render chunks as a JSON array of LangChain documents
+pub fn to_json(
+    chunks: impl IntoIterator<Item = BaseChunk>,
+    chunker: &dyn BaseChunker,
+) -> Result<String, ChunkingError> {
+    Ok(serde_json::to_string_pretty(&to_documents(
+        chunks, chunker,
+    ))?)
+}
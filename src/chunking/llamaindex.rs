@@ -0,0 +1,213 @@
+//! LlamaIndex node export
+//!
+//! Turns chunks into LlamaIndex `TextNode`s, in the JSON LlamaIndex itself
+//! writes, so they load without adapter code:
+//!
+//! ```python
+//! from llama_index.core.schema import TextNode
+//!
+//! nodes = [TextNode.from_dict(n) for n in json.load(open("report.json"))]
+//! ```
+//!
+//! The text of a node is the raw chunk text; headings and pages go to its
+//! metadata, which LlamaIndex prepends to the text when embedding it and
+//! passing it to a model. The metadata holds:
+//!
+//! | key          | value                                               |
+//! |--------------|-----------------------------------------------------|
+//! | `file_name`  | document name                                       |
+//! | `headings`   | heading path, when the chunk is under a heading     |
+//! | `caption`    | caption of the table or picture, if any             |
+//! | `page_label` | first page, as text, for paginated formats          |
+//! | `page_end`   | last page, when the chunk spans several pages       |
+//! | `languages`  | languages of the chunk, when detected               |
+//! | `is_code`    | `true` for code blocks                              |
+//!
+//! Nodes are linked to their document (`SOURCE`, named by the document
+//! name) and to the chunks before and after them (`PREVIOUS`, `NEXT`). Node
+//! IDs are [chunk IDs](crate::chunking::chunk_id).
+
+use super::base::{BaseChunk, ChunkingError};
+use super::metadata::chunk_id;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+
+/// Relationship keys of LlamaIndex's `NodeRelationship`
+const SOURCE: &str = "1";
+const PREVIOUS: &str = "2";
+const NEXT: &str = "3";
+
+/// `ObjectType` values of LlamaIndex for text nodes and documents
+const TEXT_NODE: &str = "1";
+const DOCUMENT: &str = "4";
+
+/// A chunk as a LlamaIndex `TextNode`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LlamaIndexNode {
+    /// Node ID: the chunk ID
+    #[serde(rename = "id_")]
+    pub id: String,
+
+    /// Embedding of the node, once computed
+    pub embedding: Option<Vec<f32>>,
+
+    /// Metadata (see the [module documentation](self))
+    pub metadata: Map<String, Value>,
+
+    /// Metadata keys left out of the text that is embedded
+    pub excluded_embed_metadata_keys: Vec<String>,
+
+    /// Metadata keys left out of the text passed to models
+    pub excluded_llm_metadata_keys: Vec<String>,
+
+    /// Related nodes, by `NodeRelationship` (`"1"` source, `"2"` previous,
+    /// `"3"` next)
+    pub relationships: BTreeMap<String, RelatedNodeInfo>,
+
+    /// Raw chunk text
+    pub text: String,
+
+    /// Media type of `text`
+    pub mimetype: String,
+
+    /// Offset of the chunk in the document text
+    pub start_char_idx: Option<usize>,
+
+    /// Offset of the end of the chunk in the document text
+    pub end_char_idx: Option<usize>,
+
+    /// How LlamaIndex joins metadata and text
+    pub text_template: String,
+
+    /// How LlamaIndex renders each metadata entry
+    pub metadata_template: String,
+
+    /// Separator between metadata entries (spelled as in LlamaIndex)
+    pub metadata_seperator: String,
+
+    /// Always `TextNode`
+    pub class_name: String,
+}
+
+/// A link from a node to another, as LlamaIndex's `RelatedNodeInfo`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RelatedNodeInfo {
+    /// ID of the related node
+    pub node_id: String,
+
+    /// `ObjectType` of the related node (`"1"` text node, `"4"` document)
+    pub node_type: Option<String>,
+
+    /// Metadata of the related node
+    pub metadata: Map<String, Value>,
+
+    /// Hash of the related node, left for LlamaIndex to compute
+    pub hash: Option<String>,
+
+    /// Always `RelatedNodeInfo`
+    pub class_name: String,
+}
+
+impl RelatedNodeInfo {
+    fn new(node_id: &str, node_type: &str) -> Self {
+        Self {
+            node_id: node_id.to_string(),
+            node_type: Some(node_type.to_string()),
+            metadata: Map::new(),
+            hash: None,
+            class_name: "RelatedNodeInfo".to_string(),
+        }
+    }
+}
+
+impl LlamaIndexNode {
+    /// Build the node for `chunk`, without relationships to other chunks
+    pub fn new(chunk: &BaseChunk) -> Self {
+        let meta = &chunk.meta;
+        let mut metadata = Map::new();
+        metadata.insert("file_name".to_string(), meta.doc_name.clone().into());
+        if !meta.headings.is_empty() {
+            metadata.insert("headings".to_string(), meta.headings.clone().into());
+        }
+        if let Some(caption) = &meta.caption {
+            metadata.insert("caption".to_string(), caption.clone().into());
+        }
+        if let Some(page) = meta.page_start {
+            metadata.insert("page_label".to_string(), page.to_string().into());
+        }
+        if let Some(page) = meta.page_end.filter(|&end| Some(end) != meta.page_start) {
+            metadata.insert("page_end".to_string(), page.into());
+        }
+        if !meta.languages.is_empty() {
+            metadata.insert("languages".to_string(), meta.languages.clone().into());
+        }
+        if meta.is_code {
+            metadata.insert("is_code".to_string(), true.into());
+        }
+
+        let id = if meta.chunk_id.is_empty() {
+            chunk_id(&meta.doc_name, &meta.headings, &chunk.text)
+        } else {
+            meta.chunk_id.clone()
+        };
+        let mut relationships = BTreeMap::new();
+        relationships.insert(
+            SOURCE.to_string(),
+            RelatedNodeInfo::new(&meta.doc_name, DOCUMENT),
+        );
+
+        Self {
+            id,
+            embedding: None,
+            metadata,
+            excluded_embed_metadata_keys: Vec::new(),
+            excluded_llm_metadata_keys: Vec::new(),
+            relationships,
+            text: chunk.text.clone(),
+            mimetype: "text/plain".to_string(),
+            start_char_idx: Some(meta.start_offset),
+            end_char_idx: Some(meta.end_offset),
+            text_template: "{metadata_str}\n\n{content}".to_string(),
+            metadata_template: "{key}: {value}".to_string(),
+            metadata_seperator: "\n".to_string(),
+            class_name: "TextNode".to_string(),
+        }
+    }
+}
+
+/// Convert chunks to LlamaIndex nodes, in order, each linked to the chunks
+/// before and after it
+///
+/// # Examples
+///
+/// ```ignore
+/// use docling_rs::chunking::{llamaindex, BaseChunker, HierarchicalChunker};
+///
+/// let nodes = llamaindex::to_nodes(HierarchicalChunker::new().chunk(&doc));
+/// ```
+pub fn to_nodes(chunks: impl IntoIterator<Item = BaseChunk>) -> Vec<LlamaIndexNode> {
+    let mut nodes: Vec<_> = chunks
+        .into_iter()
+        .map(|chunk| LlamaIndexNode::new(&chunk))
+        .collect();
+    let ids: Vec<_> = nodes.iter().map(|node| node.id.clone()).collect();
+    for (i, node) in nodes.iter_mut().enumerate() {
+        if let Some(previous) = i.checked_sub(1).and_then(|i| ids.get(i)) {
+            node.relationships.insert(
+                PREVIOUS.to_string(),
+                RelatedNodeInfo::new(previous, TEXT_NODE),
+            );
+        }
+        if let Some(next) = ids.get(i + 1) {
+            node.relationships
+                .insert(NEXT.to_string(), RelatedNodeInfo::new(next, TEXT_NODE));
+        }
+    }
+    nodes
+}
+
+/// Render chunks as a JSON array of LlamaIndex nodes
+pub fn to_json(chunks: impl IntoIterator<Item = BaseChunk>) -> Result<String, ChunkingError> {
+    Ok(serde_json::to_string_pretty(&to_nodes(chunks))?)
+}
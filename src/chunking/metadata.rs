@@ -1,6 +1,9 @@
 //! Chunk metadata structures
 
+use crate::datamodel::{BoundingBox, OffsetMap};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 
 /// Structured metadata attached to each chunk
 ///
@@ -15,7 +18,14 @@ use serde::{Deserialize, Serialize};
 /// * `start_offset` - Character offset where chunk starts
 /// * `end_offset` - Character offset where chunk ends
 /// * `index` - Sequential index of this chunk (0-based)
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// * `page_start` / `page_end` - Page range (1-based) when the source has pages
+/// * `is_code` - Whether the chunk holds source code
+/// * `continuation` - Whether the chunk continues a code block split across chunks
+/// * `chunk_id` - Stable content hash, see [`chunk_id`]
+/// * `bboxes` - Per-page union of the source blocks' bounding boxes
+/// * `languages` - Languages of the source nodes, see [`language`](Self::language)
+/// * `offsets` - Map from chunk text to source offsets, see [`source_offset`](Self::source_offset)
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 pub struct ChunkMetadata {
     /// Source document name/identifier
     pub doc_name: String,
@@ -34,4 +44,122 @@ pub struct ChunkMetadata {
 
     /// Sequential index of this chunk (0-based)
     pub index: usize,
+
+    /// First page (1-based) the chunk's content comes from, if known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub page_start: Option<usize>,
+
+    /// Last page (1-based) the chunk's content comes from, if known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub page_end: Option<usize>,
+
+    /// Whether the chunk holds source code (line structure is significant)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub is_code: bool,
+
+    /// Whether the chunk continues a code block that was split across chunks
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub continuation: bool,
+
+    /// Deterministic identifier derived from doc name, heading path and text
+    #[serde(default)]
+    pub chunk_id: String,
+
+    /// Region covered on each page, for highlighting the chunk in the source PDF
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub bboxes: Vec<PageBoundingBox>,
+
+    /// Distinct languages (ISO 639-3) of the chunk's source nodes, in order of appearance
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub languages: Vec<String>,
+
+    /// Map from chunk text offsets to source offsets, when the text is not a
+    /// verbatim copy of `start_offset..end_offset` (e.g. trimmed lines)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub offsets: Option<OffsetMap>,
+}
+
+impl ChunkMetadata {
+    /// Extend this chunk's provenance (page range, bounding boxes, languages) with another's
+    ///
+    /// Used when chunks are merged: page ranges are widened, boxes on the same
+    /// page are unioned and languages are collected.
+    pub fn merge_provenance(&mut self, other: &ChunkMetadata) {
+        self.page_start = match (self.page_start, other.page_start) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+        self.page_end = match (self.page_end, other.page_end) {
+            (Some(a), Some(b)) => Some(a.max(b)),
+            (a, b) => a.or(b),
+        };
+
+        for region in &other.bboxes {
+            match self.bboxes.iter_mut().find(|r| r.page == region.page) {
+                Some(existing) => existing.bbox = existing.bbox.union(&region.bbox),
+                None => self.bboxes.push(*region),
+            }
+        }
+
+        for language in &other.languages {
+            if !self.languages.contains(language) {
+                self.languages.push(language.clone());
+            }
+        }
+    }
+
+    /// Map a byte offset of the chunk text to a byte offset of the source document
+    ///
+    /// Uses the offset map when there is one; otherwise the text is taken as a
+    /// verbatim copy of the source from `start_offset`, clamped to the chunk's
+    /// span.
+    pub fn source_offset(&self, offset: usize) -> usize {
+        match &self.offsets {
+            Some(offsets) => offsets.to_source(offset).unwrap_or(self.start_offset),
+            None => {
+                let span = self.end_offset.saturating_sub(self.start_offset);
+                self.start_offset + offset.min(span)
+            }
+        }
+    }
+
+    /// Get the language of the chunk, if all tagged source nodes share one
+    pub fn language(&self) -> Option<&str> {
+        match self.languages.as_slice() {
+            [language] => Some(language),
+            _ => None,
+        }
+    }
+}
+
+/// Bounding box of a chunk's content on one page
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct PageBoundingBox {
+    /// Page number (1-based)
+    pub page: usize,
+
+    /// Union of the source blocks' boxes on this page
+    pub bbox: BoundingBox,
+}
+
+/// Compute a deterministic chunk identifier
+///
+/// The identifier is the hex-encoded SHA-256 of the document name, the heading
+/// path and the chunk text with whitespace runs collapsed, so re-chunking an
+/// unchanged document yields the same IDs (useful for vector-store upserts).
+pub fn chunk_id(doc_name: &str, headings: &[String], text: &str) -> String {
+    let mut hasher = Sha256::new();
+
+    // Length-prefix each component so different splits can't collide
+    let normalized = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    let components = std::iter::once(doc_name)
+        .chain(headings.iter().map(String::as_str))
+        .chain(std::iter::once(normalized.as_str()));
+    hasher.update((headings.len() as u64).to_le_bytes());
+    for component in components {
+        hasher.update((component.len() as u64).to_le_bytes());
+        hasher.update(component.as_bytes());
+    }
+
+    format!("{:x}", hasher.finalize())
 }
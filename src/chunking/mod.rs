@@ -6,10 +6,15 @@
 //!
 //! # Overview
 //!
-//! The chunking system offers two main strategies:
+//! The chunking system offers five main strategies:
 //!
 //! - **[`HierarchicalChunker`]**: Structure-based chunking that preserves document hierarchy
 //! - **[`HybridChunker`]**: Advanced chunking with token-awareness for embedding models
+//! - **[`RecursiveCharacterChunker`]**: Separator-based splitting in the style of LangChain
+//! - **[`WindowChunker`]**: Overlapping fixed-size token windows that ignore structure
+//! - **[`SemanticChunker`]**: Elements grouped until embedding similarity drops
+//!
+//! Strategies can also be selected by name through the [`ChunkerRegistry`].
 //!
 //! # Examples
 //!
@@ -44,13 +49,43 @@
 //! ```
 
 pub mod base;
+pub mod export;
+pub mod filter;
+#[cfg(feature = "parquet")]
+pub mod hf_dataset;
 pub mod hierarchical;
 pub mod hybrid;
+pub mod langchain;
+pub mod llamaindex;
 pub mod metadata;
+#[cfg(feature = "parquet")]
+pub mod parquet;
+pub mod recursive;
+pub mod registry;
+pub mod semantic;
+pub mod sinks;
+pub mod stats;
 pub mod tokenizer;
+pub mod window;
 
 pub use base::{BaseChunk, BaseChunker, ChunkingError};
+pub use export::ChunkRecord;
+pub use filter::{
+    ChunkFilter, ChunkerExt, DropPatterns, FilteredChunker, MinLength, RemoveBoilerplate,
+};
 pub use hierarchical::HierarchicalChunker;
 pub use hybrid::{HybridChunker, HybridChunkerBuilder};
-pub use metadata::ChunkMetadata;
-pub use tokenizer::{HuggingFaceTokenizer, Tokenizer};
+pub use langchain::LangChainDocument;
+pub use llamaindex::LlamaIndexNode;
+pub use metadata::{chunk_id, ChunkMetadata, PageBoundingBox};
+pub use recursive::{RecursiveCharacterChunker, RecursiveCharacterChunkerBuilder};
+pub use registry::{ChunkerFactory, ChunkerOptions, ChunkerRegistry};
+pub use semantic::{SemanticChunker, SemanticChunkerBuilder};
+pub use sinks::ChunkSink;
+pub use stats::ChunkStats;
+#[cfg(feature = "huggingface")]
+pub use tokenizer::HuggingFaceTokenizer;
+#[cfg(feature = "tiktoken")]
+pub use tokenizer::TiktokenTokenizer;
+pub use tokenizer::{ApproxTokenizer, Tokenizer};
+pub use window::{WindowChunker, WindowChunkerBuilder};
@@ -0,0 +1,216 @@
+//! Parquet export of chunks and nodes
+//!
+//! Writes one row per chunk or node with a fixed schema, so results load
+//! directly into data warehouses, DuckDB or Spark:
+//!
+//! | column        | type             | chunks              | nodes                        |
+//! |---------------|------------------|---------------------|------------------------------|
+//! | `id`          | string           | [`chunk_id`]        | node reference (`#/nodes/3`) |
+//! | `document`    | string           | document name       | document name                |
+//! | `text`        | string           | chunk text          | node text                    |
+//! | `headings`    | list of strings  | heading path        | heading path                 |
+//! | `page`        | uint32, nullable | first page          | page                         |
+//! | `token_count` | uint64           | contextualized text | node text                    |
+//!
+//! Files are Snappy-compressed. Requires the `parquet` feature.
+//!
+//! [`chunk_id`]: crate::chunking::chunk_id
+
+use std::io::Write;
+use std::sync::Arc;
+
+use arrow_array::builder::{ListBuilder, StringBuilder, UInt32Builder, UInt64Builder};
+use arrow_array::{ArrayRef, RecordBatch};
+use arrow_schema::{DataType, Field, Schema, SchemaRef};
+use parquet::arrow::ArrowWriter;
+use parquet::basic::Compression;
+use parquet::errors::ParquetError;
+use parquet::file::properties::WriterProperties;
+
+use super::base::{BaseChunk, BaseChunker, ChunkingError};
+use super::export::ChunkRecord;
+use super::tokenizer::Tokenizer;
+use crate::datamodel::DoclingDocument;
+
+/// Rows buffered before a record batch is written
+const BATCH_ROWS: usize = 8192;
+
+/// Write chunks to Parquet, one row per chunk
+///
+/// Token counts are those of the contextualized text, as in
+/// [`ChunkRecord`].
+///
+/// # Examples
+///
+/// ```ignore
+/// use docling_rs::chunking::{parquet, ApproxTokenizer, BaseChunker, HierarchicalChunker};
+///
+/// let chunker = HierarchicalChunker::new();
+/// let out = std::fs::File::create("chunks.parquet")?;
+/// parquet::write_chunks(out, chunker.chunk(&doc), &chunker, &ApproxTokenizer::new())?;
+/// ```
+pub fn write_chunks<W: Write + Send>(
+    writer: W,
+    chunks: impl IntoIterator<Item = BaseChunk>,
+    chunker: &dyn BaseChunker,
+    tokenizer: &dyn Tokenizer,
+) -> Result<(), ChunkingError> {
+    let rows = chunks
+        .into_iter()
+        .map(|chunk| Row::from_record(ChunkRecord::new(chunk, chunker, tokenizer)));
+    write_rows(writer, schema(), rows)
+}
+
+/// Write the nodes of a document to Parquet, one row per node with text
+///
+/// Containers without text of their own (lists, sections, page breaks) are
+/// left out.
+pub fn write_nodes<W: Write + Send>(
+    writer: W,
+    doc: &DoclingDocument,
+    tokenizer: &dyn Tokenizer,
+) -> Result<(), ChunkingError> {
+    let mut rows = Vec::new();
+    doc.for_each_with_headings(|id, node, headings| {
+        let text = node.text_content().unwrap_or_default();
+        if text.trim().is_empty() {
+            return;
+        }
+        rows.push(Row {
+            id: node
+                .self_ref()
+                .map(|r| r.as_str().to_string())
+                .unwrap_or_else(|| format!("#/nodes/{}", id.index())),
+            document: doc.name().to_string(),
+            text: text.to_string(),
+            headings: headings.to_vec(),
+            page: node.page(),
+            token_count: tokenizer.count_tokens(text),
+        });
+    });
+    write_rows(writer, schema(), rows)
+}
+
+/// Write chunks to an in-memory Parquet file
+pub fn chunks_to_bytes(
+    chunks: impl IntoIterator<Item = BaseChunk>,
+    chunker: &dyn BaseChunker,
+    tokenizer: &dyn Tokenizer,
+) -> Result<Vec<u8>, ChunkingError> {
+    let mut buffer = Vec::new();
+    write_chunks(&mut buffer, chunks, chunker, tokenizer)?;
+    Ok(buffer)
+}
+
+/// Write the nodes of a document to an in-memory Parquet file
+pub fn nodes_to_bytes(
+    doc: &DoclingDocument,
+    tokenizer: &dyn Tokenizer,
+) -> Result<Vec<u8>, ChunkingError> {
+    let mut buffer = Vec::new();
+    write_nodes(&mut buffer, doc, tokenizer)?;
+    Ok(buffer)
+}
+
+/// A row of the fixed schema
+pub(super) struct Row {
+    id: String,
+    document: String,
+    text: String,
+    headings: Vec<String>,
+    page: Option<usize>,
+    token_count: usize,
+}
+
+impl Row {
+    /// The row of a chunk
+    pub(super) fn from_record(record: ChunkRecord) -> Self {
+        Self {
+            id: record.meta.chunk_id,
+            document: record.meta.doc_name,
+            text: record.text,
+            headings: record.meta.headings,
+            page: record.meta.page_start,
+            token_count: record.token_count,
+        }
+    }
+
+    /// Size of the row in Arrow memory, offsets included
+    pub(super) fn num_bytes(&self) -> u64 {
+        let strings = self.id.len() + self.document.len() + self.text.len();
+        let headings: usize = self.headings.iter().map(|h| h.len() + 4).sum();
+        // Three string offsets, a list offset, page and token count
+        (strings + headings + 3 * 4 + 4 + 4 + 8) as u64
+    }
+}
+
+/// The fixed schema
+pub(super) fn schema() -> SchemaRef {
+    let heading = Field::new("item", DataType::Utf8, true);
+    Arc::new(Schema::new(vec![
+        Field::new("id", DataType::Utf8, false),
+        Field::new("document", DataType::Utf8, false),
+        Field::new("text", DataType::Utf8, false),
+        Field::new("headings", DataType::List(Arc::new(heading)), false),
+        Field::new("page", DataType::UInt32, true),
+        Field::new("token_count", DataType::UInt64, false),
+    ]))
+}
+
+/// Write `rows` to Parquet with `schema`, the fixed schema or the fixed
+/// schema with metadata
+pub(super) fn write_rows<W: Write + Send>(
+    writer: W,
+    schema: SchemaRef,
+    rows: impl IntoIterator<Item = Row>,
+) -> Result<(), ChunkingError> {
+    let properties = WriterProperties::builder()
+        .set_compression(Compression::SNAPPY)
+        .build();
+    let mut writer = ArrowWriter::try_new(writer, schema.clone(), Some(properties))?;
+
+    let mut batch = Vec::with_capacity(BATCH_ROWS);
+    for row in rows {
+        batch.push(row);
+        if batch.len() == BATCH_ROWS {
+            writer.write(&record_batch(&schema, &batch)?)?;
+            batch.clear();
+        }
+    }
+    if !batch.is_empty() {
+        writer.write(&record_batch(&schema, &batch)?)?;
+    }
+    writer.close()?;
+    Ok(())
+}
+
+fn record_batch(schema: &SchemaRef, rows: &[Row]) -> Result<RecordBatch, ChunkingError> {
+    let mut id = StringBuilder::new();
+    let mut document = StringBuilder::new();
+    let mut text = StringBuilder::new();
+    let mut headings = ListBuilder::new(StringBuilder::new());
+    let mut page = UInt32Builder::new();
+    let mut token_count = UInt64Builder::new();
+
+    for row in rows {
+        id.append_value(&row.id);
+        document.append_value(&row.document);
+        text.append_value(&row.text);
+        for heading in &row.headings {
+            headings.values().append_value(heading);
+        }
+        headings.append(true);
+        page.append_option(row.page.map(|p| p as u32));
+        token_count.append_value(row.token_count as u64);
+    }
+
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(id.finish()),
+        Arc::new(document.finish()),
+        Arc::new(text.finish()),
+        Arc::new(headings.finish()),
+        Arc::new(page.finish()),
+        Arc::new(token_count.finish()),
+    ];
+    RecordBatch::try_new(schema.clone(), columns).map_err(|e| ParquetError::from(e).into())
+}
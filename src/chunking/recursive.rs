@@ -0,0 +1,326 @@
+//! Recursive separator-based chunker implementation
+
+use super::base::{BaseChunk, BaseChunker, ChunkingError};
+use super::hierarchical::HierarchicalChunker;
+use super::metadata::ChunkMetadata;
+use crate::datamodel::DoclingDocument;
+use std::ops::Range;
+
+/// Default maximum chunk length in characters
+const DEFAULT_CHUNK_SIZE: usize = 1000;
+
+/// Default separators, from coarsest to finest: paragraphs, lines, sentences, words,
+/// and finally individual characters
+const DEFAULT_SEPARATORS: &[&str] = &["\n\n", "\n", ". ", " ", ""];
+
+/// Splits text by trying a prioritized list of separators
+///
+/// `RecursiveCharacterChunker` follows the strategy of LangChain's
+/// `RecursiveCharacterTextSplitter`. Each section of the document (the content
+/// under one heading path) is split on the first separator that occurs in it.
+/// Pieces that still exceed `chunk_size` are split again with the next separator,
+/// and adjacent small pieces are merged back together while they fit.
+///
+/// Sections are never merged with each other, so every chunk carries the heading
+/// path of its section. Lengths are measured in characters.
+///
+/// # Examples
+///
+/// ```ignore
+/// use docling_rs::chunking::{RecursiveCharacterChunker, BaseChunker};
+///
+/// let chunker = RecursiveCharacterChunker::builder()
+///     .chunk_size(500)
+///     .separators(vec!["\n\n".to_string(), ". ".to_string(), " ".to_string()])
+///     .build()?;
+///
+/// let chunks: Vec<_> = chunker.chunk(&doc).collect();
+/// ```
+#[derive(Debug, Clone)]
+pub struct RecursiveCharacterChunker {
+    chunk_size: usize,
+    separators: Vec<String>,
+    hierarchical: HierarchicalChunker,
+}
+
+impl RecursiveCharacterChunker {
+    /// Create a new RecursiveCharacterChunker with default settings
+    ///
+    /// # Returns
+    ///
+    /// A `RecursiveCharacterChunker` with:
+    /// - `chunk_size` = 1000 characters
+    /// - `separators` = paragraphs, lines, sentences, words, characters
+    pub fn new() -> Self {
+        Self {
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            separators: DEFAULT_SEPARATORS.iter().map(|s| s.to_string()).collect(),
+            hierarchical: HierarchicalChunker::new(),
+        }
+    }
+
+    /// Create a builder for configuring RecursiveCharacterChunker
+    pub fn builder() -> RecursiveCharacterChunkerBuilder {
+        RecursiveCharacterChunkerBuilder::new()
+    }
+}
+
+impl Default for RecursiveCharacterChunker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Builder for configuring RecursiveCharacterChunker
+pub struct RecursiveCharacterChunkerBuilder {
+    chunk_size: usize,
+    separators: Option<Vec<String>>,
+}
+
+impl RecursiveCharacterChunkerBuilder {
+    fn new() -> Self {
+        Self {
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            separators: None,
+        }
+    }
+
+    /// Set the maximum chunk length in characters
+    pub fn chunk_size(mut self, size: usize) -> Self {
+        self.chunk_size = size;
+        self
+    }
+
+    /// Set the separators to try, from coarsest to finest
+    ///
+    /// An empty string splits between individual characters. Without it, a piece
+    /// containing none of the separators is emitted as-is even if oversized.
+    pub fn separators(mut self, separators: Vec<String>) -> Self {
+        self.separators = Some(separators);
+        self
+    }
+
+    /// Build the RecursiveCharacterChunker
+    pub fn build(self) -> Result<RecursiveCharacterChunker, ChunkingError> {
+        if self.chunk_size == 0 {
+            return Err(ChunkingError::InvalidConfig(
+                "chunk_size must be greater than 0".to_string(),
+            ));
+        }
+
+        let separators = match self.separators {
+            Some(separators) if separators.is_empty() => {
+                return Err(ChunkingError::InvalidConfig(
+                    "separators must not be empty".to_string(),
+                ));
+            }
+            Some(separators) => separators,
+            None => DEFAULT_SEPARATORS.iter().map(|s| s.to_string()).collect(),
+        };
+
+        Ok(RecursiveCharacterChunker {
+            chunk_size: self.chunk_size,
+            separators,
+            hierarchical: HierarchicalChunker::new(),
+        })
+    }
+}
+
+/// A run of consecutive element chunks sharing the same heading path and caption
+struct Section {
+    text: String,
+    /// (byte offset in `text`, element chunk) for each joined element
+    parts: Vec<(usize, BaseChunk)>,
+}
+
+impl Section {
+    /// Find the element containing byte `pos` of the section text
+    fn part_at(&self, pos: usize) -> &(usize, BaseChunk) {
+        let i = self.parts.partition_point(|(start, _)| *start <= pos);
+        &self.parts[i.saturating_sub(1)]
+    }
+
+    /// Map a byte position in the section text to an offset in the document
+    fn doc_offset(&self, pos: usize) -> usize {
+        let (start, part) = self.part_at(pos);
+        part.meta.source_offset((pos - start).min(part.text.len()))
+    }
+}
+
+impl RecursiveCharacterChunker {
+    fn char_len(text: &str) -> usize {
+        text.chars().count()
+    }
+
+    /// Recursively split `range` of `text`, appending the final pieces to `out`
+    fn split_range(
+        &self,
+        text: &str,
+        range: Range<usize>,
+        separators: &[String],
+        out: &mut Vec<Range<usize>>,
+    ) {
+        let piece = &text[range.clone()];
+
+        // Use the first separator that actually occurs in this piece
+        let Some(i) = separators
+            .iter()
+            .position(|s| s.is_empty() || piece.contains(s.as_str()))
+        else {
+            out.push(range);
+            return;
+        };
+        let separator = &separators[i];
+        let finer = &separators[i + 1..];
+
+        // Separators stay attached to the end of the piece they terminate
+        let pieces: Vec<Range<usize>> = if separator.is_empty() {
+            piece
+                .char_indices()
+                .map(|(j, c)| range.start + j..range.start + j + c.len_utf8())
+                .collect()
+        } else {
+            let mut pieces = Vec::new();
+            let mut start = range.start;
+            for (j, _) in piece.match_indices(separator.as_str()) {
+                let end = range.start + j + separator.len();
+                pieces.push(start..end);
+                start = end;
+            }
+            if start < range.end {
+                pieces.push(start..range.end);
+            }
+            pieces
+        };
+
+        let mut fitting: Vec<Range<usize>> = Vec::new();
+        for piece in pieces {
+            if Self::char_len(&text[piece.clone()]) <= self.chunk_size {
+                fitting.push(piece);
+                continue;
+            }
+
+            self.merge_pieces(text, &fitting, out);
+            fitting.clear();
+
+            if finer.is_empty() {
+                out.push(piece);
+            } else {
+                self.split_range(text, piece, finer, out);
+            }
+        }
+        self.merge_pieces(text, &fitting, out);
+    }
+
+    /// Merge adjacent pieces while the result fits within `chunk_size`
+    fn merge_pieces(&self, text: &str, pieces: &[Range<usize>], out: &mut Vec<Range<usize>>) {
+        let mut current: Option<Range<usize>> = None;
+
+        for piece in pieces {
+            current = match current {
+                None => Some(piece.clone()),
+                Some(cur) if Self::char_len(&text[cur.start..piece.end]) <= self.chunk_size => {
+                    Some(cur.start..piece.end)
+                }
+                Some(cur) => {
+                    out.push(cur);
+                    Some(piece.clone())
+                }
+            };
+        }
+
+        if let Some(cur) = current {
+            out.push(cur);
+        }
+    }
+
+    /// Group element chunks into sections with identical heading paths
+    fn sections(&self, doc: &DoclingDocument) -> Vec<Section> {
+        let mut sections: Vec<Section> = Vec::new();
+
+        for chunk in self.hierarchical.chunk(doc) {
+            match sections.last_mut() {
+                Some(section)
+                    if section.parts[0].1.meta.headings == chunk.meta.headings
+                        && section.parts[0].1.meta.caption == chunk.meta.caption
+                        && section.parts[0].1.meta.is_code == chunk.meta.is_code =>
+                {
+                    section.text.push_str("\n\n");
+                    section.parts.push((section.text.len(), chunk.clone()));
+                    section.text.push_str(&chunk.text);
+                }
+                _ => sections.push(Section {
+                    text: chunk.text.clone(),
+                    parts: vec![(0, chunk)],
+                }),
+            }
+        }
+
+        sections
+    }
+}
+
+impl BaseChunker for RecursiveCharacterChunker {
+    fn chunk<'a>(&'a self, doc: &'a DoclingDocument) -> Box<dyn Iterator<Item = BaseChunk> + 'a> {
+        let mut chunks = Vec::new();
+
+        for section in self.sections(doc) {
+            let mut ranges = Vec::new();
+            self.split_range(
+                &section.text,
+                0..section.text.len(),
+                &self.separators,
+                &mut ranges,
+            );
+
+            for range in ranges {
+                // Drop the whitespace left over around separators
+                let raw = &section.text[range.clone()];
+                let start = range.start + (raw.len() - raw.trim_start().len());
+                let end = range.end - (raw.len() - raw.trim_end().len());
+                if start >= end {
+                    continue;
+                }
+
+                let (part_start, part) = section.part_at(start);
+                // Offset maps only carry over to pieces of a single element
+                let offsets = part
+                    .meta
+                    .offsets
+                    .as_ref()
+                    .filter(|_| end <= part_start + part.text.len())
+                    .map(|offsets| offsets.slice(start - part_start, end - part_start));
+                let mut meta = ChunkMetadata {
+                    start_offset: section.doc_offset(start),
+                    end_offset: section.doc_offset(end),
+                    index: chunks.len(),
+                    offsets,
+                    ..part.meta.clone()
+                };
+                // Pages and boxes span every element the chunk touches
+                for (_, part) in section
+                    .parts
+                    .iter()
+                    .filter(|(s, p)| *s < end && s + p.text.len() > start)
+                {
+                    meta.merge_provenance(&part.meta);
+                }
+
+                chunks.push(
+                    BaseChunk {
+                        text: section.text[start..end].to_string(),
+                        meta,
+                    }
+                    .with_chunk_id(),
+                );
+            }
+        }
+
+        Box::new(chunks.into_iter())
+    }
+
+    fn contextualize(&self, chunk: &BaseChunk) -> String {
+        // Use the same contextualization as HierarchicalChunker
+        self.hierarchical.contextualize(chunk)
+    }
+}
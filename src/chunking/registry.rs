@@ -0,0 +1,180 @@
+//! Name-based chunker registry
+
+use super::base::{BaseChunker, ChunkingError};
+use super::hierarchical::HierarchicalChunker;
+use super::hybrid::HybridChunker;
+use super::recursive::RecursiveCharacterChunker;
+use super::semantic::SemanticChunker;
+use super::tokenizer::Tokenizer;
+use super::window::WindowChunker;
+use crate::embeddings::Embedder;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+/// Settings passed to a chunker factory
+///
+/// Each strategy reads the settings that apply to it and ignores the rest.
+#[derive(Default)]
+pub struct ChunkerOptions {
+    /// Token limit per chunk (token-aware strategies)
+    pub max_tokens: Option<usize>,
+
+    /// Character limit per chunk (character-based strategies)
+    pub chunk_size: Option<usize>,
+
+    /// Tokenizer for token-aware strategies (default: [`ApproxTokenizer`](super::ApproxTokenizer))
+    pub tokenizer: Option<Box<dyn Tokenizer>>,
+
+    /// Embedder for similarity-based strategies (default: [`HashEmbedder`](crate::embeddings::HashEmbedder))
+    pub embedder: Option<Arc<dyn Embedder>>,
+}
+
+/// Factory building a chunker from options
+pub type ChunkerFactory =
+    Box<dyn Fn(ChunkerOptions) -> Result<Box<dyn BaseChunker>, ChunkingError> + Send + Sync>;
+
+/// Registry mapping strategy names to chunker factories
+///
+/// [`ChunkerRegistry::new`] comes with the built-in strategies:
+///
+/// - `hierarchical`: one chunk per document element ([`HierarchicalChunker`])
+/// - `hybrid`: token-aware splitting and merging ([`HybridChunker`]), using `max_tokens`
+/// - `recursive`: separator-based splitting ([`RecursiveCharacterChunker`]), using `chunk_size`
+/// - `fixed`: fixed-size character chunks on word boundaries, using `chunk_size`
+/// - `window`: overlapping token windows ([`WindowChunker`]), using `max_tokens` as
+///   the window size and half of it as the stride
+/// - `semantic`: elements grouped until the topic shifts ([`SemanticChunker`]), using
+///   `embedder` and `max_tokens`
+///
+/// Additional strategies can be added with [`ChunkerRegistry::register`].
+///
+/// # Examples
+///
+/// ```ignore
+/// use docling_rs::chunking::{ChunkerOptions, ChunkerRegistry};
+///
+/// let registry = ChunkerRegistry::new();
+/// let chunker = registry.create(
+///     "recursive",
+///     ChunkerOptions { chunk_size: Some(500), ..Default::default() },
+/// )?;
+///
+/// let chunks: Vec<_> = chunker.chunk(&doc).collect();
+/// ```
+pub struct ChunkerRegistry {
+    factories: BTreeMap<String, ChunkerFactory>,
+}
+
+impl ChunkerRegistry {
+    /// Create a registry with the built-in strategies
+    pub fn new() -> Self {
+        let mut registry = Self::empty();
+        registry.register("hierarchical", |_| Ok(Box::new(HierarchicalChunker::new())));
+        registry.register("hybrid", build_hybrid);
+        registry.register("window", build_window);
+        registry.register("semantic", build_semantic);
+        registry.register("recursive", |options| {
+            let mut builder = RecursiveCharacterChunker::builder();
+            if let Some(size) = options.chunk_size {
+                builder = builder.chunk_size(size);
+            }
+            Ok(Box::new(builder.build()?))
+        });
+        registry.register("fixed", |options| {
+            // Ignore paragraph and sentence structure, keep words whole
+            let mut builder = RecursiveCharacterChunker::builder()
+                .separators(vec![" ".to_string(), String::new()]);
+            if let Some(size) = options.chunk_size {
+                builder = builder.chunk_size(size);
+            }
+            Ok(Box::new(builder.build()?))
+        });
+        registry
+    }
+
+    /// Create a registry with no strategies
+    pub fn empty() -> Self {
+        Self {
+            factories: BTreeMap::new(),
+        }
+    }
+
+    /// Register a strategy, replacing any existing one with the same name
+    pub fn register<F>(&mut self, name: &str, factory: F)
+    where
+        F: Fn(ChunkerOptions) -> Result<Box<dyn BaseChunker>, ChunkingError>
+            + Send
+            + Sync
+            + 'static,
+    {
+        self.factories.insert(name.to_string(), Box::new(factory));
+    }
+
+    /// Build the chunker registered under `name`
+    pub fn create(
+        &self,
+        name: &str,
+        options: ChunkerOptions,
+    ) -> Result<Box<dyn BaseChunker>, ChunkingError> {
+        let factory = self.factories.get(name).ok_or_else(|| {
+            ChunkingError::InvalidConfig(format!(
+                "unknown chunker '{}' (available: {})",
+                name,
+                self.names().collect::<Vec<_>>().join(", ")
+            ))
+        })?;
+        factory(options)
+    }
+
+    /// Names of the registered strategies, in sorted order
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.factories.keys().map(String::as_str)
+    }
+
+    /// Check whether a strategy is registered
+    pub fn contains(&self, name: &str) -> bool {
+        self.factories.contains_key(name)
+    }
+}
+
+impl Default for ChunkerRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn build_hybrid(options: ChunkerOptions) -> Result<Box<dyn BaseChunker>, ChunkingError> {
+    let mut builder = HybridChunker::builder();
+    if let Some(tokenizer) = options.tokenizer {
+        builder = builder.tokenizer(tokenizer);
+    }
+    if let Some(max) = options.max_tokens {
+        builder = builder.max_tokens(max);
+    }
+    Ok(Box::new(builder.build()?))
+}
+
+fn build_window(options: ChunkerOptions) -> Result<Box<dyn BaseChunker>, ChunkingError> {
+    let mut builder = WindowChunker::builder();
+    if let Some(tokenizer) = options.tokenizer {
+        builder = builder.tokenizer(tokenizer);
+    }
+    if let Some(size) = options.max_tokens {
+        builder = builder.window_size(size);
+    }
+    Ok(Box::new(builder.build()?))
+}
+
+fn build_semantic(options: ChunkerOptions) -> Result<Box<dyn BaseChunker>, ChunkingError> {
+    let mut builder = SemanticChunker::builder();
+    if let Some(embedder) = options.embedder {
+        builder = builder.embedder(embedder);
+    }
+    if let Some(tokenizer) = options.tokenizer {
+        builder = builder.tokenizer(tokenizer);
+    }
+    if let Some(max) = options.max_tokens {
+        builder = builder.max_tokens(max);
+    }
+    Ok(Box::new(builder.build()?))
+}
@@ -0,0 +1,266 @@
+//! Semantic (embedding similarity) chunker implementation
+
+use super::base::{BaseChunk, BaseChunker, ChunkingError};
+use super::hierarchical::{push_context, HierarchicalChunker};
+use super::tokenizer::{ApproxTokenizer, Tokenizer};
+use crate::datamodel::DoclingDocument;
+use crate::embeddings::{cosine_similarity, Embedder, HashEmbedder};
+use std::sync::Arc;
+
+/// Default token limit per chunk
+const DEFAULT_MAX_TOKENS: usize = 512;
+
+/// Default breakpoint percentile
+const DEFAULT_BREAKPOINT_PERCENTILE: f32 = 90.0;
+
+/// Groups consecutive document elements into chunks, starting a new chunk
+/// where the topic shifts
+///
+/// `SemanticChunker` starts from the elements of the [`HierarchicalChunker`]
+/// and embeds each of them. Consecutive elements of the same section are
+/// grouped until the distance between two neighbours (one minus their cosine
+/// similarity) is above the `breakpoint_percentile` of all the neighbour
+/// distances of the document, or until the group would go over `max_tokens`.
+/// The breakpoint is relative, so it suits any embedding model without
+/// tuning a similarity threshold.
+///
+/// Code blocks and captioned elements (tables, figures) stay chunks of their
+/// own. Elements longer than `max_tokens` are not split; chain with a
+/// [`HybridChunker`](super::HybridChunker) pass if every chunk must fit.
+///
+/// If the embedder fails, a warning is logged and elements are grouped by
+/// section and token limit only.
+///
+/// # Examples
+///
+/// ```ignore
+/// use docling_rs::chunking::{BaseChunker, SemanticChunker};
+/// use docling_rs::embeddings;
+///
+/// let chunker = SemanticChunker::builder()
+///     .embedder(embeddings::from_spec("onnx:sentence-transformers/all-MiniLM-L6-v2")?.into())
+///     .max_tokens(256)
+///     .build()?;
+///
+/// let chunks: Vec<_> = chunker.chunk(&doc).collect();
+/// ```
+pub struct SemanticChunker {
+    embedder: Arc<dyn Embedder>,
+    tokenizer: Box<dyn Tokenizer>,
+    max_tokens: usize,
+    breakpoint_percentile: f32,
+    hierarchical: HierarchicalChunker,
+}
+
+impl SemanticChunker {
+    /// Create a new SemanticChunker with default settings
+    ///
+    /// # Returns
+    ///
+    /// A `SemanticChunker` with:
+    /// - `embedder` = [`HashEmbedder`] (lexical similarity only)
+    /// - `tokenizer` = [`ApproxTokenizer`]
+    /// - `max_tokens` = 512
+    /// - `breakpoint_percentile` = 90
+    pub fn new() -> Self {
+        Self {
+            embedder: Arc::new(HashEmbedder::default()),
+            tokenizer: Box::new(ApproxTokenizer::new()),
+            max_tokens: DEFAULT_MAX_TOKENS,
+            breakpoint_percentile: DEFAULT_BREAKPOINT_PERCENTILE,
+            hierarchical: HierarchicalChunker::new(),
+        }
+    }
+
+    /// Create a builder for configuring SemanticChunker
+    pub fn builder() -> SemanticChunkerBuilder {
+        SemanticChunkerBuilder::new()
+    }
+
+    /// For each element, whether the topic shifts after it
+    fn breakpoints(&self, units: &[BaseChunk]) -> Vec<bool> {
+        let mut breaks = vec![false; units.len()];
+        if units.len() < 2 {
+            return breaks;
+        }
+
+        let texts: Vec<String> = units
+            .iter()
+            .map(|unit| self.hierarchical.contextualize(unit))
+            .collect();
+        let texts: Vec<&str> = texts.iter().map(String::as_str).collect();
+        let vectors = match self.embedder.embed(&texts) {
+            Ok(vectors) if vectors.len() == units.len() => vectors,
+            Ok(vectors) => {
+                tracing::warn!(
+                    "Semantic chunking without embeddings: expected {} vectors, got {}",
+                    units.len(),
+                    vectors.len()
+                );
+                return breaks;
+            }
+            Err(e) => {
+                tracing::warn!("Semantic chunking without embeddings: {}", e);
+                return breaks;
+            }
+        };
+
+        let distances: Vec<f32> = vectors
+            .windows(2)
+            .map(|pair| 1.0 - cosine_similarity(&pair[0], &pair[1]))
+            .collect();
+        let threshold = percentile(&distances, self.breakpoint_percentile);
+        for (i, distance) in distances.iter().enumerate() {
+            breaks[i] = *distance > threshold;
+        }
+        breaks
+    }
+}
+
+impl Default for SemanticChunker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Builder for configuring SemanticChunker
+pub struct SemanticChunkerBuilder {
+    embedder: Option<Arc<dyn Embedder>>,
+    tokenizer: Option<Box<dyn Tokenizer>>,
+    max_tokens: usize,
+    breakpoint_percentile: f32,
+}
+
+impl SemanticChunkerBuilder {
+    fn new() -> Self {
+        Self {
+            embedder: None,
+            tokenizer: None,
+            max_tokens: DEFAULT_MAX_TOKENS,
+            breakpoint_percentile: DEFAULT_BREAKPOINT_PERCENTILE,
+        }
+    }
+
+    /// Set the embedder comparing neighbouring elements
+    ///
+    /// Defaults to [`HashEmbedder`].
+    pub fn embedder(mut self, embedder: Arc<dyn Embedder>) -> Self {
+        self.embedder = Some(embedder);
+        self
+    }
+
+    /// Set the tokenizer used to measure chunks
+    ///
+    /// Defaults to [`ApproxTokenizer`].
+    pub fn tokenizer(mut self, tokenizer: Box<dyn Tokenizer>) -> Self {
+        self.tokenizer = Some(tokenizer);
+        self
+    }
+
+    /// Set the token limit per chunk
+    pub fn max_tokens(mut self, max: usize) -> Self {
+        self.max_tokens = max;
+        self
+    }
+
+    /// Set the percentile (0 to 100) of neighbour distances above which a
+    /// new chunk starts
+    ///
+    /// Lower values break more often. Defaults to 90.
+    pub fn breakpoint_percentile(mut self, percentile: f32) -> Self {
+        self.breakpoint_percentile = percentile;
+        self
+    }
+
+    /// Build the SemanticChunker
+    pub fn build(self) -> Result<SemanticChunker, ChunkingError> {
+        if self.max_tokens == 0 {
+            return Err(ChunkingError::InvalidConfig(
+                "max_tokens must be greater than 0".to_string(),
+            ));
+        }
+        if !(0.0..=100.0).contains(&self.breakpoint_percentile) {
+            return Err(ChunkingError::InvalidConfig(format!(
+                "breakpoint_percentile ({}) must be between 0 and 100",
+                self.breakpoint_percentile
+            )));
+        }
+
+        Ok(SemanticChunker {
+            embedder: self
+                .embedder
+                .unwrap_or_else(|| Arc::new(HashEmbedder::default())),
+            tokenizer: self
+                .tokenizer
+                .unwrap_or_else(|| Box::new(ApproxTokenizer::new())),
+            max_tokens: self.max_tokens,
+            breakpoint_percentile: self.breakpoint_percentile,
+            hierarchical: HierarchicalChunker::new(),
+        })
+    }
+}
+
+impl BaseChunker for SemanticChunker {
+    fn chunk<'a>(&'a self, doc: &'a DoclingDocument) -> Box<dyn Iterator<Item = BaseChunk> + 'a> {
+        let units: Vec<BaseChunk> = self.hierarchical.chunk(doc).collect();
+        let breaks = self.breakpoints(&units);
+
+        let mut chunks: Vec<BaseChunk> = Vec::new();
+        let mut group_open = false;
+        // Contextualized text of the merge being tried, reused across merges
+        let mut buffer = String::new();
+        for (i, unit) in units.into_iter().enumerate() {
+            let atomic = unit.meta.is_code || unit.meta.caption.is_some();
+            let merged = match chunks.last_mut() {
+                Some(prev)
+                    if group_open
+                        && !atomic
+                        && !breaks[i - 1]
+                        && prev.meta.headings == unit.meta.headings =>
+                {
+                    buffer.clear();
+                    push_context(&mut buffer, &prev.meta.headings, None);
+                    buffer.push_str(&prev.text);
+                    buffer.push(' ');
+                    buffer.push_str(&unit.text);
+                    let fits = self.tokenizer.count_tokens(&buffer) <= self.max_tokens;
+                    if fits {
+                        prev.text.push(' ');
+                        prev.text.push_str(&unit.text);
+                        prev.meta.end_offset = unit.meta.end_offset;
+                        prev.meta.merge_provenance(&unit.meta);
+                        // The joined text holds separators that are not in the source
+                        prev.meta.offsets = None;
+                    }
+                    fits
+                }
+                _ => false,
+            };
+            if !merged {
+                chunks.push(unit);
+            }
+            group_open = !atomic;
+        }
+
+        for (i, chunk) in chunks.iter_mut().enumerate() {
+            chunk.meta.index = i;
+        }
+        Box::new(chunks.into_iter().map(BaseChunk::with_chunk_id))
+    }
+
+    fn contextualize(&self, chunk: &BaseChunk) -> String {
+        self.hierarchical.contextualize(chunk)
+    }
+}
+
+/// The `p`-th percentile of `values`, interpolating between neighbours
+fn percentile(values: &[f32], p: f32) -> f32 {
+    let mut sorted = values.to_vec();
+    sorted.sort_by(f32::total_cmp);
+    let Some(last) = sorted.len().checked_sub(1) else {
+        return 0.0;
+    };
+    let rank = p / 100.0 * last as f32;
+    let (low, high) = (rank.floor() as usize, rank.ceil() as usize);
+    sorted[low] + (sorted[high] - sorted[low]) * (rank - low as f32)
+}
@@ -0,0 +1,300 @@
+//! Local vector store
+
+use super::ChunkSink;
+use crate::chunking::base::ChunkingError;
+use crate::chunking::export::ChunkRecord;
+use crate::chunking::metadata::chunk_id;
+use crate::embeddings::cosine_similarity;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+/// Name of the file describing an index
+const MANIFEST: &str = "index.json";
+
+/// Value of [`Manifest::format`]
+const FORMAT: &str = "docling-rs-index";
+
+/// Version of the index layout
+const VERSION: u32 = 1;
+
+/// Contents of `index.json`
+#[derive(Debug, Serialize, Deserialize)]
+struct Manifest {
+    format: String,
+    version: u32,
+    #[serde(default)]
+    model: Option<String>,
+    #[serde(default)]
+    dimensions: Option<usize>,
+}
+
+/// A chunk found by [`LocalStore::search`]
+#[derive(Debug, Clone, Copy)]
+pub struct SearchHit<'a> {
+    /// Cosine similarity of the chunk to the query
+    pub score: f32,
+
+    /// The chunk, with its embedding
+    pub record: &'a ChunkRecord,
+}
+
+/// A vector index in a local directory, searched by exact cosine similarity
+///
+/// The directory holds an `index.json` manifest (embedding model and
+/// dimensions) and one JSON Lines file of [`ChunkRecord`]s per document, so
+/// storing a document again only rewrites its own file. Every chunk is kept
+/// in memory while the store is open, which suits the tens of thousands of
+/// chunks of a folder of documents.
+///
+/// ```ignore
+/// use docling_rs::chunking::sinks::{ChunkSink, LocalStore};
+///
+/// let mut store = LocalStore::open("./index")?;
+/// store.set_model(embedder.model())?;
+/// store.write(&records)?;
+///
+/// let query = embedder.embed(&["How do I configure it?"])?.remove(0);
+/// for hit in store.search(&query, 5) {
+///     println!("{:.3} {}", hit.score, hit.record.text);
+/// }
+/// ```
+#[derive(Debug)]
+pub struct LocalStore {
+    dir: PathBuf,
+    model: Option<String>,
+    dimensions: Option<usize>,
+    /// Records of each document, by document name
+    documents: BTreeMap<String, Vec<ChunkRecord>>,
+}
+
+impl LocalStore {
+    /// Open the index in `dir`, creating it if `dir` does not exist or is
+    /// empty
+    ///
+    /// Fails on directories holding other files, so an index is never mixed
+    /// into an unrelated directory.
+    pub fn open(dir: impl Into<PathBuf>) -> Result<Self, ChunkingError> {
+        let dir = dir.into();
+        let manifest_path = dir.join(MANIFEST);
+        if !manifest_path.exists() {
+            if dir.exists() && fs::read_dir(&dir)?.next().is_some() {
+                return Err(ChunkingError::Sink(format!(
+                    "{} is not empty and holds no {}, so it is not a docling-rs index",
+                    dir.display(),
+                    MANIFEST
+                )));
+            }
+            fs::create_dir_all(&dir)?;
+            let store = Self {
+                dir,
+                model: None,
+                dimensions: None,
+                documents: BTreeMap::new(),
+            };
+            store.write_manifest()?;
+            return Ok(store);
+        }
+
+        let manifest: Manifest = serde_json::from_slice(&fs::read(&manifest_path)?)?;
+        if manifest.format != FORMAT || manifest.version != VERSION {
+            return Err(ChunkingError::Sink(format!(
+                "{} is not a version {} docling-rs index",
+                dir.display(),
+                VERSION
+            )));
+        }
+        let mut documents = BTreeMap::new();
+        for entry in fs::read_dir(&dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("jsonl") {
+                continue;
+            }
+            let mut records = Vec::new();
+            for line in BufReader::new(fs::File::open(&path)?).lines() {
+                records.push(serde_json::from_str::<ChunkRecord>(&line?)?);
+            }
+            if let Some(first) = records.first() {
+                documents.insert(first.meta.doc_name.clone(), records);
+            }
+        }
+        Ok(Self {
+            dir,
+            model: manifest.model,
+            dimensions: manifest.dimensions,
+            documents,
+        })
+    }
+
+    /// Directory of the index
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Embedding model of the vectors, once set
+    pub fn model(&self) -> Option<&str> {
+        self.model.as_deref()
+    }
+
+    /// Record that vectors come from `model`, failing if the index holds
+    /// vectors of another model (which would not be comparable)
+    pub fn set_model(&mut self, model: &str) -> Result<(), ChunkingError> {
+        match &self.model {
+            Some(current) if current == model => Ok(()),
+            Some(current) => Err(ChunkingError::Sink(format!(
+                "{} holds embeddings of {}, not {}",
+                self.dir.display(),
+                current,
+                model
+            ))),
+            None => {
+                self.model = Some(model.to_string());
+                self.write_manifest()
+            }
+        }
+    }
+
+    /// Number of dimensions of the vectors, once a chunk is stored
+    pub fn dimensions(&self) -> Option<usize> {
+        self.dimensions
+    }
+
+    /// Number of chunks stored
+    pub fn len(&self) -> usize {
+        self.documents.values().map(Vec::len).sum()
+    }
+
+    /// Whether no chunk is stored
+    pub fn is_empty(&self) -> bool {
+        self.documents.is_empty()
+    }
+
+    /// Names of the documents stored, in order
+    pub fn documents(&self) -> impl Iterator<Item = &str> {
+        self.documents.keys().map(String::as_str)
+    }
+
+    /// Remove every chunk of `doc_name`, returning whether it had any
+    ///
+    /// Storing a new version of a document after removing the old one drops
+    /// chunks that are no longer in it.
+    pub fn remove_document(&mut self, doc_name: &str) -> Result<bool, ChunkingError> {
+        if self.documents.remove(doc_name).is_none() {
+            return Ok(false);
+        }
+        fs::remove_file(self.document_path(doc_name))?;
+        Ok(true)
+    }
+
+    /// The `limit` chunks most similar to `query`, best first
+    pub fn search(&self, query: &[f32], limit: usize) -> Vec<SearchHit<'_>> {
+        let mut hits: Vec<SearchHit<'_>> = self
+            .documents
+            .values()
+            .flatten()
+            .filter_map(|record| {
+                let vector = record.embedding.as_deref()?;
+                Some(SearchHit {
+                    score: cosine_similarity(query, vector),
+                    record,
+                })
+            })
+            .collect();
+        hits.sort_by(|a, b| b.score.total_cmp(&a.score));
+        hits.truncate(limit);
+        hits
+    }
+
+    /// File holding the chunks of `doc_name`
+    fn document_path(&self, doc_name: &str) -> PathBuf {
+        let digest = format!("{:x}", Sha256::digest(doc_name.as_bytes()));
+        self.dir.join(format!("{}.jsonl", &digest[..32]))
+    }
+
+    fn write_manifest(&self) -> Result<(), ChunkingError> {
+        let manifest = Manifest {
+            format: FORMAT.to_string(),
+            version: VERSION,
+            model: self.model.clone(),
+            dimensions: self.dimensions,
+        };
+        write_atomic(
+            &self.dir.join(MANIFEST),
+            &serde_json::to_vec_pretty(&manifest)?,
+        )
+    }
+}
+
+impl ChunkSink for LocalStore {
+    fn write(&mut self, records: &[ChunkRecord]) -> Result<(), ChunkingError> {
+        // Check every record before storing any
+        let mut dimensions = self.dimensions;
+        for record in records {
+            let Some(vector) = &record.embedding else {
+                return Err(ChunkingError::Sink(format!(
+                    "Chunk of {} has no embedding to store",
+                    record.meta.doc_name
+                )));
+            };
+            match dimensions {
+                Some(expected) if expected != vector.len() => {
+                    return Err(ChunkingError::Sink(format!(
+                        "{} holds vectors of {} dimensions, not {}",
+                        self.dir.display(),
+                        expected,
+                        vector.len()
+                    )))
+                }
+                _ => dimensions = Some(vector.len()),
+            }
+        }
+        if dimensions != self.dimensions {
+            self.dimensions = dimensions;
+            self.write_manifest()?;
+        }
+
+        let mut changed = Vec::new();
+        for record in records {
+            let mut record = record.clone();
+            if record.meta.chunk_id.is_empty() {
+                record.meta.chunk_id =
+                    chunk_id(&record.meta.doc_name, &record.meta.headings, &record.text);
+            }
+            let doc_name = record.meta.doc_name.clone();
+            let stored = self.documents.entry(doc_name.clone()).or_default();
+            match stored
+                .iter_mut()
+                .find(|stored| stored.meta.chunk_id == record.meta.chunk_id)
+            {
+                Some(stored) => *stored = record,
+                None => stored.push(record),
+            }
+            if !changed.contains(&doc_name) {
+                changed.push(doc_name);
+            }
+        }
+
+        for doc_name in changed {
+            let mut content = Vec::new();
+            for record in &self.documents[&doc_name] {
+                serde_json::to_writer(&mut content, record)?;
+                content.push(b'\n');
+            }
+            write_atomic(&self.document_path(&doc_name), &content)?;
+        }
+        Ok(())
+    }
+}
+
+/// Write `content` to `path` atomically, so an interrupted write never
+/// leaves a truncated file in the index
+fn write_atomic(path: &Path, content: &[u8]) -> Result<(), ChunkingError> {
+    let dir = path.parent().unwrap_or(Path::new("."));
+    let mut temp = tempfile::NamedTempFile::new_in(dir)?;
+    temp.write_all(content)?;
+    temp.persist(path).map_err(|e| e.error)?;
+    Ok(())
+}
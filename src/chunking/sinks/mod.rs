@@ -0,0 +1,40 @@
+//! Chunk sinks
+//!
+//! A [`ChunkSink`] takes embedded [`ChunkRecord`]s and stores them where
+//! retrieval happens, so ingestion needs no glue code between chunking and
+//! the vector store:
+//!
+//! - [`LocalStore`]: a vector index in a local directory, searchable with
+//!   [`LocalStore::search`]
+//! - [`QdrantSink`]: upserts points into a Qdrant collection over its REST
+//!   API (requires the `qdrant` feature)
+//!
+//! ```ignore
+//! use docling_rs::chunking::sinks::{ChunkSink, QdrantSink};
+//! use docling_rs::embeddings;
+//!
+//! embeddings::embed_records(&mut records, embedder.as_ref())?;
+//! let mut sink = QdrantSink::new("http://localhost:6333", "docs");
+//! sink.write(&records)?;
+//! ```
+
+mod local;
+#[cfg(feature = "qdrant")]
+mod qdrant;
+
+pub use local::{LocalStore, SearchHit};
+#[cfg(feature = "qdrant")]
+pub use qdrant::QdrantSink;
+
+use super::base::ChunkingError;
+use super::export::ChunkRecord;
+
+/// Stores embedded chunks
+pub trait ChunkSink {
+    /// Store `records`, which must all carry their
+    /// [`embedding`](ChunkRecord::embedding)
+    ///
+    /// Writing a chunk again replaces it: records are keyed by their
+    /// [chunk ID](crate::chunking::chunk_id).
+    fn write(&mut self, records: &[ChunkRecord]) -> Result<(), ChunkingError>;
+}
@@ -0,0 +1,224 @@
+//! Qdrant sink
+
+use super::ChunkSink;
+use crate::chunking::base::ChunkingError;
+use crate::chunking::export::ChunkRecord;
+use crate::chunking::metadata::chunk_id;
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::time::Duration;
+
+/// Default number of points per upsert request
+const DEFAULT_BATCH_SIZE: usize = 64;
+
+/// Upserts chunks into a Qdrant collection over its REST API
+///
+/// Each record becomes a point whose vector is the embedding and whose
+/// payload is the record itself (`text`, `contextualized_text`,
+/// `token_count` and `meta`), so searches can filter on `meta.doc_name`,
+/// `meta.headings` and the like. Point IDs are UUIDs derived from chunk
+/// IDs, so ingesting a document again updates its points instead of
+/// duplicating them.
+///
+/// The collection is created on the first write if it does not exist, sized
+/// after the first embedding, with cosine distance. Points are sent in
+/// batches; batches failing with a network error, `429` or a `5xx` are
+/// retried with exponential backoff.
+///
+/// [`QdrantSink::new`] reads the API key from `QDRANT_API_KEY`, if set.
+#[derive(Debug, Clone)]
+pub struct QdrantSink {
+    url: String,
+    collection: String,
+    api_key: Option<String>,
+    batch_size: usize,
+    max_retries: u32,
+    retry_delay: Duration,
+    timeout: Duration,
+    collection_ready: bool,
+}
+
+impl QdrantSink {
+    /// Create a sink writing to `collection` of the Qdrant instance at `url`
+    /// (such as `http://localhost:6333`)
+    pub fn new(url: impl Into<String>, collection: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            collection: collection.into(),
+            api_key: std::env::var("QDRANT_API_KEY")
+                .ok()
+                .filter(|key| !key.is_empty()),
+            batch_size: DEFAULT_BATCH_SIZE,
+            max_retries: 3,
+            retry_delay: Duration::from_millis(500),
+            timeout: Duration::from_secs(60),
+            collection_ready: false,
+        }
+    }
+
+    /// Authenticate with `api_key`
+    pub fn with_api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = Some(api_key.into());
+        self
+    }
+
+    /// Send at most `batch_size` points per request (default: 64)
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Retry a failed request up to `max_retries` times (default: 3)
+    pub fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Wait `delay` before the first retry, doubling it for each further
+    /// one (default: 500 ms)
+    pub fn with_retry_delay(mut self, delay: Duration) -> Self {
+        self.retry_delay = delay;
+        self
+    }
+
+    /// Give up on a request after `timeout` (default: 60 s)
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Create the collection, for vectors of `dimensions` dimensions, unless
+    /// it exists
+    fn ensure_collection(&mut self, dimensions: usize) -> Result<(), ChunkingError> {
+        if self.collection_ready {
+            return Ok(());
+        }
+        let path = format!("/collections/{}", self.collection);
+        match self.send("GET", &path, None) {
+            Ok(_) => {}
+            Err(e) if matches!(*e, ureq::Error::Status(404, _)) => {
+                let body = json!({ "vectors": { "size": dimensions, "distance": "Cosine" } });
+                self.send("PUT", &path, Some(&body))
+                    .map_err(|e| self.error("create the collection", e))?;
+            }
+            Err(e) => return Err(self.error("look up the collection", e)),
+        }
+        self.collection_ready = true;
+        Ok(())
+    }
+
+    /// Send a request, retrying network errors, `429`s and `5xx`s
+    fn send(
+        &self,
+        method: &str,
+        path: &str,
+        body: Option<&Value>,
+    ) -> Result<ureq::Response, Box<ureq::Error>> {
+        let url = format!("{}{}", self.url.trim_end_matches('/'), path);
+        let mut delay = self.retry_delay;
+        let mut attempt = 0;
+        loop {
+            let mut request = ureq::request(method, &url).timeout(self.timeout);
+            if let Some(key) = &self.api_key {
+                request = request.set("api-key", key);
+            }
+            let result = match body {
+                Some(body) => request
+                    .set("Content-Type", "application/json")
+                    .send_string(&body.to_string()),
+                None => request.call(),
+            };
+            match result {
+                Err(e) if attempt < self.max_retries && retryable(&e) => {
+                    std::thread::sleep(delay);
+                    delay *= 2;
+                    attempt += 1;
+                }
+                result => return result.map_err(Box::new),
+            }
+        }
+    }
+
+    fn error(&self, action: &str, error: Box<ureq::Error>) -> ChunkingError {
+        let message = match *error {
+            ureq::Error::Status(status, response) => format!(
+                "{} returned {}: {}",
+                self.url,
+                status,
+                response.into_string().unwrap_or_default().trim()
+            ),
+            e => e.to_string(),
+        };
+        ChunkingError::Sink(format!(
+            "Failed to {} {} in Qdrant: {}",
+            action, self.collection, message
+        ))
+    }
+}
+
+impl ChunkSink for QdrantSink {
+    fn write(&mut self, records: &[ChunkRecord]) -> Result<(), ChunkingError> {
+        let points = records.iter().map(point).collect::<Result<Vec<_>, _>>()?;
+        let Some(dimensions) = records
+            .first()
+            .and_then(|record| record.embedding.as_ref())
+            .map(Vec::len)
+        else {
+            return Ok(());
+        };
+        self.ensure_collection(dimensions)?;
+
+        let path = format!("/collections/{}/points?wait=true", self.collection);
+        for batch in points.chunks(self.batch_size) {
+            self.send("PUT", &path, Some(&json!({ "points": batch })))
+                .map_err(|e| self.error("upsert points into", e))?;
+        }
+        Ok(())
+    }
+}
+
+/// Whether a failed request may succeed when sent again
+fn retryable(error: &ureq::Error) -> bool {
+    match error {
+        ureq::Error::Status(status, _) => *status == 429 || *status >= 500,
+        ureq::Error::Transport(_) => true,
+    }
+}
+
+/// The Qdrant point of `record`
+fn point(record: &ChunkRecord) -> Result<Value, ChunkingError> {
+    let id = point_id(record);
+    let vector = record
+        .embedding
+        .as_ref()
+        .ok_or_else(|| ChunkingError::Sink(format!("Chunk {} has no embedding to store", id)))?;
+    let mut payload = serde_json::to_value(record)?;
+    if let Some(payload) = payload.as_object_mut() {
+        payload.remove("embedding");
+    }
+    Ok(json!({ "id": id, "vector": vector, "payload": payload }))
+}
+
+/// A UUID for `record`: the start of its chunk ID, which is a SHA-256 hex
+/// digest (other IDs set by hand are hashed first)
+fn point_id(record: &ChunkRecord) -> String {
+    let meta = &record.meta;
+    let id = if meta.chunk_id.is_empty() {
+        chunk_id(&meta.doc_name, &meta.headings, &record.text)
+    } else {
+        meta.chunk_id.clone()
+    };
+    let hex = if id.len() >= 32 && id.bytes().all(|b| b.is_ascii_hexdigit()) {
+        id.to_ascii_lowercase()
+    } else {
+        format!("{:x}", Sha256::digest(id.as_bytes()))
+    };
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..32]
+    )
+}
@@ -0,0 +1,127 @@
+//! Chunk statistics for validating chunking configurations
+
+use super::base::{BaseChunk, BaseChunker};
+use super::tokenizer::Tokenizer;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Summary statistics over a set of chunks
+///
+/// Token counts are measured on the contextualized text, which is what gets
+/// embedded. Computing statistics before embedding a large corpus helps catch
+/// configurations that produce oversized or empty chunks.
+///
+/// # Examples
+///
+/// ```ignore
+/// use docling_rs::chunking::{ApproxTokenizer, BaseChunker, ChunkStats, HierarchicalChunker};
+///
+/// let chunker = HierarchicalChunker::new();
+/// let chunks: Vec<_> = chunker.chunk(&doc).collect();
+/// let stats = ChunkStats::compute(&chunks, &chunker, &ApproxTokenizer::new(), 512);
+/// println!("{}", stats);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ChunkStats {
+    /// Number of chunks
+    pub chunk_count: usize,
+
+    /// Smallest token count
+    pub min_tokens: usize,
+
+    /// Mean token count
+    pub mean_tokens: f64,
+
+    /// 95th percentile token count (nearest rank)
+    pub p95_tokens: usize,
+
+    /// Largest token count
+    pub max_tokens: usize,
+
+    /// Token limit the chunks were checked against
+    pub token_limit: usize,
+
+    /// Number of chunks exceeding `token_limit`
+    pub oversize_count: usize,
+
+    /// Number of chunks whose text is empty or whitespace only
+    pub empty_count: usize,
+}
+
+impl ChunkStats {
+    /// Compute statistics for `chunks`, checking them against `token_limit`
+    pub fn compute(
+        chunks: &[BaseChunk],
+        chunker: &dyn BaseChunker,
+        tokenizer: &dyn Tokenizer,
+        token_limit: usize,
+    ) -> Self {
+        let mut counts: Vec<usize> = chunks
+            .iter()
+            .map(|chunk| tokenizer.count_tokens(&chunker.contextualize(chunk)))
+            .collect();
+        counts.sort_unstable();
+
+        let empty_count = chunks.iter().filter(|c| c.text.trim().is_empty()).count();
+
+        let Some(&max_tokens) = counts.last() else {
+            return Self {
+                token_limit,
+                ..Default::default()
+            };
+        };
+
+        let total: usize = counts.iter().sum();
+        // Nearest-rank percentile: smallest value with at least 95% of counts at or below it
+        let p95_rank = (counts.len() * 95).div_ceil(100).max(1);
+
+        Self {
+            chunk_count: counts.len(),
+            min_tokens: counts[0],
+            mean_tokens: total as f64 / counts.len() as f64,
+            p95_tokens: counts[p95_rank - 1],
+            max_tokens,
+            token_limit,
+            oversize_count: counts.iter().filter(|&&n| n > token_limit).count(),
+            empty_count,
+        }
+    }
+
+    /// Human-readable warnings about problems in the chunk set
+    pub fn warnings(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+        if self.chunk_count == 0 {
+            warnings.push("no chunks were produced".to_string());
+        }
+        if self.oversize_count > 0 {
+            warnings.push(format!(
+                "{} chunk(s) exceed the {}-token limit",
+                self.oversize_count, self.token_limit
+            ));
+        }
+        if self.empty_count > 0 {
+            warnings.push(format!("{} chunk(s) are empty", self.empty_count));
+        }
+        warnings
+    }
+}
+
+impl fmt::Display for ChunkStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Chunks: {}", self.chunk_count)?;
+        writeln!(
+            f,
+            "Tokens: min {} / mean {:.1} / p95 {} / max {}",
+            self.min_tokens, self.mean_tokens, self.p95_tokens, self.max_tokens
+        )?;
+        write!(
+            f,
+            "Oversize (> {} tokens): {}",
+            self.token_limit, self.oversize_count
+        )?;
+        for warning in self.warnings() {
+            write!(f, "\nWarning: {}", warning)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,64 @@
+//! Dependency-free approximate tokenizer
+
+use super::base::Tokenizer;
+
+/// Average characters per token for English text with BPE/WordPiece vocabularies
+const CHARS_PER_TOKEN: usize = 4;
+
+/// Default token limit, matching common sentence-transformers models
+const DEFAULT_MAX_TOKENS: usize = 512;
+
+/// Heuristic tokenizer that estimates token counts without a vocabulary
+///
+/// Estimates roughly 4 characters per token, and never fewer tokens than there
+/// are words, which keeps counts for short-word text from falling too low.
+/// Estimates are typically within 10-20% of real subword tokenizers for
+/// English prose; use a real tokenizer when budgets must be exact.
+///
+/// This is the fallback used by [`HybridChunkerBuilder`](crate::chunking::HybridChunkerBuilder)
+/// when no tokenizer is configured.
+///
+/// # Examples
+///
+/// ```ignore
+/// use docling_rs::chunking::tokenizer::{ApproxTokenizer, Tokenizer};
+///
+/// let tokenizer = ApproxTokenizer::new();
+/// assert_eq!(tokenizer.count_tokens("Hello world"), 3);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ApproxTokenizer {
+    max_tokens: usize,
+}
+
+impl ApproxTokenizer {
+    /// Create an approximate tokenizer with a 512-token limit
+    pub fn new() -> Self {
+        Self {
+            max_tokens: DEFAULT_MAX_TOKENS,
+        }
+    }
+
+    /// Create an approximate tokenizer with a custom token limit
+    pub fn with_max_tokens(max_tokens: usize) -> Self {
+        Self { max_tokens }
+    }
+}
+
+impl Default for ApproxTokenizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Tokenizer for ApproxTokenizer {
+    fn count_tokens(&self, text: &str) -> usize {
+        let chars = text.chars().count();
+        let words = text.split_whitespace().count();
+        chars.div_ceil(CHARS_PER_TOKEN).max(words)
+    }
+
+    fn max_tokens(&self) -> usize {
+        self.max_tokens
+    }
+}
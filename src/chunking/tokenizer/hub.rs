@@ -0,0 +1,164 @@
+//! HuggingFace Hub file download with local caching
+
+use crate::chunking::base::ChunkingError;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Default Hub endpoint
+const DEFAULT_ENDPOINT: &str = "https://huggingface.co";
+
+/// Options controlling Hub downloads
+///
+/// [`HubOptions::default`] reads the usual HuggingFace environment variables:
+///
+/// - `HF_ENDPOINT`: alternative Hub endpoint (mirrors)
+/// - `HF_TOKEN`: access token for gated or private models
+/// - `HF_HUB_OFFLINE=1`: never touch the network, use cached files only
+/// - `DOCLING_CACHE_DIR`: cache directory (default: `~/.cache/docling-rs/hub`)
+#[derive(Debug, Clone)]
+pub struct HubOptions {
+    /// Directory where downloaded files are cached
+    pub cache_dir: PathBuf,
+
+    /// Branch, tag or commit to download from
+    pub revision: String,
+
+    /// Use cached files only
+    pub offline: bool,
+
+    /// Access token sent as a bearer token
+    pub token: Option<String>,
+
+    /// Hub base URL
+    pub endpoint: String,
+
+    /// Network timeout
+    pub timeout: Duration,
+}
+
+impl Default for HubOptions {
+    fn default() -> Self {
+        let cache_dir = std::env::var_os("DOCLING_CACHE_DIR")
+            .map(PathBuf::from)
+            .unwrap_or_else(|| {
+                let home = std::env::var("HOME")
+                    .or_else(|_| std::env::var("USERPROFILE"))
+                    .unwrap_or_else(|_| ".".to_string());
+                Path::new(&home).join(".cache").join("docling-rs")
+            })
+            .join("hub");
+
+        Self {
+            cache_dir,
+            revision: "main".to_string(),
+            offline: std::env::var("HF_HUB_OFFLINE").is_ok_and(|v| v == "1" || v == "true"),
+            token: std::env::var("HF_TOKEN").ok().filter(|t| !t.is_empty()),
+            endpoint: std::env::var("HF_ENDPOINT").unwrap_or_else(|_| DEFAULT_ENDPOINT.to_string()),
+            timeout: Duration::from_secs(30),
+        }
+    }
+}
+
+impl HubOptions {
+    /// Local path of a cached file
+    pub fn cached_path(&self, model_id: &str, filename: &str) -> PathBuf {
+        self.cache_dir
+            .join(format!("models--{}", model_id.replace('/', "--")))
+            .join(&self.revision)
+            .join(filename)
+    }
+}
+
+/// Fetch a file from a Hub model repository, returning its local path
+///
+/// Cached copies are revalidated with the stored ETag, so unchanged files are
+/// not downloaded again. If the Hub cannot be reached, a cached copy is used
+/// when one exists. In offline mode only the cache is consulted.
+pub fn fetch_file(
+    model_id: &str,
+    filename: &str,
+    options: &HubOptions,
+) -> Result<PathBuf, ChunkingError> {
+    let path = options.cached_path(model_id, filename);
+    let etag_path = path.with_extension("etag");
+    let cached = path.exists();
+
+    if options.offline {
+        return if cached {
+            Ok(path)
+        } else {
+            Err(ChunkingError::TokenizerLoad(format!(
+                "'{}' for '{}' is not cached and offline mode is enabled",
+                filename, model_id
+            )))
+        };
+    }
+
+    let url = format!(
+        "{}/{}/resolve/{}/{}",
+        options.endpoint.trim_end_matches('/'),
+        model_id,
+        options.revision,
+        filename
+    );
+
+    let mut request = ureq::get(&url).timeout(options.timeout);
+    if let Some(token) = &options.token {
+        request = request.set("Authorization", &format!("Bearer {}", token));
+    }
+    if cached {
+        if let Ok(etag) = fs::read_to_string(&etag_path) {
+            request = request.set("If-None-Match", etag.trim());
+        }
+    }
+
+    let response = match request.call() {
+        Ok(response) => response,
+        // Fall back to the cached copy when the Hub is unreachable
+        Err(ureq::Error::Transport(_)) if cached => return Ok(path),
+        Err(e) => {
+            return Err(ChunkingError::TokenizerLoad(format!(
+                "Failed to download {}: {}",
+                url, e
+            )))
+        }
+    };
+
+    if response.status() == 304 {
+        return Ok(path);
+    }
+
+    // LFS files report their content hash in X-Linked-Etag
+    let etag = response
+        .header("X-Linked-Etag")
+        .or_else(|| response.header("ETag"))
+        .map(str::to_string);
+
+    write_cache(&path, response.into_reader())
+        .map_err(|e| ChunkingError::TokenizerLoad(format!("Failed to cache {}: {}", url, e)))?;
+    match etag {
+        Some(etag) => fs::write(&etag_path, etag),
+        None => fs::remove_file(&etag_path).or_else(|e| match e.kind() {
+            io::ErrorKind::NotFound => Ok(()),
+            _ => Err(e),
+        }),
+    }
+    .map_err(|e| ChunkingError::TokenizerLoad(format!("Failed to cache {}: {}", url, e)))?;
+
+    Ok(path)
+}
+
+/// Write a download to `path` atomically, so readers never see partial files
+fn write_cache(path: &Path, mut reader: impl io::Read) -> io::Result<()> {
+    let dir = path
+        .parent()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "cache path has no parent"))?;
+    fs::create_dir_all(dir)?;
+
+    let mut temp = tempfile::NamedTempFile::new_in(dir)?;
+    io::copy(&mut reader, &mut temp)?;
+    temp.persist(path).map_err(|e| e.error)?;
+    Ok(())
+}
@@ -1,12 +1,15 @@
 //! HuggingFace tokenizer wrapper
 
 use super::base::Tokenizer;
+#[cfg(feature = "hub")]
+use super::hub::HubOptions;
 use crate::chunking::base::ChunkingError;
 
 /// Wrapper around HuggingFace tokenizers crate
 ///
 /// Provides integration with the HuggingFace tokenizers library for token counting.
-/// Supports loading tokenizers from HuggingFace Hub or local files.
+/// Supports loading tokenizers from HuggingFace Hub or local files. Requires the
+/// `huggingface` feature (on by default).
 ///
 /// # Examples
 ///
@@ -33,7 +36,9 @@ pub struct HuggingFaceTokenizer {
 impl HuggingFaceTokenizer {
     /// Load tokenizer from HuggingFace Hub
     ///
-    /// Downloads the tokenizer from HuggingFace Hub and caches it locally.
+    /// Looks for `tokenizer.json` in the local HuggingFace cache and `./models/`
+    /// first. With the `hub` feature enabled, it is otherwise downloaded from the
+    /// Hub and cached (see [`HuggingFaceTokenizer::from_hub`]).
     /// Supported models include sentence-transformers and other HuggingFace models.
     ///
     /// # Arguments
@@ -52,14 +57,6 @@ impl HuggingFaceTokenizer {
     /// )?;
     /// ```
     pub fn from_pretrained(model_id: &str) -> Result<Self, ChunkingError> {
-        // For testing purposes, we'll use a simple implementation that expects
-        // the tokenizer.json to be available locally or provides a helpful error message
-        //
-        // In a full implementation, this would download from HuggingFace Hub:
-        // https://huggingface.co/{model_id}/resolve/main/tokenizer.json
-        //
-        // For now, check common cache locations or provide instructions
-
         // Try common HuggingFace cache locations
         let home_dir = std::env::var("HOME")
             .or_else(|_| std::env::var("USERPROFILE"))
@@ -89,18 +86,51 @@ impl HuggingFaceTokenizer {
             }
         }
 
+        #[cfg(feature = "hub")]
+        {
+            Self::from_hub(model_id, &HubOptions::default())
+        }
+
         // If not found, provide helpful error message
+        #[cfg(not(feature = "hub"))]
         Err(ChunkingError::TokenizerLoad(format!(
             "Tokenizer for '{}' not found in cache. \
                 Please download tokenizer.json from https://huggingface.co/{}/tree/main \
                 and either:\n\
                 1. Place it in ~/.cache/huggingface/hub/models--{}/\n\
                 2. Place it in ./models/{}/\n\
-                3. Use from_file() with the path to tokenizer.json",
+                3. Use from_file() with the path to tokenizer.json\n\
+                4. Build docling-rs with the `hub` feature to download it automatically",
             model_id, model_id, model_path, model_id
         )))
     }
 
+    /// Download tokenizer from HuggingFace Hub
+    ///
+    /// Fetches `tokenizer.json` for `model_id`, revalidating any cached copy by
+    /// ETag. Honors offline mode, custom cache directories, revisions and access
+    /// tokens through [`HubOptions`]. Requires the `hub` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// use docling_rs::chunking::tokenizer::{HubOptions, HuggingFaceTokenizer};
+    ///
+    /// let options = HubOptions {
+    ///     cache_dir: "/var/cache/models".into(),
+    ///     ..Default::default()
+    /// };
+    /// let tokenizer = HuggingFaceTokenizer::from_hub("BAAI/bge-small-en-v1.5", &options)?;
+    /// ```
+    #[cfg(feature = "hub")]
+    pub fn from_hub(model_id: &str, options: &HubOptions) -> Result<Self, ChunkingError> {
+        let path = super::hub::fetch_file(model_id, "tokenizer.json", options)?;
+        Self::from_file(
+            path.to_str()
+                .ok_or_else(|| ChunkingError::TokenizerLoad("Invalid path encoding".to_string()))?,
+        )
+    }
+
     /// Load tokenizer from file path
     ///
     /// # Arguments
@@ -135,6 +165,11 @@ impl HuggingFaceTokenizer {
             max_tokens,
         }
     }
+
+    /// The wrapped tokenizers::Tokenizer, to encode texts into token IDs
+    pub fn inner(&self) -> &tokenizers::Tokenizer {
+        &self.tokenizer
+    }
 }
 
 // Note: Default implementation removed because from_pretrained requires
@@ -1,7 +1,108 @@
 //! Tokenizer abstraction module
 
+pub mod approx;
 pub mod base;
+#[cfg(feature = "hub")]
+pub mod hub;
+#[cfg(feature = "huggingface")]
 pub mod huggingface;
+#[cfg(feature = "tiktoken")]
+pub mod tiktoken;
 
+pub use approx::ApproxTokenizer;
 pub use base::Tokenizer;
+#[cfg(feature = "hub")]
+pub use hub::HubOptions;
+#[cfg(feature = "huggingface")]
 pub use huggingface::HuggingFaceTokenizer;
+#[cfg(feature = "tiktoken")]
+pub use tiktoken::TiktokenTokenizer;
+
+use crate::chunking::base::ChunkingError;
+
+/// Build a tokenizer from a textual specification
+///
+/// Supported specifications:
+///
+/// - `approx`: the built-in [`ApproxTokenizer`]
+/// - `tiktoken:<encoding>`: OpenAI BPE encoding such as `tiktoken:cl100k_base`
+///   (requires the `tiktoken` feature)
+/// - the path of a HuggingFace `tokenizer.json` file
+/// - `hf:<model>` or a HuggingFace model id such as
+///   `sentence-transformers/all-MiniLM-L6-v2`, loaded as by
+///   `HuggingFaceTokenizer::from_pretrained`
+///
+/// HuggingFace tokenizers require the `huggingface` feature (on by default).
+pub fn from_spec(spec: &str) -> Result<Box<dyn Tokenizer>, ChunkingError> {
+    if spec == "approx" {
+        return Ok(Box::new(ApproxTokenizer::new()));
+    }
+
+    match spec.split_once(':') {
+        Some(("tiktoken", encoding)) => tiktoken_from_encoding(encoding),
+        Some(("hf", model_id)) => huggingface_from_pretrained(model_id),
+        _ if std::path::Path::new(spec).is_file() => huggingface_from_file(spec),
+        _ if spec.ends_with(".json") => Err(ChunkingError::TokenizerLoad(format!(
+            "tokenizer file {} not found",
+            spec
+        ))),
+        _ if is_model_id(spec) => huggingface_from_pretrained(spec),
+        _ => Err(ChunkingError::InvalidConfig(format!(
+            "unsupported tokenizer '{}' (expected approx, tiktoken:<encoding>, \
+             a tokenizer.json file or a HuggingFace model id)",
+            spec
+        ))),
+    }
+}
+
+/// Whether `spec` looks like a HuggingFace model id (`<owner>/<name>`)
+fn is_model_id(spec: &str) -> bool {
+    let valid = |part: &str| {
+        !part.is_empty()
+            && part
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+    };
+    matches!(spec.split_once('/'), Some((owner, name)) if valid(owner) && valid(name))
+}
+
+#[cfg(feature = "tiktoken")]
+fn tiktoken_from_encoding(encoding: &str) -> Result<Box<dyn Tokenizer>, ChunkingError> {
+    Ok(Box::new(TiktokenTokenizer::from_encoding(encoding)?))
+}
+
+#[cfg(not(feature = "tiktoken"))]
+fn tiktoken_from_encoding(_encoding: &str) -> Result<Box<dyn Tokenizer>, ChunkingError> {
+    Err(ChunkingError::InvalidConfig(
+        "tiktoken tokenizers require docling-rs to be built with the `tiktoken` feature"
+            .to_string(),
+    ))
+}
+
+#[cfg(feature = "huggingface")]
+fn huggingface_from_file(path: &str) -> Result<Box<dyn Tokenizer>, ChunkingError> {
+    Ok(Box::new(HuggingFaceTokenizer::from_file(path)?))
+}
+
+#[cfg(feature = "huggingface")]
+fn huggingface_from_pretrained(model_id: &str) -> Result<Box<dyn Tokenizer>, ChunkingError> {
+    Ok(Box::new(HuggingFaceTokenizer::from_pretrained(model_id)?))
+}
+
+#[cfg(not(feature = "huggingface"))]
+fn huggingface_from_file(_path: &str) -> Result<Box<dyn Tokenizer>, ChunkingError> {
+    Err(huggingface_disabled())
+}
+
+#[cfg(not(feature = "huggingface"))]
+fn huggingface_from_pretrained(_model_id: &str) -> Result<Box<dyn Tokenizer>, ChunkingError> {
+    Err(huggingface_disabled())
+}
+
+#[cfg(not(feature = "huggingface"))]
+fn huggingface_disabled() -> ChunkingError {
+    ChunkingError::InvalidConfig(
+        "HuggingFace tokenizers require docling-rs to be built with the `huggingface` feature"
+            .to_string(),
+    )
+}
@@ -0,0 +1,81 @@
+//! tiktoken (OpenAI BPE) tokenizer wrapper
+
+use super::base::Tokenizer;
+use crate::chunking::base::ChunkingError;
+use tiktoken_rs::CoreBPE;
+
+/// Input limit of OpenAI embedding models (text-embedding-3-*, ada-002)
+const DEFAULT_MAX_TOKENS: usize = 8191;
+
+/// Tokenizer using OpenAI's BPE encodings via `tiktoken-rs`
+///
+/// Token counts match those of OpenAI models, so chunk budgets line up with
+/// the embedding and chat APIs. The encoding tables are bundled with the crate;
+/// no download is needed. Requires the `tiktoken` feature.
+///
+/// # Examples
+///
+/// ```ignore
+/// use docling_rs::chunking::tokenizer::TiktokenTokenizer;
+///
+/// let tokenizer = TiktokenTokenizer::cl100k_base();
+/// let count = tokenizer.count_tokens("Hello world");
+/// ```
+pub struct TiktokenTokenizer {
+    bpe: &'static CoreBPE,
+    max_tokens: usize,
+}
+
+impl TiktokenTokenizer {
+    /// Encoding used by GPT-4, GPT-3.5 and the text-embedding-3 models
+    pub fn cl100k_base() -> Self {
+        Self::new(tiktoken_rs::cl100k_base_singleton())
+    }
+
+    /// Encoding used by GPT-4o and later models
+    pub fn o200k_base() -> Self {
+        Self::new(tiktoken_rs::o200k_base_singleton())
+    }
+
+    /// Load an encoding by name (`cl100k_base`, `o200k_base`, `p50k_base`, `r50k_base`)
+    pub fn from_encoding(name: &str) -> Result<Self, ChunkingError> {
+        let bpe = match name {
+            "cl100k_base" => tiktoken_rs::cl100k_base_singleton(),
+            "o200k_base" => tiktoken_rs::o200k_base_singleton(),
+            "p50k_base" => tiktoken_rs::p50k_base_singleton(),
+            "r50k_base" => tiktoken_rs::r50k_base_singleton(),
+            _ => {
+                return Err(ChunkingError::TokenizerLoad(format!(
+                    "unknown tiktoken encoding '{}' \
+                     (expected cl100k_base, o200k_base, p50k_base or r50k_base)",
+                    name
+                )))
+            }
+        };
+        Ok(Self::new(bpe))
+    }
+
+    /// Override the token limit reported by `max_tokens()`
+    pub fn with_max_tokens(mut self, max_tokens: usize) -> Self {
+        self.max_tokens = max_tokens;
+        self
+    }
+
+    fn new(bpe: &'static CoreBPE) -> Self {
+        Self {
+            bpe,
+            max_tokens: DEFAULT_MAX_TOKENS,
+        }
+    }
+}
+
+impl Tokenizer for TiktokenTokenizer {
+    fn count_tokens(&self, text: &str) -> usize {
+        // Special tokens in document text are counted as ordinary text
+        self.bpe.encode_ordinary(text).len()
+    }
+
+    fn max_tokens(&self) -> usize {
+        self.max_tokens
+    }
+}
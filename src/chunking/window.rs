@@ -0,0 +1,343 @@
+//! Sliding-window chunker implementation
+
+use super::base::{BaseChunk, BaseChunker, ChunkingError};
+use super::metadata::{ChunkMetadata, PageBoundingBox};
+use super::tokenizer::{ApproxTokenizer, Tokenizer};
+use crate::datamodel::{DoclingDocument, DocumentNode};
+
+/// Default window size in tokens
+const DEFAULT_WINDOW_SIZE: usize = 256;
+
+/// Separator placed between node texts in the linearized document
+const NODE_SEPARATOR: &str = "\n\n";
+
+/// Produces overlapping fixed-size token windows over the whole document
+///
+/// `WindowChunker` ignores document structure: the text of every node, headings
+/// included, is joined into one linear text. Windows of up to `window_size` tokens
+/// are cut from it, and each window starts `stride` tokens after the previous one,
+/// so consecutive windows overlap by `window_size - stride` tokens.
+///
+/// Windows are aligned to word boundaries. Token counts are computed per word and
+/// summed, so a window never exceeds `window_size` for tokenizers that do not merge
+/// tokens across whitespace. Chunks carry no headings.
+///
+/// # Examples
+///
+/// ```ignore
+/// use docling_rs::chunking::{WindowChunker, BaseChunker};
+///
+/// let chunker = WindowChunker::builder()
+///     .window_size(128)
+///     .stride(64)
+///     .build()?;
+///
+/// let chunks: Vec<_> = chunker.chunk(&doc).collect();
+/// ```
+pub struct WindowChunker {
+    tokenizer: Box<dyn Tokenizer>,
+    window_size: usize,
+    stride: usize,
+}
+
+impl WindowChunker {
+    /// Create a new WindowChunker with default settings
+    ///
+    /// # Returns
+    ///
+    /// A `WindowChunker` with:
+    /// - `tokenizer` = [`ApproxTokenizer`]
+    /// - `window_size` = 256 tokens
+    /// - `stride` = 128 tokens
+    pub fn new() -> Self {
+        Self {
+            tokenizer: Box::new(ApproxTokenizer::new()),
+            window_size: DEFAULT_WINDOW_SIZE,
+            stride: DEFAULT_WINDOW_SIZE / 2,
+        }
+    }
+
+    /// Create a builder for configuring WindowChunker
+    pub fn builder() -> WindowChunkerBuilder {
+        WindowChunkerBuilder::new()
+    }
+}
+
+impl Default for WindowChunker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Builder for configuring WindowChunker
+pub struct WindowChunkerBuilder {
+    tokenizer: Option<Box<dyn Tokenizer>>,
+    window_size: usize,
+    stride: Option<usize>,
+}
+
+impl WindowChunkerBuilder {
+    fn new() -> Self {
+        Self {
+            tokenizer: None,
+            window_size: DEFAULT_WINDOW_SIZE,
+            stride: None,
+        }
+    }
+
+    /// Set the tokenizer used to measure windows
+    ///
+    /// Defaults to [`ApproxTokenizer`].
+    pub fn tokenizer(mut self, tokenizer: Box<dyn Tokenizer>) -> Self {
+        self.tokenizer = Some(tokenizer);
+        self
+    }
+
+    /// Set the window size in tokens
+    pub fn window_size(mut self, size: usize) -> Self {
+        self.window_size = size;
+        self
+    }
+
+    /// Set the distance in tokens between the starts of consecutive windows
+    ///
+    /// Defaults to half the window size. A stride equal to the window size
+    /// produces non-overlapping windows.
+    pub fn stride(mut self, stride: usize) -> Self {
+        self.stride = Some(stride);
+        self
+    }
+
+    /// Build the WindowChunker
+    pub fn build(self) -> Result<WindowChunker, ChunkingError> {
+        if self.window_size == 0 {
+            return Err(ChunkingError::InvalidConfig(
+                "window_size must be greater than 0".to_string(),
+            ));
+        }
+
+        let stride = self.stride.unwrap_or(self.window_size.div_ceil(2));
+        if stride == 0 || stride > self.window_size {
+            return Err(ChunkingError::InvalidConfig(format!(
+                "stride ({}) must be between 1 and window_size ({})",
+                stride, self.window_size
+            )));
+        }
+
+        Ok(WindowChunker {
+            tokenizer: self
+                .tokenizer
+                .unwrap_or_else(|| Box::new(ApproxTokenizer::new())),
+            window_size: self.window_size,
+            stride,
+        })
+    }
+}
+
+/// A word of the linearized text with its token count
+struct Word {
+    start: usize,
+    end: usize,
+    tokens: usize,
+}
+
+/// A node's contribution to the linearized text
+struct Part<'a> {
+    /// Byte offset of the node text in the linearized text
+    start: usize,
+    node: &'a DocumentNode,
+    /// Document offset of the node text
+    doc_offset: usize,
+}
+
+impl Part<'_> {
+    fn len(&self) -> usize {
+        self.node.text_content().map_or(0, str::len)
+    }
+}
+
+/// The document text joined into one string, with a map back to its nodes
+struct Linearized<'a> {
+    text: String,
+    parts: Vec<Part<'a>>,
+}
+
+impl<'a> Linearized<'a> {
+    fn new(doc: &'a DoclingDocument) -> Self {
+        let mut text = String::new();
+        let mut parts = Vec::new();
+        // Same sequential fallback as HierarchicalChunker for nodes without positions
+        let mut current_offset = 0;
+
+        for node in doc.nodes() {
+            let Some(node_text) = node.text_content() else {
+                continue;
+            };
+            if node_text.trim().is_empty() {
+                continue;
+            }
+
+            let doc_offset = match node.position() {
+                Some(pos) => {
+                    current_offset = pos.end_offset();
+                    pos.start_offset()
+                }
+                None => {
+                    let start = current_offset;
+                    current_offset = start + node_text.len() + 1;
+                    start
+                }
+            };
+
+            if !text.is_empty() {
+                text.push_str(NODE_SEPARATOR);
+            }
+            parts.push(Part {
+                start: text.len(),
+                node,
+                doc_offset,
+            });
+            text.push_str(node_text);
+        }
+
+        Self { text, parts }
+    }
+
+    /// Find the node containing byte `pos` of the linearized text
+    fn part_at(&self, pos: usize) -> &Part<'a> {
+        let i = self.parts.partition_point(|part| part.start <= pos);
+        &self.parts[i.saturating_sub(1)]
+    }
+
+    /// Map a byte position in the linearized text to an offset in the document
+    fn doc_offset(&self, pos: usize) -> usize {
+        let part = self.part_at(pos);
+        let offset = (pos - part.start).min(part.len());
+        part.node
+            .source_offset(offset)
+            .unwrap_or(part.doc_offset + offset)
+    }
+}
+
+impl WindowChunker {
+    /// Split text into words with their byte ranges and token counts
+    fn words(&self, text: &str) -> Vec<Word> {
+        let mut words = Vec::new();
+        let mut start = None;
+
+        for (i, c) in text
+            .char_indices()
+            .chain(std::iter::once((text.len(), ' ')))
+        {
+            match (start, c.is_whitespace()) {
+                (None, false) => start = Some(i),
+                (Some(s), true) => {
+                    words.push(Word {
+                        start: s,
+                        end: i,
+                        tokens: self.tokenizer.count_tokens(&text[s..i]),
+                    });
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+
+        words
+    }
+
+    /// Compute windows as ranges of word indices
+    fn windows(&self, words: &[Word]) -> Vec<(usize, usize)> {
+        let mut windows = Vec::new();
+        let mut start = 0;
+
+        while start < words.len() {
+            // Grow the window while it fits, always taking at least one word
+            let mut end = start + 1;
+            let mut tokens = words[start].tokens;
+            while end < words.len() && tokens + words[end].tokens <= self.window_size {
+                tokens += words[end].tokens;
+                end += 1;
+            }
+            windows.push((start, end));
+
+            if end == words.len() {
+                break;
+            }
+
+            // Advance by `stride` tokens, always moving at least one word
+            let mut next = start + 1;
+            let mut advanced = words[start].tokens;
+            while next < end && advanced + words[next].tokens <= self.stride {
+                advanced += words[next].tokens;
+                next += 1;
+            }
+            start = next;
+        }
+
+        windows
+    }
+}
+
+impl BaseChunker for WindowChunker {
+    fn chunk<'a>(&'a self, doc: &'a DoclingDocument) -> Box<dyn Iterator<Item = BaseChunk> + 'a> {
+        let linear = Linearized::new(doc);
+        let words = self.words(&linear.text);
+
+        let chunks: Vec<BaseChunk> = self
+            .windows(&words)
+            .into_iter()
+            .enumerate()
+            .map(|(index, (first, last))| {
+                let start = words[first].start;
+                let end = words[last - 1].end;
+
+                let mut meta = ChunkMetadata {
+                    doc_name: doc.name().to_string(),
+                    start_offset: linear.doc_offset(start),
+                    end_offset: linear.doc_offset(end),
+                    index,
+                    ..Default::default()
+                };
+                // Pages and boxes span every node the window touches
+                for part in linear
+                    .parts
+                    .iter()
+                    .filter(|p| p.start < end && p.start + p.len() > start)
+                {
+                    meta.merge_provenance(&ChunkMetadata {
+                        page_start: part.node.page(),
+                        page_end: part.node.page(),
+                        bboxes: part
+                            .node
+                            .page()
+                            .zip(part.node.bbox())
+                            .map(|(page, bbox)| PageBoundingBox { page, bbox: *bbox })
+                            .into_iter()
+                            .collect(),
+                        languages: part
+                            .node
+                            .language()
+                            .map(str::to_string)
+                            .into_iter()
+                            .collect(),
+                        ..Default::default()
+                    });
+                }
+
+                BaseChunk {
+                    text: linear.text[start..end].to_string(),
+                    meta,
+                }
+                .with_chunk_id()
+            })
+            .collect();
+
+        Box::new(chunks.into_iter())
+    }
+
+    fn contextualize(&self, chunk: &BaseChunk) -> String {
+        // Windows carry no heading context
+        chunk.text.clone()
+    }
+}
@@ -0,0 +1,119 @@
+//! Archive input expansion (.zip, .tar.gz).
+
+use crate::pipeline::{ConversionLimits, LimitGuard};
+use anyhow::{Context, Result};
+use flate2::read::GzDecoder;
+use std::fs::{self, File};
+use std::io::{self, Read};
+use std::path::Path;
+
+/// Supported archive kinds
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveKind {
+    /// ZIP archive
+    Zip,
+    /// Gzip-compressed tarball
+    TarGz,
+}
+
+impl ArchiveKind {
+    /// Detect archive kind from file name
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let name = path.file_name()?.to_str()?.to_lowercase();
+        if name.ends_with(".zip") {
+            Some(Self::Zip)
+        } else if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+            Some(Self::TarGz)
+        } else {
+            None
+        }
+    }
+}
+
+/// Extract an archive into `dest`, preserving its internal directory structure.
+///
+/// Entries whose paths would escape `dest` (absolute paths, `..` components)
+/// are skipped. The input size limit applies to the archive, the
+/// decompressed size limit to all its entries together and the depth limit
+/// to the directories of each entry; extraction stops at the first limit
+/// passed.
+pub fn extract(
+    archive_path: &Path,
+    kind: ArchiveKind,
+    dest: &Path,
+    limits: &ConversionLimits,
+) -> Result<()> {
+    let limits = limits.start();
+    let file = File::open(archive_path)
+        .with_context(|| format!("Unable to open archive: {:?}", archive_path))?;
+    limits.check_input_size(file.metadata()?.len())?;
+
+    match kind {
+        ArchiveKind::Zip => extract_zip(file, dest, &limits),
+        ArchiveKind::TarGz => extract_tar_gz(file, dest, &limits),
+    }
+    .with_context(|| format!("Failed to extract archive: {:?}", archive_path))
+}
+
+fn extract_zip(file: File, dest: &Path, limits: &LimitGuard) -> Result<()> {
+    let mut archive = zip::ZipArchive::new(file)?;
+    let mut extracted = 0u64;
+
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i)?;
+
+        // Reject entries that would be written outside the destination
+        let Some(rel_path) = entry.enclosed_name() else {
+            continue;
+        };
+        limits.check_depth(rel_path.components().count())?;
+        let out_path = dest.join(rel_path);
+
+        if entry.is_dir() {
+            fs::create_dir_all(&out_path)?;
+            continue;
+        }
+
+        if let Some(parent) = out_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut out_file = File::create(&out_path)?;
+        extracted += copy_limited(&mut entry, &mut out_file, extracted, limits)?;
+    }
+
+    Ok(())
+}
+
+fn extract_tar_gz(file: File, dest: &Path, limits: &LimitGuard) -> Result<()> {
+    let mut archive = tar::Archive::new(GzDecoder::new(file));
+    let mut extracted = 0u64;
+
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        limits.check_depth(entry.path()?.components().count())?;
+        // Tar headers hold the exact size of each entry
+        extracted = extracted.saturating_add(entry.size());
+        limits.check_decompressed_size(extracted)?;
+        // `unpack_in` refuses entries that escape `dest`
+        entry.unpack_in(dest)?;
+    }
+
+    Ok(())
+}
+
+/// Copy `reader` to `writer`, failing once `extracted` plus the bytes copied
+/// pass the decompressed size limit; returns the bytes copied
+fn copy_limited(
+    reader: &mut impl Read,
+    writer: &mut impl io::Write,
+    extracted: u64,
+    limits: &LimitGuard,
+) -> Result<u64> {
+    let remaining = limits
+        .limits()
+        .max_decompressed_bytes()
+        .map_or(u64::MAX, |max| max.saturating_sub(extracted));
+    let copied = io::copy(&mut reader.take(remaining.saturating_add(1)), writer)?;
+    limits.check_decompressed_size(extracted + copied)?;
+    Ok(copied)
+}
@@ -1,7 +1,18 @@
 //! CLI argument parsing with clap.
 
-use clap::{Parser, ValueEnum};
+use crate::backend::pdf::PdfConfig;
+use crate::cli::exclude::ExcludeSet;
+use crate::config::ConverterConfig;
+use crate::datamodel::ColumnAlignment;
+use crate::pipeline::ConversionLimits;
+use anyhow::{anyhow, Result};
+use clap::parser::ValueSource;
+use clap::{ArgMatches, Parser, ValueEnum};
+use std::num::NonZeroUsize;
 use std::path::PathBuf;
+use std::time::Duration;
+
+pub use crate::format::OutputFormat;
 
 /// Validate chunk size is greater than 0
 fn validate_chunk_size(s: &str) -> Result<usize, String> {
@@ -14,17 +25,54 @@ fn validate_chunk_size(s: &str) -> Result<usize, String> {
     Ok(size)
 }
 
+/// Validate an exclude pattern
+pub(crate) fn validate_exclude(s: &str) -> Result<String, String> {
+    ExcludeSet::new(&[s]).map_err(|e| format!("{:#}", e))?;
+    Ok(s.to_string())
+}
+
+/// Validate a Markdown list bullet
+fn validate_bullet(s: &str) -> Result<char, String> {
+    match s {
+        "-" | "*" | "+" => Ok(s.chars().next().unwrap_or('-')),
+        _ => Err(format!("invalid bullet: {} (expected -, * or +)", s)),
+    }
+}
+
+/// Validate a dataset split name, which `datasets` restricts to word
+/// characters and dots
+fn validate_split(s: &str) -> Result<String, String> {
+    let valid = s
+        .split('.')
+        .all(|part| !part.is_empty() && part.chars().all(|c| c.is_alphanumeric() || c == '_'));
+    if !valid {
+        return Err(format!(
+            "invalid split name: {} (letters, digits, _ and .)",
+            s
+        ));
+    }
+    Ok(s.to_string())
+}
+
 /// docling-rs CLI - Document conversion tool
 #[derive(Parser, Debug)]
 #[command(name = "docling-rs")]
 #[command(about = "Convert documents to structured text and metadata", long_about = None)]
 #[command(version)]
+#[command(
+    after_help = "Exit codes: 0 success, 1 conversion error, 2 invalid arguments, \
+3 input not found, 4 unsupported format, 5 some files of a batch failed"
+)]
 pub struct CliArgs {
-    /// Input file or directory path
+    /// Input file, directory, or archive (.zip, .tar.gz) path, an http(s)
+    /// URL to download, or - to read from stdin and write to stdout (needs
+    /// --from)
     #[arg(value_name = "INPUT")]
     pub input: PathBuf,
 
-    /// Output format (markdown, json, text)
+    // Clap lists the possible values from `OutputFormat`, so the help cannot
+    // miss one
+    /// Output format
     #[arg(
         short = 't',
         long = "to",
@@ -37,7 +85,8 @@ pub struct CliArgs {
     #[arg(short = 'o', long = "output-dir", value_name = "DIR")]
     pub output_dir: Option<PathBuf>,
 
-    /// Filter input files by format (for batch processing)
+    /// Filter input files by format (for batch processing), or the format of
+    /// stdin
     #[arg(short = 'f', long = "from", value_name = "FORMAT")]
     pub input_format_filter: Option<String>,
 
@@ -45,14 +94,126 @@ pub struct CliArgs {
     #[arg(long = "ocr-enabled")]
     pub ocr_enabled: bool,
 
-    /// Extract tables from PDFs
+    /// Extract tables from PDFs (with --pdf-extract-images, only the named
+    /// kinds are extracted; both are by default)
     #[arg(long = "pdf-extract-tables")]
     pub pdf_extract_tables: bool,
 
-    /// Extract images from PDFs
+    /// Extract images from PDFs (with --pdf-extract-tables, only the named
+    /// kinds are extracted; both are by default)
     #[arg(long = "pdf-extract-images")]
     pub pdf_extract_images: bool,
 
+    /// Tag nodes and chunks with the detected language of their text
+    #[arg(long = "detect-language")]
+    pub detect_language: bool,
+
+    /// Group nodes under section nodes derived from heading levels
+    #[arg(long = "group-sections")]
+    pub group_sections: bool,
+
+    /// Markdown heading style
+    #[arg(long = "md-heading-style", value_enum, default_value_t = HeadingStyle::Atx)]
+    pub md_heading_style: HeadingStyle,
+
+    /// Backslash-escape Markdown syntax characters in text
+    #[arg(long = "md-escape")]
+    pub md_escape: bool,
+
+    /// Bullet for unordered Markdown list items (-, * or +)
+    #[arg(long = "md-bullet", value_name = "CHAR", default_value = "-", value_parser = validate_bullet)]
+    pub md_bullet: char,
+
+    /// Column alignment of Markdown tables (none, left, center, right)
+    #[arg(long = "md-table-align", value_name = "ALIGN", default_value = "none")]
+    pub md_table_align: ColumnAlignment,
+
+    /// Wrap Markdown paragraphs and list items at WIDTH characters
+    #[arg(long = "md-wrap", value_name = "WIDTH")]
+    pub md_wrap: Option<usize>,
+
+    /// Mark page starts: `<!-- page: N -->` in Markdown, a form feed in text
+    #[arg(long = "page-markers")]
+    pub page_markers: bool,
+
+    /// Write JSON on a single line instead of pretty-printed
+    #[arg(long = "json-compact")]
+    pub json_compact: bool,
+
+    /// Leave node bounding boxes out of JSON output
+    #[arg(long = "json-no-bboxes")]
+    pub json_no_bboxes: bool,
+
+    /// Leave image bytes out of JSON output
+    #[arg(long = "json-no-images")]
+    pub json_no_images: bool,
+
+    /// Nest nodes inside their parents in JSON output instead of a flat list
+    #[arg(long = "json-tree")]
+    pub json_tree: bool,
+
+    /// Also write each table to its own file (csv or xlsx), next to the output
+    #[arg(
+        long = "export-tables",
+        value_name = "FORMAT",
+        num_args = 0..=1,
+        default_missing_value = "csv"
+    )]
+    pub export_tables: Option<TableFormat>,
+
+    /// Write picture images to files in DIR and link them from the output
+    #[arg(long = "export-images", value_name = "DIR")]
+    pub export_images: Option<PathBuf>,
+
+    /// Embed picture images in the output as data URIs
+    #[arg(long = "embed-images", conflicts_with = "export_images")]
+    pub embed_images: bool,
+
+    /// Render output through a Tera template instead of --to (requires the `templates` feature)
+    #[arg(long = "template", value_name = "FILE")]
+    pub template: Option<PathBuf>,
+
+    /// Write one file per section, plus an index, to a directory named after the input
+    #[arg(long = "split-by", value_name = "LEVEL")]
+    pub split_by: Option<SplitLevel>,
+
+    /// Write all documents of a batch to PATH, with an index and a header per
+    /// document, instead of one file each (markdown, text, json or jsonl)
+    #[arg(
+        long = "combine",
+        value_name = "PATH",
+        conflicts_with_all = ["split_by", "resume", "watch"]
+    )]
+    pub combine: Option<PathBuf>,
+
+    /// Reuse conversion results cached in DIR for unchanged inputs
+    #[arg(long = "cache-dir", value_name = "DIR")]
+    pub cache_dir: Option<PathBuf>,
+
+    /// Fail conversions that take longer than SECONDS
+    #[arg(long = "timeout", value_name = "SECONDS")]
+    pub timeout: Option<u64>,
+
+    /// Fail conversions of documents with more than N pages
+    #[arg(long = "max-pages", value_name = "N")]
+    pub max_pages: Option<usize>,
+
+    /// Fail conversions whose document holds more than BYTES bytes of text and images
+    #[arg(long = "max-output-bytes", value_name = "BYTES")]
+    pub max_output_bytes: Option<usize>,
+
+    /// Refuse input files larger than BYTES bytes
+    #[arg(long = "max-input-bytes", value_name = "BYTES")]
+    pub max_input_bytes: Option<u64>,
+
+    /// Refuse DOCX files and archives that expand to more than BYTES bytes
+    #[arg(long = "max-decompressed-bytes", value_name = "BYTES")]
+    pub max_decompressed_bytes: Option<u64>,
+
+    /// Refuse HTML nested, and archives with directories nested, more than N levels deep
+    #[arg(long = "max-depth", value_name = "N")]
+    pub max_depth: Option<usize>,
+
     /// Enable document chunking
     #[arg(long = "chunk")]
     pub chunk: bool,
@@ -61,6 +222,67 @@ pub struct CliArgs {
     #[arg(long = "chunk-size", value_name = "SIZE", default_value = "1000", value_parser = validate_chunk_size)]
     pub chunk_size: usize,
 
+    /// Chunking strategy
+    #[arg(
+        long = "chunker",
+        value_name = "STRATEGY",
+        default_value = "hierarchical"
+    )]
+    pub chunker: ChunkerKind,
+
+    /// Maximum tokens per chunk (hybrid, window and semantic chunkers)
+    #[arg(long = "max-tokens", value_name = "N", value_parser = validate_chunk_size)]
+    pub max_tokens: Option<usize>,
+
+    /// Tokenizer for token-aware chunking: approx (default), tiktoken:<encoding>,
+    /// a tokenizer.json file or a HuggingFace model id
+    #[arg(long = "tokenizer", value_name = "SPEC")]
+    pub tokenizer: Option<String>,
+
+    /// Chunk documents and add the embedding of every chunk, computed with
+    /// --embedding-model, to the json or jsonl output
+    #[arg(long = "embed", requires = "embedding_model")]
+    pub embed: bool,
+
+    /// Embedding model for --embed and --chunker semantic: hash[:<dimensions>],
+    /// openai:<model> or onnx:<model directory or id>
+    #[arg(long = "embedding-model", value_name = "ID")]
+    pub embedding_model: Option<String>,
+
+    /// Store the embedded chunks in a local vector index in DIR (created if
+    /// needed) instead of writing output files; documents stored again
+    /// replace their earlier chunks
+    #[arg(
+        long = "store",
+        value_name = "DIR",
+        requires = "embed",
+        conflicts_with_all = ["combine", "split_by", "template"]
+    )]
+    pub store: Option<PathBuf>,
+
+    /// Write the chunks of every document to a Hugging Face dataset in DIR
+    /// (Parquet shards and dataset_infos.json, loadable with
+    /// datasets.load_dataset) instead of writing output files
+    #[arg(
+        long = "dataset",
+        value_name = "DIR",
+        conflicts_with_all = ["store", "embed", "combine", "split_by", "template", "watch"]
+    )]
+    pub dataset: Option<PathBuf>,
+
+    /// Split of the --dataset rows: train (default), test, validation...
+    #[arg(
+        long = "dataset-split",
+        value_name = "NAME",
+        requires = "dataset",
+        value_parser = validate_split
+    )]
+    pub dataset_split: Option<String>,
+
+    /// Print chunk statistics (token distribution, oversize and empty chunks)
+    #[arg(long = "stats", requires = "chunk")]
+    pub stats: bool,
+
     /// Continue processing on error (batch mode)
     #[arg(long = "continue-on-error")]
     pub continue_on_error: bool,
@@ -69,6 +291,44 @@ pub struct CliArgs {
     #[arg(long = "abort-on-error", conflicts_with = "continue_on_error")]
     pub abort_on_error: bool,
 
+    /// Skip files and directories matching GLOB, relative to the input
+    /// directory; repeatable (batch mode)
+    #[arg(long = "exclude", value_name = "GLOB", value_parser = validate_exclude)]
+    pub exclude: Vec<String>,
+
+    /// Only convert the files at the top level of the input directory
+    /// (batch mode)
+    #[arg(long = "no-recursive")]
+    pub no_recursive: bool,
+
+    /// Convert up to N files at the same time (batch mode)
+    #[arg(short = 'j', long = "jobs", value_name = "N", default_value = "1")]
+    pub jobs: NonZeroUsize,
+
+    /// Skip files converted by an interrupted earlier run with --resume (batch mode)
+    #[arg(long = "resume")]
+    pub resume: bool,
+
+    /// Write a JSON report of every input, its output, status, error, timing
+    /// and metrics to PATH (batch mode)
+    #[arg(long = "report", value_name = "PATH")]
+    pub report: Option<PathBuf>,
+
+    /// Keep watching the input directory and convert files as they appear or
+    /// change, until interrupted
+    #[arg(long = "watch", conflicts_with_all = ["resume", "report"])]
+    pub watch: bool,
+
+    /// Wait until a watched file has been unchanged for MS milliseconds
+    /// before converting it
+    #[arg(
+        long = "debounce",
+        value_name = "MS",
+        default_value_t = 1000,
+        requires = "watch"
+    )]
+    pub debounce: u64,
+
     /// Verbose output
     #[arg(short = 'v', long = "verbose")]
     pub verbose: bool,
@@ -76,6 +336,176 @@ pub struct CliArgs {
     /// Quiet mode (no output except errors)
     #[arg(short = 'q', long = "quiet", conflicts_with = "verbose")]
     pub quiet: bool,
+
+    /// Most detailed log messages to write to stderr (default: info with
+    /// --verbose, error with --quiet, warn otherwise)
+    #[arg(long = "log-level", value_name = "LEVEL")]
+    pub log_level: Option<LogLevel>,
+
+    /// Format of log messages: text, or json with one object per line
+    #[arg(long = "log-format", value_name = "FORMAT", default_value = "text")]
+    pub log_format: LogFormat,
+
+    /// Conversion profile (TOML or YAML); command line flags override it
+    #[arg(long = "config", value_name = "FILE")]
+    pub config: Option<PathBuf>,
+
+    /// Ignore the user (~/.config/docling-rs/config.toml) and project
+    /// (.docling.toml) config files
+    #[arg(long = "no-config")]
+    pub no_config: bool,
+
+    /// Profile loaded from the config files, see [`CliArgs::apply_config`]
+    #[arg(skip)]
+    pub profile: Option<ConverterConfig>,
+}
+
+impl CliArgs {
+    /// Whether the input is stdin (`-`)
+    pub fn reads_stdin(&self) -> bool {
+        self.input.as_os_str() == "-"
+    }
+
+    /// Load the profile of this run: the user config file, then the project
+    /// config file of the working directory (unless `--no-config`), then
+    /// `--config`, each overriding the previous ones
+    ///
+    /// `None` if there is no profile to apply.
+    pub fn load_config(&self) -> Result<Option<ConverterConfig>> {
+        let mut files = Vec::new();
+        if !self.no_config {
+            files.extend(ConverterConfig::user_file().filter(|path| path.is_file()));
+            files.extend(
+                std::env::current_dir()
+                    .ok()
+                    .and_then(|dir| ConverterConfig::project_file(&dir)),
+            );
+        }
+        files.extend(self.config.clone());
+
+        let mut profile: Option<ConverterConfig> = None;
+        for path in files {
+            let config = ConverterConfig::from_file(&path)?;
+            profile = Some(match profile {
+                Some(profile) => profile.merge(config),
+                None => config,
+            });
+        }
+        Ok(profile)
+    }
+
+    /// Fill the options not given on the command line from a profile
+    ///
+    /// `matches` are the matches the arguments were parsed from, used to tell
+    /// flags given on the command line from defaults. Switches are on when
+    /// either the command line or the profile turns them on. The profile is
+    /// kept in [`profile`](Self::profile) for the settings without a flag.
+    pub fn apply_config(&mut self, config: &ConverterConfig, matches: &ArgMatches) -> Result<()> {
+        let given = |id: &str| matches.value_source(id) == Some(ValueSource::CommandLine);
+
+        let pipeline = &config.pipeline;
+        self.detect_language |= pipeline.detect_language;
+        self.group_sections |= pipeline.group_sections;
+
+        // What to extract from PDFs is read from the profile by `pdf_config`
+        self.ocr_enabled |= config.pdf.ocr.unwrap_or(false);
+
+        let limits = &config.limits;
+        self.timeout = self.timeout.or(limits.timeout_secs);
+        self.max_pages = self.max_pages.or(limits.max_pages);
+        self.max_output_bytes = self.max_output_bytes.or(limits.max_output_bytes);
+        self.max_input_bytes = self.max_input_bytes.or(limits.max_input_bytes);
+        self.max_decompressed_bytes = self
+            .max_decompressed_bytes
+            .or(limits.max_decompressed_bytes);
+        self.max_depth = self.max_depth.or(limits.max_depth);
+        self.cache_dir = self.cache_dir.take().or_else(|| config.cache.dir.clone());
+
+        let chunking = &config.chunking;
+        self.chunk |= chunking.enabled;
+        if let Some(chunker) = chunking.chunker.as_deref().filter(|_| !given("chunker")) {
+            self.chunker = ChunkerKind::from_str(chunker, true)
+                .map_err(|e| anyhow!("chunking.chunker: {}", e))?;
+        }
+        if let Some(size) = chunking.chunk_size.filter(|_| !given("chunk_size")) {
+            self.chunk_size = validate_chunk_size(&size.to_string())
+                .map_err(|e| anyhow!("chunking.chunk_size: {}", e))?;
+        }
+        self.max_tokens = self.max_tokens.or(chunking.max_tokens);
+        self.tokenizer = self.tokenizer.take().or_else(|| chunking.tokenizer.clone());
+
+        let output = &config.output;
+        if let Some(format) = output.format.as_deref().filter(|_| !given("output_format")) {
+            self.output_format = OutputFormat::from_str(format, true)
+                .map_err(|e| anyhow!("output.format: {}", e))?;
+        }
+        self.output_dir = self.output_dir.take().or_else(|| output.dir.clone());
+        self.template = self.template.take().or_else(|| output.template.clone());
+        self.page_markers |= output.page_markers;
+        self.json_compact |= output.json_compact;
+        self.json_tree |= output.json_tree;
+
+        self.profile = Some(config.clone());
+        Ok(())
+    }
+
+    /// Log level of the run: `--log-level`, or else the level implied by
+    /// `--verbose` and `--quiet`
+    pub fn log_level(&self) -> LogLevel {
+        match self.log_level {
+            Some(level) => level,
+            None if self.verbose => LogLevel::Info,
+            None if self.quiet => LogLevel::Error,
+            None => LogLevel::Warn,
+        }
+    }
+
+    /// The resource limits and input guardrails given by the flags
+    pub fn limits(&self) -> ConversionLimits {
+        let mut limits = ConversionLimits::new();
+        if let Some(seconds) = self.timeout {
+            limits = limits.with_timeout(Duration::from_secs(seconds));
+        }
+        if let Some(pages) = self.max_pages {
+            limits = limits.with_max_pages(pages);
+        }
+        if let Some(bytes) = self.max_output_bytes {
+            limits = limits.with_max_output_bytes(bytes);
+        }
+        if let Some(bytes) = self.max_input_bytes {
+            limits = limits.with_max_input_bytes(bytes);
+        }
+        if let Some(bytes) = self.max_decompressed_bytes {
+            limits = limits.with_max_decompressed_bytes(bytes);
+        }
+        if let Some(depth) = self.max_depth {
+            limits = limits.with_max_depth(depth);
+        }
+        limits
+    }
+
+    /// The PDF backend configuration: the `pdf` section of the profile, with
+    /// the PDF flags on top
+    ///
+    /// Tables and images are both extracted by default. Once
+    /// `--pdf-extract-tables` or `--pdf-extract-images` is given, only the
+    /// named kinds are.
+    pub fn pdf_config(&self) -> PdfConfig {
+        let mut config = self
+            .profile
+            .as_ref()
+            .map(ConverterConfig::pdf_config)
+            .unwrap_or_default();
+        if self.ocr_enabled {
+            config = config.enable_ocr(true);
+        }
+        if self.pdf_extract_tables || self.pdf_extract_images {
+            config = config
+                .enable_tables(self.pdf_extract_tables)
+                .enable_images(self.pdf_extract_images);
+        }
+        config
+    }
 }
 
 /// Input document format
@@ -91,6 +521,10 @@ pub enum InputFormat {
     Csv,
     /// Microsoft Word (DOCX)
     Docx,
+    /// Plain text
+    Text,
+    /// Source code (.rs, .py, .js, ...)
+    Code,
 }
 
 impl InputFormat {
@@ -102,6 +536,8 @@ impl InputFormat {
             "html" | "htm" => Some(Self::Html),
             "csv" => Some(Self::Csv),
             "docx" => Some(Self::Docx),
+            "txt" | "text" => Some(Self::Text),
+            ext if crate::backend::CodeLanguage::from_extension(ext).is_some() => Some(Self::Code),
             _ => None,
         }
     }
@@ -114,38 +550,117 @@ impl InputFormat {
             Self::Html => crate::format::InputFormat::Html,
             Self::Csv => crate::format::InputFormat::Csv,
             Self::Docx => crate::format::InputFormat::Docx,
+            Self::Text => crate::format::InputFormat::Text,
+            Self::Code => crate::format::InputFormat::Code,
         }
     }
 }
 
-/// Output format for converted documents
+/// Chunking strategy selectable from the command line
 #[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
-pub enum OutputFormat {
-    /// Markdown format
-    Markdown,
-    /// JSON format
-    Json,
-    /// Plain text format
-    Text,
+pub enum ChunkerKind {
+    /// One chunk per document element
+    Hierarchical,
+    /// Token-aware splitting and merging (uses --max-tokens)
+    Hybrid,
+    /// Separator-based recursive splitting (uses --chunk-size)
+    Recursive,
+    /// Fixed-size chunks on word boundaries (uses --chunk-size)
+    Fixed,
+    /// Overlapping token windows, ignoring structure (uses --max-tokens)
+    Window,
+    /// Elements grouped until the topic shifts (uses --max-tokens and
+    /// --embedding-model)
+    Semantic,
+}
+
+impl ChunkerKind {
+    /// Name of the strategy in the chunker registry
+    pub fn registry_name(self) -> &'static str {
+        match self {
+            Self::Hierarchical => "hierarchical",
+            Self::Hybrid => "hybrid",
+            Self::Recursive => "recursive",
+            Self::Fixed => "fixed",
+            Self::Window => "window",
+            Self::Semantic => "semantic",
+        }
+    }
+}
+
+/// Markdown heading syntax
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum HeadingStyle {
+    /// `# Heading`
+    Atx,
+    /// Heading underlined with `=` or `-` (levels 1 and 2 only)
+    Setext,
+}
+
+/// Headings at which --split-by starts a new file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum SplitLevel {
+    /// Top-level headings (chapters)
+    Heading1,
+    /// Headings of level 2 and above
+    Heading2,
+    /// Headings of level 3 and above
+    Heading3,
 }
 
-impl std::fmt::Display for OutputFormat {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl SplitLevel {
+    /// Deepest heading level that starts a new file
+    pub fn level(&self) -> usize {
         match self {
-            Self::Markdown => write!(f, "markdown"),
-            Self::Json => write!(f, "json"),
-            Self::Text => write!(f, "text"),
+            Self::Heading1 => 1,
+            Self::Heading2 => 2,
+            Self::Heading3 => 3,
         }
     }
 }
 
-impl OutputFormat {
+/// File format for tables written by --export-tables
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum TableFormat {
+    /// Comma-separated values
+    Csv,
+    /// Excel workbook
+    Xlsx,
+}
+
+impl TableFormat {
     /// Get file extension for this format
     pub fn extension(&self) -> &'static str {
         match self {
-            Self::Markdown => "md",
-            Self::Json => "json",
-            Self::Text => "txt",
+            Self::Csv => "csv",
+            Self::Xlsx => "xlsx",
         }
     }
 }
+
+/// Most detailed log messages written
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+pub enum LogLevel {
+    /// No log messages
+    Off,
+    /// Failures
+    Error,
+    /// Failures and problems, such as parts of documents that could not be
+    /// converted
+    Warn,
+    /// Progress of the run
+    Info,
+    /// Pipeline stages, with their durations
+    Debug,
+    /// Everything
+    Trace,
+}
+
+/// Format of log messages
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum LogFormat {
+    /// Human-readable lines
+    Text,
+    /// One JSON object per line, with the fields of the message and its spans
+    Json,
+}
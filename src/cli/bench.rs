@@ -0,0 +1,467 @@
+//! Benchmarks (`docling-rs bench`).
+//!
+//! `docling-rs bench <DIR>` converts every supported file of a corpus several
+//! times, nothing written, and reports how fast it went, to size ingestion
+//! infrastructure:
+//!
+//! - throughput: documents, pages and megabytes of input per second, over the
+//!   wall-clock time of the measured passes (so `--jobs` counts)
+//! - latency of one conversion per input format: median, p90, p99 and max
+//! - peak memory: the largest resident set size of the process (Linux only)
+//!
+//! ```text
+//! docling-rs bench corpus/ --iterations 5 --jobs 4
+//! docling-rs bench corpus/ --chunker hybrid --json > bench.json
+//! ```
+//!
+//! Unmeasured `--warmup` passes run first, to fill file system caches.
+//! Failed conversions are counted and logged, and left out of the latencies.
+
+use crate::chunking::{ChunkerOptions, ChunkerRegistry};
+use crate::cli::args::{validate_exclude, ChunkerKind, InputFormat, LogFormat, LogLevel};
+use crate::cli::exclude::ExcludeSet;
+use crate::cli::exit::{failure, ExitCode};
+use crate::DocumentConverter;
+use anyhow::{Context, Result};
+use clap::{ArgMatches, Args, Command, FromArgMatches, ValueEnum};
+use rayon::prelude::*;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// Name of the subcommand
+pub const COMMAND: &str = "bench";
+
+/// Convert a corpus repeatedly and report throughput, latencies and peak memory
+#[derive(Debug, Clone, Args)]
+pub struct BenchArgs {
+    /// Directory of documents to convert (or a single file)
+    #[arg(value_name = "DIR")]
+    pub corpus: PathBuf,
+
+    /// Measured passes over the corpus
+    #[arg(
+        short = 'n',
+        long = "iterations",
+        value_name = "N",
+        default_value = "3"
+    )]
+    pub iterations: NonZeroUsize,
+
+    /// Unmeasured passes over the corpus before the measured ones
+    #[arg(long = "warmup", value_name = "N", default_value = "1")]
+    pub warmup: usize,
+
+    /// Convert up to N files at the same time
+    #[arg(short = 'j', long = "jobs", value_name = "N", default_value = "1")]
+    pub jobs: NonZeroUsize,
+
+    /// Also chunk every document with STRATEGY, timed with its conversion
+    #[arg(long = "chunker", value_name = "STRATEGY")]
+    pub chunker: Option<ChunkerKind>,
+
+    /// Skip files and directories matching GLOB, relative to DIR; repeatable
+    #[arg(long = "exclude", value_name = "GLOB", value_parser = validate_exclude)]
+    pub exclude: Vec<String>,
+
+    /// Print the report as JSON
+    #[arg(long = "json")]
+    pub json: bool,
+
+    /// Most detailed log messages to write to stderr
+    #[arg(long = "log-level", value_name = "LEVEL", default_value = "warn")]
+    pub log_level: LogLevel,
+
+    /// Format of log messages: text, or json with one object per line
+    #[arg(long = "log-format", value_name = "FORMAT", default_value = "text")]
+    pub log_format: LogFormat,
+}
+
+/// The `bench` subcommand
+pub fn subcommand() -> Command {
+    // The about text is the doc comment of `BenchArgs`
+    BenchArgs::augment_args(Command::new(COMMAND))
+}
+
+/// The benchmark options, if `matches` are those of the `bench` subcommand
+pub fn requested(matches: &ArgMatches) -> Option<Result<BenchArgs, clap::Error>> {
+    matches
+        .subcommand_matches(COMMAND)
+        .map(BenchArgs::from_arg_matches)
+}
+
+/// Run the benchmark and print its report to stdout
+pub fn run(args: &BenchArgs) -> Result<()> {
+    let report = bench(args)?;
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        println!("{}", report);
+    }
+    Ok(())
+}
+
+/// A file of the corpus
+struct CorpusFile {
+    path: PathBuf,
+    format: InputFormat,
+    bytes: u64,
+}
+
+/// Measure of one conversion
+#[derive(Debug, Clone)]
+pub struct Sample {
+    pub format: InputFormat,
+    pub bytes: u64,
+    pub pages: usize,
+    pub duration: Duration,
+    pub success: bool,
+}
+
+/// Convert the corpus of `args` as asked, and report the measures
+pub fn bench(args: &BenchArgs) -> Result<BenchReport> {
+    if !args.corpus.exists() {
+        return Err(failure(
+            ExitCode::InputNotFound,
+            format!("Corpus does not exist: {:?}", args.corpus),
+        ));
+    }
+    let exclude = ExcludeSet::new(&args.exclude)?;
+    let files = collect(&args.corpus, &exclude)?;
+    if files.is_empty() {
+        return Err(failure(
+            ExitCode::InputNotFound,
+            format!("No files to convert in {:?}", args.corpus),
+        ));
+    }
+
+    let converter = DocumentConverter::new();
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(args.jobs.get())
+        .build()
+        .context("Unable to start worker threads")?;
+    let pass = |log_failures: bool| -> Vec<Sample> {
+        pool.install(|| {
+            files
+                .par_iter()
+                .map(|file| measure(&converter, file, args.chunker, log_failures))
+                .collect()
+        })
+    };
+
+    for warmup in 0..args.warmup {
+        tracing::info!("Warmup pass {}/{}", warmup + 1, args.warmup);
+        // Failures are logged once, by the first pass
+        pass(warmup == 0);
+    }
+    let started = Instant::now();
+    let mut samples = Vec::with_capacity(files.len() * args.iterations.get());
+    for iteration in 0..args.iterations.get() {
+        tracing::info!("Pass {}/{}", iteration + 1, args.iterations);
+        samples.extend(pass(args.warmup == 0 && iteration == 0));
+    }
+    let wall = started.elapsed();
+
+    if !samples.iter().any(|sample| sample.success) {
+        return Err(failure(
+            ExitCode::ConversionFailed,
+            "Every conversion of the corpus failed",
+        ));
+    }
+    let mut report = BenchReport::new(&samples, wall);
+    report.files = files.len();
+    report.corpus_bytes = files.iter().map(|file| file.bytes).sum();
+    report.iterations = args.iterations.get();
+    report.jobs = args.jobs.get();
+    report.chunker = args.chunker.map(|kind| kind.registry_name().to_string());
+    report.peak_memory_bytes = peak_memory();
+    Ok(report)
+}
+
+/// Convert (and chunk) `file` once, timing it
+fn measure(
+    converter: &DocumentConverter,
+    file: &CorpusFile,
+    chunker: Option<ChunkerKind>,
+    log_failures: bool,
+) -> Sample {
+    let started = Instant::now();
+    let outcome = converter
+        .convert_file(&file.path)
+        .map_err(anyhow::Error::from)
+        .and_then(|result| {
+            if let Some(kind) = chunker {
+                let chunker = ChunkerRegistry::new().create(
+                    kind.registry_name(),
+                    ChunkerOptions {
+                        max_tokens: None,
+                        chunk_size: Some(1000),
+                        tokenizer: None,
+                        embedder: None,
+                    },
+                )?;
+                chunker.chunk(result.document()).for_each(drop);
+            }
+            Ok(result.metrics().total_pages())
+        });
+    let duration = started.elapsed();
+    if let (Err(e), true) = (&outcome, log_failures) {
+        tracing::warn!("Failed to convert {}: {:#}", file.path.display(), e);
+    }
+    Sample {
+        format: file.format,
+        bytes: file.bytes,
+        pages: outcome.as_ref().copied().unwrap_or(0),
+        duration,
+        success: outcome.is_ok(),
+    }
+}
+
+/// The supported files of `corpus`, a directory walked recursively or a file
+fn collect(corpus: &Path, exclude: &ExcludeSet) -> Result<Vec<CorpusFile>> {
+    let mut files = Vec::new();
+    if corpus.is_dir() {
+        collect_recursive(corpus, corpus, exclude, &mut files)?;
+    } else if let Some(format) = format_of(corpus) {
+        files.push(CorpusFile {
+            path: corpus.to_path_buf(),
+            format,
+            bytes: fs::metadata(corpus)?.len(),
+        });
+    }
+    // The same order on every run
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(files)
+}
+
+fn collect_recursive(
+    root: &Path,
+    dir: &Path,
+    exclude: &ExcludeSet,
+    files: &mut Vec<CorpusFile>,
+) -> Result<()> {
+    for entry in fs::read_dir(dir).with_context(|| format!("Unable to read {:?}", dir))? {
+        let path = entry?.path();
+        if exclude.is_excluded(path.strip_prefix(root).unwrap_or(&path)) {
+            continue;
+        }
+        if path.is_dir() {
+            collect_recursive(root, &path, exclude, files)?;
+        } else if let Some(format) = format_of(&path) {
+            files.push(CorpusFile {
+                bytes: fs::metadata(&path)?.len(),
+                path,
+                format,
+            });
+        }
+    }
+    Ok(())
+}
+
+fn format_of(path: &Path) -> Option<InputFormat> {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .and_then(InputFormat::from_extension)
+}
+
+/// Largest resident set size of the process so far, in bytes
+#[cfg(target_os = "linux")]
+fn peak_memory() -> Option<u64> {
+    // `VmHWM:    12345 kB`
+    let status = fs::read_to_string("/proc/self/status").ok()?;
+    let line = status.lines().find(|line| line.starts_with("VmHWM:"))?;
+    let kilobytes: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kilobytes * 1024)
+}
+
+/// Largest resident set size of the process so far; only measured on Linux
+#[cfg(not(target_os = "linux"))]
+fn peak_memory() -> Option<u64> {
+    None
+}
+
+/// Latencies of the conversions of one input format
+#[derive(Debug, Clone, Serialize)]
+pub struct FormatLatency {
+    pub format: String,
+    /// Conversions measured, failed ones included
+    pub conversions: usize,
+    pub failed: usize,
+    /// Nearest-rank percentiles of the successful conversions, none when
+    /// they all failed
+    pub p50_ms: Option<f64>,
+    pub p90_ms: Option<f64>,
+    pub p99_ms: Option<f64>,
+    pub max_ms: Option<f64>,
+}
+
+/// Report of a benchmark
+#[derive(Debug, Clone, Serialize)]
+pub struct BenchReport {
+    /// Files in the corpus, and their total size
+    pub files: usize,
+    pub corpus_bytes: u64,
+    pub iterations: usize,
+    pub jobs: usize,
+    /// Chunking strategy timed with the conversions, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chunker: Option<String>,
+    pub conversions: usize,
+    pub failed: usize,
+    /// Wall-clock time of the measured passes
+    pub duration_ms: f64,
+    /// Input bytes and pages of the successful conversions
+    pub bytes: u64,
+    pub pages: usize,
+    pub documents_per_sec: f64,
+    pub pages_per_sec: f64,
+    pub mb_per_sec: f64,
+    /// Largest resident set size of the process, where it can be measured
+    pub peak_memory_bytes: Option<u64>,
+    pub formats: Vec<FormatLatency>,
+}
+
+impl BenchReport {
+    /// The report of `samples`, measured over `wall` of wall-clock time
+    ///
+    /// The corpus size, passes and jobs are left at their defaults.
+    pub fn new(samples: &[Sample], wall: Duration) -> Self {
+        let succeeded = || samples.iter().filter(|sample| sample.success);
+        let documents = succeeded().count();
+        let bytes: u64 = succeeded().map(|sample| sample.bytes).sum();
+        let pages: usize = succeeded().map(|sample| sample.pages).sum();
+        let per_sec = |amount: f64| {
+            let seconds = wall.as_secs_f64();
+            if seconds > 0.0 {
+                amount / seconds
+            } else {
+                0.0
+            }
+        };
+
+        let mut by_format: BTreeMap<String, Vec<&Sample>> = BTreeMap::new();
+        for sample in samples {
+            by_format
+                .entry(format_name(sample.format))
+                .or_default()
+                .push(sample);
+        }
+        let formats = by_format
+            .into_iter()
+            .map(|(format, samples)| {
+                let mut latencies: Vec<Duration> = samples
+                    .iter()
+                    .filter(|sample| sample.success)
+                    .map(|sample| sample.duration)
+                    .collect();
+                latencies.sort();
+                FormatLatency {
+                    format,
+                    conversions: samples.len(),
+                    failed: samples.iter().filter(|sample| !sample.success).count(),
+                    p50_ms: percentile(&latencies, 50).map(millis),
+                    p90_ms: percentile(&latencies, 90).map(millis),
+                    p99_ms: percentile(&latencies, 99).map(millis),
+                    max_ms: latencies.last().copied().map(millis),
+                }
+            })
+            .collect();
+
+        Self {
+            files: 0,
+            corpus_bytes: 0,
+            iterations: 1,
+            jobs: 1,
+            chunker: None,
+            conversions: samples.len(),
+            failed: samples.len() - documents,
+            duration_ms: millis(wall),
+            bytes,
+            pages,
+            documents_per_sec: per_sec(documents as f64),
+            pages_per_sec: per_sec(pages as f64),
+            mb_per_sec: per_sec(bytes as f64 / 1e6),
+            peak_memory_bytes: None,
+            formats,
+        }
+    }
+}
+
+/// Name of `format` on the command line
+fn format_name(format: InputFormat) -> String {
+    format
+        .to_possible_value()
+        .map(|value| value.get_name().to_string())
+        .unwrap_or_else(|| format!("{:?}", format).to_lowercase())
+}
+
+/// Nearest-rank `p`th percentile of sorted `values`: the smallest value with
+/// at least `p`% of the values at or below it
+pub fn percentile(values: &[Duration], p: usize) -> Option<Duration> {
+    let rank = (values.len() * p).div_ceil(100).max(1);
+    values.get(rank - 1).copied()
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+impl fmt::Display for BenchReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Corpus: {} files ({:.2} MB), {} passes with --jobs {}{}",
+            self.files,
+            self.corpus_bytes as f64 / 1e6,
+            self.iterations,
+            self.jobs,
+            self.chunker
+                .as_ref()
+                .map(|chunker| format!(", {} chunking", chunker))
+                .unwrap_or_default()
+        )?;
+        writeln!(
+            f,
+            "Conversions: {} ({} failed) in {:.2} s",
+            self.conversions,
+            self.failed,
+            self.duration_ms / 1000.0
+        )?;
+        writeln!(
+            f,
+            "Throughput: {:.2} docs/s, {:.2} pages/s, {:.2} MB/s",
+            self.documents_per_sec, self.pages_per_sec, self.mb_per_sec
+        )?;
+        match self.peak_memory_bytes {
+            Some(bytes) => writeln!(f, "Peak memory: {:.1} MB", bytes as f64 / 1e6)?,
+            None => writeln!(f, "Peak memory: not measured on this platform")?,
+        }
+        writeln!(f)?;
+        write!(
+            f,
+            "{:<10} {:>6} {:>6} {:>10} {:>10} {:>10} {:>10}",
+            "Format", "Runs", "Failed", "p50 ms", "p90 ms", "p99 ms", "max ms"
+        )?;
+        let ms = |latency: Option<f64>| {
+            latency.map_or_else(|| "-".to_string(), |ms| format!("{:.1}", ms))
+        };
+        for format in &self.formats {
+            write!(
+                f,
+                "\n{:<10} {:>6} {:>6} {:>10} {:>10} {:>10} {:>10}",
+                format.format,
+                format.conversions,
+                format.failed,
+                ms(format.p50_ms),
+                ms(format.p90_ms),
+                ms(format.p99_ms),
+                ms(format.max_ms)
+            )?;
+        }
+        Ok(())
+    }
+}
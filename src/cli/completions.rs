@@ -0,0 +1,64 @@
+//! Shell completion scripts.
+//!
+//! `docling-rs completions <SHELL>` prints a completion script generated from
+//! the argument definitions, so every flag and value is completed as soon as
+//! it is added:
+//!
+//! ```text
+//! docling-rs completions bash > ~/.local/share/bash-completion/completions/docling-rs
+//! docling-rs completions zsh > ~/.zfunc/_docling-rs
+//! docling-rs completions fish > ~/.config/fish/completions/docling-rs.fish
+//! docling-rs completions powershell >> $PROFILE
+//! ```
+
+use super::args::CliArgs;
+use super::{bench, mcp, serve};
+use clap::{value_parser, Arg, ArgMatches, Command, CommandFactory};
+use clap_complete::Shell;
+use std::io::{self, Write};
+
+/// Name of the subcommand
+pub const COMMAND: &str = "completions";
+
+/// The full command line: the conversion arguments of [`CliArgs`], and the
+/// `completions`, [`serve`], [`mcp`] and [`bench`] subcommands
+///
+/// Subcommands stand on their own; when one is given, no input is needed.
+pub fn command() -> Command {
+    CliArgs::command()
+        .subcommand(
+            Command::new(COMMAND)
+                .about("Print the shell completion script of SHELL")
+                .arg(
+                    Arg::new("shell")
+                        .value_name("SHELL")
+                        .required(true)
+                        .value_parser(value_parser!(Shell)),
+                ),
+        )
+        .subcommand(serve::subcommand())
+        .subcommand(mcp::subcommand())
+        .subcommand(bench::subcommand())
+        .args_conflicts_with_subcommands(true)
+        .subcommand_negates_reqs(true)
+}
+
+/// The shell asked for, if `matches` are those of the `completions`
+/// subcommand
+pub fn requested_shell(matches: &ArgMatches) -> Option<Shell> {
+    matches
+        .subcommand_matches(COMMAND)
+        .and_then(|matches| matches.get_one::<Shell>("shell").copied())
+}
+
+/// Write the completion script of `shell` to `out`
+pub fn generate(shell: Shell, out: &mut dyn Write) -> io::Result<()> {
+    let mut command = command();
+    let name = command.get_name().to_string();
+    // Generated in memory: the generator panics on write errors, such as a
+    // closed pipe
+    let mut script = Vec::new();
+    clap_complete::generate(shell, &mut command, name, &mut script);
+    out.write_all(&script)?;
+    out.flush()
+}
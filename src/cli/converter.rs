@@ -1,12 +1,51 @@
 //! Conversion orchestration logic.
 
-use crate::chunking::{BaseChunker, HierarchicalChunker};
-use crate::cli::args::{CliArgs, InputFormat, OutputFormat};
+#[cfg(feature = "parquet")]
+use crate::chunking::hf_dataset::DatasetWriter;
+#[cfg(feature = "parquet")]
+use crate::chunking::parquet;
+use crate::chunking::sinks::{ChunkSink, LocalStore};
+use crate::chunking::{
+    export, langchain, llamaindex, tokenizer, ApproxTokenizer, BaseChunk, BaseChunker, ChunkStats,
+    ChunkerOptions, ChunkerRegistry, Tokenizer,
+};
+use crate::cli::archive::{self, ArchiveKind};
+use crate::cli::args::{ChunkerKind, CliArgs, InputFormat, OutputFormat, SplitLevel};
+use crate::cli::exclude::ExcludeSet;
+use crate::cli::exit::{failure, ExitCode};
+use crate::cli::journal::{Journal, JOURNAL_FILE};
+use crate::cli::logging;
 use crate::cli::output;
-use crate::DocumentConverter;
+use crate::cli::progress::ProgressDisplay;
+use crate::cli::remote;
+use crate::cli::report::{self, BatchReport, ReportEntry, ReportStatus};
+#[cfg(feature = "templates")]
+use crate::cli::template::TemplateRenderer;
+use crate::cli::watch::{ChangeTracker, POLL_INTERVAL};
+use crate::datamodel::{ConversionMetrics, TimedStage};
+use crate::embeddings::{self, Embedder, HashEmbedder};
+use crate::pipeline::{LanguageDetector, ProgressEvent, ProgressObserver};
+use crate::{ConversionCache, ConverterConfig, DocumentConverter};
 use anyhow::{Context, Result};
+use clap::ValueEnum;
 use std::fs;
+use std::io::{self, Read, Write};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+#[cfg(not(feature = "parquet"))]
+const PARQUET_DISABLED: &str =
+    "--to parquet requires docling-rs to be built with the `parquet` feature";
+
+#[cfg(not(feature = "parquet"))]
+const DATASET_DISABLED: &str =
+    "--dataset requires docling-rs to be built with the `parquet` feature";
+
+#[cfg(not(feature = "templates"))]
+const TEMPLATES_DISABLED: &str =
+    "--template requires docling-rs to be built with the `templates` feature";
 
 /// Single file conversion job
 #[derive(Debug)]
@@ -22,6 +61,13 @@ pub struct ConversionResult {
     pub job: ConversionJob,
     pub success: bool,
     pub error: Option<String>,
+    /// Time spent on the job, output included
+    pub duration: Duration,
+    /// Metrics of the conversion, when it succeeded
+    pub metrics: Option<ConversionMetrics>,
+    /// Rendered output kept for the combined file instead of being written
+    /// (`--combine`)
+    pub content: Option<Vec<u8>>,
 }
 
 /// Batch conversion progress tracker
@@ -31,69 +77,451 @@ pub struct BatchProgress {
     pub processed: usize,
     pub successful: usize,
     pub failed: usize,
+    /// Files skipped because an earlier run converted them (`--resume`)
+    pub skipped: usize,
 }
 
 /// Main CLI converter orchestrator
 pub struct Converter {
     args: CliArgs,
     converter: DocumentConverter,
+    /// Progress bars, on a terminal without `--quiet`
+    progress: Option<Arc<ProgressDisplay>>,
+    /// Embedding model of `--embed`, loaded by [`run`](Self::run)
+    embedder: OnceLock<Arc<dyn Embedder>>,
+    /// Embedding model of `--chunker semantic`, loaded by [`run`](Self::run)
+    chunk_embedder: OnceLock<Arc<dyn Embedder>>,
+    /// Vector index of `--store`, opened by [`run`](Self::run)
+    store: OnceLock<Mutex<LocalStore>>,
+    /// Dataset of `--dataset`, created by [`run`](Self::run) and finished
+    /// once every document is in it
+    #[cfg(feature = "parquet")]
+    dataset: Mutex<Option<DatasetWriter>>,
+    /// Files and directories skipped in batch mode (`--exclude`)
+    exclude: ExcludeSet,
 }
 
 impl Converter {
     /// Create new converter with CLI arguments
-    pub fn new(args: CliArgs) -> Self {
+    pub fn new(mut args: CliArgs) -> Self {
+        // Embeddings are computed per chunk, and datasets hold chunks
+        args.chunk |= args.embed || args.dataset.is_some();
+
+        // Settings without a flag (PDF passwords and pages, HTML options)
+        // come from the profile
+        let html_config = args
+            .profile
+            .as_ref()
+            .map(ConverterConfig::html_config)
+            .unwrap_or_default();
+        let mut converter = DocumentConverter::builder()
+            .pdf_config(args.pdf_config())
+            .html_config(html_config)
+            .build();
+        if args.detect_language {
+            converter = converter.with_language_detection(LanguageDetector::new());
+        }
+        converter = converter.with_section_grouping(args.group_sections);
+        if let Some(dir) = &args.cache_dir {
+            converter = converter.with_cache(ConversionCache::new(dir));
+        }
+        converter = converter.with_limits(args.limits());
+
+        let progress =
+            ProgressDisplay::enabled(args.quiet).then(|| Arc::new(ProgressDisplay::new()));
+        if let Some(display) = &progress {
+            let display = Arc::clone(display);
+            converter = converter.with_progress(move |name: &str, event: &ProgressEvent| {
+                display.on_progress(name, event)
+            });
+        }
+        // Patterns are validated when parsing the arguments
+        let exclude = ExcludeSet::new(&args.exclude).unwrap_or_default();
         Self {
             args,
-            converter: DocumentConverter::new(),
+            converter,
+            progress,
+            embedder: OnceLock::new(),
+            chunk_embedder: OnceLock::new(),
+            store: OnceLock::new(),
+            #[cfg(feature = "parquet")]
+            dataset: Mutex::new(None),
+            exclude,
+        }
+    }
+
+    /// Send log messages to stderr, as given by `--log-level` and
+    /// `--log-format`; see [`logging`](crate::cli::logging)
+    pub fn init_logging(&self) {
+        logging::init(
+            self.args.log_level(),
+            self.args.log_format,
+            self.progress.clone(),
+        );
+    }
+
+    /// Run `print` with the progress bars hidden, so its lines are not drawn over
+    fn print(&self, print: impl FnOnce()) {
+        match &self.progress {
+            Some(progress) => progress.suspend(print),
+            None => print(),
         }
     }
 
     /// Run conversion based on CLI arguments
+    ///
+    /// Errors carry the class of the failure; see [`exit`](crate::cli::exit).
     pub fn run(&self) -> Result<()> {
         // Validate input exists
-        if !self.args.input.exists() {
-            anyhow::bail!("Input path does not exist: {:?}", self.args.input);
+        if !self.args.reads_stdin()
+            && !remote::is_url(&self.args.input)
+            && !self.args.input.exists()
+        {
+            return Err(failure(
+                ExitCode::InputNotFound,
+                format!("Input path does not exist: {:?}", self.args.input),
+            ));
         }
 
-        // Determine if batch or single file
-        if self.args.input.is_dir() {
-            self.run_batch()
+        self.check_options()
+            .map_err(|e| failure(ExitCode::InvalidArguments, format!("{:#}", e)))?;
+
+        // Determine if stdin, batch, archive or single file
+        let outcome = if self.args.watch {
+            self.run_watch(&self.args.input)
+        } else if self.args.reads_stdin() {
+            self.run_stdin()
+        } else if remote::is_url(&self.args.input) {
+            self.run_url()
+        } else if self.args.input.is_dir() {
+            self.run_batch(&self.args.input)
+        } else if let Some(kind) = ArchiveKind::from_path(&self.args.input) {
+            self.run_archive(kind)
         } else {
             self.run_single()
+        };
+        // The dataset holds the documents converted, even when others failed
+        let finished = self.finish_dataset();
+        outcome.and(finished)
+    }
+
+    /// Fail if options cannot be used together or with this input, and load
+    /// the embedding model and template they name
+    fn check_options(&self) -> Result<()> {
+        #[cfg(not(feature = "parquet"))]
+        if self.args.output_format == OutputFormat::Parquet && self.args.template.is_none() {
+            anyhow::bail!(PARQUET_DISABLED);
+        }
+
+        if matches!(
+            self.args.output_format,
+            OutputFormat::Html
+                | OutputFormat::Latex
+                | OutputFormat::TextLayout
+                | OutputFormat::DoclingJson
+                | OutputFormat::Doctags
+                | OutputFormat::Xml
+        ) && self.args.chunk
+            && self.args.template.is_none()
+        {
+            anyhow::bail!(
+                "--to {} writes whole documents and cannot be used with --chunk",
+                self.args.output_format
+            );
+        }
+
+        if self.args.output_format.needs_chunks()
+            && !self.args.chunk
+            && self.args.template.is_none()
+        {
+            anyhow::bail!(
+                "--to {} writes chunks and needs --chunk",
+                self.args.output_format
+            );
+        }
+
+        if self.args.combine.is_some() {
+            if !output::can_combine(self.args.output_format) {
+                anyhow::bail!(
+                    "--combine needs markdown, text, json or jsonl output, not {}",
+                    self.args.output_format
+                );
+            }
+            if !self.args.input.is_dir() && ArchiveKind::from_path(&self.args.input).is_none() {
+                anyhow::bail!("--combine needs a directory or archive input");
+            }
+        }
+
+        if self.args.embed {
+            if self.args.template.is_none()
+                && self.args.store.is_none()
+                && !matches!(
+                    self.args.output_format,
+                    OutputFormat::Json | OutputFormat::Jsonl
+                )
+            {
+                anyhow::bail!(
+                    "--embed writes vectors to json or jsonl output, not {}",
+                    self.args.output_format
+                );
+            }
+            if let Some(spec) = &self.args.embedding_model {
+                let embedder = embeddings::from_spec(spec)?;
+                if let Some(dir) = &self.args.store {
+                    let mut store = LocalStore::open(dir)?;
+                    store.set_model(embedder.model())?;
+                    let _ = self.store.set(Mutex::new(store));
+                }
+                // Only set here
+                let _ = self.embedder.set(Arc::from(embedder));
+            }
+        }
+
+        // Semantic chunking compares elements with the model of --embed, if any
+        if self.args.chunk && self.args.chunker == ChunkerKind::Semantic {
+            let embedder = match (self.embedder.get(), &self.args.embedding_model) {
+                (Some(embedder), _) => Arc::clone(embedder),
+                (None, Some(spec)) => Arc::from(embeddings::from_spec(spec)?),
+                (None, None) => Arc::new(HashEmbedder::default()),
+            };
+            let _ = self.chunk_embedder.set(embedder);
+        }
+
+        if let Some(dir) = &self.args.dataset {
+            self.create_dataset(dir)?;
+        }
+
+        // Fail before converting anything if the template is unusable
+        if let Some(template) = &self.args.template {
+            self.load_template(template)?;
+        }
+        Ok(())
+    }
+
+    /// Convert the files of a directory, then keep converting files that
+    /// appear or change in it until interrupted
+    ///
+    /// At start, files whose output is already newer than them are skipped.
+    /// See [`watch`](crate::cli::watch).
+    fn run_watch(&self, input_dir: &Path) -> Result<()> {
+        if !input_dir.is_dir() {
+            return Err(failure(
+                ExitCode::InvalidArguments,
+                format!("--watch needs a directory input, not {:?}", input_dir),
+            ));
+        }
+
+        // Outputs written inside the watched directory are not inputs
+        let output_root = self.args.output_dir.as_deref().unwrap_or(Path::new("."));
+        fs::create_dir_all(output_root).with_context(|| {
+            format!(
+                "Permission denied or unable to create output directory: {:?}",
+                output_root
+            )
+        })?;
+        let output_root = fs::canonicalize(output_root)?;
+        if output_root == fs::canonicalize(input_dir)? {
+            return Err(failure(
+                ExitCode::InvalidArguments,
+                "--watch needs an output directory other than the watched directory",
+            ));
+        }
+
+        let debounce = Duration::from_millis(self.args.debounce);
+        let mut tracker = ChangeTracker::new(debounce);
+        if !self.args.quiet {
+            eprintln!(
+                "Watching {:?} for new or modified files (Ctrl-C to stop)",
+                input_dir
+            );
+        }
+
+        let mut first_scan = true;
+        loop {
+            let jobs: Vec<ConversionJob> = self
+                .collect_jobs(input_dir, first_scan)?
+                .into_iter()
+                .filter(|job| {
+                    fs::canonicalize(&job.input_path)
+                        .map_or(true, |path| !path.starts_with(&output_root))
+                })
+                .collect();
+
+            let now = Instant::now();
+            for job in &jobs {
+                let convert_new = !first_scan || !Self::is_up_to_date(job);
+                tracker.observe(&job.input_path, now, convert_new);
+            }
+            tracker.retain(&jobs.iter().map(|job| job.input_path.clone()).collect());
+
+            for path in tracker.settled(now) {
+                let Some(job) = jobs.iter().find(|job| job.input_path == path) else {
+                    continue;
+                };
+                let result = self.convert_file(job);
+                match result.error {
+                    None if !self.args.quiet => {
+                        self.print(|| println!("{}", job.input_path.display()))
+                    }
+                    None => {}
+                    Some(error) => {
+                        tracing::error!(input = %job.input_path.display(), "{}", error)
+                    }
+                }
+            }
+
+            first_scan = false;
+            std::thread::sleep(debounce.min(POLL_INTERVAL));
+        }
+    }
+
+    /// Whether the output of `job` exists and is newer than its input
+    fn is_up_to_date(job: &ConversionJob) -> bool {
+        let modified = |path: &Path| fs::metadata(path).and_then(|m| m.modified()).ok();
+        match (modified(&job.input_path), modified(&job.output_path)) {
+            (Some(input), Some(output)) => output >= input,
+            _ => false,
         }
     }
 
+    /// Convert the document read from stdin, writing the output to stdout
+    fn run_stdin(&self) -> Result<()> {
+        let Some(from) = &self.args.input_format_filter else {
+            return Err(failure(
+                ExitCode::InvalidArguments,
+                "Reading from stdin needs --from FORMAT",
+            ));
+        };
+        let format = InputFormat::from_str(from, true).map_err(|_| {
+            failure(
+                ExitCode::UnsupportedFormat,
+                format!("Unknown input format for stdin: {}", from),
+            )
+        })?;
+        // Only the document goes to stdout
+        let writes_files = [
+            (self.args.split_by.is_some(), "--split-by"),
+            (self.args.export_tables.is_some(), "--export-tables"),
+            (self.args.export_images.is_some(), "--export-images"),
+        ];
+        if let Some((_, flag)) = writes_files.iter().find(|(set, _)| *set) {
+            return Err(failure(
+                ExitCode::InvalidArguments,
+                format!("{} writes files and cannot be used with stdin input", flag),
+            ));
+        }
+
+        let mut data = Vec::new();
+        io::stdin()
+            .lock()
+            .read_to_end(&mut data)
+            .context("Unable to read stdin")?;
+        tracing::info!("Converting {} bytes from stdin", data.len());
+
+        let name = format!("stdin.{}", from.to_lowercase());
+        let result = self
+            .converter
+            .convert_bytes(data, name, format.to_docling_format())
+            .context("Failed to convert stdin")?;
+        for warning in result.warnings() {
+            tracing::warn!(input = "stdin", "{}", warning);
+        }
+
+        let mut doc = result.document().clone();
+        if self.args.embed_images {
+            doc.embed_images();
+        }
+        let mut metrics = result.metrics().clone();
+        if let Some(store) = self.store.get() {
+            let stored = Instant::now();
+            self.store_chunks(store, &doc)?;
+            metrics.add_stage_time(TimedStage::Chunking, stored.elapsed());
+            tracing::info!(input = "stdin", "metrics: {}", metrics);
+            return Ok(());
+        }
+        if self.args.dataset.is_some() {
+            let chunked = Instant::now();
+            self.add_to_dataset(&doc)?;
+            metrics.add_stage_time(TimedStage::Chunking, chunked.elapsed());
+            tracing::info!(input = "stdin", "metrics: {}", metrics);
+            return Ok(());
+        }
+        let rendered = Instant::now();
+        let output = self.render(&doc)?;
+        if self.args.chunk && self.args.template.is_none() {
+            metrics.add_stage_time(TimedStage::Chunking, rendered.elapsed());
+        }
+        let mut stdout = io::stdout().lock();
+        stdout
+            .write_all(&output)
+            .and_then(|()| stdout.flush())
+            .context("Unable to write stdout")?;
+        tracing::info!(input = "stdin", "metrics: {}", metrics);
+        Ok(())
+    }
+
+    /// Expand an archive into a temporary directory and convert its contents as a batch
+    fn run_archive(&self, kind: ArchiveKind) -> Result<()> {
+        let temp_dir = tempfile::tempdir().context("Unable to create temporary directory")?;
+
+        tracing::info!("Extracting {:?}", self.args.input);
+
+        archive::extract(&self.args.input, kind, temp_dir.path(), &self.args.limits())?;
+
+        self.run_batch(temp_dir.path())
+    }
+
+    /// Download the document at the input URL and convert it
+    ///
+    /// The output is named after the URL slug. See [`remote`](crate::cli::remote).
+    fn run_url(&self) -> Result<()> {
+        let url = self.args.input.to_string_lossy();
+        let temp_dir = tempfile::tempdir().context("Unable to create temporary directory")?;
+
+        tracing::info!("Downloading {}", url);
+
+        let path = remote::download(&url, temp_dir.path(), &self.args.limits())?;
+        self.convert_single(&path, &url)
+    }
+
     /// Convert single file
     fn run_single(&self) -> Result<()> {
         let input_path = &self.args.input;
+        let name = input_path
+            .file_name()
+            .map(|name| name.to_string_lossy())
+            .unwrap_or_default();
+        self.convert_single(input_path, &name)
+    }
 
+    /// Convert the file at `input_path`, printing `name` once converted
+    fn convert_single(&self, input_path: &Path, name: &str) -> Result<()> {
         // Detect format
         let format = self.detect_format(input_path)?;
 
         // Determine output path
         let output_path = self.get_output_path(input_path)?;
 
-        if self.args.verbose {
-            eprintln!("Converting {:?} to {:?}", input_path, output_path);
-        }
+        tracing::info!("Converting {:?} to {:?}", input_path, output_path);
 
         // Create job
         let job = ConversionJob {
-            input_path: input_path.clone(),
+            input_path: input_path.to_path_buf(),
             output_path: output_path.clone(),
             format,
         };
 
         // Execute conversion
         let result = self.convert_file(&job);
+        if let Some(progress) = &self.progress {
+            progress.finish();
+        }
 
         // Handle result
         if result.success {
-            if !self.args.quiet {
-                // Print input filename instead of output path
-                if let Some(filename) = input_path.file_name() {
-                    println!("{}", filename.to_string_lossy());
-                }
+            // Print the input name instead of the output path
+            if !self.args.quiet && !name.is_empty() {
+                println!("{}", name);
             }
             Ok(())
         } else {
@@ -105,14 +533,15 @@ impl Converter {
     }
 
     /// Convert batch of files in directory
-    fn run_batch(&self) -> Result<()> {
-        let input_dir = &self.args.input;
-
+    fn run_batch(&self, input_dir: &Path) -> Result<()> {
         // Collect all files
-        let jobs = self.collect_jobs(input_dir)?;
+        let jobs = self.collect_jobs(input_dir, true)?;
 
         if jobs.is_empty() {
-            anyhow::bail!("No supported files found in directory");
+            return Err(failure(
+                ExitCode::InputNotFound,
+                "No supported files found in directory",
+            ));
         }
 
         let mut progress = BatchProgress {
@@ -120,51 +549,225 @@ impl Converter {
             ..Default::default()
         };
 
-        if !self.args.quiet && self.args.verbose {
-            eprintln!("Processing {} files...", jobs.len());
-        }
+        tracing::info!("Processing {} files...", jobs.len());
+
+        let started = Instant::now();
+        let mut report = self
+            .args
+            .report
+            .as_ref()
+            .map(|_| BatchReport::new(&self.args.input));
+        let mut add_to_report = |job: &ConversionJob, status, result: Option<ConversionResult>| {
+            if let Some(report) = report.as_mut() {
+                let (error, duration, metrics) = match result {
+                    Some(result) => (result.error, result.duration, result.metrics),
+                    None => (None, Duration::ZERO, None),
+                };
+                report.push(ReportEntry {
+                    input: job
+                        .input_path
+                        .strip_prefix(input_dir)
+                        .unwrap_or(&job.input_path)
+                        .to_path_buf(),
+                    output: self
+                        .args
+                        .combine
+                        .clone()
+                        .unwrap_or_else(|| job.output_path.clone()),
+                    status,
+                    error,
+                    duration_ms: report::millis(duration),
+                    metrics,
+                });
+            }
+        };
+
+        let mut journal = if self.args.resume {
+            let output_dir = self.args.output_dir.as_deref().unwrap_or(Path::new("."));
+            Some(Journal::open(&output_dir.join(JOURNAL_FILE))?)
+        } else {
+            None
+        };
 
-        // Process each file
+        // Files an earlier run converted are skipped up front
+        let mut pending = Vec::with_capacity(jobs.len());
         for job in jobs {
-            let result = self.convert_file(&job);
-            progress.processed += 1;
-
-            if result.success {
-                progress.successful += 1;
-                if !self.args.quiet {
-                    // Print input filename instead of output path
-                    if let Some(filename) = job.input_path.file_name() {
-                        println!("{}", filename.to_string_lossy());
-                    }
-                }
+            if journal
+                .as_ref()
+                .is_some_and(|journal| journal.is_done(input_dir, &job.input_path))
+            {
+                progress.skipped += 1;
+                tracing::info!("Already converted, skipping: {:?}", job.input_path);
+                add_to_report(&job, ReportStatus::Skipped, None);
             } else {
-                progress.failed += 1;
-                if !self.args.quiet {
-                    // Print input filename in error message
-                    if let Some(filename) = job.input_path.file_name() {
-                        eprintln!("{}", filename.to_string_lossy());
+                pending.push(job);
+            }
+        }
+
+        // Workers take the next pending job until none is left (or one failed
+        // with --abort-on-error) and send back the results; only this thread
+        // prints, so lines of concurrent jobs never mix
+        let mut aborted = false;
+        let mut failures = Vec::new();
+        let mut combined = Vec::new();
+        let next = AtomicUsize::new(0);
+        let stop = AtomicBool::new(false);
+        let workers = self.args.jobs.get().min(pending.len());
+        if let Some(display) = &self.progress {
+            display.start_files(pending.len());
+        }
+        let outcome = std::thread::scope(|scope| -> Result<()> {
+            let (sender, results) = mpsc::channel();
+            for _ in 0..workers {
+                let sender = sender.clone();
+                let (pending, next, stop) = (&pending, &next, &stop);
+                scope.spawn(move || {
+                    while !stop.load(Ordering::SeqCst) {
+                        let Some(job) = pending.get(next.fetch_add(1, Ordering::SeqCst)) else {
+                            break;
+                        };
+                        if let Some(display) = &self.progress {
+                            display.file_started(&source_name(input_dir, &job.input_path));
+                        }
+                        let result = self.convert_file(job);
+                        if !result.success && self.args.abort_on_error {
+                            stop.store(true, Ordering::SeqCst);
+                        }
+                        if sender.send(result).is_err() {
+                            break;
+                        }
                     }
+                });
+            }
+            drop(sender);
+
+            for mut result in results {
+                progress.processed += 1;
+                if let Some(display) = &self.progress {
+                    display.file_done(&source_name(input_dir, &result.job.input_path));
+                }
+                let job = ConversionJob {
+                    input_path: result.job.input_path.clone(),
+                    output_path: result.job.output_path.clone(),
+                    format: result.job.format,
+                };
+                let error = result.error.clone();
+                let success = result.success;
+                if let Some(content) = result.content.take() {
+                    combined.push((source_name(input_dir, &job.input_path), content));
                 }
+                let status = if success {
+                    ReportStatus::Converted
+                } else {
+                    ReportStatus::Failed
+                };
+                add_to_report(&job, status, Some(result));
+
+                if success {
+                    progress.successful += 1;
+                    if let Some(journal) = journal.as_mut() {
+                        if let Err(e) = journal.record(input_dir, &job.input_path) {
+                            stop.store(true, Ordering::SeqCst);
+                            return Err(e);
+                        }
+                    }
+                    if !self.args.quiet {
+                        // Print input filename instead of output path
+                        if let Some(filename) = job.input_path.file_name() {
+                            self.print(|| println!("{}", filename.to_string_lossy()));
+                        }
+                    }
+                } else {
+                    progress.failed += 1;
+                    tracing::error!(
+                        input = %job.input_path.display(),
+                        "{}",
+                        error.as_deref().unwrap_or("Unknown error")
+                    );
+                    failures.push((job.input_path, error.unwrap_or_default()));
 
-                // Handle abort on error
-                if self.args.abort_on_error {
-                    anyhow::bail!("Aborting due to error");
+                    // Handle abort on error; jobs already running still finish
+                    if self.args.abort_on_error {
+                        aborted = true;
+                    }
                 }
             }
+            Ok(())
+        });
+        if let Some(display) = &self.progress {
+            display.finish();
+        }
+        outcome?;
+
+        if !self.args.quiet && !failures.is_empty() {
+            failures.sort();
+            eprintln!("Failed to convert {} files:", failures.len());
+            for (path, error) in &failures {
+                eprintln!("  {}: {}", path.display(), error);
+            }
+        }
+
+        // Documents of the combined file are in path order, whatever order
+        // the jobs finished in
+        if let Some(path) = &self.args.combine {
+            combined.sort();
+            let content =
+                output::combine(&combined, self.args.output_format, self.args.json_compact)?;
+            if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+                fs::create_dir_all(parent).with_context(|| {
+                    format!(
+                        "Permission denied or unable to create output directory: {:?}",
+                        parent
+                    )
+                })?;
+            }
+            fs::write(path, content).with_context(|| {
+                format!(
+                    "Permission denied or unable to write output file: {:?}",
+                    path
+                )
+            })?;
+        }
+
+        if let (Some(mut report), Some(path)) = (report, &self.args.report) {
+            report.aborted = aborted;
+            report.write(path, started.elapsed())?;
+        }
+        if aborted {
+            return Err(failure(ExitCode::BatchFailed, "Aborting due to error"));
+        }
+
+        if progress.skipped > 0 {
+            tracing::info!(
+                "Skipped {} files converted by an earlier run",
+                progress.skipped
+            );
         }
 
         // Final result
         if progress.failed > 0 && !self.args.continue_on_error {
-            anyhow::bail!("{} files failed to convert", progress.failed);
+            return Err(failure(
+                ExitCode::BatchFailed,
+                format!("{} files failed to convert", progress.failed),
+            ));
+        }
+
+        // A complete run leaves nothing to resume
+        if let Some(journal) = journal.filter(|_| progress.failed == 0) {
+            journal.remove()?;
         }
 
         Ok(())
     }
 
-    /// Collect conversion jobs from directory (recursive)
-    fn collect_jobs(&self, dir: &Path) -> Result<Vec<ConversionJob>> {
+    /// Collect conversion jobs from directory (recursive unless
+    /// `--no-recursive`), skipping paths matching `--exclude`
+    ///
+    /// Files of unsupported formats are logged unless `report_unsupported`
+    /// is off.
+    fn collect_jobs(&self, dir: &Path, report_unsupported: bool) -> Result<Vec<ConversionJob>> {
         let mut jobs = Vec::new();
-        self.collect_jobs_recursive(dir, dir, &mut jobs)?;
+        self.collect_jobs_recursive(dir, dir, &mut jobs, report_unsupported)?;
         Ok(jobs)
     }
 
@@ -174,14 +777,23 @@ impl Converter {
         root_dir: &Path,
         current_dir: &Path,
         jobs: &mut Vec<ConversionJob>,
+        report_unsupported: bool,
     ) -> Result<()> {
         for entry in fs::read_dir(current_dir)? {
             let entry = entry?;
             let path = entry.path();
+            if self
+                .exclude
+                .is_excluded(path.strip_prefix(root_dir).unwrap_or(&path))
+            {
+                continue;
+            }
 
             if path.is_dir() {
                 // Recursively process subdirectories
-                self.collect_jobs_recursive(root_dir, &path, jobs)?;
+                if !self.args.no_recursive {
+                    self.collect_jobs_recursive(root_dir, &path, jobs, report_unsupported)?;
+                }
             } else if path.is_file() {
                 // Check format filter
                 if let Some(ref filter) = self.args.input_format_filter {
@@ -205,10 +817,10 @@ impl Converter {
                         });
                     }
                 } else {
-                    // Unsupported format - report to stderr if not quiet
-                    if !self.args.quiet {
+                    // Unsupported format - report unless rescanning
+                    if report_unsupported {
                         if let Some(filename) = path.file_name() {
-                            eprintln!(
+                            tracing::warn!(
                                 "Unsupported format, skipping: {}",
                                 filename.to_string_lossy()
                             );
@@ -223,8 +835,14 @@ impl Converter {
 
     /// Convert a single file job
     fn convert_file(&self, job: &ConversionJob) -> ConversionResult {
-        match self.do_convert(job) {
-            Ok(()) => ConversionResult {
+        // At error level, so messages name their file at every log level
+        let _span =
+            tracing::error_span!("convert_file", input = %job.input_path.display()).entered();
+        let started = Instant::now();
+        let outcome = self.do_convert(job);
+        let duration = started.elapsed();
+        match outcome {
+            Ok((metrics, content)) => ConversionResult {
                 job: ConversionJob {
                     input_path: job.input_path.clone(),
                     output_path: job.output_path.clone(),
@@ -232,6 +850,9 @@ impl Converter {
                 },
                 success: true,
                 error: None,
+                duration,
+                metrics: Some(metrics),
+                content,
             },
             Err(e) => ConversionResult {
                 job: ConversionJob {
@@ -240,59 +861,215 @@ impl Converter {
                     format: job.format,
                 },
                 success: false,
-                error: Some(e.to_string()),
+                error: Some(format!("{:#}", e)),
+                duration,
+                metrics: None,
+                content: None,
             },
         }
     }
 
-    /// Perform actual conversion
-    fn do_convert(&self, job: &ConversionJob) -> Result<()> {
+    /// Perform actual conversion, returning its metrics, and the rendered
+    /// output with `--combine`
+    fn do_convert(&self, job: &ConversionJob) -> Result<(ConversionMetrics, Option<Vec<u8>>)> {
+        // Never overwrite the input (e.g. `notes.txt --to text` in the same directory)
+        if job.output_path.exists()
+            && fs::canonicalize(&job.output_path)? == fs::canonicalize(&job.input_path)?
+        {
+            anyhow::bail!(
+                "Output path {:?} would overwrite the input file",
+                job.output_path
+            );
+        }
+
         // Convert using convert_file
         let result = self
             .converter
             .convert_file(&job.input_path)
             .with_context(|| format!("Failed to convert {:?}", job.input_path))?;
 
+        // Parts that could not be converted are left out
+        for warning in result.warnings() {
+            tracing::warn!("{}", warning);
+        }
+
         // Get document
         let doc = result.document();
+        let mut metrics = result.metrics().clone();
+        // Sections are written to a directory named after the output file
+        let split_dir = self
+            .args
+            .split_by
+            .map(|_| job.output_path.with_extension(""));
+        let exported;
+        let doc = match &self.args.export_images {
+            Some(dir) => {
+                let mut copy = doc.clone();
+                let stem = job
+                    .output_path
+                    .file_stem()
+                    .and_then(|s| s.to_str())
+                    .unwrap_or("document");
+                let from = split_dir
+                    .as_deref()
+                    .or(job.output_path.parent())
+                    .unwrap_or(Path::new("."));
+                let images = copy
+                    .export_images(dir, stem, &relative_uri(from, dir)?)
+                    .with_context(|| format!("Unable to write images to {:?}", dir))?;
+                for path in images {
+                    tracing::info!("Exported image {}", path.display());
+                }
+                exported = copy;
+                &exported
+            }
+            None if self.args.embed_images => {
+                let mut copy = doc.clone();
+                copy.embed_images();
+                exported = copy;
+                &exported
+            }
+            None => doc,
+        };
+
+        let mut content = None;
+        match (self.args.split_by, &split_dir, self.store.get()) {
+            (_, _, Some(store)) => {
+                let stored = Instant::now();
+                self.store_chunks(store, doc)?;
+                metrics.add_stage_time(TimedStage::Chunking, stored.elapsed());
+            }
+            _ if self.args.dataset.is_some() => {
+                let chunked = Instant::now();
+                self.add_to_dataset(doc)?;
+                metrics.add_stage_time(TimedStage::Chunking, chunked.elapsed());
+            }
+            (Some(level), Some(dir), None) => self.write_sections(doc, level, dir)?,
+            _ => {
+                let rendered = Instant::now();
+                let output_content = self.render(doc)?;
+                if self.args.chunk && self.args.template.is_none() {
+                    metrics.add_stage_time(TimedStage::Chunking, rendered.elapsed());
+                }
+                if self.args.combine.is_some() {
+                    content = Some(output_content);
+                } else {
+                    // Ensure output directory exists
+                    if let Some(parent) = job.output_path.parent() {
+                        fs::create_dir_all(parent).with_context(|| {
+                            format!(
+                                "Permission denied or unable to create output directory: {:?}",
+                                parent
+                            )
+                        })?;
+                    }
+
+                    // Write output
+                    fs::write(&job.output_path, output_content).with_context(|| {
+                        format!(
+                            "Permission denied or unable to write output file: {:?}",
+                            job.output_path
+                        )
+                    })?;
+                }
+            }
+        }
+
+        if let Some(format) = self.args.export_tables {
+            let dir = job.output_path.parent().unwrap_or(Path::new("."));
+            let stem = job
+                .output_path
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or("document");
+            let tables = output::export_tables(doc, dir, stem, format)
+                .with_context(|| format!("Unable to write tables to {:?}", dir))?;
+            for path in tables {
+                tracing::info!("Exported table {}", path.display());
+            }
+        }
+
+        tracing::info!("metrics: {}", metrics);
 
-        // Apply chunking if enabled
-        let output_content = if self.args.chunk {
+        Ok((metrics, content))
+    }
+
+    /// Render a document in the output format, or through the template
+    fn render(&self, doc: &crate::datamodel::DoclingDocument) -> Result<Vec<u8>> {
+        Ok(if let Some(template) = &self.args.template {
+            self.render_template(template, doc)?
+        } else if self.args.chunk {
             self.generate_chunked_output(doc)?
+        } else if self.args.output_format == OutputFormat::Parquet {
+            self.parquet_nodes(doc)?
         } else {
             // Generate output based on format (no chunking)
-            match self.args.output_format {
-                OutputFormat::Markdown => output::to_markdown(doc),
-                OutputFormat::Json => output::to_json(doc)?,
-                OutputFormat::Text => output::to_text(doc),
-            }
-        };
+            let text = match self.args.output_format {
+                OutputFormat::Markdown => output::to_markdown_with(doc, &self.markdown_options()),
+                OutputFormat::Html => output::to_html(doc),
+                OutputFormat::Latex => output::to_latex(doc),
+                OutputFormat::Json => output::to_json_with(doc, &self.json_options())?,
+                OutputFormat::Text => output::to_text_with(doc, &self.text_options()),
+                OutputFormat::TextLayout => output::to_layout_text(doc),
+                OutputFormat::DoclingJson => output::to_docling_json(doc)?,
+                OutputFormat::Doctags => output::to_doctags(doc),
+                OutputFormat::Xml => output::to_xml(doc),
+                OutputFormat::Jsonl => output::to_node_jsonl(doc),
+                OutputFormat::Parquet => unreachable!("written as bytes above"),
+                OutputFormat::LangChain | OutputFormat::LlamaIndex => {
+                    anyhow::bail!("--to {} needs --chunk", self.args.output_format)
+                }
+            };
+            text.into_bytes()
+        })
+    }
 
-        // Ensure output directory exists
-        if let Some(parent) = job.output_path.parent() {
-            fs::create_dir_all(parent).with_context(|| {
+    /// Write each section of a document to its own file in `dir`, plus an index
+    fn write_sections(
+        &self,
+        doc: &crate::datamodel::DoclingDocument,
+        level: SplitLevel,
+        dir: &Path,
+    ) -> Result<()> {
+        fs::create_dir_all(dir).with_context(|| {
+            format!(
+                "Permission denied or unable to create output directory: {:?}",
+                dir
+            )
+        })?;
+
+        let parts = doc.split_sections(level.level());
+        let files = output::section_files(&parts, self.output_extension());
+        for (part, (_, file)) in parts.iter().zip(&files) {
+            let path = dir.join(file);
+            fs::write(&path, self.render(part)?).with_context(|| {
                 format!(
-                    "Permission denied or unable to create output directory: {:?}",
-                    parent
+                    "Permission denied or unable to write output file: {:?}",
+                    path
                 )
             })?;
+            tracing::info!("Wrote section {}", path.display());
         }
 
-        // Write output
-        fs::write(&job.output_path, output_content).with_context(|| {
+        let (name, index) = output::section_index(doc, &files, self.args.output_format)?;
+        let path = dir.join(name);
+        fs::write(&path, index).with_context(|| {
             format!(
                 "Permission denied or unable to write output file: {:?}",
-                job.output_path
+                path
             )
         })?;
-
         Ok(())
     }
 
     /// Detect format from file path (error if unsupported)
     fn detect_format(&self, path: &Path) -> Result<InputFormat> {
-        self.try_detect_format(path)
-            .ok_or_else(|| anyhow::anyhow!("Unsupported file format: {:?}", path))
+        self.try_detect_format(path).ok_or_else(|| {
+            failure(
+                ExitCode::UnsupportedFormat,
+                format!("Unsupported file format: {:?}", path),
+            )
+        })
     }
 
     /// Try to detect format (returns None if unsupported)
@@ -309,7 +1086,7 @@ impl Converter {
             .and_then(|s| s.to_str())
             .ok_or_else(|| anyhow::anyhow!("Invalid filename"))?;
 
-        let extension = self.args.output_format.extension();
+        let extension = self.output_extension();
         let output_filename = format!("{}.{}", file_stem, extension);
 
         let output_path = if let Some(ref output_dir) = self.args.output_dir {
@@ -328,7 +1105,7 @@ impl Converter {
             .and_then(|s| s.to_str())
             .ok_or_else(|| anyhow::anyhow!("Invalid filename"))?;
 
-        let extension = self.args.output_format.extension();
+        let extension = self.output_extension();
         let output_filename = format!("{}.{}", file_stem, extension);
 
         // Get relative path from root_dir
@@ -346,19 +1123,332 @@ impl Converter {
         Ok(output_path)
     }
 
-    /// Generate chunked output from document
-    fn generate_chunked_output(&self, doc: &crate::datamodel::DoclingDocument) -> Result<String> {
-        // Create hierarchical chunker
-        let chunker = HierarchicalChunker::new();
+    /// Extension of output files
+    ///
+    /// A template's own extension wins over the output format, ignoring a
+    /// trailing `.tera` (`post.md.tera` writes `.md` files).
+    fn output_extension(&self) -> &str {
+        self.args
+            .template
+            .as_deref()
+            .and_then(|path| path.file_name())
+            .and_then(|name| name.to_str())
+            .and_then(|name| Path::new(name.trim_end_matches(".tera")).extension())
+            .and_then(|ext| ext.to_str())
+            .unwrap_or_else(|| self.args.output_format.extension())
+    }
+
+    /// Markdown export options from the command line
+    fn markdown_options(&self) -> output::MarkdownExportOptions {
+        output::MarkdownExportOptions::new()
+            .with_heading_style(self.args.md_heading_style)
+            .with_escape(self.args.md_escape)
+            .with_bullet(self.args.md_bullet)
+            .with_table_alignment(self.args.md_table_align)
+            .with_max_width(self.args.md_wrap)
+            .with_page_markers(self.args.page_markers)
+    }
+
+    /// Plain text export options from the command line
+    fn text_options(&self) -> output::TextExportOptions {
+        output::TextExportOptions::new().with_page_markers(self.args.page_markers)
+    }
+
+    /// JSON export options from the command line
+    fn json_options(&self) -> output::JsonOptions {
+        output::JsonOptions::new()
+            .with_pretty(!self.args.json_compact)
+            .with_bboxes(!self.args.json_no_bboxes)
+            .with_images(!self.args.json_no_images)
+            .with_tree(self.args.json_tree)
+    }
+
+    /// Tokenizer counting tokens in exported records
+    ///
+    /// Uses the configured tokenizer, like the chunker itself.
+    fn token_counter(&self) -> Result<Box<dyn Tokenizer>> {
+        Ok(match self.args.tokenizer.as_deref() {
+            Some(spec) => tokenizer::from_spec(spec)?,
+            None => Box::new(ApproxTokenizer::new()),
+        })
+    }
+
+    /// Write the nodes of a document as Parquet
+    #[cfg(feature = "parquet")]
+    fn parquet_nodes(&self, doc: &crate::datamodel::DoclingDocument) -> Result<Vec<u8>> {
+        Ok(parquet::nodes_to_bytes(
+            doc,
+            self.token_counter()?.as_ref(),
+        )?)
+    }
+
+    #[cfg(not(feature = "parquet"))]
+    fn parquet_nodes(&self, _doc: &crate::datamodel::DoclingDocument) -> Result<Vec<u8>> {
+        anyhow::bail!(PARQUET_DISABLED)
+    }
+
+    #[cfg(feature = "templates")]
+    fn load_template(&self, path: &Path) -> Result<TemplateRenderer> {
+        TemplateRenderer::from_file(path)
+    }
+
+    #[cfg(not(feature = "templates"))]
+    fn load_template(&self, _path: &Path) -> Result<()> {
+        anyhow::bail!(TEMPLATES_DISABLED)
+    }
+
+    /// Render a document, and its chunks with --chunk, through a template
+    #[cfg(feature = "templates")]
+    fn render_template(
+        &self,
+        path: &Path,
+        doc: &crate::datamodel::DoclingDocument,
+    ) -> Result<Vec<u8>> {
+        let renderer = self.load_template(path)?;
+        let text = if self.args.chunk {
+            let (chunker, chunks) = self.chunk(doc)?;
+            let counter = self.token_counter()?;
+            let mut records: Vec<_> = chunks
+                .into_iter()
+                .map(|chunk| export::ChunkRecord::new(chunk, chunker.as_ref(), counter.as_ref()))
+                .collect();
+            if let Some(embedder) = self.embedder.get() {
+                embeddings::embed_records(&mut records, embedder.as_ref())?;
+            }
+            renderer.render_chunks(doc, &records)?
+        } else {
+            renderer.render(doc)?
+        };
+        Ok(text.into_bytes())
+    }
+
+    #[cfg(not(feature = "templates"))]
+    fn render_template(
+        &self,
+        _path: &Path,
+        _doc: &crate::datamodel::DoclingDocument,
+    ) -> Result<Vec<u8>> {
+        anyhow::bail!(TEMPLATES_DISABLED)
+    }
+
+    /// Chunk a document with the configured chunker, printing stats if asked
+    fn chunk(
+        &self,
+        doc: &crate::datamodel::DoclingDocument,
+    ) -> Result<(Box<dyn BaseChunker>, Vec<BaseChunk>)> {
+        let tokenizer = self
+            .args
+            .tokenizer
+            .as_deref()
+            .map(tokenizer::from_spec)
+            .transpose()?;
+        let options = ChunkerOptions {
+            max_tokens: self.args.max_tokens,
+            chunk_size: Some(self.args.chunk_size),
+            tokenizer,
+            embedder: self.chunk_embedder.get().cloned(),
+        };
+        let chunker = ChunkerRegistry::new().create(self.args.chunker.registry_name(), options)?;
 
         // Collect all chunks
         let chunks: Vec<_> = chunker.chunk(doc).collect();
 
+        if self.args.stats {
+            let counter = self.token_counter()?;
+            let limit = self.args.max_tokens.unwrap_or_else(|| counter.max_tokens());
+            let stats = ChunkStats::compute(&chunks, chunker.as_ref(), counter.as_ref(), limit);
+            eprintln!("{}:\n{}", doc.name(), stats);
+        }
+
+        Ok((chunker, chunks))
+    }
+
+    /// Chunk and embed a document into the `--store` index, replacing the
+    /// chunks stored for it earlier
+    fn store_chunks(
+        &self,
+        store: &Mutex<LocalStore>,
+        doc: &crate::datamodel::DoclingDocument,
+    ) -> Result<()> {
+        let (chunker, chunks) = self.chunk(doc)?;
+        let counter = self.token_counter()?;
+        let mut records: Vec<_> = chunks
+            .into_iter()
+            .map(|chunk| export::ChunkRecord::new(chunk, chunker.as_ref(), counter.as_ref()))
+            .collect();
+        let embedder = self
+            .embedder
+            .get()
+            .context("--store needs --embedding-model")?;
+        embeddings::embed_records(&mut records, embedder.as_ref())?;
+
+        // Index files are replaced atomically, so they are whole even if
+        // another worker panicked while holding the lock
+        let mut store = store
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        store.remove_document(doc.name())?;
+        store
+            .write(&records)
+            .with_context(|| format!("Unable to store chunks in {:?}", store.dir()))?;
+        Ok(())
+    }
+
+    /// Start the `--dataset` dataset
+    #[cfg(feature = "parquet")]
+    fn create_dataset(&self, dir: &Path) -> Result<()> {
+        let mut dataset = DatasetWriter::create(dir)?;
+        if let Some(split) = &self.args.dataset_split {
+            dataset = dataset.with_split(split);
+        }
+        *self
+            .dataset
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(dataset);
+        Ok(())
+    }
+
+    #[cfg(not(feature = "parquet"))]
+    fn create_dataset(&self, _dir: &Path) -> Result<()> {
+        anyhow::bail!(DATASET_DISABLED)
+    }
+
+    /// Chunk a document into the `--dataset` dataset
+    #[cfg(feature = "parquet")]
+    fn add_to_dataset(&self, doc: &crate::datamodel::DoclingDocument) -> Result<()> {
+        let (chunker, chunks) = self.chunk(doc)?;
+        let counter = self.token_counter()?;
+        // Rows of a panicked worker are at worst partly buffered, and shards
+        // are only named once finished
+        let mut dataset = self
+            .dataset
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let dataset = dataset.as_mut().context("--dataset was not created")?;
+        dataset
+            .write_chunks(chunks, chunker.as_ref(), counter.as_ref())
+            .with_context(|| format!("Unable to write chunks to {:?}", dataset.dir()))?;
+        Ok(())
+    }
+
+    #[cfg(not(feature = "parquet"))]
+    fn add_to_dataset(&self, _doc: &crate::datamodel::DoclingDocument) -> Result<()> {
+        anyhow::bail!(DATASET_DISABLED)
+    }
+
+    /// Write the last shard and description of the `--dataset` dataset
+    #[cfg(feature = "parquet")]
+    fn finish_dataset(&self) -> Result<()> {
+        let dataset = self
+            .dataset
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .take();
+        if let Some(dataset) = dataset {
+            let dir = dataset.dir().to_path_buf();
+            let rows = dataset
+                .finish()
+                .with_context(|| format!("Unable to write the dataset in {:?}", dir))?;
+            tracing::info!("Wrote {} rows to the dataset in {}", rows, dir.display());
+        }
+        Ok(())
+    }
+
+    #[cfg(not(feature = "parquet"))]
+    fn finish_dataset(&self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Generate chunked output from document
+    fn generate_chunked_output(&self, doc: &crate::datamodel::DoclingDocument) -> Result<Vec<u8>> {
+        let (chunker, chunks) = self.chunk(doc)?;
+        let counter = self.token_counter()?;
+
         // Format based on output format
         match self.args.output_format {
+            OutputFormat::Jsonl => match self.embedder.get() {
+                Some(embedder) => {
+                    let mut records: Vec<_> = chunks
+                        .into_iter()
+                        .map(|chunk| {
+                            export::ChunkRecord::new(chunk, chunker.as_ref(), counter.as_ref())
+                        })
+                        .collect();
+                    embeddings::embed_records(&mut records, embedder.as_ref())?;
+                    let mut output = Vec::new();
+                    for record in &records {
+                        serde_json::to_writer(&mut output, record)?;
+                        output.push(b'\n');
+                    }
+                    Ok(output)
+                }
+                None => {
+                    Ok(export::to_jsonl(chunks, chunker.as_ref(), counter.as_ref())?.into_bytes())
+                }
+            },
+            #[cfg(feature = "parquet")]
+            OutputFormat::Parquet => Ok(parquet::chunks_to_bytes(
+                chunks,
+                chunker.as_ref(),
+                counter.as_ref(),
+            )?),
+            #[cfg(not(feature = "parquet"))]
+            OutputFormat::Parquet => anyhow::bail!(PARQUET_DISABLED),
+            OutputFormat::LangChain => {
+                let documents = langchain::to_documents(chunks, chunker.as_ref());
+                Ok(if self.args.json_compact {
+                    serde_json::to_vec(&documents)?
+                } else {
+                    serde_json::to_vec_pretty(&documents)?
+                })
+            }
+            OutputFormat::LlamaIndex => {
+                let nodes = llamaindex::to_nodes(chunks);
+                Ok(if self.args.json_compact {
+                    serde_json::to_vec(&nodes)?
+                } else {
+                    serde_json::to_vec_pretty(&nodes)?
+                })
+            }
             OutputFormat::Json => {
                 // Output chunks as JSON array
-                Ok(serde_json::to_string_pretty(&chunks)?)
+                let Some(embedder) = self.embedder.get() else {
+                    return Ok(if self.args.json_compact {
+                        serde_json::to_vec(&chunks)?
+                    } else {
+                        serde_json::to_vec_pretty(&chunks)?
+                    });
+                };
+                // With the embedding of each chunk
+                let texts: Vec<String> = chunks
+                    .iter()
+                    .map(|chunk| chunker.contextualize(chunk))
+                    .collect();
+                let texts: Vec<&str> = texts.iter().map(String::as_str).collect();
+                let vectors = embedder.embed(&texts)?;
+                let mut value = serde_json::to_value(&chunks)?;
+                let objects = value.as_array_mut().into_iter().flatten();
+                for (object, vector) in objects.zip(vectors) {
+                    if let Some(fields) = object.as_object_mut() {
+                        fields.insert("embedding".to_string(), serde_json::json!(vector));
+                    }
+                }
+                Ok(if self.args.json_compact {
+                    serde_json::to_vec(&value)?
+                } else {
+                    serde_json::to_vec_pretty(&value)?
+                })
+            }
+            OutputFormat::Html
+            | OutputFormat::Latex
+            | OutputFormat::TextLayout
+            | OutputFormat::DoclingJson
+            | OutputFormat::Doctags
+            | OutputFormat::Xml => {
+                anyhow::bail!(
+                    "--to {} cannot be used with --chunk",
+                    self.args.output_format
+                )
             }
             OutputFormat::Markdown | OutputFormat::Text => {
                 // Output chunks separated by newlines with metadata
@@ -372,13 +1462,68 @@ impl Converter {
                     if !chunk.meta.headings.is_empty() {
                         output.push_str(&format!("Context: {}\n", chunk.meta.headings.join(" > ")));
                     }
+                    match (chunk.meta.page_start, chunk.meta.page_end) {
+                        (Some(start), Some(end)) if start != end => {
+                            output.push_str(&format!("Pages: {}-{}\n", start, end));
+                        }
+                        (Some(page), _) => output.push_str(&format!("Page: {}\n", page)),
+                        _ => {}
+                    }
                     output.push_str(&format!("Size: {} characters\n\n", chunk.text.len()));
                     // Add chunk text
                     output.push_str(&chunk.text);
                     output.push('\n');
                 }
-                Ok(output)
+                Ok(output.into_bytes())
+            }
+        }
+    }
+}
+
+/// Name of an input of a batch in the combined file: its path relative
+/// to the batch root, with forward slashes
+fn source_name(root: &Path, input: &Path) -> String {
+    input
+        .strip_prefix(root)
+        .unwrap_or(input)
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Relative URI of directory `to`, as seen from directory `from`
+///
+/// Both paths are resolved against the current directory; components are
+/// joined with `/` so the URI works in Markdown and HTML on every platform.
+fn relative_uri(from: &Path, to: &Path) -> Result<String> {
+    let cwd = std::env::current_dir()?;
+    let from = normalize(&cwd.join(from));
+    let to = normalize(&cwd.join(to));
+
+    let common = from
+        .iter()
+        .zip(to.iter())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let parts: Vec<String> = std::iter::repeat("..".to_string())
+        .take(from.len() - common)
+        .chain(to[common..].iter().cloned())
+        .collect();
+    Ok(parts.join("/"))
+}
+
+/// Path components with `.` and `..` resolved lexically
+fn normalize(path: &Path) -> Vec<String> {
+    let mut parts: Vec<String> = Vec::new();
+    for component in path.components() {
+        match component {
+            std::path::Component::CurDir => {}
+            std::path::Component::ParentDir => {
+                parts.pop();
             }
+            other => parts.push(other.as_os_str().to_string_lossy().into_owned()),
         }
     }
+    parts
 }
@@ -0,0 +1,119 @@
+//! Exclude patterns for batch mode (`--exclude`).
+//!
+//! Patterns are globs matched against paths relative to the batch root,
+//! with `/` as separator on every platform:
+//!
+//! - `*` matches any characters but `/`, `?` one character but `/`, and
+//!   `[abc]` one of the listed characters
+//! - `**` matches any characters, `/` included, so `drafts/**` matches
+//!   everything under `drafts`
+//! - a pattern without `/` matches the name of a file or directory at any
+//!   depth: `node_modules` skips every `node_modules` directory, `*.tmp.md`
+//!   every such file
+//! - a leading `/` anchors a pattern at the batch root; a trailing `/` is
+//!   ignored
+//!
+//! An excluded directory is not descended into.
+
+use anyhow::{Context, Result};
+use regex::Regex;
+use std::path::Path;
+
+/// Compiled `--exclude` patterns
+#[derive(Debug, Clone, Default)]
+pub struct ExcludeSet {
+    patterns: Vec<Regex>,
+}
+
+impl ExcludeSet {
+    /// Compile `globs`
+    pub fn new<S: AsRef<str>>(globs: &[S]) -> Result<Self> {
+        let patterns = globs
+            .iter()
+            .map(|glob| {
+                let glob = glob.as_ref();
+                glob_to_regex(glob)
+                    .and_then(|regex| Regex::new(&regex).map_err(anyhow::Error::from))
+                    .with_context(|| format!("Invalid exclude pattern: {}", glob))
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self { patterns })
+    }
+
+    /// Whether no pattern was given
+    pub fn is_empty(&self) -> bool {
+        self.patterns.is_empty()
+    }
+
+    /// Whether `path`, relative to the batch root, is excluded
+    pub fn is_excluded(&self, path: &Path) -> bool {
+        if self.patterns.is_empty() {
+            return false;
+        }
+        let path: Vec<_> = path
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect();
+        let path = path.join("/");
+        self.patterns.iter().any(|pattern| pattern.is_match(&path))
+    }
+}
+
+/// Regular expression matching the relative paths `glob` matches
+fn glob_to_regex(glob: &str) -> Result<String> {
+    let glob = glob.trim_end_matches('/');
+    let (anchored, glob) = match glob.strip_prefix('/') {
+        Some(glob) => (true, glob),
+        None => (glob.contains('/'), glob),
+    };
+
+    // Unanchored patterns match the last components of the path
+    let mut regex = String::from(if anchored { "^" } else { "^(?:.*/)?" });
+    let mut chars = glob.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                // `**/` also matches no directory at all
+                if chars.peek() == Some(&'/') {
+                    chars.next();
+                    regex.push_str("(?:.*/)?");
+                } else {
+                    regex.push_str(".*");
+                }
+            }
+            '*' => regex.push_str("[^/]*"),
+            '?' => regex.push_str("[^/]"),
+            '[' => {
+                let mut class = String::new();
+                loop {
+                    match chars.next() {
+                        Some(']') => break,
+                        Some(c) => class.push(c),
+                        None => anyhow::bail!("unclosed character class"),
+                    }
+                }
+                let (negated, class) = match class.strip_prefix('!') {
+                    Some(class) => (true, class),
+                    None => (false, class.as_str()),
+                };
+                regex.push('[');
+                if negated {
+                    regex.push('^');
+                }
+                for c in class.chars() {
+                    // Ranges keep their `-`, everything else is literal
+                    if c == '-' {
+                        regex.push('-');
+                    } else {
+                        regex.push_str(&regex::escape(&c.to_string()));
+                    }
+                }
+                regex.push(']');
+            }
+            c => regex.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    regex.push('$');
+    Ok(regex)
+}
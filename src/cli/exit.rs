@@ -0,0 +1,92 @@
+//! Exit codes of the CLI.
+//!
+//! A failed run exits with a code telling the class of its failure, so
+//! scripts can branch on it:
+//!
+//! | Code | Failure |
+//! |------|---------|
+//! | 0 | none |
+//! | 1 | a conversion failed (unreadable or invalid document, limit exceeded, output not writable) |
+//! | 2 | invalid arguments or configuration |
+//! | 3 | the input does not exist, or a directory has no files to convert |
+//! | 4 | the input format is not supported |
+//! | 5 | some files of a batch failed to convert |
+//!
+//! Errors raised with [`failure`] carry their code; other errors are
+//! classified by the [`ConversionError`] they wrap, and default to 1.
+
+use crate::error::ConversionError;
+use std::fmt;
+
+/// Exit code of a run, by class of failure
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExitCode {
+    /// The run succeeded
+    Success = 0,
+    /// A conversion failed
+    ConversionFailed = 1,
+    /// Arguments or configuration are invalid
+    InvalidArguments = 2,
+    /// The input does not exist or holds no files to convert
+    InputNotFound = 3,
+    /// The input format is not supported
+    UnsupportedFormat = 4,
+    /// Some files of a batch failed to convert
+    BatchFailed = 5,
+}
+
+impl ExitCode {
+    /// Numeric code passed to the operating system
+    pub fn code(self) -> i32 {
+        self as i32
+    }
+
+    /// Exit code of a run that failed with `error`
+    pub fn of(error: &anyhow::Error) -> Self {
+        error
+            .chain()
+            .find_map(|cause| {
+                if let Some(failure) = cause.downcast_ref::<Failure>() {
+                    return Some(failure.code);
+                }
+                match cause.downcast_ref::<ConversionError>()? {
+                    ConversionError::FileNotFound(_) => Some(Self::InputNotFound),
+                    ConversionError::UnsupportedFormat(_) => Some(Self::UnsupportedFormat),
+                    ConversionError::Config(_) => Some(Self::InvalidArguments),
+                    _ => None,
+                }
+            })
+            .unwrap_or(Self::ConversionFailed)
+    }
+}
+
+/// Error ending a run with a given exit code
+#[derive(Debug)]
+pub struct Failure {
+    code: ExitCode,
+    message: String,
+}
+
+impl Failure {
+    /// Exit code of the failure
+    pub fn code(&self) -> ExitCode {
+        self.code
+    }
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for Failure {}
+
+/// Error of class `code` with `message`, to return or bail with
+pub fn failure(code: ExitCode, message: impl Into<String>) -> anyhow::Error {
+    Failure {
+        code,
+        message: message.into(),
+    }
+    .into()
+}
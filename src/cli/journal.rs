@@ -0,0 +1,92 @@
+//! Journal of completed batch jobs, for resuming interrupted runs.
+//!
+//! With `--resume`, every converted file is appended to a journal in the
+//! output directory as soon as its output is written. A later run with
+//! `--resume` skips the files listed there, so a batch stopped by a crash or
+//! Ctrl-C continues where it left off. Files are identified by their path
+//! relative to the batch root and a hash of their content, so edited files
+//! are converted again. The journal is removed once a run completes without
+//! failures.
+
+use anyhow::{Context, Result};
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// File name of the journal in the output directory
+pub const JOURNAL_FILE: &str = ".docling-rs-journal";
+
+/// Completed jobs of a batch run
+pub struct Journal {
+    path: PathBuf,
+    done: HashSet<String>,
+    file: File,
+}
+
+impl Journal {
+    /// Open the journal at `path`, loading the jobs it records
+    pub fn open(path: &Path) -> Result<Self> {
+        let done = match fs::read_to_string(path) {
+            Ok(text) => text.lines().map(str::to_string).collect(),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => HashSet::new(),
+            Err(e) => return Err(e).with_context(|| format!("Unable to read journal {:?}", path)),
+        };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .with_context(|| format!("Unable to open journal {:?}", path))?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            done,
+            file,
+        })
+    }
+
+    /// Number of jobs recorded
+    pub fn len(&self) -> usize {
+        self.done.len()
+    }
+
+    /// Check whether the journal records no jobs
+    pub fn is_empty(&self) -> bool {
+        self.done.is_empty()
+    }
+
+    /// Check whether `input` was converted, unchanged, by an earlier run
+    pub fn is_done(&self, root: &Path, input: &Path) -> bool {
+        entry(root, input).is_ok_and(|entry| self.done.contains(&entry))
+    }
+
+    /// Record `input` as converted
+    pub fn record(&mut self, root: &Path, input: &Path) -> Result<()> {
+        let entry = entry(root, input)?;
+        writeln!(self.file, "{}", entry)
+            .and_then(|()| self.file.flush())
+            .with_context(|| format!("Unable to write journal {:?}", self.path))?;
+        self.done.insert(entry);
+        Ok(())
+    }
+
+    /// Delete the journal once the run is complete
+    pub fn remove(self) -> Result<()> {
+        fs::remove_file(&self.path)
+            .with_context(|| format!("Unable to remove journal {:?}", self.path))
+    }
+}
+
+/// Journal line of a file: content hash, tab, path relative to the root
+fn entry(root: &Path, input: &Path) -> Result<String> {
+    let content = fs::read(input).with_context(|| format!("Unable to read {:?}", input))?;
+    let relative = input.strip_prefix(root).unwrap_or(input);
+    Ok(format!(
+        "{:x}\t{}",
+        Sha256::digest(&content),
+        relative.to_string_lossy().replace('\\', "/")
+    ))
+}
@@ -0,0 +1,120 @@
+//! Log messages of the CLI, written with `tracing`.
+//!
+//! Diagnostics (warnings about documents, failed files, the details shown
+//! with `--verbose`) are `tracing` events written to stderr, filtered by
+//! `--log-level` and formatted by `--log-format`:
+//!
+//! - `text`: one human-readable line per message
+//! - `json`: one JSON object per line, with a timestamp, the level, the
+//!   message and its fields, and the spans it happened in, for services
+//!   collecting the logs of the CLI
+//!
+//! Every file conversion runs in a `convert_file` span holding the input
+//! path, and the library adds `pipeline`, `parse`, `enrich` and `stage`
+//! spans inside it. From `--log-level debug` on, the end of every span is
+//! logged with its duration.
+//!
+//! The converted files listed on stdout, the batch failure summary and
+//! `--stats` are output of the run, not log messages, and are printed as
+//! they are.
+
+use crate::cli::args::{LogFormat, LogLevel};
+use crate::cli::progress::ProgressDisplay;
+use std::io::{self, IsTerminal, Write};
+use std::sync::Arc;
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::fmt::format::FmtSpan;
+use tracing_subscriber::fmt::MakeWriter;
+
+/// Send log messages to stderr, at `level` and in `format`
+///
+/// Messages are written with the progress bars of `progress` hidden, so
+/// they are not drawn over. Does nothing if the program already set a
+/// `tracing` subscriber.
+pub fn init(level: LogLevel, format: LogFormat, progress: Option<Arc<ProgressDisplay>>) {
+    let filter = level_filter(level);
+    let debug = filter >= LevelFilter::DEBUG;
+    let builder = tracing_subscriber::fmt()
+        .with_max_level(filter)
+        .with_span_events(if debug { FmtSpan::CLOSE } else { FmtSpan::NONE })
+        .with_writer(StderrWriter { progress });
+
+    // Fails only when a subscriber is already set, which then stays
+    let _ = match format {
+        // Span durations are only shown with timestamps
+        LogFormat::Text if debug => builder
+            .with_target(false)
+            .with_ansi(io::stderr().is_terminal())
+            .try_init(),
+        LogFormat::Text => builder
+            .without_time()
+            .with_target(false)
+            .with_ansi(io::stderr().is_terminal())
+            .try_init(),
+        LogFormat::Json => builder
+            .json()
+            .with_current_span(true)
+            .with_span_list(true)
+            .try_init(),
+    };
+}
+
+fn level_filter(level: LogLevel) -> LevelFilter {
+    match level {
+        LogLevel::Off => LevelFilter::OFF,
+        LogLevel::Error => LevelFilter::ERROR,
+        LogLevel::Warn => LevelFilter::WARN,
+        LogLevel::Info => LevelFilter::INFO,
+        LogLevel::Debug => LevelFilter::DEBUG,
+        LogLevel::Trace => LevelFilter::TRACE,
+    }
+}
+
+/// Writes each message to stderr at once, with the progress bars hidden
+struct StderrWriter {
+    progress: Option<Arc<ProgressDisplay>>,
+}
+
+impl<'a> MakeWriter<'a> for StderrWriter {
+    type Writer = LogLine<'a>;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        LogLine {
+            progress: self.progress.as_deref(),
+            buf: Vec::new(),
+        }
+    }
+}
+
+/// One message, written to stderr when dropped
+struct LogLine<'a> {
+    progress: Option<&'a ProgressDisplay>,
+    buf: Vec<u8>,
+}
+
+impl Write for LogLine<'_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.buf.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Drop for LogLine<'_> {
+    fn drop(&mut self) {
+        if self.buf.is_empty() {
+            return;
+        }
+        // Messages are lost when stderr is gone
+        let write = || {
+            let _ = io::stderr().lock().write_all(&self.buf);
+        };
+        match self.progress {
+            Some(progress) => progress.suspend(write),
+            None => write(),
+        }
+    }
+}
@@ -0,0 +1,469 @@
+//! Model Context Protocol server (`docling-rs mcp`).
+//!
+//! `docling-rs mcp` speaks the [Model Context Protocol](https://modelcontextprotocol.io)
+//! over stdio, so LLM agents can call docling-rs as a tool. Messages are
+//! JSON-RPC 2.0, one per line, read from stdin and answered on stdout; log
+//! messages go to stderr. Two tools are offered:
+//!
+//! - `convert_document`: the document at `path` (a file or an http(s) URL),
+//!   or given as `content`, converted to Markdown or the format named by `to`
+//! - `chunk_document`: the chunks of the document, as a JSON array, split by
+//!   `chunker` within `max_tokens` or `chunk_size`
+//!
+//! An agent such as Claude Desktop starts the server from its configuration:
+//!
+//! ```json
+//! {
+//!   "mcpServers": {
+//!     "docling": { "command": "docling-rs", "args": ["mcp"] }
+//!   }
+//! }
+//! ```
+//!
+//! Documents that fail to convert are tool errors (`isError`), which the
+//! agent sees; malformed messages, unknown methods and unknown tools are
+//! JSON-RPC errors.
+
+use crate::chunking::{ChunkerOptions, ChunkerRegistry};
+use crate::cli::args::{ChunkerKind, InputFormat, LogFormat, LogLevel, OutputFormat};
+use crate::cli::output;
+use crate::cli::remote;
+use crate::datamodel::ConversionResult;
+use crate::pipeline::ConversionLimits;
+use crate::DocumentConverter;
+use anyhow::{Context, Result};
+use clap::{ArgMatches, Args, Command, FromArgMatches, ValueEnum};
+use serde_json::{json, Map, Value};
+use std::io::{self, BufRead, Write};
+use std::path::Path;
+use std::time::Duration;
+
+/// Name of the subcommand
+pub const COMMAND: &str = "mcp";
+
+/// Protocol versions understood, newest first
+const PROTOCOL_VERSIONS: &[&str] = &["2025-06-18", "2025-03-26", "2024-11-05"];
+
+/// JSON-RPC error codes
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+/// Serve conversion and chunking as Model Context Protocol tools over stdio
+#[derive(Debug, Clone, Args)]
+pub struct McpArgs {
+    /// Refuse documents larger than BYTES bytes
+    #[arg(
+        long = "max-input-bytes",
+        value_name = "BYTES",
+        default_value = "104857600"
+    )]
+    pub max_input_bytes: u64,
+
+    /// Fail conversions that take longer than SECONDS
+    #[arg(long = "timeout", value_name = "SECONDS")]
+    pub timeout: Option<u64>,
+
+    /// Most detailed log messages to write to stderr
+    #[arg(long = "log-level", value_name = "LEVEL", default_value = "warn")]
+    pub log_level: LogLevel,
+
+    /// Format of log messages: text, or json with one object per line
+    #[arg(long = "log-format", value_name = "FORMAT", default_value = "text")]
+    pub log_format: LogFormat,
+}
+
+impl McpArgs {
+    /// The limits applied to every conversion of the server
+    pub fn limits(&self) -> ConversionLimits {
+        let mut limits = ConversionLimits::new().with_max_input_bytes(self.max_input_bytes);
+        if let Some(seconds) = self.timeout {
+            limits = limits.with_timeout(Duration::from_secs(seconds));
+        }
+        limits
+    }
+}
+
+/// The `mcp` subcommand
+pub fn subcommand() -> Command {
+    // The about text is the doc comment of `McpArgs`
+    McpArgs::augment_args(Command::new(COMMAND))
+}
+
+/// The server options, if `matches` are those of the `mcp` subcommand
+pub fn requested(matches: &ArgMatches) -> Option<Result<McpArgs, clap::Error>> {
+    matches
+        .subcommand_matches(COMMAND)
+        .map(McpArgs::from_arg_matches)
+}
+
+/// Answer the messages of stdin on stdout until stdin closes
+pub fn run(args: &McpArgs) -> Result<()> {
+    McpServer::new(args.limits())
+        .serve(io::stdin().lock(), io::stdout().lock())
+        .context("Unable to talk to the MCP client")
+}
+
+/// Error of a JSON-RPC request
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl RpcError {
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+/// Answers the messages of an MCP client
+pub struct McpServer {
+    converter: DocumentConverter,
+    limits: ConversionLimits,
+}
+
+impl McpServer {
+    /// Create a server converting documents within `limits`
+    pub fn new(limits: ConversionLimits) -> Self {
+        Self {
+            converter: DocumentConverter::new().with_limits(limits),
+            limits,
+        }
+    }
+
+    /// Answer each line of `input` on a line of `output`, until `input` ends
+    pub fn serve(&self, input: impl BufRead, mut output: impl Write) -> io::Result<()> {
+        for line in input.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            if let Some(response) = self.handle(&line) {
+                serde_json::to_writer(&mut output, &response)?;
+                output.write_all(b"\n")?;
+                output.flush()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// The response to a JSON-RPC `message`, if it needs one
+    ///
+    /// Notifications and responses from the client are not answered; a batch
+    /// is answered with the array of its responses.
+    pub fn handle(&self, message: &str) -> Option<Value> {
+        match serde_json::from_str::<Value>(message) {
+            Ok(Value::Array(batch)) => {
+                let responses: Vec<_> = batch
+                    .into_iter()
+                    .filter_map(|message| self.handle_message(message))
+                    .collect();
+                (!responses.is_empty()).then_some(Value::Array(responses))
+            }
+            Ok(message) => self.handle_message(message),
+            Err(e) => Some(error_response(
+                Value::Null,
+                RpcError::new(PARSE_ERROR, format!("Parse error: {}", e)),
+            )),
+        }
+    }
+
+    fn handle_message(&self, message: Value) -> Option<Value> {
+        let id = message.get("id").cloned();
+        let Some(method) = message.get("method").and_then(Value::as_str) else {
+            // Responses to requests of ours, which are never sent
+            if message.get("result").is_some() || message.get("error").is_some() {
+                return None;
+            }
+            return Some(error_response(
+                id.unwrap_or(Value::Null),
+                RpcError::new(INVALID_REQUEST, "Invalid request: no method"),
+            ));
+        };
+        let params = message.get("params").cloned().unwrap_or(Value::Null);
+        // At error level, so tool warnings name their request at every log level
+        let _span = tracing::error_span!("mcp", method).entered();
+
+        // Notifications (`notifications/initialized`, ...) need no answer
+        let id = id?;
+        let result = match method {
+            "initialize" => Ok(initialize(&params)),
+            "ping" => Ok(json!({})),
+            "tools/list" => Ok(json!({ "tools": tools() })),
+            "tools/call" => self.call_tool(&params),
+            _ => Err(RpcError::new(
+                METHOD_NOT_FOUND,
+                format!("Method not found: {}", method),
+            )),
+        };
+        Some(match result {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err(error) => error_response(id, error),
+        })
+    }
+
+    /// Run the tool named in `params`
+    fn call_tool(&self, params: &Value) -> Result<Value, RpcError> {
+        let name = params
+            .get("name")
+            .and_then(Value::as_str)
+            .ok_or_else(|| RpcError::new(INVALID_PARAMS, "Missing tool name"))?;
+        let empty = Map::new();
+        let arguments = match params.get("arguments") {
+            Some(Value::Object(arguments)) => arguments,
+            None | Some(Value::Null) => &empty,
+            Some(_) => {
+                return Err(RpcError::new(
+                    INVALID_PARAMS,
+                    "Tool arguments must be an object",
+                ))
+            }
+        };
+        let outcome = match name {
+            "convert_document" => self.convert_document(arguments),
+            "chunk_document" => self.chunk_document(arguments),
+            _ => {
+                return Err(RpcError::new(
+                    INVALID_PARAMS,
+                    format!("Unknown tool: {}", name),
+                ))
+            }
+        };
+        Ok(match outcome {
+            Ok(text) => json!({
+                "content": [{ "type": "text", "text": text }],
+                "isError": false,
+            }),
+            Err(e) => {
+                tracing::warn!("{} failed: {:#}", name, e);
+                json!({
+                    "content": [{ "type": "text", "text": format!("{:#}", e) }],
+                    "isError": true,
+                })
+            }
+        })
+    }
+
+    /// The `convert_document` tool
+    fn convert_document(&self, arguments: &Map<String, Value>) -> Result<String> {
+        let to = match string_argument(arguments, "to")? {
+            Some(to) => OutputFormat::from_str(to, true)
+                .map_err(|_| anyhow::anyhow!("Unknown output format: {}", to))?,
+            None => OutputFormat::Markdown,
+        };
+        let result = self.load(arguments)?;
+        output::render(result.document(), to)
+    }
+
+    /// The `chunk_document` tool
+    fn chunk_document(&self, arguments: &Map<String, Value>) -> Result<String> {
+        let kind = match string_argument(arguments, "chunker")? {
+            Some(chunker) => ChunkerKind::from_str(chunker, true)
+                .map_err(|_| anyhow::anyhow!("Unknown chunker: {}", chunker))?,
+            None => ChunkerKind::Hierarchical,
+        };
+        let options = ChunkerOptions {
+            max_tokens: size_argument(arguments, "max_tokens")?,
+            chunk_size: Some(size_argument(arguments, "chunk_size")?.unwrap_or(1000)),
+            tokenizer: None,
+            embedder: None,
+        };
+        let chunker = ChunkerRegistry::new().create(kind.registry_name(), options)?;
+
+        let result = self.load(arguments)?;
+        let chunks: Vec<_> = chunker.chunk(result.document()).collect();
+        Ok(serde_json::to_string_pretty(&chunks)?)
+    }
+
+    /// Convert the document named by the `path` or `content` argument, in
+    /// the format given by `from` or else by the extension of `path`
+    fn load(&self, arguments: &Map<String, Value>) -> Result<ConversionResult> {
+        let from = string_argument(arguments, "from")?
+            .map(|from| {
+                InputFormat::from_str(from, true)
+                    .map_err(|_| anyhow::anyhow!("Unknown input format: {}", from))
+            })
+            .transpose()?;
+        let path = string_argument(arguments, "path")?;
+        let content = string_argument(arguments, "content")?;
+
+        let result = match (path, content) {
+            (Some(_), Some(_)) => anyhow::bail!("Give either path or content, not both"),
+            (None, None) => anyhow::bail!("Give the path or the content of the document"),
+            (None, Some(content)) => {
+                let format = from.unwrap_or(InputFormat::Markdown);
+                self.converter.convert_bytes(
+                    content.as_bytes().to_vec(),
+                    "document".to_string(),
+                    format.to_docling_format(),
+                )
+            }
+            (Some(url), None) if remote::is_url(Path::new(url)) => {
+                let dir = tempfile::tempdir().context("Unable to create temporary directory")?;
+                let path = remote::download(url, dir.path(), &self.limits)?;
+                self.convert_path(&path, from)
+            }
+            (Some(path), None) => self.convert_path(Path::new(path), from),
+        };
+        let result = result.context("Failed to convert the document")?;
+        for warning in result.warnings() {
+            tracing::warn!("{}", warning);
+        }
+        Ok(result)
+    }
+
+    /// Convert the file at `path`, in format `from` if given
+    fn convert_path(
+        &self,
+        path: &Path,
+        from: Option<InputFormat>,
+    ) -> Result<ConversionResult, crate::ConversionError> {
+        let Some(format) = from else {
+            return self.converter.convert_file(path);
+        };
+        if !path.exists() {
+            return Err(crate::ConversionError::FileNotFound(path.to_path_buf()));
+        }
+        self.limits
+            .start()
+            .check_input_size(std::fs::metadata(path)?.len())?;
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        self.converter
+            .convert_bytes(std::fs::read(path)?, name, format.to_docling_format())
+    }
+}
+
+/// Response carrying `error` for the request `id`
+fn error_response(id: Value, error: RpcError) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": error.code, "message": error.message },
+    })
+}
+
+/// Result of `initialize`: the protocol version asked for if understood,
+/// else the newest
+fn initialize(params: &Value) -> Value {
+    let version = params
+        .get("protocolVersion")
+        .and_then(Value::as_str)
+        .and_then(|asked| PROTOCOL_VERSIONS.iter().find(|&&version| version == asked))
+        .unwrap_or(&PROTOCOL_VERSIONS[0]);
+    json!({
+        "protocolVersion": version,
+        "capabilities": { "tools": {} },
+        "serverInfo": {
+            "name": "docling-rs",
+            "version": env!("CARGO_PKG_VERSION"),
+        },
+    })
+}
+
+/// Names of the values of a command-line enum
+fn value_names<T: ValueEnum + PartialEq>(skip: &[T]) -> Vec<String> {
+    T::value_variants()
+        .iter()
+        .filter(|value| !skip.contains(value))
+        .filter_map(ValueEnum::to_possible_value)
+        .map(|value| value.get_name().to_string())
+        .collect()
+}
+
+/// Definitions of the tools, with the JSON schema of their arguments
+fn tools() -> Value {
+    let source = json!({
+        "path": {
+            "type": "string",
+            "description": "Path of the document on this machine (absolute, or relative to the working directory of the server), or an http(s) URL to download it from",
+        },
+        "content": {
+            "type": "string",
+            "description": "Text of the document, instead of path: Markdown unless from names another text format",
+        },
+        "from": {
+            "type": "string",
+            "enum": value_names::<InputFormat>(&[]),
+            "description": "Format of the document (default: from the extension of path)",
+        },
+    });
+    let with = |extra: Value| {
+        let mut properties = source.clone();
+        if let (Some(properties), Value::Object(extra)) = (properties.as_object_mut(), extra) {
+            properties.extend(extra);
+        }
+        properties
+    };
+
+    json!([
+        {
+            "name": "convert_document",
+            "description": "Convert a document (PDF, DOCX, HTML, Markdown, CSV, text or source code) to Markdown or another format, keeping its headings, lists and tables",
+            "inputSchema": {
+                "type": "object",
+                "properties": with(json!({
+                    "to": {
+                        "type": "string",
+                        "enum": value_names(&[
+                            OutputFormat::Parquet,
+                            OutputFormat::LangChain,
+                            OutputFormat::LlamaIndex,
+                        ]),
+                        "description": "Output format (default: markdown)",
+                    },
+                })),
+            },
+        },
+        {
+            "name": "chunk_document",
+            "description": "Split a document into chunks for retrieval or embedding, each with its text, the headings it sits under and its pages; returns a JSON array",
+            "inputSchema": {
+                "type": "object",
+                "properties": with(json!({
+                    "chunker": {
+                        "type": "string",
+                        "enum": value_names::<ChunkerKind>(&[]),
+                        "description": "Chunking strategy (default: hierarchical, one chunk per element)",
+                    },
+                    "max_tokens": {
+                        "type": "integer",
+                        "minimum": 1,
+                        "description": "Maximum tokens per chunk (hybrid and window chunkers)",
+                    },
+                    "chunk_size": {
+                        "type": "integer",
+                        "minimum": 1,
+                        "description": "Chunk size in characters (recursive and fixed chunkers, default: 1000)",
+                    },
+                })),
+            },
+        },
+    ])
+}
+
+/// String argument `name`, if given
+fn string_argument<'a>(arguments: &'a Map<String, Value>, name: &str) -> Result<Option<&'a str>> {
+    match arguments.get(name) {
+        None | Some(Value::Null) => Ok(None),
+        Some(Value::String(value)) => Ok(Some(value)),
+        Some(_) => anyhow::bail!("{} must be a string", name),
+    }
+}
+
+/// Positive integer argument `name`, if given
+fn size_argument(arguments: &Map<String, Value>, name: &str) -> Result<Option<usize>> {
+    match arguments.get(name) {
+        None | Some(Value::Null) => Ok(None),
+        Some(value) => match value.as_u64().filter(|&size| size > 0) {
+            Some(size) => Ok(Some(size as usize)),
+            None => anyhow::bail!("{} must be a positive integer, not {}", name, value),
+        },
+    }
+}
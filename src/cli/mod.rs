@@ -2,10 +2,27 @@
 //!
 //! Provides CLI argument parsing, conversion orchestration, and output generation.
 
+pub mod archive;
 pub mod args;
+pub mod bench;
+pub mod completions;
 pub mod converter;
+pub mod exclude;
+pub mod exit;
+pub mod journal;
+pub mod logging;
+pub mod mcp;
 pub mod output;
 pub mod progress;
+pub mod remote;
+pub mod report;
+pub mod serve;
+#[cfg(feature = "templates")]
+pub mod template;
+pub mod watch;
 
-pub use args::{CliArgs, InputFormat, OutputFormat};
+pub use args::{
+    ChunkerKind, CliArgs, HeadingStyle, InputFormat, LogFormat, LogLevel, OutputFormat, SplitLevel,
+    TableFormat,
+};
 pub use converter::Converter;
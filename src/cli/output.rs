@@ -1,53 +1,1109 @@
-//! Output file generation (markdown, JSON, text).
+//! Output file generation (markdown, HTML, LaTeX, JSON, XML, text, layout text).
 
-use crate::datamodel::{DoclingDocument, NodeType};
+use super::args::{HeadingStyle, OutputFormat, TableFormat};
+use crate::datamodel::metadata::bcp47;
+use crate::datamodel::text::{escape_html, escape_latex, is_safe_image_url};
+use crate::datamodel::{
+    docling_json, doctags, text, walk, ColumnAlignment, DoclingDocument, DocumentNode, Flow,
+    ImageRef, NodeId, NodeType, TableData, TextSpan, TreeEntry, Visitor,
+};
+use crate::datamodel::{jsonl, xml};
 use anyhow::Result;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
 
 /// Convert document to Markdown format
+///
+/// Walks the document tree: the title (or the document name) becomes the only
+/// level-1 heading and headings sit one level below it. Inline formatting and
+/// links are re-emitted, lists keep their nesting and numbering, quotes become
+/// `>` blocks, tables with cell data become pipe tables, code blocks are fenced
+/// with their language, and pictures referencing image files become image
+/// references. Links and images
+/// with a URL scheme other than `http`, `https` or `mailto` (or `data:image/`
+/// for images) are left out.
 pub fn to_markdown(doc: &DoclingDocument) -> String {
-    let mut output = String::new();
+    to_markdown_with(doc, &MarkdownExportOptions::new())
+}
 
-    // Title
-    output.push_str(&format!("# {}\n\n", doc.name()));
+/// Options for Markdown output, for renderers with different expectations
+///
+/// The defaults write ATX headings, `-` bullets, unescaped text, tables
+/// without alignment markers, unwrapped lines and no page markers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MarkdownExportOptions {
+    heading_style: HeadingStyle,
+    escape: bool,
+    bullet: char,
+    table_alignment: ColumnAlignment,
+    max_width: Option<usize>,
+    page_markers: bool,
+}
 
-    // Content (iterate through document nodes)
-    for node in doc.nodes() {
-        let text = node.text_content().unwrap_or("");
+impl MarkdownExportOptions {
+    /// Create the default options
+    pub fn new() -> Self {
+        Self {
+            heading_style: HeadingStyle::Atx,
+            escape: false,
+            bullet: '-',
+            table_alignment: ColumnAlignment::None,
+            max_width: None,
+            page_markers: false,
+        }
+    }
+
+    /// Write headings as `# Title` (ATX) or underlined (setext)
+    ///
+    /// Setext only covers the first two levels; deeper headings stay ATX.
+    pub fn with_heading_style(mut self, style: HeadingStyle) -> Self {
+        self.heading_style = style;
+        self
+    }
+
+    /// Backslash-escape characters Markdown would interpret in the text
+    ///
+    /// Text extracted from PDFs often contains `*`, `_` or `#` that some
+    /// renderers would turn into formatting.
+    pub fn with_escape(mut self, escape: bool) -> Self {
+        self.escape = escape;
+        self
+    }
+
+    /// Set the bullet of unordered list items (`-`, `*` or `+`)
+    pub fn with_bullet(mut self, bullet: char) -> Self {
+        self.bullet = bullet;
+        self
+    }
+
+    /// Set the column alignment of tables
+    pub fn with_table_alignment(mut self, alignment: ColumnAlignment) -> Self {
+        self.table_alignment = alignment;
+        self
+    }
+
+    /// Wrap paragraphs, list items, captions and footnotes at `width` characters
+    ///
+    /// Headings, code blocks and tables are never wrapped, and words longer
+    /// than the width stay whole.
+    pub fn with_max_width(mut self, width: Option<usize>) -> Self {
+        self.max_width = width;
+        self
+    }
+
+    /// Insert a `<!-- page: N -->` comment before the first block of each page
+    ///
+    /// Lets consumers map rendered Markdown back to source pages. Only nodes
+    /// with a page number (PDF and other paginated sources) produce markers.
+    pub fn with_page_markers(mut self, page_markers: bool) -> Self {
+        self.page_markers = page_markers;
+        self
+    }
+}
+
+impl Default for MarkdownExportOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Convert document to Markdown format with export options
+pub fn to_markdown_with(doc: &DoclingDocument, options: &MarkdownExportOptions) -> String {
+    // Title: the document's title node if it has one, else its name
+    let title = doc
+        .nodes()
+        .iter()
+        .find(|n| n.node_type() == NodeType::Title);
+    let title_text = title
+        .and_then(DocumentNode::text_content)
+        .unwrap_or(doc.name());
+
+    let mut writer = MarkdownWriter {
+        out: String::new(),
+        options: *options,
+        title: title.and_then(DocumentNode::id),
+        code_language: doc.metadata().programming_language(),
+        containers: Vec::new(),
+        blank: false,
+        footnotes: 0,
+        page: None,
+    };
+    if let Some(title) = title {
+        writer.page_marker(title);
+    }
+    let title_text = writer.markdown(title_text, &[]);
+    writer.heading(1, &title_text);
+    walk(doc, &mut writer);
+    if writer.blank {
+        writer.out.push('\n');
+    }
+    writer.out
+}
+
+/// Emits Markdown while walking the document tree
+struct MarkdownWriter<'a> {
+    out: String,
+    options: MarkdownExportOptions,
+    /// Title node already emitted at the top
+    title: Option<NodeId>,
+    code_language: Option<&'a str>,
+    /// Open lists, list items and quotes, outermost first
+    containers: Vec<Container>,
+    /// Whether a blank line is due before the next block
+    blank: bool,
+    footnotes: usize,
+    /// Page of the last page marker
+    page: Option<usize>,
+}
+
+/// A Markdown block holding other blocks
+enum Container {
+    /// A list, with the number of items written so far
+    List {
+        ordered: bool,
+        items: usize,
+    },
+    /// A list item, whose content is indented by the width of its marker
+    Item {
+        width: usize,
+    },
+    Quote,
+}
+
+impl MarkdownWriter<'_> {
+    /// Prefix of the lines inside the open containers
+    fn prefix(&self) -> String {
+        self.containers
+            .iter()
+            .map(|container| match container {
+                Container::List { .. } => String::new(),
+                Container::Item { width } => " ".repeat(*width),
+                Container::Quote => "> ".to_string(),
+            })
+            .collect()
+    }
+
+    /// Emit the blank line due before the next block, if any
+    fn flush_blank(&mut self) {
+        if std::mem::take(&mut self.blank) {
+            let prefix = self.prefix();
+            self.out.push_str(prefix.trim_end());
+            self.out.push('\n');
+        }
+    }
+
+    /// Emit lines inside the open containers, `first` and `rest` going
+    /// between the prefix and the first and following lines
+    fn lines(&mut self, text: &str, first: &str, rest: &str) {
+        self.flush_blank();
+        let prefix = self.prefix();
+        for (i, line) in text.split('\n').enumerate() {
+            let line = format!("{}{}{}", prefix, if i == 0 { first } else { rest }, line);
+            self.out.push_str(line.trim_end_matches(' '));
+            self.out.push('\n');
+        }
+    }
+
+    /// Emit a page marker if the node starts a new page
+    fn page_marker(&mut self, node: &DocumentNode) {
+        if !self.options.page_markers {
+            return;
+        }
+        if let Some(page) = node.page().filter(|&page| self.page != Some(page)) {
+            self.page = Some(page);
+            self.block(&format!("<!-- page: {} -->", page));
+        }
+    }
+
+    /// Emit a block followed by a blank line
+    fn block(&mut self, markdown: &str) {
+        self.lines(markdown, "", "");
+        self.blank = true;
+    }
+
+    /// Emit a block of running text, wrapped if enabled
+    fn text_block(&mut self, markdown: &str) {
+        let wrapped = self.wrap(markdown, 0);
+        self.block(&wrapped);
+    }
+
+    fn heading(&mut self, level: usize, text: &str) {
+        match (self.options.heading_style, level) {
+            (HeadingStyle::Setext, 1 | 2) => {
+                let underline = if level == 1 { '=' } else { '-' };
+                let width = text.chars().count().max(3);
+                let heading = format!("{}\n{}", text, underline.to_string().repeat(width));
+                self.block(&heading);
+            }
+            _ => self.block(&format!("{} {}", "#".repeat(level), text)),
+        }
+    }
+
+    /// Text with its inline formatting, escaped if enabled
+    fn markdown(&self, text: &str, spans: &[TextSpan]) -> String {
+        if self.options.escape {
+            text::to_markdown_escaped(text, spans)
+        } else {
+            text::to_markdown(text, spans)
+        }
+    }
+
+    /// Node text with its inline formatting
+    fn inline(&self, node: &DocumentNode) -> String {
+        self.markdown(node.text_content().unwrap_or_default(), node.spans())
+    }
+
+    fn caption(&mut self, node: &DocumentNode) {
+        if let Some(caption) = node.caption() {
+            let caption = format!("*{}*", self.markdown(caption, &[]));
+            self.text_block(&caption);
+        }
+    }
+
+    /// Wrap text at the configured width, less the width of the line
+    /// prefix and of `indent`
+    fn wrap(&self, text: &str, indent: usize) -> String {
+        match self.options.max_width {
+            Some(width) => wrap(text, width.saturating_sub(self.prefix().len() + indent), ""),
+            None => text.to_string(),
+        }
+    }
+}
+
+/// Wrap each line of `text` at `width` characters
+///
+/// Lines break at spaces only, and never before a word that would start a
+/// Markdown block (a list marker, heading or quote) on a line of its own.
+fn wrap(text: &str, width: usize, indent: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for (i, line) in text.split('\n').enumerate() {
+        if i > 0 {
+            out.push('\n');
+            out.push_str(indent);
+        }
+        let mut column = 0;
+        for (j, word) in line.split(' ').enumerate() {
+            let len = word.chars().count();
+            if j > 0 {
+                if column > 0 && column + 1 + len > width && starts_line_safely(word) {
+                    out.push('\n');
+                    out.push_str(indent);
+                    column = 0;
+                } else {
+                    out.push(' ');
+                    column += 1;
+                }
+            }
+            out.push_str(word);
+            column += len;
+        }
+    }
+    out
+}
+
+/// Check that a word at the start of a line keeps being plain text
+fn starts_line_safely(word: &str) -> bool {
+    let digits = word.chars().take_while(char::is_ascii_digit).count();
+    let numbered = digits > 0 && matches!(&word[digits..], "." | ")");
+    !numbered && !matches!(word, "-" | "*" | "+") && !word.starts_with(['#', '>', '='])
+}
+
+impl Visitor for MarkdownWriter<'_> {
+    fn enter_node(&mut self, entry: TreeEntry<'_>) -> Flow {
+        self.page_marker(entry.node);
+        // Blocks of an item other than nested lists are set off from its text
+        if entry.node.node_type() != NodeType::List
+            && matches!(self.containers.last(), Some(Container::Item { .. }))
+        {
+            self.blank = true;
+        }
+        match entry.node.node_type() {
+            NodeType::List => self.containers.push(Container::List {
+                ordered: entry.node.is_ordered(),
+                items: 0,
+            }),
+            NodeType::Quote => {
+                // The blank line before a quote is outside of it
+                self.flush_blank();
+                self.containers.push(Container::Quote);
+            }
+            // Table structure comes from the table data, not row/cell nodes
+            NodeType::Table => return Flow::SkipChildren,
+            _ => {}
+        }
+        Flow::Continue
+    }
+
+    fn leave_node(&mut self, entry: TreeEntry<'_>) {
+        match entry.node.node_type() {
+            NodeType::List => {
+                self.containers.pop();
+                // A blank line ends lists that are not nested in an item
+                if !matches!(self.containers.last(), Some(Container::Item { .. })) {
+                    self.blank = true;
+                }
+            }
+            // Items push their container once their own text is written
+            NodeType::ListItem | NodeType::Quote => {
+                self.containers.pop();
+            }
+            _ => {}
+        }
+    }
+
+    fn enter_section(&mut self, entry: TreeEntry<'_>) {
+        let level = match entry.node.node_type() {
+            NodeType::Title if Some(entry.id) == self.title => return,
+            NodeType::Title => 1,
+            // Document title is the only level-1 heading in the output
+            _ => (entry.node.level().unwrap_or(1) + 1).min(6),
+        };
+        let text = self.inline(entry.node);
+        self.heading(level, &text);
+    }
+
+    fn visit_text(&mut self, entry: TreeEntry<'_>) {
+        let node = entry.node;
+        let raw = node.text_content().unwrap_or_default();
         match node.node_type() {
-            NodeType::Heading => {
-                output.push_str(&format!("## {}\n\n", text));
+            NodeType::CodeBlock => {
+                // The fence must be longer than any backtick run in the code
+                let longest = raw.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+                let fence = "`".repeat(longest.max(2) + 1);
+                let language = node.code_language().or(self.code_language).unwrap_or("");
+                self.block(&format!("{}{}\n{}\n{}", fence, language, raw, fence));
             }
-            NodeType::Paragraph | NodeType::Text => {
-                output.push_str(&format!("{}\n\n", text));
+            NodeType::Formula => self.block(&format!("$$\n{}\n$$", raw)),
+            NodeType::ListItem => {
+                let (marker, text) = list_marker(raw);
+                let ordered = match self.containers.last_mut() {
+                    Some(Container::List { ordered, items }) => {
+                        *items += 1;
+                        ordered.then_some(*items)
+                    }
+                    _ => None,
+                };
+                let marker = match (ordered, marker) {
+                    (Some(number), _) => format!("{}.", number),
+                    (None, "-") => self.options.bullet.to_string(),
+                    (None, number) => number.to_string(),
+                };
+                let text = self.markdown(text, &shift_spans(node, raw.len() - text.len()));
+                // Continuation lines and nested blocks line up with the item text
+                let width = marker.len() + 1;
+                let text = self.wrap(&text, width);
+                self.lines(&text, &format!("{} ", marker), &" ".repeat(width));
+                self.containers.push(Container::Item { width });
             }
-            NodeType::Table => {
-                output.push_str("(Table content)\n\n");
+            NodeType::Caption => {
+                let caption = format!("*{}*", self.inline(node));
+                self.text_block(&caption);
             }
+            NodeType::Footnote => {
+                self.footnotes += 1;
+                let footnote = format!("[^{}]: {}", self.footnotes, self.inline(node));
+                self.text_block(&footnote);
+            }
+            _ if raw.trim().is_empty() => {}
             _ => {
-                output.push_str(&format!("{}\n\n", text));
+                let text = self.inline(node);
+                self.text_block(&text);
             }
         }
     }
 
-    output
+    fn visit_table(&mut self, entry: TreeEntry<'_>) {
+        let node = entry.node;
+        self.caption(node);
+        let table = match node.table() {
+            Some(table) if !table.rows().is_empty() => {
+                table.to_markdown_aligned(self.options.table_alignment)
+            }
+            _ => match node.text_content().map(str::trim) {
+                Some(text) if !text.is_empty() => text.to_string(),
+                _ => "(Table content)".to_string(),
+            },
+        };
+        self.block(table.trim_end());
+    }
+
+    fn visit_picture(&mut self, entry: TreeEntry<'_>) {
+        let node = entry.node;
+        let alt = node.text_content().unwrap_or_default();
+        match node.picture().map(|picture| picture.image()) {
+            Some(ImageRef::File { uri }) if is_safe_image_url(uri) => {
+                self.block(&format!("![{}]({})", alt, uri))
+            }
+            _ if alt.is_empty() => self.block("<!-- image -->"),
+            _ => self.block(&format!("<!-- image: {} -->", alt)),
+        }
+        self.caption(node);
+    }
+}
+
+/// Split a list item into its Markdown marker and its text
+///
+/// Items keep their own marker when their text starts with one (bullets are
+/// normalized to `-`, numbers are kept); other items get `-`.
+fn list_marker(text: &str) -> (&str, &str) {
+    for bullet in ["- ", "* ", "+ ", "• "] {
+        if let Some(rest) = text.strip_prefix(bullet) {
+            return ("-", rest.trim_start());
+        }
+    }
+    let digits = text.chars().take_while(char::is_ascii_digit).count();
+    if digits > 0 {
+        let rest = &text[digits..];
+        if let Some(after) = rest.strip_prefix(". ").or_else(|| rest.strip_prefix(") ")) {
+            return (&text[..digits + 1], after.trim_start());
+        }
+    }
+    ("-", text)
+}
+
+/// Spans of a node shifted left by `offset` bytes, dropping those starting before it
+fn shift_spans(node: &DocumentNode, offset: usize) -> Vec<TextSpan> {
+    node.spans()
+        .iter()
+        .filter(|span| span.start >= offset)
+        .map(|span| {
+            TextSpan::new(
+                span.start - offset,
+                span.end - offset,
+                span.formatting.clone(),
+            )
+        })
+        .collect()
+}
+
+/// Convert document to a standalone HTML page
+///
+/// Produces semantic HTML: the title and headings become `<h1>`..`<h6>`
+/// (headings one level below the title, as in Markdown output), section
+/// groups become `<section>`, lists `<ul>` or `<ol>` (nested in their items),
+/// quotes `<blockquote>`, tables `<table>` with their caption, and pictures
+/// `<figure>` with a `<figcaption>`. Code blocks are `<pre><code>`, tagged
+/// with the language for source files. Pictures
+/// referencing image files by a safe URL (see [`is_safe_image_url`]) are
+/// shown with `<img>`; others only keep their alternative text.
+pub fn to_html(doc: &DoclingDocument) -> String {
+    let title = doc
+        .nodes()
+        .iter()
+        .find(|n| n.node_type() == NodeType::Title)
+        .and_then(DocumentNode::text_content)
+        .unwrap_or(doc.name());
+    let lang = doc.metadata().language().map(bcp47);
+
+    let mut writer = HtmlWriter {
+        out: String::new(),
+        code_language: doc.metadata().programming_language(),
+    };
+    if !doc.nodes().iter().any(|n| n.node_type() == NodeType::Title) {
+        writer.element("h1", "", &escape_html(title));
+    }
+    walk(doc, &mut writer);
+
+    let lang = lang
+        .map(|lang| format!(" lang=\"{}\"", escape_html(lang)))
+        .unwrap_or_default();
+    format!(
+        "<!DOCTYPE html>\n<html{}>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n</head>\n<body>\n{}</body>\n</html>\n",
+        lang,
+        escape_html(title),
+        writer.out
+    )
+}
+
+/// Emits HTML while walking the document tree
+struct HtmlWriter<'a> {
+    out: String,
+    code_language: Option<&'a str>,
+}
+
+impl HtmlWriter<'_> {
+    fn element(&mut self, tag: &str, attributes: &str, html: &str) {
+        self.out
+            .push_str(&format!("<{}{}>{}</{}>\n", tag, attributes, html, tag));
+    }
+
+    /// Node text with its inline formatting
+    fn inline(node: &DocumentNode) -> String {
+        text::to_html(node.text_content().unwrap_or_default(), node.spans())
+    }
+}
+
+impl Visitor for HtmlWriter<'_> {
+    fn enter_node(&mut self, entry: TreeEntry<'_>) -> Flow {
+        match entry.node.node_type() {
+            NodeType::Section => self.out.push_str("<section>\n"),
+            NodeType::List if entry.node.is_ordered() => self.out.push_str("<ol>\n"),
+            NodeType::List => self.out.push_str("<ul>\n"),
+            NodeType::Quote => self.out.push_str("<blockquote>\n"),
+            NodeType::PageBreak => self.out.push_str("<hr class=\"page-break\">\n"),
+            // Table structure comes from the table data, not row/cell nodes
+            NodeType::Table => return Flow::SkipChildren,
+            _ => {}
+        }
+        Flow::Continue
+    }
+
+    fn leave_node(&mut self, entry: TreeEntry<'_>) {
+        match entry.node.node_type() {
+            NodeType::Section => self.out.push_str("</section>\n"),
+            NodeType::List if entry.node.is_ordered() => self.out.push_str("</ol>\n"),
+            NodeType::List => self.out.push_str("</ul>\n"),
+            NodeType::ListItem => self.out.push_str("</li>\n"),
+            NodeType::Quote => self.out.push_str("</blockquote>\n"),
+            _ => {}
+        }
+    }
+
+    fn enter_section(&mut self, entry: TreeEntry<'_>) {
+        let tag = match entry.node.node_type() {
+            NodeType::Title => "h1".to_string(),
+            _ => format!("h{}", (entry.node.level().unwrap_or(1) + 1).min(6)),
+        };
+        self.element(&tag, "", &Self::inline(entry.node));
+    }
+
+    fn visit_text(&mut self, entry: TreeEntry<'_>) {
+        let node = entry.node;
+        let raw = node.text_content().unwrap_or_default();
+        match node.node_type() {
+            NodeType::CodeBlock => {
+                let class = node
+                    .code_language()
+                    .or(self.code_language)
+                    .map(|lang| format!(" class=\"language-{}\"", escape_html(lang)))
+                    .unwrap_or_default();
+                self.out.push_str(&format!(
+                    "<pre><code{}>{}</code></pre>\n",
+                    class,
+                    escape_html(raw)
+                ));
+            }
+            NodeType::Formula => self.element(
+                "div",
+                " class=\"formula\"",
+                &format!("\\[{}\\]", escape_html(raw)),
+            ),
+            NodeType::ListItem => {
+                // Markers are part of the item text in some backends
+                let html = Self::inline(node);
+                let html = ["- ", "* ", "+ ", "• "]
+                    .iter()
+                    .find_map(|marker| html.strip_prefix(marker))
+                    .map(str::to_string)
+                    .unwrap_or(html);
+                // The item stays open for its nested lists and blocks
+                self.out.push_str(&format!("<li>{}", html));
+                if !node.children().is_empty() {
+                    self.out.push('\n');
+                }
+            }
+            NodeType::Caption => self.element("p", " class=\"caption\"", &Self::inline(node)),
+            NodeType::Footnote => self.element("p", " class=\"footnote\"", &Self::inline(node)),
+            _ if raw.trim().is_empty() => {}
+            _ => self.element("p", "", &Self::inline(node)),
+        }
+    }
+
+    fn visit_table(&mut self, entry: TreeEntry<'_>) {
+        let node = entry.node;
+        let table = match node.table() {
+            Some(table) => table.to_html(),
+            None => format!(
+                "<table>\n<tbody>\n<tr><td><pre>{}</pre></td></tr>\n</tbody>\n</table>\n",
+                escape_html(node.text_content().unwrap_or_default())
+            ),
+        };
+        match node.caption() {
+            Some(caption) => {
+                let caption = format!("<caption>{}</caption>\n", escape_html(caption));
+                self.out.push_str(&table.replacen(
+                    "<table>\n",
+                    &format!("<table>\n{}", caption),
+                    1,
+                ));
+            }
+            None => self.out.push_str(&table),
+        }
+    }
+
+    fn visit_picture(&mut self, entry: TreeEntry<'_>) {
+        let node = entry.node;
+        let alt = escape_html(node.text_content().unwrap_or_default());
+        self.out.push_str("<figure>\n");
+        match node.picture().map(|picture| picture.image()) {
+            Some(ImageRef::File { uri }) if is_safe_image_url(uri) => self.out.push_str(&format!(
+                "<img src=\"{}\" alt=\"{}\">\n",
+                escape_html(uri),
+                alt
+            )),
+            _ if alt.is_empty() => self.out.push_str("<!-- image -->\n"),
+            _ => self.out.push_str(&format!("<!-- image: {} -->\n", alt)),
+        }
+        if let Some(caption) = node.caption() {
+            self.element("figcaption", "", &escape_html(caption));
+        }
+        self.out.push_str("</figure>\n");
+    }
+}
+
+/// TeX commands that read or write files, or build other commands by name
+const UNSAFE_TEX_COMMANDS: &[&str] = &[
+    "catcode", "csname", "include", "input", "openin", "openout", "read", "write",
+];
+
+/// Check whether a formula can be copied into LaTeX output as it is
+///
+/// Formulas using commands that read or write files (`\input`, `\include`,
+/// `\write18`, `\openout`, ...), that could build such a command (`\csname`,
+/// `\catcode`) or that use `^^` character codes are not.
+pub fn is_safe_formula(formula: &str) -> bool {
+    !formula.contains("^^")
+        && formula.split('\\').skip(1).all(|rest| {
+            let end = rest
+                .find(|c: char| !c.is_ascii_alphabetic())
+                .unwrap_or(rest.len());
+            !UNSAFE_TEX_COMMANDS.contains(&&rest[..end])
+        })
+}
+
+/// Wrap text in a `verbatim` environment
+///
+/// `verbatim` cannot contain its own end marker, so occurrences in the text
+/// are broken up; otherwise the text after them would be compiled as TeX.
+fn verbatim(text: &str) -> String {
+    format!(
+        "\\begin{{verbatim}}\n{}\n\\end{{verbatim}}",
+        text.replace("\\end{verbatim}", "\\end {verbatim}")
+    )
+}
+
+/// Convert document to a standalone LaTeX document
+///
+/// Headings become `\section` to `\subparagraph`, lists `itemize` or
+/// `enumerate`, tables `tabular` and code `verbatim`; pictures that reference
+/// a file are included with `\includegraphics`. Formulas are copied as they
+/// are, since they are usually LaTeX already, unless they use commands that
+/// read or write files (see [`is_safe_formula`]); those are written as text.
+pub fn to_latex(doc: &DoclingDocument) -> String {
+    let title = doc
+        .nodes()
+        .iter()
+        .find(|n| n.node_type() == NodeType::Title)
+        .and_then(DocumentNode::text_content);
+
+    let mut writer = LatexWriter {
+        out: String::new(),
+        doc,
+        lists: Vec::new(),
+    };
+    walk(doc, &mut writer);
+
+    let mut preamble = String::from(
+        "\\documentclass{article}\n\
+         \\usepackage[utf8]{inputenc}\n\
+         \\usepackage[T1]{fontenc}\n\
+         \\usepackage{graphicx}\n\
+         \\usepackage{multirow}\n\
+         \\usepackage[normalem]{ulem}\n\
+         \\usepackage{hyperref}\n",
+    );
+    if let Some(title) = title {
+        preamble.push_str(&format!("\\title{{{}}}\n\\date{{}}\n", escape_latex(title)));
+    }
+    format!(
+        "{}\n\\begin{{document}}\n\n{}\\end{{document}}\n",
+        preamble, writer.out
+    )
+}
+
+/// Emits LaTeX while walking the document tree
+struct LatexWriter<'a> {
+    out: String,
+    doc: &'a DoclingDocument,
+    /// Environments of the open lists, innermost last
+    lists: Vec<&'static str>,
+}
+
+impl LatexWriter<'_> {
+    fn block(&mut self, latex: &str) {
+        self.out.push_str(latex);
+        self.out.push_str("\n\n");
+    }
+
+    /// Node text with its inline formatting
+    fn inline(node: &DocumentNode) -> String {
+        text::to_latex(node.text_content().unwrap_or_default(), node.spans())
+    }
+
+    /// List environment of an ordered list, or else matching the marker of
+    /// the list's first item
+    fn list_environment(&self, list: &DocumentNode) -> &'static str {
+        if list.is_ordered() {
+            return "enumerate";
+        }
+        let first = list
+            .children()
+            .first()
+            .and_then(|&id| self.doc.node(id))
+            .and_then(DocumentNode::text_content);
+        match first.map(list_marker) {
+            Some((marker, _)) if marker != "-" => "enumerate",
+            _ => "itemize",
+        }
+    }
+}
+
+impl Visitor for LatexWriter<'_> {
+    fn enter_node(&mut self, entry: TreeEntry<'_>) -> Flow {
+        match entry.node.node_type() {
+            NodeType::List => {
+                let environment = self.list_environment(entry.node);
+                self.out.push_str(&format!("\\begin{{{}}}\n", environment));
+                self.lists.push(environment);
+            }
+            NodeType::Quote => self.out.push_str("\\begin{quote}\n"),
+            NodeType::PageBreak => self.block("\\newpage"),
+            // Table structure comes from the table data, not row/cell nodes
+            NodeType::Table => return Flow::SkipChildren,
+            _ => {}
+        }
+        Flow::Continue
+    }
+
+    fn leave_node(&mut self, entry: TreeEntry<'_>) {
+        match entry.node.node_type() {
+            NodeType::List => {
+                let environment = self.lists.pop().unwrap_or("itemize");
+                self.out.push_str(&format!("\\end{{{}}}\n", environment));
+                // A blank line ends the outermost list
+                if self.lists.is_empty() {
+                    self.out.push('\n');
+                }
+            }
+            NodeType::Quote => self.block("\\end{quote}"),
+            _ => {}
+        }
+    }
+
+    fn enter_section(&mut self, entry: TreeEntry<'_>) {
+        let command = match entry.node.node_type() {
+            NodeType::Title => return self.block("\\maketitle"),
+            _ => match entry.node.level().unwrap_or(1) {
+                0 | 1 => "section",
+                2 => "subsection",
+                3 => "subsubsection",
+                4 => "paragraph",
+                _ => "subparagraph",
+            },
+        };
+        self.block(&format!("\\{}{{{}}}", command, Self::inline(entry.node)));
+    }
+
+    fn visit_text(&mut self, entry: TreeEntry<'_>) {
+        let node = entry.node;
+        let raw = node.text_content().unwrap_or_default();
+        match node.node_type() {
+            NodeType::CodeBlock => self.block(&verbatim(raw)),
+            NodeType::Formula if is_safe_formula(raw) => {
+                self.block(&format!("\\[\n{}\n\\]", raw.trim()))
+            }
+            NodeType::Formula => self.block(&format!(
+                "\\texttt{{{}}}",
+                escape_latex(&raw.trim().replace('\n', " "))
+            )),
+            NodeType::ListItem => {
+                let (_, text) = list_marker(raw);
+                let text = text::to_latex(text, &shift_spans(node, raw.len() - text.len()));
+                self.out.push_str(&format!("\\item {}\n", text));
+            }
+            NodeType::Caption => self.block(&format!("\\emph{{{}}}", Self::inline(node))),
+            NodeType::Footnote => self.block(&format!("{{\\footnotesize {}}}", Self::inline(node))),
+            _ if raw.trim().is_empty() => {}
+            _ => self.block(&Self::inline(node)),
+        }
+    }
+
+    fn visit_table(&mut self, entry: TreeEntry<'_>) {
+        let node = entry.node;
+        let table = match node.table() {
+            Some(table) if !table.rows().is_empty() => table.to_latex(),
+            _ => format!(
+                "{}\n",
+                verbatim(node.text_content().unwrap_or_default().trim_end())
+            ),
+        };
+        let caption = node
+            .caption()
+            .map(|caption| format!("\\caption{{{}}}\n", escape_latex(caption)))
+            .unwrap_or_default();
+        self.block(&format!(
+            "\\begin{{table}}[h]\n\\centering\n{}{}\\end{{table}}",
+            caption, table
+        ));
+    }
+
+    fn visit_picture(&mut self, entry: TreeEntry<'_>) {
+        let node = entry.node;
+        let alt = node.text_content().unwrap_or_default();
+        let image = match node.picture().map(|picture| picture.image()) {
+            // A path that could end the argument or start a command is left out
+            Some(ImageRef::File { uri }) if !uri.contains(['\\', '{', '}', '%', '#']) => {
+                format!("\\includegraphics[width=\\linewidth]{{{}}}\n", uri)
+            }
+            _ if alt.is_empty() => "% image\n".to_string(),
+            _ => format!("% image: {}\n", alt.replace('\n', " ")),
+        };
+        let caption = node
+            .caption()
+            .map(|caption| format!("\\caption{{{}}}\n", escape_latex(caption)))
+            .unwrap_or_default();
+        self.block(&format!(
+            "\\begin{{figure}}[h]\n\\centering\n{}{}\\end{{figure}}",
+            image, caption
+        ));
+    }
 }
 
 /// Convert document to JSON format
 pub fn to_json(doc: &DoclingDocument) -> Result<String> {
-    Ok(serde_json::to_string_pretty(doc)?)
+    to_json_with(doc, &JsonOptions::new())
+}
+
+/// Options trading JSON file size for fidelity
+///
+/// The defaults write the full document, pretty-printed, in the layout
+/// [`DoclingDocument::from_json`] loads. Documents exported as a tree or
+/// without image bytes are meant for other consumers and cannot be loaded
+/// back.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JsonOptions {
+    pretty: bool,
+    bboxes: bool,
+    images: bool,
+    tree: bool,
+}
+
+impl JsonOptions {
+    /// Create the default options: pretty, with bounding boxes and image bytes, flat
+    pub fn new() -> Self {
+        Self {
+            pretty: true,
+            bboxes: true,
+            images: true,
+            tree: false,
+        }
+    }
+
+    /// Indent the output (`true`) or write it on a single line
+    pub fn with_pretty(mut self, pretty: bool) -> Self {
+        self.pretty = pretty;
+        self
+    }
+
+    /// Include the bounding boxes of nodes
+    pub fn with_bboxes(mut self, bboxes: bool) -> Self {
+        self.bboxes = bboxes;
+        self
+    }
+
+    /// Include image bytes (the image store and inline picture data)
+    ///
+    /// Without them, pictures keep their MIME type, size and digest or URI.
+    pub fn with_images(mut self, images: bool) -> Self {
+        self.images = images;
+        self
+    }
+
+    /// Nest nodes in their parents' `children` instead of listing them flat
+    ///
+    /// In the flat layout, `nodes` lists every node in reading order and
+    /// `children` holds node IDs; in the tree layout, `nodes` holds the
+    /// top-level nodes and `children` the child nodes themselves.
+    pub fn with_tree(mut self, tree: bool) -> Self {
+        self.tree = tree;
+        self
+    }
+}
+
+impl Default for JsonOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Convert document to JSON format with export options
+pub fn to_json_with(doc: &DoclingDocument, options: &JsonOptions) -> Result<String> {
+    if *options == JsonOptions::new() {
+        return Ok(serde_json::to_string_pretty(doc)?);
+    }
+
+    let mut json = serde_json::to_value(doc)?;
+    if let Some(nodes) = json.get_mut("nodes").and_then(Value::as_array_mut) {
+        for node in nodes.iter_mut() {
+            let Some(item) = node.get_mut("item").and_then(Value::as_object_mut) else {
+                continue;
+            };
+            if !options.bboxes {
+                item.remove("bbox");
+            }
+            if !options.images {
+                if let Some(picture) = item.get_mut("picture").and_then(Value::as_object_mut) {
+                    picture.remove("data");
+                }
+            }
+        }
+    }
+    if let Some(root) = json.as_object_mut() {
+        if !options.images {
+            root.remove("images");
+        }
+        if options.tree {
+            let nodes = match root.remove("nodes") {
+                Some(Value::Array(nodes)) => nodes,
+                _ => Vec::new(),
+            };
+            root.insert("nodes".to_string(), Value::Array(nest_nodes(nodes)));
+        }
+    }
+
+    Ok(if options.pretty {
+        serde_json::to_string_pretty(&json)?
+    } else {
+        serde_json::to_string(&json)?
+    })
+}
+
+/// Replace child IDs with the child nodes, returning the top-level nodes
+fn nest_nodes(nodes: Vec<Value>) -> Vec<Value> {
+    let mut slots: Vec<Option<Value>> = nodes.into_iter().map(Some).collect();
+    // Children come after their parents, so nesting from the end moves
+    // complete subtrees
+    for i in (0..slots.len()).rev() {
+        let Some(mut node) = slots[i].take() else {
+            continue;
+        };
+        let children: Vec<usize> = node
+            .get("children")
+            .and_then(Value::as_array)
+            .map(|ids| {
+                ids.iter()
+                    .filter_map(Value::as_u64)
+                    .map(|id| id as usize)
+                    .collect()
+            })
+            .unwrap_or_default();
+        if let Some(object) = node.as_object_mut() {
+            object.remove("parent");
+            if !children.is_empty() {
+                let nested = children
+                    .into_iter()
+                    .filter_map(|id| slots.get_mut(id).and_then(Option::take))
+                    .collect();
+                object.insert("children".to_string(), Value::Array(nested));
+            }
+        }
+        slots[i] = Some(node);
+    }
+    slots.into_iter().flatten().collect()
+}
+
+/// Convert document to JSON in the Python docling schema
+pub fn to_docling_json(doc: &DoclingDocument) -> Result<String> {
+    Ok(docling_json::to_string(doc)?)
+}
+
+/// Convert document to DocTags markup
+pub fn to_doctags(doc: &DoclingDocument) -> String {
+    doctags::to_string(doc)
+}
+
+/// Convert document to JSON Lines, one node per line
+pub fn to_node_jsonl(doc: &DoclingDocument) -> String {
+    jsonl::to_string(doc)
+}
+
+/// Convert document to XML
+pub fn to_xml(doc: &DoclingDocument) -> String {
+    xml::to_string(doc)
+}
+
+/// Render a document in `format`, with the default options of the format
+///
+/// Parquet is binary and cannot be rendered as a string, and formats that
+/// hold chunks cannot render whole documents.
+pub fn render(doc: &DoclingDocument, format: OutputFormat) -> Result<String> {
+    Ok(match format {
+        OutputFormat::Markdown => to_markdown(doc),
+        OutputFormat::Html => to_html(doc),
+        OutputFormat::Latex => to_latex(doc),
+        OutputFormat::Json => to_json(doc)?,
+        OutputFormat::Text => to_text(doc),
+        OutputFormat::TextLayout => to_layout_text(doc),
+        OutputFormat::Jsonl => to_node_jsonl(doc),
+        OutputFormat::DoclingJson => to_docling_json(doc)?,
+        OutputFormat::Doctags => to_doctags(doc),
+        OutputFormat::Xml => to_xml(doc),
+        OutputFormat::Parquet => {
+            anyhow::bail!("parquet output is binary and cannot be rendered as text")
+        }
+        OutputFormat::LangChain | OutputFormat::LlamaIndex => {
+            anyhow::bail!("{} output holds chunks, not whole documents", format)
+        }
+    })
 }
 
 /// Convert document to plain text format
 pub fn to_text(doc: &DoclingDocument) -> String {
+    to_text_with(doc, &TextExportOptions::new())
+}
+
+/// Options for plain text output
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TextExportOptions {
+    page_markers: bool,
+}
+
+impl TextExportOptions {
+    /// Create the default options
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start each page after the first with a form feed (`\f`)
+    ///
+    /// Pages are those recorded on the nodes, so the text splits on `\f`
+    /// into one string per page, as with `pdftotext`.
+    pub fn with_page_markers(mut self, page_markers: bool) -> Self {
+        self.page_markers = page_markers;
+        self
+    }
+}
+
+/// Convert document to plain text format with export options
+pub fn to_text_with(doc: &DoclingDocument, options: &TextExportOptions) -> String {
     let mut output = String::new();
 
     // Title
     output.push_str(&format!("{}\n\n", doc.name()));
 
     // Extract all text from nodes
+    let mut current_page = None;
     for node in doc.nodes() {
         if let Some(text) = node.text_content() {
             if !text.is_empty() {
+                if let Some(page) = node.page().filter(|_| options.page_markers) {
+                    if current_page.is_some_and(|current| current != page) {
+                        output.push('\u{c}');
+                    }
+                    current_page = Some(page);
+                }
                 output.push_str(&format!("{}\n\n", text));
             }
         }
@@ -55,3 +1111,445 @@ pub fn to_text(doc: &DoclingDocument) -> String {
 
     output
 }
+
+/// Longest caption or title excerpt used in file names
+const MAX_SLUG: usize = 40;
+
+/// Write each table of a document to its own file in `dir`
+///
+/// Files are named `<stem>-table-<n>`, followed by `-p<page>` when the page is
+/// known and a slug of the caption when there is one (e.g.
+/// `invoice-table-2-p3-line-items.csv`). Only tables with cell data are
+/// written; tables are numbered from 1 in reading order. Returns the paths
+/// of the written files.
+pub fn export_tables(
+    doc: &DoclingDocument,
+    dir: &Path,
+    stem: &str,
+    format: TableFormat,
+) -> Result<Vec<PathBuf>> {
+    let tables = doc
+        .nodes()
+        .iter()
+        .filter(|node| node.node_type() == NodeType::Table)
+        .filter_map(|node| Some((node, node.table().filter(|t| !t.rows().is_empty())?)));
+
+    let mut paths = Vec::new();
+    for (i, (node, table)) in tables.enumerate() {
+        let mut name = format!("{}-table-{}", stem, i + 1);
+        if let Some(page) = node.page() {
+            name.push_str(&format!("-p{}", page));
+        }
+        if let Some(slug) = node.caption().map(slug).filter(|s| !s.is_empty()) {
+            name.push('-');
+            name.push_str(&slug);
+        }
+        let path = dir.join(format!("{}.{}", name, format.extension()));
+        match format {
+            TableFormat::Csv => fs::write(&path, table.to_csv())?,
+            TableFormat::Xlsx => fs::write(&path, table.to_xlsx()?)?,
+        }
+        paths.push(path);
+    }
+    Ok(paths)
+}
+
+/// Titles and file names of the parts of a [split](DoclingDocument::split_sections) document
+///
+/// Files are numbered in reading order, zero-padded so they sort, and named
+/// after the part's first heading (e.g. `03-installation.md`). A part without
+/// headings is named after its title node, or the document.
+pub fn section_files(parts: &[DoclingDocument], extension: &str) -> Vec<(String, String)> {
+    let width = parts.len().to_string().len().max(2);
+    parts
+        .iter()
+        .enumerate()
+        .map(|(i, part)| {
+            let title = part
+                .nodes()
+                .iter()
+                .find(|n| n.node_type() == NodeType::Heading)
+                .or_else(|| {
+                    part.nodes()
+                        .iter()
+                        .find(|n| n.node_type() == NodeType::Title)
+                })
+                .and_then(DocumentNode::text_content)
+                .map(str::trim)
+                .filter(|t| !t.is_empty())
+                .unwrap_or(part.name())
+                .to_string();
+            let mut name = format!("{:0width$}", i + 1, width = width);
+            let slug = slug(&title);
+            if !slug.is_empty() {
+                name.push('-');
+                name.push_str(&slug);
+            }
+            (title, format!("{}.{}", name, extension))
+        })
+        .collect()
+}
+
+/// Index of the files written for a split document
+///
+/// Returns the index file name and content: a Markdown list of links for
+/// Markdown and text output, an HTML page for HTML, and JSON otherwise.
+pub fn section_index(
+    doc: &DoclingDocument,
+    sections: &[(String, String)],
+    format: OutputFormat,
+) -> Result<(String, String)> {
+    Ok(match format {
+        OutputFormat::Markdown | OutputFormat::Text => {
+            let mut out = format!("# {}\n\n", doc.name());
+            for (i, (title, file)) in sections.iter().enumerate() {
+                let title = title.replace('[', "\\[").replace(']', "\\]");
+                out.push_str(&format!("{}. [{}]({})\n", i + 1, title, file));
+            }
+            ("index.md".to_string(), out)
+        }
+        OutputFormat::Html => {
+            let name = escape_html(doc.name());
+            let mut out = format!(
+                "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n</head>\n<body>\n<h1>{}</h1>\n<ol>\n",
+                name, name
+            );
+            for (title, file) in sections {
+                out.push_str(&format!(
+                    "<li><a href=\"{}\">{}</a></li>\n",
+                    escape_html(file),
+                    escape_html(title)
+                ));
+            }
+            out.push_str("</ol>\n</body>\n</html>\n");
+            ("index.html".to_string(), out)
+        }
+        _ => {
+            let sections: Vec<Value> = sections
+                .iter()
+                .map(|(title, file)| serde_json::json!({ "title": title, "file": file }))
+                .collect();
+            let index = serde_json::json!({ "document": doc.name(), "sections": sections });
+            (
+                "index.json".to_string(),
+                serde_json::to_string_pretty(&index)?,
+            )
+        }
+    })
+}
+
+/// Whether documents in `format` can be combined into one file, see [`combine`]
+pub fn can_combine(format: OutputFormat) -> bool {
+    matches!(
+        format,
+        OutputFormat::Markdown | OutputFormat::Text | OutputFormat::Json | OutputFormat::Jsonl
+    )
+}
+
+/// Combine rendered documents into one file, in the given order
+///
+/// `documents` are the source names with the rendered output of each.
+/// Markdown and text start with a numbered index, followed by every document
+/// under a header naming its source (Markdown links the index to anchors).
+/// JSON becomes an object with the `index` of sources and the `documents`,
+/// each with its `source`. JSON Lines records get a `source` field.
+pub fn combine(
+    documents: &[(String, Vec<u8>)],
+    format: OutputFormat,
+    compact: bool,
+) -> Result<Vec<u8>> {
+    let text = |content: &[u8]| String::from_utf8_lossy(content).trim_end().to_string();
+    Ok(match format {
+        OutputFormat::Markdown => {
+            let mut out = String::from("# Index\n\n");
+            for (i, (source, _)) in documents.iter().enumerate() {
+                let source = source.replace('[', "\\[").replace(']', "\\]");
+                out.push_str(&format!("{}. [{}](#doc-{})\n", i + 1, source, i + 1));
+            }
+            for (i, (source, content)) in documents.iter().enumerate() {
+                out.push_str(&format!(
+                    "\n---\n\n<a id=\"doc-{}\"></a>\n\n# {}\n\n{}\n",
+                    i + 1,
+                    source,
+                    text(content)
+                ));
+            }
+            out.into_bytes()
+        }
+        OutputFormat::Text => {
+            let mut out = String::from("Index\n\n");
+            for (i, (source, _)) in documents.iter().enumerate() {
+                out.push_str(&format!("{}. {}\n", i + 1, source));
+            }
+            for (i, (source, content)) in documents.iter().enumerate() {
+                out.push_str(&format!(
+                    "\n==== {}. {} ====\n\n{}\n",
+                    i + 1,
+                    source,
+                    text(content)
+                ));
+            }
+            out.into_bytes()
+        }
+        OutputFormat::Json => {
+            let mut entries = Vec::with_capacity(documents.len());
+            for (source, content) in documents {
+                let document: Value = serde_json::from_slice(content)?;
+                entries.push(serde_json::json!({ "source": source, "document": document }));
+            }
+            let index: Vec<&str> = documents
+                .iter()
+                .map(|(source, _)| source.as_str())
+                .collect();
+            let combined = serde_json::json!({ "index": index, "documents": entries });
+            if compact {
+                serde_json::to_vec(&combined)?
+            } else {
+                serde_json::to_vec_pretty(&combined)?
+            }
+        }
+        OutputFormat::Jsonl => {
+            let mut out = Vec::new();
+            for (source, content) in documents {
+                for line in String::from_utf8_lossy(content).lines() {
+                    if line.trim().is_empty() {
+                        continue;
+                    }
+                    let mut record: Value = serde_json::from_str(line)?;
+                    if let Some(fields) = record.as_object_mut() {
+                        fields.insert("source".to_string(), Value::from(source.as_str()));
+                    }
+                    serde_json::to_writer(&mut out, &record)?;
+                    out.push(b'\n');
+                }
+            }
+            out
+        }
+        format => anyhow::bail!("--to {} output cannot be combined", format),
+    })
+}
+
+/// Lowercase ASCII words of a text joined by dashes, for file names
+fn slug(text: &str) -> String {
+    let mut slug = String::new();
+    for word in text
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|w| !w.is_empty())
+    {
+        if slug.len() + word.len() + 1 > MAX_SLUG {
+            break;
+        }
+        if !slug.is_empty() {
+            slug.push('-');
+        }
+        slug.push_str(&word.to_ascii_lowercase());
+    }
+    slug
+}
+
+/// Largest indentation, in characters, given to a block by its horizontal position
+const MAX_LAYOUT_INDENT: usize = 80;
+
+/// Convert document to plain text approximating the page layout
+///
+/// Similar in spirit to `pdftotext -layout`: blocks are separated by blank
+/// lines, pages by a form feed (`\f`), and table columns are padded with
+/// spaces so they line up. Columns are emitted one after the other in reading
+/// order, each block indented to its horizontal position on the page when
+/// the backend recorded bounding boxes.
+pub fn to_layout_text(doc: &DoclingDocument) -> String {
+    let mut writer = LayoutWriter {
+        out: String::new(),
+        pages: page_geometry(doc),
+        page: None,
+        lists: 0,
+    };
+    walk(doc, &mut writer);
+    let mut out = writer.out.trim_end().to_string();
+    out.push('\n');
+    out
+}
+
+/// Left margin and estimated character width of each page with bounding boxes
+fn page_geometry(doc: &DoclingDocument) -> HashMap<usize, (f64, f64)> {
+    let mut boxes: HashMap<usize, Vec<(f64, f64)>> = HashMap::new();
+    for node in doc.nodes() {
+        let (Some(page), Some(bbox), Some(text)) = (node.page(), node.bbox(), node.text_content())
+        else {
+            continue;
+        };
+        let chars = text.lines().map(|l| l.chars().count()).max().unwrap_or(0);
+        let width = bbox.right - bbox.left;
+        if chars > 0 && width > 0.0 {
+            boxes
+                .entry(page)
+                .or_default()
+                .push((bbox.left, width / chars as f64));
+        }
+    }
+    boxes
+        .into_iter()
+        .map(|(page, mut boxes)| {
+            let left = boxes.iter().map(|(l, _)| *l).fold(f64::INFINITY, f64::min);
+            boxes.sort_by(|a, b| a.1.total_cmp(&b.1));
+            (page, (left, boxes[boxes.len() / 2].1))
+        })
+        .collect()
+}
+
+/// Emits layout text while walking the document tree
+struct LayoutWriter {
+    out: String,
+    pages: HashMap<usize, (f64, f64)>,
+    /// Page of the last emitted block
+    page: Option<usize>,
+    /// Number of open lists
+    lists: usize,
+}
+
+impl LayoutWriter {
+    /// Emit a block, indenting every line, followed by a blank line
+    fn block(&mut self, node: &DocumentNode, text: &str) {
+        self.line_block(node, text);
+        self.out.push('\n');
+    }
+
+    /// Emit a block without the blank line after it
+    fn line_block(&mut self, node: &DocumentNode, text: &str) {
+        if text.trim().is_empty() {
+            return;
+        }
+        self.turn_page(node.page());
+        let indent = " ".repeat(self.indent(node));
+        for line in text.lines() {
+            let line = format!("{}{}", indent, line);
+            self.out.push_str(line.trim_end());
+            self.out.push('\n');
+        }
+    }
+
+    /// Start a new page before a block on a later page
+    fn turn_page(&mut self, page: Option<usize>) {
+        if let Some(page) = page {
+            if self.page.is_some_and(|current| current != page) {
+                self.page_separator();
+            }
+            self.page = Some(page);
+        }
+    }
+
+    fn page_separator(&mut self) {
+        if !self.out.is_empty() && !self.out.ends_with('\u{c}') {
+            self.out.push('\u{c}');
+        }
+    }
+
+    /// Indentation of a node from its horizontal position on the page
+    fn indent(&self, node: &DocumentNode) -> usize {
+        let position = node
+            .page()
+            .and_then(|page| self.pages.get(&page))
+            .zip(node.bbox());
+        match position {
+            Some((&(left, char_width), bbox)) if char_width > 0.0 => {
+                (((bbox.left - left) / char_width).round().max(0.0) as usize).min(MAX_LAYOUT_INDENT)
+            }
+            _ => 0,
+        }
+    }
+}
+
+impl Visitor for LayoutWriter {
+    fn enter_node(&mut self, entry: TreeEntry<'_>) -> Flow {
+        match entry.node.node_type() {
+            NodeType::List => self.lists += 1,
+            NodeType::PageBreak => {
+                self.page_separator();
+                self.page = None;
+            }
+            // Table structure comes from the table data, not row/cell nodes
+            NodeType::Table => return Flow::SkipChildren,
+            _ => {}
+        }
+        Flow::Continue
+    }
+
+    fn leave_node(&mut self, entry: TreeEntry<'_>) {
+        if entry.node.node_type() == NodeType::List {
+            self.lists = self.lists.saturating_sub(1);
+            if self.lists == 0 && !self.out.ends_with("\n\n") {
+                self.out.push('\n');
+            }
+        }
+    }
+
+    fn enter_section(&mut self, entry: TreeEntry<'_>) {
+        self.block(entry.node, entry.node.text_content().unwrap_or_default());
+    }
+
+    fn visit_text(&mut self, entry: TreeEntry<'_>) {
+        let node = entry.node;
+        let text = node.text_content().unwrap_or_default();
+        match node.node_type() {
+            NodeType::ListItem => {
+                let (marker, text) = list_marker(text);
+                let indent = "  ".repeat(self.lists.saturating_sub(1));
+                self.line_block(node, &format!("{}{} {}", indent, marker, text));
+            }
+            _ => self.block(node, text),
+        }
+    }
+
+    fn visit_table(&mut self, entry: TreeEntry<'_>) {
+        let node = entry.node;
+        let text = match node.table() {
+            Some(table) if !table.rows().is_empty() => padded_table(table),
+            _ => node.text_content().unwrap_or_default().to_string(),
+        };
+        self.block(node, &text);
+        if let Some(caption) = node.caption() {
+            self.block(node, caption);
+        }
+    }
+
+    fn visit_picture(&mut self, entry: TreeEntry<'_>) {
+        let node = entry.node;
+        if let Some(caption) = node.caption() {
+            self.block(node, caption);
+        }
+    }
+}
+
+/// Render table rows with columns padded to the same width
+fn padded_table(table: &TableData) -> String {
+    let rows: Vec<Vec<String>> = table
+        .logical_rows()
+        .map(|row| {
+            row.into_iter()
+                .map(|cell| cell.replace('\n', " "))
+                .collect()
+        })
+        .collect();
+    let mut widths: Vec<usize> = Vec::new();
+    for row in &rows {
+        for (c, cell) in row.iter().enumerate() {
+            let width = cell.chars().count();
+            match widths.get_mut(c) {
+                Some(w) => *w = (*w).max(width),
+                None => widths.push(width),
+            }
+        }
+    }
+
+    let mut out = String::new();
+    for row in &rows {
+        let cells: Vec<String> = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, &width)| format!("{:<width$}", cell, width = width))
+            .collect();
+        out.push_str(cells.join("  ").trim_end());
+        out.push('\n');
+    }
+    out
+}
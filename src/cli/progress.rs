@@ -1,3 +1,166 @@
-//! Progress bar utilities for batch operations.
+//! Progress bars for batch operations.
+//!
+//! On a terminal, batch runs show how many files are converted so far and
+//! which files are being converted, and paginated documents (PDFs) show a bar
+//! of their pages while they are converted. Bars are drawn on stderr, only
+//! when it is a terminal and `--quiet` is not given, so piped and logged
+//! output stays as it is.
 
-// Placeholder for T029
+use crate::pipeline::{ProgressEvent, ProgressObserver};
+use indicatif::{MultiProgress, ProgressBar, ProgressDrawTarget, ProgressStyle};
+use std::collections::HashMap;
+use std::io::{self, IsTerminal};
+use std::sync::{Mutex, MutexGuard};
+
+const FILES_TEMPLATE: &str = "{bar:30} {pos}/{len} files {wide_msg}";
+const PAGES_TEMPLATE: &str = "  {bar:30} page {pos}/{len} {wide_msg}";
+
+/// Progress bars of a run
+pub struct ProgressDisplay {
+    bars: MultiProgress,
+    state: Mutex<State>,
+}
+
+#[derive(Default)]
+struct State {
+    /// Bar of the files of a batch, once started
+    files: Option<ProgressBar>,
+    /// Files being converted, in the order they started
+    running: Vec<String>,
+    /// Bar of each document whose pages are being converted
+    pages: HashMap<String, ProgressBar>,
+}
+
+impl ProgressDisplay {
+    /// Whether bars should be shown: on a terminal, without `--quiet`
+    pub fn enabled(quiet: bool) -> bool {
+        !quiet && io::stderr().is_terminal()
+    }
+
+    /// Bars drawn on stderr
+    pub fn new() -> Self {
+        Self::with_target(ProgressDrawTarget::stderr())
+    }
+
+    /// Bars that are tracked but never drawn
+    pub fn hidden() -> Self {
+        Self::with_target(ProgressDrawTarget::hidden())
+    }
+
+    fn with_target(target: ProgressDrawTarget) -> Self {
+        Self {
+            bars: MultiProgress::with_draw_target(target),
+            state: Mutex::new(State::default()),
+        }
+    }
+
+    fn state(&self) -> MutexGuard<'_, State> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Start the bar of a batch of `total` files
+    pub fn start_files(&self, total: usize) {
+        let bar = self
+            .bars
+            .add(ProgressBar::new(total as u64).with_style(style(FILES_TEMPLATE)));
+        self.state().files = Some(bar);
+    }
+
+    /// Note that the conversion of `file` started
+    pub fn file_started(&self, file: &str) {
+        let mut state = self.state();
+        state.running.push(file.to_string());
+        update_running(&state);
+    }
+
+    /// Note that the conversion of `file` is over, converted or not
+    pub fn file_done(&self, file: &str) {
+        let mut state = self.state();
+        if let Some(i) = state.running.iter().position(|name| name == file) {
+            state.running.remove(i);
+        }
+        if let Some(files) = &state.files {
+            files.inc(1);
+        }
+        update_running(&state);
+    }
+
+    /// Number of files done in the batch
+    pub fn files_done(&self) -> u64 {
+        self.state().files.as_ref().map_or(0, ProgressBar::position)
+    }
+
+    /// Pages done and total of the document being converted as `document`,
+    /// if its pages are being converted
+    pub fn pages(&self, document: &str) -> Option<(u64, u64)> {
+        self.state()
+            .pages
+            .get(document)
+            .map(|bar| (bar.position(), bar.length().unwrap_or(0)))
+    }
+
+    /// Remove all bars
+    pub fn finish(&self) {
+        let mut state = self.state();
+        if let Some(files) = state.files.take() {
+            files.finish_and_clear();
+        }
+        for (_, bar) in state.pages.drain() {
+            bar.finish_and_clear();
+        }
+        // Clearing only fails when stderr is gone
+        let _ = self.bars.clear();
+    }
+
+    /// Run `print` with the bars hidden, so its lines are not drawn over
+    pub fn suspend<R>(&self, print: impl FnOnce() -> R) -> R {
+        self.bars.suspend(print)
+    }
+}
+
+impl Default for ProgressDisplay {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProgressObserver for ProgressDisplay {
+    fn on_progress(&self, document: &str, event: &ProgressEvent) {
+        match event {
+            ProgressEvent::Page { done, total, .. } => {
+                let mut state = self.state();
+                let bar = state.pages.entry(document.to_string()).or_insert_with(|| {
+                    let bar = self.bars.add(
+                        ProgressBar::new(*total as u64)
+                            .with_style(style(PAGES_TEMPLATE))
+                            .with_message(document.to_string()),
+                    );
+                    bar.tick();
+                    bar
+                });
+                bar.set_length(*total as u64);
+                bar.set_position(*done as u64);
+            }
+            ProgressEvent::Finished { .. } => {
+                if let Some(bar) = self.state().pages.remove(document) {
+                    bar.finish_and_clear();
+                    self.bars.remove(&bar);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Show the files being converted as the message of the files bar
+fn update_running(state: &State) {
+    if let Some(files) = &state.files {
+        files.set_message(state.running.join(", "));
+    }
+}
+
+fn style(template: &str) -> ProgressStyle {
+    ProgressStyle::with_template(template).unwrap_or_else(|_| ProgressStyle::default_bar())
+}
@@ -0,0 +1,225 @@
+//! URL inputs (`http://`, `https://`, `s3://`, `gs://`).
+//!
+//! A URL given as input is downloaded into a temporary directory, then
+//! converted like a local file. The download is named after the last segment
+//! of the URL path (its slug), so `https://example.com/docs/intro.html`
+//! writes `intro.md`. Its format comes from the `Content-Type` of the
+//! response, or else from the extension in the URL. Downloads need the
+//! `http` feature.
+//!
+//! Objects of S3 and Cloud Storage are read with the credentials of the
+//! environment and keep the name and extension of their key; see
+//! [`remote`](crate::remote). They need the `s3` and `gcs` features.
+
+use crate::cli::args::InputFormat;
+use crate::cli::exit::{failure, ExitCode};
+use crate::pipeline::ConversionLimits;
+use crate::remote::{self, ObjectUrl};
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+#[cfg(feature = "http")]
+use std::time::Duration;
+
+/// User agent sent with downloads
+#[cfg(feature = "http")]
+const USER_AGENT: &str = concat!("docling-rs/", env!("CARGO_PKG_VERSION"));
+
+/// Whether `input` is an `http://`, `https://`, `s3://` or `gs://` URL
+pub fn is_url(input: &Path) -> bool {
+    input.to_str().is_some_and(|input| {
+        let lower = input.to_ascii_lowercase();
+        lower.starts_with("http://")
+            || lower.starts_with("https://")
+            || remote::is_object_url(input)
+    })
+}
+
+/// Host and path of `url`, without scheme, user, port, query and fragment
+fn host_and_path(url: &str) -> (&str, &str) {
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let rest = rest.split(['?', '#']).next().unwrap_or_default();
+    let (authority, path) = rest.split_once('/').unwrap_or((rest, ""));
+    let host = authority.rsplit('@').next().unwrap_or_default();
+    let host = host.split(':').next().unwrap_or_default();
+    (host, path)
+}
+
+/// Last non-empty segment of the path of `url`
+fn last_segment(url: &str) -> Option<&str> {
+    let (_, path) = host_and_path(url);
+    path.rsplit('/').find(|segment| !segment.is_empty())
+}
+
+/// File name stem for the download of `url`: the last segment of its path
+/// without extension, or its host for bare domains, with characters unsafe in
+/// file names replaced by `-`
+///
+/// `https://example.com/docs/getting-started.html?lang=en` gives
+/// `getting-started`, `https://example.com/` gives `example.com`.
+pub fn slug(url: &str) -> String {
+    let name = match last_segment(url) {
+        Some(segment) => Path::new(segment)
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .unwrap_or(segment),
+        None => host_and_path(url).0,
+    };
+    let slug: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    let slug = slug.trim_matches(['-', '.']);
+    if slug.is_empty() {
+        "download".to_string()
+    } else {
+        slug.to_string()
+    }
+}
+
+/// Format of a download, from its `Content-Type` or else the extension in `url`
+pub fn detect_format(url: &str, content_type: Option<&str>) -> Option<InputFormat> {
+    let mime = content_type
+        .and_then(|content_type| content_type.split(';').next())
+        .map(|mime| mime.trim().to_ascii_lowercase());
+    let from_mime = match mime.as_deref() {
+        Some("application/pdf") => Some(InputFormat::Pdf),
+        Some("text/html" | "application/xhtml+xml") => Some(InputFormat::Html),
+        Some("text/markdown" | "text/x-markdown") => Some(InputFormat::Markdown),
+        Some("text/csv") => Some(InputFormat::Csv),
+        Some("application/vnd.openxmlformats-officedocument.wordprocessingml.document") => {
+            Some(InputFormat::Docx)
+        }
+        _ => None,
+    };
+    let from_extension = || {
+        last_segment(url)
+            .and_then(|segment| Path::new(segment).extension())
+            .and_then(|ext| ext.to_str())
+            .and_then(InputFormat::from_extension)
+    };
+    // Servers often send plain text for any file they do not know
+    match (from_mime, mime.as_deref()) {
+        (Some(format), _) => Some(format),
+        (None, Some("text/plain")) => from_extension().or(Some(InputFormat::Text)),
+        _ => from_extension(),
+    }
+}
+
+/// File extension for downloads of `format`
+#[cfg(feature = "http")]
+fn extension(format: InputFormat, url: &str) -> &str {
+    match format {
+        InputFormat::Pdf => "pdf",
+        InputFormat::Markdown => "md",
+        InputFormat::Html => "html",
+        InputFormat::Csv => "csv",
+        InputFormat::Docx => "docx",
+        InputFormat::Text => "txt",
+        // Code is only detected by extension, so the URL has one
+        InputFormat::Code => last_segment(url)
+            .and_then(|segment| Path::new(segment).extension())
+            .and_then(|ext| ext.to_str())
+            .unwrap_or("txt"),
+    }
+}
+
+/// Download `url` into `dir`, returning the path of the downloaded file
+pub fn download(url: &str, dir: &Path, limits: &ConversionLimits) -> Result<PathBuf> {
+    if remote::is_object_url(url) {
+        let object = ObjectUrl::parse(url)?;
+        let reader = remote::reader_for(object.scheme)?;
+        return Ok(remote::download(&object, reader.as_ref(), dir, limits)?);
+    }
+    download_http(url, dir, limits)
+}
+
+/// Download an `http://` or `https://` URL into `dir`
+///
+/// Connecting times out after 10 seconds, and a response that sends nothing
+/// for 30 seconds is abandoned. The input size limit applies to the download.
+#[cfg(feature = "http")]
+fn download_http(url: &str, dir: &Path, limits: &ConversionLimits) -> Result<PathBuf> {
+    use anyhow::Context;
+    use std::fs::File;
+    use std::io::{self, Read};
+
+    let limits = limits.start();
+    let agent = ureq::AgentBuilder::new()
+        .timeout_connect(Duration::from_secs(10))
+        .timeout_read(Duration::from_secs(30))
+        .user_agent(USER_AGENT)
+        .build();
+    let response = match agent.get(url).call() {
+        Ok(response) => response,
+        Err(ureq::Error::Status(status, response)) => {
+            let code = match status {
+                404 | 410 => ExitCode::InputNotFound,
+                _ => ExitCode::ConversionFailed,
+            };
+            return Err(failure(
+                code,
+                format!(
+                    "Failed to download {}: HTTP {} {}",
+                    url,
+                    status,
+                    response.status_text()
+                ),
+            ));
+        }
+        Err(e) => anyhow::bail!("Failed to download {}: {}", url, e),
+    };
+
+    // The final URL, after redirects, names the document
+    let final_url = response.get_url().to_string();
+    let format = detect_format(&final_url, response.header("Content-Type"))
+        .or_else(|| detect_format(url, None))
+        .ok_or_else(|| {
+            failure(
+                ExitCode::UnsupportedFormat,
+                format!(
+                    "Unsupported content type of {}: {}",
+                    url,
+                    response.header("Content-Type").unwrap_or("none")
+                ),
+            )
+        })?;
+    if let Some(length) = response
+        .header("Content-Length")
+        .and_then(|length| length.parse().ok())
+    {
+        limits.check_input_size(length)?;
+    }
+
+    let path = dir.join(format!(
+        "{}.{}",
+        slug(&final_url),
+        extension(format, &final_url)
+    ));
+    let mut file = File::create(&path).with_context(|| format!("Unable to create {:?}", path))?;
+    let max = limits.limits().max_input_bytes().unwrap_or(u64::MAX);
+    let copied = io::copy(
+        &mut response.into_reader().take(max.saturating_add(1)),
+        &mut file,
+    )
+    .with_context(|| format!("Failed to download {}", url))?;
+    limits.check_input_size(copied)?;
+    Ok(path)
+}
+
+/// Download `url` into `dir`; URL inputs need the `http` feature
+#[cfg(not(feature = "http"))]
+fn download_http(url: &str, _dir: &Path, _limits: &ConversionLimits) -> Result<PathBuf> {
+    Err(failure(
+        ExitCode::InvalidArguments,
+        format!(
+            "Cannot download {}: URL inputs require docling-rs to be built with the `http` feature",
+            url
+        ),
+    ))
+}
@@ -0,0 +1,101 @@
+//! Machine-readable report of a batch run.
+//!
+//! With `--report <PATH>`, a batch run writes a JSON manifest listing every
+//! input it found: where its output went, whether it was converted, failed or
+//! skipped (`--resume`), the error message, how long it took and the
+//! conversion metrics. The report is written even when the run fails or is
+//! aborted, so large ingestion jobs can be audited afterwards.
+
+use crate::datamodel::ConversionMetrics;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Outcome of one input of a batch
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportStatus {
+    /// The output was written
+    Converted,
+    /// The conversion failed; see the error
+    Failed,
+    /// An earlier run converted the input (`--resume`)
+    Skipped,
+}
+
+/// Report entry of one input
+#[derive(Debug, Clone, Serialize)]
+pub struct ReportEntry {
+    /// Input path, relative to the batch root
+    pub input: PathBuf,
+    pub output: PathBuf,
+    pub status: ReportStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub duration_ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metrics: Option<ConversionMetrics>,
+}
+
+/// Report of a batch run
+#[derive(Debug, Clone, Serialize)]
+pub struct BatchReport {
+    /// The input given on the command line (directory or archive)
+    pub input: PathBuf,
+    pub started_at: DateTime<Utc>,
+    pub duration_ms: u64,
+    pub total: usize,
+    pub converted: usize,
+    pub failed: usize,
+    pub skipped: usize,
+    /// Whether the run stopped at the first failure (`--abort-on-error`)
+    pub aborted: bool,
+    pub files: Vec<ReportEntry>,
+}
+
+impl BatchReport {
+    /// Start the report of a run over `input`
+    pub fn new(input: &Path) -> Self {
+        Self {
+            input: input.to_path_buf(),
+            started_at: Utc::now(),
+            duration_ms: 0,
+            total: 0,
+            converted: 0,
+            failed: 0,
+            skipped: 0,
+            aborted: false,
+            files: Vec::new(),
+        }
+    }
+
+    /// Add the entry of one input and count its outcome
+    pub fn push(&mut self, entry: ReportEntry) {
+        self.total += 1;
+        match entry.status {
+            ReportStatus::Converted => self.converted += 1,
+            ReportStatus::Failed => self.failed += 1,
+            ReportStatus::Skipped => self.skipped += 1,
+        }
+        self.files.push(entry);
+    }
+
+    /// Write the report as pretty-printed JSON to `path`
+    pub fn write(mut self, path: &Path, duration: Duration) -> Result<()> {
+        self.duration_ms = millis(duration);
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Unable to create report directory {:?}", parent))?;
+        }
+        let json = serde_json::to_string_pretty(&self)?;
+        fs::write(path, json).with_context(|| format!("Unable to write report {:?}", path))
+    }
+}
+
+/// Whole milliseconds of `duration`
+pub fn millis(duration: Duration) -> u64 {
+    u64::try_from(duration.as_millis()).unwrap_or(u64::MAX)
+}
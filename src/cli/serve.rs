@@ -0,0 +1,623 @@
+//! HTTP server (`docling-rs serve`).
+//!
+//! `docling-rs serve` runs docling-rs as a document-processing service.
+//! Documents are uploaded as the `file` field of a `multipart/form-data`
+//! request, and every answer is JSON:
+//!
+//! - `POST /convert`: the converted document, with the warnings and metrics
+//!   of its conversion
+//! - `POST /chunk`: the chunks of the document
+//! - `GET /health`: `{"status": "ok"}`, for load balancers and probes
+//!
+//! ```text
+//! docling-rs serve --port 8080
+//! curl -F file=@report.pdf http://localhost:8080/convert
+//! curl -F file=@report.pdf 'http://localhost:8080/chunk?chunker=hybrid&max_tokens=256'
+//! ```
+//!
+//! Query parameters tune a request:
+//!
+//! - `from`: the input format, for files named without a known extension
+//! - `to` (`/convert`): the output format, as `--to`; `json` (the default)
+//!   and `docling-json` give the document as a `document` object, the other
+//!   formats as a `content` string
+//! - `chunker`, `max_tokens`, `chunk_size` (`/chunk`): as `--chunker`,
+//!   `--max-tokens` and `--chunk-size`
+//!
+//! Failed requests are answered with `{"error": "<message>"}` and status 400
+//! for malformed requests, 404 and 405 for unknown routes and methods, 413
+//! for uploads over the input size limit, 415 for unsupported formats, and
+//! 422 for documents that fail to convert.
+//!
+//! Listening needs the `serve` feature; [`Service`] answers requests without
+//! it.
+
+use crate::chunking::{ChunkerOptions, ChunkerRegistry};
+use crate::cli::args::{ChunkerKind, InputFormat, LogFormat, LogLevel, OutputFormat};
+use crate::cli::output;
+use crate::error::ConversionError;
+use crate::pipeline::{ConversionLimits, ExceededLimit};
+use crate::DocumentConverter;
+use anyhow::Result;
+use clap::{ArgMatches, Args, Command, FromArgMatches, ValueEnum};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::num::NonZeroUsize;
+use std::path::Path;
+use std::time::Duration;
+
+/// Name of the subcommand
+pub const COMMAND: &str = "serve";
+
+/// Room left in request bodies for the multipart envelope around the document
+#[cfg(feature = "serve")]
+const MULTIPART_OVERHEAD: u64 = 64 * 1024;
+
+/// Serve conversion and chunking over HTTP, with JSON results
+#[derive(Debug, Clone, Args)]
+pub struct ServeArgs {
+    /// Address to listen on
+    #[arg(long = "host", value_name = "ADDR", default_value = "127.0.0.1")]
+    pub host: String,
+
+    /// Port to listen on (0 picks a free port)
+    #[arg(
+        short = 'p',
+        long = "port",
+        value_name = "PORT",
+        default_value = "8080"
+    )]
+    pub port: u16,
+
+    /// Handle up to N requests at the same time
+    #[arg(short = 'j', long = "jobs", value_name = "N", default_value = "4")]
+    pub jobs: NonZeroUsize,
+
+    /// Refuse uploaded documents larger than BYTES bytes
+    #[arg(
+        long = "max-input-bytes",
+        value_name = "BYTES",
+        default_value = "104857600"
+    )]
+    pub max_input_bytes: u64,
+
+    /// Refuse DOCX files and archives that expand to more than BYTES bytes
+    #[arg(
+        long = "max-decompressed-bytes",
+        value_name = "BYTES",
+        default_value = "1073741824"
+    )]
+    pub max_decompressed_bytes: u64,
+
+    /// Refuse HTML nested, and archives with directories nested, more than N
+    /// levels deep
+    #[arg(long = "max-depth", value_name = "N", default_value = "256")]
+    pub max_depth: usize,
+
+    /// Fail conversions that take longer than SECONDS
+    #[arg(long = "timeout", value_name = "SECONDS")]
+    pub timeout: Option<u64>,
+
+    /// Most detailed log messages to write to stderr; requests are logged at
+    /// info
+    #[arg(long = "log-level", value_name = "LEVEL", default_value = "info")]
+    pub log_level: LogLevel,
+
+    /// Format of log messages: text, or json with one object per line
+    #[arg(long = "log-format", value_name = "FORMAT", default_value = "text")]
+    pub log_format: LogFormat,
+}
+
+impl ServeArgs {
+    /// The limits applied to every conversion of the server
+    pub fn limits(&self) -> ConversionLimits {
+        let mut limits = ConversionLimits::new()
+            .with_max_input_bytes(self.max_input_bytes)
+            .with_max_decompressed_bytes(self.max_decompressed_bytes)
+            .with_max_depth(self.max_depth);
+        if let Some(seconds) = self.timeout {
+            limits = limits.with_timeout(Duration::from_secs(seconds));
+        }
+        limits
+    }
+}
+
+/// The `serve` subcommand
+pub fn subcommand() -> Command {
+    // The about text is the doc comment of `ServeArgs`
+    ServeArgs::augment_args(Command::new(COMMAND))
+}
+
+/// The server options, if `matches` are those of the `serve` subcommand
+pub fn requested(matches: &ArgMatches) -> Option<Result<ServeArgs, clap::Error>> {
+    matches
+        .subcommand_matches(COMMAND)
+        .map(ServeArgs::from_arg_matches)
+}
+
+/// Answer to a request: a status code and a JSON body
+#[derive(Debug, Clone, PartialEq)]
+pub struct Response {
+    pub status: u16,
+    pub body: Value,
+}
+
+impl Response {
+    fn error(status: u16, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            body: json!({ "error": message.into() }),
+        }
+    }
+}
+
+/// Answers the requests of the server, independently of the transport
+pub struct Service {
+    converter: DocumentConverter,
+}
+
+impl Service {
+    /// Create a service converting documents within `limits`
+    pub fn new(limits: ConversionLimits) -> Self {
+        Self {
+            converter: DocumentConverter::new().with_limits(limits),
+        }
+    }
+
+    /// Answer a `method` request for `url` (path and query), whose body of
+    /// type `content_type` is `body`
+    pub fn handle(
+        &self,
+        method: &str,
+        url: &str,
+        content_type: Option<&str>,
+        body: &[u8],
+    ) -> Response {
+        let (path, query) = url.split_once('?').unwrap_or((url, ""));
+        let query = parse_query(query);
+        let answer = match (method, path) {
+            ("GET", "/health") => Ok(json!({
+                "status": "ok",
+                "version": env!("CARGO_PKG_VERSION"),
+            })),
+            ("POST", "/convert") => self.convert(&query, content_type, body),
+            ("POST", "/chunk") => self.chunk(&query, content_type, body),
+            (_, "/health" | "/convert" | "/chunk") => Err(Response::error(
+                405,
+                format!("Method {} is not allowed on {}", method, path),
+            )),
+            _ => Err(Response::error(404, format!("No route for {}", path))),
+        };
+        match answer {
+            Ok(body) => Response { status: 200, body },
+            Err(error) => error,
+        }
+    }
+
+    /// Convert the uploaded document
+    fn convert(
+        &self,
+        query: &HashMap<String, String>,
+        content_type: Option<&str>,
+        body: &[u8],
+    ) -> Result<Value, Response> {
+        let to = match query.get("to") {
+            Some(to) => OutputFormat::from_str(to, true)
+                .map_err(|_| Response::error(400, format!("Unknown output format: {}", to)))?,
+            None => OutputFormat::Json,
+        };
+        if to == OutputFormat::Parquet {
+            return Err(Response::error(
+                400,
+                "Parquet output cannot be sent as JSON",
+            ));
+        }
+        if to.needs_chunks() {
+            return Err(Response::error(
+                400,
+                format!("{} output holds chunks: use /chunk", to),
+            ));
+        }
+
+        let upload = Upload::parse(query, content_type, body)?;
+        let result = self
+            .converter
+            .convert_bytes(
+                upload.data.to_vec(),
+                upload.name.clone(),
+                upload.format.to_docling_format(),
+            )
+            .map_err(conversion_error)?;
+        let doc = result.document();
+
+        let mut answer = json!({
+            "name": upload.name,
+            "warnings": result.warnings(),
+            "metrics": result.metrics(),
+        });
+        match to {
+            OutputFormat::Json => {
+                answer["document"] = serde_json::to_value(doc).map_err(internal_error)?;
+            }
+            OutputFormat::DoclingJson => {
+                let json = output::to_docling_json(doc).map_err(internal_error)?;
+                answer["document"] = serde_json::from_str(&json).map_err(internal_error)?;
+            }
+            _ => {
+                let content = output::render(doc, to).map_err(internal_error)?;
+                answer["content"] = Value::String(content);
+            }
+        }
+        Ok(answer)
+    }
+
+    /// Convert the uploaded document and chunk it
+    fn chunk(
+        &self,
+        query: &HashMap<String, String>,
+        content_type: Option<&str>,
+        body: &[u8],
+    ) -> Result<Value, Response> {
+        let kind = match query.get("chunker") {
+            Some(chunker) => ChunkerKind::from_str(chunker, true)
+                .map_err(|_| Response::error(400, format!("Unknown chunker: {}", chunker)))?,
+            None => ChunkerKind::Hierarchical,
+        };
+        let options = ChunkerOptions {
+            max_tokens: size_param(query, "max_tokens")?,
+            chunk_size: Some(size_param(query, "chunk_size")?.unwrap_or(1000)),
+            tokenizer: None,
+            embedder: None,
+        };
+        let chunker = ChunkerRegistry::new()
+            .create(kind.registry_name(), options)
+            .map_err(|e| Response::error(400, e.to_string()))?;
+
+        let upload = Upload::parse(query, content_type, body)?;
+        let result = self
+            .converter
+            .convert_bytes(
+                upload.data.to_vec(),
+                upload.name.clone(),
+                upload.format.to_docling_format(),
+            )
+            .map_err(conversion_error)?;
+        let chunks: Vec<_> = chunker.chunk(result.document()).collect();
+
+        Ok(json!({
+            "name": upload.name,
+            "chunker": kind.registry_name(),
+            "warnings": result.warnings(),
+            "chunks": chunks,
+        }))
+    }
+}
+
+/// Response to a conversion that failed with `error`
+fn conversion_error(error: ConversionError) -> Response {
+    let status = match &error {
+        ConversionError::UnsupportedFormat(_) => 415,
+        ConversionError::LimitExceeded(ExceededLimit::InputSize { .. }) => 413,
+        ConversionError::Io(_)
+        | ConversionError::Serialization(_)
+        | ConversionError::Schema(_)
+        | ConversionError::Config(_) => 500,
+        _ => 422,
+    };
+    Response::error(status, error.to_string())
+}
+
+/// Response to a failure of the server itself
+fn internal_error(error: impl std::fmt::Display) -> Response {
+    Response::error(500, error.to_string())
+}
+
+/// Positive integer query parameter `name`, if given
+fn size_param(query: &HashMap<String, String>, name: &str) -> Result<Option<usize>, Response> {
+    query
+        .get(name)
+        .map(|value| {
+            value
+                .parse::<NonZeroUsize>()
+                .map(NonZeroUsize::get)
+                .map_err(|_| {
+                    Response::error(
+                        400,
+                        format!("{} must be a positive integer, not {:?}", name, value),
+                    )
+                })
+        })
+        .transpose()
+}
+
+/// Parameters of a query string, percent-decoded
+fn parse_query(query: &str) -> HashMap<String, String> {
+    query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (percent_decode(name), percent_decode(value))
+        })
+        .collect()
+}
+
+/// Decode `%XX` escapes and `+` in a query string component
+fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (b'+', _) => {
+                decoded.push(b' ');
+                i += 1;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// The document uploaded with a request
+struct Upload<'a> {
+    name: String,
+    data: &'a [u8],
+    format: InputFormat,
+}
+
+impl<'a> Upload<'a> {
+    /// The `file` field of the multipart `body`, in the format named by the
+    /// `from` parameter or else by the extension of its file name
+    fn parse(
+        query: &HashMap<String, String>,
+        content_type: Option<&str>,
+        body: &'a [u8],
+    ) -> Result<Self, Response> {
+        let missing = || {
+            Response::error(
+                400,
+                "Expected a multipart/form-data upload with a `file` field",
+            )
+        };
+        let boundary = content_type
+            .and_then(multipart_boundary)
+            .ok_or_else(missing)?;
+        let file = parse_multipart(body, &boundary)
+            .map_err(|e| Response::error(400, format!("Malformed multipart body: {}", e)))?
+            .into_iter()
+            .find(|part| part.name == "file")
+            .ok_or_else(missing)?;
+
+        // Only the last component of names sent with a path
+        let name = file
+            .filename
+            .as_deref()
+            .and_then(|name| name.rsplit(['/', '\\']).next())
+            .filter(|name| !name.is_empty())
+            .unwrap_or("document")
+            .to_string();
+        let format = match query.get("from") {
+            Some(from) => InputFormat::from_str(from, true)
+                .map_err(|_| Response::error(415, format!("Unknown input format: {}", from)))?,
+            None => Path::new(&name)
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .and_then(InputFormat::from_extension)
+                .ok_or_else(|| {
+                    Response::error(
+                        415,
+                        format!(
+                            "Unsupported file format: {:?}; name the file with its extension or pass `from`",
+                            name
+                        ),
+                    )
+                })?,
+        };
+        Ok(Self {
+            name,
+            data: file.data,
+            format,
+        })
+    }
+}
+
+/// Boundary of a `multipart/form-data` content type
+fn multipart_boundary(content_type: &str) -> Option<String> {
+    let mut params = content_type.split(';');
+    let mime = params.next()?.trim();
+    if !mime.eq_ignore_ascii_case("multipart/form-data") {
+        return None;
+    }
+    params
+        .filter_map(|param| param.trim().split_once('='))
+        .find(|(name, _)| name.trim().eq_ignore_ascii_case("boundary"))
+        .map(|(_, value)| value.trim().trim_matches('"').to_string())
+        .filter(|boundary| !boundary.is_empty())
+}
+
+/// A field of a `multipart/form-data` body
+struct Part<'a> {
+    name: String,
+    filename: Option<String>,
+    data: &'a [u8],
+}
+
+/// The fields of the multipart `body` delimited by `boundary` (RFC 7578)
+fn parse_multipart<'a>(body: &'a [u8], boundary: &str) -> Result<Vec<Part<'a>>, &'static str> {
+    let delimiter = format!("--{}", boundary);
+    let delimiter = delimiter.as_bytes();
+    let start = find(body, delimiter).ok_or("no boundary")?;
+    let mut rest = &body[start + delimiter.len()..];
+    let mut parts = Vec::new();
+    loop {
+        // `--` after the delimiter closes the body
+        if rest.starts_with(b"--") {
+            return Ok(parts);
+        }
+        rest = rest.strip_prefix(b"\r\n").ok_or("missing line break")?;
+        let headers_end = find(rest, b"\r\n\r\n").ok_or("unterminated headers")?;
+        let headers = std::str::from_utf8(&rest[..headers_end]).map_err(|_| "invalid headers")?;
+        rest = &rest[headers_end + 4..];
+
+        let end = find(rest, &[b"\r\n", delimiter].concat()).ok_or("unterminated field")?;
+        let data = &rest[..end];
+        rest = &rest[end + 2 + delimiter.len()..];
+
+        let disposition = headers
+            .split("\r\n")
+            .filter_map(|header| header.split_once(':'))
+            .find(|(name, _)| name.trim().eq_ignore_ascii_case("content-disposition"))
+            .map(|(_, value)| disposition_params(value))
+            .ok_or("field without Content-Disposition")?;
+        let param = |name: &str| {
+            disposition
+                .iter()
+                .find(|(param, _)| param.eq_ignore_ascii_case(name))
+                .map(|(_, value)| value.clone())
+        };
+        parts.push(Part {
+            name: param("name").unwrap_or_default(),
+            filename: param("filename"),
+            data,
+        });
+    }
+}
+
+/// Parameters of a `Content-Disposition` value, with quoted values unquoted
+fn disposition_params(value: &str) -> Vec<(String, String)> {
+    // Split on `;` outside quotes, as file names may hold it. Browsers send
+    // `\` as is (Windows paths) and `"` as `%22`, so there are no escapes.
+    let mut params = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    for c in value.chars() {
+        match c {
+            '"' => quoted = !quoted,
+            ';' if !quoted => params.push(std::mem::take(&mut current)),
+            c => current.push(c),
+        }
+    }
+    params.push(current);
+    params
+        .iter()
+        .filter_map(|param| param.split_once('='))
+        .map(|(name, value)| (name.trim().to_string(), value.trim().to_string()))
+        .collect()
+}
+
+/// Position of the first occurrence of `needle` in `haystack`
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+/// Listen on the address of `args` and answer requests until interrupted
+///
+/// The address listened on is printed to stderr once the server is ready;
+/// each request is logged at info level, with its status and duration.
+#[cfg(feature = "serve")]
+pub fn run(args: &ServeArgs) -> Result<()> {
+    let address = format!("{}:{}", args.host, args.port);
+    let server = tiny_http::Server::http(&address)
+        .map_err(|e| anyhow::anyhow!("Unable to listen on {}: {}", address, e))?;
+    let service = Service::new(args.limits());
+    let max_body = args.max_input_bytes.saturating_add(MULTIPART_OVERHEAD);
+
+    let listening = server
+        .server_addr()
+        .to_ip()
+        .map_or(address, |address| address.to_string());
+    eprintln!("Listening on http://{}", listening);
+
+    std::thread::scope(|scope| {
+        for _ in 0..args.jobs.get() {
+            scope.spawn(|| {
+                for request in server.incoming_requests() {
+                    respond(&service, request, max_body);
+                }
+            });
+        }
+    });
+    Ok(())
+}
+
+/// Listen for requests; the server needs the `serve` feature
+#[cfg(not(feature = "serve"))]
+pub fn run(_args: &ServeArgs) -> Result<()> {
+    use crate::cli::exit::{failure, ExitCode};
+
+    Err(failure(
+        ExitCode::InvalidArguments,
+        "docling-rs serve requires docling-rs to be built with the `serve` feature",
+    ))
+}
+
+/// Answer `request` with `service`, refusing bodies over `max_body` bytes
+#[cfg(feature = "serve")]
+fn respond(service: &Service, mut request: tiny_http::Request, max_body: u64) {
+    use std::io::Read;
+    use std::time::Instant;
+
+    let started = Instant::now();
+    let method = request.method().as_str().to_string();
+    let url = request.url().to_string();
+    let content_type = request
+        .headers()
+        .iter()
+        .find(|header| header.field.equiv("Content-Type"))
+        .map(|header| header.value.as_str().to_string());
+
+    let too_large = || {
+        Response::error(
+            413,
+            format!("Request body is over the limit of {} bytes", max_body),
+        )
+    };
+    let response = if request
+        .body_length()
+        .is_some_and(|length| length as u64 > max_body)
+    {
+        too_large()
+    } else {
+        let mut body = Vec::new();
+        match request
+            .as_reader()
+            .take(max_body.saturating_add(1))
+            .read_to_end(&mut body)
+        {
+            Ok(length) if length as u64 > max_body => too_large(),
+            Ok(_) => service.handle(&method, &url, content_type.as_deref(), &body),
+            Err(e) => Response::error(400, format!("Unable to read request body: {}", e)),
+        }
+    };
+
+    tracing::info!(
+        status = response.status,
+        "{} {} {} in {:?}",
+        method,
+        url,
+        response.status,
+        started.elapsed()
+    );
+    let body = serde_json::to_vec(&response.body).unwrap_or_default();
+    let mut reply = tiny_http::Response::from_data(body).with_status_code(response.status);
+    if let Ok(header) = tiny_http::Header::from_bytes("Content-Type", "application/json") {
+        reply = reply.with_header(header);
+    }
+    if let Err(e) = request.respond(reply) {
+        tracing::warn!("Unable to answer {} {}: {}", method, url, e);
+    }
+}
@@ -0,0 +1,108 @@
+//! Template-based output rendering.
+//!
+//! Renders documents through a user-supplied [Tera](https://keats.github.io/tera/)
+//! template, for output formats the crate does not ship (front matter for
+//! static site generators, custom XML, report layouts, ...). Requires the
+//! `templates` feature.
+//!
+//! Templates see these variables:
+//!
+//! - `name`: the document name
+//! - `title`: the title node text, else the metadata title, else the name
+//! - `metadata`: the document metadata (`language`, `page_count`, `authors`, ...)
+//! - `nodes`: one record per node in reading order, with `type`, `text`,
+//!   `headings`, `level`, `page`, ... (see [`NodeRecord`])
+//! - `markdown`: the document rendered as Markdown
+//! - `chunks`: chunk records (`text`, `contextualized_text`, `token_count`,
+//!   `meta`) when chunking, else an empty list
+//! - `document`: the full document, as in JSON output
+//!
+//! ```text
+//! ---
+//! title: "{{ title }}"
+//! lang: {{ metadata.language | default(value="en") }}
+//! ---
+//! {{ markdown }}
+//! ```
+//!
+//! Templates whose file name ends in `.html`, `.htm` or `.xml` escape
+//! variables automatically; use the `safe` filter to insert markup.
+
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use tera::Tera;
+
+use super::output;
+use crate::chunking::ChunkRecord;
+use crate::datamodel::jsonl::NodeRecord;
+use crate::datamodel::{DocumentNode, NodeType};
+use crate::DoclingDocument;
+
+/// A parsed output template, reusable across documents
+pub struct TemplateRenderer {
+    tera: Tera,
+    name: String,
+}
+
+impl TemplateRenderer {
+    /// Parse a template from a string
+    pub fn new(template: &str) -> Result<Self> {
+        Self::with_name("template", template)
+    }
+
+    /// Load and parse a template file
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let template = fs::read_to_string(path)
+            .with_context(|| format!("Unable to read template {:?}", path))?;
+        let name = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("template");
+        Self::with_name(name, &template)
+    }
+
+    fn with_name(name: &str, template: &str) -> Result<Self> {
+        let mut tera = Tera::default();
+        tera.add_raw_template(name, template)
+            .with_context(|| format!("Invalid template {}", name))?;
+        Ok(Self {
+            tera,
+            name: name.to_string(),
+        })
+    }
+
+    /// Render a document
+    pub fn render(&self, doc: &DoclingDocument) -> Result<String> {
+        self.render_chunks(doc, &[])
+    }
+
+    /// Render a document along with its chunks
+    pub fn render_chunks(&self, doc: &DoclingDocument, chunks: &[ChunkRecord]) -> Result<String> {
+        let title = doc
+            .nodes()
+            .iter()
+            .find(|n| n.node_type() == NodeType::Title)
+            .and_then(DocumentNode::text_content)
+            .or(doc.metadata().title())
+            .unwrap_or(doc.name());
+        let mut nodes = Vec::new();
+        doc.for_each_with_headings(|id, node, headings| {
+            nodes.push(NodeRecord::new(doc, id, node, headings));
+        });
+
+        let mut context = tera::Context::new();
+        context.insert("name", doc.name());
+        context.insert("title", title);
+        context.insert("metadata", doc.metadata());
+        context.insert("nodes", &nodes);
+        context.insert("markdown", &output::to_markdown(doc));
+        context.insert("chunks", chunks);
+        context.insert("document", doc);
+
+        self.tera
+            .render(&self.name, &context)
+            .with_context(|| format!("Unable to render template {}", self.name))
+    }
+}
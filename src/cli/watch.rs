@@ -0,0 +1,101 @@
+//! Change tracking for watch mode.
+//!
+//! With `--watch`, the CLI converts the files of the input directory, then
+//! keeps polling it and converts every file that appears or changes. A file
+//! is converted once its size and modification time have stayed the same for
+//! the debounce delay, so files still being copied into the directory are
+//! not picked up half-written.
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+/// How often the watched directory is scanned, at most
+pub const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Size and modification time of a file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Stamp {
+    modified: Option<SystemTime>,
+    len: u64,
+}
+
+#[derive(Debug)]
+struct Tracked {
+    stamp: Stamp,
+    changed: Instant,
+    pending: bool,
+}
+
+/// Files of a watched directory and whether they wait for conversion
+#[derive(Debug)]
+pub struct ChangeTracker {
+    debounce: Duration,
+    files: HashMap<PathBuf, Tracked>,
+}
+
+impl ChangeTracker {
+    /// Track files, converting them once unchanged for `debounce`
+    pub fn new(debounce: Duration) -> Self {
+        Self {
+            debounce,
+            files: HashMap::new(),
+        }
+    }
+
+    /// Note the state of `path` at `now`
+    ///
+    /// A file seen for the first time waits for conversion if `convert_new`
+    /// is set; a file that changed since it was last seen always does.
+    pub fn observe(&mut self, path: &Path, now: Instant, convert_new: bool) {
+        let Ok(metadata) = fs::metadata(path) else {
+            return;
+        };
+        let stamp = Stamp {
+            modified: metadata.modified().ok(),
+            len: metadata.len(),
+        };
+        match self.files.get_mut(path) {
+            Some(tracked) if tracked.stamp == stamp => {}
+            Some(tracked) => {
+                tracked.stamp = stamp;
+                tracked.changed = now;
+                tracked.pending = true;
+            }
+            None => {
+                self.files.insert(
+                    path.to_path_buf(),
+                    Tracked {
+                        stamp,
+                        changed: now,
+                        pending: convert_new,
+                    },
+                );
+            }
+        }
+    }
+
+    /// Stop tracking files that are gone, so they count as new if they come back
+    pub fn retain(&mut self, present: &HashSet<PathBuf>) {
+        self.files.retain(|path, _| present.contains(path));
+    }
+
+    /// Files waiting for conversion and unchanged for the debounce delay, in
+    /// path order; they no longer wait afterwards
+    pub fn settled(&mut self, now: Instant) -> Vec<PathBuf> {
+        let mut settled: Vec<PathBuf> = self
+            .files
+            .iter_mut()
+            .filter(|(_, tracked)| {
+                tracked.pending && now.duration_since(tracked.changed) >= self.debounce
+            })
+            .map(|(path, tracked)| {
+                tracked.pending = false;
+                path.clone()
+            })
+            .collect();
+        settled.sort();
+        settled
+    }
+}
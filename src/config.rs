@@ -0,0 +1,422 @@
+//! Converter configuration profiles
+//!
+//! A profile holds conversion options in a TOML or YAML file, so a team can
+//! share reproducible settings instead of repeating flags:
+//!
+//! ```toml
+//! [pipeline]
+//! detect_language = true
+//!
+//! [pdf]
+//! images = false
+//! first_page = 1
+//! last_page = 20
+//!
+//! [html]
+//! extract_metadata = false
+//!
+//! [limits]
+//! timeout_secs = 60
+//! max_pages = 500
+//!
+//! [cache]
+//! dir = ".docling-cache"
+//!
+//! [chunking]
+//! enabled = true
+//! chunker = "hybrid"
+//! max_tokens = 512
+//!
+//! [output]
+//! format = "jsonl"
+//! ```
+//!
+//! [`DocumentConverter::from_config`](crate::DocumentConverter::from_config)
+//! applies the `pipeline`, `pdf`, `html`, `limits` and `cache` sections, and
+//! the chunker, size and tokenizer of the `chunking` section. The `chunking`
+//! and `output` sections are also read by the command line tool
+//! (`--config`), where flags given on the command line take precedence.
+//! Every section and field is optional; unknown fields are rejected, so typos
+//! do not go unnoticed.
+//!
+//! Profiles can be layered with [`merge`](ConverterConfig::merge). The command
+//! line tool reads the user profile ([`user_file`](ConverterConfig::user_file)),
+//! then the project profile ([`project_file`](ConverterConfig::project_file)),
+//! then `--config`, each overriding the previous ones.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::backend::pdf::PdfConfig;
+use crate::backend::HtmlConfig;
+use crate::cache::ConversionCache;
+use crate::chunking::{tokenizer, BaseChunker, ChunkerOptions, ChunkerRegistry};
+use crate::converter::DocumentConverter;
+use crate::error::ConversionError;
+use crate::pipeline::{ConversionLimits, LanguageDetector};
+
+/// File name of project profiles, see [`ConverterConfig::project_file`]
+pub const PROJECT_FILE: &str = ".docling.toml";
+
+/// Conversion options loaded from a profile
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ConverterConfig {
+    pub pipeline: PipelineSettings,
+    pub pdf: PdfSettings,
+    pub html: HtmlSettings,
+    pub limits: LimitSettings,
+    pub cache: CacheSettings,
+    pub chunking: ChunkingSettings,
+    pub output: OutputSettings,
+}
+
+/// Enrichment options
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PipelineSettings {
+    /// Tag nodes with their language
+    pub detect_language: bool,
+    /// Group nodes into sections by heading level
+    pub group_sections: bool,
+}
+
+/// PDF backend options; unset fields keep the [`PdfConfig`] defaults
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PdfSettings {
+    /// Password for encrypted PDFs
+    pub password: Option<String>,
+    /// First page to convert (1-based)
+    pub first_page: Option<usize>,
+    /// Last page to convert (1-based, inclusive)
+    pub last_page: Option<usize>,
+    /// Run OCR on scanned pages
+    pub ocr: Option<bool>,
+    /// OCR language, such as `eng`
+    pub ocr_language: Option<String>,
+    /// Detect and extract tables
+    pub tables: Option<bool>,
+    /// Extract images
+    pub images: Option<bool>,
+}
+
+/// HTML backend options; unset fields keep the [`HtmlConfig`] defaults
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HtmlSettings {
+    /// Read the title, language and authors from the document head
+    pub extract_metadata: Option<bool>,
+}
+
+/// Resource limits, see [`ConversionLimits`]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LimitSettings {
+    pub timeout_secs: Option<u64>,
+    pub max_pages: Option<usize>,
+    pub max_output_bytes: Option<usize>,
+    pub max_input_bytes: Option<u64>,
+    pub max_decompressed_bytes: Option<u64>,
+    pub max_depth: Option<usize>,
+}
+
+/// Result cache, see [`ConversionCache`]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CacheSettings {
+    pub dir: Option<PathBuf>,
+}
+
+/// Chunking options of the command line tool
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ChunkingSettings {
+    /// Chunk documents (`--chunk`)
+    pub enabled: bool,
+    /// Chunking strategy, as for `--chunker`
+    pub chunker: Option<String>,
+    /// Chunk size in characters (`--chunk-size`)
+    pub chunk_size: Option<usize>,
+    /// Maximum tokens per chunk (`--max-tokens`)
+    pub max_tokens: Option<usize>,
+    /// Tokenizer, as for `--tokenizer`
+    pub tokenizer: Option<String>,
+}
+
+/// Output options of the command line tool
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct OutputSettings {
+    /// Output format, as for `--to`
+    pub format: Option<String>,
+    /// Output directory (`--output-dir`)
+    pub dir: Option<PathBuf>,
+    /// Output template (`--template`)
+    pub template: Option<PathBuf>,
+    /// Mark page boundaries (`--page-markers`)
+    pub page_markers: bool,
+    /// Write compact JSON (`--json-compact`)
+    pub json_compact: bool,
+    /// Nest JSON nodes as a tree (`--json-tree`)
+    pub json_tree: bool,
+}
+
+impl ConverterConfig {
+    /// Load a profile, as TOML or YAML depending on its extension
+    /// (`.toml`, `.yaml` or `.yml`)
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, ConversionError> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Err(ConversionError::FileNotFound(path.to_path_buf()));
+        }
+        let text = fs::read_to_string(path)?;
+        let extension = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(str::to_lowercase);
+        let config = match extension.as_deref() {
+            Some("toml") => Self::from_toml(&text),
+            Some("yaml" | "yml") => Self::from_yaml(&text),
+            _ => {
+                return Err(ConversionError::Config(format!(
+                    "{}: expected a .toml, .yaml or .yml file",
+                    path.display()
+                )))
+            }
+        };
+        config.map_err(|e| match e {
+            ConversionError::Config(message) => {
+                ConversionError::Config(format!("{}: {}", path.display(), message))
+            }
+            e => e,
+        })
+    }
+
+    /// Parse a TOML profile
+    pub fn from_toml(text: &str) -> Result<Self, ConversionError> {
+        let config: Self =
+            toml::from_str(text).map_err(|e| ConversionError::Config(e.to_string()))?;
+        config.validate()
+    }
+
+    /// Parse a YAML profile
+    pub fn from_yaml(text: &str) -> Result<Self, ConversionError> {
+        // An empty document is an empty profile
+        if text.trim().is_empty() {
+            return Ok(Self::default());
+        }
+        let config: Self =
+            serde_yaml::from_str(text).map_err(|e| ConversionError::Config(e.to_string()))?;
+        config.validate()
+    }
+
+    /// Layer `overrides` on top of this profile
+    ///
+    /// Fields set in `overrides` replace those of `self`; switches are on when
+    /// either profile turns them on.
+    pub fn merge(self, overrides: Self) -> Self {
+        Self {
+            pipeline: PipelineSettings {
+                detect_language: self.pipeline.detect_language
+                    || overrides.pipeline.detect_language,
+                group_sections: self.pipeline.group_sections || overrides.pipeline.group_sections,
+            },
+            pdf: PdfSettings {
+                password: overrides.pdf.password.or(self.pdf.password),
+                first_page: overrides.pdf.first_page.or(self.pdf.first_page),
+                last_page: overrides.pdf.last_page.or(self.pdf.last_page),
+                ocr: overrides.pdf.ocr.or(self.pdf.ocr),
+                ocr_language: overrides.pdf.ocr_language.or(self.pdf.ocr_language),
+                tables: overrides.pdf.tables.or(self.pdf.tables),
+                images: overrides.pdf.images.or(self.pdf.images),
+            },
+            html: HtmlSettings {
+                extract_metadata: overrides
+                    .html
+                    .extract_metadata
+                    .or(self.html.extract_metadata),
+            },
+            limits: LimitSettings {
+                timeout_secs: overrides.limits.timeout_secs.or(self.limits.timeout_secs),
+                max_pages: overrides.limits.max_pages.or(self.limits.max_pages),
+                max_output_bytes: overrides
+                    .limits
+                    .max_output_bytes
+                    .or(self.limits.max_output_bytes),
+                max_input_bytes: overrides
+                    .limits
+                    .max_input_bytes
+                    .or(self.limits.max_input_bytes),
+                max_decompressed_bytes: overrides
+                    .limits
+                    .max_decompressed_bytes
+                    .or(self.limits.max_decompressed_bytes),
+                max_depth: overrides.limits.max_depth.or(self.limits.max_depth),
+            },
+            cache: CacheSettings {
+                dir: overrides.cache.dir.or(self.cache.dir),
+            },
+            chunking: ChunkingSettings {
+                enabled: self.chunking.enabled || overrides.chunking.enabled,
+                chunker: overrides.chunking.chunker.or(self.chunking.chunker),
+                chunk_size: overrides.chunking.chunk_size.or(self.chunking.chunk_size),
+                max_tokens: overrides.chunking.max_tokens.or(self.chunking.max_tokens),
+                tokenizer: overrides.chunking.tokenizer.or(self.chunking.tokenizer),
+            },
+            output: OutputSettings {
+                format: overrides.output.format.or(self.output.format),
+                dir: overrides.output.dir.or(self.output.dir),
+                template: overrides.output.template.or(self.output.template),
+                page_markers: self.output.page_markers || overrides.output.page_markers,
+                json_compact: self.output.json_compact || overrides.output.json_compact,
+                json_tree: self.output.json_tree || overrides.output.json_tree,
+            },
+        }
+    }
+
+    /// Path of the user profile: `docling-rs/config.toml` in
+    /// `$XDG_CONFIG_HOME`, or else in `~/.config`
+    ///
+    /// The file may not exist. `None` if no home directory is known.
+    pub fn user_file() -> Option<PathBuf> {
+        let config_home = std::env::var_os("XDG_CONFIG_HOME")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| {
+                std::env::var_os("HOME")
+                    .or_else(|| std::env::var_os("USERPROFILE"))
+                    .filter(|dir| !dir.is_empty())
+                    .map(|home| PathBuf::from(home).join(".config"))
+            })?;
+        Some(config_home.join("docling-rs").join("config.toml"))
+    }
+
+    /// The project profile of `dir`: the nearest [`PROJECT_FILE`] in `dir`
+    /// or one of its parents, if any
+    pub fn project_file(dir: &Path) -> Option<PathBuf> {
+        dir.ancestors()
+            .map(|dir| dir.join(PROJECT_FILE))
+            .find(|path| path.is_file())
+    }
+
+    fn validate(self) -> Result<Self, ConversionError> {
+        if let (Some(first), Some(last)) = (self.pdf.first_page, self.pdf.last_page) {
+            if first > last {
+                return Err(ConversionError::Config(format!(
+                    "pdf.first_page ({}) is after pdf.last_page ({})",
+                    first, last
+                )));
+            }
+        }
+        if self.pdf.first_page == Some(0) {
+            return Err(ConversionError::Config(
+                "pdf.first_page starts at 1".to_string(),
+            ));
+        }
+        Ok(self)
+    }
+
+    /// The PDF backend configuration described by the `pdf` section
+    pub fn pdf_config(&self) -> PdfConfig {
+        let pdf = &self.pdf;
+        let mut config = PdfConfig::default().password(pdf.password.clone());
+        if pdf.first_page.is_some() || pdf.last_page.is_some() {
+            let start = pdf.first_page.unwrap_or(1) - 1;
+            config = config.page_range(Some(start..pdf.last_page.unwrap_or(usize::MAX)));
+        }
+        if let Some(ocr) = pdf.ocr {
+            config = config.enable_ocr(ocr);
+        }
+        if let Some(language) = &pdf.ocr_language {
+            config = config.ocr_language(language);
+        }
+        if let Some(tables) = pdf.tables {
+            config = config.enable_tables(tables);
+        }
+        if let Some(images) = pdf.images {
+            config = config.enable_images(images);
+        }
+        config
+    }
+
+    /// The HTML backend configuration described by the `html` section
+    pub fn html_config(&self) -> HtmlConfig {
+        let mut config = HtmlConfig::default();
+        if let Some(extract) = self.html.extract_metadata {
+            config = config.extract_metadata(extract);
+        }
+        config
+    }
+
+    /// The limits described by the `limits` section
+    pub fn limits(&self) -> ConversionLimits {
+        let mut limits = ConversionLimits::new();
+        if let Some(seconds) = self.limits.timeout_secs {
+            limits = limits.with_timeout(Duration::from_secs(seconds));
+        }
+        if let Some(pages) = self.limits.max_pages {
+            limits = limits.with_max_pages(pages);
+        }
+        if let Some(bytes) = self.limits.max_output_bytes {
+            limits = limits.with_max_output_bytes(bytes);
+        }
+        if let Some(bytes) = self.limits.max_input_bytes {
+            limits = limits.with_max_input_bytes(bytes);
+        }
+        if let Some(bytes) = self.limits.max_decompressed_bytes {
+            limits = limits.with_max_decompressed_bytes(bytes);
+        }
+        if let Some(depth) = self.limits.max_depth {
+            limits = limits.with_max_depth(depth);
+        }
+        limits
+    }
+
+    /// The chunker described by the `chunking` section, hierarchical by default
+    ///
+    /// Strategies are looked up in the built-in
+    /// [`ChunkerRegistry`](crate::chunking::ChunkerRegistry).
+    pub fn chunker(&self) -> Result<Box<dyn BaseChunker>, ConversionError> {
+        let chunking = &self.chunking;
+        let tokenizer = chunking
+            .tokenizer
+            .as_deref()
+            .map(tokenizer::from_spec)
+            .transpose()
+            .map_err(|e| ConversionError::Config(format!("chunking.tokenizer: {}", e)))?;
+        let options = ChunkerOptions {
+            max_tokens: chunking.max_tokens,
+            chunk_size: chunking.chunk_size,
+            tokenizer,
+            embedder: None,
+        };
+        ChunkerRegistry::new()
+            .create(
+                chunking.chunker.as_deref().unwrap_or("hierarchical"),
+                options,
+            )
+            .map_err(|e| ConversionError::Config(format!("chunking.chunker: {}", e)))
+    }
+
+    /// Build a converter with the `pipeline`, `pdf`, `html`, `limits` and
+    /// `cache` settings
+    pub fn build(&self) -> DocumentConverter {
+        let mut converter = DocumentConverter::builder()
+            .pdf_config(self.pdf_config())
+            .html_config(self.html_config())
+            .build()
+            .with_section_grouping(self.pipeline.group_sections)
+            .with_limits(self.limits());
+        if self.pipeline.detect_language {
+            converter = converter.with_language_detection(LanguageDetector::new());
+        }
+        if let Some(dir) = &self.cache.dir {
+            converter = converter.with_cache(ConversionCache::new(dir));
+        }
+        converter
+    }
+}
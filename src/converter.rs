@@ -1,14 +1,30 @@
 //! Document converter - main entry point
 
-use crate::datamodel::{ConversionResult, InputDocument};
+use crate::backend::pdf::PdfConfig;
+use crate::backend::{Backend, HtmlConfig};
+use crate::cache::ConversionCache;
+use crate::chunking::{BaseChunk, BaseChunker, HierarchicalChunker};
+use crate::config::ConverterConfig;
+use crate::datamodel::DoclingDocument;
+use crate::datamodel::{ConversionResult, DocumentSource, InputDocument};
 use crate::error::ConversionError;
 use crate::format::InputFormat;
-use crate::pipeline::{Pipeline, SimplePipeline};
-use std::path::Path;
+use crate::pipeline::{
+    AfterConvertHook, BeforeParseHook, ConversionLimits, LanguageDetector, Pipeline, PipelineStage,
+    ProgressObserver, SimplePipeline, StreamSummary,
+};
+use crate::remote::{self, ObjectReader, ObjectUrl};
+use rayon::prelude::*;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 
 /// Main entry point for document conversion
 pub struct DocumentConverter {
     pipeline: SimplePipeline,
+    /// File extensions (lowercase) routed to a format, ahead of the built-in ones
+    extensions: HashMap<String, InputFormat>,
+    cache: Option<ConversionCache>,
+    chunker: Box<dyn BaseChunker>,
 }
 
 impl DocumentConverter {
@@ -16,26 +32,252 @@ impl DocumentConverter {
     pub fn new() -> Self {
         Self {
             pipeline: SimplePipeline::new(),
+            extensions: HashMap::new(),
+            cache: None,
+            chunker: Box::new(HierarchicalChunker::new()),
         }
     }
 
+    /// Create a converter from a TOML or YAML configuration profile
+    ///
+    /// See [`config`](crate::config) for the file layout. The chunker of the
+    /// `chunking` section is used by [`chunk`](Self::chunk).
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// use docling_rs::DocumentConverter;
+    ///
+    /// let converter = DocumentConverter::from_config("docling.toml")?;
+    /// let result = converter.convert_file("report.pdf")?;
+    /// ```
+    pub fn from_config(path: impl AsRef<Path>) -> Result<Self, ConversionError> {
+        let config = ConverterConfig::from_file(path)?;
+        Ok(config.build().with_chunker(config.chunker()?))
+    }
+
+    /// Start building a converter with per-format options or custom backends
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// use docling_rs::backend::pdf::PdfConfig;
+    /// use docling_rs::backend::HtmlConfig;
+    /// use docling_rs::DocumentConverter;
+    ///
+    /// let converter = DocumentConverter::builder()
+    ///     .pdf_config(PdfConfig::default().enable_ocr(true).enable_images(false))
+    ///     .html_config(HtmlConfig::default().extract_metadata(false))
+    ///     .build();
+    /// ```
+    ///
+    /// ```ignore
+    /// use docling_rs::{DocumentConverter, InputFormat};
+    ///
+    /// // MDX files go through a backend that understands JSX blocks
+    /// let converter = DocumentConverter::builder()
+    ///     .register_backend(InputFormat::Markdown, Box::new(MdxBackend::new()))
+    ///     .register_extension("mdx", InputFormat::Markdown)
+    ///     .build();
+    /// ```
+    pub fn builder() -> DocumentConverterBuilder {
+        DocumentConverterBuilder::default()
+    }
+
+    /// Convert PDFs with `config` instead of the default configuration
+    pub fn with_pdf_config(mut self, config: PdfConfig) -> Self {
+        self.pipeline = self.pipeline.with_pdf_config(config);
+        self
+    }
+
+    /// Convert HTML with `config` instead of the default configuration
+    pub fn with_html_config(mut self, config: HtmlConfig) -> Self {
+        self.pipeline = self.pipeline.with_html_config(config);
+        self
+    }
+
+    /// Tag converted documents with the language of their nodes
+    pub fn with_language_detection(mut self, detector: LanguageDetector) -> Self {
+        self.pipeline = self.pipeline.with_language_detection(detector);
+        self
+    }
+
+    /// Group the nodes of converted documents into sections by heading level
+    pub fn with_section_grouping(mut self, group: bool) -> Self {
+        self.pipeline = self.pipeline.with_section_grouping(group);
+        self
+    }
+
+    /// Run an enrichment stage on converted documents
+    ///
+    /// See [`PipelineStage`](crate::pipeline::PipelineStage).
+    pub fn with_stage(mut self, stage: impl PipelineStage + 'static) -> Self {
+        self.pipeline = self.pipeline.with_stage(stage);
+        self
+    }
+
+    /// Rewrite the bytes of sources before they are parsed
+    ///
+    /// See [`hooks`](crate::pipeline::hooks).
+    pub fn with_before_parse(mut self, hook: impl BeforeParseHook + 'static) -> Self {
+        self.pipeline = self.pipeline.with_before_parse(hook);
+        self
+    }
+
+    /// Edit converted documents once parsing and enrichment are done
+    ///
+    /// See [`hooks`](crate::pipeline::hooks).
+    pub fn with_after_convert(mut self, hook: impl AfterConvertHook + 'static) -> Self {
+        self.pipeline = self.pipeline.with_after_convert(hook);
+        self
+    }
+
+    /// Fail conversions that go over `limits`, with
+    /// [`ConversionError::LimitExceeded`]
+    ///
+    /// See [`limits`](crate::pipeline::limits) for when limits are checked.
+    pub fn with_limits(mut self, limits: ConversionLimits) -> Self {
+        self.pipeline = self.pipeline.with_limits(limits);
+        self
+    }
+
+    /// Reuse results stored in `cache`, and store new ones there
+    ///
+    /// Sources are read once more to compute their key. Cached results are
+    /// returned without running the pipeline, so no progress is reported for
+    /// them. Failing to store a result does not fail the conversion.
+    pub fn with_cache(mut self, cache: ConversionCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Report the progress of conversions to `observer`
+    ///
+    /// See [`progress`](crate::pipeline::progress) for the events reported.
+    pub fn with_progress(mut self, observer: impl ProgressObserver + 'static) -> Self {
+        self.pipeline = self.pipeline.with_progress(observer);
+        self
+    }
+
+    /// Chunk documents with `chunker` in [`chunk`](Self::chunk), instead of
+    /// a [`HierarchicalChunker`]
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// use docling_rs::chunking::{ChunkerOptions, ChunkerRegistry};
+    /// use docling_rs::DocumentConverter;
+    ///
+    /// let chunker = ChunkerRegistry::new().create(
+    ///     "hybrid",
+    ///     ChunkerOptions { max_tokens: Some(256), ..Default::default() },
+    /// )?;
+    /// let converter = DocumentConverter::new().with_chunker(chunker);
+    ///
+    /// let result = converter.convert_file("report.pdf")?;
+    /// let chunks: Vec<_> = converter.chunk(result.document()).collect();
+    /// ```
+    pub fn with_chunker(mut self, chunker: impl BaseChunker + 'static) -> Self {
+        self.chunker = Box::new(chunker);
+        self
+    }
+
+    /// The chunker of [`chunk`](Self::chunk), to contextualize its chunks
+    pub fn chunker(&self) -> &dyn BaseChunker {
+        self.chunker.as_ref()
+    }
+
+    /// Chunk a converted document with the chunker of this converter
+    pub fn chunk<'a>(
+        &'a self,
+        doc: &'a DoclingDocument,
+    ) -> Box<dyn Iterator<Item = BaseChunk> + 'a> {
+        self.chunker.chunk(doc)
+    }
+
     /// Convert a document from a file path
     pub fn convert_file<P: AsRef<Path>>(
         &self,
         path: P,
     ) -> Result<ConversionResult, ConversionError> {
         let path = path.as_ref();
+        let input = self.input_for(path)?;
 
+        // Execute pipeline
+        match &self.cache {
+            Some(cache) => {
+                // Refuse oversized sources before reading them for the key
+                if let Some(size) = input.size() {
+                    self.pipeline.limits().start().check_input_size(size)?;
+                }
+                self.execute_cached(cache, &input, &std::fs::read(path)?)
+            }
+            None => self.pipeline.execute(&input),
+        }
+    }
+
+    /// Convert a document of object storage: `s3://bucket/key` (`s3`
+    /// feature) or `gs://bucket/key` (`gcs` feature)
+    ///
+    /// The object is streamed to a temporary file, removed once converted,
+    /// and its format detected from the extension of its key. Credentials
+    /// are discovered from the environment; see [`remote`](crate::remote).
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// use docling_rs::DocumentConverter;
+    ///
+    /// let result = DocumentConverter::new().convert_url("s3://reports/2024/annual.pdf")?;
+    /// ```
+    pub fn convert_url(&self, url: &str) -> Result<ConversionResult, ConversionError> {
+        let object = ObjectUrl::parse(url)?;
+        let reader = remote::reader_for(object.scheme)?;
+        self.convert_object(&object, reader.as_ref())
+    }
+
+    /// Convert the object at `url`, read with `reader`
+    ///
+    /// Like [`convert_url`](Self::convert_url), with a reader configured by
+    /// the caller instead of from the environment.
+    pub fn convert_object(
+        &self,
+        url: &ObjectUrl,
+        reader: &dyn ObjectReader,
+    ) -> Result<ConversionResult, ConversionError> {
+        let dir = tempfile::tempdir()?;
+        let path = remote::download(url, reader, dir.path(), self.pipeline.limits())?;
+        let input =
+            InputDocument::from_remote(url.to_string(), path.clone(), self.format_for(&path)?);
+
+        match &self.cache {
+            Some(cache) => self.execute_cached(cache, &input, &std::fs::read(&path)?),
+            None => self.pipeline.execute(&input),
+        }
+    }
+
+    /// The input document for a file, with the format detected from its extension
+    fn input_for(&self, path: &Path) -> Result<InputDocument, ConversionError> {
         // Check if file exists
         if !path.exists() {
             return Err(ConversionError::FileNotFound(path.to_path_buf()));
         }
+        Ok(InputDocument::from_path(
+            path.to_path_buf(),
+            self.format_for(path)?,
+        ))
+    }
 
-        // Detect format from extension
-        let format = path
-            .extension()
+    /// Format of a file, from its extension
+    fn format_for(&self, path: &Path) -> Result<InputFormat, ConversionError> {
+        path.extension()
             .and_then(|ext| ext.to_str())
-            .and_then(InputFormat::from_extension)
+            .and_then(|ext| {
+                self.extensions
+                    .get(&ext.to_lowercase())
+                    .copied()
+                    .or_else(|| InputFormat::from_extension(ext))
+            })
             .ok_or_else(|| {
                 ConversionError::UnsupportedFormat(
                     path.extension()
@@ -43,13 +285,84 @@ impl DocumentConverter {
                         .unwrap_or("unknown")
                         .to_string(),
                 )
-            })?;
+            })
+    }
 
-        // Create InputDocument
-        let input = InputDocument::from_path(path.to_path_buf(), format);
+    /// Convert a file, handing the document to `on_part` in parts as they
+    /// are converted
+    ///
+    /// Keeps memory low for large documents and lets consumers start
+    /// chunking or embedding before the conversion finishes. Results are not
+    /// cached. See [`stream`](crate::pipeline::stream).
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// use docling_rs::DocumentConverter;
+    ///
+    /// let converter = DocumentConverter::new();
+    /// let summary = converter.convert_streaming("manual.pdf", |part| {
+    ///     for chunk in chunker.chunk(&part) {
+    ///         index.add(embed(&chunk.text));
+    ///     }
+    ///     Ok(())
+    /// })?;
+    /// println!("{} parts, {} nodes", summary.parts(), summary.nodes());
+    /// ```
+    pub fn convert_streaming<P: AsRef<Path>>(
+        &self,
+        path: P,
+        mut on_part: impl FnMut(DoclingDocument) -> Result<(), ConversionError>,
+    ) -> Result<StreamSummary, ConversionError> {
+        let input = self.input_for(path.as_ref())?;
+        self.pipeline.execute_streaming(&input, &mut on_part)
+    }
 
-        // Execute pipeline
-        self.pipeline.execute(&input)
+    /// Convert several files concurrently
+    ///
+    /// Files are converted on a pool of at most
+    /// [`max_workers`](ParallelOptions::with_max_workers) threads. Returns one
+    /// result per path, in the order of `paths`; a failed file does not stop
+    /// the others.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// use docling_rs::{DocumentConverter, ParallelOptions};
+    ///
+    /// let converter = DocumentConverter::new();
+    /// let options = ParallelOptions::new().with_max_workers(4);
+    /// for (path, result) in converter.convert_many(&["a.pdf", "b.docx"], &options) {
+    ///     match result {
+    ///         Ok(result) => println!("{}: {} nodes", path.display(), result.document().nodes().len()),
+    ///         Err(e) => eprintln!("{}: {}", path.display(), e),
+    ///     }
+    /// }
+    /// ```
+    pub fn convert_many<P: AsRef<Path> + Sync>(
+        &self,
+        paths: &[P],
+        options: &ParallelOptions,
+    ) -> Vec<(PathBuf, Result<ConversionResult, ConversionError>)> {
+        let convert = || {
+            paths
+                .par_iter()
+                .map(|path| {
+                    let path = path.as_ref();
+                    (path.to_path_buf(), self.convert_file(path))
+                })
+                .collect()
+        };
+
+        let mut pool = rayon::ThreadPoolBuilder::new();
+        if let Some(workers) = options.max_workers {
+            pool = pool.num_threads(workers);
+        }
+        match pool.build() {
+            Ok(pool) => pool.install(convert),
+            // Fall back to the global pool if no threads could be started
+            Err(_) => convert(),
+        }
     }
 
     /// Convert a document from bytes
@@ -63,7 +376,36 @@ impl DocumentConverter {
         let input = InputDocument::from_bytes(bytes, name, format);
 
         // Execute pipeline
-        self.pipeline.execute(&input)
+        match (&self.cache, input.source()) {
+            (Some(cache), DocumentSource::Bytes { data, .. }) => {
+                self.execute_cached(cache, &input, data)
+            }
+            _ => self.pipeline.execute(&input),
+        }
+    }
+
+    /// Execute the pipeline unless `cache` holds the result for `content`
+    fn execute_cached(
+        &self,
+        cache: &ConversionCache,
+        input: &InputDocument,
+        content: &[u8],
+    ) -> Result<ConversionResult, ConversionError> {
+        let key = cache.key(
+            content,
+            input.name(),
+            input.format(),
+            &self.pipeline.fingerprint(),
+        );
+        if let Some(result) = cache.get(&key) {
+            return Ok(result);
+        }
+        let result = self.pipeline.execute(input)?;
+        // A cache that cannot be written only costs a future conversion
+        if let Err(e) = cache.put(&key, &result) {
+            tracing::warn!(document = input.name(), "Unable to cache the result: {}", e);
+        }
+        Ok(result)
     }
 }
 
@@ -72,3 +414,87 @@ impl Default for DocumentConverter {
         Self::new()
     }
 }
+
+/// Builder for a [`DocumentConverter`] with per-format options or custom
+/// backends
+///
+/// Created by [`DocumentConverter::builder`]. Options such as language
+/// detection and stages are set on the built converter.
+#[derive(Default)]
+pub struct DocumentConverterBuilder {
+    pipeline: SimplePipeline,
+    extensions: HashMap<String, InputFormat>,
+}
+
+impl DocumentConverterBuilder {
+    /// Convert PDFs with `config` instead of the default configuration
+    pub fn pdf_config(mut self, config: PdfConfig) -> Self {
+        self.pipeline = self.pipeline.with_pdf_config(config);
+        self
+    }
+
+    /// Convert HTML with `config` instead of the default configuration
+    pub fn html_config(mut self, config: HtmlConfig) -> Self {
+        self.pipeline = self.pipeline.with_html_config(config);
+        self
+    }
+
+    /// Convert documents of `format` with `backend`, replacing the built-in backend
+    pub fn register_backend(
+        mut self,
+        format: InputFormat,
+        backend: Box<dyn Backend + Send + Sync>,
+    ) -> Self {
+        self.pipeline = self.pipeline.with_backend(format, backend);
+        self
+    }
+
+    /// Treat files with extension `ext` as `format` in
+    /// [`convert_file`](DocumentConverter::convert_file)
+    ///
+    /// Adds extensions the built-in detection does not know (or reroutes
+    /// known ones), so files of a new kind reach the backend registered for
+    /// `format`. Extensions are matched case-insensitively, without the dot.
+    pub fn register_extension(mut self, ext: &str, format: InputFormat) -> Self {
+        self.extensions
+            .insert(ext.trim_start_matches('.').to_lowercase(), format);
+        self
+    }
+
+    /// Build the converter
+    pub fn build(self) -> DocumentConverter {
+        DocumentConverter {
+            pipeline: self.pipeline,
+            extensions: self.extensions,
+            cache: None,
+            chunker: Box::new(HierarchicalChunker::new()),
+        }
+    }
+}
+
+/// Options for [`DocumentConverter::convert_many`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ParallelOptions {
+    max_workers: Option<usize>,
+}
+
+impl ParallelOptions {
+    /// Create the default options: one worker per CPU
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Convert at most `workers` files at a time
+    ///
+    /// Bounds memory use when converting large PDFs. A value of 0 means one
+    /// worker per CPU.
+    pub fn with_max_workers(mut self, workers: usize) -> Self {
+        self.max_workers = Some(workers);
+        self
+    }
+
+    /// Maximum number of files converted at a time, if bounded
+    pub fn max_workers(&self) -> Option<usize> {
+        self.max_workers
+    }
+}
@@ -0,0 +1,141 @@
+//! Annotations attached to document nodes by enrichment steps
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+use crate::datamodel::NodeId;
+
+/// A labeled finding about a node or a range of its text
+///
+/// Annotations hold the output of enrichment steps (named entities, PII,
+/// classifications, OCR confidence, ...) next to the document instead of in
+/// side files. Each one points at a node and optionally at a byte range of the
+/// node's text (like [`TextSpan`](crate::datamodel::TextSpan)), and records
+/// which step produced it and how confident it was. Anything else goes in the
+/// free-form attributes.
+///
+/// Add annotations with
+/// [`DoclingDocument::annotate`](crate::datamodel::DoclingDocument::annotate).
+///
+/// # Examples
+///
+/// ```ignore
+/// use docling_rs::datamodel::Annotation;
+///
+/// // "Ada Lovelace" at bytes 0..12 of the paragraph
+/// let person = Annotation::new(paragraph, "PERSON")
+///     .with_range(0, 12)
+///     .with_source("ner")
+///     .with_confidence(0.97);
+/// doc.annotate(person);
+///
+/// // Document classification of a whole node
+/// doc.annotate(Annotation::new(heading, "invoice").with_source("classifier"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Annotation {
+    node: NodeId,
+    label: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    start: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    end: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    source: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    confidence: Option<f64>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    attributes: BTreeMap<String, Value>,
+}
+
+impl Annotation {
+    /// Create an annotation covering a whole node
+    pub fn new(node: NodeId, label: impl Into<String>) -> Self {
+        Self {
+            node,
+            label: label.into(),
+            start: None,
+            end: None,
+            source: None,
+            confidence: None,
+            attributes: BTreeMap::new(),
+        }
+    }
+
+    /// Get the annotated node
+    pub fn node(&self) -> NodeId {
+        self.node
+    }
+
+    pub(crate) fn set_node(&mut self, node: NodeId) {
+        self.node = node;
+    }
+
+    /// Get the label (e.g. `PERSON`, `EMAIL`, `invoice`)
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// Get the annotated byte range of the node text, if limited to one
+    pub fn range(&self) -> Option<(usize, usize)> {
+        self.start.zip(self.end)
+    }
+
+    /// Limit the annotation to a byte range of the node text
+    pub fn with_range(mut self, start: usize, end: usize) -> Self {
+        self.start = Some(start);
+        self.end = Some(end);
+        self
+    }
+
+    /// Get the name of the step that produced the annotation
+    pub fn source(&self) -> Option<&str> {
+        self.source.as_deref()
+    }
+
+    /// Set the name of the step that produced the annotation
+    pub fn with_source(mut self, source: impl Into<String>) -> Self {
+        self.source = Some(source.into());
+        self
+    }
+
+    /// Get the confidence, between 0 and 1
+    pub fn confidence(&self) -> Option<f64> {
+        self.confidence
+    }
+
+    /// Set the confidence, clamped to 0..=1
+    pub fn with_confidence(mut self, confidence: f64) -> Self {
+        self.confidence = Some(confidence.clamp(0.0, 1.0));
+        self
+    }
+
+    /// Get the free-form attributes
+    pub fn attributes(&self) -> &BTreeMap<String, Value> {
+        &self.attributes
+    }
+
+    /// Get an attribute
+    pub fn attribute(&self, key: &str) -> Option<&Value> {
+        self.attributes.get(key)
+    }
+
+    /// Add an attribute (e.g. a normalized value or a linked entity ID)
+    pub fn with_attribute(mut self, key: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.attributes.insert(key.into(), value.into());
+        self
+    }
+
+    /// Get the annotated part of `text`, the annotated node's text
+    ///
+    /// Returns the whole text for node-level annotations, and `None` if the
+    /// range does not fit the text.
+    pub fn covered_text<'a>(&self, text: &'a str) -> Option<&'a str> {
+        match self.range() {
+            Some((start, end)) => text.get(start..end),
+            None => Some(text),
+        }
+    }
+}
@@ -0,0 +1,618 @@
+//! Interoperability with the Python docling `DoclingDocument` JSON schema
+//!
+//! [`to_value`] emits documents in the schema used by the upstream Python
+//! `docling-core` package, so they can be loaded with
+//! `DoclingDocument.model_validate_json` and fed to docling-based tooling.
+//! [`from_value`] reads documents in that schema back into a
+//! [`DoclingDocument`].
+//!
+//! The mapping is structural:
+//!
+//! | docling-rs              | docling                                          |
+//! |-------------------------|--------------------------------------------------|
+//! | Title, Heading          | `texts` labelled `title`, `section_header`       |
+//! | Text, Paragraph         | `texts` labelled `text`                          |
+//! | ListItem, CodeBlock, Formula, Caption, Footnote | `texts` with the matching label |
+//! | List                    | `groups` labelled `list` or `ordered_list`       |
+//! | Quote                   | `groups` labelled `unspecified`                  |
+//! | Table (+ [`TableData`]) | `tables`                                         |
+//! | Picture                 | `pictures`, alt text as a description annotation |
+//! | captions of tables and pictures | `caption` texts referenced in `captions` |
+//! | page, bounding box      | `prov` (bottom-left origin)                      |
+//!
+//! Page breaks have no docling counterpart and are dropped; table row and cell
+//! nodes are dropped in favor of the table's [`TableData`]. On import, docling
+//! labels without a docling-rs counterpart become paragraphs, groups other
+//! than lists are flattened into their parent, and furniture (page headers and
+//! footers) is skipped.
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use serde_json::{json, Map, Value};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+use crate::datamodel::{
+    BoundingBox, DoclingDocument, DocumentNode, ImageRef, NodeId, NodeType, PictureItem, TableCell,
+    TableData, TableRow,
+};
+use crate::error::ConversionError;
+
+/// Schema name of docling documents
+pub const SCHEMA_NAME: &str = "DoclingDocument";
+
+/// docling schema version emitted by [`to_value`]
+pub const SCHEMA_VERSION: &str = "1.3.0";
+
+/// Convert a document to docling JSON
+pub fn to_value(doc: &DoclingDocument) -> Value {
+    let mut writer = Writer::new(doc);
+    let body: Vec<Value> = doc
+        .roots()
+        .filter_map(|(id, _)| writer.item(id, "#/body"))
+        .map(reference)
+        .collect();
+
+    json!({
+        "schema_name": SCHEMA_NAME,
+        "version": SCHEMA_VERSION,
+        "name": doc.name(),
+        "furniture": root("#/furniture", "furniture", Vec::new()),
+        "body": root("#/body", "body", body),
+        "groups": writer.groups,
+        "texts": writer.texts,
+        "pictures": writer.pictures,
+        "tables": writer.tables,
+        "key_value_items": [],
+        "form_items": [],
+        "pages": writer.pages(),
+    })
+}
+
+/// Convert a document to a docling JSON string
+pub fn to_string(doc: &DoclingDocument) -> Result<String, ConversionError> {
+    Ok(serde_json::to_string_pretty(&to_value(doc))?)
+}
+
+/// Read a document from docling JSON
+pub fn from_value(value: &Value) -> Result<DoclingDocument, ConversionError> {
+    match value.get("schema_name").and_then(Value::as_str) {
+        Some(SCHEMA_NAME) | None => {}
+        Some(other) => {
+            return Err(ConversionError::ParseError(format!(
+                "Expected a {} document, got schema '{}'",
+                SCHEMA_NAME, other
+            )))
+        }
+    }
+    let body = value
+        .get("body")
+        .ok_or_else(|| ConversionError::ParseError("docling JSON has no body".to_string()))?;
+
+    let name = value
+        .get("name")
+        .and_then(Value::as_str)
+        .unwrap_or("document");
+    let mut reader = Reader {
+        root: value,
+        doc: DoclingDocument::new(name),
+        visited: HashSet::new(),
+    };
+    for child in children(body) {
+        reader.item(&child, None);
+    }
+
+    let mut doc = reader.doc;
+    let pages = value
+        .get("pages")
+        .and_then(Value::as_object)
+        .map_or(0, Map::len);
+    if pages > 0 {
+        let metadata = doc.metadata().clone().with_page_count(pages);
+        doc = doc.with_document_metadata(metadata);
+    }
+    Ok(doc)
+}
+
+/// Read a document from a docling JSON string
+pub fn from_str(json: &str) -> Result<DoclingDocument, ConversionError> {
+    from_value(&serde_json::from_str(json)?)
+}
+
+fn root(self_ref: &str, layer: &str, children: Vec<Value>) -> Value {
+    json!({
+        "self_ref": self_ref,
+        "children": children,
+        "content_layer": layer,
+        "name": "_root_",
+        "label": "unspecified",
+    })
+}
+
+fn reference(cref: String) -> Value {
+    json!({ "$ref": cref })
+}
+
+/// Builds the item arrays of a docling document
+struct Writer<'a> {
+    doc: &'a DoclingDocument,
+    groups: Vec<Value>,
+    texts: Vec<Value>,
+    pictures: Vec<Value>,
+    tables: Vec<Value>,
+    pages: BTreeSet<usize>,
+}
+
+impl<'a> Writer<'a> {
+    fn new(doc: &'a DoclingDocument) -> Self {
+        Self {
+            doc,
+            groups: Vec::new(),
+            texts: Vec::new(),
+            pictures: Vec::new(),
+            tables: Vec::new(),
+            pages: (1..=doc.metadata().page_count().unwrap_or(0)).collect(),
+        }
+    }
+
+    /// Emit a node and its subtree, returning the node's reference
+    fn item(&mut self, id: NodeId, parent: &str) -> Option<String> {
+        let node = self.doc.node(id)?;
+        let (array, label) = match node.node_type() {
+            NodeType::PageBreak | NodeType::TableRow | NodeType::TableCell => return None,
+            NodeType::Section => ("groups", "section"),
+            NodeType::List if node.is_ordered() => ("groups", "ordered_list"),
+            NodeType::List => ("groups", "list"),
+            // docling has no quote label; the group keeps the quoted blocks together
+            NodeType::Quote => ("groups", "unspecified"),
+            NodeType::Table => ("tables", "table"),
+            NodeType::Picture => ("pictures", "picture"),
+            NodeType::Title => ("texts", "title"),
+            NodeType::Heading => ("texts", "section_header"),
+            NodeType::ListItem => ("texts", "list_item"),
+            NodeType::CodeBlock => ("texts", "code"),
+            NodeType::Formula => ("texts", "formula"),
+            NodeType::Caption => ("texts", "caption"),
+            NodeType::Footnote => ("texts", "footnote"),
+            NodeType::Text | NodeType::Paragraph => ("texts", "text"),
+        };
+
+        // Reserve the slot first so children can point at their parent
+        let index = self.array(array).len();
+        self.array(array).push(Value::Null);
+        let self_ref = format!("#/{}/{}", array, index);
+
+        let mut item = json!({
+            "self_ref": self_ref,
+            "parent": reference(parent.to_string()),
+            "content_layer": "body",
+            "label": label,
+        });
+        let text = node.text_content().unwrap_or_default();
+        match array {
+            "groups" => {
+                item["name"] = json!(label);
+            }
+            "texts" => {
+                item["orig"] = json!(text);
+                item["text"] = json!(text);
+                item["prov"] = self.prov(node, text);
+                if node.node_type() == NodeType::Heading {
+                    item["level"] = json!(node.level().unwrap_or(1));
+                }
+                if node.node_type() == NodeType::ListItem {
+                    let list = node.parent().and_then(|list| self.doc.node(list));
+                    let marker = match list.filter(|list| list.is_ordered()) {
+                        Some(list) => {
+                            let number = list.children().iter().position(|&c| c == id);
+                            format!("{}.", number.unwrap_or(0) + 1)
+                        }
+                        None => "-".to_string(),
+                    };
+                    item["enumerated"] = json!(marker != "-");
+                    item["marker"] = json!(marker);
+                }
+            }
+            "tables" => {
+                item["prov"] = self.prov(node, "");
+                item["data"] = table_data(node.table());
+            }
+            _ => {
+                item["prov"] = self.prov(node, "");
+                if let Some(image) = node.picture().and_then(|p| self.image(p)) {
+                    item["image"] = image;
+                }
+                if !text.is_empty() {
+                    item["annotations"] = json!([{
+                        "kind": "description",
+                        "text": text,
+                        "provenance": "docling-rs",
+                    }]);
+                }
+            }
+        }
+
+        let mut children = Vec::new();
+        if let Some(caption) = node.caption() {
+            let caption_ref = self.caption(caption, &self_ref);
+            item["captions"] = json!([reference(caption_ref.clone())]);
+            children.push(reference(caption_ref));
+        }
+        for &child in node.children() {
+            if let Some(child_ref) = self.item(child, &self_ref) {
+                children.push(reference(child_ref));
+            }
+        }
+        item["children"] = Value::Array(children);
+
+        self.array(array)[index] = item;
+        Some(self_ref)
+    }
+
+    fn array(&mut self, name: &str) -> &mut Vec<Value> {
+        match name {
+            "groups" => &mut self.groups,
+            "tables" => &mut self.tables,
+            "pictures" => &mut self.pictures,
+            _ => &mut self.texts,
+        }
+    }
+
+    /// Emit a caption text under a table or picture
+    fn caption(&mut self, text: &str, parent: &str) -> String {
+        let self_ref = format!("#/texts/{}", self.texts.len());
+        self.texts.push(json!({
+            "self_ref": self_ref,
+            "parent": reference(parent.to_string()),
+            "children": [],
+            "content_layer": "body",
+            "label": "caption",
+            "prov": [],
+            "orig": text,
+            "text": text,
+        }));
+        self_ref
+    }
+
+    /// Provenance entries; docling only records provenance on pages
+    fn prov(&mut self, node: &DocumentNode, text: &str) -> Value {
+        let Some(page) = node.page() else {
+            return json!([]);
+        };
+        self.pages.insert(page);
+
+        let bbox = node
+            .bbox()
+            .copied()
+            .unwrap_or_else(|| BoundingBox::new(0.0, 0.0, 0.0, 0.0));
+        json!([{
+            "page_no": page,
+            "bbox": {
+                "l": bbox.left,
+                "t": bbox.top,
+                "r": bbox.right,
+                "b": bbox.bottom,
+                "coord_origin": "BOTTOMLEFT",
+            },
+            "charspan": [0, text.chars().count()],
+        }])
+    }
+
+    /// Image reference; bytes become a data URI
+    fn image(&self, picture: &PictureItem) -> Option<Value> {
+        let mimetype = picture.mimetype().unwrap_or("image/png");
+        let uri = match picture.image() {
+            ImageRef::File { uri } => uri.clone(),
+            _ => format!(
+                "data:{};base64,{}",
+                mimetype,
+                STANDARD.encode(self.doc.image_data(picture)?)
+            ),
+        };
+        Some(json!({
+            "mimetype": mimetype,
+            "dpi": 72,
+            "size": {
+                "width": picture.width().unwrap_or(0),
+                "height": picture.height().unwrap_or(0),
+            },
+            "uri": uri,
+        }))
+    }
+
+    fn pages(&self) -> Value {
+        let pages: Map<String, Value> = self
+            .pages
+            .iter()
+            .map(|page| {
+                (
+                    page.to_string(),
+                    json!({ "size": { "width": 0.0, "height": 0.0 }, "page_no": page }),
+                )
+            })
+            .collect();
+        Value::Object(pages)
+    }
+}
+
+/// docling `TableData`: one entry per cell with its grid offsets
+fn table_data(table: Option<&TableData>) -> Value {
+    let Some(table) = table else {
+        return json!({ "table_cells": [], "num_rows": 0, "num_cols": 0 });
+    };
+    let headers = table.num_header_rows();
+    let cells: Vec<Value> = table
+        .placed_cells()
+        .into_iter()
+        .map(|(row, col, cell)| {
+            let (row_span, col_span) = (cell.row_span().max(1), cell.col_span().max(1));
+            json!({
+                "text": cell.content(),
+                "row_span": row_span,
+                "col_span": col_span,
+                "start_row_offset_idx": row,
+                "end_row_offset_idx": row + row_span,
+                "start_col_offset_idx": col,
+                "end_col_offset_idx": col + col_span,
+                "column_header": row < headers,
+                "row_header": false,
+                "row_section": false,
+            })
+        })
+        .collect();
+
+    json!({
+        "table_cells": cells,
+        "num_rows": table.num_rows(),
+        "num_cols": table.num_cols(),
+    })
+}
+
+/// Rebuilds a docling-rs document from docling items
+struct Reader<'a> {
+    root: &'a Value,
+    doc: DoclingDocument,
+    /// References already read, to survive cyclic or shared references
+    visited: HashSet<String>,
+}
+
+impl<'a> Reader<'a> {
+    /// Resolve a `#/<array>/<index>` reference
+    fn resolve(&self, cref: &str) -> Option<&'a Value> {
+        let root: &'a Value = self.root;
+        let (array, index) = cref.strip_prefix("#/")?.split_once('/')?;
+        root.get(array)?.get(index.parse::<usize>().ok()?)
+    }
+
+    /// Read the item behind `cref` and its subtree under `parent`
+    fn item(&mut self, cref: &str, parent: Option<NodeId>) {
+        if !self.visited.insert(cref.to_string()) {
+            return;
+        }
+        let Some(item) = self.resolve(cref) else {
+            return;
+        };
+        let array = cref.trim_start_matches("#/").split('/').next();
+        let label = item.get("label").and_then(Value::as_str).unwrap_or("text");
+        if item.get("content_layer").and_then(Value::as_str) == Some("furniture") {
+            return;
+        }
+
+        let node = match array {
+            Some("groups") if matches!(label, "list" | "ordered_list") => {
+                Some(DocumentNode::new(NodeType::List, "").with_ordered(label == "ordered_list"))
+            }
+            Some("groups") if label == "section" => Some(DocumentNode::new(NodeType::Section, "")),
+            // Other groups only organize their children
+            Some("groups") => None,
+            Some("tables") => Some(self.table(item)),
+            Some("pictures") => Some(self.picture(item)),
+            Some("texts") => Some(text_node(item, label)),
+            _ => return,
+        };
+
+        // Captions are folded into their table or picture
+        let captions: Vec<String> = refs(item, "captions");
+        let node = node.map(|mut node| {
+            let caption = captions
+                .iter()
+                .filter_map(|c| self.resolve(c)?.get("text")?.as_str())
+                .collect::<Vec<_>>()
+                .join(" ");
+            if !caption.is_empty() {
+                node = node.with_caption(caption);
+            }
+            with_prov(node, item)
+        });
+        self.visited.extend(captions);
+
+        let id = match node {
+            Some(node) => match parent {
+                Some(parent) => self.doc.add_child(parent, node),
+                None => Some(self.doc.add_node(node)),
+            },
+            None => parent,
+        };
+        for child in children(item) {
+            self.item(&child, id);
+        }
+    }
+
+    fn table(&self, item: &Value) -> DocumentNode {
+        let cells = item
+            .pointer("/data/table_cells")
+            .and_then(Value::as_array)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        let offset = |cell: &Value, key: &str| cell.get(key).and_then(Value::as_u64).unwrap_or(0);
+
+        // Cells by start row; offsets are untrusted, so rows are only
+        // allocated for the cells there are
+        let mut starts: BTreeMap<u64, Vec<(u64, TableCell, bool)>> = BTreeMap::new();
+        for cell in cells {
+            let row = offset(cell, "start_row_offset_idx");
+            let col = offset(cell, "start_col_offset_idx");
+            let row_span = offset(cell, "end_row_offset_idx")
+                .saturating_sub(row)
+                .max(1);
+            let col_span = offset(cell, "end_col_offset_idx")
+                .saturating_sub(col)
+                .max(1);
+            let text = cell.get("text").and_then(Value::as_str).unwrap_or_default();
+            let header = cell.get("column_header").and_then(Value::as_bool) == Some(true);
+            starts.entry(row).or_default().push((
+                col,
+                TableCell::new(text)
+                    .with_row_span(usize::try_from(row_span).unwrap_or(usize::MAX))
+                    .with_col_span(usize::try_from(col_span).unwrap_or(usize::MAX)),
+                header,
+            ));
+        }
+
+        // Rows without cells of their own are kept while a row span covers
+        // them, and dropped otherwise
+        let mut rows: Vec<Vec<(u64, TableCell, bool)>> = Vec::new();
+        let (mut next, mut covered) = (0, 0);
+        for (row, cells) in starts {
+            let gap = row.min(covered).saturating_sub(next);
+            rows.extend((0..gap).map(|_| Vec::new()));
+            next = row.saturating_add(1);
+            covered = cells
+                .iter()
+                .map(|(_, cell, _)| row.saturating_add(cell.row_span() as u64))
+                .fold(covered, u64::max);
+            rows.push(cells);
+        }
+
+        // Leading rows made only of column headers form the header
+        let header_rows = rows
+            .iter()
+            .take_while(|cells| !cells.is_empty() && cells.iter().all(|(_, _, h)| *h))
+            .count();
+        let mut table = TableData::new().with_header_rows(header_rows);
+        for mut cells in rows {
+            cells.sort_by_key(|(col, _, _)| *col);
+            table = table.with_row(TableRow::new(
+                cells.into_iter().map(|(_, cell, _)| cell).collect(),
+            ));
+        }
+
+        let text = table.to_markdown().trim_end().to_string();
+        DocumentNode::new(NodeType::Table, text).with_table(table)
+    }
+
+    fn picture(&mut self, item: &Value) -> DocumentNode {
+        // Alt text from a description annotation (or the newer `meta` field)
+        let description = item
+            .get("annotations")
+            .and_then(Value::as_array)
+            .and_then(|annotations| {
+                annotations
+                    .iter()
+                    .find(|a| a.get("kind").and_then(Value::as_str) == Some("description"))
+            })
+            .and_then(|a| a.get("text"))
+            .or_else(|| item.pointer("/meta/description/text"))
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        let mut node = DocumentNode::new(NodeType::Picture, description);
+
+        if let Some(image) = item.get("image").filter(|i| i.is_object()) {
+            let uri = image.get("uri").and_then(Value::as_str).unwrap_or_default();
+            let picture = match decode_data_uri(uri) {
+                Some(data) => self.doc.store_image(data),
+                None => PictureItem::file(uri),
+            };
+            let picture = match image.get("mimetype").and_then(Value::as_str) {
+                Some(mimetype) => picture.with_mimetype(mimetype),
+                None => picture,
+            };
+            let size = |key: &str| {
+                image
+                    .pointer(&format!("/size/{}", key))
+                    .and_then(Value::as_f64)
+                    .unwrap_or(0.0) as u32
+            };
+            let picture = match (size("width"), size("height")) {
+                (0, _) | (_, 0) => picture,
+                (width, height) => picture.with_size(width, height),
+            };
+            node = node.with_picture(picture);
+        }
+        node
+    }
+}
+
+fn text_node(item: &Value, label: &str) -> DocumentNode {
+    let text = item.get("text").and_then(Value::as_str).unwrap_or_default();
+    let node_type = match label {
+        "title" => NodeType::Title,
+        "section_header" => NodeType::Heading,
+        "list_item" => NodeType::ListItem,
+        "code" => NodeType::CodeBlock,
+        "formula" => NodeType::Formula,
+        "caption" => NodeType::Caption,
+        "footnote" => NodeType::Footnote,
+        _ => NodeType::Paragraph,
+    };
+    let node = DocumentNode::new(node_type, text);
+    match node_type {
+        NodeType::Heading => {
+            let level = item.get("level").and_then(Value::as_u64).unwrap_or(1);
+            node.with_level(level as usize)
+        }
+        NodeType::CodeBlock => match item
+            .get("code_language")
+            .and_then(Value::as_str)
+            .filter(|language| *language != "unknown")
+        {
+            Some(language) => node.with_code_language(language.to_lowercase()),
+            None => node,
+        },
+        _ => node,
+    }
+}
+
+/// Copy the page and bounding box of the first provenance entry
+fn with_prov(mut node: DocumentNode, item: &Value) -> DocumentNode {
+    let Some(prov) = item.pointer("/prov/0") else {
+        return node;
+    };
+    if let Some(page) = prov.get("page_no").and_then(Value::as_u64) {
+        node = node.with_page(page as usize);
+    }
+    if let Some(bbox) = prov.get("bbox") {
+        let coord = |key: &str| bbox.get(key).and_then(Value::as_f64).unwrap_or(0.0);
+        node = node.with_bbox(BoundingBox::new(
+            coord("l"),
+            coord("t"),
+            coord("r"),
+            coord("b"),
+        ));
+    }
+    node
+}
+
+/// References listed under `key`
+fn refs(item: &Value, key: &str) -> Vec<String> {
+    item.get(key)
+        .and_then(Value::as_array)
+        .map(|list| {
+            list.iter()
+                .filter_map(|r| r.get("$ref")?.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn children(item: &Value) -> Vec<String> {
+    refs(item, "children")
+}
+
+/// Decode a base64 `data:` URI
+fn decode_data_uri(uri: &str) -> Option<Vec<u8>> {
+    let (header, data) = uri.strip_prefix("data:")?.split_once(',')?;
+    header
+        .ends_with(";base64")
+        .then(|| STANDARD.decode(data).ok())
+        .flatten()
+}
@@ -0,0 +1,376 @@
+//! DocTags serialization
+//!
+//! DocTags is the tag-based document markup used by docling's vision-language
+//! models (SmolDocling and successors). A document is a `<doctag>` element
+//! holding one element per item, in reading order:
+//!
+//! ```text
+//! <doctag><title>Report</title>
+//! <section_header_level_1>Results</section_header_level_1>
+//! <text>Scores improved.</text>
+//! <unordered_list><list_item>First</list_item>
+//! </unordered_list>
+//! <otsl><ched>Name<ched>Score<nl><fcel>Ann<fcel>0.9<nl><caption>Scores</caption></otsl>
+//! <page_break>
+//! </doctag>
+//! ```
+//!
+//! Tables use OTSL cell tokens: `fcel` (cell with text), `ecel` (empty cell),
+//! `ched` (column header), `lcel`/`ucel`/`xcel` (positions merged with the cell
+//! to the left, above, or both) and `nl` (end of row). Location tokens
+//! (`<loc_N>`) are skipped when parsing and not emitted, since docling-rs does
+//! not track page sizes.
+
+use crate::datamodel::{
+    walk, DoclingDocument, DocumentNode, Flow, NodeId, NodeType, TableCell, TableData, TableRow,
+    TreeEntry, Visitor,
+};
+use crate::error::ConversionError;
+
+/// Serialize a document to DocTags
+pub fn to_string(doc: &DoclingDocument) -> String {
+    let mut writer = Writer {
+        out: String::from("<doctag>"),
+    };
+    walk(doc, &mut writer);
+    writer.out.push_str("</doctag>");
+    writer.out
+}
+
+/// Parse DocTags into a document named `name`
+pub fn from_str(doctags: &str, name: &str) -> Result<DoclingDocument, ConversionError> {
+    let body = doctags.trim();
+    let body = body.strip_prefix("<doctag>").unwrap_or(body);
+    let body = body.strip_suffix("</doctag>").unwrap_or(body);
+
+    let mut doc = DoclingDocument::new(name);
+    let mut list: Option<NodeId> = None;
+    let mut rest = body;
+
+    while let Some(start) = rest.find('<') {
+        let after = &rest[start + 1..];
+        let end = after.find('>').ok_or_else(|| unclosed(after))?;
+        let tag = &after[..end];
+        rest = &after[end + 1..];
+
+        match tag {
+            "page_break" => {
+                doc.add_node(DocumentNode::new(NodeType::PageBreak, ""));
+            }
+            "unordered_list" | "ordered_list" => {
+                let node =
+                    DocumentNode::new(NodeType::List, "").with_ordered(tag == "ordered_list");
+                list = Some(doc.add_node(node));
+            }
+            "/unordered_list" | "/ordered_list" => list = None,
+            _ if tag.starts_with("loc_") => {}
+            _ => {
+                // Unknown standalone tags are skipped
+                let (content, remaining) = match element(rest, tag) {
+                    Ok(element) => element,
+                    Err(e) if is_content_tag(tag) => return Err(e),
+                    Err(_) => continue,
+                };
+                rest = remaining;
+                let node = match tag {
+                    "otsl" => table(content),
+                    "picture" | "chart" => {
+                        let (text, caption) = split_caption(content);
+                        with_caption(DocumentNode::new(NodeType::Picture, text.trim()), caption)
+                    }
+                    _ => DocumentNode::new(text_type(tag), clean(content)),
+                };
+                let node = match tag.strip_prefix("section_header_level_") {
+                    Some(level) => node.with_level(level.parse().unwrap_or(1)),
+                    None => node,
+                };
+                match list.filter(|_| tag == "list_item") {
+                    Some(parent) => doc.add_child(parent, node),
+                    None => Some(doc.add_node(node)),
+                };
+            }
+        }
+    }
+
+    Ok(doc)
+}
+
+fn unclosed(text: &str) -> ConversionError {
+    ConversionError::ParseError(format!(
+        "Unclosed DocTags tag near '{}'",
+        text.chars().take(20).collect::<String>()
+    ))
+}
+
+/// Split the content of element `tag` from the text after its closing tag
+fn element<'a>(rest: &'a str, tag: &str) -> Result<(&'a str, &'a str), ConversionError> {
+    let close = format!("</{}>", tag);
+    let end = rest.find(&close).ok_or_else(|| {
+        ConversionError::ParseError(format!("Missing closing tag {} in DocTags", close))
+    })?;
+    Ok((&rest[..end], &rest[end + close.len()..]))
+}
+
+/// Remove location tokens and surrounding whitespace
+fn clean(content: &str) -> String {
+    let mut text = String::new();
+    let mut rest = content;
+    while let Some(start) = rest.find("<loc_") {
+        text.push_str(&rest[..start]);
+        rest = rest[start..]
+            .find('>')
+            .map_or("", |end| &rest[start + end + 1..]);
+    }
+    text.push_str(rest);
+    text.trim().to_string()
+}
+
+/// Separate a trailing `<caption>` element from element content
+fn split_caption(content: &str) -> (String, Option<String>) {
+    match content.split_once("<caption>") {
+        Some((before, caption)) => (
+            clean(before),
+            Some(clean(caption.trim_end().trim_end_matches("</caption>"))),
+        ),
+        None => (clean(content), None),
+    }
+}
+
+fn with_caption(node: DocumentNode, caption: Option<String>) -> DocumentNode {
+    match caption.filter(|c| !c.is_empty()) {
+        Some(caption) => node.with_caption(caption),
+        None => node,
+    }
+}
+
+fn is_content_tag(tag: &str) -> bool {
+    matches!(
+        tag,
+        "title"
+            | "text"
+            | "list_item"
+            | "code"
+            | "formula"
+            | "caption"
+            | "footnote"
+            | "otsl"
+            | "picture"
+            | "chart"
+    ) || tag.starts_with("section_header_level_")
+}
+
+fn text_type(tag: &str) -> NodeType {
+    match tag {
+        "title" => NodeType::Title,
+        "list_item" => NodeType::ListItem,
+        "code" => NodeType::CodeBlock,
+        "formula" => NodeType::Formula,
+        "caption" => NodeType::Caption,
+        "footnote" => NodeType::Footnote,
+        _ if tag.starts_with("section_header_level_") => NodeType::Heading,
+        _ => NodeType::Paragraph,
+    }
+}
+
+/// Cell token of an OTSL table
+#[derive(Clone, Copy, PartialEq)]
+enum Token {
+    /// Cell with content; `true` for column headers
+    Cell(bool),
+    /// Position merged with the cell to the left
+    Left,
+    /// Position merged with the cell above
+    Up,
+    /// Position merged with the cells to the left and above
+    Both,
+}
+
+/// Build a table node from OTSL content
+fn table(content: &str) -> DocumentNode {
+    let (otsl, caption) = split_caption_raw(content);
+
+    // Tokenize into rows of (token, text)
+    let mut rows: Vec<Vec<(Token, String)>> = vec![Vec::new()];
+    let mut rest = otsl;
+    while let Some(start) = rest.find('<') {
+        let Some(end) = rest[start..].find('>') else {
+            break;
+        };
+        let tag = &rest[start + 1..start + end];
+        rest = &rest[start + end + 1..];
+        let text_end = rest.find('<').unwrap_or(rest.len());
+        let text = rest[..text_end].trim().to_string();
+
+        let token = match tag {
+            "fcel" | "ecel" | "rhed" | "srow" => Token::Cell(false),
+            "ched" => Token::Cell(true),
+            "lcel" => Token::Left,
+            "ucel" => Token::Up,
+            "xcel" => Token::Both,
+            "nl" => {
+                rows.push(Vec::new());
+                continue;
+            }
+            _ => continue,
+        };
+        if let Some(row) = rows.last_mut() {
+            row.push((token, text));
+        }
+    }
+    rows.retain(|row| !row.is_empty());
+
+    let at = |r: usize, c: usize| rows.get(r).and_then(|row| row.get(c)).map(|(t, _)| *t);
+    let mut data = TableData::new();
+    let mut header_rows = 0;
+    for (r, row) in rows.iter().enumerate() {
+        let mut cells = Vec::new();
+        let mut header = true;
+        for (c, (token, text)) in row.iter().enumerate() {
+            let Token::Cell(is_header) = *token else {
+                continue;
+            };
+            header &= is_header;
+            let col_span = 1
+                + (c + 1..row.len())
+                    .take_while(|&c| at(r, c) == Some(Token::Left))
+                    .count();
+            let row_span = 1
+                + (r + 1..rows.len())
+                    .take_while(|&r| at(r, c) == Some(Token::Up))
+                    .count();
+            cells.push(
+                TableCell::new(text.as_str())
+                    .with_col_span(col_span)
+                    .with_row_span(row_span),
+            );
+        }
+        if header && !cells.is_empty() && header_rows == r {
+            header_rows += 1;
+        }
+        data = data.with_row(TableRow::new(cells));
+    }
+    let data = data.with_header_rows(header_rows);
+
+    let text = data.to_markdown().trim_end().to_string();
+    with_caption(
+        DocumentNode::new(NodeType::Table, text).with_table(data),
+        caption,
+    )
+}
+
+/// Like [`split_caption`], without cleaning the content before the caption
+fn split_caption_raw(content: &str) -> (&str, Option<String>) {
+    match content.split_once("<caption>") {
+        Some((before, caption)) => (
+            before,
+            Some(clean(caption.trim_end().trim_end_matches("</caption>"))),
+        ),
+        None => (content, None),
+    }
+}
+
+/// Emits DocTags while walking the document tree
+struct Writer {
+    out: String,
+}
+
+impl Writer {
+    fn element(&mut self, tag: &str, text: &str) {
+        self.out.push_str(&format!("<{}>{}</{}>\n", tag, text, tag));
+    }
+
+    fn caption(node: &DocumentNode) -> String {
+        node.caption()
+            .map(|c| format!("<caption>{}</caption>", c))
+            .unwrap_or_default()
+    }
+}
+
+impl Visitor for Writer {
+    fn enter_node(&mut self, entry: TreeEntry<'_>) -> Flow {
+        match entry.node.node_type() {
+            NodeType::List if entry.node.is_ordered() => self.out.push_str("<ordered_list>"),
+            NodeType::List => self.out.push_str("<unordered_list>"),
+            NodeType::PageBreak => self.out.push_str("<page_break>\n"),
+            // Table structure comes from the table data, not row/cell nodes
+            NodeType::Table => return Flow::SkipChildren,
+            _ => {}
+        }
+        Flow::Continue
+    }
+
+    fn leave_node(&mut self, entry: TreeEntry<'_>) {
+        match entry.node.node_type() {
+            NodeType::List if entry.node.is_ordered() => self.out.push_str("</ordered_list>\n"),
+            NodeType::List => self.out.push_str("</unordered_list>\n"),
+            _ => {}
+        }
+    }
+
+    fn enter_section(&mut self, entry: TreeEntry<'_>) {
+        let text = entry.node.text_content().unwrap_or_default();
+        match entry.node.node_type() {
+            NodeType::Title => self.element("title", text),
+            _ => {
+                let tag = format!("section_header_level_{}", entry.node.level().unwrap_or(1));
+                self.element(&tag, text);
+            }
+        }
+    }
+
+    fn visit_text(&mut self, entry: TreeEntry<'_>) {
+        let tag = match entry.node.node_type() {
+            NodeType::ListItem => "list_item",
+            NodeType::CodeBlock => "code",
+            NodeType::Formula => "formula",
+            NodeType::Caption => "caption",
+            NodeType::Footnote => "footnote",
+            _ => "text",
+        };
+        self.element(tag, entry.node.text_content().unwrap_or_default());
+    }
+
+    fn visit_table(&mut self, entry: TreeEntry<'_>) {
+        let otsl = entry.node.table().map(otsl).unwrap_or_default();
+        let content = format!("{}{}", otsl, Self::caption(entry.node));
+        self.element("otsl", &content);
+    }
+
+    fn visit_picture(&mut self, entry: TreeEntry<'_>) {
+        let text = entry.node.text_content().unwrap_or_default();
+        let content = format!("{}{}", text, Self::caption(entry.node));
+        self.element("picture", &content);
+    }
+}
+
+/// Encode table data as OTSL tokens
+fn otsl(table: &TableData) -> String {
+    let grid = table.grid();
+    let headers = table.num_header_rows();
+    let mut out = String::new();
+
+    for (r, row) in grid.iter().enumerate() {
+        for (c, cell) in row.iter().enumerate() {
+            // Find where the covering cell starts
+            let same = |rr: usize, cc: usize| match (cell, grid[rr][cc]) {
+                (Some(a), Some(b)) => std::ptr::eq(*a, b),
+                _ => false,
+            };
+            let from_left = c > 0 && same(r, c - 1);
+            let from_above = r > 0 && same(r - 1, c);
+            match (cell, from_left, from_above) {
+                (_, true, true) => out.push_str("<xcel>"),
+                (_, true, false) => out.push_str("<lcel>"),
+                (_, false, true) => out.push_str("<ucel>"),
+                (Some(cell), false, false) if !cell.content().is_empty() => {
+                    let tag = if r < headers { "ched" } else { "fcel" };
+                    out.push_str(&format!("<{}>{}", tag, cell.content()));
+                }
+                _ => out.push_str("<ecel>"),
+            }
+        }
+        out.push_str("<nl>");
+    }
+
+    out
+}
@@ -1,58 +1,710 @@
 //! Document representation
 
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use sha2::{Digest, Sha256};
 use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
 
-use crate::datamodel::DocumentNode;
+use crate::datamodel::{
+    query, schema, Annotation, DocumentMetadata, DocumentNode, ImageRef, ImageStore, NodeId,
+    NodeQuery, NodeType, PictureItem, RefItem,
+};
+use crate::error::ConversionError;
 
 /// Main document representation
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// Nodes form a tree: each node has an optional parent and an ordered list of
+/// children (e.g. the paragraphs under a heading). All nodes are also kept in a
+/// flat list in reading order, available through [`nodes`](Self::nodes), so
+/// consumers that do not care about structure can ignore the tree.
+///
+/// Every node gets a stable [`NodeId`] when it is added, and nodes can point at
+/// each other with [`RefItem`] references.
+///
+/// Picture bytes are kept once per distinct image in the document's
+/// [`ImageStore`] and referenced from picture nodes by digest.
+///
+/// Enrichment results (entities, PII, classifications, ...) are kept as
+/// [`Annotation`]s pointing at nodes.
+///
+/// Serialized documents record the [`schema`](crate::datamodel::schema) version
+/// they were written with. [`from_json`](Self::from_json) and
+/// [`from_file`](Self::from_file) load documents saved with
+/// [`to_json`](Self::to_json) or [`to_file`](Self::to_file), migrating those
+/// written by older versions, so converted documents can be re-chunked or
+/// re-exported without converting the source again.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct DoclingDocument {
+    #[serde(default = "schema::current_version")]
+    schema_version: String,
     name: String,
     nodes: Vec<DocumentNode>,
-    metadata: HashMap<String, Value>,
+    #[serde(default)]
+    metadata: DocumentMetadata,
+    #[serde(default, skip_serializing_if = "ImageStore::is_empty")]
+    images: ImageStore,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    annotations: Vec<Annotation>,
 }
 
 impl DoclingDocument {
     /// Create a new document with the given name
     pub fn new(name: impl Into<String>) -> Self {
         Self {
+            schema_version: schema::current_version(),
             name: name.into(),
             nodes: Vec::new(),
-            metadata: HashMap::new(),
+            metadata: DocumentMetadata::new(),
+            images: ImageStore::new(),
+            annotations: Vec::new(),
         }
     }
 
+    /// Load a document from its JSON serialization
+    ///
+    /// Older schema versions are migrated, and the document is
+    /// [validated](schema::validate_document) before it is returned.
+    pub fn from_json(json: &str) -> Result<Self, ConversionError> {
+        let value: Value = serde_json::from_str(json)?;
+        Ok(schema::document_from_value(value)?)
+    }
+
+    /// Load a document from a JSON file written by [`to_file`](Self::to_file)
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, ConversionError> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Err(ConversionError::FileNotFound(path.to_path_buf()));
+        }
+        Self::from_json(&fs::read_to_string(path)?)
+    }
+
+    /// Serialize the document to pretty-printed JSON
+    pub fn to_json(&self) -> Result<String, ConversionError> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Save the document as a JSON file
+    pub fn to_file(&self, path: impl AsRef<Path>) -> Result<(), ConversionError> {
+        fs::write(path, self.to_json()?)?;
+        Ok(())
+    }
+
     /// Get the document name
     pub fn name(&self) -> &str {
         &self.name
     }
 
-    /// Get the document nodes
+    /// Get the schema version the document was created or loaded with
+    pub fn schema_version(&self) -> &str {
+        &self.schema_version
+    }
+
+    /// Get all document nodes in reading order, regardless of nesting
     pub fn nodes(&self) -> &[DocumentNode] {
         &self.nodes
     }
 
     /// Get the document metadata
-    pub fn metadata(&self) -> &HashMap<String, Value> {
+    pub fn metadata(&self) -> &DocumentMetadata {
         &self.metadata
     }
 
-    /// Add metadata to the document
+    /// Get mutable access to the document metadata
+    pub fn metadata_mut(&mut self) -> &mut DocumentMetadata {
+        &mut self.metadata
+    }
+
+    /// Replace the document metadata
+    pub fn with_document_metadata(mut self, metadata: DocumentMetadata) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
+    /// Add an extra metadata entry (one without a typed field) to the document
     pub fn with_metadata(mut self, key: impl Into<String>, value: impl Into<Value>) -> Self {
-        self.metadata.insert(key.into(), value.into());
+        self.metadata.insert(key, value);
         self
     }
 
-    /// Add a node to the document
-    pub fn add_node(&mut self, node: DocumentNode) {
-        self.nodes.push(node);
+    /// Get the document's image store
+    pub fn images(&self) -> &ImageStore {
+        &self.images
+    }
+
+    /// Get mutable access to the document's image store
+    pub fn images_mut(&mut self) -> &mut ImageStore {
+        &mut self.images
+    }
+
+    /// Add image bytes to the image store, returning a picture referencing them
+    pub fn store_image(&mut self, data: Vec<u8>) -> PictureItem {
+        let digest = self.images.insert(data);
+        let picture = PictureItem::stored(digest.as_str());
+        match self.images.get(&digest).and_then(|image| image.mimetype()) {
+            Some(mimetype) => picture.with_mimetype(mimetype),
+            None => picture,
+        }
+    }
+
+    /// Write picture bytes to files in `dir` and link the pictures to them
+    ///
+    /// Each distinct image is written once, as `<prefix>-image-<n>.<ext>`,
+    /// numbered from 1 in reading order, with the extension of the detected
+    /// image type (`bin` if unknown). Pictures with inline or stored bytes then
+    /// reference `<uri_base>/<file name>` (just the file name if `uri_base` is
+    /// empty), keeping their MIME type and size, and the exported images are
+    /// dropped from the image store. Pictures that already reference a file
+    /// are left alone.
+    ///
+    /// Returns the paths of the written files.
+    pub fn export_images(
+        &mut self,
+        dir: &Path,
+        prefix: &str,
+        uri_base: &str,
+    ) -> Result<Vec<PathBuf>, ConversionError> {
+        let mut written = Vec::new();
+        self.link_images(|data, _| {
+            let extension = infer::get(data).map_or("bin", |kind| kind.extension());
+            let name = format!("{}-image-{}.{}", prefix, written.len() + 1, extension);
+            fs::create_dir_all(dir)?;
+            let path = dir.join(&name);
+            fs::write(&path, data)?;
+            written.push(path);
+            Ok(match uri_base.trim_end_matches('/') {
+                "" => name,
+                base => format!("{}/{}", base, name),
+            })
+        })?;
+        Ok(written)
+    }
+
+    /// Embed picture bytes in the pictures as `data:` URIs
+    ///
+    /// Pictures with inline or stored bytes then reference a base64 `data:`
+    /// URI, which Markdown and HTML output show like any other image link, so
+    /// a single output file carries its images. The MIME type is the
+    /// picture's, or the detected one; embedded images are dropped from the
+    /// image store. Pictures that already reference a file are left alone.
+    pub fn embed_images(&mut self) {
+        // Building a data URI cannot fail
+        let _ = self.link_images(|data, mimetype| {
+            let mimetype = mimetype
+                .or_else(|| infer::get(data).map(|kind| kind.mime_type()))
+                .unwrap_or("application/octet-stream");
+            Ok(format!(
+                "data:{};base64,{}",
+                mimetype,
+                STANDARD.encode(data)
+            ))
+        });
+    }
+
+    /// Point every picture with bytes at the URI returned by `uri_for`
+    ///
+    /// `uri_for` gets the bytes and MIME type of each distinct image once, in
+    /// reading order. Relinked images are removed from the image store.
+    fn link_images(
+        &mut self,
+        mut uri_for: impl FnMut(&[u8], Option<&str>) -> Result<String, ConversionError>,
+    ) -> Result<(), ConversionError> {
+        // URI of each linked image, by digest
+        let mut uris: HashMap<String, String> = HashMap::new();
+
+        for i in 0..self.nodes.len() {
+            let (picture, uri) = {
+                let Some(picture) = self.nodes[i].picture() else {
+                    continue;
+                };
+                let Some(data) = self.image_data(picture) else {
+                    continue;
+                };
+                let digest = format!("{:x}", Sha256::digest(data));
+                let uri = match uris.get(&digest) {
+                    Some(uri) => uri.clone(),
+                    None => {
+                        let uri = uri_for(data, picture.mimetype())?;
+                        uris.insert(digest, uri.clone());
+                        uri
+                    }
+                };
+                (picture.clone(), uri)
+            };
+            self.nodes[i].set_picture(picture.with_image(ImageRef::File { uri }));
+        }
+
+        // Every picture showing a linked image now references its URI
+        for digest in uris.keys() {
+            self.images.remove(digest);
+        }
+        Ok(())
+    }
+
+    /// Get the bytes of a picture, if they are inline or in the image store
+    ///
+    /// File references are not loaded and return `None`.
+    pub fn image_data<'a>(&'a self, picture: &'a PictureItem) -> Option<&'a [u8]> {
+        match picture.image() {
+            ImageRef::Inline { data } => Some(data),
+            ImageRef::Stored { digest } => self.images.get(digest).map(|image| image.data()),
+            ImageRef::File { .. } => None,
+        }
+    }
+
+    /// Add an annotation
+    ///
+    /// Returns `None` (and adds nothing) if the annotated node is not a node of
+    /// this document or the annotated range does not fit its text; otherwise
+    /// returns the index of the annotation.
+    pub fn annotate(&mut self, annotation: Annotation) -> Option<usize> {
+        let text = self
+            .node(annotation.node())?
+            .text_content()
+            .unwrap_or_default();
+        annotation.covered_text(text)?;
+        self.annotations.push(annotation);
+        Some(self.annotations.len() - 1)
+    }
+
+    /// Get all annotations, in the order they were added
+    pub fn annotations(&self) -> &[Annotation] {
+        &self.annotations
+    }
+
+    /// Get the annotations of a node
+    pub fn annotations_for(&self, id: NodeId) -> impl Iterator<Item = &Annotation> {
+        self.annotations.iter().filter(move |a| a.node() == id)
+    }
+
+    /// Get the annotations with a label
+    pub fn annotations_labeled<'a>(
+        &'a self,
+        label: &'a str,
+    ) -> impl Iterator<Item = &'a Annotation> {
+        self.annotations.iter().filter(move |a| a.label() == label)
+    }
+
+    /// Remove the annotations a predicate rejects (e.g. those of one source)
+    pub fn retain_annotations(&mut self, keep: impl FnMut(&Annotation) -> bool) {
+        self.annotations.retain(keep);
+    }
+
+    /// Get the text an annotation covers
+    pub fn annotated_text(&self, annotation: &Annotation) -> Option<&str> {
+        let text = self.node(annotation.node())?.text_content()?;
+        annotation.covered_text(text)
+    }
+
+    /// Add a top-level node to the document
+    pub fn add_node(&mut self, node: DocumentNode) -> NodeId {
+        let id = NodeId::new(self.nodes.len());
+        self.nodes.push(node.attach(id, None));
+        id
+    }
+
+    /// Add a node as the last child of `parent`
+    ///
+    /// Returns `None` (and adds nothing) if `parent` is not a node of this document.
+    pub fn add_child(&mut self, parent: NodeId, node: DocumentNode) -> Option<NodeId> {
+        let id = NodeId::new(self.nodes.len());
+        self.nodes.get_mut(parent.index())?.push_child(id);
+        self.nodes.push(node.attach(id, Some(parent)));
+        Some(id)
     }
 
     /// Add multiple nodes to the document
+    ///
+    /// Replaces any existing nodes; the new nodes are all top-level.
     pub fn with_nodes(mut self, nodes: Vec<DocumentNode>) -> Self {
-        self.nodes = nodes;
+        self.nodes = nodes
+            .into_iter()
+            .enumerate()
+            .map(|(i, node)| node.attach(NodeId::new(i), None))
+            .collect();
         self
     }
+
+    /// Get a node by ID
+    pub fn node(&self, id: NodeId) -> Option<&DocumentNode> {
+        self.nodes.get(id.index())
+    }
+
+    pub(crate) fn node_mut(&mut self, id: NodeId) -> Option<&mut DocumentNode> {
+        self.nodes.get_mut(id.index())
+    }
+
+    /// Get the node a reference points at
+    pub fn resolve(&self, reference: &RefItem) -> Option<&DocumentNode> {
+        self.node(reference.node_id()?)
+    }
+
+    /// Iterate over the top-level nodes with their IDs
+    pub fn roots(&self) -> impl Iterator<Item = (NodeId, &DocumentNode)> {
+        self.nodes
+            .iter()
+            .enumerate()
+            .filter(|(_, node)| node.parent().is_none())
+            .map(|(i, node)| (NodeId::new(i), node))
+    }
+
+    /// Iterate over the children of a node with their IDs
+    pub fn children(&self, id: NodeId) -> impl Iterator<Item = (NodeId, &DocumentNode)> {
+        self.node(id)
+            .map(DocumentNode::children)
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|&child| Some((child, self.node(child)?)))
+    }
+
+    /// Get the parent of a node, if it is not a top-level node
+    pub fn parent(&self, id: NodeId) -> Option<(NodeId, &DocumentNode)> {
+        let parent = self.node(id)?.parent()?;
+        Some((parent, self.node(parent)?))
+    }
+
+    /// Iterate over the tree depth-first (pre-order), starting from the roots
+    pub fn depth_first(&self) -> DepthFirst<'_> {
+        let mut stack: Vec<(NodeId, usize)> = self.roots().map(|(id, _)| (id, 0)).collect();
+        stack.reverse();
+        DepthFirst { doc: self, stack }
+    }
+
+    /// Get the nodes matching a query, in reading order
+    pub fn select(&self, query: &NodeQuery) -> Vec<(NodeId, &DocumentNode)> {
+        let mut selected = Vec::new();
+        self.for_each_with_headings(|id, node, headings| {
+            if query.matches(node, headings) {
+                selected.push((id, node));
+            }
+        });
+        selected
+    }
+
+    /// Call `f` for every node in reading order, with the headings in effect
+    /// at the node, outermost first
+    ///
+    /// Headings are derived from heading levels; a heading's own path holds
+    /// the headings above it, not itself.
+    pub(crate) fn for_each_with_headings<'a>(
+        &'a self,
+        mut f: impl FnMut(NodeId, &'a DocumentNode, &[String]),
+    ) {
+        // Headings in effect, outermost first, with their levels
+        let mut levels: Vec<usize> = Vec::new();
+        let mut path: Vec<String> = Vec::new();
+
+        for (i, node) in self.nodes.iter().enumerate() {
+            // A heading closes the sections at its level and below before it
+            // is visited, and opens its own section after
+            let heading = node.node_type() == NodeType::Heading;
+            if heading {
+                let level = node.level().unwrap_or(1);
+                while levels.last().is_some_and(|&l| l >= level) {
+                    levels.pop();
+                    path.pop();
+                }
+            }
+
+            f(NodeId::new(i), node, &path);
+
+            if heading {
+                levels.push(node.level().unwrap_or(1));
+                path.push(node.text_content().unwrap_or_default().trim().to_string());
+            }
+        }
+    }
+
+    /// Group nodes under explicit [`Section`](NodeType::Section) nodes
+    ///
+    /// Returns a new document where every heading and the content up to the
+    /// next heading of the same or a higher level are the children of a
+    /// section node, and sections nest by heading level. The title closes all
+    /// open sections. Lists, tables and other containers keep their children.
+    /// Existing section nodes are dropped and rebuilt, so grouping an already
+    /// grouped document changes nothing.
+    ///
+    /// Nodes are renumbered: references and annotations are updated to the new
+    /// IDs.
+    pub fn group_sections(&self) -> DoclingDocument {
+        let mut grouped = self.empty_copy(self.nodes.len());
+        let mut new_ids: Vec<Option<NodeId>> = vec![None; self.nodes.len()];
+        // Open sections, outermost first, with their levels
+        let mut open: Vec<(usize, NodeId)> = Vec::new();
+
+        for (i, node) in self.nodes.iter().enumerate() {
+            let container = self.container(NodeId::new(i));
+            let parent = match node.node_type() {
+                NodeType::Section => continue,
+                // Content of lists, tables, ... stays with its container
+                _ if container.is_some() => container
+                    .and_then(|container| new_ids.get(container.index()).copied().flatten()),
+                NodeType::Title => {
+                    open.clear();
+                    None
+                }
+                NodeType::Heading => {
+                    let level = node.level().unwrap_or(1);
+                    while open.last().is_some_and(|&(l, _)| l >= level) {
+                        open.pop();
+                    }
+                    let section = DocumentNode::new(NodeType::Section, "").with_level(level);
+                    let id = grouped.push_node(open.last().map(|&(_, id)| id), section);
+                    open.push((level, id));
+                    Some(id)
+                }
+                _ => open.last().map(|&(_, id)| id),
+            };
+            new_ids[i] = Some(grouped.push_node(parent, node.clone()));
+        }
+
+        let new_id = |id: NodeId| new_ids.get(id.index()).copied().flatten();
+        for node in &mut grouped.nodes {
+            node.map_references(new_id);
+        }
+        grouped.annotations = self
+            .annotations
+            .iter()
+            .filter_map(|annotation| {
+                let mut annotation = annotation.clone();
+                annotation.set_node(new_id(annotation.node())?);
+                Some(annotation)
+            })
+            .collect();
+        grouped
+    }
+
+    /// Split the document into parts at headings of `level` and above
+    ///
+    /// Each part starts at such a heading (or at the section node holding it,
+    /// in a [grouped](Self::group_sections) document) and runs up to the next
+    /// one; content before the first heading, such as the title, becomes a
+    /// part of its own. Parts keep the name, metadata and images of the
+    /// document. Nodes are renumbered per part: nodes whose parent falls in an
+    /// earlier part become top-level, and references to nodes in other parts
+    /// are dropped.
+    pub fn split_sections(&self, level: usize) -> Vec<DoclingDocument> {
+        let mut starts = vec![0];
+        for (i, node) in self.nodes.iter().enumerate() {
+            if node.node_type() != NodeType::Heading || node.level().unwrap_or(1) > level {
+                continue;
+            }
+            let start = match self.parent(NodeId::new(i)) {
+                Some((parent, section))
+                    if section.node_type() == NodeType::Section && parent.index() + 1 == i =>
+                {
+                    parent.index()
+                }
+                _ => i,
+            };
+            if starts.last() != Some(&start) {
+                starts.push(start);
+            }
+        }
+        starts.push(self.nodes.len());
+
+        starts
+            .windows(2)
+            .filter(|range| range[0] < range[1])
+            .map(|range| self.part(range[0], range[1]))
+            .collect()
+    }
+
+    /// Copy of the nodes from `start` (inclusive) to `end` (exclusive)
+    fn part(&self, start: usize, end: usize) -> DoclingDocument {
+        let mut part = self.empty_copy(end - start);
+        let new_id = |id: NodeId| {
+            (start..end)
+                .contains(&id.index())
+                .then(|| NodeId::new(id.index() - start))
+        };
+        for node in &self.nodes[start..end] {
+            let parent = node.parent().and_then(new_id);
+            let mut node = node.clone();
+            node.map_references(new_id);
+            part.push_node(parent, node);
+        }
+        part.annotations = self
+            .annotations
+            .iter()
+            .filter_map(|annotation| {
+                let mut annotation = annotation.clone();
+                annotation.set_node(new_id(annotation.node())?);
+                Some(annotation)
+            })
+            .collect();
+        part
+    }
+
+    /// Find a section of a [grouped](Self::group_sections) document
+    ///
+    /// `key` is a heading path (`"Methods > Results"`, see
+    /// [`NodeQuery::heading_path`]) whose headings are compared with the
+    /// section headings either as a whole or by their section number, so
+    /// `"3.2"` finds the section headed "3.2 Results". The path may leave out
+    /// the outer sections. Returns the first matching section in reading order.
+    pub fn section(&self, key: &str) -> Option<NodeId> {
+        let key = query::parse_path(key);
+        if key.is_empty() {
+            return None;
+        }
+        self.nodes
+            .iter()
+            .enumerate()
+            .filter(|(_, node)| node.node_type() == NodeType::Section)
+            .map(|(i, _)| NodeId::new(i))
+            .find(|&id| {
+                let mut path = Vec::new();
+                let mut current = Some(id);
+                while let Some(section) = current {
+                    if path.len() == key.len() {
+                        break;
+                    }
+                    path.push(self.section_heading(section).unwrap_or_default());
+                    current = self
+                        .parent(section)
+                        .filter(|(_, parent)| parent.node_type() == NodeType::Section)
+                        .map(|(parent, _)| parent);
+                }
+                path.len() == key.len()
+                    && key
+                        .iter()
+                        .rev()
+                        .zip(&path)
+                        .all(|(key, heading)| heading_matches(heading, key))
+            })
+    }
+
+    /// Get the full text of a section of a [grouped](Self::group_sections)
+    /// document, heading and subsections included
+    ///
+    /// See [`section`](Self::section) for the accepted keys.
+    pub fn section_text(&self, key: &str) -> Option<String> {
+        self.section(key).map(|id| self.subtree_text(id))
+    }
+
+    /// Get the text of a node and all its descendants, in reading order
+    ///
+    /// Texts are separated by blank lines; nodes without text are skipped.
+    pub fn subtree_text(&self, id: NodeId) -> String {
+        let mut texts = Vec::new();
+        let mut stack = vec![id];
+        while let Some(id) = stack.pop() {
+            let Some(node) = self.node(id) else {
+                continue;
+            };
+            if let Some(text) = node.text_content().filter(|t| !t.trim().is_empty()) {
+                texts.push(text);
+            }
+            stack.extend(node.children().iter().rev());
+        }
+        texts.join("\n\n")
+    }
+
+    /// Get the heading text of a section node
+    fn section_heading(&self, id: NodeId) -> Option<&str> {
+        self.children(id)
+            .next()
+            .filter(|(_, node)| node.node_type() == NodeType::Heading)
+            .and_then(|(_, node)| node.text_content())
+    }
+
+    /// Get the nearest ancestor that is not a heading, title or section
+    fn container(&self, id: NodeId) -> Option<NodeId> {
+        let mut current = self.parent(id);
+        while let Some((parent, node)) = current {
+            if !matches!(
+                node.node_type(),
+                NodeType::Heading | NodeType::Title | NodeType::Section
+            ) {
+                return Some(parent);
+            }
+            current = self.parent(parent);
+        }
+        None
+    }
+
+    /// Document with the same name, metadata and images, but no nodes
+    fn empty_copy(&self, capacity: usize) -> DoclingDocument {
+        DoclingDocument {
+            schema_version: self.schema_version.clone(),
+            name: self.name.clone(),
+            nodes: Vec::with_capacity(capacity),
+            metadata: self.metadata.clone(),
+            images: self.images.clone(),
+            annotations: Vec::new(),
+        }
+    }
+
+    /// Append a node under `parent`, or at the top level
+    fn push_node(&mut self, parent: Option<NodeId>, node: DocumentNode) -> NodeId {
+        let id = NodeId::new(self.nodes.len());
+        let parent = parent.and_then(|parent| {
+            self.nodes.get_mut(parent.index())?.push_child(id);
+            Some(parent)
+        });
+        self.nodes.push(node.attach(id, parent));
+        id
+    }
+}
+
+/// Check a section heading against a heading path component
+///
+/// The component matches the whole heading or its leading section number
+/// (`"3.2"` for "3.2 Results" or "3.2. Results").
+fn heading_matches(heading: &str, key: &str) -> bool {
+    let heading = heading.trim();
+    if heading == key {
+        return true;
+    }
+    let number = heading
+        .split_whitespace()
+        .next()
+        .filter(|n| n.starts_with(|c: char| c.is_ascii_digit()))
+        .filter(|n| n.chars().all(|c| c.is_ascii_digit() || c == '.'))
+        .map(|n| n.trim_end_matches('.'));
+    number.is_some_and(|number| number == key.trim_end_matches('.'))
+}
+
+/// A node visited during tree traversal
+#[derive(Debug, Clone, Copy)]
+pub struct TreeEntry<'a> {
+    /// ID of the node
+    pub id: NodeId,
+    /// Nesting depth (0 for top-level nodes)
+    pub depth: usize,
+    /// The node itself
+    pub node: &'a DocumentNode,
+}
+
+/// Depth-first iterator over a document's node tree
+///
+/// Created by [`DoclingDocument::depth_first`].
+pub struct DepthFirst<'a> {
+    doc: &'a DoclingDocument,
+    stack: Vec<(NodeId, usize)>,
+}
+
+impl<'a> Iterator for DepthFirst<'a> {
+    type Item = TreeEntry<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (id, depth) = self.stack.pop()?;
+            let Some(node) = self.doc.node(id) else {
+                continue;
+            };
+            self.stack.extend(
+                node.children()
+                    .iter()
+                    .rev()
+                    .map(|&child| (child, depth + 1)),
+            );
+            return Some(TreeEntry { id, depth, node });
+        }
+    }
 }
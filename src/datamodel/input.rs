@@ -2,6 +2,7 @@
 
 use crate::InputFormat;
 use serde::{Deserialize, Serialize};
+use std::fs;
 use std::path::PathBuf;
 
 /// Input document representation
@@ -31,6 +32,17 @@ impl InputDocument {
         }
     }
 
+    /// Create from an object of remote storage at `url`, downloaded to `path`
+    pub fn from_remote(url: impl Into<String>, path: PathBuf, format: InputFormat) -> Self {
+        Self {
+            source: DocumentSource::Remote {
+                url: url.into(),
+                path,
+            },
+            format,
+        }
+    }
+
     /// Get the document source
     pub fn source(&self) -> &DocumentSource {
         &self.source
@@ -40,11 +52,41 @@ impl InputDocument {
     pub fn format(&self) -> InputFormat {
         self.format
     }
+
+    /// Get the name of the document: the file name, or the name given with the bytes
+    pub fn name(&self) -> &str {
+        match &self.source {
+            DocumentSource::FilePath(path) | DocumentSource::Remote { path, .. } => path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .unwrap_or_default(),
+            DocumentSource::Bytes { name, .. } => name,
+        }
+    }
+
+    /// Get the size of the source in bytes, if it can be determined
+    pub fn size(&self) -> Option<u64> {
+        match &self.source {
+            DocumentSource::FilePath(path) | DocumentSource::Remote { path, .. } => {
+                fs::metadata(path).ok().map(|m| m.len())
+            }
+            DocumentSource::Bytes { data, .. } => Some(data.len() as u64),
+        }
+    }
 }
 
-/// Document source (file path or bytes)
+/// Document source (file path, bytes or remote object)
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum DocumentSource {
     FilePath(PathBuf),
-    Bytes { data: Vec<u8>, name: String },
+    Bytes {
+        data: Vec<u8>,
+        name: String,
+    },
+    /// An object of remote storage (`s3://`, `gs://`), downloaded to a local
+    /// file; see [`remote`](crate::remote)
+    Remote {
+        url: String,
+        path: PathBuf,
+    },
 }
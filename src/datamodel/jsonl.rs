@@ -0,0 +1,125 @@
+//! JSON Lines node stream
+//!
+//! Writes a document as one JSON object per node, in reading order, so large
+//! corpora can be filtered and aggregated with line-oriented tools (`jq`,
+//! `grep`, `split`, ...) without loading whole documents:
+//!
+//! ```text
+//! {"document":"report.md","id":0,"type":"Heading","text":"Results","headings":[],"level":1}
+//! {"document":"report.md","id":1,"parent":0,"type":"Paragraph","text":"Scores improved.","headings":["Results"]}
+//! ```
+
+use std::io::Write;
+
+use serde::{Deserialize, Serialize};
+
+use crate::datamodel::{
+    BoundingBox, DoclingDocument, DocumentNode, NodeId, NodeType, SourcePosition,
+};
+
+/// One line of the node stream
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NodeRecord {
+    /// Name of the document the node belongs to
+    pub document: String,
+
+    /// Node ID within the document
+    pub id: NodeId,
+
+    /// Parent node ID, for nodes that are not top-level
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent: Option<NodeId>,
+
+    /// Node type
+    #[serde(rename = "type")]
+    pub node_type: NodeType,
+
+    /// Text content
+    pub text: String,
+
+    /// Headings in effect at the node, outermost first (see
+    /// [`NodeQuery::heading_path`](crate::datamodel::NodeQuery::heading_path))
+    pub headings: Vec<String>,
+
+    /// Heading level
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub level: Option<usize>,
+
+    /// Caption of a table or picture
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub caption: Option<String>,
+
+    /// Language (programming language for code blocks)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+
+    /// Page number
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub page: Option<usize>,
+
+    /// Bounding box on the page
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bbox: Option<BoundingBox>,
+
+    /// Position in the source file
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub position: Option<SourcePosition>,
+}
+
+impl NodeRecord {
+    /// Build the record of a node under the given headings
+    pub fn new(
+        doc: &DoclingDocument,
+        id: NodeId,
+        node: &DocumentNode,
+        headings: &[String],
+    ) -> Self {
+        Self {
+            document: doc.name().to_string(),
+            id,
+            parent: node.parent(),
+            node_type: node.node_type(),
+            text: node.text_content().unwrap_or_default().to_string(),
+            headings: headings.to_vec(),
+            level: node.level(),
+            caption: node.caption().map(str::to_string),
+            language: node.language().map(str::to_string),
+            page: node.page(),
+            bbox: node.bbox().copied(),
+            position: node.position().cloned(),
+        }
+    }
+}
+
+/// Write every node of a document as a [`NodeRecord`] line
+///
+/// # Examples
+///
+/// ```ignore
+/// use docling_rs::datamodel::jsonl;
+///
+/// let mut out = std::io::BufWriter::new(std::fs::File::create("nodes.jsonl")?);
+/// for doc in &documents {
+///     jsonl::write_nodes(&mut out, doc)?;
+/// }
+/// ```
+pub fn write_nodes<W: Write>(writer: &mut W, doc: &DoclingDocument) -> std::io::Result<()> {
+    let mut result = Ok(());
+    doc.for_each_with_headings(|id, node, headings| {
+        if result.is_ok() {
+            result = serde_json::to_writer(&mut *writer, &NodeRecord::new(doc, id, node, headings))
+                .map_err(std::io::Error::from)
+                .and_then(|()| writer.write_all(b"\n"));
+        }
+    });
+    result
+}
+
+/// Render every node of a document as a JSON Lines string
+pub fn to_string(doc: &DoclingDocument) -> String {
+    let mut buffer = Vec::new();
+    // Writing to a Vec cannot fail
+    let _ = write_nodes(&mut buffer, doc);
+    // serde_json only emits valid UTF-8
+    String::from_utf8_lossy(&buffer).into_owned()
+}
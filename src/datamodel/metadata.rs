@@ -0,0 +1,306 @@
+//! Document-level metadata
+
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+use crate::datamodel::{DocumentSource, InputDocument};
+use crate::InputFormat;
+
+/// Descriptive metadata of a document
+///
+/// Backends fill in what their format provides: every backend records the
+/// input format and source, paginated formats the page count, and formats with
+/// document properties (PDF info dictionary, HTML `<title>`/`lang`, DOCX core
+/// properties) the title, authors, dates and language. Source files record
+/// their programming language. Entries without a typed field go in the extra
+/// key/value map.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct DocumentMetadata {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    title: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    authors: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    created: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    modified: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    language: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    programming_language: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    source_uri: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    format: Option<InputFormat>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    page_count: Option<usize>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    extra: HashMap<String, Value>,
+}
+
+impl DocumentMetadata {
+    /// Create empty metadata
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create metadata recording the format and source of an input document
+    ///
+    /// The source URI is the file path for file inputs and the URL for
+    /// remote objects; byte inputs have none.
+    pub fn from_input(input: &InputDocument) -> Self {
+        let source_uri = match input.source() {
+            DocumentSource::FilePath(path) => Some(path.display().to_string()),
+            DocumentSource::Remote { url, .. } => Some(url.clone()),
+            DocumentSource::Bytes { .. } => None,
+        };
+
+        Self {
+            source_uri,
+            format: Some(input.format()),
+            ..Self::default()
+        }
+    }
+
+    /// Get the document title
+    pub fn title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+
+    /// Set the document title
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Get the authors
+    pub fn authors(&self) -> &[String] {
+        &self.authors
+    }
+
+    /// Add an author
+    pub fn with_author(mut self, author: impl Into<String>) -> Self {
+        self.authors.push(author.into());
+        self
+    }
+
+    /// Get the creation date
+    pub fn created(&self) -> Option<DateTime<Utc>> {
+        self.created
+    }
+
+    /// Set the creation date
+    pub fn with_created(mut self, created: DateTime<Utc>) -> Self {
+        self.created = Some(created);
+        self
+    }
+
+    /// Get the last modification date
+    pub fn modified(&self) -> Option<DateTime<Utc>> {
+        self.modified
+    }
+
+    /// Set the last modification date
+    pub fn with_modified(mut self, modified: DateTime<Utc>) -> Self {
+        self.modified = Some(modified);
+        self
+    }
+
+    /// Get the document language
+    ///
+    /// An ISO 639-3 code (e.g. `eng`, `por`), as used by node languages.
+    /// Backends reading BCP 47 tags convert them with [`iso639_3`].
+    pub fn language(&self) -> Option<&str> {
+        self.language.as_deref()
+    }
+
+    /// Set the document language (an ISO 639-3 code)
+    pub fn with_language(mut self, language: impl Into<String>) -> Self {
+        self.language = Some(language.into());
+        self
+    }
+
+    /// Get the programming language of a source file (e.g. `rust`)
+    pub fn programming_language(&self) -> Option<&str> {
+        self.programming_language.as_deref()
+    }
+
+    /// Set the programming language
+    pub fn with_programming_language(mut self, language: impl Into<String>) -> Self {
+        self.programming_language = Some(language.into());
+        self
+    }
+
+    /// Get the URI or path the document was read from
+    pub fn source_uri(&self) -> Option<&str> {
+        self.source_uri.as_deref()
+    }
+
+    /// Set the source URI
+    pub fn with_source_uri(mut self, uri: impl Into<String>) -> Self {
+        self.source_uri = Some(uri.into());
+        self
+    }
+
+    /// Get the input format
+    pub fn format(&self) -> Option<InputFormat> {
+        self.format
+    }
+
+    /// Set the input format
+    pub fn with_format(mut self, format: InputFormat) -> Self {
+        self.format = Some(format);
+        self
+    }
+
+    /// Get the number of pages (paginated formats)
+    pub fn page_count(&self) -> Option<usize> {
+        self.page_count
+    }
+
+    /// Set the number of pages
+    pub fn with_page_count(mut self, count: usize) -> Self {
+        self.page_count = Some(count);
+        self
+    }
+
+    /// Get an extra entry
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.extra.get(key)
+    }
+
+    /// Get all extra entries
+    pub fn extra(&self) -> &HashMap<String, Value> {
+        &self.extra
+    }
+
+    /// Set an extra entry
+    pub fn insert(&mut self, key: impl Into<String>, value: impl Into<Value>) {
+        self.extra.insert(key.into(), value.into());
+    }
+}
+
+/// ISO 639-1 codes and the ISO 639-3 code used for them
+///
+/// Covers the languages of the language detector, plus a few more common in
+/// document properties. Macrolanguages map to the individual language the
+/// detector reports (`zh` to Mandarin, `fa` to Iranian Persian, `no` to
+/// Bokmål).
+const ISO_639: &[(&str, &str)] = &[
+    ("af", "afr"),
+    ("ak", "aka"),
+    ("am", "amh"),
+    ("ar", "ara"),
+    ("az", "aze"),
+    ("be", "bel"),
+    ("bg", "bul"),
+    ("bn", "ben"),
+    ("ca", "cat"),
+    ("cs", "ces"),
+    ("cy", "cym"),
+    ("da", "dan"),
+    ("de", "deu"),
+    ("el", "ell"),
+    ("en", "eng"),
+    ("eo", "epo"),
+    ("es", "spa"),
+    ("et", "est"),
+    ("eu", "eus"),
+    ("fa", "pes"),
+    ("fi", "fin"),
+    ("fr", "fra"),
+    ("ga", "gle"),
+    ("gl", "glg"),
+    ("gu", "guj"),
+    ("he", "heb"),
+    ("hi", "hin"),
+    ("hr", "hrv"),
+    ("hu", "hun"),
+    ("hy", "hye"),
+    ("id", "ind"),
+    ("is", "isl"),
+    ("it", "ita"),
+    ("ja", "jpn"),
+    ("jv", "jav"),
+    ("ka", "kat"),
+    ("km", "khm"),
+    ("kn", "kan"),
+    ("ko", "kor"),
+    ("la", "lat"),
+    ("lt", "lit"),
+    ("lv", "lav"),
+    ("mk", "mkd"),
+    ("ml", "mal"),
+    ("mr", "mar"),
+    ("ms", "msa"),
+    ("my", "mya"),
+    ("nb", "nob"),
+    ("ne", "nep"),
+    ("nl", "nld"),
+    ("no", "nob"),
+    ("or", "ori"),
+    ("pa", "pan"),
+    ("pl", "pol"),
+    ("pt", "por"),
+    ("ro", "ron"),
+    ("ru", "rus"),
+    ("si", "sin"),
+    ("sk", "slk"),
+    ("sl", "slv"),
+    ("sn", "sna"),
+    ("sq", "sqi"),
+    ("sr", "srp"),
+    ("sv", "swe"),
+    ("sw", "swa"),
+    ("ta", "tam"),
+    ("te", "tel"),
+    ("th", "tha"),
+    ("tk", "tuk"),
+    ("tl", "tgl"),
+    ("tr", "tur"),
+    ("uk", "ukr"),
+    ("ur", "urd"),
+    ("uz", "uzb"),
+    ("vi", "vie"),
+    ("yi", "yid"),
+    ("zh", "cmn"),
+    ("zu", "zul"),
+];
+
+/// Convert a BCP 47 language tag (`en-GB`, `pt_BR`, `deu`) to an ISO 639-3 code
+///
+/// Only the primary language subtag is kept. Two-letter codes are mapped to
+/// their three-letter equivalent and three-letter codes are kept as they are;
+/// unknown two-letter codes, `und` and malformed tags give `None`.
+pub fn iso639_3(tag: &str) -> Option<String> {
+    let primary = tag
+        .trim()
+        .split(['-', '_'])
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    if !primary.bytes().all(|b| b.is_ascii_lowercase()) {
+        return None;
+    }
+    match primary.len() {
+        2 => ISO_639
+            .iter()
+            .find(|(short, _)| *short == primary)
+            .map(|(_, code)| code.to_string()),
+        3 if primary != "und" => Some(primary),
+        _ => None,
+    }
+}
+
+/// The shortest BCP 47 tag for an ISO 639-3 code (`eng` to `en`)
+///
+/// Codes without a two-letter equivalent are valid tags as they are.
+pub fn bcp47(code: &str) -> &str {
+    ISO_639
+        .iter()
+        .find(|(_, long)| *long == code)
+        .map_or(code, |(short, _)| short)
+}
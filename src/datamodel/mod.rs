@@ -1,16 +1,40 @@
 //! Data model types for unified document representation
 
+pub mod annotation;
+pub mod docling_json;
+pub mod doctags;
 pub mod document;
 pub mod input;
+pub mod jsonl;
+pub mod metadata;
 pub mod node;
+pub mod offsets;
+pub mod picture;
+pub mod query;
 pub mod result;
+pub mod schema;
 pub mod table;
 pub mod text;
+pub mod visit;
+mod xlsx;
+pub mod xml;
 
 // Re-exports
-pub use document::DoclingDocument;
+pub use annotation::Annotation;
+pub use document::{DepthFirst, DoclingDocument, TreeEntry};
 pub use input::{DocumentSource, InputDocument};
-pub use node::{DocumentNode, NodeItem, NodeMetadata, NodeType, SourcePosition};
-pub use result::{ConversionMetrics, ConversionResult, ConversionStatus};
-pub use table::{TableCell, TableData, TableMetadata, TableRow};
-pub use text::{Formatting, TextItem};
+pub use metadata::DocumentMetadata;
+pub use node::{
+    BoundingBox, DocumentNode, NodeId, NodeItem, NodeMetadata, NodeType, RefItem, SourcePosition,
+};
+pub use offsets::{OffsetMap, OffsetSegment};
+pub use picture::{ImageRef, ImageStore, PictureItem, StoredImage};
+pub use query::NodeQuery;
+pub use result::{
+    ConversionMetrics, ConversionResult, ConversionStatus, ConversionWarning, StageTimings,
+    TimedStage,
+};
+pub use schema::SchemaError;
+pub use table::{ColumnAlignment, TableCell, TableData, TableMetadata, TableRow};
+pub use text::{Formatting, TextItem, TextSpan};
+pub use visit::{walk, walk_node, Flow, Visitor};
@@ -1,21 +1,158 @@
 //! Document node types
 
+use super::offsets::OffsetMap;
+use super::picture::PictureItem;
+use super::table::TableData;
+use super::text::TextSpan;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+/// Identifier of a node within its [`DoclingDocument`](crate::datamodel::DoclingDocument)
+///
+/// IDs are assigned by the document when nodes are added and index its flat
+/// node list. Nodes are only ever appended, so an ID keeps designating the same
+/// node for the lifetime of the document, including across serialization.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize, JsonSchema,
+)]
+#[serde(transparent)]
+pub struct NodeId(usize);
+
+impl NodeId {
+    pub(crate) fn new(index: usize) -> Self {
+        Self(index)
+    }
+
+    /// Position of the node in the document's flat node list
+    pub fn index(self) -> usize {
+        self.0
+    }
+}
+
+/// Prefix of node references; a JSON pointer into the serialized node list
+const NODE_REF_PREFIX: &str = "#/nodes/";
+
+/// Reference to another node of the same document
+///
+/// Serializes as `{"$ref": "#/nodes/<index>"}`, a JSON pointer to the node in the
+/// serialized document, so references stay valid when documents are stored and
+/// patched incrementally. Resolve with
+/// [`DoclingDocument::resolve`](crate::datamodel::DoclingDocument::resolve).
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+pub struct RefItem {
+    #[serde(rename = "$ref")]
+    cref: String,
+}
+
+impl RefItem {
+    /// Create a reference to a node
+    pub fn new(id: NodeId) -> Self {
+        Self {
+            cref: format!("{}{}", NODE_REF_PREFIX, id.index()),
+        }
+    }
+
+    /// Get the reference as a JSON pointer string
+    pub fn as_str(&self) -> &str {
+        &self.cref
+    }
+
+    /// Get the referenced node ID, if the reference points at a node
+    pub fn node_id(&self) -> Option<NodeId> {
+        self.cref
+            .strip_prefix(NODE_REF_PREFIX)?
+            .parse()
+            .ok()
+            .map(NodeId)
+    }
+}
+
+impl From<NodeId> for RefItem {
+    fn from(id: NodeId) -> Self {
+        Self::new(id)
+    }
+}
+
 /// Document node
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct DocumentNode {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    id: Option<NodeId>,
     item: NodeItem,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    parent: Option<NodeId>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    children: Vec<NodeId>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    references: Vec<RefItem>,
 }
 
 impl DocumentNode {
     /// Create a new document node
     pub fn new(node_type: NodeType, text: impl Into<String>) -> Self {
         Self {
+            id: None,
             item: NodeItem::new(node_type, text),
+            parent: None,
+            children: Vec::new(),
+            references: Vec::new(),
         }
     }
 
+    /// Get the node's ID, assigned when it is added to a document
+    pub fn id(&self) -> Option<NodeId> {
+        self.id
+    }
+
+    /// Get a reference to this node, once it belongs to a document
+    pub fn self_ref(&self) -> Option<RefItem> {
+        self.id.map(RefItem::new)
+    }
+
+    /// Get the parent node, if this is not a top-level node
+    pub fn parent(&self) -> Option<NodeId> {
+        self.parent
+    }
+
+    /// Get the child nodes, in reading order
+    pub fn children(&self) -> &[NodeId] {
+        &self.children
+    }
+
+    /// Get the nodes this node refers to (captions, footnotes, cited tables, ...)
+    pub fn references(&self) -> &[RefItem] {
+        &self.references
+    }
+
+    /// Add a reference to another node
+    pub fn with_reference(mut self, reference: impl Into<RefItem>) -> Self {
+        self.references.push(reference.into());
+        self
+    }
+
+    /// Assign the node's place in a document, dropping any previous tree links
+    pub(crate) fn attach(mut self, id: NodeId, parent: Option<NodeId>) -> Self {
+        self.id = Some(id);
+        self.parent = parent;
+        self.children.clear();
+        self
+    }
+
+    pub(crate) fn push_child(&mut self, child: NodeId) {
+        self.children.push(child);
+    }
+
+    /// Repoint references at renumbered nodes, dropping those without a new ID
+    pub(crate) fn map_references(&mut self, map: impl Fn(NodeId) -> Option<NodeId>) {
+        self.references = std::mem::take(&mut self.references)
+            .into_iter()
+            .filter_map(|reference| match reference.node_id() {
+                Some(id) => map(id).map(RefItem::new),
+                None => Some(reference),
+            })
+            .collect();
+    }
+
     /// Get the text content
     pub fn text_content(&self) -> Option<&str> {
         self.item.text_content()
@@ -26,6 +163,17 @@ impl DocumentNode {
         self.item.node_type()
     }
 
+    /// Get the provenance metadata (source position, page, bounding box)
+    pub fn metadata(&self) -> &NodeMetadata {
+        self.item.metadata()
+    }
+
+    /// Set the provenance metadata
+    pub fn with_metadata(mut self, metadata: NodeMetadata) -> Self {
+        self.item = self.item.with_metadata(metadata);
+        self
+    }
+
     /// Get the source position
     pub fn position(&self) -> Option<&SourcePosition> {
         self.item.position()
@@ -36,14 +184,193 @@ impl DocumentNode {
         self.item = self.item.with_position(position);
         self
     }
+
+    /// Get the heading level (1 = top level), if this node is a heading
+    pub fn level(&self) -> Option<usize> {
+        self.item.level()
+    }
+
+    /// Set the heading level
+    pub fn with_level(mut self, level: usize) -> Self {
+        self.item = self.item.with_level(level);
+        self
+    }
+
+    /// Get the programming language of a code block (e.g. `rust`), if known
+    pub fn code_language(&self) -> Option<&str> {
+        self.item.code_language()
+    }
+
+    /// Set the programming language of a code block
+    pub fn with_code_language(mut self, language: impl Into<String>) -> Self {
+        self.item = self.item.with_code_language(language);
+        self
+    }
+
+    /// Check whether this is a numbered list (`1.`, `2.`, ...) rather than a
+    /// bulleted one
+    pub fn is_ordered(&self) -> bool {
+        self.item.is_ordered()
+    }
+
+    /// Mark a list as numbered or bulleted
+    pub fn with_ordered(mut self, ordered: bool) -> Self {
+        self.item = self.item.with_ordered(ordered);
+        self
+    }
+
+    /// Get the page number (1-based) this node was extracted from
+    pub fn page(&self) -> Option<usize> {
+        self.item.page()
+    }
+
+    /// Set the page number (1-based)
+    pub fn with_page(mut self, page: usize) -> Self {
+        self.item = self.item.with_page(page);
+        self
+    }
+
+    /// Get the bounding box on the page, if known
+    pub fn bbox(&self) -> Option<&BoundingBox> {
+        self.item.bbox()
+    }
+
+    /// Get the language of the node text (ISO 639-3 code, e.g. `eng`), if known
+    pub fn language(&self) -> Option<&str> {
+        self.item.language()
+    }
+
+    /// Set the language of the node text
+    pub fn with_language(mut self, language: impl Into<String>) -> Self {
+        self.item = self.item.with_language(language);
+        self
+    }
+
+    /// Get the map from text offsets to source offsets, if the text is not a
+    /// verbatim slice of the source
+    pub fn offsets(&self) -> Option<&OffsetMap> {
+        self.item.offsets()
+    }
+
+    /// Set the map from text offsets to source offsets
+    pub fn with_offsets(mut self, offsets: OffsetMap) -> Self {
+        self.item = self.item.with_offsets(offsets);
+        self
+    }
+
+    /// Map a byte offset of the node text to a byte offset of the source file
+    ///
+    /// Uses the offset map when there is one. Otherwise the text must be a
+    /// verbatim slice of the source position (same length), and `None` is
+    /// returned when it is not or the node has no position.
+    pub fn source_offset(&self, offset: usize) -> Option<usize> {
+        match self.offsets() {
+            Some(offsets) => offsets.to_source(offset),
+            None => self
+                .verbatim_source()
+                .map(|(source, len)| source + offset.min(len)),
+        }
+    }
+
+    /// Map a byte range of the node text to the source range it covers
+    pub fn source_range(&self, start: usize, end: usize) -> Option<(usize, usize)> {
+        match self.offsets() {
+            Some(offsets) => offsets.source_range(start, end),
+            None => self
+                .verbatim_source()
+                .map(|(source, len)| (source + start.min(len), source + end.min(len))),
+        }
+    }
+
+    /// Get the source start and length of the text, if it is a verbatim slice
+    fn verbatim_source(&self) -> Option<(usize, usize)> {
+        let position = self.position()?;
+        let len = self.text_content().map_or(0, str::len);
+        (position.end_offset() - position.start_offset() == len)
+            .then_some((position.start_offset(), len))
+    }
+
+    pub(crate) fn set_picture(&mut self, picture: PictureItem) {
+        self.item.picture = Some(picture);
+    }
+
+    pub(crate) fn set_language(&mut self, language: impl Into<String>) {
+        let metadata = std::mem::take(&mut self.item.metadata);
+        self.item.metadata = metadata.with_language(language);
+    }
+
+    /// Set the bounding box on the page
+    pub fn with_bbox(mut self, bbox: BoundingBox) -> Self {
+        self.item = self.item.with_bbox(bbox);
+        self
+    }
+
+    /// Get the caption of a table or figure, if any
+    pub fn caption(&self) -> Option<&str> {
+        self.item.caption()
+    }
+
+    /// Set the caption of a table or figure
+    pub fn with_caption(mut self, caption: impl Into<String>) -> Self {
+        self.item = self.item.with_caption(caption);
+        self
+    }
+
+    /// Get the inline formatting spans (bold, links, ...) over the text content
+    pub fn spans(&self) -> &[TextSpan] {
+        self.item.spans()
+    }
+
+    /// Add an inline formatting span
+    pub fn with_span(mut self, span: TextSpan) -> Self {
+        self.item = self.item.with_span(span);
+        self
+    }
+
+    /// Get the image payload of a picture node
+    pub fn picture(&self) -> Option<&PictureItem> {
+        self.item.picture()
+    }
+
+    /// Set the image payload of a picture node
+    pub fn with_picture(mut self, picture: PictureItem) -> Self {
+        self.item = self.item.with_picture(picture);
+        self
+    }
+
+    /// Get the cell structure of a table node
+    pub fn table(&self) -> Option<&TableData> {
+        self.item.table()
+    }
+
+    /// Set the cell structure of a table node
+    pub fn with_table(mut self, table: TableData) -> Self {
+        self.item = self.item.with_table(table);
+        self
+    }
 }
 
 /// Node item
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct NodeItem {
     node_type: NodeType,
     text_content: Option<String>,
-    position: Option<SourcePosition>,
+    #[serde(flatten)]
+    metadata: NodeMetadata,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    level: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    code_language: Option<String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    ordered: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    caption: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    spans: Vec<TextSpan>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    picture: Option<PictureItem>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    table: Option<TableData>,
 }
 
 impl NodeItem {
@@ -52,7 +379,14 @@ impl NodeItem {
         Self {
             node_type,
             text_content: Some(text.into()),
-            position: None,
+            metadata: NodeMetadata::new(),
+            level: None,
+            code_language: None,
+            ordered: false,
+            caption: None,
+            spans: Vec::new(),
+            picture: None,
+            table: None,
         }
     }
 
@@ -66,26 +400,258 @@ impl NodeItem {
         self.text_content.as_deref()
     }
 
+    /// Get the provenance metadata
+    pub fn metadata(&self) -> &NodeMetadata {
+        &self.metadata
+    }
+
+    /// Set the provenance metadata
+    pub fn with_metadata(mut self, metadata: NodeMetadata) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
     /// Get the source position
     pub fn position(&self) -> Option<&SourcePosition> {
-        self.position.as_ref()
+        self.metadata.position()
     }
 
     /// Set the source position
     pub fn with_position(mut self, position: SourcePosition) -> Self {
-        self.position = Some(position);
+        self.metadata = self.metadata.with_position(position);
+        self
+    }
+
+    /// Get the heading level
+    pub fn level(&self) -> Option<usize> {
+        self.level
+    }
+
+    /// Set the heading level
+    pub fn with_level(mut self, level: usize) -> Self {
+        self.level = Some(level);
+        self
+    }
+
+    /// Get the programming language of a code block
+    pub fn code_language(&self) -> Option<&str> {
+        self.code_language.as_deref()
+    }
+
+    /// Set the programming language of a code block
+    pub fn with_code_language(mut self, language: impl Into<String>) -> Self {
+        self.code_language = Some(language.into());
+        self
+    }
+
+    /// Check whether this is a numbered list
+    pub fn is_ordered(&self) -> bool {
+        self.ordered
+    }
+
+    /// Mark a list as numbered or bulleted
+    pub fn with_ordered(mut self, ordered: bool) -> Self {
+        self.ordered = ordered;
+        self
+    }
+
+    /// Get the page number (1-based)
+    pub fn page(&self) -> Option<usize> {
+        self.metadata.page()
+    }
+
+    /// Set the page number (1-based)
+    pub fn with_page(mut self, page: usize) -> Self {
+        self.metadata = self.metadata.with_page(page);
+        self
+    }
+
+    /// Get the bounding box
+    pub fn bbox(&self) -> Option<&BoundingBox> {
+        self.metadata.bbox()
+    }
+
+    /// Get the language of the text
+    pub fn language(&self) -> Option<&str> {
+        self.metadata.language()
+    }
+
+    /// Get the map from text offsets to source offsets
+    pub fn offsets(&self) -> Option<&OffsetMap> {
+        self.metadata.offsets()
+    }
+
+    /// Set the map from text offsets to source offsets
+    pub fn with_offsets(mut self, offsets: OffsetMap) -> Self {
+        self.metadata = self.metadata.with_offsets(offsets);
+        self
+    }
+
+    /// Set the language of the text
+    pub fn with_language(mut self, language: impl Into<String>) -> Self {
+        self.metadata = self.metadata.with_language(language);
+        self
+    }
+
+    /// Set the bounding box
+    pub fn with_bbox(mut self, bbox: BoundingBox) -> Self {
+        self.metadata = self.metadata.with_bbox(bbox);
+        self
+    }
+
+    /// Get the caption
+    pub fn caption(&self) -> Option<&str> {
+        self.caption.as_deref()
+    }
+
+    /// Set the caption
+    pub fn with_caption(mut self, caption: impl Into<String>) -> Self {
+        self.caption = Some(caption.into());
+        self
+    }
+
+    /// Get the inline formatting spans over the text content
+    pub fn spans(&self) -> &[TextSpan] {
+        &self.spans
+    }
+
+    /// Add an inline formatting span
+    pub fn with_span(mut self, span: TextSpan) -> Self {
+        self.spans.push(span);
+        self
+    }
+
+    /// Get the image payload
+    pub fn picture(&self) -> Option<&PictureItem> {
+        self.picture.as_ref()
+    }
+
+    /// Set the image payload
+    pub fn with_picture(mut self, picture: PictureItem) -> Self {
+        self.picture = Some(picture);
+        self
+    }
+
+    /// Get the table cell structure
+    pub fn table(&self) -> Option<&TableData> {
+        self.table.as_ref()
+    }
+
+    /// Set the table cell structure
+    pub fn with_table(mut self, table: TableData) -> Self {
+        self.table = Some(table);
         self
     }
 }
 
-/// Node metadata
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Node provenance: where a node came from in the source document
+///
+/// Every backend records provenance in the same shape. Text-based formats fill
+/// in the source position; paginated formats add the page and bounding box.
+/// For PDF, the position is a range in the document's extracted text (pages
+/// joined in order, each followed by a newline).
+///
+/// The language of the node text is recorded here as well, by
+/// [`LanguageDetector`](crate::pipeline::LanguageDetector) or by backends whose
+/// format declares it.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct NodeMetadata {
-    // Placeholder - will be implemented in T014
+    #[serde(default)]
+    position: Option<SourcePosition>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    page: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    bbox: Option<BoundingBox>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    language: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    offsets: Option<OffsetMap>,
+}
+
+impl NodeMetadata {
+    /// Create empty provenance
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the source position (byte range and lines)
+    pub fn position(&self) -> Option<&SourcePosition> {
+        self.position.as_ref()
+    }
+
+    /// Set the source position
+    pub fn with_position(mut self, position: SourcePosition) -> Self {
+        self.position = Some(position);
+        self
+    }
+
+    /// Get the page number (1-based)
+    pub fn page(&self) -> Option<usize> {
+        self.page
+    }
+
+    /// Set the page number (1-based)
+    pub fn with_page(mut self, page: usize) -> Self {
+        self.page = Some(page);
+        self
+    }
+
+    /// Get the bounding box on the page
+    pub fn bbox(&self) -> Option<&BoundingBox> {
+        self.bbox.as_ref()
+    }
+
+    /// Set the bounding box on the page
+    pub fn with_bbox(mut self, bbox: BoundingBox) -> Self {
+        self.bbox = Some(bbox);
+        self
+    }
+
+    /// Get the language of the node text (ISO 639-3 code, e.g. `eng`)
+    pub fn language(&self) -> Option<&str> {
+        self.language.as_deref()
+    }
+
+    /// Set the language of the node text
+    pub fn with_language(mut self, language: impl Into<String>) -> Self {
+        self.language = Some(language.into());
+        self
+    }
+
+    /// Get the map from text offsets to source offsets
+    ///
+    /// Only set when the text is not a verbatim slice of the source position.
+    pub fn offsets(&self) -> Option<&OffsetMap> {
+        self.offsets.as_ref()
+    }
+
+    /// Set the map from text offsets to source offsets
+    ///
+    /// The identity map of the source position is not stored, as the position
+    /// already describes it; set the position first.
+    pub fn with_offsets(mut self, offsets: OffsetMap) -> Self {
+        let identity = self.position.as_ref().is_some_and(|position| {
+            offsets.is_identity(
+                position.start_offset(),
+                position.end_offset() - position.start_offset(),
+            )
+        });
+        self.offsets = (!identity).then_some(offsets);
+        self
+    }
+
+    /// Check whether no provenance is recorded
+    pub fn is_empty(&self) -> bool {
+        self.position.is_none()
+            && self.page.is_none()
+            && self.bbox.is_none()
+            && self.language.is_none()
+            && self.offsets.is_none()
+    }
 }
 
 /// Source position
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
 pub struct SourcePosition {
     start_offset: usize,
     end_offset: usize,
@@ -125,15 +691,90 @@ impl SourcePosition {
     }
 }
 
+/// Rectangle on a page, in PDF points
+///
+/// Uses the PDF coordinate system: the origin is the bottom-left corner of the
+/// page, so `top >= bottom`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub struct BoundingBox {
+    /// Left x coordinate
+    pub left: f64,
+    /// Top y coordinate
+    pub top: f64,
+    /// Right x coordinate
+    pub right: f64,
+    /// Bottom y coordinate
+    pub bottom: f64,
+}
+
+impl BoundingBox {
+    /// Create a new bounding box
+    pub fn new(left: f64, top: f64, right: f64, bottom: f64) -> Self {
+        Self {
+            left,
+            top,
+            right,
+            bottom,
+        }
+    }
+
+    /// Width of the box
+    pub fn width(&self) -> f64 {
+        self.right - self.left
+    }
+
+    /// Height of the box
+    pub fn height(&self) -> f64 {
+        self.top - self.bottom
+    }
+
+    /// Smallest box containing both boxes
+    pub fn union(&self, other: &BoundingBox) -> BoundingBox {
+        BoundingBox {
+            left: self.left.min(other.left),
+            top: self.top.max(other.top),
+            right: self.right.max(other.right),
+            bottom: self.bottom.min(other.bottom),
+        }
+    }
+}
+
 /// Node type enumeration
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
 pub enum NodeType {
+    /// Unstructured text (e.g. a block of a PDF page)
     Text,
+    /// Document title
+    Title,
+    /// Section heading, with a level
     Heading,
+    /// Section container, with the level of its heading; the heading is its
+    /// first child and the section content follows
+    Section,
+    /// Paragraph of body text
     Paragraph,
+    /// List container; its items are its children
     List,
+    /// List item; a nested list is a child of its item
     ListItem,
+    /// Block quote container; the quoted blocks are its children
+    Quote,
+    /// Table
     Table,
+    /// Table row
     TableRow,
+    /// Table cell
     TableCell,
+    /// Source code, kept verbatim
+    CodeBlock,
+    /// Mathematical formula
+    Formula,
+    /// Caption of a table or figure
+    Caption,
+    /// Footnote text
+    Footnote,
+    /// Image or figure; the text is its alternative text
+    Picture,
+    /// Boundary between two pages
+    PageBreak,
 }
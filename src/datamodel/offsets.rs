@@ -0,0 +1,165 @@
+//! Mapping from node text offsets back to source bytes
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// A run of text copied verbatim from the source
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct OffsetSegment {
+    /// Start byte offset in the text
+    pub text: usize,
+    /// Start byte offset in the source file
+    pub source: usize,
+    /// Length in bytes
+    pub len: usize,
+}
+
+/// Maps byte offsets of a text to byte offsets of the source file it came from
+///
+/// Node text is not always a verbatim slice of the input: backends trim
+/// indentation, join lines with `\n` whatever the source line endings, and
+/// drop markup. The map lists the runs of text that were copied verbatim, in
+/// text order. Offsets between runs (separators the backend inserted) map to
+/// the source position right after the preceding run.
+///
+/// # Examples
+///
+/// ```ignore
+/// use docling_rs::datamodel::OffsetMap;
+///
+/// // "one\ntwo" built from "  one\r\n  two"
+/// let map = OffsetMap::from_lines([(2, 3), (9, 3)], 1);
+/// assert_eq!(map.to_source(4), Some(9)); // 't' of "two"
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(transparent)]
+pub struct OffsetMap {
+    segments: Vec<OffsetSegment>,
+}
+
+impl OffsetMap {
+    /// Create an empty map
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create the map of a text copied verbatim from `source_start`
+    pub fn identity(source_start: usize, len: usize) -> Self {
+        let mut map = Self::new();
+        map.push(0, source_start, len);
+        map
+    }
+
+    /// Create the map of source lines joined with a separator
+    ///
+    /// Lines are given as (source start, length) pairs, in text order. Lines
+    /// separated in the source by exactly as many bytes as the separator (the
+    /// same line break) stay in one run.
+    pub fn from_lines(
+        lines: impl IntoIterator<Item = (usize, usize)>,
+        separator_len: usize,
+    ) -> Self {
+        let mut map = Self::new();
+        let mut text = 0;
+        for (i, (source, len)) in lines.into_iter().enumerate() {
+            if i > 0 {
+                text += separator_len;
+                if let Some(last) = map.segments.last_mut() {
+                    let gap = source.checked_sub(last.source + last.len);
+                    if last.text + last.len + separator_len == text && gap == Some(separator_len) {
+                        last.len += separator_len + len;
+                        text += len;
+                        continue;
+                    }
+                }
+            }
+            map.push(text, source, len);
+            text += len;
+        }
+        map
+    }
+
+    /// Add a run of verbatim text
+    ///
+    /// Runs must be added in text order. A run continuing the previous one in
+    /// both the text and the source extends it.
+    pub fn push(&mut self, text: usize, source: usize, len: usize) {
+        if len == 0 {
+            return;
+        }
+        if let Some(last) = self.segments.last_mut() {
+            if last.text + last.len == text && last.source + last.len == source {
+                last.len += len;
+                return;
+            }
+        }
+        self.segments.push(OffsetSegment { text, source, len });
+    }
+
+    /// Get the verbatim runs, in text order
+    pub fn segments(&self) -> &[OffsetSegment] {
+        &self.segments
+    }
+
+    /// Check whether the map is that of a text of `len` bytes copied verbatim
+    /// from `source_start`
+    pub fn is_identity(&self, source_start: usize, len: usize) -> bool {
+        match self.segments.as_slice() {
+            [] => len == 0,
+            [segment] => segment.text == 0 && segment.source == source_start && segment.len == len,
+            _ => false,
+        }
+    }
+
+    /// Map a byte offset of the text to the source
+    ///
+    /// Returns `None` for an empty map.
+    pub fn to_source(&self, offset: usize) -> Option<usize> {
+        self.map(self.segments.partition_point(|s| s.text <= offset), offset)
+    }
+
+    /// Map a byte range of the text to the source range it covers
+    ///
+    /// A start offset between runs maps to the start of the next run, so the
+    /// source range does not include inserted separators.
+    pub fn source_range(&self, start: usize, end: usize) -> Option<(usize, usize)> {
+        let i = self.segments.partition_point(|s| s.text <= start);
+        let in_gap = i
+            .checked_sub(1)
+            .and_then(|i| self.segments.get(i))
+            .is_some_and(|s| start >= s.text + s.len);
+        let source_start = match self.segments.get(i).filter(|_| in_gap) {
+            Some(next) => next.source,
+            None => self.to_source(start)?,
+        };
+        // An end on a run boundary belongs to the run it ends
+        let source_end = self.map(self.segments.partition_point(|s| s.text < end), end)?;
+        Some((source_start.min(source_end), source_end))
+    }
+
+    /// Map `offset` through the run before index `i`
+    fn map(&self, i: usize, offset: usize) -> Option<usize> {
+        let segment = match i.checked_sub(1) {
+            Some(i) => self.segments.get(i)?,
+            None => return self.segments.first().map(|s| s.source),
+        };
+        Some(segment.source + (offset - segment.text).min(segment.len))
+    }
+
+    /// Get the map of the text range `start..end`, with offsets relative to `start`
+    pub fn slice(&self, start: usize, end: usize) -> Self {
+        let mut map = Self::new();
+        for segment in &self.segments {
+            let from = segment.text.max(start);
+            let to = (segment.text + segment.len).min(end);
+            if from < to {
+                map.push(
+                    from - start,
+                    segment.source + (from - segment.text),
+                    to - from,
+                );
+            }
+        }
+        map
+    }
+}
@@ -0,0 +1,225 @@
+//! Picture payloads and the document image store
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+
+/// Where the bytes of a picture live
+///
+/// Serializes with a `kind` tag: `{"kind": "inline", "data": "<base64>"}`,
+/// `{"kind": "file", "uri": "..."}` or `{"kind": "stored", "digest": "..."}`.
+/// In a [`PictureItem`] these fields sit next to the picture's own.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ImageRef {
+    /// Bytes embedded in the node (base64 in JSON)
+    Inline {
+        #[serde(with = "base64_bytes")]
+        #[schemars(with = "String")]
+        data: Vec<u8>,
+    },
+    /// External file path or URI, not loaded by the library
+    File { uri: String },
+    /// Blob in the document's [`ImageStore`], by hex SHA-256 digest
+    Stored { digest: String },
+}
+
+/// Payload of a [`NodeType::Picture`](crate::datamodel::NodeType::Picture) node
+///
+/// Small images can be embedded inline, images that already exist on disk or on
+/// the web can be referenced by URI, and everything else should go to the
+/// document's [`ImageStore`] (see
+/// [`DoclingDocument::store_image`](crate::datamodel::DoclingDocument::store_image)),
+/// where identical images are stored once however many nodes show them.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct PictureItem {
+    #[serde(flatten)]
+    image: ImageRef,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    mimetype: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    width: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    height: Option<u32>,
+}
+
+impl PictureItem {
+    /// Create a picture with embedded bytes
+    ///
+    /// The MIME type is detected from the data when possible.
+    pub fn inline(data: Vec<u8>) -> Self {
+        let mimetype = detect_mimetype(&data);
+        Self {
+            image: ImageRef::Inline { data },
+            mimetype,
+            width: None,
+            height: None,
+        }
+    }
+
+    /// Create a picture referencing an external file or URI
+    pub fn file(uri: impl Into<String>) -> Self {
+        Self::from_ref(ImageRef::File { uri: uri.into() })
+    }
+
+    /// Create a picture referencing a blob of an [`ImageStore`]
+    pub fn stored(digest: impl Into<String>) -> Self {
+        Self::from_ref(ImageRef::Stored {
+            digest: digest.into(),
+        })
+    }
+
+    fn from_ref(image: ImageRef) -> Self {
+        Self {
+            image,
+            mimetype: None,
+            width: None,
+            height: None,
+        }
+    }
+
+    /// Point the picture at other bytes, keeping its MIME type and size
+    pub(crate) fn with_image(mut self, image: ImageRef) -> Self {
+        self.image = image;
+        self
+    }
+
+    /// Get where the picture's bytes live
+    pub fn image(&self) -> &ImageRef {
+        &self.image
+    }
+
+    /// Get the MIME type (e.g. `image/png`), if known
+    pub fn mimetype(&self) -> Option<&str> {
+        self.mimetype.as_deref()
+    }
+
+    /// Set the MIME type
+    pub fn with_mimetype(mut self, mimetype: impl Into<String>) -> Self {
+        self.mimetype = Some(mimetype.into());
+        self
+    }
+
+    /// Get the width in pixels, if known
+    pub fn width(&self) -> Option<u32> {
+        self.width
+    }
+
+    /// Get the height in pixels, if known
+    pub fn height(&self) -> Option<u32> {
+        self.height
+    }
+
+    /// Set the size in pixels
+    pub fn with_size(mut self, width: u32, height: u32) -> Self {
+        self.width = Some(width);
+        self.height = Some(height);
+        self
+    }
+}
+
+/// An image held by an [`ImageStore`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct StoredImage {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    mimetype: Option<String>,
+    #[serde(with = "base64_bytes")]
+    #[schemars(with = "String")]
+    data: Vec<u8>,
+}
+
+impl StoredImage {
+    /// Get the MIME type, if detected
+    pub fn mimetype(&self) -> Option<&str> {
+        self.mimetype.as_deref()
+    }
+
+    /// Get the image bytes
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+/// Content-addressed storage for the images of a document
+///
+/// Images are keyed by the hex SHA-256 digest of their bytes, so inserting the
+/// same image twice stores it once. Serializes as a map from digest to image,
+/// with the bytes base64-encoded.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(transparent)]
+pub struct ImageStore {
+    images: BTreeMap<String, StoredImage>,
+}
+
+impl ImageStore {
+    /// Create an empty store
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an image, returning its digest
+    pub fn insert(&mut self, data: Vec<u8>) -> String {
+        let digest = format!("{:x}", Sha256::digest(&data));
+        self.images
+            .entry(digest.clone())
+            .or_insert_with(|| StoredImage {
+                mimetype: detect_mimetype(&data),
+                data,
+            });
+        digest
+    }
+
+    /// Get an image by digest
+    pub fn get(&self, digest: &str) -> Option<&StoredImage> {
+        self.images.get(digest)
+    }
+
+    /// Check whether an image is stored
+    pub fn contains(&self, digest: &str) -> bool {
+        self.images.contains_key(digest)
+    }
+
+    /// Remove an image, returning it
+    pub fn remove(&mut self, digest: &str) -> Option<StoredImage> {
+        self.images.remove(digest)
+    }
+
+    /// Iterate over the stored images by digest, in digest order
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &StoredImage)> {
+        self.images
+            .iter()
+            .map(|(digest, image)| (digest.as_str(), image))
+    }
+
+    /// Get the number of stored images
+    pub fn len(&self) -> usize {
+        self.images.len()
+    }
+
+    /// Check whether the store is empty
+    pub fn is_empty(&self) -> bool {
+        self.images.is_empty()
+    }
+}
+
+fn detect_mimetype(data: &[u8]) -> Option<String> {
+    infer::get(data).map(|kind| kind.mime_type().to_string())
+}
+
+/// Serde adapter writing bytes as a base64 string
+mod base64_bytes {
+    use super::{Engine, STANDARD};
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(data: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&STANDARD.encode(data))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        let encoded = String::deserialize(deserializer)?;
+        STANDARD.decode(encoded).map_err(serde::de::Error::custom)
+    }
+}
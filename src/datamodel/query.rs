@@ -0,0 +1,146 @@
+//! Node selection queries
+
+use crate::datamodel::{DocumentNode, NodeType};
+
+/// Separator between headings in a heading path (`"Introduction > Background"`)
+const PATH_SEPARATOR: char = '>';
+
+/// Criteria selecting nodes of a document
+///
+/// A query matches the nodes that satisfy all of its criteria; a query without
+/// criteria matches every node. Run it with
+/// [`DoclingDocument::select`](crate::datamodel::DoclingDocument::select).
+///
+/// Heading paths list section headings from the outermost down, separated by
+/// `>`, and are compared with the headings in effect at each node (derived from
+/// heading levels in reading order, the document title excluded). Headings are
+/// compared exactly, after trimming whitespace.
+///
+/// # Examples
+///
+/// ```ignore
+/// use docling_rs::datamodel::{NodeQuery, NodeType};
+///
+/// // All level-2 headings
+/// let headings = doc.select(&NodeQuery::type_(NodeType::Heading).level(2));
+///
+/// // Every node in the "Introduction > Background" section, subsections included
+/// let section = doc.select(&NodeQuery::new().within("Introduction > Background"));
+///
+/// // Tables on page 3
+/// let tables = doc.select(&NodeQuery::type_(NodeType::Table).page(3));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NodeQuery {
+    types: Vec<NodeType>,
+    level: Option<usize>,
+    page: Option<usize>,
+    text: Option<String>,
+    heading: Option<Vec<String>>,
+    within: Option<Vec<String>>,
+}
+
+impl NodeQuery {
+    /// Create a query matching every node
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a query matching nodes of a type
+    pub fn type_(node_type: NodeType) -> Self {
+        Self::new().or_type(node_type)
+    }
+
+    /// Create a query matching the heading at a heading path
+    ///
+    /// `"Introduction > Background"` matches the "Background" heading of the
+    /// "Introduction" section.
+    pub fn heading_path(path: &str) -> Self {
+        Self {
+            heading: Some(parse_path(path)),
+            ..Self::new()
+        }
+    }
+
+    /// Also match nodes of another type
+    pub fn or_type(mut self, node_type: NodeType) -> Self {
+        self.types.push(node_type);
+        self
+    }
+
+    /// Only match nodes with this heading level
+    pub fn level(mut self, level: usize) -> Self {
+        self.level = Some(level);
+        self
+    }
+
+    /// Only match nodes on this page (1-based)
+    pub fn page(mut self, page: usize) -> Self {
+        self.page = Some(page);
+        self
+    }
+
+    /// Only match nodes whose text contains `text`
+    pub fn contains(mut self, text: impl Into<String>) -> Self {
+        self.text = Some(text.into());
+        self
+    }
+
+    /// Only match nodes inside the section at a heading path
+    ///
+    /// The section includes its subsections and their headings, but not its
+    /// own heading.
+    pub fn within(mut self, path: &str) -> Self {
+        self.within = Some(parse_path(path));
+        self
+    }
+
+    /// Check a node against the query
+    ///
+    /// `headings` is the heading path in effect at the node: the headings of
+    /// the enclosing sections, outermost first, not including the node itself.
+    pub fn matches(&self, node: &DocumentNode, headings: &[String]) -> bool {
+        if !self.types.is_empty() && !self.types.contains(&node.node_type()) {
+            return false;
+        }
+        if self.level.is_some() && node.level() != self.level {
+            return false;
+        }
+        if self.page.is_some() && node.page() != self.page {
+            return false;
+        }
+        if let Some(text) = &self.text {
+            if !node
+                .text_content()
+                .is_some_and(|t| t.contains(text.as_str()))
+            {
+                return false;
+            }
+        }
+        if let Some(within) = &self.within {
+            if !headings.starts_with(within) {
+                return false;
+            }
+        }
+        if let Some(path) = &self.heading {
+            let Some((last, parents)) = path.split_last() else {
+                return false;
+            };
+            let is_heading = node.node_type() == NodeType::Heading
+                && node.text_content().map(str::trim) == Some(last.as_str());
+            if !is_heading || headings != parents {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Split a heading path into trimmed, non-empty headings
+pub(crate) fn parse_path(path: &str) -> Vec<String> {
+    path.split(PATH_SEPARATOR)
+        .map(str::trim)
+        .filter(|h| !h.is_empty())
+        .map(str::to_string)
+        .collect()
+}
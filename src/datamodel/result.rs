@@ -1,26 +1,52 @@
 //! Conversion result types
 
-use crate::datamodel::DoclingDocument;
+use crate::datamodel::{schema, DoclingDocument};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::time::Duration;
 
 /// Conversion result
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ConversionResult {
+    #[serde(default = "schema::current_version")]
+    schema_version: String,
     document: DoclingDocument,
     status: ConversionStatus,
     metrics: ConversionMetrics,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    warnings: Vec<ConversionWarning>,
 }
 
 impl ConversionResult {
     /// Create a new conversion result
     pub fn new(document: DoclingDocument, status: ConversionStatus) -> Self {
         Self {
+            schema_version: schema::current_version(),
             document,
             status,
             metrics: ConversionMetrics::default(),
+            warnings: Vec::new(),
         }
     }
 
+    /// Attach the metrics of the conversion
+    pub fn with_metrics(mut self, metrics: ConversionMetrics) -> Self {
+        self.metrics = metrics;
+        self
+    }
+
+    /// Attach the non-fatal issues met during conversion
+    pub fn with_warnings(mut self, warnings: Vec<ConversionWarning>) -> Self {
+        self.warnings = warnings;
+        self
+    }
+
+    /// Get the schema version the result was created or loaded with
+    pub fn schema_version(&self) -> &str {
+        &self.schema_version
+    }
+
     /// Get the converted document
     pub fn document(&self) -> &DoclingDocument {
         &self.document
@@ -35,21 +61,84 @@ impl ConversionResult {
     pub fn metrics(&self) -> &ConversionMetrics {
         &self.metrics
     }
+
+    /// Get the non-fatal issues met during conversion
+    ///
+    /// Results with warnings have status
+    /// [`PartialSuccess`](ConversionStatus::PartialSuccess): the document
+    /// holds everything that could be converted.
+    pub fn warnings(&self) -> &[ConversionWarning] {
+        &self.warnings
+    }
 }
 
 /// Conversion status
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 pub enum ConversionStatus {
     Success,
+    /// Parts of the source could not be converted; see
+    /// [`ConversionResult::warnings`]
     PartialSuccess,
     Failure,
 }
 
+/// A non-fatal issue met during conversion, such as a page that could not be
+/// read or an unterminated block
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct ConversionWarning {
+    message: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    page: Option<usize>,
+}
+
+impl ConversionWarning {
+    /// Create a warning
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            page: None,
+        }
+    }
+
+    /// Set the page (1-based) the issue is on
+    pub fn with_page(mut self, page: usize) -> Self {
+        self.page = Some(page);
+        self
+    }
+
+    /// Get the description of the issue
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Get the page (1-based) the issue is on, if known
+    pub fn page(&self) -> Option<usize> {
+        self.page
+    }
+}
+
+impl fmt::Display for ConversionWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.page {
+            Some(page) => write!(f, "page {}: {}", page, self.message),
+            None => f.write_str(&self.message),
+        }
+    }
+}
+
 /// Conversion metrics
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema, Default)]
 pub struct ConversionMetrics {
     total_pages: Option<usize>,
     processing_time_ms: Option<u64>,
+    #[serde(default)]
+    node_count: usize,
+    #[serde(default)]
+    characters: usize,
+    #[serde(default)]
+    warning_count: usize,
+    #[serde(default, skip_serializing_if = "StageTimings::is_empty")]
+    stages: StageTimings,
 }
 
 impl ConversionMetrics {
@@ -70,6 +159,35 @@ impl ConversionMetrics {
         self
     }
 
+    /// Set the number of nodes in the document
+    pub fn with_node_count(mut self, nodes: usize) -> Self {
+        self.node_count = nodes;
+        self
+    }
+
+    /// Set the number of characters extracted
+    pub fn with_characters(mut self, characters: usize) -> Self {
+        self.characters = characters;
+        self
+    }
+
+    /// Set the number of warnings
+    pub fn with_warning_count(mut self, warnings: usize) -> Self {
+        self.warning_count = warnings;
+        self
+    }
+
+    /// Set the time spent in each stage
+    pub fn with_stages(mut self, stages: StageTimings) -> Self {
+        self.stages = stages;
+        self
+    }
+
+    /// Add `elapsed` to the time spent in `stage`
+    pub fn add_stage_time(&mut self, stage: TimedStage, elapsed: Duration) {
+        self.stages.add(stage, elapsed);
+    }
+
     /// Get total pages
     pub fn total_pages(&self) -> usize {
         self.total_pages.unwrap_or(0)
@@ -79,4 +197,148 @@ impl ConversionMetrics {
     pub fn processing_time_ms(&self) -> u64 {
         self.processing_time_ms.unwrap_or(0)
     }
+
+    /// Number of nodes in the document
+    pub fn node_count(&self) -> usize {
+        self.node_count
+    }
+
+    /// Number of characters in the text of all nodes
+    pub fn characters(&self) -> usize {
+        self.characters
+    }
+
+    /// Number of non-fatal issues met during conversion
+    pub fn warning_count(&self) -> usize {
+        self.warning_count
+    }
+
+    /// Time spent in each stage
+    pub fn stages(&self) -> &StageTimings {
+        &self.stages
+    }
+}
+
+impl fmt::Display for ConversionMetrics {
+    /// One line such as `pages: 3, nodes: 12, characters: 4051, warnings: 0,
+    /// time: 20 ms (parse: 15 ms, layout: 2 ms)`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(pages) = self.total_pages {
+            write!(f, "pages: {}, ", pages)?;
+        }
+        write!(
+            f,
+            "nodes: {}, characters: {}, warnings: {}, time: {} ms",
+            self.node_count,
+            self.characters,
+            self.warning_count,
+            self.processing_time_ms()
+        )?;
+        let stages: Vec<String> = TimedStage::ALL
+            .iter()
+            .filter_map(|&stage| {
+                self.stages
+                    .get(stage)
+                    .map(|ms| format!("{}: {} ms", stage, ms))
+            })
+            .collect();
+        if !stages.is_empty() {
+            write!(f, " ({})", stages.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// A step of a conversion whose duration is measured
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum TimedStage {
+    /// The backend parsing the source
+    Parse,
+    /// Layout analysis of pages
+    Layout,
+    /// Table detection
+    Tables,
+    /// Text recognition on scanned pages
+    Ocr,
+    /// Splitting the document into chunks
+    Chunking,
+}
+
+impl TimedStage {
+    /// All stages, in the order they run
+    pub const ALL: [TimedStage; 5] = [
+        Self::Parse,
+        Self::Layout,
+        Self::Tables,
+        Self::Ocr,
+        Self::Chunking,
+    ];
+}
+
+impl fmt::Display for TimedStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Parse => "parse",
+            Self::Layout => "layout",
+            Self::Tables => "tables",
+            Self::Ocr => "ocr",
+            Self::Chunking => "chunking",
+        })
+    }
+}
+
+/// Milliseconds spent in each [`TimedStage`]; stages that did not run have
+/// no time
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct StageTimings {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    parse_ms: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    layout_ms: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tables_ms: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ocr_ms: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    chunking_ms: Option<u64>,
+}
+
+impl StageTimings {
+    /// Create timings with no stage run
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Milliseconds spent in `stage`, if it ran
+    pub fn get(&self, stage: TimedStage) -> Option<u64> {
+        match stage {
+            TimedStage::Parse => self.parse_ms,
+            TimedStage::Layout => self.layout_ms,
+            TimedStage::Tables => self.tables_ms,
+            TimedStage::Ocr => self.ocr_ms,
+            TimedStage::Chunking => self.chunking_ms,
+        }
+    }
+
+    /// Add `elapsed` to the time spent in `stage`
+    pub fn add(&mut self, stage: TimedStage, elapsed: Duration) {
+        let slot = match stage {
+            TimedStage::Parse => &mut self.parse_ms,
+            TimedStage::Layout => &mut self.layout_ms,
+            TimedStage::Tables => &mut self.tables_ms,
+            TimedStage::Ocr => &mut self.ocr_ms,
+            TimedStage::Chunking => &mut self.chunking_ms,
+        };
+        let ms = u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX);
+        *slot = Some(slot.unwrap_or(0).saturating_add(ms));
+    }
+
+    /// Whether no stage was timed
+    pub fn is_empty(&self) -> bool {
+        TimedStage::ALL
+            .iter()
+            .all(|&stage| self.get(stage).is_none())
+    }
 }
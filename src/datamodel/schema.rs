@@ -0,0 +1,277 @@
+//! Versioned JSON schema of serialized documents
+//!
+//! Serialized [`DoclingDocument`]s and [`ConversionResult`]s carry a
+//! `schema_version` field. [`document_schema`] and [`result_schema`] generate
+//! the JSON Schema of the current version from the Rust types; the generated
+//! schemas are checked in under `schema/` for consumers in other languages.
+//!
+//! JSON from older versions is upgraded by [`migrate_document`] and
+//! [`migrate_result`]. [`document_from_value`] and [`result_from_value`]
+//! migrate, deserialize and [validate](validate_document) in one step, and are
+//! the recommended way to load documents written by another version of the
+//! library.
+//!
+//! # Versions
+//!
+//! - `0` (no `schema_version` field): metadata was a free-form key/value map
+//!   and nodes had no IDs
+//! - `1.0.0`: typed [`DocumentMetadata`](crate::datamodel::DocumentMetadata)
+//!   with an `extra` map, node IDs and parent/child links, image store
+//! - `1.1.0`: document [`Annotation`](crate::datamodel::Annotation)s
+//! - `1.2.0`: node languages
+//! - `1.3.0`: node [`OffsetMap`](crate::datamodel::OffsetMap)s back to source bytes
+//! - `1.4.0`: [`Section`](crate::datamodel::NodeType::Section) nodes
+//! - `1.5.0`: [`Quote`](crate::datamodel::NodeType::Quote) nodes and ordered lists
+
+use serde_json::{Map, Value};
+
+use crate::datamodel::{ConversionResult, DoclingDocument, DocumentMetadata, ImageRef, NodeId};
+
+/// Schema version written by this version of the library
+pub const SCHEMA_VERSION: &str = "1.5.0";
+
+/// Typed fields of the document metadata; other legacy keys go to `extra`
+const METADATA_FIELDS: [&str; 8] = [
+    "title",
+    "authors",
+    "created",
+    "modified",
+    "language",
+    "source_uri",
+    "format",
+    "page_count",
+];
+
+/// Error type for schema migration and validation
+#[derive(Debug, thiserror::Error)]
+pub enum SchemaError {
+    #[error("Unsupported schema version: {0}")]
+    UnsupportedVersion(String),
+
+    #[error("Invalid document: {0}")]
+    Invalid(String),
+
+    #[error("Serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+}
+
+/// Default of the `schema_version` fields
+pub(crate) fn current_version() -> String {
+    SCHEMA_VERSION.to_string()
+}
+
+/// Generate the JSON Schema of [`DoclingDocument`]
+pub fn document_schema() -> Value {
+    serde_json::to_value(schemars::schema_for!(DoclingDocument)).unwrap_or(Value::Null)
+}
+
+/// Generate the JSON Schema of [`ConversionResult`]
+pub fn result_schema() -> Value {
+    serde_json::to_value(schemars::schema_for!(ConversionResult)).unwrap_or(Value::Null)
+}
+
+/// Upgrade a serialized document to the current schema version
+///
+/// Documents already at the current version are returned unchanged. Fails for
+/// documents written by a newer major version.
+pub fn migrate_document(mut value: Value) -> Result<Value, SchemaError> {
+    let doc = value
+        .as_object_mut()
+        .ok_or_else(|| SchemaError::Invalid("document is not a JSON object".to_string()))?;
+
+    match major_version(doc)? {
+        0 => migrate_v0(doc),
+        1 => {}
+        _ => {
+            let version = doc.get("schema_version").and_then(Value::as_str);
+            return Err(SchemaError::UnsupportedVersion(
+                version.unwrap_or_default().to_string(),
+            ));
+        }
+    }
+    Ok(value)
+}
+
+/// Upgrade a serialized conversion result to the current schema version
+///
+/// Migrates the contained document as well.
+pub fn migrate_result(mut value: Value) -> Result<Value, SchemaError> {
+    let result = value
+        .as_object_mut()
+        .ok_or_else(|| SchemaError::Invalid("result is not a JSON object".to_string()))?;
+
+    let document = result
+        .remove("document")
+        .ok_or_else(|| SchemaError::Invalid("result has no document".to_string()))?;
+    result.insert("document".to_string(), migrate_document(document)?);
+    result
+        .entry("schema_version")
+        .or_insert_with(|| Value::from(SCHEMA_VERSION));
+    Ok(value)
+}
+
+/// Migrate, deserialize and validate a serialized document
+pub fn document_from_value(value: Value) -> Result<DoclingDocument, SchemaError> {
+    let doc: DoclingDocument = serde_json::from_value(migrate_document(value)?)?;
+    validate_document(&doc)?;
+    Ok(doc)
+}
+
+/// Migrate, deserialize and validate a serialized conversion result
+pub fn result_from_value(value: Value) -> Result<ConversionResult, SchemaError> {
+    let result: ConversionResult = serde_json::from_value(migrate_result(value)?)?;
+    validate_document(result.document())?;
+    Ok(result)
+}
+
+/// Check the structural integrity of a document
+///
+/// Deserialization only checks the shape of the JSON; this checks what the
+/// types cannot express:
+///
+/// - node IDs match node positions
+/// - parent and child links point at existing nodes and agree with each other
+/// - node references resolve
+/// - formatting spans lie within the node text, on character boundaries
+/// - stored pictures exist in the image store
+/// - annotations point at existing nodes and lie within their text
+pub fn validate_document(doc: &DoclingDocument) -> Result<(), SchemaError> {
+    let nodes = doc.nodes();
+    let invalid = |index: usize, message: String| {
+        Err(SchemaError::Invalid(format!("node {}: {}", index, message)))
+    };
+
+    for (index, node) in nodes.iter().enumerate() {
+        if let Some(id) = node.id() {
+            if id.index() != index {
+                return invalid(index, format!("has ID {}", id.index()));
+            }
+        }
+
+        if let Some(parent) = node.parent() {
+            let Some(parent_node) = doc.node(parent) else {
+                return invalid(index, format!("parent {} does not exist", parent.index()));
+            };
+            if !parent_node.children().contains(&NodeId::new(index)) {
+                return invalid(
+                    index,
+                    format!("not listed as a child of its parent {}", parent.index()),
+                );
+            }
+        }
+
+        for &child in node.children() {
+            match doc.node(child) {
+                None => return invalid(index, format!("child {} does not exist", child.index())),
+                Some(child_node) if child_node.parent() != Some(NodeId::new(index)) => {
+                    return invalid(
+                        index,
+                        format!("child {} has a different parent", child.index()),
+                    );
+                }
+                Some(_) => {}
+            }
+        }
+
+        for reference in node.references() {
+            if doc.resolve(reference).is_none() {
+                return invalid(
+                    index,
+                    format!("reference {} does not resolve", reference.as_str()),
+                );
+            }
+        }
+
+        let text = node.text_content().unwrap_or_default();
+        for span in node.spans() {
+            let in_bounds = span.start <= span.end
+                && span.end <= text.len()
+                && text.is_char_boundary(span.start)
+                && text.is_char_boundary(span.end);
+            if !in_bounds {
+                return invalid(
+                    index,
+                    format!("span {}..{} is outside the text", span.start, span.end),
+                );
+            }
+        }
+
+        if let Some(ImageRef::Stored { digest }) = node.picture().map(|p| p.image()) {
+            if !doc.images().contains(digest) {
+                return invalid(index, format!("image {} is not in the store", digest));
+            }
+        }
+    }
+
+    for (index, annotation) in doc.annotations().iter().enumerate() {
+        let node = annotation.node().index();
+        let invalid = |message: String| {
+            Err(SchemaError::Invalid(format!(
+                "annotation {}: {}",
+                index, message
+            )))
+        };
+        let Some(text) = doc.node(annotation.node()).map(|n| n.text_content()) else {
+            return invalid(format!("node {} does not exist", node));
+        };
+        if annotation.covered_text(text.unwrap_or_default()).is_none() {
+            return invalid(format!("range is outside the text of node {}", node));
+        }
+    }
+
+    Ok(())
+}
+
+/// Get the major version of a serialized document (0 when unversioned)
+fn major_version(doc: &Map<String, Value>) -> Result<u64, SchemaError> {
+    let Some(version) = doc.get("schema_version") else {
+        return Ok(0);
+    };
+    let version = version
+        .as_str()
+        .ok_or_else(|| SchemaError::UnsupportedVersion(version.to_string()))?;
+    version
+        .split('.')
+        .next()
+        .and_then(|major| major.parse().ok())
+        .ok_or_else(|| SchemaError::UnsupportedVersion(version.to_string()))
+}
+
+/// Upgrade an unversioned document
+fn migrate_v0(doc: &mut Map<String, Value>) {
+    doc.insert("schema_version".to_string(), Value::from(SCHEMA_VERSION));
+
+    // Metadata was a free-form map; keep entries that fit a typed field
+    if let Some(Value::Object(legacy)) = doc.remove("metadata") {
+        let mut metadata = Map::new();
+        let mut extra = Map::new();
+        for (key, value) in legacy {
+            let typed = METADATA_FIELDS.contains(&key.as_str()) && fits_metadata(&key, &value);
+            if typed {
+                metadata.insert(key, value);
+            } else {
+                extra.insert(key, value);
+            }
+        }
+        if !extra.is_empty() {
+            metadata.insert("extra".to_string(), Value::Object(extra));
+        }
+        doc.insert("metadata".to_string(), Value::Object(metadata));
+    }
+
+    // Nodes had no IDs; all of them were top-level
+    if let Some(Value::Array(nodes)) = doc.get_mut("nodes") {
+        for (index, node) in nodes.iter_mut().enumerate() {
+            if let Value::Object(node) = node {
+                node.entry("id").or_insert_with(|| Value::from(index));
+            }
+        }
+    }
+}
+
+/// Check whether a legacy metadata entry deserializes into its typed field
+fn fits_metadata(key: &str, value: &Value) -> bool {
+    let mut probe = Map::new();
+    probe.insert(key.to_string(), value.clone());
+    serde_json::from_value::<DocumentMetadata>(Value::Object(probe)).is_ok()
+}
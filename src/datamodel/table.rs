@@ -1,23 +1,89 @@
 //! Table types
 
-use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Deserializer, Serialize};
+
+use super::text::{escape_html, escape_latex};
+use crate::error::ConversionError;
+
+/// Largest column span of a cell, as for `colspan` in the HTML standard
+pub const MAX_COL_SPAN: usize = 1000;
+
+/// Largest row span of a cell, as for `rowspan` in the HTML standard
+pub const MAX_ROW_SPAN: usize = 65534;
 
 /// Table structure
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Table {
     // Placeholder - will be implemented in T016
 }
 
+/// Column alignment in Markdown tables
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColumnAlignment {
+    /// No alignment marker; renderers usually align left
+    #[default]
+    None,
+    /// Left-aligned (`:----`)
+    Left,
+    /// Centered (`:---:`)
+    Center,
+    /// Right-aligned (`----:`)
+    Right,
+}
+
+impl ColumnAlignment {
+    /// Separator row cell marking this alignment
+    fn separator(self) -> &'static str {
+        match self {
+            Self::None => "-----",
+            Self::Left => ":----",
+            Self::Center => ":---:",
+            Self::Right => "----:",
+        }
+    }
+}
+
+impl std::str::FromStr for ColumnAlignment {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "none" => Ok(Self::None),
+            "left" => Ok(Self::Left),
+            "center" => Ok(Self::Center),
+            "right" => Ok(Self::Right),
+            _ => Err(format!(
+                "invalid alignment: {} (expected none, left, center or right)",
+                s
+            )),
+        }
+    }
+}
+
 /// Table data
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// Rows list their cells in reading order, as in HTML: a cell spanning several
+/// columns or rows is stored once, and later cells skip the positions it covers.
+/// The `logical_*`, `to_*` and [`grid`](Self::grid) methods resolve spans into
+/// a rectangular grid, repeating the content of a merged cell in every position
+/// it covers and leaving uncovered positions empty.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct TableData {
     rows: Vec<TableRow>,
+    #[serde(default)]
+    header_rows: usize,
 }
 
 impl TableData {
     /// Create a new empty table
     pub fn new() -> Self {
-        Self { rows: Vec::new() }
+        Self {
+            rows: Vec::new(),
+            header_rows: 0,
+        }
     }
 
     /// Get the rows
@@ -31,10 +97,270 @@ impl TableData {
         self
     }
 
-    /// Get the number of columns (from first row)
+    /// Mark the first `count` rows as header rows
+    pub fn with_header_rows(mut self, count: usize) -> Self {
+        self.header_rows = count;
+        self
+    }
+
+    /// Get the header rows
+    pub fn header_rows(&self) -> &[TableRow] {
+        &self.rows[..self.num_header_rows()]
+    }
+
+    /// Get the body rows (all rows after the header)
+    pub fn body_rows(&self) -> &[TableRow] {
+        &self.rows[self.num_header_rows()..]
+    }
+
+    /// Get the number of header rows
+    pub fn num_header_rows(&self) -> usize {
+        self.header_rows.min(self.rows.len())
+    }
+
+    /// Get the number of logical columns, counting spanned positions
     pub fn num_cols(&self) -> usize {
-        self.rows.first().map(|r| r.cells.len()).unwrap_or(0)
+        self.placed_cells()
+            .iter()
+            .map(|(_, c, cell)| c + cell.col_span)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Get the number of logical rows, including rows only covered by row spans
+    pub fn num_rows(&self) -> usize {
+        self.placed_cells()
+            .iter()
+            .map(|(r, _, cell)| r + cell.row_span)
+            .fold(self.rows.len(), usize::max)
+    }
+
+    /// Get every cell with the logical position of its top-left corner
+    ///
+    /// Returns `(row, column, cell)` triples in reading order.
+    pub fn placed_cells(&self) -> Vec<(usize, usize, &TableCell)> {
+        let mut occupied: Vec<Vec<bool>> = Vec::new();
+        let mut placed = Vec::new();
+
+        for (r, row) in self.rows.iter().enumerate() {
+            let mut c = 0;
+            for cell in &row.cells {
+                // Skip positions taken by row spans from above
+                while occupied.get(r).and_then(|line| line.get(c)) == Some(&true) {
+                    c += 1;
+                }
+                let rows = r..r + cell.row_span;
+                let cols = c..c + cell.col_span;
+                if occupied.len() < rows.end {
+                    occupied.resize_with(rows.end, Vec::new);
+                }
+                for line in &mut occupied[rows] {
+                    if line.len() < cols.end {
+                        line.resize(cols.end, false);
+                    }
+                    line[cols.clone()].fill(true);
+                }
+                placed.push((r, c, cell));
+                c = cols.end;
+            }
+        }
+
+        placed
+    }
+
+    /// Resolve merged cells into a rectangular grid
+    ///
+    /// Each position holds the cell covering it, or `None` if no cell does
+    /// (ragged rows).
+    pub fn grid(&self) -> Vec<Vec<Option<&TableCell>>> {
+        let placed = self.placed_cells();
+        let height = placed
+            .iter()
+            .map(|(r, _, cell)| r + cell.row_span)
+            .fold(self.rows.len(), usize::max);
+        let width = placed
+            .iter()
+            .map(|(_, c, cell)| c + cell.col_span)
+            .max()
+            .unwrap_or(0);
+
+        let mut grid = vec![vec![None; width]; height];
+        for (r, c, cell) in placed {
+            for line in &mut grid[r..r + cell.row_span] {
+                line[c..c + cell.col_span].fill(Some(cell));
+            }
+        }
+        grid
+    }
+
+    /// Iterate over the logical rows as cell texts
+    pub fn logical_rows(&self) -> impl Iterator<Item = Vec<&str>> {
+        self.grid()
+            .into_iter()
+            .map(|line| line.into_iter().map(cell_text).collect())
+    }
+
+    /// Iterate over the logical columns as cell texts
+    pub fn logical_columns(&self) -> impl Iterator<Item = Vec<&str>> {
+        let grid = self.grid();
+        let width = grid.first().map_or(0, Vec::len);
+        (0..width).map(move |c| grid.iter().map(|line| cell_text(line[c])).collect())
+    }
+
+    /// Convert to a grid of cell texts
+    pub fn to_vec(&self) -> Vec<Vec<String>> {
+        self.logical_rows()
+            .map(|row| row.into_iter().map(str::to_string).collect())
+            .collect()
+    }
+
+    /// Render as a GitHub-flavored Markdown pipe table
+    ///
+    /// The first logical row becomes the Markdown header, since pipe tables
+    /// require one. Pipes are escaped and line breaks become `<br>`.
+    pub fn to_markdown(&self) -> String {
+        self.to_markdown_aligned(ColumnAlignment::None)
+    }
+
+    /// Render as a Markdown pipe table with every column aligned the same way
+    pub fn to_markdown_aligned(&self, alignment: ColumnAlignment) -> String {
+        let mut out = String::new();
+
+        for (i, row) in self.logical_rows().enumerate() {
+            let cells: Vec<String> = row
+                .into_iter()
+                .map(|text| text.replace('|', "\\|").replace('\n', "<br>"))
+                .collect();
+            out.push_str(&format!("| {} |\n", cells.join(" | ")));
+            if i == 0 {
+                let separator = format!("{}|", alignment.separator());
+                out.push_str(&format!("|{}\n", separator.repeat(cells.len())));
+            }
+        }
+
+        out
+    }
+
+    /// Render as an HTML table
+    ///
+    /// Header rows go in `<thead>` as `<th>` cells, and merged cells keep
+    /// their `colspan` and `rowspan`.
+    pub fn to_html(&self) -> String {
+        let mut out = String::from("<table>\n");
+        let sections = [
+            ("thead", "th", self.header_rows()),
+            ("tbody", "td", self.body_rows()),
+        ];
+        for (section, tag, rows) in sections {
+            if rows.is_empty() {
+                continue;
+            }
+            out.push_str(&format!("<{}>\n", section));
+            for row in rows {
+                out.push_str("<tr>");
+                for cell in &row.cells {
+                    out.push_str(&format!("<{}", tag));
+                    if cell.col_span > 1 {
+                        out.push_str(&format!(" colspan=\"{}\"", cell.col_span));
+                    }
+                    if cell.row_span > 1 {
+                        out.push_str(&format!(" rowspan=\"{}\"", cell.row_span));
+                    }
+                    let content = escape_html(&cell.content).replace('\n', "<br>");
+                    out.push_str(&format!(">{}</{}>", content, tag));
+                }
+                out.push_str("</tr>\n");
+            }
+            out.push_str(&format!("</{}>\n", section));
+        }
+        out.push_str("</table>\n");
+        out
     }
+
+    /// Render as a LaTeX `tabular`
+    ///
+    /// Header rows are ruled off; merged cells use `\multicolumn` and
+    /// `\multirow` (from the `multirow` package).
+    pub fn to_latex(&self) -> String {
+        let grid = self.grid();
+        let width = grid.iter().map(Vec::len).max().unwrap_or(0);
+        let starts: HashMap<(usize, usize), &TableCell> = self
+            .placed_cells()
+            .into_iter()
+            .map(|(r, c, cell)| ((r, c), cell))
+            .collect();
+
+        let mut out = format!("\\begin{{tabular}}{{{}}}\n\\hline\n", "l".repeat(width));
+        for (r, line) in grid.iter().enumerate() {
+            let mut cells = Vec::new();
+            let mut c = 0;
+            while c < width {
+                // Positions covered by a row span from above stay empty
+                let span = line
+                    .get(c)
+                    .copied()
+                    .flatten()
+                    .map_or(1, |cell| cell.col_span);
+                let content = match starts.get(&(r, c)) {
+                    Some(cell) if cell.row_span > 1 => format!(
+                        "\\multirow{{{}}}{{*}}{{{}}}",
+                        cell.row_span,
+                        escape_latex(&cell.content)
+                    ),
+                    Some(cell) => escape_latex(&cell.content),
+                    None => String::new(),
+                };
+                cells.push(if span > 1 {
+                    format!("\\multicolumn{{{}}}{{l}}{{{}}}", span, content)
+                } else {
+                    content
+                });
+                c += span;
+            }
+            out.push_str(&format!("{} \\\\\n", cells.join(" & ")));
+            if r + 1 == self.header_rows {
+                out.push_str("\\hline\n");
+            }
+        }
+        out.push_str("\\hline\n\\end{tabular}\n");
+        out
+    }
+
+    /// Render as an XLSX workbook with a single sheet
+    ///
+    /// Cells holding plain numbers become numeric cells; merged cells stay
+    /// merged.
+    pub fn to_xlsx(&self) -> Result<Vec<u8>, ConversionError> {
+        super::xlsx::write_table(self)
+    }
+
+    /// Render as CSV, one line per logical row
+    ///
+    /// Fields containing commas, quotes or line breaks are quoted.
+    pub fn to_csv(&self) -> String {
+        let mut out = String::new();
+
+        for row in self.logical_rows() {
+            let fields: Vec<String> = row
+                .into_iter()
+                .map(|text| {
+                    if text.contains([',', '"', '\n', '\r']) {
+                        format!("\"{}\"", text.replace('"', "\"\""))
+                    } else {
+                        text.to_string()
+                    }
+                })
+                .collect();
+            out.push_str(&fields.join(","));
+            out.push('\n');
+        }
+
+        out
+    }
+}
+
+fn cell_text(cell: Option<&TableCell>) -> &str {
+    cell.map_or("", TableCell::content)
 }
 
 impl Default for TableData {
@@ -44,10 +370,15 @@ impl Default for TableData {
 }
 
 /// Table cell
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// Spans are at least 1 and at most 1000 columns and 65534 rows, as in HTML;
+/// out-of-range spans are clamped when set or deserialized.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct TableCell {
     content: String,
+    #[serde(deserialize_with = "col_span")]
     col_span: usize,
+    #[serde(deserialize_with = "row_span")]
     row_span: usize,
 }
 
@@ -76,21 +407,29 @@ impl TableCell {
         self.row_span
     }
 
-    /// Set the column span
+    /// Set the column span, clamped to `1..=MAX_COL_SPAN`
     pub fn with_col_span(mut self, span: usize) -> Self {
-        self.col_span = span;
+        self.col_span = span.clamp(1, MAX_COL_SPAN);
         self
     }
 
-    /// Set the row span
+    /// Set the row span, clamped to `1..=MAX_ROW_SPAN`
     pub fn with_row_span(mut self, span: usize) -> Self {
-        self.row_span = span;
+        self.row_span = span.clamp(1, MAX_ROW_SPAN);
         self
     }
 }
 
+fn col_span<'de, D: Deserializer<'de>>(deserializer: D) -> Result<usize, D::Error> {
+    Ok(usize::deserialize(deserializer)?.clamp(1, MAX_COL_SPAN))
+}
+
+fn row_span<'de, D: Deserializer<'de>>(deserializer: D) -> Result<usize, D::Error> {
+    Ok(usize::deserialize(deserializer)?.clamp(1, MAX_ROW_SPAN))
+}
+
 /// Table row
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct TableRow {
     cells: Vec<TableCell>,
 }
@@ -108,7 +447,7 @@ impl TableRow {
 }
 
 /// Table metadata
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct TableMetadata {
     // Placeholder - will be implemented in T016
 }
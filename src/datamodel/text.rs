@@ -1,18 +1,23 @@
 //! Text content types
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 /// Text content
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct TextContent {
     // Placeholder - will be implemented in T015
 }
 
 /// Text item
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// `formatting` applies to the whole content; `spans` apply to parts of it.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct TextItem {
     content: String,
     formatting: Vec<Formatting>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    spans: Vec<TextSpan>,
 }
 
 impl TextItem {
@@ -21,6 +26,7 @@ impl TextItem {
         Self {
             content: content.into(),
             formatting: Vec::new(),
+            spans: Vec::new(),
         }
     }
 
@@ -39,14 +45,289 @@ impl TextItem {
         self.formatting.push(formatting);
         self
     }
+
+    /// Get the formatted spans
+    pub fn spans(&self) -> &[TextSpan] {
+        &self.spans
+    }
+
+    /// Add a formatted span
+    pub fn with_span(mut self, span: TextSpan) -> Self {
+        self.spans.push(span);
+        self
+    }
+
+    /// Render the content as Markdown, re-emitting formatting and links
+    pub fn to_markdown(&self) -> String {
+        let spans: Vec<TextSpan> = self
+            .formatting
+            .iter()
+            .map(|f| TextSpan::new(0, self.content.len(), f.clone()))
+            .chain(self.spans.iter().cloned())
+            .collect();
+        to_markdown(&self.content, &spans)
+    }
+}
+
+/// Formatting applied to a byte range of a text
+///
+/// `start` and `end` are byte offsets into the text and must fall on character
+/// boundaries; spans that do not are ignored when rendering.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub struct TextSpan {
+    /// Start byte offset (inclusive)
+    pub start: usize,
+    /// End byte offset (exclusive)
+    pub end: usize,
+    /// Formatting of the range
+    pub formatting: Formatting,
+}
+
+impl TextSpan {
+    /// Create a new formatted span
+    pub fn new(start: usize, end: usize, formatting: Formatting) -> Self {
+        Self {
+            start,
+            end,
+            formatting,
+        }
+    }
+}
+
+/// Render text with formatted spans as Markdown
+///
+/// Nested spans are emitted inside their enclosing span; spans that partially
+/// overlap are emitted in start order, which Markdown may not render as intended.
+pub fn to_markdown(text: &str, spans: &[TextSpan]) -> String {
+    render(
+        text,
+        spans,
+        |f| f.opening_marker().to_string(),
+        Formatting::closing_marker,
+        |text, _| text.to_string(),
+    )
+}
+
+/// Render text with formatted spans as Markdown, escaping Markdown syntax
+///
+/// Like [`to_markdown`], but characters Markdown would interpret in the text
+/// are backslash-escaped (see [`escape_markdown`]), except inside code spans.
+pub fn to_markdown_escaped(text: &str, spans: &[TextSpan]) -> String {
+    render(
+        text,
+        spans,
+        |f| f.opening_marker().to_string(),
+        Formatting::closing_marker,
+        |text, in_code| match in_code {
+            true => text.to_string(),
+            false => escape_markdown(text),
+        },
+    )
+}
+
+/// Backslash-escape the characters Markdown may interpret as syntax
+pub fn escape_markdown(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(
+            c,
+            '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '#' | '|' | '~'
+        ) {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// Render text with formatted spans as HTML
+///
+/// Text is escaped; formatting becomes `<strong>`, `<em>`, `<code>`, `<del>`
+/// and `<a>` elements, except links whose URL [`is_safe_url`] rejects, which
+/// stay plain text. Partially overlapping spans are handled as in
+/// [`to_markdown`], so the markup may not nest properly.
+pub fn to_html(text: &str, spans: &[TextSpan]) -> String {
+    render(
+        text,
+        spans,
+        Formatting::opening_tag,
+        Formatting::closing_tag,
+        |text, _| escape_html(text),
+    )
+}
+
+/// Escape text for use in HTML content and attribute values
+pub fn escape_html(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Check whether a link or image URL is safe to render
+///
+/// Only `http`, `https` and `mailto` URLs and relative URLs (no scheme) are
+/// allowed, so links such as `javascript:` are not made clickable. As in
+/// browsers, tabs and line breaks inside the URL and leading spaces and
+/// control characters are ignored when reading the scheme.
+pub fn is_safe_url(url: &str) -> bool {
+    let url: String = url
+        .trim_start_matches(|c: char| c <= ' ')
+        .chars()
+        .filter(|c| !matches!(c, '\t' | '\n' | '\r'))
+        .collect();
+    match url.find([':', '/', '?', '#']) {
+        Some(i) if url[i..].starts_with(':') => matches!(
+            url[..i].to_ascii_lowercase().as_str(),
+            "http" | "https" | "mailto"
+        ),
+        _ => true,
+    }
+}
+
+/// Check whether an image URL is safe to render
+///
+/// As [`is_safe_url`], plus `data:image/` URLs of embedded images.
+pub fn is_safe_image_url(url: &str) -> bool {
+    is_safe_url(url)
+        || url
+            .trim_start()
+            .get(..11)
+            .is_some_and(|scheme| scheme.eq_ignore_ascii_case("data:image/"))
+}
+
+/// Render text with formatted spans as LaTeX
+///
+/// Text is escaped; formatting becomes `\textbf`, `\emph`, `\texttt`, `\sout`
+/// (from `ulem`) and `\href` (from `hyperref`).
+pub fn to_latex(text: &str, spans: &[TextSpan]) -> String {
+    render(
+        text,
+        spans,
+        Formatting::opening_command,
+        |_| "}".to_string(),
+        |text, _| escape_latex(text),
+    )
+}
+
+/// Escape text for use in LaTeX paragraphs
+pub fn escape_latex(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' | '%' | '$' | '#' | '_' | '{' | '}' => {
+                out.push('\\');
+                out.push(c);
+            }
+            '~' => out.push_str("\\textasciitilde{}"),
+            '^' => out.push_str("\\textasciicircum{}"),
+            '\\' => out.push_str("\\textbackslash{}"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Escape a URL for the first argument of `\href`
+///
+/// The characters TeX treats specially are backslash-escaped, except the
+/// backslash and braces, which are percent-encoded so they cannot end the
+/// argument or start a command.
+pub fn escape_latex_url(url: &str) -> String {
+    let mut out = String::with_capacity(url.len());
+    for c in url.chars() {
+        match c {
+            '#' | '%' | '&' | '_' | '~' | '^' | '$' => {
+                out.push('\\');
+                out.push(c);
+            }
+            '\\' => out.push_str("\\%5C"),
+            '{' => out.push_str("\\%7B"),
+            '}' => out.push_str("\\%7D"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Render text with spans, wrapping each span in its opening and closing markup
+///
+/// `escape` gets each run of text and whether it is inside a code span.
+fn render(
+    text: &str,
+    spans: &[TextSpan],
+    open_markup: impl Fn(&Formatting) -> String,
+    close_markup: impl Fn(&Formatting) -> String,
+    escape: impl Fn(&str, bool) -> String,
+) -> String {
+    let mut spans: Vec<&TextSpan> = spans
+        .iter()
+        .filter(|s| {
+            s.start < s.end
+                && s.end <= text.len()
+                && text.is_char_boundary(s.start)
+                && text.is_char_boundary(s.end)
+        })
+        .collect();
+    // Outer spans first, so they open before and close after the spans they contain
+    spans.sort_by(|a, b| a.start.cmp(&b.start).then(b.end.cmp(&a.end)));
+
+    let mut output = String::with_capacity(text.len());
+    let mut open: Vec<&TextSpan> = Vec::new();
+    let mut pos = 0;
+    let mut next = spans.into_iter().peekable();
+
+    loop {
+        // Close spans ending here, innermost first
+        while let Some(span) = open.last().filter(|s| s.end == pos) {
+            output.push_str(&close_markup(&span.formatting));
+            open.pop();
+        }
+        while let Some(span) = next.next_if(|s| s.start == pos) {
+            output.push_str(&open_markup(&span.formatting));
+            open.push(span);
+        }
+
+        // Advance to the next span boundary
+        let boundary = open
+            .iter()
+            .map(|s| s.end)
+            .chain(next.peek().map(|s| s.start))
+            .filter(|&b| b > pos)
+            .min()
+            .unwrap_or(text.len());
+        if boundary <= pos {
+            break;
+        }
+        let in_code = open.iter().any(|s| s.formatting.is_code());
+        output.push_str(&escape(&text[pos..boundary], in_code));
+        pos = boundary;
+    }
+
+    // Close anything left open by partially overlapping spans
+    while let Some(span) = open.pop() {
+        output.push_str(&close_markup(&span.formatting));
+    }
+
+    output
 }
 
 /// Text formatting
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
 pub enum Formatting {
     Bold,
     Italic,
     Code,
+    Strikethrough,
+    Link { url: String },
 }
 
 impl Formatting {
@@ -75,14 +356,104 @@ impl Formatting {
         matches!(self, Formatting::Italic)
     }
 
+    /// Create strikethrough formatting
+    pub fn strikethrough() -> Self {
+        Formatting::Strikethrough
+    }
+
+    /// Create a hyperlink
+    pub fn link(url: impl Into<String>) -> Self {
+        Formatting::Link { url: url.into() }
+    }
+
     /// Check if code
     pub fn is_code(&self) -> bool {
         matches!(self, Formatting::Code)
     }
+
+    /// Check if strikethrough
+    pub fn is_strikethrough(&self) -> bool {
+        matches!(self, Formatting::Strikethrough)
+    }
+
+    /// Get the link target, if this is a hyperlink
+    pub fn url(&self) -> Option<&str> {
+        match self {
+            Formatting::Link { url } => Some(url),
+            _ => None,
+        }
+    }
+
+    /// Whether this is a link whose URL [`is_safe_url`] rejects
+    ///
+    /// Such links are rendered as their plain text.
+    fn is_unsafe_link(&self) -> bool {
+        self.url().is_some_and(|url| !is_safe_url(url))
+    }
+
+    /// Markdown marker opening this formatting
+    fn opening_marker(&self) -> &'static str {
+        match self {
+            Formatting::Bold => "**",
+            Formatting::Italic => "*",
+            Formatting::Code => "`",
+            Formatting::Strikethrough => "~~",
+            Formatting::Link { .. } if self.is_unsafe_link() => "",
+            Formatting::Link { .. } => "[",
+        }
+    }
+
+    /// Markdown marker closing this formatting
+    fn closing_marker(&self) -> String {
+        match self {
+            Formatting::Link { .. } if self.is_unsafe_link() => String::new(),
+            Formatting::Link { url } => format!("]({})", url),
+            other => other.opening_marker().to_string(),
+        }
+    }
+
+    /// HTML tag opening this formatting
+    fn opening_tag(&self) -> String {
+        match self {
+            Formatting::Link { .. } if self.is_unsafe_link() => String::new(),
+            Formatting::Link { url } => format!("<a href=\"{}\">", escape_html(url)),
+            other => format!("<{}>", other.tag_name()),
+        }
+    }
+
+    /// HTML tag closing this formatting
+    fn closing_tag(&self) -> String {
+        match self {
+            Formatting::Link { .. } if self.is_unsafe_link() => String::new(),
+            other => format!("</{}>", other.tag_name()),
+        }
+    }
+
+    /// LaTeX command opening this formatting; a `}` closes it
+    fn opening_command(&self) -> String {
+        match self {
+            Formatting::Bold => "\\textbf{".to_string(),
+            Formatting::Italic => "\\emph{".to_string(),
+            Formatting::Code => "\\texttt{".to_string(),
+            Formatting::Strikethrough => "\\sout{".to_string(),
+            Formatting::Link { .. } if self.is_unsafe_link() => "{".to_string(),
+            Formatting::Link { url } => format!("\\href{{{}}}{{", escape_latex_url(url)),
+        }
+    }
+
+    fn tag_name(&self) -> &'static str {
+        match self {
+            Formatting::Bold => "strong",
+            Formatting::Italic => "em",
+            Formatting::Code => "code",
+            Formatting::Strikethrough => "del",
+            Formatting::Link { .. } => "a",
+        }
+    }
 }
 
 /// Text metadata
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct TextMetadata {
     // Placeholder - will be implemented in T015
 }
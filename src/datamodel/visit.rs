@@ -0,0 +1,134 @@
+//! Visitor-based traversal of the document tree
+
+use crate::datamodel::{DoclingDocument, NodeId, NodeType, TreeEntry};
+
+/// What [`walk`] does after entering a node
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Flow {
+    /// Visit the node's children
+    #[default]
+    Continue,
+    /// Skip the node's children (the node is still left)
+    SkipChildren,
+    /// End the traversal without leaving any open node
+    Stop,
+}
+
+/// Callbacks invoked by [`walk`] while traversing a document tree
+///
+/// All methods have empty defaults, so a visitor implements only what it needs.
+/// For every node, [`walk`] calls [`enter_node`](Self::enter_node), then the
+/// callback matching the node type, then recurses into the children, and
+/// finally calls [`leave_section`](Self::leave_section) for section headings and
+/// [`leave_node`](Self::leave_node):
+///
+/// - titles and headings open a section: their children are the section content
+/// - tables go to [`visit_table`](Self::visit_table)
+/// - pictures go to [`visit_picture`](Self::visit_picture)
+/// - section groups, lists, quotes, table rows and page breaks have no
+///   callback of their own
+/// - everything else (paragraphs, list items, code, formulas, ...) goes to
+///   [`visit_text`](Self::visit_text)
+///
+/// # Examples
+///
+/// ```ignore
+/// use docling_rs::datamodel::{walk, TreeEntry, Visitor};
+///
+/// struct Outline(Vec<String>);
+///
+/// impl Visitor for Outline {
+///     fn enter_section(&mut self, entry: TreeEntry<'_>) {
+///         let title = entry.node.text_content().unwrap_or_default();
+///         self.0.push(format!("{}{}", "  ".repeat(entry.depth), title));
+///     }
+/// }
+///
+/// let mut outline = Outline(Vec::new());
+/// walk(&doc, &mut outline);
+/// ```
+pub trait Visitor {
+    /// Called before anything else for every node
+    fn enter_node(&mut self, _entry: TreeEntry<'_>) -> Flow {
+        Flow::Continue
+    }
+
+    /// Called after a node and its children have been visited
+    fn leave_node(&mut self, _entry: TreeEntry<'_>) {}
+
+    /// Called for a title or heading, before the section content
+    fn enter_section(&mut self, _entry: TreeEntry<'_>) {}
+
+    /// Called for a title or heading, after the section content
+    fn leave_section(&mut self, _entry: TreeEntry<'_>) {}
+
+    /// Called for text-bearing nodes
+    fn visit_text(&mut self, _entry: TreeEntry<'_>) {}
+
+    /// Called for table nodes
+    fn visit_table(&mut self, _entry: TreeEntry<'_>) {}
+
+    /// Called for picture nodes
+    fn visit_picture(&mut self, _entry: TreeEntry<'_>) {}
+}
+
+/// Traverse the whole document depth-first, in reading order
+pub fn walk<V: Visitor + ?Sized>(doc: &DoclingDocument, visitor: &mut V) {
+    for (id, _) in doc.roots() {
+        if walk_from(doc, id, 0, visitor) == Flow::Stop {
+            return;
+        }
+    }
+}
+
+/// Traverse the subtree rooted at `id`
+///
+/// Depths passed to the visitor are relative to `id`. Does nothing if `id` is
+/// not a node of the document.
+pub fn walk_node<V: Visitor + ?Sized>(doc: &DoclingDocument, id: NodeId, visitor: &mut V) {
+    walk_from(doc, id, 0, visitor);
+}
+
+fn walk_from<V: Visitor + ?Sized>(
+    doc: &DoclingDocument,
+    id: NodeId,
+    depth: usize,
+    visitor: &mut V,
+) -> Flow {
+    let Some(node) = doc.node(id) else {
+        return Flow::Continue;
+    };
+    let entry = TreeEntry { id, depth, node };
+
+    let flow = visitor.enter_node(entry);
+    if flow == Flow::Stop {
+        return Flow::Stop;
+    }
+
+    let section = matches!(node.node_type(), NodeType::Title | NodeType::Heading);
+    match node.node_type() {
+        NodeType::Title | NodeType::Heading => visitor.enter_section(entry),
+        NodeType::Table => visitor.visit_table(entry),
+        NodeType::Picture => visitor.visit_picture(entry),
+        NodeType::Section
+        | NodeType::List
+        | NodeType::Quote
+        | NodeType::TableRow
+        | NodeType::PageBreak => {}
+        _ => visitor.visit_text(entry),
+    }
+
+    if flow == Flow::Continue {
+        for &child in node.children() {
+            if walk_from(doc, child, depth + 1, visitor) == Flow::Stop {
+                return Flow::Stop;
+            }
+        }
+    }
+
+    if section {
+        visitor.leave_section(entry);
+    }
+    visitor.leave_node(entry);
+    Flow::Continue
+}
@@ -0,0 +1,116 @@
+//! Minimal XLSX writer for single-table workbooks
+
+use std::io::{Cursor, Write};
+
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+use super::table::TableData;
+use super::xml::xml_text;
+use crate::error::ConversionError;
+
+const CONTENT_TYPES: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types"><Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/><Default Extension="xml" ContentType="application/xml"/><Override PartName="/xl/workbook.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.sheet.main+xml"/><Override PartName="/xl/worksheets/sheet1.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.worksheet+xml"/></Types>"#;
+
+const ROOT_RELS: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="xl/workbook.xml"/></Relationships>"#;
+
+const WORKBOOK: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<workbook xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships"><sheets><sheet name="Table" sheetId="1" r:id="rId1"/></sheets></workbook>"#;
+
+const WORKBOOK_RELS: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet" Target="worksheets/sheet1.xml"/></Relationships>"#;
+
+/// Write a table as a workbook with a single sheet
+///
+/// Cells holding plain numbers are written as numbers, everything else as
+/// text, and merged cells are merged in the sheet too.
+pub(crate) fn write_table(table: &TableData) -> Result<Vec<u8>, ConversionError> {
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    let parts = [
+        ("[Content_Types].xml", CONTENT_TYPES.to_string()),
+        ("_rels/.rels", ROOT_RELS.to_string()),
+        ("xl/workbook.xml", WORKBOOK.to_string()),
+        ("xl/_rels/workbook.xml.rels", WORKBOOK_RELS.to_string()),
+        ("xl/worksheets/sheet1.xml", sheet(table)),
+    ];
+    for (name, content) in parts {
+        zip.start_file(name, options)
+            .map_err(std::io::Error::other)?;
+        zip.write_all(content.as_bytes())?;
+    }
+    let cursor = zip.finish().map_err(std::io::Error::other)?;
+    Ok(cursor.into_inner())
+}
+
+/// Worksheet XML for a table
+fn sheet(table: &TableData) -> String {
+    let mut rows: Vec<String> = Vec::new();
+    let mut merges = Vec::new();
+
+    for (r, c, cell) in table.placed_cells() {
+        if rows.len() <= r {
+            rows.resize(r + 1, String::new());
+        }
+        let reference = cell_ref(r, c);
+        let content = cell.content();
+        rows[r].push_str(&if is_plain_number(content) {
+            format!("<c r=\"{}\"><v>{}</v></c>", reference, content)
+        } else {
+            format!(
+                "<c r=\"{}\" t=\"inlineStr\"><is><t xml:space=\"preserve\">{}</t></is></c>",
+                reference,
+                xml_text(content)
+            )
+        });
+        if cell.row_span() > 1 || cell.col_span() > 1 {
+            let end = cell_ref(
+                r + cell.row_span().max(1) - 1,
+                c + cell.col_span().max(1) - 1,
+            );
+            merges.push(format!("<mergeCell ref=\"{}:{}\"/>", reference, end));
+        }
+    }
+
+    let mut xml = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n\
+         <worksheet xmlns=\"http://schemas.openxmlformats.org/spreadsheetml/2006/main\"><sheetData>",
+    );
+    for (r, cells) in rows.iter().enumerate() {
+        xml.push_str(&format!("<row r=\"{}\">{}</row>", r + 1, cells));
+    }
+    xml.push_str("</sheetData>");
+    if !merges.is_empty() {
+        xml.push_str(&format!(
+            "<mergeCells count=\"{}\">{}</mergeCells>",
+            merges.len(),
+            merges.concat()
+        ));
+    }
+    xml.push_str("</worksheet>");
+    xml
+}
+
+/// Check whether a cell holds a number a spreadsheet can store as such
+///
+/// Numbers with leading zeros (codes, zip codes) stay text.
+fn is_plain_number(content: &str) -> bool {
+    let digits = content.trim_start_matches('-');
+    let leading_zero = digits.len() > 1 && digits.starts_with('0') && !digits.starts_with("0.");
+    !leading_zero
+        && digits.starts_with(|c: char| c.is_ascii_digit())
+        && content.parse::<f64>().is_ok_and(f64::is_finite)
+}
+
+/// A1-style reference of a zero-based position
+fn cell_ref(row: usize, col: usize) -> String {
+    let mut letters = Vec::new();
+    let mut n = col + 1;
+    while n > 0 {
+        let rem = (n - 1) % 26;
+        letters.push(char::from(b'A' + rem as u8));
+        n = (n - 1) / 26;
+    }
+    letters.iter().rev().collect::<String>() + &(row + 1).to_string()
+}
@@ -0,0 +1,223 @@
+//! XML serialization
+//!
+//! The document becomes a `<document>` element with one element per node,
+//! named after the node type in snake case and nested like the document tree:
+//!
+//! ```text
+//! <?xml version="1.0" encoding="UTF-8"?>
+//! <document name="report.pdf" format="pdf" pages="2">
+//!   <heading id="0" level="1" page="1" bbox="72,720,300,700">
+//!     <content>Results</content>
+//!     <paragraph id="1" page="1">Scores improved.</paragraph>
+//!   </heading>
+//!   <table id="2" page="2">
+//!     <caption>Scores</caption>
+//!     <row header="true"><cell>Name</cell><cell col-span="2">Score</cell></row>
+//!   </table>
+//! </document>
+//! ```
+//!
+//! Provenance goes in attributes: `id`, `page`, `bbox` (left, top, right,
+//! bottom in PDF points), `lines` and `offsets` (source ranges), plus `level`,
+//! `language` and `code-language` when set. A leaf node holds its text directly; a node with
+//! children puts its text in a `<content>` element before them. Tables with
+//! cell data are written as `<row>` and `<cell>` elements, pictures as an
+//! `<image>` reference (image bytes are not included).
+
+use crate::datamodel::text::escape_html;
+use crate::datamodel::{DoclingDocument, DocumentNode, ImageRef, NodeId, NodeType, TableData};
+
+/// Serialize a document to XML
+pub fn to_string(doc: &DoclingDocument) -> String {
+    let metadata = doc.metadata();
+    let mut attributes = vec![("name", doc.name().to_string())];
+    if let Some(format) = metadata.format() {
+        attributes.push(("format", format!("{:?}", format).to_lowercase()));
+    }
+    if let Some(language) = metadata.language() {
+        attributes.push(("language", language.to_string()));
+    }
+    if let Some(pages) = metadata.page_count() {
+        attributes.push(("pages", pages.to_string()));
+    }
+
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    out.push_str(&format!("<document{}>\n", attribute_list(&attributes)));
+    for (id, _) in doc.roots() {
+        write_node(doc, id, 1, &mut out);
+    }
+    out.push_str("</document>\n");
+    out
+}
+
+/// Element name of a node type
+fn element_name(node_type: NodeType) -> &'static str {
+    match node_type {
+        NodeType::Text => "text",
+        NodeType::Title => "title",
+        NodeType::Heading => "heading",
+        NodeType::Section => "section",
+        NodeType::Paragraph => "paragraph",
+        NodeType::List => "list",
+        NodeType::ListItem => "list_item",
+        NodeType::Quote => "quote",
+        NodeType::Table => "table",
+        NodeType::TableRow => "table_row",
+        NodeType::TableCell => "table_cell",
+        NodeType::CodeBlock => "code_block",
+        NodeType::Formula => "formula",
+        NodeType::Caption => "caption",
+        NodeType::Footnote => "footnote",
+        NodeType::Picture => "picture",
+        NodeType::PageBreak => "page_break",
+    }
+}
+
+fn write_node(doc: &DoclingDocument, id: NodeId, depth: usize, out: &mut String) {
+    let Some(node) = doc.node(id) else {
+        return;
+    };
+    let indent = "  ".repeat(depth);
+    let name = element_name(node.node_type());
+    let text = xml_text(node.text_content().unwrap_or_default());
+
+    // Body elements: caption, picture reference, table cells, then children
+    let mut body = Vec::new();
+    if let Some(caption) = node.caption() {
+        body.push(format!("<caption>{}</caption>", xml_text(caption)));
+    }
+    if let Some(picture) = node.picture() {
+        let mut attributes = match picture.image() {
+            ImageRef::File { uri } => vec![("src", uri.clone())],
+            ImageRef::Stored { digest } => vec![("digest", digest.clone())],
+            ImageRef::Inline { .. } => vec![("inline", "true".to_string())],
+        };
+        if let Some(mimetype) = picture.mimetype() {
+            attributes.push(("mimetype", mimetype.to_string()));
+        }
+        if let (Some(width), Some(height)) = (picture.width(), picture.height()) {
+            attributes.push(("width", width.to_string()));
+            attributes.push(("height", height.to_string()));
+        }
+        body.push(format!("<image{}/>", attribute_list(&attributes)));
+    }
+    let table = node.table().filter(|table| !table.rows().is_empty());
+    if let Some(table) = table {
+        body.extend(table_rows(table));
+    }
+
+    // Row and cell nodes duplicate the table data
+    let children: &[NodeId] = if table.is_some() {
+        &[]
+    } else {
+        node.children()
+    };
+
+    let open = format!("{}{}", name, attribute_list(&provenance(id, node)));
+    if body.is_empty() && children.is_empty() {
+        if text.is_empty() {
+            out.push_str(&format!("{}<{}/>\n", indent, open));
+        } else {
+            out.push_str(&format!("{}<{}>{}</{}>\n", indent, open, text, name));
+        }
+        return;
+    }
+
+    out.push_str(&format!("{}<{}>\n", indent, open));
+    // Table text duplicates the cells when there is table data
+    if !text.is_empty() && table.is_none() {
+        out.push_str(&format!("{}  <content>{}</content>\n", indent, text));
+    }
+    for element in body {
+        out.push_str(&format!("{}  {}\n", indent, element));
+    }
+    for &child in children {
+        write_node(doc, child, depth + 1, out);
+    }
+    out.push_str(&format!("{}</{}>\n", indent, name));
+}
+
+/// Provenance attributes of a node
+fn provenance(id: NodeId, node: &DocumentNode) -> Vec<(&'static str, String)> {
+    let mut attributes = vec![("id", id.index().to_string())];
+    if let Some(level) = node.level() {
+        attributes.push(("level", level.to_string()));
+    }
+    if let Some(language) = node.language() {
+        attributes.push(("language", language.to_string()));
+    }
+    if let Some(language) = node.code_language() {
+        attributes.push(("code-language", language.to_string()));
+    }
+    if node.is_ordered() {
+        attributes.push(("ordered", "true".to_string()));
+    }
+    if let Some(page) = node.page() {
+        attributes.push(("page", page.to_string()));
+    }
+    if let Some(bbox) = node.bbox() {
+        attributes.push((
+            "bbox",
+            format!("{},{},{},{}", bbox.left, bbox.top, bbox.right, bbox.bottom),
+        ));
+    }
+    if let Some(position) = node.position() {
+        attributes.push((
+            "lines",
+            format!("{}-{}", position.start_line(), position.end_line()),
+        ));
+        attributes.push((
+            "offsets",
+            format!("{}-{}", position.start_offset(), position.end_offset()),
+        ));
+    }
+    attributes
+}
+
+/// `<row>` elements of a table, header rows marked
+fn table_rows(table: &TableData) -> Vec<String> {
+    let headers = table.num_header_rows();
+    table
+        .rows()
+        .iter()
+        .enumerate()
+        .map(|(r, row)| {
+            let cells: String = row
+                .cells()
+                .iter()
+                .map(|cell| {
+                    let mut attributes = Vec::new();
+                    if cell.row_span() > 1 {
+                        attributes.push(("row-span", cell.row_span().to_string()));
+                    }
+                    if cell.col_span() > 1 {
+                        attributes.push(("col-span", cell.col_span().to_string()));
+                    }
+                    format!(
+                        "<cell{}>{}</cell>",
+                        attribute_list(&attributes),
+                        xml_text(cell.content())
+                    )
+                })
+                .collect();
+            let header = if r < headers { " header=\"true\"" } else { "" };
+            format!("<row{}>{}</row>", header, cells)
+        })
+        .collect()
+}
+
+fn attribute_list(attributes: &[(&str, String)]) -> String {
+    attributes
+        .iter()
+        .map(|(name, value)| format!(" {}=\"{}\"", name, xml_text(value)))
+        .collect()
+}
+
+/// Escape text for XML, dropping control characters XML cannot represent
+pub(crate) fn xml_text(text: &str) -> String {
+    let text: String = text
+        .chars()
+        .filter(|&c| !c.is_control() || matches!(c, '\t' | '\n' | '\r'))
+        .collect();
+    escape_html(&text)
+}
@@ -0,0 +1,75 @@
+//! Feature-hashing embedder
+
+use super::{Embedder, EmbeddingError};
+
+/// Default number of dimensions of [`HashEmbedder`]
+const DEFAULT_DIMENSIONS: usize = 256;
+
+/// Embeds texts by hashing their lowercased words into a fixed number of
+/// dimensions (the "hashing trick"), then normalizing to unit length
+///
+/// Needs no model and gives the same vectors on every platform, so it suits
+/// tests and keyword-style similarity. Texts sharing words get close vectors;
+/// synonyms do not.
+#[derive(Debug, Clone)]
+pub struct HashEmbedder {
+    dimensions: usize,
+    model: String,
+}
+
+impl HashEmbedder {
+    /// Create an embedder producing vectors of `dimensions` dimensions
+    pub fn new(dimensions: usize) -> Self {
+        let dimensions = dimensions.max(1);
+        Self {
+            dimensions,
+            model: format!("hash:{}", dimensions),
+        }
+    }
+
+    /// Number of dimensions of the vectors
+    pub fn dimensions(&self) -> usize {
+        self.dimensions
+    }
+
+    fn embed_one(&self, text: &str) -> Vec<f32> {
+        let mut vector = vec![0.0f32; self.dimensions];
+        for word in text
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+        {
+            let hash = fnv1a(&word.to_lowercase());
+            let index = (hash % self.dimensions as u64) as usize;
+            // The sign comes from another bit, so collisions tend to cancel
+            vector[index] += if hash & (1 << 63) == 0 { 1.0 } else { -1.0 };
+        }
+        let norm = vector.iter().map(|x| x * x).sum::<f32>().sqrt();
+        if norm > 0.0 {
+            vector.iter_mut().for_each(|x| *x /= norm);
+        }
+        vector
+    }
+}
+
+impl Default for HashEmbedder {
+    fn default() -> Self {
+        Self::new(DEFAULT_DIMENSIONS)
+    }
+}
+
+impl Embedder for HashEmbedder {
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    fn embed(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>, EmbeddingError> {
+        Ok(texts.iter().map(|text| self.embed_one(text)).collect())
+    }
+}
+
+/// 64-bit FNV-1a hash, stable across platforms and releases
+fn fnv1a(text: &str) -> u64 {
+    text.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
@@ -0,0 +1,171 @@
+//! Chunk embeddings
+//!
+//! An [`Embedder`] turns texts into vectors, so chunks can be written out
+//! ready for a vector store. Embedders are picked by a textual specification
+//! with [`from_spec`] (the CLI's `--embedding-model`):
+//!
+//! - `hash` or `hash:<dimensions>`: the built-in [`HashEmbedder`], which
+//!   hashes words into a fixed number of dimensions; it needs no model and
+//!   captures lexical overlap only
+//! - `openai:<model>`: a model served by an OpenAI-compatible `/embeddings`
+//!   endpoint, see [`OpenAiEmbedder`] (requires the `openai` feature)
+//! - `onnx:<dir>` or `onnx:<model id>`: a sentence-transformers model run
+//!   locally, from a directory or downloaded from the HuggingFace Hub, see
+//!   [`OnnxEmbedder`] (requires the `onnx` feature, and `hub` for downloads)
+//!
+//! Chunks are embedded by their contextualized text (headings and caption
+//! prepended), as produced by
+//! [`BaseChunker::contextualize`](crate::chunking::BaseChunker::contextualize):
+//!
+//! ```ignore
+//! use docling_rs::chunking::{ApproxTokenizer, BaseChunker, ChunkRecord, HierarchicalChunker};
+//! use docling_rs::embeddings;
+//!
+//! let chunker = HierarchicalChunker::new();
+//! let mut records: Vec<ChunkRecord> = chunker
+//!     .chunk(&doc)
+//!     .map(|chunk| ChunkRecord::new(chunk, &chunker, &ApproxTokenizer::new()))
+//!     .collect();
+//! embeddings::embed_records(&mut records, embeddings::from_spec("hash:384")?.as_ref())?;
+//! ```
+
+mod hashing;
+#[cfg(feature = "onnx")]
+mod onnx;
+#[cfg(feature = "openai")]
+mod openai;
+
+pub use hashing::HashEmbedder;
+#[cfg(feature = "onnx")]
+pub use onnx::{OnnxEmbedder, Pooling};
+#[cfg(feature = "openai")]
+pub use openai::OpenAiEmbedder;
+
+use crate::chunking::ChunkRecord;
+
+/// Error type for embedding operations
+#[derive(Debug, thiserror::Error)]
+pub enum EmbeddingError {
+    #[error("Invalid configuration: {0}")]
+    InvalidConfig(String),
+
+    #[error("Embedding request failed: {0}")]
+    Request(String),
+
+    #[error("Invalid embedding response: {0}")]
+    InvalidResponse(String),
+
+    #[error("Embedding model failed: {0}")]
+    Inference(String),
+}
+
+/// Computes embedding vectors for texts
+pub trait Embedder: Send + Sync {
+    /// Identifier of the model producing the vectors
+    fn model(&self) -> &str;
+
+    /// Embed `texts`, returning one vector per text, in order
+    fn embed(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>, EmbeddingError>;
+}
+
+/// Build an embedder from a textual specification, see the
+/// [module documentation](self)
+pub fn from_spec(spec: &str) -> Result<Box<dyn Embedder>, EmbeddingError> {
+    match spec.split_once(':') {
+        None if spec == "hash" => Ok(Box::new(HashEmbedder::default())),
+        Some(("hash", dimensions)) => {
+            let dimensions = dimensions
+                .parse()
+                .ok()
+                .filter(|&dimensions| dimensions > 0)
+                .ok_or_else(|| {
+                    EmbeddingError::InvalidConfig(format!(
+                        "invalid hash embedding dimensions '{}'",
+                        dimensions
+                    ))
+                })?;
+            Ok(Box::new(HashEmbedder::new(dimensions)))
+        }
+        Some(("openai", model)) => openai_embedder(model),
+        Some(("onnx", model)) => onnx_embedder(model),
+        _ => Err(EmbeddingError::InvalidConfig(format!(
+            "unsupported embedding model '{}' (expected hash[:<dimensions>], openai:<model> \
+             or onnx:<model>)",
+            spec
+        ))),
+    }
+}
+
+#[cfg(feature = "openai")]
+fn openai_embedder(model: &str) -> Result<Box<dyn Embedder>, EmbeddingError> {
+    Ok(Box::new(OpenAiEmbedder::new(model)))
+}
+
+#[cfg(not(feature = "openai"))]
+fn openai_embedder(_model: &str) -> Result<Box<dyn Embedder>, EmbeddingError> {
+    Err(EmbeddingError::InvalidConfig(
+        "openai embedding models require docling-rs to be built with the `openai` feature"
+            .to_string(),
+    ))
+}
+
+#[cfg(feature = "onnx")]
+fn onnx_embedder(model: &str) -> Result<Box<dyn Embedder>, EmbeddingError> {
+    if std::path::Path::new(model).is_dir() {
+        return Ok(Box::new(OnnxEmbedder::from_dir(model)?));
+    }
+    #[cfg(feature = "hub")]
+    {
+        let options = crate::chunking::tokenizer::HubOptions::default();
+        Ok(Box::new(OnnxEmbedder::from_hub(model, &options)?))
+    }
+    #[cfg(not(feature = "hub"))]
+    Err(EmbeddingError::InvalidConfig(format!(
+        "no model directory {} (downloading models from the HuggingFace Hub requires \
+         docling-rs to be built with the `hub` feature)",
+        model
+    )))
+}
+
+#[cfg(not(feature = "onnx"))]
+fn onnx_embedder(_model: &str) -> Result<Box<dyn Embedder>, EmbeddingError> {
+    Err(EmbeddingError::InvalidConfig(
+        "onnx embedding models require docling-rs to be built with the `onnx` feature".to_string(),
+    ))
+}
+
+/// Cosine similarity of `a` and `b`, 0 if either is all zeros
+pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm = |v: &[f32]| v.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norms = norm(a) * norm(b);
+    if norms > 0.0 {
+        dot / norms
+    } else {
+        0.0
+    }
+}
+
+/// Embed the contextualized text of every record, filling in
+/// [`ChunkRecord::embedding`]
+pub fn embed_records(
+    records: &mut [ChunkRecord],
+    embedder: &dyn Embedder,
+) -> Result<(), EmbeddingError> {
+    let texts: Vec<&str> = records
+        .iter()
+        .map(|record| record.contextualized_text.as_str())
+        .collect();
+    let vectors = embedder.embed(&texts)?;
+    if vectors.len() != records.len() {
+        return Err(EmbeddingError::InvalidResponse(format!(
+            "expected {} vectors, got {}",
+            records.len(),
+            vectors.len()
+        )));
+    }
+    for (record, vector) in records.iter_mut().zip(vectors) {
+        record.embedding = Some(vector);
+    }
+    Ok(())
+}
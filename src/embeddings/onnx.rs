@@ -0,0 +1,317 @@
+//! Local sentence-transformer models run with ONNX Runtime
+
+use super::{Embedder, EmbeddingError};
+#[cfg(feature = "hub")]
+use crate::chunking::tokenizer::HubOptions;
+use crate::chunking::tokenizer::{HuggingFaceTokenizer, Tokenizer};
+use ndarray::{Array2, ArrayViewD, Axis};
+use ort::session::{Session, SessionInputValue};
+use ort::value::Tensor;
+use std::borrow::Cow;
+use std::path::Path;
+
+/// Default number of texts per inference run
+const DEFAULT_BATCH_SIZE: usize = 32;
+
+/// How the token embeddings of a text are combined into one vector
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Pooling {
+    /// Average of the token embeddings, padding excluded (most
+    /// sentence-transformers models)
+    #[default]
+    Mean,
+
+    /// Embedding of the first token, `[CLS]` (BGE models, among others)
+    Cls,
+}
+
+/// Embeds texts with a sentence-transformers model exported to ONNX, run
+/// locally with ONNX Runtime, so chunks are embedded without any network
+/// access
+///
+/// Texts are tokenized with the model's `tokenizer.json`, through the same
+/// [`HuggingFaceTokenizer`] used for token-aware chunking, and truncated to
+/// its [`max_tokens`](Tokenizer::max_tokens). Models either output pooled
+/// `sentence_embedding`s or token embeddings, which are pooled as given by
+/// [`Pooling`]; vectors are then normalized to unit length.
+///
+/// ONNX Runtime is loaded when the first model is: `libonnxruntime.so`
+/// (`.dylib`, `onnxruntime.dll`) from the library search path or next to the
+/// executable, or the file named by `ORT_DYLIB_PATH`. It must be a 1.20
+/// release or later.
+///
+/// ```ignore
+/// use docling_rs::embeddings::{Embedder, OnnxEmbedder};
+///
+/// // optimum-cli export onnx --model sentence-transformers/all-MiniLM-L6-v2 all-MiniLM-L6-v2/
+/// let embedder = OnnxEmbedder::from_dir("all-MiniLM-L6-v2")?;
+/// let vectors = embedder.embed(&["Revenue grew in every region."])?;
+/// ```
+pub struct OnnxEmbedder {
+    session: Session,
+    tokenizer: HuggingFaceTokenizer,
+    model: String,
+    /// Model output holding the embeddings
+    output: String,
+    /// Whether the model takes `token_type_ids`
+    token_types: bool,
+    pooling: Pooling,
+    normalize: bool,
+    batch_size: usize,
+}
+
+impl OnnxEmbedder {
+    /// Load the model in `dir`: `model.onnx` (or `onnx/model.onnx`, as in
+    /// sentence-transformers repositories) and `tokenizer.json`
+    ///
+    /// The pooling comes from `1_Pooling/config.json` when there is one.
+    pub fn from_dir(dir: impl AsRef<Path>) -> Result<Self, EmbeddingError> {
+        let dir = dir.as_ref();
+        let model_path = [dir.join("model.onnx"), dir.join("onnx").join("model.onnx")]
+            .into_iter()
+            .find(|path| path.is_file())
+            .ok_or_else(|| {
+                EmbeddingError::InvalidConfig(format!(
+                    "no model.onnx or onnx/model.onnx in {}",
+                    dir.display()
+                ))
+            })?;
+        let tokenizer = load_tokenizer(&dir.join("tokenizer.json"))?;
+        let name = dir
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| dir.display().to_string());
+
+        let embedder = Self::new(&model_path, tokenizer, name)?;
+        let pooling = read_pooling(&dir.join("1_Pooling").join("config.json"));
+        Ok(match pooling {
+            Some(pooling) => embedder.with_pooling(pooling),
+            None => embedder,
+        })
+    }
+
+    /// Download `model_id` from the HuggingFace Hub (its `onnx/model.onnx`
+    /// and `tokenizer.json`) and load it; files are cached as described for
+    /// [`HubOptions`]. Requires the `hub` feature.
+    #[cfg(feature = "hub")]
+    pub fn from_hub(model_id: &str, options: &HubOptions) -> Result<Self, EmbeddingError> {
+        use crate::chunking::tokenizer::hub::fetch_file;
+
+        let fetch = |filename: &str| {
+            fetch_file(model_id, filename, options)
+                .map_err(|e| EmbeddingError::InvalidConfig(e.to_string()))
+        };
+        let model_path = fetch("onnx/model.onnx")?;
+        let tokenizer = load_tokenizer(&fetch("tokenizer.json")?)?;
+
+        let embedder = Self::new(&model_path, tokenizer, model_id.to_string())?;
+        // Models without a pooling configuration use mean pooling
+        let pooling = fetch("1_Pooling/config.json")
+            .ok()
+            .and_then(|path| read_pooling(&path));
+        Ok(match pooling {
+            Some(pooling) => embedder.with_pooling(pooling),
+            None => embedder,
+        })
+    }
+
+    /// Load the ONNX model at `model_path`, which reads the tokens of
+    /// `tokenizer`, identified as `model`
+    pub fn new(
+        model_path: &Path,
+        tokenizer: HuggingFaceTokenizer,
+        model: impl Into<String>,
+    ) -> Result<Self, EmbeddingError> {
+        // ort panics when ONNX Runtime cannot be loaded
+        let session = std::panic::catch_unwind(|| {
+            Session::builder().and_then(|builder| builder.commit_from_file(model_path))
+        })
+        .map_err(|_| {
+            EmbeddingError::InvalidConfig(
+                "cannot load ONNX Runtime: install it or set ORT_DYLIB_PATH".to_string(),
+            )
+        })?
+        .map_err(|e| {
+            EmbeddingError::InvalidConfig(format!("cannot load {}: {}", model_path.display(), e))
+        })?;
+
+        let names: Vec<&str> = session.outputs.iter().map(|o| o.name.as_str()).collect();
+        let output = ["sentence_embedding", "last_hidden_state"]
+            .into_iter()
+            .find(|name| names.contains(name))
+            .or(names.first().copied())
+            .ok_or_else(|| {
+                EmbeddingError::InvalidConfig(format!("{} has no outputs", model_path.display()))
+            })?
+            .to_string();
+        let token_types = session
+            .inputs
+            .iter()
+            .any(|input| input.name == "token_type_ids");
+
+        Ok(Self {
+            session,
+            tokenizer,
+            model: model.into(),
+            output,
+            token_types,
+            pooling: Pooling::default(),
+            normalize: true,
+            batch_size: DEFAULT_BATCH_SIZE,
+        })
+    }
+
+    /// Pool token embeddings with `pooling` (default: mean)
+    pub fn with_pooling(mut self, pooling: Pooling) -> Self {
+        self.pooling = pooling;
+        self
+    }
+
+    /// Normalize vectors to unit length or not (default: normalized)
+    pub fn with_normalize(mut self, normalize: bool) -> Self {
+        self.normalize = normalize;
+        self
+    }
+
+    /// Run the model on at most `batch_size` texts at a time (default: 32)
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Embed one batch of texts
+    fn embed_batch(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>, EmbeddingError> {
+        let encodings = self
+            .tokenizer
+            .inner()
+            .encode_batch(texts.to_vec(), true)
+            .map_err(|e| EmbeddingError::Inference(e.to_string()))?;
+
+        // Truncated to the model's limit, keeping the closing special token,
+        // and padded to the longest text
+        let limit = self.tokenizer.max_tokens().max(2);
+        let columns = encodings
+            .iter()
+            .map(|encoding| encoding.get_ids().len().min(limit))
+            .max()
+            .unwrap_or(0);
+        let mut ids = Array2::<i64>::zeros((texts.len(), columns));
+        let mut mask = Array2::<i64>::zeros((texts.len(), columns));
+        let mut types = Array2::<i64>::zeros((texts.len(), columns));
+        for (row, encoding) in encodings.iter().enumerate() {
+            let length = encoding.get_ids().len();
+            let positions =
+                (0..length.min(limit - 1)).chain((length > limit).then_some(length - 1));
+            for (column, position) in positions.enumerate() {
+                ids[[row, column]] = i64::from(encoding.get_ids()[position]);
+                mask[[row, column]] = i64::from(encoding.get_attention_mask()[position]);
+                types[[row, column]] = i64::from(encoding.get_type_ids()[position]);
+            }
+        }
+
+        let tensor = |array: Array2<i64>| {
+            Tensor::from_array(array)
+                .map(|tensor| SessionInputValue::from(tensor.into_dyn()))
+                .map_err(|e| EmbeddingError::Inference(e.to_string()))
+        };
+        let mut inputs: Vec<(Cow<'_, str>, SessionInputValue<'_>)> = vec![
+            ("input_ids".into(), tensor(ids)?),
+            ("attention_mask".into(), tensor(mask.clone())?),
+        ];
+        if self.token_types {
+            inputs.push(("token_type_ids".into(), tensor(types)?));
+        }
+        let outputs = self
+            .session
+            .run(inputs)
+            .map_err(|e| EmbeddingError::Inference(e.to_string()))?;
+        let embeddings = outputs
+            .get(&self.output)
+            .ok_or_else(|| EmbeddingError::Inference(format!("no {} output", self.output)))?
+            .try_extract_tensor::<f32>()
+            .map_err(|e| EmbeddingError::Inference(e.to_string()))?;
+
+        let mut vectors = match embeddings.ndim() {
+            2 => embeddings
+                .outer_iter()
+                .map(|row| row.iter().copied().collect())
+                .collect(),
+            3 => self.pool(&embeddings, &mask),
+            n => {
+                return Err(EmbeddingError::Inference(format!(
+                    "{} has {} dimensions, expected 2 or 3",
+                    self.output, n
+                )))
+            }
+        };
+        if self.normalize {
+            vectors.iter_mut().for_each(|vector| normalize(vector));
+        }
+        Ok(vectors)
+    }
+
+    /// Pool token embeddings (texts × tokens × dimensions) into one vector
+    /// per text
+    fn pool(&self, embeddings: &ArrayViewD<'_, f32>, mask: &Array2<i64>) -> Vec<Vec<f32>> {
+        embeddings
+            .outer_iter()
+            .zip(mask.outer_iter())
+            .map(|(tokens, mask)| match self.pooling {
+                Pooling::Cls => tokens.index_axis(Axis(0), 0).iter().copied().collect(),
+                Pooling::Mean => {
+                    let mut sum = vec![0.0f32; tokens.shape().get(1).copied().unwrap_or(0)];
+                    let mut count = 0.0f32;
+                    for (token, _) in tokens.outer_iter().zip(&mask).filter(|(_, &m)| m != 0) {
+                        sum.iter_mut().zip(token.iter()).for_each(|(s, x)| *s += x);
+                        count += 1.0;
+                    }
+                    if count > 0.0 {
+                        sum.iter_mut().for_each(|s| *s /= count);
+                    }
+                    sum
+                }
+            })
+            .collect()
+    }
+}
+
+impl Embedder for OnnxEmbedder {
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    fn embed(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>, EmbeddingError> {
+        let mut vectors = Vec::with_capacity(texts.len());
+        for batch in texts.chunks(self.batch_size) {
+            vectors.extend(self.embed_batch(batch)?);
+        }
+        Ok(vectors)
+    }
+}
+
+fn load_tokenizer(path: &Path) -> Result<HuggingFaceTokenizer, EmbeddingError> {
+    let path = path
+        .to_str()
+        .ok_or_else(|| EmbeddingError::InvalidConfig("Invalid path encoding".to_string()))?;
+    HuggingFaceTokenizer::from_file(path).map_err(|e| EmbeddingError::InvalidConfig(e.to_string()))
+}
+
+/// Pooling of a sentence-transformers `1_Pooling/config.json`, if readable
+fn read_pooling(path: &Path) -> Option<Pooling> {
+    let config: serde_json::Value = serde_json::from_slice(&std::fs::read(path).ok()?).ok()?;
+    if config["pooling_mode_cls_token"] == true {
+        Some(Pooling::Cls)
+    } else if config["pooling_mode_mean_tokens"] == true {
+        Some(Pooling::Mean)
+    } else {
+        None
+    }
+}
+
+/// Scale `vector` to unit length, leaving all-zero vectors alone
+fn normalize(vector: &mut [f32]) {
+    let norm = vector.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        vector.iter_mut().for_each(|x| *x /= norm);
+    }
+}
@@ -0,0 +1,190 @@
+//! Client for OpenAI-compatible embedding endpoints
+
+use super::{Embedder, EmbeddingError};
+use serde::Deserialize;
+use std::time::Duration;
+
+/// Default API base URL
+const DEFAULT_BASE_URL: &str = "https://api.openai.com/v1";
+
+/// Default number of texts per request (OpenAI accepts up to 2048)
+const DEFAULT_BATCH_SIZE: usize = 256;
+
+/// Embeds texts with a model served by an OpenAI-compatible `/embeddings`
+/// endpoint (OpenAI, Azure OpenAI, vLLM, Ollama, LiteLLM, ...)
+///
+/// [`OpenAiEmbedder::new`] reads the usual environment variables:
+///
+/// - `OPENAI_BASE_URL`: API base URL (default: `https://api.openai.com/v1`)
+/// - `OPENAI_API_KEY`: key sent as a bearer token, if set
+///
+/// Texts are sent in batches. Requests refused for rate limiting (`429`),
+/// failing with a `5xx` or a network error are retried, after the delay
+/// given by the `Retry-After` header or else with exponential backoff.
+#[derive(Debug, Clone)]
+pub struct OpenAiEmbedder {
+    model: String,
+    base_url: String,
+    api_key: Option<String>,
+    timeout: Duration,
+    batch_size: usize,
+    max_retries: u32,
+    retry_delay: Duration,
+}
+
+impl OpenAiEmbedder {
+    /// Create a client for `model`, configured from the environment
+    pub fn new(model: &str) -> Self {
+        Self {
+            model: model.to_string(),
+            base_url: std::env::var("OPENAI_BASE_URL")
+                .ok()
+                .filter(|url| !url.is_empty())
+                .unwrap_or_else(|| DEFAULT_BASE_URL.to_string()),
+            api_key: std::env::var("OPENAI_API_KEY")
+                .ok()
+                .filter(|key| !key.is_empty()),
+            timeout: Duration::from_secs(60),
+            batch_size: DEFAULT_BATCH_SIZE,
+            max_retries: 5,
+            retry_delay: Duration::from_secs(1),
+        }
+    }
+
+    /// Send requests to `base_url` (the part before `/embeddings`)
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+
+    /// Authenticate with `api_key`
+    pub fn with_api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = Some(api_key.into());
+        self
+    }
+
+    /// Send at most `batch_size` texts per request (default: 256)
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Retry a failed request up to `max_retries` times (default: 5)
+    pub fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Wait `delay` before the first retry when the server does not say how
+    /// long to wait, doubling it for each further one (default: 1 s)
+    pub fn with_retry_delay(mut self, delay: Duration) -> Self {
+        self.retry_delay = delay;
+        self
+    }
+
+    /// Give up on a request after `timeout` (default: 60 s)
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Send a request for `texts`, retrying as described on the type
+    fn send(&self, url: &str, texts: &[&str]) -> Result<ureq::Response, EmbeddingError> {
+        let body = serde_json::json!({ "model": self.model, "input": texts }).to_string();
+        let mut delay = self.retry_delay;
+        let mut attempt = 0;
+        loop {
+            let mut request = ureq::post(url)
+                .timeout(self.timeout)
+                .set("Content-Type", "application/json");
+            if let Some(key) = &self.api_key {
+                request = request.set("Authorization", &format!("Bearer {}", key));
+            }
+            let wait = match request.send_string(&body) {
+                Ok(response) => return Ok(response),
+                Err(ureq::Error::Status(status, response))
+                    if attempt < self.max_retries && (status == 429 || status >= 500) =>
+                {
+                    retry_after(&response).unwrap_or(delay)
+                }
+                Err(ureq::Error::Transport(_)) if attempt < self.max_retries => delay,
+                Err(ureq::Error::Status(status, response)) => {
+                    let message = response.into_string().unwrap_or_default();
+                    return Err(EmbeddingError::Request(format!(
+                        "{} returned {}: {}",
+                        url,
+                        status,
+                        message.trim()
+                    )));
+                }
+                Err(e) => return Err(EmbeddingError::Request(format!("{}: {}", url, e))),
+            };
+            tracing::debug!("Retrying {} in {:?}", url, wait);
+            std::thread::sleep(wait);
+            delay *= 2;
+            attempt += 1;
+        }
+    }
+
+    /// Embed one batch of texts
+    fn embed_batch(&self, url: &str, texts: &[&str]) -> Result<Vec<Vec<f32>>, EmbeddingError> {
+        let text = self
+            .send(url, texts)?
+            .into_string()
+            .map_err(|e| EmbeddingError::Request(format!("{}: {}", url, e)))?;
+        let mut parsed: EmbeddingResponse = serde_json::from_str(&text)
+            .map_err(|e| EmbeddingError::InvalidResponse(e.to_string()))?;
+
+        if parsed.data.len() != texts.len() {
+            return Err(EmbeddingError::InvalidResponse(format!(
+                "expected {} vectors, got {}",
+                texts.len(),
+                parsed.data.len()
+            )));
+        }
+        parsed.data.sort_by_key(|data| data.index);
+        Ok(parsed.data.into_iter().map(|data| data.embedding).collect())
+    }
+}
+
+/// How long the server asks to wait before retrying, from `retry-after-ms`
+/// (sent by OpenAI) or `Retry-After` in seconds
+fn retry_after(response: &ureq::Response) -> Option<Duration> {
+    if let Some(ms) = response
+        .header("retry-after-ms")
+        .and_then(|value| value.trim().parse::<f64>().ok())
+        .filter(|ms| ms.is_finite() && *ms >= 0.0)
+    {
+        return Some(Duration::from_secs_f64(ms / 1000.0));
+    }
+    response
+        .header("retry-after")
+        .and_then(|value| value.trim().parse::<u64>().ok())
+        .map(Duration::from_secs)
+}
+
+#[derive(Deserialize)]
+struct EmbeddingResponse {
+    data: Vec<EmbeddingData>,
+}
+
+#[derive(Deserialize)]
+struct EmbeddingData {
+    index: usize,
+    embedding: Vec<f32>,
+}
+
+impl Embedder for OpenAiEmbedder {
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    fn embed(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>, EmbeddingError> {
+        let url = format!("{}/embeddings", self.base_url.trim_end_matches('/'));
+        let mut vectors = Vec::with_capacity(texts.len());
+        for batch in texts.chunks(self.batch_size) {
+            vectors.extend(self.embed_batch(&url, batch)?);
+        }
+        Ok(vectors)
+    }
+}
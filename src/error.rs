@@ -3,6 +3,9 @@
 use std::path::PathBuf;
 use thiserror::Error;
 
+use crate::datamodel::SchemaError;
+use crate::pipeline::ExceededLimit;
+
 /// Errors during document conversion
 #[derive(Debug, Error)]
 pub enum ConversionError {
@@ -26,4 +29,19 @@ pub enum ConversionError {
 
     #[error("Serialization error: {0}")]
     Serialization(#[from] serde_json::Error),
+
+    #[error("Schema error: {0}")]
+    Schema(#[from] SchemaError),
+
+    #[error("Pipeline stage {stage} failed: {message}")]
+    Stage { stage: String, message: String },
+
+    #[error("Limit exceeded: {0}")]
+    LimitExceeded(ExceededLimit),
+
+    #[error("Configuration error: {0}")]
+    Config(String),
+
+    #[error("Remote source error: {0}")]
+    Remote(String),
 }
@@ -1,26 +1,44 @@
-//! Input format detection and enumeration
+//! Input format detection and enumeration, and output formats
 
+use crate::backend::code::CodeLanguage;
+use clap::ValueEnum;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 /// Supported input document formats
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
 pub enum InputFormat {
     Markdown,
     Html,
     Csv,
     Docx,
     PDF,
+    Text,
+    Code,
 }
 
 impl InputFormat {
     /// Get file extension for this format
+    ///
+    /// [`Code`](Self::Code) files keep the extension of their language, so it
+    /// falls back to `txt`; see [`fixed_extension`](Self::fixed_extension).
     pub fn extension(&self) -> &'static str {
+        self.fixed_extension().unwrap_or("txt")
+    }
+
+    /// The one file extension of this format
+    ///
+    /// `None` for [`Code`](Self::Code), whose files keep the extension of
+    /// their language (`.rs`, `.py`, ...).
+    pub fn fixed_extension(&self) -> Option<&'static str> {
         match self {
-            InputFormat::Markdown => "md",
-            InputFormat::Html => "html",
-            InputFormat::Csv => "csv",
-            InputFormat::Docx => "docx",
-            InputFormat::PDF => "pdf",
+            InputFormat::Markdown => Some("md"),
+            InputFormat::Html => Some("html"),
+            InputFormat::Csv => Some("csv"),
+            InputFormat::Docx => Some("docx"),
+            InputFormat::PDF => Some("pdf"),
+            InputFormat::Text => Some("txt"),
+            InputFormat::Code => None,
         }
     }
 
@@ -32,10 +50,31 @@ impl InputFormat {
             "csv" => Some(InputFormat::Csv),
             "docx" => Some(InputFormat::Docx),
             "pdf" => Some(InputFormat::PDF),
+            "txt" | "text" => Some(InputFormat::Text),
+            ext if CodeLanguage::from_extension(ext).is_some() => Some(InputFormat::Code),
             _ => None,
         }
     }
 
+    /// Format named `name`: an extension, as for [`from_extension`](Self::from_extension),
+    /// or `code` for source files
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "code" => Some(InputFormat::Code),
+            name => Self::from_extension(name),
+        }
+    }
+
+    /// Detect the format of a file named `file_name` from its extension, or
+    /// else from its `content`
+    pub fn detect(file_name: &str, content: &[u8]) -> Option<Self> {
+        std::path::Path::new(file_name)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .and_then(Self::from_extension)
+            .or_else(|| Self::from_bytes(content))
+    }
+
     /// Detect format from magic bytes
     pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
         // Use infer crate for magic number detection
@@ -54,3 +93,81 @@ impl InputFormat {
         }
     }
 }
+
+/// Output format for converted documents
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Markdown format
+    Markdown,
+    /// Standalone HTML page
+    Html,
+    /// Standalone LaTeX document
+    Latex,
+    /// JSON format
+    Json,
+    /// Plain text format
+    Text,
+    /// Plain text approximating the page layout (like `pdftotext -layout`)
+    TextLayout,
+    /// JSON Lines, one node per line (one chunk per line with --chunk)
+    Jsonl,
+    /// Parquet table of nodes, or of chunks with --chunk (requires the `parquet` feature)
+    Parquet,
+    /// JSON in the Python docling DoclingDocument schema
+    DoclingJson,
+    /// DocTags markup, as used by docling's vision-language models
+    Doctags,
+    /// XML document tree, with provenance attributes
+    Xml,
+    /// JSON array of LangChain documents, one per chunk (requires --chunk)
+    #[value(name = "langchain")]
+    LangChain,
+    /// JSON array of LlamaIndex text nodes, one per chunk (requires --chunk)
+    #[value(name = "llamaindex")]
+    LlamaIndex,
+}
+
+impl std::fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Markdown => write!(f, "markdown"),
+            Self::Html => write!(f, "html"),
+            Self::Latex => write!(f, "latex"),
+            Self::Json => write!(f, "json"),
+            Self::Text => write!(f, "text"),
+            Self::TextLayout => write!(f, "text-layout"),
+            Self::Jsonl => write!(f, "jsonl"),
+            Self::Parquet => write!(f, "parquet"),
+            Self::DoclingJson => write!(f, "docling-json"),
+            Self::Doctags => write!(f, "doctags"),
+            Self::Xml => write!(f, "xml"),
+            Self::LangChain => write!(f, "langchain"),
+            Self::LlamaIndex => write!(f, "llamaindex"),
+        }
+    }
+}
+
+impl OutputFormat {
+    /// Get file extension for this format
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Markdown => "md",
+            Self::Html => "html",
+            Self::Latex => "tex",
+            Self::Json => "json",
+            Self::Text => "txt",
+            Self::TextLayout => "txt",
+            Self::Jsonl => "jsonl",
+            Self::Parquet => "parquet",
+            Self::DoclingJson => "json",
+            Self::Doctags => "doctags",
+            Self::Xml => "xml",
+            Self::LangChain | Self::LlamaIndex => "json",
+        }
+    }
+
+    /// Whether this format holds chunks, and so needs --chunk
+    pub fn needs_chunks(&self) -> bool {
+        matches!(self, Self::LangChain | Self::LlamaIndex)
+    }
+}
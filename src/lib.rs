@@ -12,17 +12,27 @@
 //! ```
 
 pub mod backend;
+pub mod cache;
 pub mod chunking;
 pub mod cli;
+pub mod config;
 pub mod datamodel;
+pub mod embeddings;
 pub mod error;
 pub mod format;
 pub mod pipeline;
+pub mod remote;
+#[cfg(feature = "server")]
+pub mod server;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 
 mod converter;
 
 // Re-exports
-pub use converter::DocumentConverter;
+pub use cache::ConversionCache;
+pub use config::ConverterConfig;
+pub use converter::{DocumentConverter, DocumentConverterBuilder, ParallelOptions};
 pub use datamodel::{ConversionResult, DoclingDocument, InputDocument};
 pub use error::ConversionError;
-pub use format::InputFormat;
+pub use format::{InputFormat, OutputFormat};
@@ -0,0 +1,74 @@
+//! Hooks around conversions
+//!
+//! Hooks let applications adjust conversions without writing a backend:
+//!
+//! - a [`BeforeParseHook`] rewrites the raw bytes of a source before the
+//!   backend parses them (strip tracking pixels from HTML, fix a broken
+//!   encoding, ...)
+//! - an [`AfterConvertHook`] edits the converted document, after enrichment
+//!   (inject metadata, drop boilerplate nodes, ...)
+//!
+//! Closures with the matching signature are hooks:
+//!
+//! ```ignore
+//! use docling_rs::{DocumentConverter, DoclingDocument, InputFormat};
+//!
+//! let converter = DocumentConverter::new()
+//!     .with_before_parse(|_name: &str, format: InputFormat, bytes: &mut Vec<u8>| {
+//!         if format == InputFormat::Html {
+//!             *bytes = strip_tracking_pixels(bytes);
+//!         }
+//!         Ok(())
+//!     })
+//!     .with_after_convert(|doc: &mut DoclingDocument| {
+//!         doc.metadata_mut().insert("team", "search");
+//!         Ok(())
+//!     });
+//! ```
+//!
+//! Hooks of each kind run in the order they were added; an error from a hook
+//! fails the conversion with that error.
+
+use crate::datamodel::DoclingDocument;
+use crate::error::ConversionError;
+use crate::InputFormat;
+
+/// Rewrites the bytes of a source before it is parsed
+pub trait BeforeParseHook: Send + Sync {
+    /// Called with the name and format of the document and its bytes
+    fn before_parse(
+        &self,
+        name: &str,
+        format: InputFormat,
+        bytes: &mut Vec<u8>,
+    ) -> Result<(), ConversionError>;
+}
+
+impl<F> BeforeParseHook for F
+where
+    F: Fn(&str, InputFormat, &mut Vec<u8>) -> Result<(), ConversionError> + Send + Sync,
+{
+    fn before_parse(
+        &self,
+        name: &str,
+        format: InputFormat,
+        bytes: &mut Vec<u8>,
+    ) -> Result<(), ConversionError> {
+        self(name, format, bytes)
+    }
+}
+
+/// Edits a converted document
+pub trait AfterConvertHook: Send + Sync {
+    /// Called with the document once parsing and enrichment are done
+    fn after_convert(&self, doc: &mut DoclingDocument) -> Result<(), ConversionError>;
+}
+
+impl<F> AfterConvertHook for F
+where
+    F: Fn(&mut DoclingDocument) -> Result<(), ConversionError> + Send + Sync,
+{
+    fn after_convert(&self, doc: &mut DoclingDocument) -> Result<(), ConversionError> {
+        self(doc)
+    }
+}
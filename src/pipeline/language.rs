@@ -0,0 +1,146 @@
+//! Language detection enrichment
+
+use std::collections::HashMap;
+
+use whatlang::{Detector, Lang};
+
+use crate::datamodel::{DoclingDocument, NodeType};
+
+/// Tags document nodes with the language of their text
+///
+/// Each text-bearing node is detected on its own, so documents mixing
+/// languages get per-node tags that chunkers carry into
+/// [`ChunkMetadata::languages`](crate::chunking::ChunkMetadata::languages).
+/// Nodes with too little text for a reliable guess (short headings, list
+/// items, table cells) inherit the language of their parent. Code blocks and
+/// formulas are not natural language and are left untagged.
+///
+/// Languages are ISO 639-3 codes (`eng`, `deu`, `spa`, ...). The most common
+/// language, weighted by text length, also becomes the document language when
+/// the backend did not set one.
+///
+/// # Examples
+///
+/// ```ignore
+/// use docling_rs::pipeline::LanguageDetector;
+///
+/// let detector = LanguageDetector::new().with_languages(&["eng", "spa"]);
+/// detector.annotate(&mut doc);
+/// ```
+#[derive(Debug, Clone)]
+pub struct LanguageDetector {
+    min_chars: usize,
+    min_confidence: f64,
+    languages: Vec<Lang>,
+}
+
+impl LanguageDetector {
+    /// Create a detector considering every supported language
+    pub fn new() -> Self {
+        Self {
+            min_chars: 20,
+            min_confidence: 0.5,
+            languages: Vec::new(),
+        }
+    }
+
+    /// Set the minimum number of characters a node needs to be detected
+    pub fn with_min_chars(mut self, min_chars: usize) -> Self {
+        self.min_chars = min_chars;
+        self
+    }
+
+    /// Set the minimum confidence (0 to 1) for a detection to be kept
+    pub fn with_min_confidence(mut self, min_confidence: f64) -> Self {
+        self.min_confidence = min_confidence;
+        self
+    }
+
+    /// Only consider these languages (ISO 639-3 codes; unknown codes are ignored)
+    ///
+    /// Restricting detection to the languages a corpus can contain makes short
+    /// texts much more reliable.
+    pub fn with_languages(mut self, codes: &[&str]) -> Self {
+        self.languages = codes.iter().filter_map(|c| Lang::from_code(*c)).collect();
+        self
+    }
+
+    /// Detect the language of a text
+    ///
+    /// Returns `None` for texts that are too short or too ambiguous.
+    pub fn detect(&self, text: &str) -> Option<&'static str> {
+        if text.chars().filter(|c| c.is_alphabetic()).count() < self.min_chars {
+            return None;
+        }
+        let detector = if self.languages.is_empty() {
+            Detector::new()
+        } else {
+            Detector::with_allowlist(self.languages.clone())
+        };
+        detector
+            .detect(text)
+            .filter(|info| info.confidence() >= self.min_confidence)
+            .map(|info| info.lang().code())
+    }
+
+    /// Tag the nodes of a document, returning how many were tagged
+    ///
+    /// Nodes that already have a language keep it.
+    pub fn annotate(&self, doc: &mut DoclingDocument) -> usize {
+        let mut tagged = 0;
+        let mut weights: HashMap<String, usize> = HashMap::new();
+
+        // Parents come before their children in the node list, so inherited
+        // languages are always resolved by the time a child is reached
+        let ids: Vec<_> = doc.nodes().iter().filter_map(|node| node.id()).collect();
+        for id in ids {
+            let Some(node) = doc.node(id) else {
+                continue;
+            };
+            if matches!(
+                node.node_type(),
+                NodeType::CodeBlock | NodeType::Formula | NodeType::PageBreak
+            ) {
+                continue;
+            }
+            let text = node.text_content().unwrap_or_default();
+            let language = match node.language() {
+                Some(language) => Some(language.to_string()),
+                None => self.detect(text).map(str::to_string).or_else(|| {
+                    doc.parent(id)
+                        .and_then(|(_, parent)| parent.language())
+                        .map(str::to_string)
+                }),
+            };
+            let Some(language) = language else {
+                continue;
+            };
+
+            *weights.entry(language.clone()).or_default() += text.len();
+            if node.language().is_none() {
+                if let Some(node) = doc.node_mut(id) {
+                    node.set_language(language);
+                    tagged += 1;
+                }
+            }
+        }
+
+        if doc.metadata().language().is_none() {
+            let dominant = weights
+                .into_iter()
+                .max_by(|(a, wa), (b, wb)| wa.cmp(wb).then_with(|| b.cmp(a)));
+            if let Some((language, _)) = dominant {
+                let metadata = doc.metadata().clone().with_language(language);
+                *doc.metadata_mut() = metadata;
+            }
+        }
+
+        tagged
+    }
+}
+
+impl Default for LanguageDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
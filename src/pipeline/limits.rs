@@ -0,0 +1,341 @@
+//! Resource limits for conversions
+//!
+//! [`ConversionLimits`] bound the wall-clock time, page count and output size
+//! of each conversion, so one pathological document cannot stall a batch or
+//! a service. A conversion over a limit fails with
+//! [`ConversionError::LimitExceeded`]:
+//!
+//! ```ignore
+//! use std::time::Duration;
+//! use docling_rs::pipeline::ConversionLimits;
+//! use docling_rs::DocumentConverter;
+//!
+//! let converter = DocumentConverter::new().with_limits(
+//!     ConversionLimits::new()
+//!         .with_timeout(Duration::from_secs(30))
+//!         .with_max_pages(500),
+//! );
+//! ```
+//!
+//! Limits are checked cooperatively: the pipeline checks them after parsing
+//! and after every enrichment stage, and the PDF backend checks the page
+//! count before parsing and the time after every page. A backend stuck inside
+//! a single step is only stopped once that step returns.
+//!
+//! Services taking files from untrusted sources also want guardrails against
+//! hostile inputs, which are checked before or while parsing:
+//!
+//! - [`with_max_input_bytes`](ConversionLimits::with_max_input_bytes) bounds
+//!   the size of sources, before any backend reads them
+//! - [`with_max_decompressed_bytes`](ConversionLimits::with_max_decompressed_bytes)
+//!   bounds how much zip-based formats (DOCX) and archives expand, so zip
+//!   bombs are rejected after reading at most that many bytes
+//! - [`with_max_depth`](ConversionLimits::with_max_depth) bounds the nesting
+//!   of HTML elements and of directories in archives
+
+use std::fmt;
+use std::io::{self, Cursor, Read};
+use std::time::Duration;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+// `std::time::Instant` panics in the browser
+#[cfg(target_arch = "wasm32")]
+use web_time::Instant;
+
+use crate::datamodel::DoclingDocument;
+use crate::error::ConversionError;
+
+/// Limits applied to each conversion
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ConversionLimits {
+    timeout: Option<Duration>,
+    max_pages: Option<usize>,
+    max_output_bytes: Option<usize>,
+    max_input_bytes: Option<u64>,
+    max_decompressed_bytes: Option<u64>,
+    max_depth: Option<usize>,
+}
+
+impl ConversionLimits {
+    /// Create limits that allow everything
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fail conversions that take longer than `timeout`
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Fail conversions of documents with more than `pages` pages
+    ///
+    /// Checked by backends of paginated formats before parsing. For PDFs with
+    /// a page range, the pages in the range are counted.
+    pub fn with_max_pages(mut self, pages: usize) -> Self {
+        self.max_pages = Some(pages);
+        self
+    }
+
+    /// Fail conversions whose document holds more than `bytes` bytes
+    ///
+    /// Counts the text of all nodes and the bytes of stored images.
+    pub fn with_max_output_bytes(mut self, bytes: usize) -> Self {
+        self.max_output_bytes = Some(bytes);
+        self
+    }
+
+    /// Refuse sources larger than `bytes` bytes
+    pub fn with_max_input_bytes(mut self, bytes: u64) -> Self {
+        self.max_input_bytes = Some(bytes);
+        self
+    }
+
+    /// Refuse zip-based sources and archives that expand to more than
+    /// `bytes` bytes
+    pub fn with_max_decompressed_bytes(mut self, bytes: u64) -> Self {
+        self.max_decompressed_bytes = Some(bytes);
+        self
+    }
+
+    /// Refuse sources nested more than `depth` levels deep (HTML elements,
+    /// directories in archives)
+    pub fn with_max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = Some(depth);
+        self
+    }
+
+    /// Maximum duration of a conversion, if bounded
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /// Maximum number of pages, if bounded
+    pub fn max_pages(&self) -> Option<usize> {
+        self.max_pages
+    }
+
+    /// Maximum document size in bytes, if bounded
+    pub fn max_output_bytes(&self) -> Option<usize> {
+        self.max_output_bytes
+    }
+
+    /// Maximum source size in bytes, if bounded
+    pub fn max_input_bytes(&self) -> Option<u64> {
+        self.max_input_bytes
+    }
+
+    /// Maximum decompressed size in bytes, if bounded
+    pub fn max_decompressed_bytes(&self) -> Option<u64> {
+        self.max_decompressed_bytes
+    }
+
+    /// Maximum nesting depth, if bounded
+    pub fn max_depth(&self) -> Option<usize> {
+        self.max_depth
+    }
+
+    /// Start the clock of a conversion
+    pub fn start(&self) -> LimitGuard {
+        LimitGuard {
+            limits: *self,
+            started: Instant::now(),
+        }
+    }
+}
+
+/// Limits of a running conversion, created by [`ConversionLimits::start`]
+#[derive(Debug, Clone, Copy)]
+pub struct LimitGuard {
+    limits: ConversionLimits,
+    started: Instant,
+}
+
+impl LimitGuard {
+    /// A guard that never fails
+    pub fn unlimited() -> Self {
+        ConversionLimits::new().start()
+    }
+
+    /// The limits being applied
+    pub fn limits(&self) -> &ConversionLimits {
+        &self.limits
+    }
+
+    /// Fail if the conversion has run longer than the timeout
+    pub fn check_time(&self) -> Result<(), ConversionError> {
+        match self.limits.timeout {
+            Some(timeout) if self.started.elapsed() > timeout => Err(
+                ConversionError::LimitExceeded(ExceededLimit::Timeout(timeout)),
+            ),
+            _ => Ok(()),
+        }
+    }
+
+    /// Fail if a document of `pages` pages is over the page limit
+    pub fn check_pages(&self, pages: usize) -> Result<(), ConversionError> {
+        match self.limits.max_pages {
+            Some(max) if pages > max => Err(ConversionError::LimitExceeded(ExceededLimit::Pages {
+                pages,
+                max,
+            })),
+            _ => Ok(()),
+        }
+    }
+
+    /// Fail if `doc` is over the output size limit
+    pub fn check_output(&self, doc: &DoclingDocument) -> Result<(), ConversionError> {
+        if self.limits.max_output_bytes.is_none() {
+            return Ok(());
+        }
+        self.check_output_size(output_size(doc))
+    }
+
+    /// Fail if `bytes` of output are over the output size limit
+    pub fn check_output_size(&self, bytes: usize) -> Result<(), ConversionError> {
+        match self.limits.max_output_bytes {
+            Some(max) if bytes > max => {
+                Err(ConversionError::LimitExceeded(ExceededLimit::OutputSize {
+                    bytes,
+                    max,
+                }))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Fail if a source of `bytes` bytes is over the input size limit
+    pub fn check_input_size(&self, bytes: u64) -> Result<(), ConversionError> {
+        match self.limits.max_input_bytes {
+            Some(max) if bytes > max => {
+                Err(ConversionError::LimitExceeded(ExceededLimit::InputSize {
+                    bytes,
+                    max,
+                }))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Fail if content expanding to `bytes` bytes is over the decompressed
+    /// size limit
+    pub fn check_decompressed_size(&self, bytes: u64) -> Result<(), ConversionError> {
+        match self.limits.max_decompressed_bytes {
+            Some(max) if bytes > max => Err(ConversionError::LimitExceeded(
+                ExceededLimit::DecompressedSize { bytes, max },
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Fail if content nested `depth` levels deep is over the depth limit
+    pub fn check_depth(&self, depth: usize) -> Result<(), ConversionError> {
+        match self.limits.max_depth {
+            Some(max) if depth > max => Err(ConversionError::LimitExceeded(ExceededLimit::Depth {
+                depth,
+                max,
+            })),
+            _ => Ok(()),
+        }
+    }
+
+    /// Fail if the zip archive in `data` expands to more than the
+    /// decompressed size limit
+    ///
+    /// Sizes declared by the archive are checked first; since they can lie,
+    /// the entries are then decompressed (and discarded), stopping as soon as
+    /// the limit is passed. Data that is not a zip archive passes, for the
+    /// backend to reject.
+    pub fn check_zip(&self, data: &[u8]) -> Result<(), ConversionError> {
+        let Some(max) = self.limits.max_decompressed_bytes else {
+            return Ok(());
+        };
+        let Ok(mut archive) = zip::ZipArchive::new(Cursor::new(data)) else {
+            return Ok(());
+        };
+        let declared = (0..archive.len())
+            .filter_map(|i| archive.by_index_raw(i).ok().map(|entry| entry.size()))
+            .fold(0u64, u64::saturating_add);
+        self.check_decompressed_size(declared)?;
+
+        let mut total = 0u64;
+        for i in 0..archive.len() {
+            // Entries that cannot be read are left for the backend to report
+            let Ok(entry) = archive.by_index(i) else {
+                continue;
+            };
+            let remaining = max - total;
+            total += io::copy(&mut entry.take(remaining + 1), &mut io::sink()).unwrap_or(0);
+            self.check_decompressed_size(total)?;
+        }
+        Ok(())
+    }
+
+    /// Check the time and output size limits on a converted document
+    pub fn check(&self, doc: &DoclingDocument) -> Result<(), ConversionError> {
+        self.check_time()?;
+        self.check_output(doc)
+    }
+}
+
+/// Size of a document as counted by the output size limit: the text of all
+/// nodes and the bytes of stored images
+pub fn output_size(doc: &DoclingDocument) -> usize {
+    let text: usize = doc
+        .nodes()
+        .iter()
+        .filter_map(|node| node.text_content())
+        .map(str::len)
+        .sum();
+    let images: usize = doc
+        .images()
+        .iter()
+        .map(|(_, image)| image.data().len())
+        .sum();
+    text + images
+}
+
+/// The limit a conversion went over
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ExceededLimit {
+    /// The conversion took longer than the timeout
+    Timeout(Duration),
+    /// The document has `pages` pages, more than `max`
+    Pages { pages: usize, max: usize },
+    /// The document holds `bytes` bytes, more than `max`
+    OutputSize { bytes: usize, max: usize },
+    /// The source is `bytes` bytes, more than `max`
+    InputSize { bytes: u64, max: u64 },
+    /// The content expands to at least `bytes` bytes, more than `max`
+    DecompressedSize { bytes: u64, max: u64 },
+    /// The content is nested `depth` levels deep, more than `max`
+    Depth { depth: usize, max: usize },
+}
+
+impl fmt::Display for ExceededLimit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Timeout(timeout) => write!(f, "conversion took longer than {:?}", timeout),
+            Self::Pages { pages, max } => {
+                write!(f, "document has {} pages, the limit is {}", pages, max)
+            }
+            Self::OutputSize { bytes, max } => {
+                write!(f, "document holds {} bytes, the limit is {}", bytes, max)
+            }
+            Self::InputSize { bytes, max } => {
+                write!(f, "input is {} bytes, the limit is {}", bytes, max)
+            }
+            Self::DecompressedSize { bytes, max } => write!(
+                f,
+                "content expands to at least {} bytes, the limit is {}",
+                bytes, max
+            ),
+            Self::Depth { depth, max } => write!(
+                f,
+                "content is nested {} levels deep, the limit is {}",
+                depth, max
+            ),
+        }
+    }
+}
@@ -1,8 +1,20 @@
 //! Pipeline implementations for document processing
 
+pub mod hooks;
+pub mod language;
+pub mod limits;
+pub mod progress;
 pub mod simple;
+pub mod stage;
+pub mod stream;
 pub mod traits;
 
 // Re-exports
+pub use hooks::{AfterConvertHook, BeforeParseHook};
+pub use language::LanguageDetector;
+pub use limits::{ConversionLimits, ExceededLimit, LimitGuard};
+pub use progress::{ConversionStage, NoProgress, ProgressEvent, ProgressObserver};
 pub use simple::SimplePipeline;
+pub use stage::PipelineStage;
+pub use stream::StreamSummary;
 pub use traits::Pipeline;
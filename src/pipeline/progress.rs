@@ -0,0 +1,139 @@
+//! Progress reporting for long conversions
+//!
+//! An observer registered with
+//! [`DocumentConverter::with_progress`](crate::DocumentConverter::with_progress)
+//! (or [`SimplePipeline::with_progress`](crate::pipeline::SimplePipeline::with_progress))
+//! receives a [`ProgressEvent`] as each conversion moves through its stages,
+//! and after every page for paginated formats:
+//!
+//! ```ignore
+//! use docling_rs::pipeline::ProgressEvent;
+//! use docling_rs::DocumentConverter;
+//!
+//! let converter = DocumentConverter::new().with_progress(|name: &str, event: &ProgressEvent| {
+//!     if let ProgressEvent::Page { done, total, .. } = event {
+//!         eprintln!("{}: page {}/{}", name, done, total);
+//!     }
+//! });
+//! ```
+//!
+//! Backends also report non-fatal issues as [`ProgressEvent::Warning`]s and
+//! the time spent in steps such as OCR as [`ProgressEvent::Timing`]s, which
+//! the pipeline collects into the result.
+
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::datamodel::{ConversionWarning, StageTimings, TimedStage};
+
+/// Stage of a conversion
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConversionStage {
+    /// The backend parses the source into a document
+    Parse,
+    /// Enrichment (language detection, section grouping) runs on the document
+    Enrich,
+}
+
+/// Progress of a conversion
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ProgressEvent {
+    /// Conversion started; `bytes` is the size of the source, when known
+    Started { bytes: Option<u64> },
+    /// A stage started
+    Stage(ConversionStage),
+    /// A page was processed: `page` is its 1-based number, `done` the pages
+    /// processed so far and `total` the pages to process
+    Page {
+        page: usize,
+        done: usize,
+        total: usize,
+    },
+    /// A non-fatal issue was met; conversion goes on without the affected
+    /// part and the warning ends up in
+    /// [`ConversionResult::warnings`](crate::ConversionResult::warnings)
+    Warning(ConversionWarning),
+    /// `elapsed` was spent in `stage`; a stage may be reported several
+    /// times, and the times add up in
+    /// [`ConversionMetrics`](crate::datamodel::ConversionMetrics)
+    Timing {
+        stage: TimedStage,
+        elapsed: Duration,
+    },
+    /// Conversion finished with a document of `nodes` nodes
+    Finished { nodes: usize },
+}
+
+/// Receives progress events
+///
+/// Observers are shared by all conversions of a converter, including those
+/// running in parallel (see
+/// [`convert_many`](crate::DocumentConverter::convert_many)), so events name
+/// the document they belong to. Closures taking `(&str, &ProgressEvent)` are
+/// observers.
+pub trait ProgressObserver: Send + Sync {
+    /// Called for every event of the conversion of `document`
+    fn on_progress(&self, document: &str, event: &ProgressEvent);
+}
+
+impl<F> ProgressObserver for F
+where
+    F: Fn(&str, &ProgressEvent) + Send + Sync,
+{
+    fn on_progress(&self, document: &str, event: &ProgressEvent) {
+        self(document, event)
+    }
+}
+
+/// Observer ignoring all events
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoProgress;
+
+impl ProgressObserver for NoProgress {
+    fn on_progress(&self, _document: &str, _event: &ProgressEvent) {}
+}
+
+/// Observer collecting the warnings and stage timings of a conversion and
+/// passing every event on
+pub(crate) struct EventCollector<'a> {
+    inner: &'a dyn ProgressObserver,
+    collected: Mutex<(Vec<ConversionWarning>, StageTimings)>,
+}
+
+impl<'a> EventCollector<'a> {
+    pub(crate) fn new(inner: &'a dyn ProgressObserver) -> Self {
+        Self {
+            inner,
+            collected: Mutex::new((Vec::new(), StageTimings::new())),
+        }
+    }
+
+    /// The warnings reported so far, in order, and the time of each stage
+    pub(crate) fn into_parts(self) -> (Vec<ConversionWarning>, StageTimings) {
+        self.collected
+            .into_inner()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl ProgressObserver for EventCollector<'_> {
+    fn on_progress(&self, document: &str, event: &ProgressEvent) {
+        match event {
+            ProgressEvent::Warning(warning) => self
+                .collected
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .0
+                .push(warning.clone()),
+            ProgressEvent::Timing { stage, elapsed } => self
+                .collected
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .1
+                .add(*stage, *elapsed),
+            _ => {}
+        }
+        self.inner.on_progress(document, event);
+    }
+}
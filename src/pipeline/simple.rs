@@ -1,31 +1,358 @@
 //! Simple pipeline implementation
 
-use crate::backend::{CsvBackend, DocxBackend, HtmlBackend, MarkdownBackend, PdfBackend};
-use crate::datamodel::{ConversionResult, ConversionStatus, InputDocument};
+use std::collections::HashMap;
+use std::sync::Arc;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+// `std::time::Instant` panics in the browser
+#[cfg(target_arch = "wasm32")]
+use web_time::Instant;
+
+use crate::backend::pdf::PdfConfig;
+use crate::backend::{
+    Backend, CodeBackend, CsvBackend, DocxBackend, HtmlBackend, HtmlConfig, MarkdownBackend,
+    PdfBackend, TextBackend,
+};
+use crate::datamodel::{
+    ConversionMetrics, ConversionResult, ConversionStatus, DoclingDocument, DocumentSource,
+    InputDocument, TimedStage,
+};
 use crate::error::ConversionError;
-use crate::pipeline::Pipeline;
+use crate::pipeline::limits::output_size;
+use crate::pipeline::progress::EventCollector;
+use crate::pipeline::{
+    AfterConvertHook, BeforeParseHook, ConversionLimits, ConversionStage, LanguageDetector,
+    LimitGuard, NoProgress, Pipeline, PipelineStage, ProgressEvent, ProgressObserver,
+    StreamSummary,
+};
 use crate::InputFormat;
 
 /// Simple pipeline (build → assemble → enrich)
+///
+/// [Before-parse hooks](Self::with_before_parse) run on the source first.
+/// Enrichment runs language detection first, then the registered
+/// [stages](Self::with_stage) in order, then section grouping, and
+/// [after-convert hooks](Self::with_after_convert) run last.
 pub struct SimplePipeline {
+    /// Backends registered to replace the built-in ones
+    backends: HashMap<InputFormat, Box<dyn Backend + Send + Sync>>,
     markdown_backend: MarkdownBackend,
     html_backend: HtmlBackend,
     csv_backend: CsvBackend,
     docx_backend: DocxBackend,
     pdf_backend: PdfBackend,
+    text_backend: TextBackend,
+    code_backend: CodeBackend,
+    language_detector: Option<LanguageDetector>,
+    stages: Vec<Box<dyn PipelineStage>>,
+    before_parse: Vec<Box<dyn BeforeParseHook>>,
+    after_convert: Vec<Box<dyn AfterConvertHook>>,
+    group_sections: bool,
+    progress: Option<Arc<dyn ProgressObserver>>,
+    limits: ConversionLimits,
 }
 
 impl SimplePipeline {
     /// Create a new SimplePipeline
     pub fn new() -> Self {
         Self {
+            backends: HashMap::new(),
             markdown_backend: MarkdownBackend::new(),
             html_backend: HtmlBackend::new(),
             csv_backend: CsvBackend::new(),
             docx_backend: DocxBackend::new(),
             pdf_backend: PdfBackend::new(),
+            text_backend: TextBackend::new(),
+            code_backend: CodeBackend::new(),
+            language_detector: None,
+            stages: Vec::new(),
+            before_parse: Vec::new(),
+            after_convert: Vec::new(),
+            group_sections: false,
+            progress: None,
+            limits: ConversionLimits::new(),
         }
     }
+
+    /// Convert PDFs with `config` instead of the default configuration
+    pub fn with_pdf_config(mut self, config: PdfConfig) -> Self {
+        self.pdf_backend = PdfBackend::with_config(config);
+        self
+    }
+
+    /// Convert HTML with `config` instead of the default configuration
+    pub fn with_html_config(mut self, config: HtmlConfig) -> Self {
+        self.html_backend = HtmlBackend::with_config(config);
+        self
+    }
+
+    /// Tag converted documents with the language of their nodes
+    pub fn with_language_detection(mut self, detector: LanguageDetector) -> Self {
+        self.language_detector = Some(detector);
+        self
+    }
+
+    /// Convert documents of `format` with `backend` instead of the built-in backend
+    pub fn with_backend(
+        mut self,
+        format: InputFormat,
+        backend: Box<dyn Backend + Send + Sync>,
+    ) -> Self {
+        self.backends.insert(format, backend);
+        self
+    }
+
+    /// Run a stage on converted documents, after the stages already added
+    ///
+    /// See [`PipelineStage`].
+    pub fn with_stage(mut self, stage: impl PipelineStage + 'static) -> Self {
+        self.stages.push(Box::new(stage));
+        self
+    }
+
+    /// Rewrite the bytes of sources before they are parsed, after the hooks
+    /// already added
+    ///
+    /// File sources are read into memory to run the hook. See
+    /// [`hooks`](crate::pipeline::hooks).
+    pub fn with_before_parse(mut self, hook: impl BeforeParseHook + 'static) -> Self {
+        self.before_parse.push(Box::new(hook));
+        self
+    }
+
+    /// Edit converted documents after enrichment, after the hooks already
+    /// added
+    ///
+    /// See [`hooks`](crate::pipeline::hooks).
+    pub fn with_after_convert(mut self, hook: impl AfterConvertHook + 'static) -> Self {
+        self.after_convert.push(Box::new(hook));
+        self
+    }
+
+    /// Group the nodes of converted documents into sections by heading level
+    ///
+    /// See [`DoclingDocument::group_sections`](crate::datamodel::DoclingDocument::group_sections).
+    pub fn with_section_grouping(mut self, group: bool) -> Self {
+        self.group_sections = group;
+        self
+    }
+
+    /// Report the progress of conversions to `observer`
+    ///
+    /// See [`progress`](crate::pipeline::progress) for the events reported.
+    pub fn with_progress(mut self, observer: impl ProgressObserver + 'static) -> Self {
+        self.progress = Some(Arc::new(observer));
+        self
+    }
+
+    /// Fail conversions that go over `limits`
+    ///
+    /// See [`limits`](crate::pipeline::limits) for when limits are checked.
+    pub fn with_limits(mut self, limits: ConversionLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// The limits applied to each conversion
+    pub fn limits(&self) -> &ConversionLimits {
+        &self.limits
+    }
+
+    /// Description of the settings that affect conversion results
+    ///
+    /// Used as part of [cache](crate::ConversionCache) keys. Stages and
+    /// registered backends are identified by name and format only, and hooks
+    /// by their number, so changing their behavior needs a new cache.
+    pub fn fingerprint(&self) -> String {
+        let mut backends: Vec<String> = self
+            .backends
+            .keys()
+            .map(|format| format!("{:?}", format))
+            .collect();
+        backends.sort();
+        let stages: Vec<&str> = self.stages.iter().map(|stage| stage.name()).collect();
+        // The password does not change the result, and has no place in a key
+        let pdf = PdfConfig {
+            password: None,
+            ..self.pdf_backend.config().clone()
+        };
+        // Results under looser limits must not be returned under stricter
+        // ones; the timeout depends on the machine, not on the result
+        let limits = &self.limits;
+        let limits = (
+            limits.max_pages(),
+            limits.max_output_bytes(),
+            limits.max_input_bytes(),
+            limits.max_decompressed_bytes(),
+            limits.max_depth(),
+        );
+        format!(
+            "language={:?};stages={:?};sections={};backends={:?};hooks={}+{};pdf={:?};html={:?};limits={:?}",
+            self.language_detector,
+            stages,
+            self.group_sections,
+            backends,
+            self.before_parse.len(),
+            self.after_convert.len(),
+            pdf,
+            self.html_backend.config(),
+            limits
+        )
+    }
+}
+
+impl SimplePipeline {
+    /// Convert a document, handing it to `emit` in parts as they are converted
+    ///
+    /// See [`stream`](crate::pipeline::stream). Each part is enriched on its
+    /// own before it is emitted; an error from `emit` stops the conversion.
+    pub fn execute_streaming(
+        &self,
+        input: &InputDocument,
+        emit: &mut dyn FnMut(DoclingDocument) -> Result<(), ConversionError>,
+    ) -> Result<StreamSummary, ConversionError> {
+        let _span = pipeline_span(input).entered();
+        let observer = self.observer();
+        let collector = EventCollector::new(observer);
+        let progress: &dyn ProgressObserver = &collector;
+        let limits = self.limits.start();
+        progress.on_progress(
+            input.name(),
+            &ProgressEvent::Started {
+                bytes: input.size(),
+            },
+        );
+        progress.on_progress(input.name(), &ProgressEvent::Stage(ConversionStage::Parse));
+
+        let hooked = self.run_before_parse(input, &limits)?;
+        let (mut parts, mut nodes, mut bytes) = (0, 0, 0);
+        let mut enrich_and_emit = |part: DoclingDocument| {
+            let part = {
+                let _span = tracing::debug_span!("enrich", part = parts).entered();
+                self.enrich(input, part, &limits)?
+            };
+            bytes += output_size(&part);
+            limits.check_output_size(bytes)?;
+            parts += 1;
+            nodes += part.nodes().len();
+            emit(part)
+        };
+        self.backend(input.format()).convert_streaming(
+            hooked.as_ref().unwrap_or(input),
+            progress,
+            &limits,
+            &mut enrich_and_emit,
+        )?;
+
+        progress.on_progress(input.name(), &ProgressEvent::Finished { nodes });
+        let (warnings, _) = collector.into_parts();
+        Ok(StreamSummary::new(parts, nodes, warnings))
+    }
+
+    /// The registered progress observer, or one ignoring all events
+    fn observer(&self) -> &dyn ProgressObserver {
+        match &self.progress {
+            Some(observer) => observer.as_ref(),
+            None => &NoProgress,
+        }
+    }
+
+    /// Select the backend for a format, registered backends first
+    fn backend(&self, format: InputFormat) -> &dyn Backend {
+        match self.backends.get(&format) {
+            Some(backend) => backend.as_ref(),
+            None => match format {
+                InputFormat::Markdown => &self.markdown_backend,
+                InputFormat::Html => &self.html_backend,
+                InputFormat::Csv => &self.csv_backend,
+                InputFormat::Docx => &self.docx_backend,
+                InputFormat::PDF => &self.pdf_backend,
+                InputFormat::Text => &self.text_backend,
+                InputFormat::Code => &self.code_backend,
+            },
+        }
+    }
+
+    /// The input with its bytes rewritten by the before-parse hooks, if any
+    ///
+    /// The input size limit applies to the source and to the rewritten bytes.
+    fn run_before_parse(
+        &self,
+        input: &InputDocument,
+        limits: &LimitGuard,
+    ) -> Result<Option<InputDocument>, ConversionError> {
+        if let Some(size) = input.size() {
+            limits.check_input_size(size)?;
+        }
+        if self.before_parse.is_empty() {
+            return Ok(None);
+        }
+        let mut bytes = match input.source() {
+            DocumentSource::FilePath(path) | DocumentSource::Remote { path, .. } => {
+                std::fs::read(path)?
+            }
+            DocumentSource::Bytes { data, .. } => data.clone(),
+        };
+        for hook in &self.before_parse {
+            hook.before_parse(input.name(), input.format(), &mut bytes)?;
+        }
+        limits.check_input_size(bytes.len() as u64)?;
+        Ok(Some(InputDocument::from_bytes(
+            bytes,
+            input.name(),
+            input.format(),
+        )))
+    }
+
+    /// Run enrichment and after-convert hooks on a parsed document
+    fn enrich(
+        &self,
+        input: &InputDocument,
+        mut document: DoclingDocument,
+        limits: &LimitGuard,
+    ) -> Result<DoclingDocument, ConversionError> {
+        // A source rewritten by hooks lives in memory; keep pointing at the
+        // file or object
+        let source_uri = match input.source() {
+            DocumentSource::FilePath(path) => Some(path.display().to_string()),
+            DocumentSource::Remote { url, .. } => Some(url.clone()),
+            DocumentSource::Bytes { .. } => None,
+        };
+        if let Some(source_uri) = source_uri.filter(|_| !self.before_parse.is_empty()) {
+            let metadata = std::mem::take(document.metadata_mut());
+            *document.metadata_mut() = metadata.with_source_uri(source_uri);
+        }
+        limits.check(&document)?;
+
+        if let Some(detector) = &self.language_detector {
+            detector.annotate(&mut document);
+        }
+        for stage in &self.stages {
+            let _span = tracing::debug_span!("stage", name = stage.name()).entered();
+            stage
+                .process(&mut document)
+                .map_err(|e| ConversionError::Stage {
+                    stage: stage.name().to_string(),
+                    message: e.to_string(),
+                })?;
+            limits.check(&document)?;
+        }
+        if self.group_sections {
+            document = document.group_sections();
+        }
+        for hook in &self.after_convert {
+            hook.after_convert(&mut document)?;
+        }
+        if !self.after_convert.is_empty() {
+            limits.check(&document)?;
+        }
+        Ok(document)
+    }
+}
+
+/// Span of the conversion of `input`, holding its name and format
+fn pipeline_span(input: &InputDocument) -> tracing::Span {
+    tracing::debug_span!("pipeline", document = input.name(), format = ?input.format())
 }
 
 impl Default for SimplePipeline {
@@ -36,19 +363,69 @@ impl Default for SimplePipeline {
 
 impl Pipeline for SimplePipeline {
     fn execute(&self, input: &InputDocument) -> Result<ConversionResult, ConversionError> {
-        use crate::backend::Backend;
-
-        // Select backend based on format
-        let document = match input.format() {
-            InputFormat::Markdown => self.markdown_backend.convert(input)?,
-            InputFormat::Html => self.html_backend.convert(input)?,
-            InputFormat::Csv => self.csv_backend.convert(input)?,
-            InputFormat::Docx => self.docx_backend.convert(input)?,
-            InputFormat::PDF => self.pdf_backend.convert(input)?,
-        };
+        let _span = pipeline_span(input).entered();
+        let started = Instant::now();
+        let collector = EventCollector::new(self.observer());
+        let progress: &dyn ProgressObserver = &collector;
+        let report = |event| progress.on_progress(input.name(), &event);
+        let limits = self.limits.start();
+        report(ProgressEvent::Started {
+            bytes: input.size(),
+        });
 
-        // Create conversion result
-        let result = ConversionResult::new(document, ConversionStatus::Success);
+        report(ProgressEvent::Stage(ConversionStage::Parse));
+        let hooked = self.run_before_parse(input, &limits)?;
+        let parse_started = Instant::now();
+        let document = tracing::debug_span!("parse").in_scope(|| {
+            self.backend(input.format()).convert_with_limits(
+                hooked.as_ref().unwrap_or(input),
+                progress,
+                &limits,
+            )
+        })?;
+        report(ProgressEvent::Timing {
+            stage: TimedStage::Parse,
+            elapsed: parse_started.elapsed(),
+        });
+
+        // Enrich
+        if self.language_detector.is_some() || !self.stages.is_empty() || self.group_sections {
+            report(ProgressEvent::Stage(ConversionStage::Enrich));
+        }
+        let document =
+            tracing::debug_span!("enrich").in_scope(|| self.enrich(input, document, &limits))?;
+        report(ProgressEvent::Finished {
+            nodes: document.nodes().len(),
+        });
+
+        // Create conversion result, partial if parts could not be converted
+        let (warnings, stages) = collector.into_parts();
+        let status = if warnings.is_empty() {
+            ConversionStatus::Success
+        } else {
+            ConversionStatus::PartialSuccess
+        };
+        let mut metrics = ConversionMetrics::new()
+            .with_processing_time_ms(
+                u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX),
+            )
+            .with_node_count(document.nodes().len())
+            .with_characters(
+                document
+                    .nodes()
+                    .iter()
+                    .filter_map(|node| node.text_content())
+                    .map(|text| text.chars().count())
+                    .sum(),
+            )
+            .with_warning_count(warnings.len())
+            .with_stages(stages);
+        if let Some(pages) = document.metadata().page_count() {
+            metrics = metrics.with_total_pages(pages);
+        }
+        let result = ConversionResult::new(document, status)
+            .with_metrics(metrics)
+            .with_warnings(warnings);
 
         Ok(result)
     }
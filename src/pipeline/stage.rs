@@ -0,0 +1,62 @@
+//! Enrichment stages run on converted documents
+//!
+//! A [`PipelineStage`] receives each document after the backend has built it
+//! and may change it in place: tag nodes, add annotations, redact text, drop
+//! nodes. Stages are registered on the pipeline (or the converter) and run in
+//! registration order:
+//!
+//! ```ignore
+//! use docling_rs::pipeline::PipelineStage;
+//! use docling_rs::{ConversionError, DoclingDocument, DocumentConverter};
+//! use docling_rs::datamodel::Annotation;
+//!
+//! struct EmailFinder;
+//!
+//! impl PipelineStage for EmailFinder {
+//!     fn name(&self) -> &str {
+//!         "email-finder"
+//!     }
+//!
+//!     fn process(&self, doc: &mut DoclingDocument) -> Result<(), ConversionError> {
+//!         let hits: Vec<_> = doc
+//!             .nodes()
+//!             .iter()
+//!             .filter(|node| node.text_content().is_some_and(|t| t.contains('@')))
+//!             .filter_map(|node| node.id())
+//!             .collect();
+//!         for id in hits {
+//!             doc.annotate(Annotation::new(id, "pii:email"));
+//!         }
+//!         Ok(())
+//!     }
+//! }
+//!
+//! let converter = DocumentConverter::new().with_stage(EmailFinder);
+//! ```
+
+use crate::datamodel::DoclingDocument;
+use crate::error::ConversionError;
+use crate::pipeline::LanguageDetector;
+
+/// A step run on every converted document, between the backend and the result
+///
+/// Stages are shared by all conversions of a pipeline, including parallel
+/// ones, so they must be `Send + Sync`. An error aborts the conversion.
+pub trait PipelineStage: Send + Sync {
+    /// Name of the stage, used in error messages
+    fn name(&self) -> &str;
+
+    /// Process a converted document in place
+    fn process(&self, doc: &mut DoclingDocument) -> Result<(), ConversionError>;
+}
+
+impl PipelineStage for LanguageDetector {
+    fn name(&self) -> &str {
+        "language-detection"
+    }
+
+    fn process(&self, doc: &mut DoclingDocument) -> Result<(), ConversionError> {
+        self.annotate(doc);
+        Ok(())
+    }
+}
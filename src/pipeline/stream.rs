@@ -0,0 +1,83 @@
+//! Streaming conversion
+//!
+//! [`DocumentConverter::convert_streaming`](crate::DocumentConverter::convert_streaming)
+//! hands a document over in parts as they are converted, instead of building
+//! the whole document first. Each part is a small [`DoclingDocument`] with
+//! the name and metadata of the source, so it can be chunked, embedded or
+//! written out right away and then dropped:
+//!
+//! - PDFs are handed over page by page while the next pages are still being
+//!   read, keeping memory flat for large files
+//! - other formats are parsed in one go, then handed over one top-level
+//!   section at a time
+//!
+//! Every part goes through the enrichment of the pipeline (language
+//! detection, stages, section grouping, after-convert hooks) on its own.
+//! Limits apply to the conversion as a whole.
+//!
+//! To consume parts on another thread, send them through a channel:
+//!
+//! ```ignore
+//! use std::sync::mpsc;
+//! use docling_rs::DocumentConverter;
+//!
+//! let converter = DocumentConverter::new();
+//! let (parts, received) = mpsc::sync_channel(4);
+//! std::thread::scope(|scope| {
+//!     let converter = &converter;
+//!     scope.spawn(move || {
+//!         converter.convert_streaming("manual.pdf", |part| {
+//!             // Stops the conversion if the receiver hung up
+//!             parts.send(part).map_err(|e| std::io::Error::other(e.to_string()).into())
+//!         })
+//!     });
+//!     for part in received {
+//!         embed(chunker.chunk(&part));
+//!     }
+//! });
+//! ```
+
+use crate::datamodel::{ConversionStatus, ConversionWarning};
+
+/// Outcome of a streaming conversion
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StreamSummary {
+    parts: usize,
+    nodes: usize,
+    warnings: Vec<ConversionWarning>,
+}
+
+impl StreamSummary {
+    pub(crate) fn new(parts: usize, nodes: usize, warnings: Vec<ConversionWarning>) -> Self {
+        Self {
+            parts,
+            nodes,
+            warnings,
+        }
+    }
+
+    /// Number of parts handed over
+    pub fn parts(&self) -> usize {
+        self.parts
+    }
+
+    /// Number of nodes in all parts
+    pub fn nodes(&self) -> usize {
+        self.nodes
+    }
+
+    /// Non-fatal issues met during conversion
+    pub fn warnings(&self) -> &[ConversionWarning] {
+        &self.warnings
+    }
+
+    /// [`PartialSuccess`](ConversionStatus::PartialSuccess) if there were
+    /// warnings, else [`Success`](ConversionStatus::Success)
+    pub fn status(&self) -> ConversionStatus {
+        if self.warnings.is_empty() {
+            ConversionStatus::Success
+        } else {
+            ConversionStatus::PartialSuccess
+        }
+    }
+}
@@ -0,0 +1,376 @@
+//! Google Cloud Storage
+
+use super::{
+    agent, home_dir, percent_encode, request_error, var, Object, ObjectReader, ObjectUrl, Scheme,
+};
+use crate::error::ConversionError;
+use base64::engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD};
+use base64::Engine;
+use ring::rand::SystemRandom;
+use ring::signature::{RsaKeyPair, RSA_PKCS1_SHA256};
+use serde_json::{json, Value};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Endpoint of the Cloud Storage JSON API
+const DEFAULT_ENDPOINT: &str = "https://storage.googleapis.com";
+
+/// Token endpoint of Google OAuth 2.0
+const TOKEN_URI: &str = "https://oauth2.googleapis.com/token";
+
+/// Scope of the access tokens requested
+const SCOPE: &str = "https://www.googleapis.com/auth/devstorage.read_only";
+
+/// Tokens are renewed this long before they expire
+const EXPIRY_MARGIN: Duration = Duration::from_secs(60);
+
+/// Where access tokens come from
+enum TokenSource {
+    /// A token given as is, never renewed
+    Static(String),
+
+    /// A service account key, signing its own token requests
+    ServiceAccount {
+        email: String,
+        key: Box<RsaKeyPair>,
+        token_uri: String,
+    },
+
+    /// The refresh token of a user, as written by
+    /// `gcloud auth application-default login`
+    AuthorizedUser {
+        client_id: String,
+        client_secret: String,
+        refresh_token: String,
+    },
+
+    /// The service account of a Compute Engine VM, Cloud Run service or GKE
+    /// workload, from the metadata server at this address
+    Metadata(String),
+}
+
+/// Reads objects from Google Cloud Storage, through its JSON API
+///
+/// Requires the `gcs` feature.
+///
+/// ```ignore
+/// use docling_rs::remote::GcsReader;
+///
+/// // fake-gcs-server, anonymous
+/// let reader = GcsReader::new().with_endpoint("http://localhost:4443");
+/// ```
+pub struct GcsReader {
+    endpoint: String,
+    source: Option<TokenSource>,
+    /// Last access token and when it expires
+    token: Mutex<Option<(String, Instant)>>,
+    agent: ureq::Agent,
+}
+
+impl fmt::Debug for GcsReader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GcsReader")
+            .field("endpoint", &self.endpoint)
+            .field("authenticated", &self.source.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+impl GcsReader {
+    /// Anonymous reader of Cloud Storage
+    pub fn new() -> Self {
+        Self {
+            endpoint: DEFAULT_ENDPOINT.to_string(),
+            source: None,
+            token: Mutex::new(None),
+            agent: agent(Duration::from_secs(10)),
+        }
+    }
+
+    /// Reader configured as Google's client libraries are (Application
+    /// Default Credentials)
+    ///
+    /// The endpoint is `STORAGE_EMULATOR_HOST` when set, for emulators.
+    /// Credentials are the first found of:
+    ///
+    /// 1. an access token in `GOOGLE_OAUTH_ACCESS_TOKEN`
+    /// 2. the service account key or user credentials file named by
+    ///    `GOOGLE_APPLICATION_CREDENTIALS`
+    /// 3. the credentials of `gcloud auth application-default login`
+    /// 4. the metadata server (Compute Engine, Cloud Run, GKE), at
+    ///    `GCE_METADATA_HOST` if set
+    ///
+    /// Without any, requests are anonymous. Fails if a credentials file
+    /// cannot be read or is of an unsupported type.
+    pub fn from_env() -> Result<Self, ConversionError> {
+        let mut reader = Self::new();
+        if let Some(host) = var("STORAGE_EMULATOR_HOST") {
+            reader = reader.with_endpoint(if host.contains("://") {
+                host
+            } else {
+                format!("http://{}", host)
+            });
+        }
+
+        let source = if let Some(token) = var("GOOGLE_OAUTH_ACCESS_TOKEN") {
+            Some(TokenSource::Static(token))
+        } else if let Some(path) = var("GOOGLE_APPLICATION_CREDENTIALS") {
+            Some(read_credentials(Path::new(&path))?)
+        } else if let Some(path) = gcloud_credentials().filter(|path| path.is_file()) {
+            Some(read_credentials(&path)?)
+        } else {
+            metadata_server()
+        };
+        match source {
+            Some(source) => reader.source = Some(source),
+            None => tracing::debug!("No Google credentials found, reading GCS anonymously"),
+        }
+        Ok(reader)
+    }
+
+    /// Send `token` as the access token (default: anonymous requests)
+    pub fn with_token(mut self, token: impl Into<String>) -> Self {
+        self.source = Some(TokenSource::Static(token.into()));
+        self
+    }
+
+    /// Request objects from `endpoint` instead of Cloud Storage, for
+    /// emulators
+    pub fn with_endpoint(mut self, endpoint: impl Into<String>) -> Self {
+        self.endpoint = endpoint.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Current access token, requesting a new one if the last expired
+    fn access_token(&self) -> Result<Option<String>, ConversionError> {
+        let Some(source) = &self.source else {
+            return Ok(None);
+        };
+        if let TokenSource::Static(token) = source {
+            return Ok(Some(token.clone()));
+        }
+
+        let mut cached = self
+            .token
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some((token, expiry)) = cached.as_ref() {
+            if Instant::now() + EXPIRY_MARGIN < *expiry {
+                return Ok(Some(token.clone()));
+            }
+        }
+        let (token, lifetime) = request_token(&self.agent, source)?;
+        let expiry = Instant::now()
+            .checked_add(lifetime)
+            .unwrap_or_else(Instant::now);
+        *cached = Some((token.clone(), expiry));
+        Ok(Some(token))
+    }
+}
+
+impl Default for GcsReader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ObjectReader for GcsReader {
+    fn open(&self, bucket: &str, key: &str) -> Result<Object, ConversionError> {
+        let url = ObjectUrl {
+            scheme: Scheme::Gcs,
+            bucket: bucket.to_string(),
+            key: key.to_string(),
+        };
+        let mut request = self
+            .agent
+            .get(&format!(
+                "{}/storage/v1/b/{}/o/{}",
+                self.endpoint,
+                percent_encode(bucket, false),
+                percent_encode(key, false)
+            ))
+            .query("alt", "media");
+        if let Some(token) = self.access_token()? {
+            request = request.set("Authorization", &format!("Bearer {}", token));
+        }
+        let response = request.call().map_err(|e| request_error(&url, e))?;
+
+        let length = response
+            .header("Content-Length")
+            .and_then(|length| length.parse().ok());
+        Ok(Object {
+            reader: response.into_reader(),
+            length,
+        })
+    }
+}
+
+/// File written by `gcloud auth application-default login`
+fn gcloud_credentials() -> Option<PathBuf> {
+    let config = match var("CLOUDSDK_CONFIG") {
+        Some(dir) => PathBuf::from(dir),
+        None if cfg!(windows) => PathBuf::from(var("APPDATA")?).join("gcloud"),
+        None => home_dir()?.join(".config").join("gcloud"),
+    };
+    Some(config.join("application_default_credentials.json"))
+}
+
+/// Token source of a credentials file
+fn read_credentials(path: &Path) -> Result<TokenSource, ConversionError> {
+    let invalid = |reason: &str| {
+        ConversionError::Config(format!(
+            "Invalid Google credentials in {}: {}",
+            path.display(),
+            reason
+        ))
+    };
+    let content = std::fs::read(path).map_err(|e| invalid(&e.to_string()))?;
+    let credentials: Value =
+        serde_json::from_slice(&content).map_err(|e| invalid(&e.to_string()))?;
+    let field = |name: &str| {
+        credentials[name]
+            .as_str()
+            .map(str::to_string)
+            .ok_or_else(|| invalid(&format!("no {}", name)))
+    };
+
+    match credentials["type"].as_str() {
+        Some("service_account") => {
+            let pem = field("private_key")?;
+            let der: String = pem
+                .lines()
+                .filter(|line| !line.starts_with("-----"))
+                .collect();
+            let der = STANDARD
+                .decode(der.trim())
+                .map_err(|e| invalid(&format!("private_key: {}", e)))?;
+            let key = RsaKeyPair::from_pkcs8(&der)
+                .map(Box::new)
+                .map_err(|e| invalid(&format!("private_key: {}", e)))?;
+            Ok(TokenSource::ServiceAccount {
+                email: field("client_email")?,
+                key,
+                token_uri: field("token_uri").unwrap_or_else(|_| TOKEN_URI.to_string()),
+            })
+        }
+        Some("authorized_user") => Ok(TokenSource::AuthorizedUser {
+            client_id: field("client_id")?,
+            client_secret: field("client_secret")?,
+            refresh_token: field("refresh_token")?,
+        }),
+        Some(other) => Err(invalid(&format!("unsupported type {}", other))),
+        None => Err(invalid("no type")),
+    }
+}
+
+/// The metadata server, if there is one and it has a service account
+fn metadata_server() -> Option<TokenSource> {
+    let host = var("GCE_METADATA_HOST").unwrap_or_else(|| "169.254.169.254".to_string());
+    // Off Google Cloud nothing answers, so give up quickly
+    agent(Duration::from_secs(1))
+        .get(&format!(
+            "http://{}/computeMetadata/v1/instance/service-accounts/default/email",
+            host
+        ))
+        .set("Metadata-Flavor", "Google")
+        .call()
+        .ok()?;
+    Some(TokenSource::Metadata(host))
+}
+
+/// Request an access token from `source`, returning it with its lifetime
+fn request_token(
+    agent: &ureq::Agent,
+    source: &TokenSource,
+) -> Result<(String, Duration), ConversionError> {
+    let failed = |e: ureq::Error| {
+        let detail = match e {
+            ureq::Error::Status(status, response) => {
+                format!(
+                    "HTTP {} {}",
+                    status,
+                    response.into_string().unwrap_or_default()
+                )
+            }
+            e => e.to_string(),
+        };
+        ConversionError::Remote(format!("Cannot get a Google access token: {}", detail))
+    };
+
+    let response = match source {
+        TokenSource::Static(token) => return Ok((token.clone(), Duration::from_secs(3600))),
+        TokenSource::ServiceAccount {
+            email,
+            key,
+            token_uri,
+        } => {
+            let assertion = service_account_jwt(email, key, token_uri)?;
+            agent.post(token_uri).send_form(&[
+                ("grant_type", "urn:ietf:params:oauth:grant-type:jwt-bearer"),
+                ("assertion", &assertion),
+            ])
+        }
+        TokenSource::AuthorizedUser {
+            client_id,
+            client_secret,
+            refresh_token,
+        } => agent.post(TOKEN_URI).send_form(&[
+            ("grant_type", "refresh_token"),
+            ("client_id", client_id),
+            ("client_secret", client_secret),
+            ("refresh_token", refresh_token),
+        ]),
+        TokenSource::Metadata(host) => agent
+            .get(&format!(
+                "http://{}/computeMetadata/v1/instance/service-accounts/default/token",
+                host
+            ))
+            .set("Metadata-Flavor", "Google")
+            .call(),
+    }
+    .map_err(failed)?;
+
+    let body: Value = serde_json::from_reader(response.into_reader())?;
+    let token = body["access_token"].as_str().ok_or_else(|| {
+        ConversionError::Remote("Google token response has no access_token".to_string())
+    })?;
+    let lifetime = Duration::from_secs(body["expires_in"].as_u64().unwrap_or(3600));
+    Ok((token.to_string(), lifetime))
+}
+
+/// JWT asserting the identity of a service account, exchanged for an access
+/// token
+fn service_account_jwt(
+    email: &str,
+    key: &RsaKeyPair,
+    token_uri: &str,
+) -> Result<String, ConversionError> {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let header = json!({ "alg": "RS256", "typ": "JWT" });
+    let claims = json!({
+        "iss": email,
+        "scope": SCOPE,
+        "aud": token_uri,
+        "iat": now,
+        "exp": now + 3600,
+    });
+    let message = format!(
+        "{}.{}",
+        URL_SAFE_NO_PAD.encode(header.to_string()),
+        URL_SAFE_NO_PAD.encode(claims.to_string())
+    );
+    let mut signature = vec![0; key.public().modulus_len()];
+    key.sign(
+        &RSA_PKCS1_SHA256,
+        &SystemRandom::new(),
+        message.as_bytes(),
+        &mut signature,
+    )
+    .map_err(|_| ConversionError::Remote("Cannot sign the token request".to_string()))?;
+    Ok(format!("{}.{}", message, URL_SAFE_NO_PAD.encode(signature)))
+}
@@ -0,0 +1,247 @@
+//! Object storage sources (`s3://`, `gs://`)
+//!
+//! Objects in Amazon S3 (or an S3-compatible store) and Google Cloud
+//! Storage are streamed to a local file, then converted like any other file,
+//! so large documents are never held in memory:
+//!
+//! ```ignore
+//! use docling_rs::DocumentConverter;
+//!
+//! let result = DocumentConverter::new().convert_url("s3://reports/2024/annual.pdf")?;
+//! ```
+//!
+//! Credentials are discovered the way the providers' own tools do; see
+//! [`S3Reader::from_env`] and [`GcsReader::from_env`]. Without credentials,
+//! requests are anonymous, which reads public objects. S3 needs the `s3`
+//! feature and GCS the `gcs` feature.
+
+#[cfg(feature = "gcs")]
+mod gcs;
+#[cfg(feature = "s3")]
+mod s3;
+
+#[cfg(feature = "gcs")]
+pub use gcs::GcsReader;
+#[cfg(feature = "s3")]
+pub use s3::{Credentials, S3Reader};
+
+use crate::error::ConversionError;
+use crate::pipeline::ConversionLimits;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+/// Object storage service of an [`ObjectUrl`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scheme {
+    /// Amazon S3 or an S3-compatible store (`s3://`)
+    S3,
+
+    /// Google Cloud Storage (`gs://`)
+    Gcs,
+}
+
+/// Location of an object: `s3://bucket/key` or `gs://bucket/key`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ObjectUrl {
+    /// Storage service
+    pub scheme: Scheme,
+
+    /// Bucket name
+    pub bucket: String,
+
+    /// Object key (name), without leading `/`
+    pub key: String,
+}
+
+impl ObjectUrl {
+    /// Parse an `s3://` or `gs://` URL
+    pub fn parse(url: &str) -> Result<Self, ConversionError> {
+        let (scheme, rest) = url
+            .split_once("://")
+            .ok_or_else(|| ConversionError::Config(format!("Not an object URL: {}", url)))?;
+        let scheme = match scheme.to_ascii_lowercase().as_str() {
+            "s3" => Scheme::S3,
+            "gs" => Scheme::Gcs,
+            _ => {
+                return Err(ConversionError::Config(format!(
+                    "Unsupported URL scheme {}:// (expected s3:// or gs://)",
+                    scheme
+                )))
+            }
+        };
+        match rest.split_once('/') {
+            Some((bucket, key)) if !bucket.is_empty() && !key.is_empty() => Ok(Self {
+                scheme,
+                bucket: bucket.to_string(),
+                key: key.to_string(),
+            }),
+            _ => Err(ConversionError::Config(format!(
+                "Object URL needs a bucket and a key: {}",
+                url
+            ))),
+        }
+    }
+
+    /// Last segment of the key, which names the document
+    pub fn file_name(&self) -> &str {
+        self.key
+            .rsplit('/')
+            .find(|segment| !segment.is_empty())
+            .unwrap_or(&self.bucket)
+    }
+}
+
+impl fmt::Display for ObjectUrl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let scheme = match self.scheme {
+            Scheme::S3 => "s3",
+            Scheme::Gcs => "gs",
+        };
+        write!(f, "{}://{}/{}", scheme, self.bucket, self.key)
+    }
+}
+
+/// Whether `input` is an `s3://` or `gs://` URL
+pub fn is_object_url(input: &str) -> bool {
+    let lower = input.to_ascii_lowercase();
+    lower.starts_with("s3://") || lower.starts_with("gs://")
+}
+
+/// An object being read
+pub struct Object {
+    /// Content of the object, streamed from the store
+    pub reader: Box<dyn Read + Send>,
+
+    /// Size of the object in bytes, when the store sends it
+    pub length: Option<u64>,
+}
+
+/// Reads objects from a storage service
+pub trait ObjectReader: Send + Sync {
+    /// Start reading the object `key` of `bucket`
+    ///
+    /// Missing objects fail with [`ConversionError::FileNotFound`].
+    fn open(&self, bucket: &str, key: &str) -> Result<Object, ConversionError>;
+}
+
+/// Reader for `scheme`, with credentials discovered from the environment
+pub fn reader_for(scheme: Scheme) -> Result<Box<dyn ObjectReader>, ConversionError> {
+    match scheme {
+        #[cfg(feature = "s3")]
+        Scheme::S3 => Ok(Box::new(S3Reader::from_env())),
+        #[cfg(not(feature = "s3"))]
+        Scheme::S3 => Err(ConversionError::Config(
+            "s3:// sources require docling-rs to be built with the `s3` feature".to_string(),
+        )),
+        #[cfg(feature = "gcs")]
+        Scheme::Gcs => Ok(Box::new(GcsReader::from_env()?)),
+        #[cfg(not(feature = "gcs"))]
+        Scheme::Gcs => Err(ConversionError::Config(
+            "gs:// sources require docling-rs to be built with the `gcs` feature".to_string(),
+        )),
+    }
+}
+
+/// Stream the object at `url` into `dir` with `reader`, returning the path
+/// of the file, named after the last segment of the key
+///
+/// The input size limit applies to the download, which stops as soon as it
+/// goes over.
+pub fn download(
+    url: &ObjectUrl,
+    reader: &dyn ObjectReader,
+    dir: &Path,
+    limits: &ConversionLimits,
+) -> Result<PathBuf, ConversionError> {
+    let limits = limits.start();
+    let object = reader.open(&url.bucket, &url.key)?;
+    if let Some(length) = object.length {
+        limits.check_input_size(length)?;
+    }
+
+    let name: String = url
+        .file_name()
+        .chars()
+        .map(|c| if matches!(c, '\\' | ':') { '-' } else { c })
+        .collect();
+    let name = match name.as_str() {
+        "." | ".." => "object",
+        name => name,
+    };
+    let path = dir.join(name);
+    let mut file = File::create(&path)?;
+    let max = limits.limits().max_input_bytes().unwrap_or(u64::MAX);
+    let copied = io::copy(&mut object.reader.take(max.saturating_add(1)), &mut file)
+        .map_err(|e| ConversionError::Remote(format!("Failed to download {}: {}", url, e)))?;
+    limits.check_input_size(copied)?;
+    Ok(path)
+}
+
+/// User agent sent to storage services
+#[cfg(any(feature = "s3", feature = "gcs"))]
+const USER_AGENT: &str = concat!("docling-rs/", env!("CARGO_PKG_VERSION"));
+
+/// HTTP agent giving up on connections after `connect` and on responses that
+/// send nothing for 30 seconds
+#[cfg(any(feature = "s3", feature = "gcs"))]
+fn agent(connect: std::time::Duration) -> ureq::Agent {
+    ureq::AgentBuilder::new()
+        .timeout_connect(connect)
+        .timeout_read(std::time::Duration::from_secs(30))
+        .user_agent(USER_AGENT)
+        .build()
+}
+
+/// Error of a failed request for `url`
+#[cfg(any(feature = "s3", feature = "gcs"))]
+fn request_error(url: &ObjectUrl, error: ureq::Error) -> ConversionError {
+    match error {
+        ureq::Error::Status(404, _) => {
+            ConversionError::FileNotFound(PathBuf::from(url.to_string()))
+        }
+        ureq::Error::Status(status, response) => {
+            let text = response.status_text().to_string();
+            let body = response.into_string().unwrap_or_default();
+            ConversionError::Remote(format!(
+                "Failed to read {}: HTTP {} {} {}",
+                url,
+                status,
+                text,
+                body.trim()
+            ))
+        }
+        e => ConversionError::Remote(format!("Failed to read {}: {}", url, e)),
+    }
+}
+
+/// Percent-encode `value` for a URL path, keeping `/` if `keep_slash`
+#[cfg(any(feature = "s3", feature = "gcs"))]
+fn percent_encode(value: &str, keep_slash: bool) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            b'/' if keep_slash => encoded.push('/'),
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+/// Value of the environment variable `name`, if set and not empty
+#[cfg(any(feature = "s3", feature = "gcs"))]
+fn var(name: &str) -> Option<String> {
+    std::env::var(name).ok().filter(|value| !value.is_empty())
+}
+
+/// Home directory of the user
+#[cfg(any(feature = "s3", feature = "gcs"))]
+fn home_dir() -> Option<PathBuf> {
+    var("HOME")
+        .or_else(|| var("USERPROFILE"))
+        .map(PathBuf::from)
+}
@@ -0,0 +1,503 @@
+//! Amazon S3 and S3-compatible stores
+
+use super::{
+    agent, home_dir, percent_encode, request_error, var, Object, ObjectReader, ObjectUrl, Scheme,
+};
+use crate::error::ConversionError;
+use chrono::{DateTime, Utc};
+use ring::hmac;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fmt;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Region of buckets when none is configured
+const DEFAULT_REGION: &str = "us-east-1";
+
+/// Payload hash of requests whose body is not signed
+const UNSIGNED_PAYLOAD: &str = "UNSIGNED-PAYLOAD";
+
+/// Access key of an AWS account or role
+#[derive(Clone)]
+pub struct Credentials {
+    /// Access key ID
+    pub access_key_id: String,
+
+    /// Secret access key
+    pub secret_access_key: String,
+
+    /// Session token of temporary credentials
+    pub session_token: Option<String>,
+}
+
+impl Credentials {
+    /// Long-term credentials
+    pub fn new(access_key_id: impl Into<String>, secret_access_key: impl Into<String>) -> Self {
+        Self {
+            access_key_id: access_key_id.into(),
+            secret_access_key: secret_access_key.into(),
+            session_token: None,
+        }
+    }
+
+    /// Add the session token of temporary credentials
+    pub fn with_session_token(mut self, token: impl Into<String>) -> Self {
+        self.session_token = Some(token.into());
+        self
+    }
+}
+
+// The secret stays out of logs
+impl fmt::Debug for Credentials {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Credentials")
+            .field("access_key_id", &self.access_key_id)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Reads objects from Amazon S3 or an S3-compatible store (MinIO, R2,
+/// Ceph...), signing requests with AWS Signature Version 4
+///
+/// Objects are requested from `https://<bucket>.s3.<region>.amazonaws.com`,
+/// or from `<endpoint>/<bucket>` once an endpoint is set. Buckets in another
+/// region than the one configured are found through the region S3 answers
+/// with. Requires the `s3` feature.
+///
+/// ```ignore
+/// use docling_rs::remote::{Credentials, S3Reader};
+///
+/// let reader = S3Reader::new("eu-west-1")
+///     .with_endpoint("http://localhost:9000")
+///     .with_credentials(Credentials::new("minio", "minio-secret"));
+/// ```
+#[derive(Debug)]
+pub struct S3Reader {
+    region: String,
+    endpoint: Option<String>,
+    credentials: Option<Credentials>,
+    agent: ureq::Agent,
+}
+
+impl S3Reader {
+    /// Anonymous reader of the AWS buckets of `region`
+    pub fn new(region: impl Into<String>) -> Self {
+        Self {
+            region: region.into(),
+            endpoint: None,
+            credentials: None,
+            // Signed requests are not sent on to other hosts
+            agent: ureq::AgentBuilder::new()
+                .timeout_connect(Duration::from_secs(10))
+                .timeout_read(Duration::from_secs(30))
+                .user_agent(super::USER_AGENT)
+                .redirects(0)
+                .build(),
+        }
+    }
+
+    /// Reader configured as the AWS CLI and SDKs are
+    ///
+    /// The profile is `AWS_PROFILE`, or `default`. The region comes from
+    /// `AWS_REGION`, `AWS_DEFAULT_REGION` or the profile in `~/.aws/config`
+    /// (`AWS_CONFIG_FILE`), and is `us-east-1` otherwise; the endpoint from
+    /// `AWS_ENDPOINT_URL_S3` or `AWS_ENDPOINT_URL`. Credentials are the first
+    /// found of:
+    ///
+    /// 1. `AWS_ACCESS_KEY_ID`, `AWS_SECRET_ACCESS_KEY` and `AWS_SESSION_TOKEN`
+    /// 2. the profile in `~/.aws/credentials` (`AWS_SHARED_CREDENTIALS_FILE`)
+    ///    or `~/.aws/config`
+    /// 3. a web identity token (`AWS_WEB_IDENTITY_TOKEN_FILE` and
+    ///    `AWS_ROLE_ARN`, as on EKS), exchanged with STS
+    /// 4. the container credentials endpoint (ECS, EKS Pod Identity)
+    /// 5. the instance metadata service (EC2), unless
+    ///    `AWS_EC2_METADATA_DISABLED` is `true`
+    ///
+    /// Without any, requests are anonymous.
+    pub fn from_env() -> Self {
+        let profile = var("AWS_PROFILE").unwrap_or_else(|| "default".to_string());
+        let config = read_profile(
+            var("AWS_CONFIG_FILE").map(PathBuf::from),
+            ".aws/config",
+            &if profile == "default" {
+                profile.clone()
+            } else {
+                format!("profile {}", profile)
+            },
+        );
+        let region = var("AWS_REGION")
+            .or_else(|| var("AWS_DEFAULT_REGION"))
+            .or_else(|| config.get("region").cloned())
+            .unwrap_or_else(|| DEFAULT_REGION.to_string());
+
+        let mut reader = Self::new(region);
+        if let Some(endpoint) = var("AWS_ENDPOINT_URL_S3").or_else(|| var("AWS_ENDPOINT_URL")) {
+            reader = reader.with_endpoint(endpoint);
+        }
+        let credentials = env_credentials()
+            .or_else(|| {
+                let file = read_profile(
+                    var("AWS_SHARED_CREDENTIALS_FILE").map(PathBuf::from),
+                    ".aws/credentials",
+                    &profile,
+                );
+                profile_credentials(&file)
+            })
+            .or_else(|| profile_credentials(&config))
+            .or_else(|| web_identity_credentials(&reader.region))
+            .or_else(container_credentials)
+            .or_else(instance_credentials);
+        match credentials {
+            Some(credentials) => reader.with_credentials(credentials),
+            None => {
+                tracing::debug!("No AWS credentials found, reading S3 anonymously");
+                reader
+            }
+        }
+    }
+
+    /// Sign requests with `credentials` (default: anonymous requests)
+    pub fn with_credentials(mut self, credentials: Credentials) -> Self {
+        self.credentials = Some(credentials);
+        self
+    }
+
+    /// Request objects from `endpoint`, as `<endpoint>/<bucket>/<key>`, for
+    /// S3-compatible stores
+    pub fn with_endpoint(mut self, endpoint: impl Into<String>) -> Self {
+        self.endpoint = Some(endpoint.into().trim_end_matches('/').to_string());
+        self
+    }
+
+    /// Region requests are signed for
+    pub fn region(&self) -> &str {
+        &self.region
+    }
+
+    /// URL, `Host` header and path of the object `key` of `bucket`
+    fn location(&self, bucket: &str, key: &str, region: &str) -> (String, String, String) {
+        let key = percent_encode(key, true);
+        let (base, path) = match &self.endpoint {
+            Some(endpoint) => (endpoint.clone(), format!("/{}/{}", bucket, key)),
+            // Bucket names with dots do not match the certificate of
+            // virtual-hosted URLs
+            None if bucket.contains('.') => (
+                format!("https://s3.{}.amazonaws.com", region),
+                format!("/{}/{}", bucket, key),
+            ),
+            None => (
+                format!("https://{}.s3.{}.amazonaws.com", bucket, region),
+                format!("/{}", key),
+            ),
+        };
+        let (scheme, rest) = base.split_once("://").unwrap_or(("https", &base));
+        let (authority, prefix) = match rest.split_once('/') {
+            Some((authority, prefix)) => (authority, format!("/{}", prefix)),
+            None => (rest, String::new()),
+        };
+        // Default ports are left out of the Host header
+        let host = match (scheme, authority.rsplit_once(':')) {
+            ("https", Some((host, "443"))) | ("http", Some((host, "80"))) => host,
+            _ => authority,
+        };
+        let path = format!("{}{}", prefix, path);
+        (
+            format!("{}://{}{}", scheme, authority, path),
+            host.to_string(),
+            path,
+        )
+    }
+
+    /// Request the object at `url` from `region`, whatever the status of
+    /// the response
+    fn get(&self, url: &ObjectUrl, region: &str) -> Result<ureq::Response, ConversionError> {
+        let (location, host, path) = self.location(&url.bucket, &url.key, region);
+        let mut request = self.agent.get(&location);
+        if let Some(credentials) = &self.credentials {
+            let headers = sign(
+                credentials,
+                region,
+                &host,
+                &path,
+                &[("x-amz-content-sha256", UNSIGNED_PAYLOAD)],
+                UNSIGNED_PAYLOAD,
+                Utc::now(),
+            );
+            for (name, value) in headers {
+                request = request.set(&name, &value);
+            }
+        }
+        match request.call() {
+            Ok(response) | Err(ureq::Error::Status(_, response)) => Ok(response),
+            Err(e) => Err(request_error(url, e)),
+        }
+    }
+}
+
+impl ObjectReader for S3Reader {
+    fn open(&self, bucket: &str, key: &str) -> Result<Object, ConversionError> {
+        let url = ObjectUrl {
+            scheme: Scheme::S3,
+            bucket: bucket.to_string(),
+            key: key.to_string(),
+        };
+        let mut response = self.get(&url, &self.region)?;
+        // A bucket of another region answers with its region, as a redirect
+        // or an error
+        let moved = response
+            .header("x-amz-bucket-region")
+            .filter(|region| *region != self.region)
+            .map(str::to_string);
+        if let Some(region) = moved.filter(|_| response.status() >= 300) {
+            tracing::debug!("{} is in {}, not {}", url, region, self.region);
+            response = self.get(&url, &region)?;
+        }
+        if response.status() >= 300 {
+            return Err(request_error(
+                &url,
+                ureq::Error::Status(response.status(), response),
+            ));
+        }
+
+        let length = response
+            .header("Content-Length")
+            .and_then(|length| length.parse().ok());
+        Ok(Object {
+            reader: response.into_reader(),
+            length,
+        })
+    }
+}
+
+/// Headers authenticating a `GET` of `path` on `host` with `credentials`:
+/// `headers` (lowercase names) signed along, `x-amz-date`, the session
+/// token if any, and `Authorization`
+fn sign(
+    credentials: &Credentials,
+    region: &str,
+    host: &str,
+    path: &str,
+    headers: &[(&str, &str)],
+    payload_hash: &str,
+    now: DateTime<Utc>,
+) -> Vec<(String, String)> {
+    let timestamp = now.format("%Y%m%dT%H%M%SZ").to_string();
+    let date = now.format("%Y%m%d").to_string();
+
+    let mut signed: Vec<(String, String)> = vec![
+        ("host".to_string(), host.to_string()),
+        ("x-amz-date".to_string(), timestamp.clone()),
+    ];
+    signed.extend(
+        headers
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.trim().to_string())),
+    );
+    if let Some(token) = &credentials.session_token {
+        signed.push(("x-amz-security-token".to_string(), token.clone()));
+    }
+    signed.sort();
+
+    let names = signed
+        .iter()
+        .map(|(name, _)| name.as_str())
+        .collect::<Vec<_>>()
+        .join(";");
+    let canonical_headers: String = signed
+        .iter()
+        .map(|(name, value)| format!("{}:{}\n", name, value))
+        .collect();
+    let canonical_request = format!(
+        "GET\n{}\n\n{}\n{}\n{}",
+        path, canonical_headers, names, payload_hash
+    );
+    let scope = format!("{}/{}/s3/aws4_request", date, region);
+    let string_to_sign = format!(
+        "AWS4-HMAC-SHA256\n{}\n{}\n{:x}",
+        timestamp,
+        scope,
+        Sha256::digest(canonical_request.as_bytes())
+    );
+
+    let mut key = format!("AWS4{}", credentials.secret_access_key).into_bytes();
+    for part in [date.as_str(), region, "s3", "aws4_request"] {
+        key = hmac::sign(&hmac::Key::new(hmac::HMAC_SHA256, &key), part.as_bytes())
+            .as_ref()
+            .to_vec();
+    }
+    let signature = hmac::sign(
+        &hmac::Key::new(hmac::HMAC_SHA256, &key),
+        string_to_sign.as_bytes(),
+    );
+    let signature: String = signature
+        .as_ref()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+
+    // Host is set by the HTTP client
+    let mut output: Vec<(String, String)> = signed
+        .into_iter()
+        .filter(|(name, _)| name != "host")
+        .collect();
+    output.push((
+        "Authorization".to_string(),
+        format!(
+            "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+            credentials.access_key_id, scope, names, signature
+        ),
+    ));
+    output
+}
+
+/// Credentials of the `AWS_*` environment variables
+fn env_credentials() -> Option<Credentials> {
+    let credentials = Credentials::new(var("AWS_ACCESS_KEY_ID")?, var("AWS_SECRET_ACCESS_KEY")?);
+    Some(match var("AWS_SESSION_TOKEN") {
+        Some(token) => credentials.with_session_token(token),
+        None => credentials,
+    })
+}
+
+/// Credentials of a profile section of an AWS configuration file
+fn profile_credentials(profile: &HashMap<String, String>) -> Option<Credentials> {
+    let credentials = Credentials::new(
+        profile.get("aws_access_key_id")?,
+        profile.get("aws_secret_access_key")?,
+    );
+    Some(match profile.get("aws_session_token") {
+        Some(token) => credentials.with_session_token(token),
+        None => credentials,
+    })
+}
+
+/// Keys of `section` in the AWS configuration file at `path`, or at
+/// `default` under the home directory; empty if there is no such file or
+/// section
+fn read_profile(path: Option<PathBuf>, default: &str, section: &str) -> HashMap<String, String> {
+    let Some(path) = path.or_else(|| home_dir().map(|home| home.join(default))) else {
+        return HashMap::new();
+    };
+    let Ok(content) = std::fs::read_to_string(path) else {
+        return HashMap::new();
+    };
+    let mut current = None;
+    let mut keys = HashMap::new();
+    for line in content.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with(['#', ';']) {
+            continue;
+        }
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            current = Some(name.trim().to_string());
+        } else if current.as_deref() == Some(section) {
+            if let Some((key, value)) = line.split_once('=') {
+                keys.insert(key.trim().to_lowercase(), value.trim().to_string());
+            }
+        }
+    }
+    keys
+}
+
+/// Credentials of the role of a web identity token, from STS
+fn web_identity_credentials(region: &str) -> Option<Credentials> {
+    let token = std::fs::read_to_string(var("AWS_WEB_IDENTITY_TOKEN_FILE")?).ok()?;
+    let role = var("AWS_ROLE_ARN")?;
+    let session = var("AWS_ROLE_SESSION_NAME").unwrap_or_else(|| "docling-rs".to_string());
+    let response = agent(Duration::from_secs(5))
+        .get(&format!("https://sts.{}.amazonaws.com/", region))
+        .query("Action", "AssumeRoleWithWebIdentity")
+        .query("Version", "2011-06-15")
+        .query("RoleArn", &role)
+        .query("RoleSessionName", &session)
+        .query("WebIdentityToken", token.trim())
+        .call()
+        .map_err(|e| tracing::warn!("Cannot assume {} with a web identity: {}", role, e))
+        .ok()?
+        .into_string()
+        .ok()?;
+    let element = |name: &str| {
+        let start = response.find(&format!("<{}>", name))? + name.len() + 2;
+        let end = start + response[start..].find(&format!("</{}>", name))?;
+        Some(response[start..end].to_string())
+    };
+    Some(
+        Credentials::new(element("AccessKeyId")?, element("SecretAccessKey")?)
+            .with_session_token(element("SessionToken")?),
+    )
+}
+
+/// Credentials of the container credentials endpoint of ECS and EKS Pod
+/// Identity
+fn container_credentials() -> Option<Credentials> {
+    let url = match var("AWS_CONTAINER_CREDENTIALS_RELATIVE_URI") {
+        Some(path) => format!("http://169.254.170.2{}", path),
+        None => var("AWS_CONTAINER_CREDENTIALS_FULL_URI")?,
+    };
+    let token = var("AWS_CONTAINER_AUTHORIZATION_TOKEN").or_else(|| {
+        std::fs::read_to_string(var("AWS_CONTAINER_AUTHORIZATION_TOKEN_FILE")?)
+            .ok()
+            .map(|token| token.trim().to_string())
+    });
+    let mut request = agent(Duration::from_secs(2)).get(&url);
+    if let Some(token) = &token {
+        request = request.set("Authorization", token);
+    }
+    let response = request
+        .call()
+        .map_err(|e| tracing::warn!("Cannot read container credentials: {}", e))
+        .ok()?;
+    json_credentials(response)
+}
+
+/// Credentials of the role of the EC2 instance, from the instance metadata
+/// service (IMDSv2)
+fn instance_credentials() -> Option<Credentials> {
+    if var("AWS_EC2_METADATA_DISABLED")
+        .is_some_and(|disabled| disabled.eq_ignore_ascii_case("true"))
+    {
+        return None;
+    }
+    let endpoint = var("AWS_EC2_METADATA_SERVICE_ENDPOINT")
+        .unwrap_or_else(|| "http://169.254.169.254".to_string());
+    let endpoint = endpoint.trim_end_matches('/');
+    // Off EC2 nothing answers, so give up quickly
+    let agent = agent(Duration::from_secs(1));
+    let token = agent
+        .put(&format!("{}/latest/api/token", endpoint))
+        .set("X-aws-ec2-metadata-token-ttl-seconds", "21600")
+        .call()
+        .ok()?
+        .into_string()
+        .ok()?;
+    let roles = format!("{}/latest/meta-data/iam/security-credentials/", endpoint);
+    let role = agent
+        .get(&roles)
+        .set("X-aws-ec2-metadata-token", &token)
+        .call()
+        .ok()?
+        .into_string()
+        .ok()?;
+    let role = role.lines().next()?.trim();
+    let response = agent
+        .get(&format!("{}{}", roles, role))
+        .set("X-aws-ec2-metadata-token", &token)
+        .call()
+        .map_err(|e| tracing::warn!("Cannot read the credentials of {}: {}", role, e))
+        .ok()?;
+    json_credentials(response)
+}
+
+/// Credentials of a JSON response of the container or instance metadata
+/// endpoints
+fn json_credentials(response: ureq::Response) -> Option<Credentials> {
+    let body: serde_json::Value = serde_json::from_reader(response.into_reader()).ok()?;
+    let credentials = Credentials::new(
+        body["AccessKeyId"].as_str()?,
+        body["SecretAccessKey"].as_str()?,
+    );
+    Some(match body["Token"].as_str() {
+        Some(token) => credentials.with_session_token(token),
+        None => credentials,
+    })
+}
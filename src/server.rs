@@ -0,0 +1,541 @@
+//! Embeddable REST server (`server` feature)
+//!
+//! [`router`] builds an [axum](https://docs.rs/axum) [`Router`] answering
+//! conversion requests, to be served as is or mounted inside another
+//! service:
+//!
+//! ```ignore
+//! use docling_rs::pipeline::ConversionLimits;
+//!
+//! let app = axum::Router::new()
+//!     .nest("/docling", docling_rs::server::router(ConversionLimits::new()));
+//! let listener = tokio::net::TcpListener::bind("0.0.0.0:8080").await?;
+//! axum::serve(listener, app).await?;
+//! ```
+//!
+//! Requests and responses are JSON, typed by the structs of this module:
+//!
+//! - `POST /convert`: [`ConvertRequest`] answered with [`ConvertResponse`]
+//! - `POST /chunk`: [`ChunkRequest`] answered with [`ChunkResponse`]
+//! - `GET /health`: [`HealthResponse`], for load balancers and probes
+//! - `GET /openapi.json`: the OpenAPI document of these endpoints ([`openapi`])
+//!
+//! Documents travel base64-encoded in the `data` field of requests. Failed
+//! requests are answered with an [`ErrorResponse`] and status 400 for
+//! malformed requests, 413 for documents over the input size limit, 415 for
+//! unsupported formats, and 422 for documents that fail to convert.
+//!
+//! Conversions run on tokio's blocking thread pool, so the router needs a
+//! tokio runtime. `docling-rs serve` answers the same endpoints with
+//! multipart uploads instead, without tokio.
+
+use crate::chunking::{BaseChunk, ChunkerOptions, ChunkerRegistry};
+use crate::cli::output;
+use crate::datamodel::{ConversionMetrics, ConversionWarning, DoclingDocument};
+use crate::error::ConversionError;
+use crate::pipeline::{ConversionLimits, ExceededLimit};
+use crate::{DocumentConverter, InputFormat, OutputFormat};
+use axum::extract::rejection::JsonRejection;
+use axum::extract::{DefaultBodyLimit, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use clap::ValueEnum;
+use schemars::gen::SchemaSettings;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+/// Room left in request bodies for the JSON around the document
+const JSON_OVERHEAD: u64 = 64 * 1024;
+
+/// Body of a `POST /convert` request
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ConvertRequest {
+    /// File name of the document; its extension gives the format unless
+    /// `from` is set
+    pub name: String,
+
+    /// Content of the document, base64-encoded
+    pub data: String,
+
+    /// Input format: `markdown`, `html`, `csv`, `docx`, `pdf`, `text`,
+    /// `code` or an extension
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from: Option<String>,
+
+    /// Output format, as `docling-rs --to` (default: `json`); `json` gives
+    /// the document as `document`, the other formats as `content`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to: Option<String>,
+}
+
+impl ConvertRequest {
+    /// Request the conversion of `content`, named `name`
+    pub fn new(name: impl Into<String>, content: &[u8]) -> Self {
+        Self {
+            name: name.into(),
+            data: BASE64.encode(content),
+            from: None,
+            to: None,
+        }
+    }
+
+    /// Set the input format
+    pub fn with_from(mut self, from: impl Into<String>) -> Self {
+        self.from = Some(from.into());
+        self
+    }
+
+    /// Set the output format
+    pub fn with_to(mut self, to: impl Into<String>) -> Self {
+        self.to = Some(to.into());
+        self
+    }
+}
+
+/// Body of a `POST /convert` response
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ConvertResponse {
+    /// File name of the document
+    pub name: String,
+
+    /// Problems that did not stop the conversion
+    pub warnings: Vec<ConversionWarning>,
+
+    /// Measurements of the conversion
+    pub metrics: ConversionMetrics,
+
+    /// The converted document, for `json` output
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub document: Option<DoclingDocument>,
+
+    /// The converted document, for every other output format
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+}
+
+/// Body of a `POST /chunk` request
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ChunkRequest {
+    /// File name of the document; its extension gives the format unless
+    /// `from` is set
+    pub name: String,
+
+    /// Content of the document, base64-encoded
+    pub data: String,
+
+    /// Input format: `markdown`, `html`, `csv`, `docx`, `pdf`, `text`,
+    /// `code` or an extension
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from: Option<String>,
+
+    /// Chunking strategy: `hierarchical` (default), `hybrid`, `recursive`,
+    /// `fixed`, `window` or `semantic`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chunker: Option<String>,
+
+    /// Token limit per chunk (`hybrid`, `window`, `semantic`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1))]
+    pub max_tokens: Option<usize>,
+
+    /// Character limit per chunk (`recursive`, `fixed`; default: 1000)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1))]
+    pub chunk_size: Option<usize>,
+}
+
+impl ChunkRequest {
+    /// Request the chunks of `content`, named `name`
+    pub fn new(name: impl Into<String>, content: &[u8]) -> Self {
+        Self {
+            name: name.into(),
+            data: BASE64.encode(content),
+            from: None,
+            chunker: None,
+            max_tokens: None,
+            chunk_size: None,
+        }
+    }
+
+    /// Set the input format
+    pub fn with_from(mut self, from: impl Into<String>) -> Self {
+        self.from = Some(from.into());
+        self
+    }
+
+    /// Set the chunking strategy
+    pub fn with_chunker(mut self, chunker: impl Into<String>) -> Self {
+        self.chunker = Some(chunker.into());
+        self
+    }
+
+    /// Set the token limit per chunk
+    pub fn with_max_tokens(mut self, max_tokens: usize) -> Self {
+        self.max_tokens = Some(max_tokens);
+        self
+    }
+
+    /// Set the character limit per chunk
+    pub fn with_chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = Some(chunk_size);
+        self
+    }
+}
+
+/// Body of a `POST /chunk` response
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ChunkResponse {
+    /// File name of the document
+    pub name: String,
+
+    /// Chunking strategy used
+    pub chunker: String,
+
+    /// Problems that did not stop the conversion
+    pub warnings: Vec<ConversionWarning>,
+
+    /// Chunks of the document, in order
+    pub chunks: Vec<BaseChunk>,
+}
+
+/// Body of a `GET /health` response
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct HealthResponse {
+    /// Always `ok`
+    pub status: String,
+
+    /// Version of docling-rs
+    pub version: String,
+}
+
+/// Body of the response to a failed request
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ErrorResponse {
+    /// What went wrong
+    pub error: String,
+}
+
+/// Failed request: a status code and a message
+#[derive(Debug)]
+struct ApiError {
+    status: StatusCode,
+    message: String,
+}
+
+impl ApiError {
+    fn new(status: StatusCode, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            message: message.into(),
+        }
+    }
+
+    fn bad_request(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::BAD_REQUEST, message)
+    }
+
+    fn internal(error: impl std::fmt::Display) -> Self {
+        Self::new(StatusCode::INTERNAL_SERVER_ERROR, error.to_string())
+    }
+}
+
+impl From<ConversionError> for ApiError {
+    fn from(error: ConversionError) -> Self {
+        let status = match &error {
+            ConversionError::UnsupportedFormat(_) => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            ConversionError::LimitExceeded(ExceededLimit::InputSize { .. }) => {
+                StatusCode::PAYLOAD_TOO_LARGE
+            }
+            ConversionError::Io(_)
+            | ConversionError::Serialization(_)
+            | ConversionError::Schema(_)
+            | ConversionError::Config(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            _ => StatusCode::UNPROCESSABLE_ENTITY,
+        };
+        Self::new(status, error.to_string())
+    }
+}
+
+impl From<JsonRejection> for ApiError {
+    fn from(rejection: JsonRejection) -> Self {
+        // axum answers 422 for JSON of the wrong shape, which is kept for
+        // documents that fail to convert
+        let status = match rejection.status() {
+            StatusCode::PAYLOAD_TOO_LARGE => StatusCode::PAYLOAD_TOO_LARGE,
+            _ => StatusCode::BAD_REQUEST,
+        };
+        Self::new(status, rejection.body_text())
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let body = ErrorResponse {
+            error: self.message,
+        };
+        (self.status, Json(body)).into_response()
+    }
+}
+
+/// Build the router of the server, converting documents within `limits`
+///
+/// Request bodies are bounded by the input size limit, with room for the
+/// base64 encoding and the JSON around the document.
+pub fn router(limits: ConversionLimits) -> Router {
+    let body_limit = match limits.max_input_bytes() {
+        Some(max) => DefaultBodyLimit::max(
+            usize::try_from(max.div_ceil(3) * 4 + JSON_OVERHEAD).unwrap_or(usize::MAX),
+        ),
+        None => DefaultBodyLimit::disable(),
+    };
+    let converter = Arc::new(DocumentConverter::new().with_limits(limits));
+    let spec = Arc::new(openapi());
+    Router::new()
+        .route("/health", get(health))
+        .route("/convert", post(convert))
+        .route("/chunk", post(chunk))
+        .route(
+            "/openapi.json",
+            get(move || async move { Json(spec.as_ref().clone()) }),
+        )
+        .layer(body_limit)
+        .with_state(converter)
+}
+
+async fn health() -> Json<HealthResponse> {
+    Json(HealthResponse {
+        status: "ok".to_string(),
+        version: env!("CARGO_PKG_VERSION").to_string(),
+    })
+}
+
+async fn convert(
+    State(converter): State<Arc<DocumentConverter>>,
+    request: Result<Json<ConvertRequest>, JsonRejection>,
+) -> Result<Json<ConvertResponse>, ApiError> {
+    let Json(request) = request?;
+    tokio::task::spawn_blocking(move || convert_document(&converter, request))
+        .await
+        .map_err(ApiError::internal)?
+        .map(Json)
+}
+
+async fn chunk(
+    State(converter): State<Arc<DocumentConverter>>,
+    request: Result<Json<ChunkRequest>, JsonRejection>,
+) -> Result<Json<ChunkResponse>, ApiError> {
+    let Json(request) = request?;
+    tokio::task::spawn_blocking(move || chunk_document(&converter, request))
+        .await
+        .map_err(ApiError::internal)?
+        .map(Json)
+}
+
+fn convert_document(
+    converter: &DocumentConverter,
+    request: ConvertRequest,
+) -> Result<ConvertResponse, ApiError> {
+    let to = match request.to.as_deref() {
+        Some(to) => OutputFormat::from_str(to, true)
+            .map_err(|_| ApiError::bad_request(format!("Unknown output format: {}", to)))?,
+        None => OutputFormat::Json,
+    };
+    if to == OutputFormat::Parquet {
+        return Err(ApiError::bad_request(
+            "Parquet output cannot be sent as JSON",
+        ));
+    }
+    if to.needs_chunks() {
+        return Err(ApiError::bad_request(format!(
+            "{} output holds chunks: use /chunk",
+            to
+        )));
+    }
+
+    let result = load(
+        converter,
+        &request.name,
+        &request.data,
+        request.from.as_deref(),
+    )?;
+    let (document, content) = match to {
+        OutputFormat::Json => (Some(result.document().clone()), None),
+        OutputFormat::DoclingJson => {
+            let json = output::to_docling_json(result.document()).map_err(ApiError::internal)?;
+            (None, Some(json))
+        }
+        _ => {
+            let content = output::render(result.document(), to).map_err(ApiError::internal)?;
+            (None, Some(content))
+        }
+    };
+    Ok(ConvertResponse {
+        name: request.name,
+        warnings: result.warnings().to_vec(),
+        metrics: result.metrics().clone(),
+        document,
+        content,
+    })
+}
+
+fn chunk_document(
+    converter: &DocumentConverter,
+    request: ChunkRequest,
+) -> Result<ChunkResponse, ApiError> {
+    for (name, value) in [
+        ("max_tokens", request.max_tokens),
+        ("chunk_size", request.chunk_size),
+    ] {
+        if value == Some(0) {
+            return Err(ApiError::bad_request(format!(
+                "{} must be a positive integer",
+                name
+            )));
+        }
+    }
+    let kind = request.chunker.as_deref().unwrap_or("hierarchical");
+    let options = ChunkerOptions {
+        max_tokens: request.max_tokens,
+        chunk_size: Some(request.chunk_size.unwrap_or(1000)),
+        tokenizer: None,
+        embedder: None,
+    };
+    let chunker = ChunkerRegistry::new()
+        .create(kind, options)
+        .map_err(|e| ApiError::bad_request(e.to_string()))?;
+
+    let result = load(
+        converter,
+        &request.name,
+        &request.data,
+        request.from.as_deref(),
+    )?;
+    let chunks = chunker.chunk(result.document()).collect();
+    Ok(ChunkResponse {
+        chunker: kind.to_string(),
+        name: request.name,
+        warnings: result.warnings().to_vec(),
+        chunks,
+    })
+}
+
+/// Decode and convert the document `data`, named `name`, of format `from`
+/// or else detected from its name and content
+fn load(
+    converter: &DocumentConverter,
+    name: &str,
+    data: &str,
+    from: Option<&str>,
+) -> Result<crate::ConversionResult, ApiError> {
+    let data = BASE64
+        .decode(data)
+        .map_err(|e| ApiError::bad_request(format!("`data` is not valid base64: {}", e)))?;
+    let format = match from {
+        Some(from) => InputFormat::from_name(from).ok_or_else(|| {
+            ApiError::new(
+                StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                format!("Unsupported file format: {}", from),
+            )
+        })?,
+        None => InputFormat::detect(name, &data).ok_or_else(|| {
+            ApiError::new(
+                StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                format!(
+                    "Unsupported file format: cannot tell the format of {}",
+                    name
+                ),
+            )
+        })?,
+    };
+    Ok(converter.convert_bytes(data, name.to_string(), format)?)
+}
+
+/// Generate the OpenAPI 3 document of the endpoints of [`router`]
+///
+/// The schemas of requests and responses are generated from the types of
+/// this module, like the JSON Schemas of [`schema`](crate::datamodel::schema).
+pub fn openapi() -> Value {
+    let mut generator = SchemaSettings::openapi3().into_generator();
+    let schema =
+        |schema: schemars::schema::Schema| serde_json::to_value(schema).unwrap_or(Value::Null);
+    let convert_request = schema(generator.subschema_for::<ConvertRequest>());
+    let convert_response = schema(generator.subschema_for::<ConvertResponse>());
+    let chunk_request = schema(generator.subschema_for::<ChunkRequest>());
+    let chunk_response = schema(generator.subschema_for::<ChunkResponse>());
+    let health_response = schema(generator.subschema_for::<HealthResponse>());
+    let error_response = schema(generator.subschema_for::<ErrorResponse>());
+    let schemas = serde_json::to_value(generator.take_definitions()).unwrap_or(Value::Null);
+
+    let json_body =
+        |schema: &Value| json!({ "content": { "application/json": { "schema": schema } } });
+    let error = |description: &str| {
+        let mut response = json_body(&error_response);
+        response["description"] = json!(description);
+        response
+    };
+    let ok = |description: &str, schema: &Value| {
+        let mut response = json_body(schema);
+        response["description"] = json!(description);
+        response
+    };
+    let document_operation = |summary: &str, request: &Value, response: Value| {
+        let mut body = json_body(request);
+        body["required"] = json!(true);
+        json!({
+            "summary": summary,
+            "requestBody": body,
+            "responses": {
+                "200": response,
+                "400": error("Malformed request"),
+                "413": error("Document over the input size limit"),
+                "415": error("Unsupported input format"),
+                "422": error("Document that fails to convert"),
+                "500": error("Failure of the server"),
+            },
+        })
+    };
+
+    json!({
+        "openapi": "3.0.3",
+        "info": {
+            "title": "docling-rs",
+            "description": "Document conversion and chunking",
+            "version": env!("CARGO_PKG_VERSION"),
+        },
+        "paths": {
+            "/convert": {
+                "post": document_operation(
+                    "Convert a document",
+                    &convert_request,
+                    ok("The converted document", &convert_response),
+                ),
+            },
+            "/chunk": {
+                "post": document_operation(
+                    "Convert a document and chunk it",
+                    &chunk_request,
+                    ok("The chunks of the document", &chunk_response),
+                ),
+            },
+            "/health": {
+                "get": {
+                    "summary": "Check that the server is up",
+                    "responses": { "200": ok("The server is up", &health_response) },
+                },
+            },
+            "/openapi.json": {
+                "get": {
+                    "summary": "This OpenAPI document",
+                    "responses": { "200": { "description": "The OpenAPI document" } },
+                },
+            },
+        },
+        "components": { "schemas": schemas },
+    })
+}
@@ -0,0 +1,140 @@
+//! WebAssembly bindings (`wasm` feature)
+//!
+//! [`convert_bytes`] and [`chunk`] expose conversion and chunking to
+//! JavaScript, so documents can be processed client-side in the browser.
+//! PDF needs pdfium, which is not available there, so browser builds leave
+//! out the default `pdf` feature:
+//!
+//! ```text
+//! cargo rustc --lib --release --target wasm32-unknown-unknown \
+//!     --no-default-features --features wasm --crate-type cdylib
+//! wasm-bindgen --target web --out-dir pkg \
+//!     target/wasm32-unknown-unknown/release/docling_rs.wasm
+//! ```
+//!
+//! Both functions take the document as a `Uint8Array` with its file name,
+//! and answer plain objects shaped like the `/convert` and `/chunk`
+//! responses of `docling-rs serve`:
+//!
+//! ```js
+//! import init, { convert_bytes, chunk } from "./pkg/docling_rs.js";
+//!
+//! await init();
+//! const data = new Uint8Array(await file.arrayBuffer());
+//! const { document, warnings } = convert_bytes(data, file.name);
+//! const { chunks } = chunk(data, file.name, { chunker: "hybrid", max_tokens: 256 });
+//! ```
+//!
+//! The format comes from the `from` option (a name such as `markdown` or an
+//! extension such as `md`), else from the extension of the file name, else
+//! from the content. Failures are thrown as `Error`s.
+
+use crate::chunking::{ChunkerOptions, ChunkerRegistry};
+use crate::datamodel::ConversionResult;
+use crate::{DocumentConverter, InputFormat};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use wasm_bindgen::prelude::*;
+
+/// Options of [`convert_bytes`]
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct ConvertOptions {
+    /// Input format, for names without a known extension
+    from: Option<String>,
+}
+
+/// Options of [`chunk`]
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct ChunkOptions {
+    /// Input format, for names without a known extension
+    from: Option<String>,
+
+    /// Chunking strategy (default: `hierarchical`)
+    chunker: Option<String>,
+
+    /// Token limit per chunk (`hybrid`, `window`)
+    max_tokens: Option<usize>,
+
+    /// Character limit per chunk (`recursive`, `fixed`)
+    chunk_size: Option<usize>,
+}
+
+/// Convert the document `data`, named `name`
+///
+/// Answers `{name, warnings, metrics, document}`. `options` may set `from`.
+#[wasm_bindgen]
+pub fn convert_bytes(data: &[u8], name: &str, options: JsValue) -> Result<JsValue, JsError> {
+    let options: ConvertOptions = parse_options(options)?;
+    let result = convert(data, name, options.from.as_deref())?;
+    to_js(&json!({
+        "name": name,
+        "warnings": result.warnings(),
+        "metrics": result.metrics(),
+        "document": result.document(),
+    }))
+}
+
+/// Convert the document `data`, named `name`, and chunk it
+///
+/// Answers `{name, chunker, warnings, chunks}`. `options` may set `from`,
+/// `chunker`, `max_tokens` and `chunk_size`.
+#[wasm_bindgen]
+pub fn chunk(data: &[u8], name: &str, options: JsValue) -> Result<JsValue, JsError> {
+    let options: ChunkOptions = parse_options(options)?;
+    let kind = options.chunker.as_deref().unwrap_or("hierarchical");
+    let chunker = ChunkerRegistry::new()
+        .create(
+            kind,
+            ChunkerOptions {
+                max_tokens: options.max_tokens,
+                chunk_size: options.chunk_size,
+                tokenizer: None,
+                embedder: None,
+            },
+        )
+        .map_err(|e| JsError::new(&e.to_string()))?;
+
+    let result = convert(data, name, options.from.as_deref())?;
+    let chunks: Vec<_> = chunker.chunk(result.document()).collect();
+    to_js(&json!({
+        "name": name,
+        "chunker": kind,
+        "warnings": result.warnings(),
+        "chunks": chunks,
+    }))
+}
+
+/// Convert `data` in the format given by `from`, `name` or its content
+fn convert(data: &[u8], name: &str, from: Option<&str>) -> Result<ConversionResult, JsError> {
+    let format = match from {
+        Some(from) => InputFormat::from_name(from)
+            .ok_or_else(|| JsError::new(&format!("Unknown input format: {}", from)))?,
+        None => InputFormat::detect(name, data).ok_or_else(|| {
+            JsError::new(&format!(
+                "Unsupported file format: cannot tell the format of {}",
+                name
+            ))
+        })?,
+    };
+    DocumentConverter::new()
+        .convert_bytes(data.to_vec(), name.to_string(), format)
+        .map_err(|e| JsError::new(&e.to_string()))
+}
+
+/// `options` as `T`, with every option unset when it is `undefined` or `null`
+fn parse_options<T: Default + for<'de> Deserialize<'de>>(options: JsValue) -> Result<T, JsError> {
+    if options.is_undefined() || options.is_null() {
+        return Ok(T::default());
+    }
+    serde_wasm_bindgen::from_value(options)
+        .map_err(|e| JsError::new(&format!("Invalid options: {}", e)))
+}
+
+/// `value` as a plain JavaScript object (maps as objects, not `Map`s)
+fn to_js(value: &impl Serialize) -> Result<JsValue, JsError> {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .map_err(|e| JsError::new(&e.to_string()))
+}
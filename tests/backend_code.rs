@@ -0,0 +1,152 @@
+//! Source code backend tests
+
+use docling_rs::backend::{Backend, CodeBackend, CodeLanguage};
+use docling_rs::datamodel::{InputDocument, NodeType};
+use docling_rs::InputFormat;
+
+fn convert(name: &str, content: &str) -> docling_rs::DoclingDocument {
+    let backend = CodeBackend::new();
+    let input = InputDocument::from_bytes(content.as_bytes().to_vec(), name, InputFormat::Code);
+    backend.convert(&input).expect("Failed to convert")
+}
+
+fn headings(doc: &docling_rs::DoclingDocument) -> Vec<&str> {
+    doc.nodes()
+        .iter()
+        .filter(|n| n.node_type() == NodeType::Heading)
+        .filter_map(|n| n.text_content())
+        .collect()
+}
+
+#[test]
+fn test_code_backend_supports_format() {
+    let backend = CodeBackend::new();
+
+    assert!(backend.supports_format(InputFormat::Code));
+    assert!(!backend.supports_format(InputFormat::Text));
+    assert_eq!(InputFormat::from_extension("rs"), Some(InputFormat::Code));
+    assert_eq!(InputFormat::from_extension("py"), Some(InputFormat::Code));
+    assert_eq!(InputFormat::Code.fixed_extension(), None);
+    assert_eq!(InputFormat::Code.extension(), "txt");
+    assert_eq!(
+        CodeLanguage::from_extension("tsx"),
+        Some(CodeLanguage::TypeScript)
+    );
+}
+
+#[test]
+fn test_code_backend_rust_sections() {
+    let source = r#"use std::fmt;
+
+/// A point
+#[derive(Debug)]
+pub struct Point {
+    x: i32,
+}
+
+impl Point {
+    pub fn new(x: i32) -> Self {
+        Self { x }
+    }
+}
+
+fn main() {
+    let s = "{ not a brace";
+    println!("{}", s);
+}
+"#;
+    let doc = convert("lib.rs", source);
+
+    assert_eq!(
+        headings(&doc),
+        vec!["pub struct Point", "impl Point", "fn main()"]
+    );
+
+    // Doc comments and attributes belong to the definition's code block
+    let struct_block = doc.nodes()[2].text_content().unwrap();
+    assert!(struct_block.starts_with("/// A point\n#[derive(Debug)]"));
+    assert!(struct_block.ends_with('}'));
+
+    // Module-level code is preserved as its own block
+    assert_eq!(doc.nodes()[0].node_type(), NodeType::CodeBlock);
+    assert_eq!(doc.nodes()[0].text_content(), Some("use std::fmt;"));
+
+    assert_eq!(doc.metadata().programming_language(), Some("rust"));
+    assert_eq!(doc.metadata().language(), None);
+    assert_eq!(doc.metadata().format(), Some(InputFormat::Code));
+}
+
+#[test]
+fn test_code_backend_python_sections() {
+    let source = r#"import os
+
+@dataclass
+class Config:
+    name: str
+
+    def load(self):
+        return os.environ
+
+
+def main():
+    print("hi")
+
+if __name__ == "__main__":
+    main()
+"#;
+    let doc = convert("tool.py", source);
+
+    assert_eq!(headings(&doc), vec!["class Config", "def main()"]);
+
+    let last = doc.nodes().last().unwrap();
+    assert_eq!(last.node_type(), NodeType::CodeBlock);
+    assert!(last.text_content().unwrap().starts_with("if __name__"));
+}
+
+#[test]
+fn test_code_backend_quoted_braces() {
+    let source = r#"function open() {
+    return '{';
+}
+
+function second() {
+    return 'it\'s }';
+}
+"#;
+    let doc = convert("app.js", source);
+    assert_eq!(headings(&doc), vec!["function open()", "function second()"]);
+
+    // Rust char literals hide braces, lifetimes are not quotes
+    let source = r#"fn open<'a>(s: &'a str) -> char {
+    '{'
+}
+
+fn second() {
+}
+"#;
+    let doc = convert("lib.rs", source);
+    assert_eq!(
+        headings(&doc),
+        vec!["fn open<'a>(s: &'a str) -> char", "fn second()"]
+    );
+}
+
+#[test]
+fn test_code_backend_positions() {
+    let doc = convert("main.go", "package main\n\nfunc main() {\n}\n");
+
+    let heading = &doc.nodes()[1];
+    assert_eq!(heading.text_content(), Some("func main()"));
+    assert_eq!(heading.position().unwrap().start_line(), 3);
+
+    let body = &doc.nodes()[2];
+    assert_eq!(body.position().unwrap().end_line(), 4);
+}
+
+#[test]
+fn test_code_backend_unknown_language() {
+    let backend = CodeBackend::new();
+    let input = InputDocument::from_bytes(b"x".to_vec(), "file.unknown", InputFormat::Code);
+
+    assert!(backend.convert(&input).is_err());
+}
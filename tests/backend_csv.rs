@@ -56,3 +56,35 @@ fn test_csv_backend_convert_single_column() {
     let result = backend.convert(&input);
     assert!(result.is_ok());
 }
+
+#[test]
+fn test_csv_backend_reads_table() {
+    use docling_rs::datamodel::NodeType;
+
+    let csv_data = b"Name,Age\nAlice,30\n\"Smith, Bob\",25,extra\n".to_vec();
+    let input = InputDocument::from_bytes(csv_data, "people.csv", InputFormat::Csv);
+
+    let doc = CsvBackend::new().convert(&input).unwrap();
+
+    assert_eq!(doc.nodes().len(), 1);
+    let node = &doc.nodes()[0];
+    assert_eq!(node.node_type(), NodeType::Table);
+    let table = node.table().unwrap();
+    assert_eq!(table.num_header_rows(), 1);
+    assert_eq!(
+        table.to_vec(),
+        vec![
+            vec!["Name", "Age", ""],
+            vec!["Alice", "30", ""],
+            vec!["Smith, Bob", "25", "extra"],
+        ]
+    );
+}
+
+#[test]
+fn test_csv_backend_empty_has_no_table() {
+    let input = InputDocument::from_bytes(Vec::new(), "empty.csv", InputFormat::Csv);
+
+    let doc = CsvBackend::new().convert(&input).unwrap();
+    assert!(doc.nodes().is_empty());
+}
@@ -38,3 +38,135 @@ fn test_docx_backend_name_extraction() {
     let _result = backend.convert(&input);
     // Test passes if backend is properly structured
 }
+
+#[test]
+fn test_docx_backend_reads_headings() {
+    use docling_rs::datamodel::NodeType;
+
+    let input = InputDocument::from_path(
+        "tests/documents-test/Conferencistas_Futurologia_Educativa.docx".into(),
+        InputFormat::Docx,
+    );
+
+    let doc = DocxBackend::new().convert(&input).unwrap();
+
+    let first = &doc.nodes()[0];
+    assert_eq!(first.node_type(), NodeType::Heading);
+    assert_eq!(first.level(), Some(1));
+    assert_eq!(
+        first.text_content(),
+        Some("Conferencistas sobre Futurología Tecnológica para Audiencias Educativas")
+    );
+
+    // Speakers are level 2 sections nested in the first one
+    let (root, _) = doc.roots().next().unwrap();
+    let speakers: Vec<_> = doc
+        .children(root)
+        .filter(|(_, node)| node.node_type() == NodeType::Heading)
+        .map(|(_, node)| node.text_content().unwrap())
+        .collect();
+    assert_eq!(speakers.len(), 6);
+    assert_eq!(speakers[0], "Amy Webb");
+
+    let (amy, _) = doc.children(root).next().unwrap();
+    let details: Vec<_> = doc.children(amy).map(|(_, n)| n.node_type()).collect();
+    assert_eq!(details, vec![NodeType::Paragraph, NodeType::Paragraph]);
+}
+
+#[test]
+fn test_docx_backend_reads_title_lists_and_tables() {
+    use docling_rs::datamodel::NodeType;
+    use docx_rs::{Docx, IndentLevel, NumberingId, Paragraph, Run, Table, TableCell, TableRow};
+
+    let text = |t: &str| Paragraph::new().add_run(Run::new().add_text(t));
+    let cell = |t: &str| TableCell::new().add_paragraph(text(t));
+    let mut bytes = std::io::Cursor::new(Vec::new());
+    Docx::new()
+        .add_paragraph(text("Annual Report").style("Title"))
+        .add_paragraph(text("Point one").numbering(NumberingId::new(1), IndentLevel::new(0)))
+        .add_paragraph(text("Detail").numbering(NumberingId::new(1), IndentLevel::new(1)))
+        .add_paragraph(text("Table 1: Sales").style("Caption"))
+        .add_table(Table::new(vec![
+            TableRow::new(vec![cell("Region"), cell("Total")]),
+            TableRow::new(vec![cell("North"), cell("12")]),
+        ]))
+        .build()
+        .pack(&mut bytes)
+        .unwrap();
+    let input = InputDocument::from_bytes(bytes.into_inner(), "report.docx", InputFormat::Docx);
+
+    let doc = DocxBackend::new().convert(&input).unwrap();
+    let nodes: Vec<_> = doc
+        .nodes()
+        .iter()
+        .map(|n| (n.node_type(), n.text_content().unwrap_or_default()))
+        .collect();
+    assert_eq!(
+        nodes[..6],
+        [
+            (NodeType::Title, "Annual Report"),
+            (NodeType::List, ""),
+            (NodeType::ListItem, "Point one"),
+            (NodeType::List, ""),
+            (NodeType::ListItem, "Detail"),
+            (NodeType::Caption, "Table 1: Sales"),
+        ]
+    );
+    let table = doc.nodes()[6].table().unwrap();
+    assert_eq!(
+        table.to_vec(),
+        vec![vec!["Region", "Total"], vec!["North", "12"]]
+    );
+}
+
+#[test]
+fn test_docx_backend_reads_core_properties() {
+    use std::io::{Cursor, Write};
+
+    let mut packed = Cursor::new(Vec::new());
+    docx_rs::Docx::new().build().pack(&mut packed).unwrap();
+
+    // Replace the core properties docx-rs writes with filled-in ones
+    let mut archive = zip::ZipArchive::new(packed).unwrap();
+    let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    for i in 0..archive.len() {
+        let file = archive.by_index_raw(i).unwrap();
+        if file.name() != "docProps/core.xml" {
+            writer.raw_copy_file(file).unwrap();
+        }
+    }
+    writer
+        .start_file(
+            "docProps/core.xml",
+            zip::write::SimpleFileOptions::default(),
+        )
+        .unwrap();
+    writer
+        .write_all(
+            br#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<cp:coreProperties xmlns:cp="http://schemas.openxmlformats.org/package/2006/metadata/core-properties" xmlns:dc="http://purl.org/dc/elements/1.1/" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance">
+<dc:title>Sales &amp; Costs</dc:title>
+<dc:creator>Ada Lovelace; Charles Babbage</dc:creator>
+<dc:language>pt-BR</dc:language>
+<dcterms:created xsi:type="dcterms:W3CDTF">2024-03-01T09:30:00Z</dcterms:created>
+<dcterms:modified xsi:type="dcterms:W3CDTF">2024-03-02T10:00:00+02:00</dcterms:modified>
+</cp:coreProperties>"#,
+        )
+        .unwrap();
+    let bytes = writer.finish().unwrap().into_inner();
+    let input = InputDocument::from_bytes(bytes, "report.docx", InputFormat::Docx);
+
+    let doc = DocxBackend::new().convert(&input).unwrap();
+    let metadata = doc.metadata();
+    assert_eq!(metadata.title(), Some("Sales & Costs"));
+    assert_eq!(metadata.authors(), ["Ada Lovelace", "Charles Babbage"]);
+    assert_eq!(metadata.language(), Some("por"));
+    assert_eq!(
+        metadata.created().unwrap().to_rfc3339(),
+        "2024-03-01T09:30:00+00:00"
+    );
+    assert_eq!(
+        metadata.modified().unwrap().to_rfc3339(),
+        "2024-03-02T08:00:00+00:00"
+    );
+}
@@ -1,6 +1,6 @@
 //! HTML backend tests
 
-use docling_rs::backend::{Backend, HtmlBackend};
+use docling_rs::backend::{Backend, HtmlBackend, HtmlConfig};
 use docling_rs::datamodel::InputDocument;
 use docling_rs::InputFormat;
 
@@ -56,3 +56,108 @@ fn test_html_backend_convert_table() {
     let result = backend.convert(&input);
     assert!(result.is_ok());
 }
+
+#[test]
+fn test_html_backend_document_metadata() {
+    let backend = HtmlBackend::new();
+    let html = br#"<html lang="en-GB"><head><title> Quarterly Report </title>
+        <meta name="author" content="Ada Lovelace"></head><body></body></html>"#
+        .to_vec();
+    let input = InputDocument::from_bytes(html, "test.html", InputFormat::Html);
+
+    let doc = backend.convert(&input).unwrap();
+    let metadata = doc.metadata();
+
+    assert_eq!(metadata.title(), Some("Quarterly Report"));
+    assert_eq!(metadata.language(), Some("eng"));
+    assert_eq!(metadata.authors(), ["Ada Lovelace"]);
+    assert_eq!(metadata.format(), Some(InputFormat::Html));
+    assert_eq!(metadata.source_uri(), None);
+}
+
+#[test]
+fn test_html_backend_without_metadata() {
+    let backend = HtmlBackend::with_config(HtmlConfig::default().extract_metadata(false));
+    let html =
+        br#"<html lang="en-GB"><head><title>Quarterly Report</title></head></html>"#.to_vec();
+    let input = InputDocument::from_bytes(html, "test.html", InputFormat::Html);
+
+    let doc = backend.convert(&input).unwrap();
+    assert_eq!(doc.metadata().title(), None);
+    assert_eq!(doc.metadata().language(), None);
+    assert_eq!(doc.metadata().format(), Some(InputFormat::Html));
+}
+
+#[test]
+fn test_html_backend_reads_body() {
+    use docling_rs::datamodel::NodeType;
+
+    let html = br#"<html><head><title>Report</title><style>p { color: red }</style></head>
+        <body>
+          <h1>Results</h1>
+          <p>Scores   went <b>up</b>.</p>
+          <ul><li>First<ul><li>Nested</li></ul></li><li>Second</li></ul>
+          <table>
+            <caption>Table 1: Scores</caption>
+            <thead><tr><th>Model</th><th>Score</th></tr></thead>
+            <tbody><tr><td colspan="2">n/a</td></tr></tbody>
+          </table>
+          <figure><img src="chart.png" alt="A chart"><figcaption>Figure 1: Trend</figcaption></figure>
+          <pre><code class="hljs language-rust">fn main() {}
+</code></pre>
+          <div>Loose text<script>ignored()</script></div>
+        </body></html>"#
+        .to_vec();
+    let input = InputDocument::from_bytes(html, "test.html", InputFormat::Html);
+
+    let doc = HtmlBackend::new().convert(&input).unwrap();
+    let nodes: Vec<_> = doc
+        .nodes()
+        .iter()
+        .map(|n| (n.node_type(), n.text_content().unwrap_or_default()))
+        .collect();
+    assert_eq!(
+        nodes,
+        vec![
+            (NodeType::Title, "Report"),
+            (NodeType::Heading, "Results"),
+            (NodeType::Paragraph, "Scores went up."),
+            (NodeType::List, ""),
+            (NodeType::ListItem, "First"),
+            (NodeType::List, ""),
+            (NodeType::ListItem, "Nested"),
+            (NodeType::ListItem, "Second"),
+            (NodeType::Table, nodes[8].1),
+            (NodeType::Picture, "A chart"),
+            (NodeType::CodeBlock, "fn main() {}"),
+            (NodeType::Paragraph, "Loose text"),
+        ]
+    );
+
+    // Content is nested under its heading
+    let (heading, _) = doc.roots().nth(1).unwrap();
+    assert_eq!(doc.children(heading).count(), 6);
+
+    let table = &doc.nodes()[8];
+    assert_eq!(table.caption(), Some("Table 1: Scores"));
+    let data = table.table().unwrap();
+    assert_eq!(data.num_header_rows(), 1);
+    assert_eq!(data.rows()[1].cells()[0].col_span(), 2);
+
+    let picture = &doc.nodes()[9];
+    assert_eq!(picture.caption(), Some("Figure 1: Trend"));
+    assert!(picture.picture().is_some());
+
+    assert_eq!(doc.nodes()[10].code_language(), Some("rust"));
+}
+
+#[test]
+fn test_html_backend_clamps_spans() {
+    let html = br#"<table><tr><td colspan="99999999" rowspan="0">x</td></tr></table>"#.to_vec();
+    let input = InputDocument::from_bytes(html, "test.html", InputFormat::Html);
+
+    let doc = HtmlBackend::new().convert(&input).unwrap();
+    let cell = &doc.nodes()[0].table().unwrap().rows()[0].cells()[0];
+    assert_eq!(cell.col_span(), 1000);
+    assert_eq!(cell.row_span(), 1);
+}
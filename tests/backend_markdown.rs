@@ -69,3 +69,309 @@ fn test_markdown_backend_convert_code_block() {
     let result = backend.convert(&input);
     assert!(result.is_ok());
 }
+
+#[test]
+fn test_markdown_backend_nests_sections() {
+    let backend = MarkdownBackend::new();
+    let input = InputDocument::from_bytes(
+        b"# Chapter\nIntro.\n## Section\nBody.\n# Appendix\n".to_vec(),
+        "test.md",
+        InputFormat::Markdown,
+    );
+
+    let doc = backend.convert(&input).unwrap();
+
+    // Flat access keeps reading order
+    let texts: Vec<_> = doc
+        .nodes()
+        .iter()
+        .filter_map(|n| n.text_content())
+        .collect();
+    assert_eq!(
+        texts,
+        vec!["Chapter", "Intro.", "Section", "Body.", "Appendix"]
+    );
+
+    let tree: Vec<_> = doc
+        .depth_first()
+        .map(|e| (e.depth, e.node.text_content().unwrap()))
+        .collect();
+    assert_eq!(
+        tree,
+        vec![
+            (0, "Chapter"),
+            (1, "Intro."),
+            (1, "Section"),
+            (2, "Body."),
+            (0, "Appendix"),
+        ]
+    );
+}
+
+#[test]
+fn test_markdown_backend_source_positions() {
+    let content = "# Title\n\n  Some text.\n";
+    let input = InputDocument::from_bytes(
+        content.as_bytes().to_vec(),
+        "test.md",
+        InputFormat::Markdown,
+    );
+
+    let doc = MarkdownBackend::new().convert(&input).unwrap();
+
+    for node in doc.nodes() {
+        let pos = node.position().expect("markdown nodes carry positions");
+        assert_eq!(
+            &content[pos.start_offset()..pos.end_offset()],
+            node.text_content().unwrap()
+        );
+    }
+    assert_eq!(doc.nodes()[1].position().unwrap().start_line(), 3);
+}
+
+#[test]
+fn test_markdown_backend_image_title_caption() {
+    let content = "![A chart](chart.png \"Figure 1: Sales\")\n\n![Plain](plain.png)\n";
+    let input = InputDocument::from_bytes(
+        content.as_bytes().to_vec(),
+        "test.md",
+        InputFormat::Markdown,
+    );
+
+    let doc = MarkdownBackend::new().convert(&input).unwrap();
+
+    assert_eq!(doc.nodes()[0].text_content(), Some("A chart"));
+    assert_eq!(doc.nodes()[0].caption(), Some("Figure 1: Sales"));
+    assert_eq!(doc.nodes()[1].caption(), None);
+}
+
+#[test]
+fn test_markdown_backend_structured_node_types() {
+    use docling_rs::datamodel::{ImageRef, NodeType};
+
+    let content = "Intro.\n\n- one\n- two\n\n```rust\nfn main() {\n    run();\n}\n```\n\n\
+                   $$\nE = mc^2\n$$\n\n![A diagram](diagram.png)\n\n[^1]: A footnote.\n";
+    let input = InputDocument::from_bytes(
+        content.as_bytes().to_vec(),
+        "test.md",
+        InputFormat::Markdown,
+    );
+
+    let doc = MarkdownBackend::new().convert(&input).unwrap();
+    let nodes: Vec<_> = doc
+        .nodes()
+        .iter()
+        .map(|n| (n.node_type(), n.text_content().unwrap()))
+        .collect();
+
+    assert_eq!(
+        nodes,
+        vec![
+            (NodeType::Paragraph, "Intro."),
+            (NodeType::List, ""),
+            (NodeType::ListItem, "one"),
+            (NodeType::ListItem, "two"),
+            (NodeType::CodeBlock, "fn main() {\n    run();\n}"),
+            (NodeType::Formula, "E = mc^2"),
+            (NodeType::Picture, "A diagram"),
+            (NodeType::Footnote, "A footnote."),
+        ]
+    );
+
+    // List items are children of their list
+    let (list, _) = doc.roots().nth(1).unwrap();
+    assert_eq!(doc.children(list).count(), 2);
+
+    // Images reference their source file
+    let picture = doc.nodes()[6].picture().unwrap();
+    assert_eq!(
+        picture.image(),
+        &ImageRef::File {
+            uri: "diagram.png".to_string()
+        }
+    );
+
+    // Code block positions cover the fenced content
+    let code = &doc.nodes()[4];
+    let pos = code.position().unwrap();
+    assert_eq!(
+        &content[pos.start_offset()..pos.end_offset()],
+        code.text_content().unwrap()
+    );
+}
+
+#[test]
+fn test_markdown_backend_nested_lists_and_quotes() {
+    use docling_rs::datamodel::NodeType;
+
+    let content =
+        "1. first\n   - inner\n   - other\n2. second\n\n> Quoted text.\n>\n> - quoted item\n";
+    let input = InputDocument::from_bytes(
+        content.as_bytes().to_vec(),
+        "test.md",
+        InputFormat::Markdown,
+    );
+
+    let doc = MarkdownBackend::new().convert(&input).unwrap();
+    let tree: Vec<_> = doc
+        .depth_first()
+        .map(|e| (e.depth, e.node.node_type(), e.node.text_content().unwrap()))
+        .collect();
+    assert_eq!(
+        tree,
+        vec![
+            (0, NodeType::List, ""),
+            (1, NodeType::ListItem, "first"),
+            (2, NodeType::List, ""),
+            (3, NodeType::ListItem, "inner"),
+            (3, NodeType::ListItem, "other"),
+            (1, NodeType::ListItem, "second"),
+            (0, NodeType::Quote, ""),
+            (1, NodeType::Paragraph, "Quoted text."),
+            (1, NodeType::List, ""),
+            (2, NodeType::ListItem, "quoted item"),
+        ]
+    );
+
+    // Only the outer list is numbered
+    let lists: Vec<_> = doc
+        .nodes()
+        .iter()
+        .filter(|n| n.node_type() == NodeType::List)
+        .map(|n| n.is_ordered())
+        .collect();
+    assert_eq!(lists, vec![true, false, false]);
+
+    // Item text positions point at the text, not the marker
+    let inner = &doc.nodes()[3];
+    let pos = inner.position().unwrap();
+    assert_eq!(&content[pos.start_offset()..pos.end_offset()], "inner");
+    assert_eq!(pos.start_line(), 2);
+}
+
+#[test]
+fn test_markdown_backend_inline_spans() {
+    use docling_rs::datamodel::{Formatting, TextSpan};
+
+    let content = "A **bold** and *italic* ~~old~~ [link](https://example.com) `x`.\n";
+    let input = InputDocument::from_bytes(
+        content.as_bytes().to_vec(),
+        "test.md",
+        InputFormat::Markdown,
+    );
+
+    let doc = MarkdownBackend::new().convert(&input).unwrap();
+    let node = &doc.nodes()[0];
+    assert_eq!(node.text_content(), Some("A bold and italic old link x."));
+    assert_eq!(
+        node.spans(),
+        &[
+            TextSpan::new(2, 6, Formatting::bold()),
+            TextSpan::new(11, 17, Formatting::italic()),
+            TextSpan::new(18, 21, Formatting::strikethrough()),
+            TextSpan::new(22, 26, Formatting::link("https://example.com")),
+            TextSpan::new(27, 28, Formatting::code()),
+        ]
+    );
+
+    // Stripped text still maps back to the source
+    let (start, end) = node.source_range(22, 26).unwrap();
+    assert_eq!(&content[start..end], "link");
+}
+
+#[test]
+fn test_markdown_output_reemits_spans() {
+    use docling_rs::cli::output;
+    use docling_rs::datamodel::{DocumentNode, Formatting, NodeType, TextSpan};
+    use docling_rs::DoclingDocument;
+
+    let mut doc = DoclingDocument::new("test.md");
+    doc.add_node(
+        DocumentNode::new(NodeType::Paragraph, "See the guide.").with_span(TextSpan::new(
+            8,
+            13,
+            Formatting::link("guide.md"),
+        )),
+    );
+
+    assert!(output::to_markdown(&doc).contains("See the [guide](guide.md)."));
+}
+
+#[test]
+fn test_markdown_round_trip() {
+    use docling_rs::cli::output;
+    use docling_rs::DoclingDocument;
+
+    let content = "Read the [guide](guide.md) *first*.\n\n\
+                   1. **Install** it\n   - from `crates.io`\n   - or from source\n\
+                   2. Run it\n\n> Quoted ~~text~~.\n";
+    let convert = |markdown: &str| {
+        let input = InputDocument::from_bytes(
+            markdown.as_bytes().to_vec(),
+            "test.md",
+            InputFormat::Markdown,
+        );
+        MarkdownBackend::new().convert(&input).unwrap()
+    };
+    let structure = |doc: &DoclingDocument| {
+        doc.depth_first()
+            .map(|e| {
+                (
+                    e.depth,
+                    e.node.node_type(),
+                    e.node.is_ordered(),
+                    e.node.text_content().unwrap().to_string(),
+                    e.node.spans().to_vec(),
+                )
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let doc = convert(content);
+    let markdown = output::to_markdown(&doc);
+    assert_eq!(
+        markdown,
+        "# test.md\n\n\
+         Read the [guide](guide.md) *first*.\n\n\
+         1. **Install** it\n   - from `crates.io`\n   - or from source\n\
+         2. Run it\n\n> Quoted ~~text~~.\n\n"
+    );
+
+    // Reading the output back gives the same tree, below the added title
+    let again = convert(markdown.trim_start_matches("# test.md\n\n"));
+    assert_eq!(structure(&again), structure(&doc));
+}
+
+#[test]
+fn test_markdown_backend_pipe_table() {
+    use docling_rs::datamodel::NodeType;
+
+    let content = "# Results\n\nScores:\n\n| Model | Score |\n| --- | :-: |\n| a | 1 | extra |\n| b \\| c |\n\nDone.\n";
+    let input = InputDocument::from_bytes(
+        content.as_bytes().to_vec(),
+        "test.md",
+        InputFormat::Markdown,
+    );
+
+    let doc = MarkdownBackend::new().convert(&input).unwrap();
+    let table = doc
+        .nodes()
+        .iter()
+        .find(|n| n.node_type() == NodeType::Table)
+        .unwrap();
+    let data = table.table().unwrap();
+    assert_eq!(data.num_header_rows(), 1);
+    assert_eq!(
+        data.to_vec(),
+        vec![vec!["Model", "Score"], vec!["a", "1"], vec!["b | c", ""]]
+    );
+
+    let position = table.position().unwrap();
+    assert_eq!(position.start_line(), 5);
+    assert_eq!(position.end_line(), 8);
+    assert!(content[position.start_offset()..position.end_offset()].ends_with("| b \\| c |"));
+
+    // The paragraph after the table is read as usual
+    let last = doc.nodes().last().unwrap();
+    assert_eq!(last.text_content(), Some("Done."));
+}
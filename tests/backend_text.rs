@@ -0,0 +1,85 @@
+//! Plain text backend tests
+
+use docling_rs::backend::{Backend, TextBackend};
+use docling_rs::datamodel::{InputDocument, NodeType};
+use docling_rs::InputFormat;
+
+fn convert(backend: &TextBackend, content: &str) -> docling_rs::DoclingDocument {
+    let input =
+        InputDocument::from_bytes(content.as_bytes().to_vec(), "notes.txt", InputFormat::Text);
+    backend.convert(&input).expect("Failed to convert")
+}
+
+#[test]
+fn test_text_backend_supports_format() {
+    let backend = TextBackend::new();
+
+    assert!(backend.supports_format(InputFormat::Text));
+    assert!(!backend.supports_format(InputFormat::Markdown));
+    assert_eq!(InputFormat::from_extension("txt"), Some(InputFormat::Text));
+}
+
+#[test]
+fn test_text_backend_splits_paragraphs() {
+    let backend = TextBackend::new();
+    let doc = convert(
+        &backend,
+        "First line\nstill first.\n\n\nSecond paragraph.\n",
+    );
+
+    let nodes = doc.nodes();
+    assert_eq!(nodes.len(), 2);
+    assert_eq!(nodes[0].node_type(), NodeType::Paragraph);
+    assert_eq!(nodes[0].text_content(), Some("First line\nstill first."));
+    assert_eq!(nodes[1].text_content(), Some("Second paragraph."));
+
+    let pos = nodes[1]
+        .position()
+        .expect("Paragraph should have a position");
+    assert_eq!(pos.start_line(), 5);
+    assert_eq!(pos.start_offset(), 26);
+    assert_eq!(pos.end_offset(), 43);
+}
+
+#[test]
+fn test_text_backend_infers_headings() {
+    let backend = TextBackend::new();
+    let content = "INTRODUCTION\n\nSome text.\n\nUsage\n=====\nRun the tool.\n";
+    let doc = convert(&backend, content);
+
+    let types: Vec<_> = doc.nodes().iter().map(|n| n.node_type()).collect();
+    assert_eq!(
+        types,
+        vec![
+            NodeType::Heading,
+            NodeType::Paragraph,
+            NodeType::Heading,
+            NodeType::Paragraph
+        ]
+    );
+    assert_eq!(doc.nodes()[2].text_content(), Some("Usage"));
+    assert_eq!(doc.nodes()[3].text_content(), Some("Run the tool."));
+}
+
+#[test]
+fn test_text_backend_without_heading_inference() {
+    let backend = TextBackend::with_infer_headings(false);
+    let doc = convert(&backend, "INTRODUCTION\n\nSome text.\n");
+
+    assert!(doc
+        .nodes()
+        .iter()
+        .all(|n| n.node_type() == NodeType::Paragraph));
+}
+
+#[test]
+fn test_text_backend_invalid_utf8() {
+    let backend = TextBackend::new();
+    let input =
+        InputDocument::from_bytes(b"log \xff entry".to_vec(), "app.log.txt", InputFormat::Text);
+
+    let doc = backend
+        .convert(&input)
+        .expect("Invalid UTF-8 should not fail");
+    assert_eq!(doc.nodes().len(), 1);
+}
@@ -150,6 +150,7 @@ fn test_contextualize_includes_headings() {
             start_offset: 0,
             end_offset: 16,
             index: 0,
+            ..Default::default()
         },
     };
 
@@ -191,6 +192,7 @@ fn test_contextualize_is_deterministic() {
             start_offset: 0,
             end_offset: 13,
             index: 0,
+            ..Default::default()
         },
     };
 
@@ -213,6 +215,7 @@ fn test_contextualize_without_metadata() {
             start_offset: 0,
             end_offset: 11,
             index: 0,
+            ..Default::default()
         },
     };
 
@@ -224,3 +227,44 @@ fn test_contextualize_without_metadata() {
         "Should contain chunk text"
     );
 }
+
+// Test 7: Stable chunk IDs
+#[test]
+fn test_chunk_ids_are_stable() {
+    let doc = create_test_document();
+    let chunker = HierarchicalChunker::new();
+
+    let first: Vec<_> = chunker.chunk(&doc).map(|c| c.meta.chunk_id).collect();
+    let second: Vec<_> = chunker
+        .chunk(&doc.clone())
+        .map(|c| c.meta.chunk_id)
+        .collect();
+
+    assert_eq!(first, second, "Re-chunking must reproduce the same IDs");
+    assert!(first.iter().all(|id| id.len() == 64));
+
+    // Distinct content gets distinct IDs
+    let mut unique = first.clone();
+    unique.dedup();
+    assert_eq!(unique.len(), first.len());
+}
+
+// Test 8: Chunk IDs ignore whitespace differences but not context
+#[test]
+fn test_chunk_id_normalization() {
+    use docling_rs::chunking::chunk_id;
+
+    let headings = vec!["Chapter 1".to_string()];
+    assert_eq!(
+        chunk_id("doc.md", &headings, "some  text\n here"),
+        chunk_id("doc.md", &headings, "some text here")
+    );
+    assert_ne!(
+        chunk_id("doc.md", &headings, "some text"),
+        chunk_id("doc.md", &[], "some text")
+    );
+    assert_ne!(
+        chunk_id("doc.md", &headings, "some text"),
+        chunk_id("other.md", &headings, "some text")
+    );
+}
@@ -0,0 +1,96 @@
+//! Chunk filter tests
+
+use docling_rs::chunking::{
+    BaseChunk, BaseChunker, ChunkerExt, ChunkerOptions, ChunkerRegistry, HierarchicalChunker,
+};
+use docling_rs::datamodel::{DocumentNode, NodeType};
+use docling_rs::DoclingDocument;
+
+fn document(paragraphs: &[&str]) -> DoclingDocument {
+    DoclingDocument::new("test.pdf").with_nodes(
+        paragraphs
+            .iter()
+            .map(|p| DocumentNode::new(NodeType::Paragraph, *p))
+            .collect(),
+    )
+}
+
+fn texts(chunker: &impl BaseChunker, doc: &DoclingDocument) -> Vec<String> {
+    chunker.chunk(doc).map(|c| c.text).collect()
+}
+
+#[test]
+fn test_min_length() {
+    let doc = document(&["ok", "long enough paragraph"]);
+    let chunker = HierarchicalChunker::new().filtered().min_length(5);
+
+    assert_eq!(texts(&chunker, &doc), vec!["long enough paragraph"]);
+}
+
+#[test]
+fn test_drop_patterns() {
+    let doc = document(&["Table of Contents", "Real content.", "TOC entry ....... 4"]);
+    let chunker = HierarchicalChunker::new()
+        .filtered()
+        .drop_patterns(["(?i)^table of contents", r"\.{5,}\s*\d+$"])
+        .unwrap();
+
+    assert_eq!(texts(&chunker, &doc), vec!["Real content."]);
+}
+
+#[test]
+fn test_invalid_pattern() {
+    let result = HierarchicalChunker::new().filtered().drop_patterns(["("]);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_remove_boilerplate() {
+    let doc = document(&[
+        "Body text.\nPage 3 of 10",
+        "- 12 -",
+        "Copyright 2024 Example Corp.\nAll rights reserved.",
+    ]);
+    let chunker = HierarchicalChunker::new().filtered().remove_boilerplate();
+
+    let chunks: Vec<_> = chunker.chunk(&doc).collect();
+    assert_eq!(chunks.len(), 1);
+    assert_eq!(chunks[0].text, "Body text.");
+    assert_eq!(chunks[0].meta.index, 0);
+
+    // ID reflects the cleaned text
+    let unfiltered: Vec<_> = HierarchicalChunker::new().chunk(&doc).collect();
+    assert_ne!(chunks[0].meta.chunk_id, unfiltered[0].meta.chunk_id);
+}
+
+#[test]
+fn test_custom_closure_and_order() {
+    let doc = document(&["first", "second", "third"]);
+    let chunker = HierarchicalChunker::new()
+        .filtered()
+        .with_filter(|mut chunk: BaseChunk| {
+            chunk.text = chunk.text.to_uppercase();
+            Some(chunk)
+        })
+        .with_filter(|chunk: BaseChunk| (chunk.text != "SECOND").then_some(chunk));
+
+    let chunks: Vec<_> = chunker.chunk(&doc).collect();
+    let indices: Vec<_> = chunks.iter().map(|c| c.meta.index).collect();
+    assert_eq!(
+        chunks.iter().map(|c| c.text.as_str()).collect::<Vec<_>>(),
+        vec!["FIRST", "THIRD"]
+    );
+    assert_eq!(indices, vec![0, 1]);
+}
+
+#[test]
+fn test_wraps_registry_chunkers() {
+    let doc = document(&["tiny", "a paragraph of useful length"]);
+    let chunker = ChunkerRegistry::new()
+        .create("hierarchical", ChunkerOptions::default())
+        .unwrap()
+        .filtered()
+        .min_length(10);
+
+    assert_eq!(texts(&chunker, &doc), vec!["a paragraph of useful length"]);
+}
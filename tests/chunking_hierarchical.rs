@@ -3,6 +3,7 @@
 //! Tests for hierarchical (structure-based) chunking behavior.
 
 use docling_rs::chunking::{BaseChunker, HierarchicalChunker};
+use docling_rs::datamodel::{DocumentNode, NodeType};
 use docling_rs::{DoclingDocument, DocumentConverter};
 use std::io::Write;
 
@@ -247,3 +248,152 @@ First paragraph under chapter 2."#;
         );
     }
 }
+
+// Test 10: Heading ancestry is tracked in chunk metadata
+#[test]
+fn test_heading_path_populated() {
+    let chunker = HierarchicalChunker::new();
+
+    let mut doc = DoclingDocument::new("test.md");
+    doc.add_node(DocumentNode::new(NodeType::Heading, "Chapter 1").with_level(1));
+    doc.add_node(DocumentNode::new(NodeType::Paragraph, "Intro."));
+    doc.add_node(DocumentNode::new(NodeType::Heading, "Section 1.1").with_level(2));
+    doc.add_node(DocumentNode::new(NodeType::Paragraph, "Details."));
+    doc.add_node(DocumentNode::new(NodeType::Heading, "Chapter 2").with_level(1));
+    doc.add_node(DocumentNode::new(NodeType::Paragraph, "More."));
+
+    let chunks: Vec<_> = chunker.chunk(&doc).collect();
+
+    // Heading nodes do not produce chunks of their own
+    let texts: Vec<_> = chunks.iter().map(|c| c.text.as_str()).collect();
+    assert_eq!(texts, vec!["Intro.", "Details.", "More."]);
+
+    assert_eq!(chunks[0].meta.headings, vec!["Chapter 1"]);
+    assert_eq!(chunks[1].meta.headings, vec!["Chapter 1", "Section 1.1"]);
+    assert_eq!(chunks[2].meta.headings, vec!["Chapter 2"]);
+
+    let contextualized = chunker.contextualize(&chunks[1]);
+    assert_eq!(contextualized, "Chapter 1\nSection 1.1\nDetails.");
+}
+
+// Test 11: Markdown heading levels drive the heading path
+#[test]
+fn test_markdown_heading_levels() {
+    use docling_rs::backend::{Backend, MarkdownBackend};
+    use docling_rs::{InputDocument, InputFormat};
+
+    let input = InputDocument::from_bytes(
+        b"# Chapter 1\n\n## Section 1.1\n\nContent in section 1.1.\n".to_vec(),
+        "test.md",
+        InputFormat::Markdown,
+    );
+    let doc = MarkdownBackend::new().convert(&input).unwrap();
+
+    let chunks: Vec<_> = HierarchicalChunker::new().chunk(&doc).collect();
+    assert_eq!(chunks.len(), 1);
+    assert_eq!(chunks[0].meta.headings, vec!["Chapter 1", "Section 1.1"]);
+}
+
+// Test 12: Page numbers carry through to chunk metadata
+#[test]
+fn test_page_provenance() {
+    let mut doc = DoclingDocument::new("test.pdf");
+    doc.add_node(DocumentNode::new(NodeType::Text, "First page.").with_page(1));
+    doc.add_node(DocumentNode::new(NodeType::Text, "Second page.").with_page(2));
+    doc.add_node(DocumentNode::new(NodeType::Text, "No page."));
+
+    let chunks: Vec<_> = HierarchicalChunker::new().chunk(&doc).collect();
+
+    assert_eq!(chunks[0].meta.page_start, Some(1));
+    assert_eq!(chunks[0].meta.page_end, Some(1));
+    assert_eq!(chunks[1].meta.page_start, Some(2));
+    assert_eq!(chunks[2].meta.page_start, None);
+}
+
+// Test 13: Bounding boxes are recorded per page
+#[test]
+fn test_bbox_provenance() {
+    use docling_rs::datamodel::BoundingBox;
+
+    let bbox = BoundingBox::new(72.0, 720.0, 540.0, 600.0);
+    let mut doc = DoclingDocument::new("test.pdf");
+    doc.add_node(
+        DocumentNode::new(NodeType::Text, "Located text.")
+            .with_page(3)
+            .with_bbox(bbox),
+    );
+    doc.add_node(DocumentNode::new(NodeType::Text, "No box.").with_page(3));
+
+    let chunks: Vec<_> = HierarchicalChunker::new().chunk(&doc).collect();
+
+    assert_eq!(chunks[0].meta.bboxes.len(), 1);
+    assert_eq!(chunks[0].meta.bboxes[0].page, 3);
+    assert_eq!(chunks[0].meta.bboxes[0].bbox, bbox);
+    assert!(chunks[1].meta.bboxes.is_empty());
+}
+
+// Test 14: Table and figure captions carry into chunks and their context
+#[test]
+fn test_caption_in_metadata_and_context() {
+    let mut doc = DoclingDocument::new("test.md");
+    doc.add_node(DocumentNode::new(NodeType::Heading, "Results").with_level(1));
+    doc.add_node(
+        DocumentNode::new(NodeType::Table, "Model | Score\nA | 0.9")
+            .with_caption("Table 1: Benchmark scores"),
+    );
+    doc.add_node(DocumentNode::new(NodeType::Paragraph, "Discussion."));
+
+    let chunker = HierarchicalChunker::new();
+    let chunks: Vec<_> = chunker.chunk(&doc).collect();
+
+    assert_eq!(
+        chunks[0].meta.caption.as_deref(),
+        Some("Table 1: Benchmark scores")
+    );
+    assert_eq!(
+        chunker.contextualize(&chunks[0]),
+        "Results\nTable 1: Benchmark scores\nModel | Score\nA | 0.9"
+    );
+    assert_eq!(chunks[1].meta.caption, None);
+}
+
+// Test 15: The document title heads every heading path
+#[test]
+fn test_title_in_heading_path() {
+    let mut doc = DoclingDocument::new("test.docx");
+    doc.add_node(DocumentNode::new(NodeType::Title, "Annual Report"));
+    doc.add_node(DocumentNode::new(NodeType::Heading, "Summary").with_level(1));
+    doc.add_node(DocumentNode::new(NodeType::Paragraph, "Revenue grew."));
+    doc.add_node(DocumentNode::new(NodeType::PageBreak, ""));
+
+    let chunks: Vec<_> = HierarchicalChunker::new().chunk(&doc).collect();
+    assert_eq!(chunks.len(), 1);
+    assert_eq!(chunks[0].meta.headings, vec!["Annual Report", "Summary"]);
+}
+
+// Test 16: Caption nodes next to tables and figures fold into their chunks
+#[test]
+fn test_sibling_caption_nodes() {
+    let mut doc = DoclingDocument::new("test.html");
+    doc.add_node(DocumentNode::new(NodeType::Caption, "Table 1: Scores"));
+    doc.add_node(DocumentNode::new(NodeType::Table, "Model | Score"));
+    doc.add_node(DocumentNode::new(NodeType::Picture, "A chart"));
+    doc.add_node(DocumentNode::new(NodeType::Caption, "Figure 1: Trend"));
+    doc.add_node(DocumentNode::new(NodeType::Caption, "A stray caption"));
+
+    let chunks: Vec<_> = HierarchicalChunker::new().chunk(&doc).collect();
+
+    let summary: Vec<_> = chunks
+        .iter()
+        .map(|c| (c.text.as_str(), c.meta.caption.as_deref()))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            ("Model | Score", Some("Table 1: Scores")),
+            ("A chart", Some("Figure 1: Trend")),
+            ("A stray caption", None),
+        ]
+    );
+    assert_eq!(chunks[1].meta.index, 1);
+}
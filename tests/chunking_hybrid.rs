@@ -1,7 +1,9 @@
 //! HybridChunker tests
 
 use docling_rs::chunking::tokenizer::Tokenizer;
-use docling_rs::chunking::HybridChunker;
+use docling_rs::chunking::{BaseChunker, HybridChunker};
+use docling_rs::datamodel::{DocumentNode, NodeType};
+use docling_rs::DoclingDocument;
 
 // Mock tokenizer for testing
 struct MockTokenizer {
@@ -61,12 +63,16 @@ fn test_max_tokens_validation() {
     assert!(result.is_err(), "Should fail with max_tokens = 0");
 }
 
-// Test 4: Builder requires tokenizer
+// Test 4: Builder falls back to the approximate tokenizer
 #[test]
-fn test_builder_requires_tokenizer() {
-    let result = HybridChunker::builder().max_tokens(256).build();
+fn test_builder_defaults_to_approx_tokenizer() {
+    let chunker = HybridChunker::builder()
+        .build()
+        .expect("Builder should succeed without a tokenizer");
 
-    assert!(result.is_err(), "Should fail without tokenizer");
+    let doc = paragraph_document(&["A short paragraph that fits easily."]);
+    let chunks: Vec<_> = chunker.chunk(&doc).collect();
+    assert_eq!(chunks.len(), 1);
 }
 
 // Test 5: Builder with merge_peers
@@ -141,3 +147,378 @@ fn test_builder_parameter_order() {
     assert!(chunker1.is_ok());
     assert!(chunker2.is_ok());
 }
+
+// Word-count tokenizer for deterministic overlap tests
+struct WordTokenizer;
+
+impl Tokenizer for WordTokenizer {
+    fn count_tokens(&self, text: &str) -> usize {
+        text.split_whitespace().count()
+    }
+
+    fn max_tokens(&self) -> usize {
+        512
+    }
+}
+
+fn paragraph_document(paragraphs: &[&str]) -> DoclingDocument {
+    let mut doc = DoclingDocument::new("test.md");
+    for text in paragraphs {
+        doc.add_node(DocumentNode::new(NodeType::Paragraph, *text));
+    }
+    doc
+}
+
+// Test 9: overlap must be smaller than max_tokens
+#[test]
+fn test_overlap_validation() {
+    let result = HybridChunker::builder()
+        .tokenizer(Box::new(WordTokenizer))
+        .max_tokens(10)
+        .overlap_tokens(10)
+        .build();
+
+    assert!(result.is_err(), "Should fail with overlap >= max_tokens");
+}
+
+// Test 10: consecutive chunks share the configured window
+#[test]
+fn test_overlap_prefixes_previous_tail() {
+    let doc = paragraph_document(&["one two three four five six seven eight nine ten"]);
+
+    let chunker = HybridChunker::builder()
+        .tokenizer(Box::new(WordTokenizer))
+        .max_tokens(5)
+        .overlap_tokens(2)
+        .merge_peers(false)
+        .build()
+        .unwrap();
+
+    let chunks: Vec<_> = chunker.chunk(&doc).collect();
+
+    // Content is limited to max_tokens - overlap, leaving room for the window
+    assert!(chunks.len() > 1);
+    assert_eq!(chunks[0].text, "one two three");
+    assert_eq!(chunks[1].text, "two three four five six");
+    for chunk in &chunks {
+        assert!(WordTokenizer.count_tokens(&chunk.text) <= 5);
+    }
+}
+
+// Test 11: no overlap by default
+#[test]
+fn test_no_overlap_by_default() {
+    let doc = paragraph_document(&["alpha beta gamma", "delta epsilon zeta"]);
+
+    let chunker = HybridChunker::builder()
+        .tokenizer(Box::new(WordTokenizer))
+        .max_tokens(3)
+        .build()
+        .unwrap();
+
+    let texts: Vec<_> = chunker.chunk(&doc).map(|c| c.text).collect();
+    assert_eq!(texts, vec!["alpha beta gamma", "delta epsilon zeta"]);
+}
+
+// Test 12: merged chunks span the pages of their parts
+#[test]
+fn test_merge_spans_pages() {
+    let mut doc = DoclingDocument::new("test.pdf");
+    doc.add_node(DocumentNode::new(NodeType::Text, "end of page one").with_page(1));
+    doc.add_node(DocumentNode::new(NodeType::Text, "start of page two").with_page(2));
+
+    let chunker = HybridChunker::builder()
+        .tokenizer(Box::new(WordTokenizer))
+        .max_tokens(50)
+        .build()
+        .unwrap();
+
+    let chunks: Vec<_> = chunker.chunk(&doc).collect();
+    assert_eq!(chunks.len(), 1);
+    assert_eq!(chunks[0].meta.page_start, Some(1));
+    assert_eq!(chunks[0].meta.page_end, Some(2));
+}
+
+// Test 13: code blocks that fit are kept whole and never merged with prose
+#[test]
+fn test_code_block_kept_atomic() {
+    let mut doc = DoclingDocument::new("test.rs");
+    doc.add_node(DocumentNode::new(NodeType::Paragraph, "Some intro text"));
+    doc.add_node(DocumentNode::new(
+        NodeType::CodeBlock,
+        "fn main() {\n    run();\n}",
+    ));
+
+    let chunker = HybridChunker::builder()
+        .tokenizer(Box::new(WordTokenizer))
+        .max_tokens(50)
+        .build()
+        .unwrap();
+
+    let chunks: Vec<_> = chunker.chunk(&doc).collect();
+    assert_eq!(chunks.len(), 2);
+    assert_eq!(chunks[1].text, "fn main() {\n    run();\n}");
+    assert!(chunks[1].meta.is_code);
+    assert!(!chunks[1].meta.continuation);
+}
+
+// Test 14: oversized code blocks split on line boundaries
+#[test]
+fn test_code_block_split_on_lines() {
+    let code = "let a = 1 + 2;\nlet b = a * 3;\nlet c = b - 4;\nprintln!(\"{}\", c);";
+    let mut doc = DoclingDocument::new("test.rs");
+    doc.add_node(DocumentNode::new(NodeType::CodeBlock, code));
+
+    let chunker = HybridChunker::builder()
+        .tokenizer(Box::new(WordTokenizer))
+        .max_tokens(12)
+        .build()
+        .unwrap();
+
+    let chunks: Vec<_> = chunker.chunk(&doc).collect();
+    assert_eq!(chunks.len(), 2);
+    assert_eq!(chunks[0].text, "let a = 1 + 2;\nlet b = a * 3;");
+    assert_eq!(chunks[1].text, "let c = b - 4;\nprintln!(\"{}\", c);");
+    assert!(!chunks[0].meta.continuation);
+    assert!(chunks[1].meta.continuation);
+
+    // Every line survives intact
+    let rejoined: Vec<_> = chunks.iter().map(|c| c.text.as_str()).collect();
+    assert_eq!(rejoined.join("\n"), code);
+}
+
+// Test 15: parallel chunking matches sequential output
+#[test]
+fn test_parallel_matches_sequential() {
+    let mut doc = DoclingDocument::new("book.md");
+    for chapter in 0..20 {
+        doc.add_node(
+            DocumentNode::new(NodeType::Heading, format!("Chapter {}", chapter)).with_level(1),
+        );
+        for paragraph in 0..5 {
+            doc.add_node(DocumentNode::new(
+                NodeType::Paragraph,
+                format!(
+                    "Paragraph {} of chapter {} has a handful of words in it",
+                    paragraph, chapter
+                ),
+            ));
+        }
+    }
+
+    let build = |parallel| {
+        HybridChunker::builder()
+            .tokenizer(Box::new(WordTokenizer))
+            .max_tokens(25)
+            .parallel(parallel)
+            .build()
+            .unwrap()
+    };
+
+    let sequential: Vec<_> = build(false).chunk(&doc).collect();
+    let parallel: Vec<_> = build(true).chunk(&doc).collect();
+
+    assert_eq!(sequential.len(), parallel.len());
+    for (i, (s, p)) in sequential.iter().zip(&parallel).enumerate() {
+        assert_eq!(s.text, p.text);
+        assert_eq!(s.meta.headings, p.meta.headings);
+        assert_eq!(p.meta.index, i);
+    }
+}
+
+// Test 16: indices are sequential even without peer merging
+#[test]
+fn test_indices_sequential_without_merge() {
+    let doc = paragraph_document(&["one two three four five six", "seven eight nine"]);
+
+    let chunker = HybridChunker::builder()
+        .tokenizer(Box::new(WordTokenizer))
+        .max_tokens(3)
+        .merge_peers(false)
+        .build()
+        .unwrap();
+
+    let indices: Vec<_> = chunker.chunk(&doc).map(|c| c.meta.index).collect();
+    assert_eq!(indices, vec![0, 1, 2]);
+}
+
+// Test 17: merged chunks union their bounding boxes per page
+#[test]
+fn test_merge_unions_bboxes() {
+    use docling_rs::datamodel::BoundingBox;
+
+    let mut doc = DoclingDocument::new("test.pdf");
+    doc.add_node(
+        DocumentNode::new(NodeType::Text, "top block")
+            .with_page(1)
+            .with_bbox(BoundingBox::new(50.0, 700.0, 300.0, 650.0)),
+    );
+    doc.add_node(
+        DocumentNode::new(NodeType::Text, "lower block")
+            .with_page(1)
+            .with_bbox(BoundingBox::new(40.0, 600.0, 280.0, 500.0)),
+    );
+    doc.add_node(
+        DocumentNode::new(NodeType::Text, "next page")
+            .with_page(2)
+            .with_bbox(BoundingBox::new(50.0, 700.0, 300.0, 690.0)),
+    );
+
+    let chunker = HybridChunker::builder()
+        .tokenizer(Box::new(WordTokenizer))
+        .max_tokens(50)
+        .build()
+        .unwrap();
+
+    let chunks: Vec<_> = chunker.chunk(&doc).collect();
+    assert_eq!(chunks.len(), 1);
+
+    let bboxes = &chunks[0].meta.bboxes;
+    assert_eq!(bboxes.len(), 2);
+    assert_eq!(bboxes[0].page, 1);
+    assert_eq!(bboxes[0].bbox, BoundingBox::new(40.0, 700.0, 300.0, 500.0));
+    assert_eq!(bboxes[1].page, 2);
+}
+
+fn sibling_sections_document() -> DoclingDocument {
+    let mut doc = DoclingDocument::new("test.md");
+    doc.add_node(DocumentNode::new(NodeType::Heading, "Chapter").with_level(1));
+    doc.add_node(DocumentNode::new(NodeType::Heading, "Intro").with_level(2));
+    doc.add_node(DocumentNode::new(NodeType::Paragraph, "alpha beta"));
+    doc.add_node(DocumentNode::new(NodeType::Heading, "Usage").with_level(2));
+    doc.add_node(DocumentNode::new(NodeType::Paragraph, "gamma delta"));
+    doc
+}
+
+// Test 18: sibling sections stay separate by default
+#[test]
+fn test_no_merge_across_headings_by_default() {
+    let chunker = HybridChunker::builder()
+        .tokenizer(Box::new(WordTokenizer))
+        .max_tokens(50)
+        .build()
+        .unwrap();
+
+    let chunks: Vec<_> = chunker.chunk(&sibling_sections_document()).collect();
+    assert_eq!(chunks.len(), 2);
+}
+
+// Test 19: sibling sections merge under their shared parent heading
+#[test]
+fn test_merge_across_headings() {
+    let chunker = HybridChunker::builder()
+        .tokenizer(Box::new(WordTokenizer))
+        .max_tokens(50)
+        .merge_across_headings(true)
+        .merge_separator("\n---\n")
+        .build()
+        .unwrap();
+
+    let chunks: Vec<_> = chunker.chunk(&sibling_sections_document()).collect();
+    assert_eq!(chunks.len(), 1);
+    assert_eq!(chunks[0].text, "Intro\nalpha beta\n---\nUsage\ngamma delta");
+    assert_eq!(chunks[0].meta.headings, vec!["Chapter".to_string()]);
+}
+
+// Test 20: merging across headings still respects the token limit
+#[test]
+fn test_merge_across_headings_respects_limit() {
+    let chunker = HybridChunker::builder()
+        .tokenizer(Box::new(WordTokenizer))
+        .max_tokens(5)
+        .merge_across_headings(true)
+        .build()
+        .unwrap();
+
+    let chunks: Vec<_> = chunker.chunk(&sibling_sections_document()).collect();
+    assert_eq!(chunks.len(), 2);
+    assert_eq!(chunks[0].text, "alpha beta");
+    assert_eq!(
+        chunks[1].meta.headings,
+        vec!["Chapter".to_string(), "Usage".to_string()]
+    );
+}
+
+// Test 21: split offsets slice the source text exactly
+#[test]
+fn test_split_offsets_slice_source() {
+    use docling_rs::datamodel::SourcePosition;
+
+    let source = "Intro line.\n\nalpha  beta gamma\ndelta epsilon   zeta eta theta";
+    let body_start = source.find("alpha").unwrap();
+    let body = &source[body_start..];
+
+    let mut doc = DoclingDocument::new("test.txt");
+    doc.add_node(
+        DocumentNode::new(NodeType::Paragraph, body).with_position(SourcePosition::new(
+            body_start,
+            source.len(),
+            3,
+            4,
+        )),
+    );
+
+    let chunker = HybridChunker::builder()
+        .tokenizer(Box::new(WordTokenizer))
+        .max_tokens(3)
+        .merge_peers(false)
+        .build()
+        .unwrap();
+
+    let chunks: Vec<_> = chunker.chunk(&doc).collect();
+    assert_eq!(chunks.len(), 3);
+    for chunk in &chunks {
+        assert_eq!(
+            &source[chunk.meta.start_offset..chunk.meta.end_offset],
+            chunk.text
+        );
+    }
+    assert_eq!(chunks[1].text, "delta epsilon   zeta");
+}
+
+// Word-count tokenizer recording the number of counts
+struct CountingTokenizer(std::sync::Arc<std::sync::atomic::AtomicUsize>);
+
+impl Tokenizer for CountingTokenizer {
+    fn count_tokens(&self, text: &str) -> usize {
+        self.0.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        text.split_whitespace().count()
+    }
+
+    fn max_tokens(&self) -> usize {
+        512
+    }
+}
+
+// Test 22: long sections split into full pieces without counting every prefix
+#[test]
+fn test_split_long_section() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    let words: Vec<String> = (0..1000).map(|i| format!("w{}", i)).collect();
+    let mut doc = DoclingDocument::new("test.md");
+    let heading = doc.add_node(DocumentNode::new(NodeType::Heading, "Title").with_level(1));
+    doc.add_child(
+        heading,
+        DocumentNode::new(NodeType::Paragraph, words.join(" ")),
+    );
+
+    let counts = Arc::new(AtomicUsize::new(0));
+    let chunker = HybridChunker::builder()
+        .tokenizer(Box::new(CountingTokenizer(counts.clone())))
+        .max_tokens(50)
+        .merge_peers(false)
+        .build()
+        .unwrap();
+
+    let chunks: Vec<_> = chunker.chunk(&doc).collect();
+    // The heading takes one token of every piece
+    assert_eq!(chunks.len(), 21);
+    for (i, chunk) in chunks.iter().enumerate() {
+        let expected = if i < 20 { 49 } else { 20 };
+        assert_eq!(chunk.text.split_whitespace().count(), expected);
+        assert_eq!(chunk.text.split(' ').next(), Some(words[i * 49].as_str()));
+    }
+    assert!(counts.load(Ordering::Relaxed) < 400);
+}
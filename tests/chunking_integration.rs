@@ -105,11 +105,15 @@ The system is deployed on AWS."#;
 
     let chunks: Vec<_> = chunker.chunk(&doc).collect();
 
-    // Should produce multiple chunks
+    // Should produce one chunk per paragraph; headings become chunk context
     assert!(
-        chunks.len() >= 5,
+        chunks.len() >= 4,
         "Complex document should produce multiple chunks"
     );
+    assert_eq!(
+        chunks[1].meta.headings,
+        vec!["Technical Documentation", "Components", "Frontend"]
+    );
 
     // All chunks should be sequential
     for i in 1..chunks.len() {
@@ -297,3 +301,26 @@ Content here."#;
     assert!(json.contains("end_offset"), "Should preserve end_offset");
     assert!(json.contains("index"), "Should preserve index");
 }
+
+// JSONL export round-trips through ChunkRecord
+#[test]
+fn test_jsonl_export() {
+    use docling_rs::chunking::{export, ApproxTokenizer, ChunkRecord};
+    use docling_rs::datamodel::{DocumentNode, NodeType};
+
+    let doc = DoclingDocument::new("test.md").with_nodes(vec![
+        DocumentNode::new(NodeType::Heading, "Intro").with_level(1),
+        DocumentNode::new(NodeType::Paragraph, "Hello world."),
+    ]);
+    let chunker = HierarchicalChunker::new();
+
+    let jsonl = export::to_jsonl(chunker.chunk(&doc), &chunker, &ApproxTokenizer::new()).unwrap();
+    let records: Vec<ChunkRecord> = jsonl
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].contextualized_text, "Intro\nHello world.");
+    assert_eq!(records[0].token_count, 5);
+}
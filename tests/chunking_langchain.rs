@@ -0,0 +1,87 @@
+//! Tests for the LangChain document export
+
+use docling_rs::chunking::{
+    langchain, BaseChunk, BaseChunker, ChunkMetadata, HierarchicalChunker, LangChainDocument,
+};
+use docling_rs::{DocumentConverter, InputFormat};
+use serde_json::json;
+
+const MARKDOWN: &str =
+    "# Guide\n\n## Setup\n\nInstall the package.\n\n```sh\ncargo add docling-rs\n```\n";
+
+fn chunks() -> Vec<BaseChunk> {
+    let result = DocumentConverter::new()
+        .convert_bytes(
+            MARKDOWN.as_bytes().to_vec(),
+            "guide.md".to_string(),
+            InputFormat::Markdown,
+        )
+        .unwrap();
+    HierarchicalChunker::new()
+        .chunk(result.document())
+        .collect()
+}
+
+#[test]
+fn test_langchain_documents() {
+    let chunker = HierarchicalChunker::new();
+    let chunks = chunks();
+    let documents = langchain::to_documents(chunks.clone(), &chunker);
+    assert_eq!(documents.len(), chunks.len());
+
+    let setup = documents
+        .iter()
+        .find(|document| document.page_content.ends_with("Install the package."))
+        .unwrap();
+    // The contextualized text, headings first
+    assert!(setup.page_content.starts_with("Guide"));
+    assert_eq!(setup.metadata["source"], "guide.md");
+    assert_eq!(setup.metadata["headings"], json!(["Guide", "Setup"]));
+    assert!(setup.metadata["start_index"].is_u64());
+    assert_eq!(setup.id.as_deref(), setup.metadata["chunk_id"].as_str());
+    assert!(!setup.metadata.contains_key("page"));
+    assert!(!setup.metadata.contains_key("is_code"));
+
+    let code = documents
+        .iter()
+        .find(|document| document.page_content.contains("cargo add"))
+        .unwrap();
+    assert_eq!(code.metadata["is_code"], true);
+
+    for (index, document) in documents.iter().enumerate() {
+        assert_eq!(document.metadata["chunk_index"], index);
+    }
+}
+
+#[test]
+fn test_langchain_page_metadata() {
+    let chunk = BaseChunk {
+        text: "Revenue grew.".to_string(),
+        meta: ChunkMetadata {
+            doc_name: "report.pdf".to_string(),
+            page_start: Some(2),
+            page_end: Some(3),
+            caption: Some("Table 1".to_string()),
+            ..Default::default()
+        },
+    };
+    let document = LangChainDocument::new(&chunk, &HierarchicalChunker::new());
+    assert_eq!(document.metadata["page"], 2);
+    assert_eq!(document.metadata["page_end"], 3);
+    assert_eq!(document.metadata["caption"], "Table 1");
+    // No chunk ID to name the document by
+    assert!(document.id.is_none());
+}
+
+#[test]
+fn test_langchain_json() {
+    let chunker = HierarchicalChunker::new();
+    let json = langchain::to_json(chunks(), &chunker).unwrap();
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    for document in value.as_array().unwrap() {
+        let keys: Vec<_> = document.as_object().unwrap().keys().collect();
+        assert_eq!(keys, ["id", "metadata", "page_content"]);
+    }
+    let documents: Vec<LangChainDocument> = serde_json::from_str(&json).unwrap();
+    assert_eq!(documents, langchain::to_documents(chunks(), &chunker));
+}
@@ -0,0 +1,101 @@
+//! Tests for the LlamaIndex node export
+
+use docling_rs::chunking::{
+    chunk_id, llamaindex, BaseChunk, BaseChunker, ChunkMetadata, HierarchicalChunker,
+    LlamaIndexNode,
+};
+use docling_rs::{DocumentConverter, InputFormat};
+use serde_json::json;
+
+const MARKDOWN: &str =
+    "# Guide\n\n## Setup\n\nInstall the package.\n\nThen configure it.\n\n## Usage\n\nRun it.\n";
+
+fn chunks() -> Vec<BaseChunk> {
+    let result = DocumentConverter::new()
+        .convert_bytes(
+            MARKDOWN.as_bytes().to_vec(),
+            "guide.md".to_string(),
+            InputFormat::Markdown,
+        )
+        .unwrap();
+    HierarchicalChunker::new()
+        .chunk(result.document())
+        .collect()
+}
+
+#[test]
+fn test_llamaindex_nodes() {
+    let chunks = chunks();
+    let nodes = llamaindex::to_nodes(chunks.clone());
+    assert_eq!(nodes.len(), chunks.len());
+    assert!(nodes.len() >= 3);
+
+    for (node, chunk) in nodes.iter().zip(&chunks) {
+        assert_eq!(node.id, chunk.meta.chunk_id);
+        assert_eq!(node.text, chunk.text);
+        assert_eq!(node.start_char_idx, Some(chunk.meta.start_offset));
+        assert_eq!(node.end_char_idx, Some(chunk.meta.end_offset));
+        assert_eq!(node.metadata["file_name"], "guide.md");
+        assert_eq!(node.relationships["1"].node_id, "guide.md");
+        assert_eq!(node.relationships["1"].node_type.as_deref(), Some("4"));
+    }
+
+    let setup = nodes
+        .iter()
+        .find(|node| node.text == "Install the package.")
+        .unwrap();
+    assert_eq!(setup.metadata["headings"], json!(["Guide", "Setup"]));
+
+    // Chained in order
+    assert!(!nodes[0].relationships.contains_key("2"));
+    assert!(!nodes.last().unwrap().relationships.contains_key("3"));
+    for pair in nodes.windows(2) {
+        assert_eq!(pair[0].relationships["3"].node_id, pair[1].id);
+        assert_eq!(pair[1].relationships["2"].node_id, pair[0].id);
+    }
+}
+
+#[test]
+fn test_llamaindex_page_metadata() {
+    let chunk = BaseChunk {
+        text: "Revenue grew.".to_string(),
+        meta: ChunkMetadata {
+            doc_name: "report.pdf".to_string(),
+            page_start: Some(2),
+            page_end: Some(3),
+            ..Default::default()
+        },
+    };
+    let node = LlamaIndexNode::new(&chunk);
+    assert_eq!(node.metadata["page_label"], "2");
+    assert_eq!(node.metadata["page_end"], 3);
+    // Chunks built by hand get an ID all the same
+    assert_eq!(node.id, chunk_id("report.pdf", &[], "Revenue grew."));
+
+    let chunk = BaseChunk {
+        meta: ChunkMetadata {
+            page_end: Some(2),
+            ..chunk.meta
+        },
+        ..chunk
+    };
+    assert!(!LlamaIndexNode::new(&chunk)
+        .metadata
+        .contains_key("page_end"));
+}
+
+#[test]
+fn test_llamaindex_json() {
+    let json = llamaindex::to_json(chunks()).unwrap();
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    let node = &value[0];
+    assert!(node["id_"].is_string());
+    assert_eq!(node["class_name"], "TextNode");
+    assert_eq!(node["mimetype"], "text/plain");
+    assert_eq!(node["metadata_seperator"], "\n");
+    assert!(node["embedding"].is_null());
+    assert_eq!(node["relationships"]["3"]["class_name"], "RelatedNodeInfo");
+
+    let nodes: Vec<LlamaIndexNode> = serde_json::from_str(&json).unwrap();
+    assert_eq!(nodes, llamaindex::to_nodes(chunks()));
+}
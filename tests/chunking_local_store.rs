@@ -0,0 +1,116 @@
+//! Tests for the local vector store sink
+
+use docling_rs::chunking::sinks::{ChunkSink, LocalStore};
+use docling_rs::chunking::{ApproxTokenizer, BaseChunker, ChunkRecord, HierarchicalChunker};
+use docling_rs::embeddings::{self, Embedder, HashEmbedder};
+use docling_rs::{DocumentConverter, InputFormat};
+use std::fs;
+use tempfile::TempDir;
+
+fn records(name: &str, markdown: &str) -> Vec<ChunkRecord> {
+    let result = DocumentConverter::new()
+        .convert_bytes(
+            markdown.as_bytes().to_vec(),
+            name.to_string(),
+            InputFormat::Markdown,
+        )
+        .unwrap();
+    let chunker = HierarchicalChunker::new();
+    let mut records: Vec<ChunkRecord> = chunker
+        .chunk(result.document())
+        .map(|chunk| ChunkRecord::new(chunk, &chunker, &ApproxTokenizer::new()))
+        .collect();
+    embeddings::embed_records(&mut records, &HashEmbedder::new(64)).unwrap();
+    records
+}
+
+#[test]
+fn test_local_store_search() {
+    let temp = TempDir::new().unwrap();
+    let dir = temp.path().join("index");
+    let mut store = LocalStore::open(&dir).unwrap();
+    assert!(store.is_empty());
+    store.set_model("hash:64").unwrap();
+    store
+        .write(&records(
+            "garden.md",
+            "# Garden\n\nWater the tomato plants every morning.\n\nPrune the roses in spring.\n",
+        ))
+        .unwrap();
+    store
+        .write(&records(
+            "kitchen.md",
+            "# Kitchen\n\nBoil the pasta in salted water.\n",
+        ))
+        .unwrap();
+    assert_eq!(store.len(), 3);
+    assert_eq!(store.dimensions(), Some(64));
+
+    let embedder = HashEmbedder::new(64);
+    let query = embedder.embed(&["when to prune roses"]).unwrap().remove(0);
+    let hits = store.search(&query, 2);
+    assert_eq!(hits.len(), 2);
+    assert_eq!(hits[0].record.text, "Prune the roses in spring.");
+    assert!(hits[0].score > hits[1].score);
+
+    // Everything is back after reopening
+    let store = LocalStore::open(&dir).unwrap();
+    assert_eq!(store.len(), 3);
+    assert_eq!(store.model(), Some("hash:64"));
+    assert_eq!(
+        store.documents().collect::<Vec<_>>(),
+        ["garden.md", "kitchen.md"]
+    );
+    assert_eq!(
+        store.search(&query, 1)[0].record.text,
+        "Prune the roses in spring."
+    );
+}
+
+#[test]
+fn test_local_store_replaces_chunks() {
+    let temp = TempDir::new().unwrap();
+    let mut store = LocalStore::open(temp.path()).unwrap();
+    let first = records("notes.md", "# Notes\n\nFirst.\n\nSecond.\n");
+    store.write(&first).unwrap();
+    // Writing a chunk again replaces it
+    store.write(&first[..1]).unwrap();
+    assert_eq!(store.len(), 2);
+
+    // A new version of the document replaces the old one once removed
+    assert!(store.remove_document("notes.md").unwrap());
+    assert!(!store.remove_document("notes.md").unwrap());
+    store
+        .write(&records("notes.md", "# Notes\n\nRewritten.\n"))
+        .unwrap();
+    let store = LocalStore::open(temp.path()).unwrap();
+    assert_eq!(store.len(), 1);
+    assert_eq!(store.search(&[1.0; 64], 5)[0].record.text, "Rewritten.");
+}
+
+#[test]
+fn test_local_store_errors() {
+    let temp = TempDir::new().unwrap();
+
+    // Not an index
+    let other = temp.path().join("other");
+    fs::create_dir(&other).unwrap();
+    fs::write(other.join("notes.txt"), "keep me").unwrap();
+    assert!(LocalStore::open(&other).is_err());
+
+    let mut store = LocalStore::open(temp.path().join("index")).unwrap();
+    store.set_model("hash:64").unwrap();
+    assert!(store.set_model("hash:64").is_ok());
+    assert!(store.set_model("openai:text-embedding-3-small").is_err());
+
+    let mut records = records("doc.md", "# Title\n\nFirst.\n\nSecond.\n");
+    store.write(&records[..1]).unwrap();
+
+    // Vectors of another size, and chunks without one, are refused
+    records[1].embedding = Some(vec![1.0; 8]);
+    assert!(store.write(&records).is_err());
+    records[1].embedding = None;
+    let error = store.write(&records).unwrap_err().to_string();
+    assert!(error.contains("no embedding"), "{}", error);
+    assert_eq!(store.len(), 1);
+}
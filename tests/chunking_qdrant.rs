@@ -0,0 +1,164 @@
+//! Tests for the Qdrant chunk sink
+
+#![cfg(feature = "qdrant")]
+
+use docling_rs::chunking::sinks::{ChunkSink, QdrantSink};
+use docling_rs::chunking::{ApproxTokenizer, BaseChunker, ChunkRecord, HierarchicalChunker};
+use docling_rs::embeddings::{self, HashEmbedder};
+use docling_rs::{DocumentConverter, InputFormat};
+use serde_json::Value;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+const MARKDOWN: &str = "# Title\n\nFirst paragraph.\n\nSecond paragraph.\n\nThird paragraph.\n";
+
+fn records() -> Vec<ChunkRecord> {
+    let result = DocumentConverter::new()
+        .convert_bytes(
+            MARKDOWN.as_bytes().to_vec(),
+            "doc.md".to_string(),
+            InputFormat::Markdown,
+        )
+        .unwrap();
+    let chunker = HierarchicalChunker::new();
+    let mut records: Vec<ChunkRecord> = chunker
+        .chunk(result.document())
+        .map(|chunk| ChunkRecord::new(chunk, &chunker, &ApproxTokenizer::new()))
+        .collect();
+    embeddings::embed_records(&mut records, &HashEmbedder::new(8)).unwrap();
+    records
+}
+
+/// A request received by [`mock`]: request line, API key and JSON body
+type Request = (String, Option<String>, Value);
+
+/// Answer one request per connection with `statuses`, in order, and
+/// return the requests received
+fn mock(statuses: Vec<u16>) -> (String, JoinHandle<Vec<Request>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = format!("http://{}", listener.local_addr().unwrap());
+    let server = std::thread::spawn(move || {
+        let mut requests = Vec::new();
+        for status in statuses {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            let mut length = 0;
+            let mut api_key = None;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                let lower = line.to_ascii_lowercase();
+                if let Some(value) = lower.strip_prefix("content-length:") {
+                    length = value.trim().parse().unwrap();
+                }
+                if let Some(value) = lower.strip_prefix("api-key:") {
+                    api_key = Some(value.trim().to_string());
+                }
+                if line == "\r\n" {
+                    break;
+                }
+            }
+            let mut body = vec![0; length];
+            reader.read_exact(&mut body).unwrap();
+            requests.push((
+                request_line.trim().to_string(),
+                api_key,
+                serde_json::from_slice(&body).unwrap_or(Value::Null),
+            ));
+
+            let response = r#"{"status":"ok"}"#;
+            write!(
+                reader.get_mut(),
+                "HTTP/1.1 {} Status\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                response.len(),
+                response
+            )
+            .unwrap();
+        }
+        requests
+    });
+    (address, server)
+}
+
+#[test]
+fn test_qdrant_sink_upserts_in_batches() {
+    let records = records();
+    assert_eq!(records.len(), 3);
+
+    // No collection yet; the first batch is refused once
+    let (address, server) = mock(vec![404, 200, 503, 200, 200]);
+    let mut sink = QdrantSink::new(address, "docs")
+        .with_api_key("secret")
+        .with_batch_size(2)
+        .with_retry_delay(Duration::from_millis(1));
+    sink.write(&records).unwrap();
+
+    let requests = server.join().unwrap();
+    let lines: Vec<_> = requests.iter().map(|(line, _, _)| line.as_str()).collect();
+    assert_eq!(
+        lines,
+        [
+            "GET /collections/docs HTTP/1.1",
+            "PUT /collections/docs HTTP/1.1",
+            "PUT /collections/docs/points?wait=true HTTP/1.1",
+            "PUT /collections/docs/points?wait=true HTTP/1.1",
+            "PUT /collections/docs/points?wait=true HTTP/1.1",
+        ]
+    );
+    assert!(requests
+        .iter()
+        .all(|(_, key, _)| key.as_deref() == Some("secret")));
+    assert_eq!(requests[1].2["vectors"]["size"], 8);
+    assert_eq!(requests[1].2["vectors"]["distance"], "Cosine");
+
+    // The retried batch is sent again as is
+    assert_eq!(requests[2].2, requests[3].2);
+    let mut points = requests[3].2["points"].as_array().unwrap().clone();
+    assert_eq!(points.len(), 2);
+    points.extend(requests[4].2["points"].as_array().unwrap().clone());
+    assert_eq!(points.len(), 3);
+
+    for (point, record) in points.iter().zip(&records) {
+        let id = point["id"].as_str().unwrap();
+        assert_eq!(id.len(), 36);
+        assert!(record.meta.chunk_id.starts_with(&id[..8]));
+        assert_eq!(
+            point["vector"],
+            serde_json::json!(record.embedding.as_ref().unwrap())
+        );
+        assert_eq!(point["payload"]["text"], record.text.as_str());
+        assert_eq!(point["payload"]["meta"]["doc_name"], "doc.md");
+        assert_eq!(point["payload"]["meta"]["headings"][0], "Title");
+        assert!(point["payload"].get("embedding").is_none());
+    }
+}
+
+#[test]
+fn test_qdrant_sink_errors() {
+    // The collection exists; upserts keep failing past the retries
+    let (address, server) = mock(vec![200, 500, 500]);
+    let mut sink = QdrantSink::new(address, "docs")
+        .with_max_retries(1)
+        .with_retry_delay(Duration::from_millis(1));
+    let error = sink.write(&records()).unwrap_err().to_string();
+    assert!(error.contains("500"), "{}", error);
+    assert_eq!(server.join().unwrap().len(), 3);
+
+    // Client errors are not retried
+    let (address, server) = mock(vec![400]);
+    let mut sink = QdrantSink::new(address, "docs");
+    assert!(sink.write(&records()).is_err());
+    assert_eq!(server.join().unwrap().len(), 1);
+
+    // Records must be embedded, and nothing is sent when one is not
+    let mut records = records();
+    records[1].embedding = None;
+    let mut sink = QdrantSink::new("http://127.0.0.1:9", "docs").with_max_retries(0);
+    let error = sink.write(&records).unwrap_err().to_string();
+    assert!(error.contains("no embedding"), "{}", error);
+}
@@ -0,0 +1,105 @@
+//! RecursiveCharacterChunker behavior tests
+//!
+//! Tests for separator-based recursive splitting.
+
+use docling_rs::chunking::{BaseChunker, ChunkingError, RecursiveCharacterChunker};
+use docling_rs::datamodel::{DocumentNode, NodeType};
+use docling_rs::DoclingDocument;
+
+fn document(nodes: Vec<DocumentNode>) -> DoclingDocument {
+    DoclingDocument::new("test.md").with_nodes(nodes)
+}
+
+fn chunker(size: usize) -> RecursiveCharacterChunker {
+    RecursiveCharacterChunker::builder()
+        .chunk_size(size)
+        .build()
+        .unwrap()
+}
+
+// Test 1: zero chunk size and empty separators are rejected
+#[test]
+fn test_invalid_config() {
+    let result = RecursiveCharacterChunker::builder().chunk_size(0).build();
+    assert!(matches!(result, Err(ChunkingError::InvalidConfig(_))));
+
+    let result = RecursiveCharacterChunker::builder()
+        .separators(Vec::new())
+        .build();
+    assert!(matches!(result, Err(ChunkingError::InvalidConfig(_))));
+}
+
+// Test 2: small paragraphs under one heading are merged
+#[test]
+fn test_small_paragraphs_merged() {
+    let doc = document(vec![
+        DocumentNode::new(NodeType::Heading, "Intro").with_level(1),
+        DocumentNode::new(NodeType::Paragraph, "First."),
+        DocumentNode::new(NodeType::Paragraph, "Second."),
+    ]);
+
+    let chunks: Vec<_> = chunker(100).chunk(&doc).collect();
+    assert_eq!(chunks.len(), 1);
+    assert_eq!(chunks[0].text, "First.\n\nSecond.");
+    assert_eq!(chunks[0].meta.headings, vec!["Intro"]);
+}
+
+// Test 3: sections are never merged with each other
+#[test]
+fn test_sections_kept_apart() {
+    let doc = document(vec![
+        DocumentNode::new(NodeType::Heading, "A").with_level(1),
+        DocumentNode::new(NodeType::Paragraph, "Alpha."),
+        DocumentNode::new(NodeType::Heading, "B").with_level(1),
+        DocumentNode::new(NodeType::Paragraph, "Beta."),
+    ]);
+
+    let chunks: Vec<_> = chunker(100).chunk(&doc).collect();
+    let texts: Vec<_> = chunks.iter().map(|c| c.text.as_str()).collect();
+    assert_eq!(texts, vec!["Alpha.", "Beta."]);
+    assert_eq!(chunks[1].meta.headings, vec!["B"]);
+    assert_eq!(chunks[1].meta.index, 1);
+}
+
+// Test 4: long paragraphs fall back to sentences, then words
+#[test]
+fn test_falls_back_to_finer_separators() {
+    let doc = document(vec![DocumentNode::new(
+        NodeType::Paragraph,
+        "One two three. Four five six. Seven eight nine ten eleven twelve.",
+    )]);
+
+    let chunks: Vec<_> = chunker(30).chunk(&doc).collect();
+    let texts: Vec<_> = chunks.iter().map(|c| c.text.as_str()).collect();
+    assert_eq!(
+        texts,
+        vec![
+            "One two three. Four five six.",
+            "Seven eight nine ten eleven",
+            "twelve."
+        ]
+    );
+
+    for chunk in &chunks {
+        assert!(chunk.text.chars().count() <= 30);
+        assert_eq!(
+            &doc.nodes()[0].text_content().unwrap()[chunk.meta.start_offset..chunk.meta.end_offset],
+            chunk.text
+        );
+    }
+}
+
+// Test 5: custom separators are honored
+#[test]
+fn test_custom_separators() {
+    let doc = document(vec![DocumentNode::new(NodeType::Paragraph, "a;b;c")]);
+
+    let chunker = RecursiveCharacterChunker::builder()
+        .chunk_size(2)
+        .separators(vec![";".to_string()])
+        .build()
+        .unwrap();
+
+    let texts: Vec<_> = chunker.chunk(&doc).map(|c| c.text).collect();
+    assert_eq!(texts, vec!["a;", "b;", "c"]);
+}
@@ -0,0 +1,67 @@
+//! ChunkerRegistry tests
+
+use docling_rs::chunking::{ChunkerOptions, ChunkerRegistry, ChunkingError, HierarchicalChunker};
+use docling_rs::datamodel::{DocumentNode, NodeType};
+use docling_rs::DoclingDocument;
+
+fn document() -> DoclingDocument {
+    DoclingDocument::new("test.md").with_nodes(vec![
+        DocumentNode::new(NodeType::Paragraph, "First paragraph."),
+        DocumentNode::new(NodeType::Paragraph, "Second paragraph."),
+    ])
+}
+
+#[test]
+fn test_builtin_strategies_registered() {
+    let registry = ChunkerRegistry::new();
+    let names: Vec<_> = registry.names().collect();
+    assert_eq!(
+        names,
+        vec![
+            "fixed",
+            "hierarchical",
+            "hybrid",
+            "recursive",
+            "semantic",
+            "window"
+        ]
+    );
+}
+
+#[test]
+fn test_create_by_name() {
+    let registry = ChunkerRegistry::new();
+    let chunker = registry
+        .create("hierarchical", ChunkerOptions::default())
+        .unwrap();
+    assert_eq!(chunker.chunk(&document()).count(), 2);
+
+    let chunker = registry
+        .create(
+            "recursive",
+            ChunkerOptions {
+                chunk_size: Some(100),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    assert_eq!(chunker.chunk(&document()).count(), 1);
+}
+
+#[test]
+fn test_unknown_strategy() {
+    let result = ChunkerRegistry::new().create("sentences", ChunkerOptions::default());
+    assert!(matches!(result, Err(ChunkingError::InvalidConfig(_))));
+}
+
+#[test]
+fn test_register_custom_strategy() {
+    let mut registry = ChunkerRegistry::empty();
+    registry.register("flat", |_| {
+        Ok(Box::new(HierarchicalChunker::with_merge_list_items(false)))
+    });
+
+    assert!(registry.contains("flat"));
+    assert!(!registry.contains("hierarchical"));
+    assert!(registry.create("flat", ChunkerOptions::default()).is_ok());
+}
@@ -0,0 +1,135 @@
+//! SemanticChunker tests
+
+use docling_rs::chunking::{ApproxTokenizer, BaseChunker, SemanticChunker, Tokenizer};
+use docling_rs::datamodel::{DocumentNode, NodeType};
+use docling_rs::embeddings::{Embedder, EmbeddingError};
+use docling_rs::DoclingDocument;
+use std::sync::Arc;
+
+/// Embeds texts mentioning cats and texts mentioning ships on opposite axes
+struct TopicEmbedder;
+
+impl Embedder for TopicEmbedder {
+    fn model(&self) -> &str {
+        "topics"
+    }
+
+    fn embed(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>, EmbeddingError> {
+        Ok(texts
+            .iter()
+            .map(|text| {
+                if text.contains("cat") {
+                    vec![1.0, 0.1]
+                } else {
+                    vec![0.1, 1.0]
+                }
+            })
+            .collect())
+    }
+}
+
+struct FailingEmbedder;
+
+impl Embedder for FailingEmbedder {
+    fn model(&self) -> &str {
+        "failing"
+    }
+
+    fn embed(&self, _texts: &[&str]) -> Result<Vec<Vec<f32>>, EmbeddingError> {
+        Err(EmbeddingError::Request("offline".to_string()))
+    }
+}
+
+fn paragraphs(texts: &[&str]) -> DoclingDocument {
+    DoclingDocument::new("topics.md").with_nodes(
+        texts
+            .iter()
+            .map(|text| DocumentNode::new(NodeType::Paragraph, *text))
+            .collect(),
+    )
+}
+
+fn topics() -> DoclingDocument {
+    paragraphs(&[
+        "The cat sleeps all day.",
+        "A cat purrs when content.",
+        "My cat chases mice.",
+        "Ships sail across the sea.",
+        "A ship needs a crew.",
+    ])
+}
+
+#[test]
+fn test_breaks_where_topic_shifts() {
+    let chunker = SemanticChunker::builder()
+        .embedder(Arc::new(TopicEmbedder))
+        .build()
+        .unwrap();
+
+    let chunks: Vec<_> = chunker.chunk(&topics()).collect();
+    assert_eq!(chunks.len(), 2);
+    assert_eq!(
+        chunks[0].text,
+        "The cat sleeps all day. A cat purrs when content. My cat chases mice."
+    );
+    assert_eq!(
+        chunks[1].text,
+        "Ships sail across the sea. A ship needs a crew."
+    );
+    assert_eq!(chunks[1].meta.index, 1);
+    assert!(!chunks[1].meta.chunk_id.is_empty());
+}
+
+#[test]
+fn test_respects_max_tokens() {
+    let chunker = SemanticChunker::builder()
+        .embedder(Arc::new(TopicEmbedder))
+        .max_tokens(12)
+        .build()
+        .unwrap();
+
+    let chunks: Vec<_> = chunker.chunk(&topics()).collect();
+    assert!(chunks.len() > 2);
+    let tokenizer = ApproxTokenizer::new();
+    for chunk in &chunks {
+        assert!(tokenizer.count_tokens(&chunker.contextualize(chunk)) <= 12);
+    }
+}
+
+#[test]
+fn test_keeps_sections_apart() {
+    let doc = DoclingDocument::new("sections.md").with_nodes(vec![
+        DocumentNode::new(NodeType::Heading, "Cats").with_level(1),
+        DocumentNode::new(NodeType::Paragraph, "The cat sleeps."),
+        DocumentNode::new(NodeType::Heading, "More cats").with_level(1),
+        DocumentNode::new(NodeType::Paragraph, "The cat wakes."),
+    ]);
+    let chunker = SemanticChunker::builder()
+        .embedder(Arc::new(TopicEmbedder))
+        .build()
+        .unwrap();
+
+    let chunks: Vec<_> = chunker.chunk(&doc).collect();
+    assert_eq!(chunks.len(), 2);
+    assert_eq!(chunks[1].meta.headings, vec!["More cats"]);
+}
+
+#[test]
+fn test_embedder_failure_groups_by_section() {
+    let chunker = SemanticChunker::builder()
+        .embedder(Arc::new(FailingEmbedder))
+        .build()
+        .unwrap();
+
+    let chunks: Vec<_> = chunker.chunk(&topics()).collect();
+    assert_eq!(chunks.len(), 1);
+}
+
+#[test]
+fn test_invalid_config() {
+    assert!(SemanticChunker::builder().max_tokens(0).build().is_err());
+    assert!(SemanticChunker::builder()
+        .breakpoint_percentile(120.0)
+        .build()
+        .is_err());
+}
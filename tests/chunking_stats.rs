@@ -0,0 +1,58 @@
+//! ChunkStats tests
+
+use docling_rs::chunking::tokenizer::Tokenizer;
+use docling_rs::chunking::{BaseChunk, ChunkMetadata, ChunkStats, HierarchicalChunker};
+
+// Counts whitespace-separated words
+struct WordTokenizer;
+
+impl Tokenizer for WordTokenizer {
+    fn count_tokens(&self, text: &str) -> usize {
+        text.split_whitespace().count()
+    }
+
+    fn max_tokens(&self) -> usize {
+        512
+    }
+}
+
+fn chunk(text: &str) -> BaseChunk {
+    BaseChunk {
+        text: text.to_string(),
+        meta: ChunkMetadata::default(),
+    }
+}
+
+#[test]
+fn test_distribution() {
+    // 1..=20 words
+    let chunks: Vec<_> = (1..=20).map(|n| chunk(&vec!["w"; n].join(" "))).collect();
+
+    let stats = ChunkStats::compute(&chunks, &HierarchicalChunker::new(), &WordTokenizer, 18);
+
+    assert_eq!(stats.chunk_count, 20);
+    assert_eq!(stats.min_tokens, 1);
+    assert_eq!(stats.max_tokens, 20);
+    assert_eq!(stats.mean_tokens, 10.5);
+    assert_eq!(stats.p95_tokens, 19);
+    assert_eq!(stats.oversize_count, 2);
+    assert_eq!(stats.empty_count, 0);
+    assert_eq!(stats.warnings().len(), 1);
+}
+
+#[test]
+fn test_empty_chunks_warned() {
+    let chunks = vec![chunk("hello"), chunk("   ")];
+    let stats = ChunkStats::compute(&chunks, &HierarchicalChunker::new(), &WordTokenizer, 10);
+
+    assert_eq!(stats.empty_count, 1);
+    assert!(stats.to_string().contains("Warning: 1 chunk(s) are empty"));
+}
+
+#[test]
+fn test_no_chunks() {
+    let stats = ChunkStats::compute(&[], &HierarchicalChunker::new(), &WordTokenizer, 10);
+
+    assert_eq!(stats.chunk_count, 0);
+    assert_eq!(stats.warnings(), vec!["no chunks were produced"]);
+}
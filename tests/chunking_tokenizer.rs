@@ -134,3 +134,110 @@ fn test_unicode_handling() {
 
     assert!(count > 0, "Should handle Unicode text");
 }
+
+// Tokenizer specifications
+#[test]
+fn test_from_spec_rejects_unknown() {
+    use docling_rs::chunking::tokenizer::from_spec;
+
+    assert!(from_spec("bogus").is_err());
+    assert!(from_spec("tiktoken:not_an_encoding").is_err());
+}
+
+#[cfg(feature = "tiktoken")]
+#[test]
+fn test_tiktoken_counts() {
+    use docling_rs::chunking::tokenizer::{from_spec, TiktokenTokenizer};
+
+    let tokenizer = TiktokenTokenizer::cl100k_base();
+    assert_eq!(tokenizer.count_tokens("hello world"), 2);
+    assert_eq!(tokenizer.count_tokens(""), 0);
+    assert_eq!(tokenizer.max_tokens(), 8191);
+    assert_eq!(tokenizer.with_max_tokens(512).max_tokens(), 512);
+
+    let from_spec = from_spec("tiktoken:o200k_base").unwrap();
+    assert_eq!(from_spec.count_tokens("hello world"), 2);
+}
+
+#[cfg(feature = "hub")]
+#[test]
+fn test_hub_offline_uses_cache_only() {
+    use docling_rs::chunking::tokenizer::hub::fetch_file;
+    use docling_rs::chunking::tokenizer::HubOptions;
+
+    let temp = tempfile::TempDir::new().unwrap();
+    let options = HubOptions {
+        cache_dir: temp.path().to_path_buf(),
+        offline: true,
+        ..Default::default()
+    };
+
+    // Nothing cached yet
+    assert!(fetch_file("org/model", "tokenizer.json", &options).is_err());
+
+    let cached = options.cached_path("org/model", "tokenizer.json");
+    std::fs::create_dir_all(cached.parent().unwrap()).unwrap();
+    std::fs::write(&cached, "{}").unwrap();
+
+    assert_eq!(
+        fetch_file("org/model", "tokenizer.json", &options).unwrap(),
+        cached
+    );
+}
+
+#[test]
+fn test_approx_tokenizer() {
+    use docling_rs::chunking::tokenizer::ApproxTokenizer;
+
+    let tokenizer = ApproxTokenizer::new();
+    assert_eq!(tokenizer.max_tokens(), 512);
+    assert_eq!(tokenizer.count_tokens(""), 0);
+    // ~4 characters per token
+    assert_eq!(tokenizer.count_tokens("internationalization"), 5);
+    // Never fewer tokens than words
+    assert_eq!(tokenizer.count_tokens("a b c d e f"), 6);
+    assert_eq!(ApproxTokenizer::with_max_tokens(128).max_tokens(), 128);
+}
+
+/// A word-level HuggingFace tokenizer knowing a few words
+#[cfg(feature = "huggingface")]
+const WORD_LEVEL_TOKENIZER: &str = r#"{
+  "version": "1.0",
+  "truncation": null,
+  "padding": null,
+  "added_tokens": [],
+  "normalizer": null,
+  "pre_tokenizer": { "type": "Whitespace" },
+  "post_processor": null,
+  "decoder": null,
+  "model": {
+    "type": "WordLevel",
+    "vocab": { "[UNK]": 0, "hello": 1, "world": 2 },
+    "unk_token": "[UNK]"
+  }
+}"#;
+
+#[cfg(feature = "huggingface")]
+#[test]
+fn test_from_spec_loads_tokenizer_files() {
+    use docling_rs::chunking::tokenizer::from_spec;
+
+    let temp = tempfile::TempDir::new().unwrap();
+    let path = temp.path().join("tokenizer.json");
+    std::fs::write(&path, WORD_LEVEL_TOKENIZER).unwrap();
+
+    let tokenizer = from_spec(path.to_str().unwrap()).unwrap();
+    assert_eq!(tokenizer.count_tokens("hello world again"), 3);
+
+    let missing = temp.path().join("missing.json");
+    assert!(from_spec(missing.to_str().unwrap()).is_err());
+}
+
+#[cfg(not(feature = "huggingface"))]
+#[test]
+fn test_from_spec_huggingface_needs_feature() {
+    use docling_rs::chunking::tokenizer::from_spec;
+
+    let error = from_spec("hf:bert-base-uncased").err().unwrap();
+    assert!(error.to_string().contains("`huggingface` feature"));
+}
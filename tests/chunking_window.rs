@@ -0,0 +1,107 @@
+//! WindowChunker tests
+
+use docling_rs::chunking::tokenizer::Tokenizer;
+use docling_rs::chunking::{BaseChunker, WindowChunker};
+use docling_rs::datamodel::{DocumentNode, NodeType};
+use docling_rs::DoclingDocument;
+
+// Word-count tokenizer for deterministic window sizes
+struct WordTokenizer;
+
+impl Tokenizer for WordTokenizer {
+    fn count_tokens(&self, text: &str) -> usize {
+        text.split_whitespace().count()
+    }
+
+    fn max_tokens(&self) -> usize {
+        512
+    }
+}
+
+fn chunker(window_size: usize, stride: usize) -> WindowChunker {
+    WindowChunker::builder()
+        .tokenizer(Box::new(WordTokenizer))
+        .window_size(window_size)
+        .stride(stride)
+        .build()
+        .unwrap()
+}
+
+fn document() -> DoclingDocument {
+    let mut doc = DoclingDocument::new("test.md");
+    doc.add_node(DocumentNode::new(NodeType::Heading, "Title").with_level(1));
+    doc.add_node(DocumentNode::new(NodeType::Paragraph, "one two three"));
+    doc.add_node(DocumentNode::new(
+        NodeType::Paragraph,
+        "four five six seven",
+    ));
+    doc
+}
+
+#[test]
+fn test_stride_validation() {
+    assert!(WindowChunker::builder().window_size(0).build().is_err());
+    assert!(WindowChunker::builder()
+        .window_size(4)
+        .stride(0)
+        .build()
+        .is_err());
+    assert!(WindowChunker::builder()
+        .window_size(4)
+        .stride(5)
+        .build()
+        .is_err());
+    assert!(WindowChunker::builder().window_size(4).build().is_ok());
+}
+
+#[test]
+fn test_overlapping_windows_ignore_structure() {
+    let chunks: Vec<_> = chunker(4, 2).chunk(&document()).collect();
+    let texts: Vec<_> = chunks.iter().map(|c| c.text.as_str()).collect();
+
+    assert_eq!(
+        texts,
+        vec![
+            "Title\n\none two three",
+            "two three\n\nfour five",
+            "four five six seven",
+        ]
+    );
+    for (i, chunk) in chunks.iter().enumerate() {
+        assert_eq!(chunk.meta.index, i);
+        assert!(chunk.meta.headings.is_empty());
+        assert!(!chunk.meta.chunk_id.is_empty());
+    }
+}
+
+#[test]
+fn test_stride_equal_to_window_does_not_overlap() {
+    let chunks: Vec<_> = chunker(4, 4).chunk(&document()).collect();
+    let texts: Vec<_> = chunks.iter().map(|c| c.text.as_str()).collect();
+    assert_eq!(texts, vec!["Title\n\none two three", "four five six seven"]);
+}
+
+#[test]
+fn test_window_offsets_and_pages() {
+    let mut doc = DoclingDocument::new("test.pdf");
+    doc.add_node(DocumentNode::new(NodeType::Text, "alpha beta").with_page(1));
+    doc.add_node(DocumentNode::new(NodeType::Text, "gamma delta").with_page(2));
+
+    let chunks: Vec<_> = chunker(3, 3).chunk(&doc).collect();
+    assert_eq!(chunks.len(), 2);
+    assert_eq!(chunks[0].meta.page_start, Some(1));
+    assert_eq!(chunks[0].meta.page_end, Some(2));
+    assert_eq!(chunks[1].meta.page_start, Some(2));
+
+    // Sequential offsets: "alpha beta" at 0..10, "gamma delta" at 11..22
+    assert_eq!(chunks[0].meta.start_offset, 0);
+    assert_eq!(chunks[0].meta.end_offset, 16);
+    assert_eq!(chunks[1].meta.start_offset, 17);
+    assert_eq!(chunks[1].meta.end_offset, 22);
+}
+
+#[test]
+fn test_empty_document() {
+    let doc = DoclingDocument::new("empty.md");
+    assert_eq!(WindowChunker::new().chunk(&doc).count(), 0);
+}
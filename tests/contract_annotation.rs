@@ -0,0 +1,121 @@
+//! Contract tests for document annotations
+
+use docling_rs::datamodel::{Annotation, DoclingDocument, DocumentNode, NodeType};
+use docling_rs::ConversionError;
+use serde_json::json;
+
+fn sample() -> DoclingDocument {
+    let mut doc = DoclingDocument::new("letter.txt");
+    doc.add_node(DocumentNode::new(NodeType::Title, "Invoice"));
+    doc.add_node(DocumentNode::new(
+        NodeType::Paragraph,
+        "Contact Ada Lovelace at ada@example.com.",
+    ));
+    doc
+}
+
+#[test]
+fn test_annotation_spans_and_labels() {
+    let mut doc = sample();
+    let paragraph = doc.nodes()[1].id().unwrap();
+    let title = doc.nodes()[0].id().unwrap();
+
+    let person = Annotation::new(paragraph, "PERSON")
+        .with_range(8, 20)
+        .with_source("ner")
+        .with_confidence(0.97);
+    let email = Annotation::new(paragraph, "EMAIL")
+        .with_range(24, 39)
+        .with_source("pii")
+        .with_attribute("redact", true);
+    let class = Annotation::new(title, "invoice")
+        .with_source("classifier")
+        .with_confidence(1.4);
+
+    assert_eq!(doc.annotate(person), Some(0));
+    assert_eq!(doc.annotate(email), Some(1));
+    assert_eq!(doc.annotate(class), Some(2));
+
+    let annotations = doc.annotations();
+    assert_eq!(doc.annotated_text(&annotations[0]), Some("Ada Lovelace"));
+    assert_eq!(doc.annotated_text(&annotations[1]), Some("ada@example.com"));
+    assert_eq!(doc.annotated_text(&annotations[2]), Some("Invoice"));
+    assert_eq!(annotations[1].attribute("redact"), Some(&json!(true)));
+    assert_eq!(
+        annotations[2].confidence(),
+        Some(1.0),
+        "confidence is clamped"
+    );
+
+    assert_eq!(doc.annotations_for(paragraph).count(), 2);
+    let labels: Vec<_> = doc
+        .annotations_labeled("EMAIL")
+        .map(|a| a.source())
+        .collect();
+    assert_eq!(labels, [Some("pii")]);
+
+    doc.retain_annotations(|a| a.source() != Some("pii"));
+    assert_eq!(doc.annotations().len(), 2);
+}
+
+#[test]
+fn test_annotation_rejects_invalid_targets() {
+    let mut doc = sample();
+    let paragraph = doc.nodes()[1].id().unwrap();
+    let mut other = DoclingDocument::new("other");
+    for _ in 0..5 {
+        other.add_node(DocumentNode::new(NodeType::Paragraph, "x"));
+    }
+    let missing = other.nodes()[4].id().unwrap();
+
+    // Range past the end of the text, reversed range, unknown node
+    assert_eq!(
+        doc.annotate(Annotation::new(paragraph, "X").with_range(0, 500)),
+        None
+    );
+    assert_eq!(
+        doc.annotate(Annotation::new(paragraph, "X").with_range(10, 5)),
+        None
+    );
+    assert_eq!(doc.annotate(Annotation::new(missing, "X")), None);
+    assert!(doc.annotations().is_empty());
+}
+
+#[test]
+fn test_annotation_roundtrip() {
+    let mut doc = sample();
+    let paragraph = doc.nodes()[1].id().unwrap();
+    doc.annotate(
+        Annotation::new(paragraph, "PERSON")
+            .with_range(8, 20)
+            .with_source("ner")
+            .with_confidence(0.5),
+    );
+
+    let json = serde_json::to_value(&doc).unwrap();
+    assert_eq!(
+        json["annotations"],
+        json!([{
+            "node": 1,
+            "label": "PERSON",
+            "start": 8,
+            "end": 20,
+            "source": "ner",
+            "confidence": 0.5
+        }])
+    );
+    let restored = DoclingDocument::from_json(&json.to_string()).unwrap();
+    assert_eq!(restored, doc);
+
+    // Documents without annotations don't serialize the list
+    let json = serde_json::to_value(sample()).unwrap();
+    assert!(json.get("annotations").is_none());
+
+    // Loading checks annotations against the nodes
+    let mut json = serde_json::to_value(&doc).unwrap();
+    json["annotations"][0]["end"] = json!(100);
+    assert!(matches!(
+        DoclingDocument::from_json(&json.to_string()),
+        Err(ConversionError::Schema(_))
+    ));
+}
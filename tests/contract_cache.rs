@@ -0,0 +1,199 @@
+//! Contract tests for the conversion result cache
+
+mod helpers;
+
+use docling_rs::pipeline::{ConversionLimits, ExceededLimit, LanguageDetector};
+use docling_rs::{ConversionCache, ConversionError, DocumentConverter, InputFormat};
+use std::fs;
+use tempfile::TempDir;
+
+fn entries(dir: &std::path::Path) -> usize {
+    fs::read_dir(dir)
+        .map(|entries| entries.count())
+        .unwrap_or(0)
+}
+
+#[test]
+fn test_cache_returns_stored_result() {
+    let temp = TempDir::new().unwrap();
+    let cache_dir = temp.path().join("cache");
+    let input = temp.path().join("notes.md");
+    fs::write(&input, "# Notes\n\nFirst draft.\n").unwrap();
+
+    let converter = DocumentConverter::new().with_cache(ConversionCache::new(&cache_dir));
+    let first = converter.convert_file(&input).unwrap();
+    assert_eq!(entries(&cache_dir), 1);
+
+    let second = converter.convert_file(&input).unwrap();
+    assert_eq!(second.document(), first.document());
+    assert_eq!(entries(&cache_dir), 1, "hit does not add an entry");
+
+    // A changed source is converted again
+    fs::write(&input, "# Notes\n\nSecond draft.\n").unwrap();
+    let third = converter.convert_file(&input).unwrap();
+    assert!(third
+        .document()
+        .nodes()
+        .iter()
+        .any(|node| node.text_content() == Some("Second draft.")));
+    assert_eq!(entries(&cache_dir), 2);
+}
+
+#[test]
+fn test_cache_hit_skips_conversion() {
+    let temp = TempDir::new().unwrap();
+    let cache = ConversionCache::new(temp.path());
+    let converter = DocumentConverter::new().with_cache(cache.clone());
+    let result = converter
+        .convert_bytes(b"# A".to_vec(), "a.md".to_string(), InputFormat::Markdown)
+        .unwrap();
+
+    // Tamper with the stored entry: a hit returns it as is
+    let key = cache.key(
+        b"# A",
+        "a.md",
+        InputFormat::Markdown,
+        &docling_rs::pipeline::SimplePipeline::new().fingerprint(),
+    );
+    assert_eq!(cache.get(&key).unwrap().document(), result.document());
+    let entry = temp.path().join(format!("{}.json", key));
+    let tampered = fs::read_to_string(&entry)
+        .unwrap()
+        .replace("\"A\"", "\"B\"");
+    fs::write(&entry, tampered).unwrap();
+    let cached = converter
+        .convert_bytes(b"# A".to_vec(), "a.md".to_string(), InputFormat::Markdown)
+        .unwrap();
+    assert_eq!(cached.document().nodes()[0].text_content(), Some("B"));
+
+    // Corrupt entries are misses
+    fs::write(&entry, "not json").unwrap();
+    let fresh = converter
+        .convert_bytes(b"# A".to_vec(), "a.md".to_string(), InputFormat::Markdown)
+        .unwrap();
+    assert_eq!(fresh.document(), result.document());
+}
+
+#[test]
+fn test_cache_key_depends_on_settings() {
+    let temp = TempDir::new().unwrap();
+    let convert = |converter: DocumentConverter| {
+        converter
+            .with_cache(ConversionCache::new(temp.path()))
+            .convert_bytes(
+                b"# Hello\n\nSome text here.".to_vec(),
+                "doc.md".to_string(),
+                InputFormat::Markdown,
+            )
+            .unwrap()
+    };
+
+    convert(DocumentConverter::new());
+    convert(DocumentConverter::new().with_section_grouping(true));
+    convert(DocumentConverter::new().with_language_detection(LanguageDetector::new()));
+    convert(DocumentConverter::new());
+    assert_eq!(entries(temp.path()), 3);
+
+    // Same content under another name is a different document
+    DocumentConverter::new()
+        .with_cache(ConversionCache::new(temp.path()))
+        .convert_bytes(
+            b"# Hello\n\nSome text here.".to_vec(),
+            "other.md".to_string(),
+            InputFormat::Markdown,
+        )
+        .unwrap();
+    assert_eq!(entries(temp.path()), 4);
+
+    assert_eq!(ConversionCache::new(temp.path()).clear().unwrap(), 4);
+    assert_eq!(entries(temp.path()), 0);
+    assert_eq!(
+        ConversionCache::new(temp.path().join("missing"))
+            .clear()
+            .unwrap(),
+        0
+    );
+}
+
+#[test]
+fn test_cache_concurrent_writers() {
+    let temp = TempDir::new().unwrap();
+    let cache = ConversionCache::new(temp.path());
+    let markdown = "Some paragraph of text.\n\n".repeat(200);
+    let result = DocumentConverter::new()
+        .convert_bytes(
+            markdown.into_bytes(),
+            "a.md".to_string(),
+            InputFormat::Markdown,
+        )
+        .unwrap();
+    cache.put("same", &result).unwrap();
+
+    // Threads storing the same entry each write their own temporary file, so
+    // readers only ever see complete entries
+    std::thread::scope(|scope| {
+        for _ in 0..4 {
+            scope.spawn(|| {
+                for _ in 0..10 {
+                    cache.put("same", &result).unwrap();
+                }
+            });
+            scope.spawn(|| {
+                for _ in 0..10 {
+                    assert!(cache.get("same").is_some(), "partial entry");
+                }
+            });
+        }
+    });
+
+    assert_eq!(cache.get("same").unwrap().document(), result.document());
+    assert_eq!(entries(temp.path()), 1, "no temporary file is left behind");
+}
+
+#[test]
+fn test_cache_hit_keeps_page_limit() {
+    let temp = TempDir::new().unwrap();
+    let pdf = helpers::pdf_fixtures::create_multipage_pdf(3);
+
+    DocumentConverter::new()
+        .with_cache(ConversionCache::new(temp.path()))
+        .convert_file(&pdf)
+        .unwrap();
+
+    // The result cached without limits is not returned under a stricter one
+    let error = DocumentConverter::new()
+        .with_cache(ConversionCache::new(temp.path()))
+        .with_limits(ConversionLimits::new().with_max_pages(1))
+        .convert_file(&pdf)
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        ConversionError::LimitExceeded(ExceededLimit::Pages { pages: 3, max: 1 })
+    ));
+}
+
+#[test]
+fn test_cache_hit_keeps_output_limit() {
+    let temp = TempDir::new().unwrap();
+    let convert = |limits: ConversionLimits| {
+        DocumentConverter::new()
+            .with_cache(ConversionCache::new(temp.path()))
+            .with_limits(limits)
+            .convert_bytes(
+                b"# Hello\n\nSome text here.".to_vec(),
+                "doc.md".to_string(),
+                InputFormat::Markdown,
+            )
+    };
+
+    convert(ConversionLimits::new()).unwrap();
+    let error = convert(ConversionLimits::new().with_max_output_bytes(5)).unwrap_err();
+    assert!(matches!(
+        error,
+        ConversionError::LimitExceeded(ExceededLimit::OutputSize { max: 5, .. })
+    ));
+
+    // The timeout does not change the key
+    convert(ConversionLimits::new().with_timeout(std::time::Duration::from_secs(60))).unwrap();
+    assert_eq!(entries(temp.path()), 1);
+}
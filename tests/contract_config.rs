@@ -0,0 +1,232 @@
+//! Contract tests for converter configuration profiles
+
+use clap::{CommandFactory, FromArgMatches};
+use docling_rs::cli::CliArgs;
+use docling_rs::config::ConverterConfig;
+use docling_rs::pipeline::ExceededLimit;
+use docling_rs::{ConversionError, DocumentConverter, InputFormat};
+use std::fs;
+use std::time::Duration;
+use tempfile::TempDir;
+
+const PROFILE: &str = r#"
+[pipeline]
+detect_language = true
+
+[pdf]
+password = "secret"
+first_page = 2
+last_page = 5
+images = false
+
+[html]
+extract_metadata = false
+
+[limits]
+timeout_secs = 30
+max_output_bytes = 8
+
+[chunking]
+enabled = true
+chunker = "hybrid"
+max_tokens = 256
+
+[output]
+format = "jsonl"
+"#;
+
+#[test]
+fn test_toml_profile() {
+    let config = ConverterConfig::from_toml(PROFILE).unwrap();
+    assert!(config.pipeline.detect_language);
+    assert!(!config.pipeline.group_sections);
+    assert_eq!(config.chunking.chunker.as_deref(), Some("hybrid"));
+    assert!(!config.html_config().extract_metadata);
+    assert_eq!(config.output.format.as_deref(), Some("jsonl"));
+
+    let pdf = config.pdf_config();
+    assert_eq!(pdf.password.as_deref(), Some("secret"));
+    assert_eq!(pdf.page_range, Some(1..5));
+    assert!(!pdf.enable_images);
+    assert!(pdf.enable_tables, "unset fields keep the defaults");
+
+    let limits = config.limits();
+    assert_eq!(limits.timeout(), Some(Duration::from_secs(30)));
+    assert_eq!(limits.max_pages(), None);
+    assert_eq!(limits.max_output_bytes(), Some(8));
+}
+
+#[test]
+fn test_yaml_profile_matches_toml() {
+    let yaml = r#"
+pipeline:
+  detect_language: true
+pdf:
+  password: secret
+  first_page: 2
+  last_page: 5
+  images: false
+html:
+  extract_metadata: false
+limits:
+  timeout_secs: 30
+  max_output_bytes: 8
+chunking:
+  enabled: true
+  chunker: hybrid
+  max_tokens: 256
+output:
+  format: jsonl
+"#;
+    assert_eq!(
+        ConverterConfig::from_yaml(yaml).unwrap(),
+        ConverterConfig::from_toml(PROFILE).unwrap()
+    );
+    assert_eq!(
+        ConverterConfig::from_yaml("").unwrap(),
+        ConverterConfig::default()
+    );
+}
+
+#[test]
+fn test_invalid_profiles_rejected() {
+    let typo = ConverterConfig::from_toml("[pipeline]\ndetect_langauge = true\n").unwrap_err();
+    assert!(matches!(typo, ConversionError::Config(_)));
+    assert!(typo.to_string().contains("detect_langauge"));
+
+    let pages = ConverterConfig::from_toml("[pdf]\nfirst_page = 4\nlast_page = 2\n").unwrap_err();
+    assert!(pages.to_string().contains("first_page"));
+
+    let temp = TempDir::new().unwrap();
+    let ini = temp.path().join("docling.ini");
+    fs::write(&ini, "").unwrap();
+    assert!(matches!(
+        ConverterConfig::from_file(&ini),
+        Err(ConversionError::Config(_))
+    ));
+    assert!(matches!(
+        DocumentConverter::from_config(temp.path().join("missing.toml")),
+        Err(ConversionError::FileNotFound(_))
+    ));
+}
+
+#[test]
+fn test_converter_from_config() {
+    let temp = TempDir::new().unwrap();
+    let path = temp.path().join("docling.toml");
+    fs::write(&path, PROFILE).unwrap();
+
+    let converter = DocumentConverter::from_config(&path).unwrap();
+    let error = converter
+        .convert_bytes(
+            b"# Title\n\nA long paragraph.".to_vec(),
+            "doc.md".to_string(),
+            InputFormat::Markdown,
+        )
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        ConversionError::LimitExceeded(ExceededLimit::OutputSize { max: 8, .. })
+    ));
+
+    assert!(converter
+        .convert_bytes(
+            b"Hello".to_vec(),
+            "doc.md".to_string(),
+            InputFormat::Markdown
+        )
+        .is_ok());
+}
+
+#[test]
+fn test_converter_chunker_from_config() {
+    let temp = TempDir::new().unwrap();
+    let path = temp.path().join("docling.toml");
+    fs::write(&path, "[chunking]\nchunker = \"fixed\"\nchunk_size = 20\n").unwrap();
+
+    let converter = DocumentConverter::from_config(&path).unwrap();
+    let result = converter
+        .convert_bytes(
+            b"alpha beta gamma delta epsilon zeta eta theta iota kappa".to_vec(),
+            "doc.md".to_string(),
+            InputFormat::Markdown,
+        )
+        .unwrap();
+    let chunks: Vec<_> = converter.chunk(result.document()).collect();
+    assert!(chunks.len() > 1);
+    assert!(chunks.iter().all(|chunk| chunk.text.len() <= 20));
+
+    fs::write(&path, "[chunking]\nchunker = \"bogus\"\n").unwrap();
+    assert!(matches!(
+        DocumentConverter::from_config(&path),
+        Err(ConversionError::Config(message)) if message.contains("chunking.chunker")
+    ));
+}
+
+#[test]
+fn test_cli_pdf_config() {
+    let parse = |args: &[&str]| {
+        let matches = CliArgs::command().try_get_matches_from(args).unwrap();
+        let mut cli = CliArgs::from_arg_matches(&matches).unwrap();
+        cli.apply_config(&ConverterConfig::from_toml(PROFILE).unwrap(), &matches)
+            .unwrap();
+        cli.pdf_config()
+    };
+
+    // The profile turns images off and keeps tables
+    let config = parse(&["docling-rs", "in.pdf"]);
+    assert!(config.enable_tables);
+    assert!(!config.enable_images);
+    assert!(!config.enable_ocr);
+    assert_eq!(config.password.as_deref(), Some("secret"));
+    assert_eq!(config.page_range, Some(1..5));
+
+    // Naming one kind extracts only that kind
+    let config = parse(&[
+        "docling-rs",
+        "in.pdf",
+        "--pdf-extract-images",
+        "--ocr-enabled",
+    ]);
+    assert!(!config.enable_tables);
+    assert!(config.enable_images);
+    assert!(config.enable_ocr);
+}
+
+#[test]
+fn test_merge_profiles() {
+    let user = ConverterConfig::from_toml(
+        "[pipeline]\ngroup_sections = true\n[pdf]\nocr = true\nocr_language = \"deu\"\n[output]\nformat = \"json\"\n",
+    )
+    .unwrap();
+    let project = ConverterConfig::from_toml(PROFILE).unwrap();
+    let config = user.merge(project);
+
+    // Set fields of the later profile win, the others are kept
+    assert_eq!(config.output.format.as_deref(), Some("jsonl"));
+    assert_eq!(config.pdf.ocr, Some(true));
+    assert_eq!(config.pdf.ocr_language.as_deref(), Some("deu"));
+    assert_eq!(config.pdf.images, Some(false));
+    assert!(config.pipeline.group_sections);
+    assert!(config.pipeline.detect_language);
+    assert_eq!(config.chunking.max_tokens, Some(256));
+
+    assert_eq!(
+        ConverterConfig::default().merge(ConverterConfig::from_toml(PROFILE).unwrap()),
+        ConverterConfig::from_toml(PROFILE).unwrap()
+    );
+}
+
+#[test]
+fn test_project_file_lookup() {
+    let temp = TempDir::new().unwrap();
+    let nested = temp.path().join("a").join("b");
+    fs::create_dir_all(&nested).unwrap();
+    assert_eq!(ConverterConfig::project_file(&nested), None);
+
+    fs::write(temp.path().join("a").join(".docling.toml"), "").unwrap();
+    assert_eq!(
+        ConverterConfig::project_file(&nested),
+        Some(temp.path().join("a").join(".docling.toml"))
+    );
+}
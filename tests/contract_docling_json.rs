@@ -0,0 +1,264 @@
+//! Contract tests for Python docling JSON interoperability
+
+use docling_rs::backend::{Backend, MarkdownBackend};
+use docling_rs::datamodel::table::MAX_ROW_SPAN;
+use docling_rs::datamodel::{
+    docling_json, DoclingDocument, DocumentNode, ImageRef, InputDocument, NodeType, PictureItem,
+    TableCell, TableData, TableRow,
+};
+use docling_rs::InputFormat;
+use serde_json::json;
+
+fn markdown(content: &str) -> DoclingDocument {
+    let input = InputDocument::from_bytes(
+        content.as_bytes().to_vec(),
+        "test.md",
+        InputFormat::Markdown,
+    );
+    MarkdownBackend::new().convert(&input).unwrap()
+}
+
+#[test]
+fn test_export_docling_schema() {
+    let doc = markdown("# Intro\n\nSome text.\n\n- one\n- two\n");
+    let json = docling_json::to_value(&doc);
+
+    assert_eq!(json["schema_name"], "DoclingDocument");
+    assert_eq!(json["version"], docling_json::SCHEMA_VERSION);
+    assert_eq!(json["body"]["children"], json!([{ "$ref": "#/texts/0" }]));
+
+    // The heading keeps its section content as children
+    let heading = &json["texts"][0];
+    assert_eq!(heading["label"], "section_header");
+    assert_eq!(heading["level"], 1);
+    assert_eq!(heading["parent"], json!({ "$ref": "#/body" }));
+    assert_eq!(
+        heading["children"],
+        json!([{ "$ref": "#/texts/1" }, { "$ref": "#/groups/0" }])
+    );
+
+    assert_eq!(json["texts"][1]["label"], "text");
+    assert_eq!(json["texts"][1]["text"], "Some text.");
+
+    let list = &json["groups"][0];
+    assert_eq!(list["label"], "list");
+    assert_eq!(list["parent"], json!({ "$ref": "#/texts/0" }));
+    assert_eq!(list["children"].as_array().unwrap().len(), 2);
+    assert_eq!(json["texts"][2]["label"], "list_item");
+    assert_eq!(json["texts"][2]["parent"], json!({ "$ref": "#/groups/0" }));
+}
+
+#[test]
+fn test_export_tables_pictures_and_provenance() {
+    let table = TableData::new()
+        .with_header_rows(1)
+        .with_row(TableRow::new(vec![
+            TableCell::new("Name"),
+            TableCell::new("Score").with_col_span(2),
+        ]))
+        .with_row(TableRow::new(vec![
+            TableCell::new("Ann"),
+            TableCell::new("1"),
+            TableCell::new("2"),
+        ]));
+
+    let mut doc = DoclingDocument::new("report.pdf");
+    let picture = doc.store_image(vec![0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A]);
+    doc.add_node(
+        DocumentNode::new(NodeType::Table, "Name | Score")
+            .with_table(table)
+            .with_caption("Table 1: Scores")
+            .with_page(2),
+    );
+    doc.add_node(DocumentNode::new(NodeType::Picture, "A chart").with_picture(picture));
+    doc.add_node(DocumentNode::new(NodeType::PageBreak, ""));
+
+    let json = docling_json::to_value(&doc);
+
+    let table = &json["tables"][0];
+    assert_eq!(table["data"]["num_rows"], 2);
+    assert_eq!(table["data"]["num_cols"], 3);
+    let merged = &table["data"]["table_cells"][1];
+    assert_eq!(merged["text"], "Score");
+    assert_eq!(merged["start_col_offset_idx"], 1);
+    assert_eq!(merged["end_col_offset_idx"], 3);
+    assert_eq!(merged["column_header"], true);
+    assert_eq!(table["data"]["table_cells"][2]["column_header"], false);
+
+    // Captions become caption texts referenced from the table
+    assert_eq!(table["captions"], json!([{ "$ref": "#/texts/0" }]));
+    assert_eq!(json["texts"][0]["label"], "caption");
+    assert_eq!(json["texts"][0]["text"], "Table 1: Scores");
+
+    assert_eq!(table["prov"][0]["page_no"], 2);
+    assert!(json["pages"].get("2").is_some());
+
+    let picture = &json["pictures"][0];
+    assert_eq!(picture["image"]["mimetype"], "image/png");
+    assert!(picture["image"]["uri"]
+        .as_str()
+        .unwrap()
+        .starts_with("data:image/png;base64,"));
+    assert_eq!(picture["annotations"][0]["text"], "A chart");
+
+    // Page breaks have no docling counterpart
+    assert_eq!(json["body"]["children"].as_array().unwrap().len(), 2);
+}
+
+#[test]
+fn test_import_docling_document() {
+    let json = json!({
+        "schema_name": "DoclingDocument",
+        "version": "1.3.0",
+        "name": "paper",
+        "furniture": { "self_ref": "#/furniture", "children": [{ "$ref": "#/texts/0" }] },
+        "body": {
+            "self_ref": "#/body",
+            "children": [
+                { "$ref": "#/texts/1" },
+                { "$ref": "#/texts/2" },
+                { "$ref": "#/groups/0" },
+                { "$ref": "#/tables/0" },
+                { "$ref": "#/pictures/0" }
+            ]
+        },
+        "groups": [{
+            "self_ref": "#/groups/0",
+            "label": "list",
+            "name": "list",
+            "children": [{ "$ref": "#/texts/3" }]
+        }],
+        "texts": [
+            { "self_ref": "#/texts/0", "label": "page_header", "content_layer": "furniture",
+              "orig": "Header", "text": "Header" },
+            { "self_ref": "#/texts/1", "label": "section_header", "level": 2,
+              "orig": "Methods", "text": "Methods",
+              "prov": [{ "page_no": 3, "bbox": { "l": 1.0, "t": 9.0, "r": 5.0, "b": 2.0,
+                         "coord_origin": "BOTTOMLEFT" }, "charspan": [0, 7] }] },
+            { "self_ref": "#/texts/2", "label": "text", "orig": "We measured.", "text": "We measured." },
+            { "self_ref": "#/texts/3", "label": "list_item", "orig": "Step", "text": "Step" },
+            { "self_ref": "#/texts/4", "label": "caption", "orig": "Results", "text": "Results" }
+        ],
+        "tables": [{
+            "self_ref": "#/tables/0",
+            "label": "table",
+            "captions": [{ "$ref": "#/texts/4" }],
+            "children": [{ "$ref": "#/texts/4" }],
+            "data": {
+                "num_rows": 2,
+                "num_cols": 2,
+                "table_cells": [
+                    { "text": "A", "start_row_offset_idx": 0, "end_row_offset_idx": 1,
+                      "start_col_offset_idx": 0, "end_col_offset_idx": 1, "column_header": true },
+                    { "text": "B", "start_row_offset_idx": 0, "end_row_offset_idx": 1,
+                      "start_col_offset_idx": 1, "end_col_offset_idx": 2, "column_header": true },
+                    { "text": "wide", "start_row_offset_idx": 1, "end_row_offset_idx": 2,
+                      "start_col_offset_idx": 0, "end_col_offset_idx": 2 }
+                ]
+            }
+        }],
+        "pictures": [{
+            "self_ref": "#/pictures/0",
+            "label": "picture",
+            "image": { "mimetype": "image/png", "dpi": 72, "size": { "width": 4, "height": 3 },
+                       "uri": "data:image/png;base64,AQID" },
+            "annotations": [{ "kind": "description", "text": "A plot", "provenance": "vlm" }]
+        }],
+        "pages": { "3": { "size": { "width": 612, "height": 792 }, "page_no": 3 } }
+    });
+
+    let doc = docling_json::from_value(&json).unwrap();
+    assert_eq!(doc.name(), "paper");
+    assert_eq!(doc.metadata().page_count(), Some(1));
+
+    let nodes: Vec<_> = doc
+        .nodes()
+        .iter()
+        .map(|n| (n.node_type(), n.text_content().unwrap()))
+        .collect();
+    assert_eq!(nodes[0], (NodeType::Heading, "Methods"));
+    assert_eq!(nodes[1], (NodeType::Paragraph, "We measured."));
+    assert_eq!(nodes[2], (NodeType::List, ""));
+    assert_eq!(nodes[3], (NodeType::ListItem, "Step"));
+    assert_eq!(nodes[4].0, NodeType::Table);
+    assert_eq!(nodes[5], (NodeType::Picture, "A plot"));
+    assert_eq!(nodes.len(), 6, "furniture and folded captions are skipped");
+
+    let heading = &doc.nodes()[0];
+    assert_eq!(heading.level(), Some(2));
+    assert_eq!(heading.page(), Some(3));
+    assert_eq!(heading.bbox().map(|b| b.top), Some(9.0));
+
+    let table = &doc.nodes()[4];
+    assert_eq!(table.caption(), Some("Results"));
+    let data = table.table().unwrap();
+    assert_eq!(data.num_header_rows(), 1);
+    assert_eq!(data.to_vec(), vec![vec!["A", "B"], vec!["wide", "wide"]]);
+
+    let picture = doc.nodes()[5].picture().unwrap();
+    assert!(matches!(picture.image(), ImageRef::Stored { .. }));
+    assert_eq!(doc.image_data(picture), Some([1u8, 2, 3].as_slice()));
+    assert_eq!(picture.width(), Some(4));
+}
+
+#[test]
+fn test_docling_json_round_trip() {
+    let mut doc = markdown("# Intro\n\nText.\n\n## Part\n\n- item\n\n![Logo](logo.png)\n");
+    doc.add_node(
+        DocumentNode::new(NodeType::Table, "X")
+            .with_table(TableData::new().with_row(TableRow::new(vec![TableCell::new("X")]))),
+    );
+
+    let restored = docling_json::from_str(&docling_json::to_string(&doc).unwrap()).unwrap();
+
+    let shape = |doc: &DoclingDocument| {
+        doc.depth_first()
+            .map(|e| (e.depth, e.node.node_type(), e.node.level()))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(shape(&restored), shape(&doc));
+    assert_eq!(
+        restored.nodes()[5].picture(),
+        Some(&PictureItem::file("logo.png").with_mimetype("image/png"))
+    );
+    assert_eq!(restored.nodes()[6].table(), doc.nodes()[6].table());
+}
+
+#[test]
+fn test_import_rejects_other_schemas() {
+    assert!(docling_json::from_value(&json!({ "schema_name": "Other", "body": {} })).is_err());
+    assert!(docling_json::from_value(&json!({ "schema_name": "DoclingDocument" })).is_err());
+}
+
+#[test]
+fn test_import_table_with_malformed_offsets() {
+    let cell = |text: &str, row: u64, end_row: u64, col: u64| {
+        json!({ "text": text, "start_row_offset_idx": row, "end_row_offset_idx": end_row,
+                "start_col_offset_idx": col, "end_col_offset_idx": col + 1 })
+    };
+    let json = json!({
+        "schema_name": "DoclingDocument",
+        "version": "1.3.0",
+        "name": "broken",
+        "body": { "self_ref": "#/body", "children": [{ "$ref": "#/tables/0" }] },
+        "tables": [{
+            "self_ref": "#/tables/0",
+            "label": "table",
+            "data": { "table_cells": [
+                cell("tall", 0, 3, 0),
+                cell("b", 0, 1, 1),
+                cell("c", 1, 2, 1),
+                // A row far past the others, spanning to the end of u64
+                cell("far", 1 << 40, u64::MAX, 0),
+            ] }
+        }]
+    });
+
+    let doc = docling_json::from_value(&json).unwrap();
+    let data = doc.nodes()[0].table().unwrap();
+    // Row 2 is kept while "tall" covers it; the rows before "far" are dropped
+    assert_eq!(data.rows().len(), 4);
+    assert_eq!(data.rows()[2].cells().len(), 0);
+    assert_eq!(data.rows()[3].cells()[0].content(), "far");
+    assert_eq!(data.rows()[3].cells()[0].row_span(), MAX_ROW_SPAN);
+}
@@ -7,8 +7,9 @@
 //! - Provides access to document structure
 
 use docling_rs::datamodel::{
-    ConversionMetrics, ConversionResult, ConversionStatus, DoclingDocument,
+    ConversionMetrics, ConversionResult, ConversionStatus, DoclingDocument, TimedStage,
 };
+use std::time::Duration;
 
 #[test]
 fn test_doclingdocument_new() {
@@ -57,4 +58,247 @@ fn test_doclingdocument_metrics() {
 
     assert_eq!(metrics.total_pages(), 10);
     assert_eq!(metrics.processing_time_ms(), 250);
+
+    let mut metrics = metrics
+        .with_node_count(12)
+        .with_characters(4051)
+        .with_warning_count(1);
+    metrics.add_stage_time(TimedStage::Ocr, Duration::from_millis(30));
+    metrics.add_stage_time(TimedStage::Ocr, Duration::from_millis(12));
+    assert_eq!(metrics.stages().get(TimedStage::Ocr), Some(42));
+    assert_eq!(metrics.stages().get(TimedStage::Tables), None);
+    assert_eq!(
+        metrics.to_string(),
+        "pages: 10, nodes: 12, characters: 4051, warnings: 1, time: 250 ms (ocr: 42 ms)"
+    );
+}
+
+#[test]
+fn test_doclingdocument_node_tree() {
+    use docling_rs::datamodel::{DocumentNode, NodeType};
+
+    let mut doc = DoclingDocument::new("test.md");
+    let list = doc.add_node(DocumentNode::new(NodeType::List, ""));
+    let first = doc
+        .add_child(list, DocumentNode::new(NodeType::ListItem, "one"))
+        .unwrap();
+    doc.add_child(list, DocumentNode::new(NodeType::ListItem, "two"))
+        .unwrap();
+    doc.add_child(first, DocumentNode::new(NodeType::Paragraph, "nested"))
+        .unwrap();
+    doc.add_node(DocumentNode::new(NodeType::Paragraph, "after"));
+
+    // Flat access is in insertion order
+    assert_eq!(doc.nodes().len(), 5);
+    assert_eq!(doc.roots().count(), 2);
+
+    let children: Vec<_> = doc
+        .children(list)
+        .map(|(_, n)| n.text_content().unwrap())
+        .collect();
+    assert_eq!(children, vec!["one", "two"]);
+    assert_eq!(doc.parent(first).map(|(id, _)| id), Some(list));
+    assert!(doc.parent(list).is_none());
+
+    // Depth-first order visits "nested" before "two"
+    let order: Vec<_> = doc
+        .depth_first()
+        .map(|e| (e.depth, e.node.text_content().unwrap()))
+        .collect();
+    assert_eq!(
+        order,
+        vec![(0, ""), (1, "one"), (2, "nested"), (1, "two"), (0, "after")]
+    );
+
+    // Tree links survive serialization
+    let json = serde_json::to_string(&doc).unwrap();
+    let restored: DoclingDocument = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored.depth_first().count(), 5);
+    assert_eq!(restored.children(list).count(), 2);
+}
+
+#[test]
+fn test_doclingdocument_node_references() {
+    use docling_rs::datamodel::{DocumentNode, NodeType, RefItem};
+
+    let mut doc = DoclingDocument::new("test.md");
+    let caption = doc.add_node(DocumentNode::new(NodeType::Paragraph, "Table 1: Scores"));
+    let table = doc.add_node(
+        DocumentNode::new(NodeType::Table, "A | 0.9").with_reference(RefItem::new(caption)),
+    );
+
+    let table_node = doc.node(table).unwrap();
+    assert_eq!(table_node.id(), Some(table));
+    assert_eq!(
+        table_node.self_ref().map(|r| r.as_str().to_string()),
+        Some("#/nodes/1".to_string())
+    );
+    assert!(DocumentNode::new(NodeType::Text, "detached").id().is_none());
+
+    // References serialize as JSON pointers and resolve after a round trip
+    let json = serde_json::to_value(&doc).unwrap();
+    assert_eq!(
+        json["nodes"][1]["references"][0],
+        serde_json::json!({ "$ref": "#/nodes/0" })
+    );
+
+    let restored: DoclingDocument = serde_json::from_value(json).unwrap();
+    let reference = &restored.node(table).unwrap().references()[0];
+    assert_eq!(reference.node_id(), Some(caption));
+    assert_eq!(
+        restored.resolve(reference).and_then(|n| n.text_content()),
+        Some("Table 1: Scores")
+    );
+}
+
+#[test]
+fn test_doclingdocument_typed_metadata() {
+    use chrono::{TimeZone, Utc};
+    use docling_rs::datamodel::DocumentMetadata;
+    use docling_rs::InputFormat;
+
+    let created = Utc.with_ymd_and_hms(2024, 1, 2, 3, 4, 5).unwrap();
+    let doc = DoclingDocument::new("report.pdf")
+        .with_document_metadata(
+            DocumentMetadata::new()
+                .with_title("Report")
+                .with_author("A. Author")
+                .with_created(created)
+                .with_format(InputFormat::PDF)
+                .with_page_count(12),
+        )
+        .with_metadata("image_count", 3);
+
+    let json = serde_json::to_value(&doc).unwrap();
+    assert_eq!(json["metadata"]["title"], "Report");
+    assert_eq!(json["metadata"]["page_count"], 12);
+    assert_eq!(json["metadata"]["extra"]["image_count"], 3);
+
+    let restored: DoclingDocument = serde_json::from_value(json).unwrap();
+    assert_eq!(restored.metadata(), doc.metadata());
+    assert_eq!(restored.metadata().created(), Some(created));
+    assert_eq!(restored.metadata().authors(), ["A. Author"]);
+}
+
+#[test]
+fn test_doclingdocument_image_store() {
+    use docling_rs::datamodel::{DocumentNode, ImageRef, NodeType, PictureItem};
+
+    // 1x1 PNG header is enough for MIME detection
+    let png = vec![0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A, 0, 0, 0, 0];
+
+    let mut doc = DoclingDocument::new("slides.pdf");
+    let first = doc.store_image(png.clone());
+    let second = doc.store_image(png.clone());
+    assert_eq!(first, second);
+    assert_eq!(doc.images().len(), 1, "identical images are stored once");
+    assert_eq!(first.mimetype(), Some("image/png"));
+
+    doc.add_node(DocumentNode::new(NodeType::Picture, "Logo").with_picture(first));
+    doc.add_node(DocumentNode::new(NodeType::Picture, "Logo again").with_picture(second));
+    doc.add_node(
+        DocumentNode::new(NodeType::Picture, "Photo")
+            .with_picture(PictureItem::file("photos/cat.jpg")),
+    );
+    doc.add_node(
+        DocumentNode::new(NodeType::Picture, "Icon")
+            .with_picture(PictureItem::inline(vec![1, 2, 3]).with_size(1, 3)),
+    );
+
+    let json = serde_json::to_value(&doc).unwrap();
+    assert_eq!(json["nodes"][0]["item"]["picture"]["kind"], "stored");
+    assert_eq!(json["nodes"][2]["item"]["picture"]["uri"], "photos/cat.jpg");
+    assert_eq!(json["nodes"][3]["item"]["picture"]["data"], "AQID");
+    assert_eq!(json["images"].as_object().unwrap().len(), 1);
+
+    let restored: DoclingDocument = serde_json::from_value(json).unwrap();
+    let picture = |i: usize| restored.nodes()[i].picture().unwrap();
+    assert_eq!(restored.image_data(picture(1)), Some(png.as_slice()));
+    assert_eq!(restored.image_data(picture(2)), None);
+    assert_eq!(
+        restored.image_data(picture(3)),
+        Some([1u8, 2, 3].as_slice())
+    );
+    assert!(matches!(picture(2).image(), ImageRef::File { .. }));
+    assert_eq!(picture(3).width(), Some(1));
+
+    // Documents without images don't serialize a store
+    let json = serde_json::to_value(DoclingDocument::new("empty.md")).unwrap();
+    assert!(json.get("images").is_none());
+}
+
+#[test]
+fn test_doclingdocument_json_roundtrip() {
+    use docling_rs::datamodel::{
+        BoundingBox, DocumentMetadata, DocumentNode, Formatting, NodeType, RefItem, SourcePosition,
+        TableCell, TableData, TableRow, TextSpan,
+    };
+
+    let mut doc = DoclingDocument::new("report.pdf")
+        .with_document_metadata(DocumentMetadata::new().with_title("Report"));
+    let heading = doc.add_node(
+        DocumentNode::new(NodeType::Heading, "Results")
+            .with_level(1)
+            .with_page(2)
+            .with_bbox(BoundingBox::new(72.0, 700.5, 300.25, 680.0))
+            .with_position(SourcePosition::new(0, 9, 1, 1)),
+    );
+    let table = TableData::new()
+        .with_row(TableRow::new(vec![
+            TableCell::new("Name"),
+            TableCell::new("Score"),
+        ]))
+        .with_row(TableRow::new(vec![
+            TableCell::new("Ann").with_row_span(2),
+            TableCell::new("0.9"),
+        ]))
+        .with_row(TableRow::new(vec![TableCell::new("0.8")]))
+        .with_header_rows(1);
+    let table = doc
+        .add_child(
+            heading,
+            DocumentNode::new(NodeType::Table, table.to_markdown())
+                .with_table(table)
+                .with_caption("Scores"),
+        )
+        .unwrap();
+    doc.add_child(
+        heading,
+        DocumentNode::new(NodeType::Paragraph, "See the table.")
+            .with_span(TextSpan::new(8, 13, Formatting::bold()))
+            .with_reference(RefItem::new(table)),
+    );
+    let picture = doc.store_image(vec![0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A]);
+    doc.add_node(DocumentNode::new(NodeType::Picture, "Chart").with_picture(picture));
+
+    let json = doc.to_json().unwrap();
+    let restored = DoclingDocument::from_json(&json).unwrap();
+    assert_eq!(restored, doc);
+    assert_eq!(restored.to_json().unwrap(), json);
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("report.json");
+    doc.to_file(&path).unwrap();
+    assert_eq!(DoclingDocument::from_file(&path).unwrap(), doc);
+}
+
+#[test]
+fn test_doclingdocument_load_errors() {
+    use docling_rs::ConversionError;
+
+    assert!(matches!(
+        DoclingDocument::from_file("missing/document.json"),
+        Err(ConversionError::FileNotFound(_))
+    ));
+    assert!(matches!(
+        DoclingDocument::from_json("not json"),
+        Err(ConversionError::Serialization(_))
+    ));
+
+    // Structurally broken documents are rejected
+    let json = r#"{"name": "broken", "nodes": [{"id": 0, "item": {"node_type": "Paragraph", "text_content": "x", "position": null}, "children": [5]}]}"#;
+    assert!(matches!(
+        DoclingDocument::from_json(json),
+        Err(ConversionError::Schema(_))
+    ));
 }
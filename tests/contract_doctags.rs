@@ -0,0 +1,118 @@
+//! Contract tests for DocTags serialization
+
+use docling_rs::backend::{Backend, MarkdownBackend};
+use docling_rs::datamodel::{
+    doctags, DoclingDocument, DocumentNode, InputDocument, NodeType, TableCell, TableData, TableRow,
+};
+use docling_rs::InputFormat;
+
+fn scores() -> TableData {
+    TableData::new()
+        .with_header_rows(1)
+        .with_row(TableRow::new(vec![
+            TableCell::new("Name"),
+            TableCell::new("Score").with_col_span(2),
+        ]))
+        .with_row(TableRow::new(vec![
+            TableCell::new("Ann").with_row_span(2),
+            TableCell::new("1"),
+            TableCell::new(""),
+        ]))
+        .with_row(TableRow::new(vec![
+            TableCell::new("3"),
+            TableCell::new("4"),
+        ]))
+}
+
+#[test]
+fn test_doctags_export() {
+    let input = InputDocument::from_bytes(
+        b"# Intro\n\nSome text.\n\n- one\n- two\n\n$$\nx^2\n$$\n".to_vec(),
+        "test.md",
+        InputFormat::Markdown,
+    );
+    let mut doc = MarkdownBackend::new().convert(&input).unwrap();
+    doc.add_node(DocumentNode::new(NodeType::PageBreak, ""));
+    doc.add_node(
+        DocumentNode::new(NodeType::Table, "Name | Score")
+            .with_table(scores())
+            .with_caption("Scores"),
+    );
+
+    assert_eq!(
+        doctags::to_string(&doc),
+        "<doctag><section_header_level_1>Intro</section_header_level_1>\n\
+         <text>Some text.</text>\n\
+         <unordered_list><list_item>one</list_item>\n\
+         <list_item>two</list_item>\n\
+         </unordered_list>\n\
+         <formula>x^2</formula>\n\
+         <page_break>\n\
+         <otsl><ched>Name<ched>Score<lcel><nl>\
+         <fcel>Ann<fcel>1<ecel><nl>\
+         <ucel><fcel>3<fcel>4<nl>\
+         <caption>Scores</caption></otsl>\n\
+         </doctag>"
+    );
+}
+
+#[test]
+fn test_doctags_parse() {
+    let doctags = "<doctag><title>Report</title>\n\
+        <section_header_level_2><loc_10><loc_20><loc_30><loc_40>Results</section_header_level_2>\n\
+        <text><loc_1><loc_2><loc_3><loc_4>Scores improved.</text>\n\
+        <unordered_list><list_item>First</list_item><list_item>Second</list_item></unordered_list>\n\
+        <picture><loc_5><loc_6><loc_7><loc_8><caption>A chart</caption></picture>\n\
+        <page_break>\n\
+        <code>fn main() {}</code>\n\
+        </doctag>";
+
+    let doc = doctags::from_str(doctags, "report").unwrap();
+    let nodes: Vec<_> = doc
+        .nodes()
+        .iter()
+        .map(|n| (n.node_type(), n.text_content().unwrap()))
+        .collect();
+    assert_eq!(
+        nodes,
+        vec![
+            (NodeType::Title, "Report"),
+            (NodeType::Heading, "Results"),
+            (NodeType::Paragraph, "Scores improved."),
+            (NodeType::List, ""),
+            (NodeType::ListItem, "First"),
+            (NodeType::ListItem, "Second"),
+            (NodeType::Picture, ""),
+            (NodeType::PageBreak, ""),
+            (NodeType::CodeBlock, "fn main() {}"),
+        ]
+    );
+    assert_eq!(doc.nodes()[1].level(), Some(2));
+    assert_eq!(doc.nodes()[4].parent(), doc.nodes()[3].id());
+    assert_eq!(doc.nodes()[6].caption(), Some("A chart"));
+}
+
+#[test]
+fn test_doctags_table_round_trip() {
+    let mut doc = DoclingDocument::new("t");
+    doc.add_node(
+        DocumentNode::new(NodeType::Table, "")
+            .with_table(scores())
+            .with_caption("Scores"),
+    );
+
+    let restored = doctags::from_str(&doctags::to_string(&doc), "t").unwrap();
+    let table = &restored.nodes()[0];
+    assert_eq!(table.caption(), Some("Scores"));
+    assert_eq!(table.table(), Some(&scores()));
+}
+
+#[test]
+fn test_doctags_parse_errors() {
+    assert!(doctags::from_str("<doctag><text>unterminated</doctag>", "x").is_err());
+    assert!(doctags::from_str("<doctag><text", "x").is_err());
+
+    // Unknown standalone tags are ignored
+    let doc = doctags::from_str("<doctag><checkbox><text>ok</text></doctag>", "x").unwrap();
+    assert_eq!(doc.nodes().len(), 1);
+}
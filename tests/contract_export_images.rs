@@ -0,0 +1,79 @@
+//! Contract tests for exporting picture images to files
+
+use docling_rs::cli::output;
+use docling_rs::datamodel::{DocumentNode, ImageRef, NodeType, PictureItem};
+use docling_rs::DoclingDocument;
+
+const PNG: &[u8] = &[0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a, 0, 0, 0, 0];
+
+#[test]
+fn test_export_images_links_pictures() {
+    let mut doc = DoclingDocument::new("report.pdf");
+    let stored = doc.store_image(PNG.to_vec()).with_size(4, 3);
+    doc.add_node(DocumentNode::new(NodeType::Picture, "Chart").with_picture(stored.clone()));
+    doc.add_node(DocumentNode::new(NodeType::Picture, "Chart again").with_picture(stored));
+    doc.add_node(
+        DocumentNode::new(NodeType::Picture, "Raw")
+            .with_picture(PictureItem::inline(vec![1, 2, 3])),
+    );
+    doc.add_node(
+        DocumentNode::new(NodeType::Picture, "Logo").with_picture(PictureItem::file("logo.svg")),
+    );
+
+    let dir = tempfile::tempdir().unwrap();
+    let written = doc
+        .export_images(&dir.path().join("images"), "report", "images")
+        .unwrap();
+
+    let names: Vec<_> = written
+        .iter()
+        .map(|p| p.file_name().unwrap().to_str().unwrap())
+        .collect();
+    assert_eq!(names, ["report-image-1.png", "report-image-2.bin"]);
+    assert_eq!(std::fs::read(&written[0]).unwrap(), PNG);
+    assert!(doc.images().is_empty(), "exported images leave the store");
+
+    let uris: Vec<_> = doc
+        .nodes()
+        .iter()
+        .map(|n| match n.picture().unwrap().image() {
+            ImageRef::File { uri } => uri.as_str(),
+            _ => "",
+        })
+        .collect();
+    assert_eq!(
+        uris,
+        [
+            "images/report-image-1.png",
+            "images/report-image-1.png",
+            "images/report-image-2.bin",
+            "logo.svg"
+        ]
+    );
+    let picture = doc.nodes()[0].picture().unwrap();
+    assert_eq!(picture.mimetype(), Some("image/png"));
+    assert_eq!(picture.width(), Some(4));
+
+    let markdown = output::to_markdown(&doc);
+    assert!(markdown.contains("![Chart](images/report-image-1.png)"));
+    assert!(output::to_html(&doc).contains("<img src=\"images/report-image-1.png\" alt=\"Chart\">"));
+}
+
+#[test]
+fn test_embed_images_as_data_uris() {
+    let mut doc = DoclingDocument::new("report.pdf");
+    let stored = doc.store_image(PNG.to_vec());
+    doc.add_node(DocumentNode::new(NodeType::Picture, "Chart").with_picture(stored));
+    doc.add_node(
+        DocumentNode::new(NodeType::Picture, "Raw")
+            .with_picture(PictureItem::inline(vec![1, 2, 3]).with_mimetype("image/x-custom")),
+    );
+
+    doc.embed_images();
+    assert!(doc.images().is_empty());
+    let markdown = output::to_markdown(&doc);
+    assert!(markdown.contains("![Chart](data:image/png;base64,iVBORw0KGgoAAAAA)"));
+    assert!(
+        output::to_html(&doc).contains("<img src=\"data:image/x-custom;base64,AQID\" alt=\"Raw\">")
+    );
+}
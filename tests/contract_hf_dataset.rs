@@ -0,0 +1,167 @@
+//! Contract tests for Hugging Face datasets export of chunks
+
+#[cfg(feature = "parquet")]
+mod hf_dataset_export {
+    use std::fs::{self, File};
+    use std::path::Path;
+
+    use ::parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+    use assert_cmd::Command;
+    use docling_rs::chunking::hf_dataset::DatasetWriter;
+    use docling_rs::chunking::{ApproxTokenizer, BaseChunker, HierarchicalChunker};
+    use docling_rs::datamodel::{DocumentNode, NodeType};
+    use docling_rs::DoclingDocument;
+    use serde_json::Value;
+
+    fn sample(name: &str) -> DoclingDocument {
+        let mut doc = DoclingDocument::new(name);
+        let heading = doc.add_node(DocumentNode::new(NodeType::Heading, "Results").with_level(1));
+        doc.add_child(
+            heading,
+            DocumentNode::new(NodeType::Paragraph, "Scores improved a lot."),
+        );
+        doc.add_child(
+            heading,
+            DocumentNode::new(NodeType::Paragraph, "Costs went down."),
+        );
+        doc
+    }
+
+    fn write(dir: &Path, split: &str, docs: &[DoclingDocument]) -> usize {
+        let chunker = HierarchicalChunker::new();
+        let mut dataset = DatasetWriter::create(dir)
+            .unwrap()
+            .with_split(split)
+            .with_shard_rows(2);
+        for doc in docs {
+            dataset
+                .write_chunks(chunker.chunk(doc), &chunker, &ApproxTokenizer::new())
+                .unwrap();
+        }
+        dataset.finish().unwrap()
+    }
+
+    fn shards(dir: &Path) -> Vec<String> {
+        let mut names: Vec<_> = fs::read_dir(dir.join("data"))
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+        names
+    }
+
+    fn infos(dir: &Path) -> Value {
+        serde_json::from_slice(&fs::read(dir.join("dataset_infos.json")).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_dataset_layout() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let dir = temp.path().join("corpus");
+        let rows = write(&dir, "train", &[sample("a.md"), sample("b.md")]);
+        assert_eq!(rows, 4);
+
+        assert_eq!(
+            shards(&dir),
+            [
+                "train-00000-of-00002.parquet",
+                "train-00001-of-00002.parquet"
+            ]
+        );
+        let reader = ParquetRecordBatchReaderBuilder::try_new(
+            File::open(dir.join("data").join(shards(&dir)[0].as_str())).unwrap(),
+        )
+        .unwrap();
+        let metadata: Value =
+            serde_json::from_str(&reader.schema().metadata()["huggingface"]).unwrap();
+        assert_eq!(
+            metadata["info"]["features"]["headings"]["_type"],
+            "Sequence"
+        );
+        let batches: Vec<_> = reader.build().unwrap().map(Result::unwrap).collect();
+        assert_eq!(batches.iter().map(|b| b.num_rows()).sum::<usize>(), 2);
+
+        let infos = infos(&dir);
+        let info = &infos["default"];
+        assert_eq!(info["splits"]["train"]["num_examples"], 4);
+        assert!(info["splits"]["train"]["num_bytes"].as_u64().unwrap() > 0);
+        assert_eq!(info["features"]["page"]["dtype"], "uint32");
+        assert!(info["download_size"].as_u64().unwrap() > 0);
+    }
+
+    #[test]
+    fn test_dataset_splits() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let dir = temp.path().join("corpus");
+        write(&dir, "train", &[sample("a.md"), sample("b.md")]);
+        write(&dir, "test", &[sample("c.md")]);
+        // Writing a split again replaces its shards only
+        write(&dir, "train", &[sample("a.md")]);
+
+        assert_eq!(
+            shards(&dir),
+            [
+                "test-00000-of-00001.parquet",
+                "train-00000-of-00001.parquet"
+            ]
+        );
+        let infos = infos(&dir);
+        assert_eq!(infos["default"]["splits"]["train"]["num_examples"], 2);
+        assert_eq!(infos["default"]["splits"]["test"]["num_examples"], 2);
+    }
+
+    #[test]
+    fn test_dataset_refuses_unrelated_directory() {
+        let temp = tempfile::TempDir::new().unwrap();
+        fs::write(temp.path().join("notes.txt"), "keep me").unwrap();
+        assert!(DatasetWriter::create(temp.path()).is_err());
+    }
+
+    #[test]
+    fn test_cli_dataset() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let docs = temp.path().join("docs");
+        fs::create_dir(&docs).unwrap();
+        fs::write(docs.join("a.md"), "# Alpha\n\nFirst body.\n").unwrap();
+        fs::write(docs.join("b.md"), "# Beta\n\nSecond body.\n").unwrap();
+        let dataset = temp.path().join("dataset");
+
+        Command::cargo_bin("docling-rs")
+            .unwrap()
+            .arg(&docs)
+            .arg("--dataset")
+            .arg(&dataset)
+            .arg("--dataset-split")
+            .arg("validation")
+            .arg("--quiet")
+            .assert()
+            .success();
+
+        assert_eq!(shards(&dataset), ["validation-00000-of-00001.parquet"]);
+        let infos = infos(&dataset);
+        let rows = infos["default"]["splits"]["validation"]["num_examples"]
+            .as_u64()
+            .unwrap();
+        assert!(rows >= 2, "one row per chunk of both documents");
+    }
+}
+
+#[cfg(not(feature = "parquet"))]
+#[test]
+fn test_dataset_requires_feature() {
+    use assert_cmd::Command;
+    use predicates::prelude::*;
+
+    let temp = tempfile::TempDir::new().unwrap();
+    let input = temp.path().join("doc.md");
+    std::fs::write(&input, "# Test\n\nBody.\n").unwrap();
+
+    Command::cargo_bin("docling-rs")
+        .unwrap()
+        .arg(&input)
+        .arg("--dataset")
+        .arg(temp.path().join("dataset"))
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("parquet"));
+}
@@ -0,0 +1,172 @@
+//! Contract tests for HTML export
+
+use docling_rs::backend::{Backend, MarkdownBackend};
+use docling_rs::cli::output;
+use docling_rs::datamodel::{
+    text, DocumentNode, Formatting, InputDocument, NodeType, PictureItem, TableCell, TableData,
+    TableRow, TextSpan,
+};
+use docling_rs::{DoclingDocument, InputFormat};
+
+#[test]
+fn test_html_export_structure() {
+    let mut doc = DoclingDocument::new("report.md");
+    doc.add_node(DocumentNode::new(NodeType::Title, "Q3 <Report>"));
+    let heading = doc.add_node(DocumentNode::new(NodeType::Heading, "Results").with_level(1));
+    doc.add_child(
+        heading,
+        DocumentNode::new(NodeType::Paragraph, "Revenue grew & costs fell.")
+            .with_span(TextSpan::new(0, 7, Formatting::bold())),
+    );
+    let list = doc
+        .add_child(heading, DocumentNode::new(NodeType::List, ""))
+        .unwrap();
+    doc.add_child(list, DocumentNode::new(NodeType::ListItem, "- first"));
+    doc.add_child(list, DocumentNode::new(NodeType::ListItem, "second"));
+    doc.add_child(
+        heading,
+        DocumentNode::new(NodeType::CodeBlock, "if a < b {}"),
+    );
+    doc.add_child(
+        heading,
+        DocumentNode::new(NodeType::Picture, "A chart")
+            .with_picture(PictureItem::file("chart.png"))
+            .with_caption("Figure 1"),
+    );
+    doc.add_node(DocumentNode::new(NodeType::PageBreak, ""));
+
+    let html = output::to_html(&doc);
+    assert!(html.starts_with("<!DOCTYPE html>\n<html>"));
+    assert!(html.contains("<title>Q3 &lt;Report&gt;</title>"));
+    assert!(html.contains("<h1>Q3 &lt;Report&gt;</h1>\n<h2>Results</h2>\n"));
+    assert!(html.contains("<p><strong>Revenue</strong> grew &amp; costs fell.</p>"));
+    assert!(html.contains("<ul>\n<li>first</li>\n<li>second</li>\n</ul>\n"));
+    assert!(html.contains("<pre><code>if a &lt; b {}</code></pre>"));
+    assert!(html.contains(
+        "<figure>\n<img src=\"chart.png\" alt=\"A chart\">\n<figcaption>Figure 1</figcaption>\n</figure>"
+    ));
+    assert!(html.contains("<hr class=\"page-break\">"));
+    assert!(html.ends_with("</body>\n</html>\n"));
+}
+
+#[test]
+fn test_html_export_tables() {
+    let table = TableData::new()
+        .with_row(TableRow::new(vec![
+            TableCell::new("Name"),
+            TableCell::new("Score").with_col_span(2),
+        ]))
+        .with_row(TableRow::new(vec![
+            TableCell::new("Ann").with_row_span(2),
+            TableCell::new("1"),
+            TableCell::new("a|b"),
+        ]))
+        .with_header_rows(1);
+    assert_eq!(
+        table.to_html(),
+        "<table>\n\
+         <thead>\n<tr><th>Name</th><th colspan=\"2\">Score</th></tr>\n</thead>\n\
+         <tbody>\n<tr><td rowspan=\"2\">Ann</td><td>1</td><td>a|b</td></tr>\n</tbody>\n\
+         </table>\n"
+    );
+
+    let mut doc = DoclingDocument::new("t");
+    doc.add_node(
+        DocumentNode::new(NodeType::Table, table.to_markdown())
+            .with_table(table)
+            .with_caption("Scores"),
+    );
+    let html = output::to_html(&doc);
+    assert!(
+        html.contains("<h1>t</h1>"),
+        "the name stands in for a missing title"
+    );
+    assert!(html.contains("<table>\n<caption>Scores</caption>\n<thead>"));
+}
+
+#[test]
+fn test_html_export_sections_and_language() {
+    let content = "# Intro\n\nHello.\n\n## Details\n\nMore.\n";
+    let input =
+        InputDocument::from_bytes(content.as_bytes().to_vec(), "a.md", InputFormat::Markdown);
+    let doc = MarkdownBackend::new().convert(&input).unwrap();
+    let metadata = doc.metadata().clone().with_language("eng");
+    let doc = doc.with_document_metadata(metadata).group_sections();
+
+    let html = output::to_html(&doc);
+    assert!(html.contains("<html lang=\"en\">"));
+    assert!(html.contains(
+        "<section>\n<h2>Intro</h2>\n<p>Hello.</p>\n<section>\n<h3>Details</h3>\n<p>More.</p>\n</section>\n</section>\n"
+    ));
+}
+
+#[test]
+fn test_html_export_from_markdown() {
+    let content = "Read the [guide](https://x.test/guide) **first**, *then* ~~not~~ this.\n\n\
+                   1. Install\n   - from `crates.io`\n2. Run\n\n> Quoted.\n";
+    let input =
+        InputDocument::from_bytes(content.as_bytes().to_vec(), "a.md", InputFormat::Markdown);
+    let doc = MarkdownBackend::new().convert(&input).unwrap();
+
+    let html = output::to_html(&doc);
+    assert!(html.contains(
+        "<p>Read the <a href=\"https://x.test/guide\">guide</a> <strong>first</strong>, \
+         <em>then</em> <del>not</del> this.</p>\n"
+    ));
+    // Nested lists sit inside their item
+    assert!(html.contains(
+        "<ol>\n<li>Install\n<ul>\n<li>from <code>crates.io</code></li>\n</ul>\n</li>\n\
+         <li>Run</li>\n</ol>\n"
+    ));
+    assert!(html.contains("<blockquote>\n<p>Quoted.</p>\n</blockquote>\n"));
+    assert!(!html.contains("**") && !html.contains("]("));
+}
+
+#[test]
+fn test_inline_html() {
+    let spans = [
+        TextSpan::new(0, 4, Formatting::link("https://x.test/?a=1&b=\"2\"")),
+        TextSpan::new(5, 8, Formatting::strikethrough()),
+    ];
+    assert_eq!(
+        text::to_html("docs old <new>", &spans),
+        "<a href=\"https://x.test/?a=1&amp;b=&quot;2&quot;\">docs</a> <del>old</del> &lt;new&gt;"
+    );
+}
+
+#[test]
+fn test_unsafe_urls_render_as_text() {
+    for url in [
+        "javascript:alert(1)",
+        " JavaScript:x",
+        "java\tscript:x",
+        "data:text/html,x",
+    ] {
+        assert!(!text::is_safe_url(url), "{:?}", url);
+        let spans = [TextSpan::new(0, 5, Formatting::link(url))];
+        assert_eq!(text::to_html("click", &spans), "click");
+        assert_eq!(text::to_markdown("click", &spans), "click");
+    }
+    for url in [
+        "https://x.test",
+        "HTTP://x.test",
+        "mailto:a@x.test",
+        "../a.html",
+        "#top",
+        "a/b:c",
+    ] {
+        assert!(text::is_safe_url(url), "{:?}", url);
+    }
+    assert!(!text::is_safe_url("data:image/png;base64,AA"));
+    assert!(text::is_safe_image_url("data:image/png;base64,AA"));
+    assert!(!text::is_safe_image_url("data:text/html,x"));
+
+    let mut doc = DoclingDocument::new("a.md");
+    doc.add_node(
+        DocumentNode::new(NodeType::Picture, "chart")
+            .with_picture(PictureItem::file("javascript:alert(1)")),
+    );
+    let html = output::to_html(&doc);
+    assert!(!html.contains("<img"));
+    assert!(html.contains("<!-- image: chart -->"));
+}
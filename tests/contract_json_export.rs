@@ -0,0 +1,82 @@
+//! Contract tests for JSON export options
+
+use docling_rs::cli::output::{self, JsonOptions};
+use docling_rs::datamodel::{BoundingBox, DocumentNode, NodeType, PictureItem};
+use docling_rs::DoclingDocument;
+use serde_json::{json, Value};
+
+fn sample() -> DoclingDocument {
+    let mut doc = DoclingDocument::new("doc.pdf");
+    let heading = doc.add_node(
+        DocumentNode::new(NodeType::Heading, "Intro")
+            .with_level(1)
+            .with_page(1)
+            .with_bbox(BoundingBox::new(10.0, 20.0, 100.0, 10.0)),
+    );
+    let list = doc
+        .add_child(heading, DocumentNode::new(NodeType::List, ""))
+        .unwrap();
+    doc.add_child(list, DocumentNode::new(NodeType::ListItem, "one"));
+    let picture = doc.store_image(vec![0x89, b'P', b'N', b'G', 1, 2, 3]);
+    doc.add_node(DocumentNode::new(NodeType::Picture, "stored").with_picture(picture));
+    doc.add_node(
+        DocumentNode::new(NodeType::Picture, "inline")
+            .with_picture(PictureItem::inline(vec![1, 2])),
+    );
+    doc
+}
+
+fn export(options: JsonOptions) -> Value {
+    serde_json::from_str(&output::to_json_with(&sample(), &options).unwrap()).unwrap()
+}
+
+#[test]
+fn test_default_json_is_loadable() {
+    let doc = sample();
+    let text = output::to_json(&doc).unwrap();
+    assert_eq!(
+        text,
+        output::to_json_with(&doc, &JsonOptions::default()).unwrap()
+    );
+    assert!(text.contains("\n  "));
+    assert_eq!(DoclingDocument::from_json(&text).unwrap(), doc);
+}
+
+#[test]
+fn test_compact_json() {
+    let doc = sample();
+    let text = output::to_json_with(&doc, &JsonOptions::new().with_pretty(false)).unwrap();
+    assert!(!text.contains('\n'));
+    assert_eq!(DoclingDocument::from_json(&text).unwrap(), doc);
+}
+
+#[test]
+fn test_json_without_bboxes_and_images() {
+    let full = export(JsonOptions::new());
+    assert!(full["nodes"][0]["item"].get("bbox").is_some());
+    assert!(full.get("images").is_some());
+
+    let json = export(JsonOptions::new().with_bboxes(false).with_images(false));
+    assert!(json["nodes"][0]["item"].get("bbox").is_none());
+    assert_eq!(json["nodes"][0]["item"]["page"], 1);
+    assert!(json.get("images").is_none());
+    let inline = &json["nodes"][4]["item"]["picture"];
+    assert_eq!(inline["kind"], "inline");
+    assert!(inline.get("data").is_none());
+    assert_eq!(json["nodes"][3]["item"]["picture"]["kind"], "stored");
+}
+
+#[test]
+fn test_json_tree_layout() {
+    let json = export(JsonOptions::new().with_tree(true));
+    let nodes = json["nodes"].as_array().unwrap();
+    assert_eq!(nodes.len(), 3, "only top-level nodes");
+
+    let heading = &nodes[0];
+    assert_eq!(heading["item"]["text_content"], "Intro");
+    let list = &heading["children"][0];
+    assert_eq!(list["id"], 1);
+    assert!(list.get("parent").is_none());
+    assert_eq!(list["children"][0]["item"]["text_content"], "one");
+    assert_eq!(list["children"][0]["id"], json!(2));
+}
@@ -0,0 +1,172 @@
+//! Contract tests for LaTeX export
+
+use docling_rs::cli::output;
+use docling_rs::datamodel::{
+    text, DocumentNode, Formatting, NodeType, PictureItem, TableCell, TableData, TableRow, TextSpan,
+};
+use docling_rs::DoclingDocument;
+
+#[test]
+fn test_latex_export_structure() {
+    let mut doc = DoclingDocument::new("report.md");
+    doc.add_node(DocumentNode::new(NodeType::Title, "Q3 Report"));
+    let heading = doc.add_node(DocumentNode::new(NodeType::Heading, "Results").with_level(1));
+    doc.add_child(
+        heading,
+        DocumentNode::new(NodeType::Paragraph, "Revenue grew 5% & costs fell.")
+            .with_span(TextSpan::new(0, 7, Formatting::bold())),
+    );
+    let list = doc
+        .add_child(heading, DocumentNode::new(NodeType::List, ""))
+        .unwrap();
+    doc.add_child(list, DocumentNode::new(NodeType::ListItem, "1. first"));
+    let item = doc
+        .add_child(list, DocumentNode::new(NodeType::ListItem, "2. second"))
+        .unwrap();
+    let nested = doc
+        .add_child(item, DocumentNode::new(NodeType::List, ""))
+        .unwrap();
+    doc.add_child(nested, DocumentNode::new(NodeType::ListItem, "- nested"));
+    doc.add_child(
+        heading,
+        DocumentNode::new(NodeType::Heading, "Details").with_level(2),
+    );
+    doc.add_child(
+        heading,
+        DocumentNode::new(NodeType::CodeBlock, "let x = {1};"),
+    );
+    doc.add_child(
+        heading,
+        DocumentNode::new(NodeType::Picture, "A chart")
+            .with_picture(PictureItem::file("chart.png"))
+            .with_caption("Figure_1"),
+    );
+    doc.add_node(DocumentNode::new(NodeType::PageBreak, ""));
+
+    let latex = output::to_latex(&doc);
+    assert!(latex.starts_with("\\documentclass{article}\n"));
+    assert!(latex.contains("\\title{Q3 Report}\n"));
+    assert!(latex.contains(
+        "\\begin{document}\n\n\
+         \\maketitle\n\n\
+         \\section{Results}\n\n\
+         \\textbf{Revenue} grew 5\\% \\& costs fell.\n\n\
+         \\begin{enumerate}\n\
+         \\item first\n\
+         \\item second\n\
+         \\begin{itemize}\n\
+         \\item nested\n\
+         \\end{itemize}\n\
+         \\end{enumerate}\n\n\
+         \\subsection{Details}\n\n\
+         \\begin{verbatim}\nlet x = {1};\n\\end{verbatim}\n\n\
+         \\begin{figure}[h]\n\\centering\n\
+         \\includegraphics[width=\\linewidth]{chart.png}\n\
+         \\caption{Figure\\_1}\n\
+         \\end{figure}\n\n\
+         \\newpage\n\n\
+         \\end{document}\n"
+    ));
+}
+
+#[test]
+fn test_latex_export_tables() {
+    let table = TableData::new()
+        .with_row(TableRow::new(vec![
+            TableCell::new("Name"),
+            TableCell::new("Score").with_col_span(2),
+        ]))
+        .with_row(TableRow::new(vec![
+            TableCell::new("Ann").with_row_span(2),
+            TableCell::new("1"),
+            TableCell::new("a_b"),
+        ]))
+        .with_row(TableRow::new(vec![
+            TableCell::new("2"),
+            TableCell::new("3"),
+        ]))
+        .with_header_rows(1);
+    assert_eq!(
+        table.to_latex(),
+        "\\begin{tabular}{lll}\n\\hline\n\
+         Name & \\multicolumn{2}{l}{Score} \\\\\n\\hline\n\
+         \\multirow{2}{*}{Ann} & 1 & a\\_b \\\\\n\
+         \u{20}& 2 & 3 \\\\\n\
+         \\hline\n\\end{tabular}\n"
+    );
+
+    let mut doc = DoclingDocument::new("t");
+    doc.add_node(
+        DocumentNode::new(NodeType::Table, "")
+            .with_table(table)
+            .with_caption("Scores"),
+    );
+    let latex = output::to_latex(&doc);
+    assert!(!latex.contains("\\title"));
+    assert!(latex.contains("\\begin{table}[h]\n\\centering\n\\caption{Scores}\n\\begin{tabular}"));
+}
+
+#[test]
+fn test_inline_latex() {
+    let spans = [
+        TextSpan::new(0, 4, Formatting::link("https://x.test/#a%20b")),
+        TextSpan::new(5, 8, Formatting::code()),
+    ];
+    assert_eq!(
+        text::to_latex("docs a_b ~^\\", &spans),
+        "\\href{https://x.test/\\#a\\%20b}{docs} \\texttt{a\\_b} \\textasciitilde{}\\textasciicircum{}\\textbackslash{}"
+    );
+}
+
+#[test]
+fn test_latex_escapes_urls_and_unsafe_formulas() {
+    let spans = [TextSpan::new(
+        0,
+        4,
+        Formatting::link("https://x.test/a_b?c=1&d=~e^f}\\input{g"),
+    )];
+    assert_eq!(
+        text::to_latex("docs", &spans),
+        "\\href{https://x.test/a\\_b?c=1\\&d=\\~e\\^f\\%7D\\%5Cinput\\%7Bg}{docs}"
+    );
+
+    assert!(output::is_safe_formula(
+        "\\frac{a}{b} \\\\ \\includegraphics"
+    ));
+    for formula in [
+        "\\input{/etc/passwd}",
+        "x \\include{chapter}",
+        "\\immediate\\write18{rm -rf ~}",
+        "\\openout1=out.tex",
+        "^^5cinput{secret}",
+    ] {
+        assert!(!output::is_safe_formula(formula), "{:?}", formula);
+    }
+
+    let mut doc = DoclingDocument::new("a.md");
+    doc.add_node(DocumentNode::new(NodeType::Formula, "\\input{/etc/passwd}"));
+    doc.add_node(DocumentNode::new(NodeType::Formula, "E = mc^2"));
+    let latex = output::to_latex(&doc);
+    assert!(latex.contains("\\texttt{\\textbackslash{}input\\{/etc/passwd\\}}"));
+    assert!(latex.contains("\\[\nE = mc^2\n\\]"));
+}
+
+#[test]
+fn test_latex_verbatim_cannot_be_closed_early() {
+    let mut doc = DoclingDocument::new("t");
+    doc.add_node(DocumentNode::new(
+        NodeType::Table,
+        "a | b\n\\end{verbatim}\\input{x}",
+    ));
+    doc.add_node(DocumentNode::new(
+        NodeType::CodeBlock,
+        "\\end{verbatim}\\input{y}",
+    ));
+
+    let latex = output::to_latex(&doc);
+    assert!(
+        latex.contains("\\begin{verbatim}\na | b\n\\end {verbatim}\\input{x}\n\\end{verbatim}\n")
+    );
+    assert!(latex.contains("\\begin{verbatim}\n\\end {verbatim}\\input{y}\n\\end{verbatim}"));
+    assert_eq!(latex.matches("\\end{verbatim}").count(), 2);
+}
@@ -0,0 +1,68 @@
+//! Contract tests for layout-aware plain text export
+
+use docling_rs::cli::output;
+use docling_rs::datamodel::{BoundingBox, DocumentNode, NodeType, TableCell, TableData, TableRow};
+use docling_rs::DoclingDocument;
+
+fn block(text: &str, page: usize, left: f64) -> DocumentNode {
+    // 5 points per character
+    let right = left + 5.0 * text.chars().count() as f64;
+    DocumentNode::new(NodeType::Paragraph, text)
+        .with_page(page)
+        .with_bbox(BoundingBox::new(left, 700.0, right, 690.0))
+}
+
+#[test]
+fn test_layout_text_pages_and_columns() {
+    let mut doc = DoclingDocument::new("paper.pdf");
+    doc.add_node(block("Left column text", 1, 50.0));
+    doc.add_node(block("More on the left", 1, 50.0));
+    doc.add_node(block("Right column", 1, 300.0));
+    doc.add_node(block("Second page", 2, 50.0));
+
+    assert_eq!(
+        output::to_layout_text(&doc),
+        "Left column text\n\n\
+         More on the left\n\n\
+         \u{20}                                                 Right column\n\n\
+         \u{c}Second page\n"
+    );
+}
+
+#[test]
+fn test_layout_text_tables_and_lists() {
+    let table = TableData::new()
+        .with_row(TableRow::new(vec![
+            TableCell::new("Name"),
+            TableCell::new("Score"),
+        ]))
+        .with_row(TableRow::new(vec![
+            TableCell::new("Bartholomew"),
+            TableCell::new("7"),
+        ]))
+        .with_header_rows(1);
+
+    let mut doc = DoclingDocument::new("doc.md");
+    doc.add_node(DocumentNode::new(NodeType::Heading, "Scores").with_level(1));
+    doc.add_node(
+        DocumentNode::new(NodeType::Table, "")
+            .with_table(table)
+            .with_caption("Table 1"),
+    );
+    let list = doc.add_node(DocumentNode::new(NodeType::List, ""));
+    doc.add_child(list, DocumentNode::new(NodeType::ListItem, "- one"));
+    doc.add_child(list, DocumentNode::new(NodeType::ListItem, "two"));
+    doc.add_node(DocumentNode::new(NodeType::PageBreak, ""));
+    doc.add_node(DocumentNode::new(NodeType::Paragraph, "After the break"));
+
+    assert_eq!(
+        output::to_layout_text(&doc),
+        "Scores\n\n\
+         Name         Score\n\
+         Bartholomew  7\n\n\
+         Table 1\n\n\
+         - one\n\
+         - two\n\n\
+         \u{c}After the break\n"
+    );
+}
@@ -0,0 +1,211 @@
+//! Contract tests for Markdown export
+
+use docling_rs::backend::{Backend, CodeBackend, MarkdownBackend};
+use docling_rs::cli::output::{self, MarkdownExportOptions, TextExportOptions};
+use docling_rs::cli::HeadingStyle;
+use docling_rs::datamodel::{
+    ColumnAlignment, DocumentNode, Formatting, InputDocument, NodeType, PictureItem, TableCell,
+    TableData, TableRow, TextSpan,
+};
+use docling_rs::{DoclingDocument, InputFormat};
+
+#[test]
+fn test_markdown_export_structure() {
+    let mut doc = DoclingDocument::new("report.md");
+    doc.add_node(DocumentNode::new(NodeType::Title, "Report"));
+    let heading = doc.add_node(DocumentNode::new(NodeType::Heading, "Results").with_level(1));
+    doc.add_child(
+        heading,
+        DocumentNode::new(NodeType::Paragraph, "Read the guide now.")
+            .with_span(TextSpan::new(9, 14, Formatting::link("guide.md")))
+            .with_span(TextSpan::new(15, 18, Formatting::italic())),
+    );
+    let list = doc
+        .add_child(heading, DocumentNode::new(NodeType::List, ""))
+        .unwrap();
+    doc.add_child(
+        list,
+        DocumentNode::new(NodeType::ListItem, "* first").with_span(TextSpan::new(
+            2,
+            7,
+            Formatting::bold(),
+        )),
+    );
+    let item = doc
+        .add_child(list, DocumentNode::new(NodeType::ListItem, "second"))
+        .unwrap();
+    let nested = doc
+        .add_child(item, DocumentNode::new(NodeType::List, ""))
+        .unwrap();
+    doc.add_child(nested, DocumentNode::new(NodeType::ListItem, "1. nested"));
+    doc.add_child(
+        heading,
+        DocumentNode::new(NodeType::CodeBlock, "let s = \"```\";").with_code_language("rust"),
+    );
+    doc.add_child(
+        heading,
+        DocumentNode::new(NodeType::Picture, "A chart")
+            .with_picture(PictureItem::file("chart.png"))
+            .with_caption("Figure 1"),
+    );
+
+    assert_eq!(
+        output::to_markdown(&doc),
+        "# Report\n\n\
+         ## Results\n\n\
+         Read the [guide](guide.md) *now*.\n\n\
+         - **first**\n\
+         - second\n  \
+           1. nested\n\n\
+         ````rust\nlet s = \"```\";\n````\n\n\
+         ![A chart](chart.png)\n\n\
+         *Figure 1*\n\n"
+    );
+}
+
+#[test]
+fn test_markdown_export_tables() {
+    let table = TableData::new()
+        .with_row(TableRow::new(vec![
+            TableCell::new("a"),
+            TableCell::new("b"),
+        ]))
+        .with_row(TableRow::new(vec![
+            TableCell::new("1"),
+            TableCell::new("2"),
+        ]))
+        .with_header_rows(1);
+    let mut doc = DoclingDocument::new("t");
+    doc.add_node(
+        DocumentNode::new(NodeType::Table, "")
+            .with_table(table)
+            .with_caption("Table 1"),
+    );
+    doc.add_node(DocumentNode::new(NodeType::Table, ""));
+
+    assert_eq!(
+        output::to_markdown(&doc),
+        "# t\n\n*Table 1*\n\n| a | b |\n|-----|-----|\n| 1 | 2 |\n\n(Table content)\n\n"
+    );
+}
+
+#[test]
+fn test_markdown_round_trip_keeps_structure() {
+    let source = "# Guide\n\nSee the [docs](https://x.test).\n\n- one\n- two\n\n```python\nprint(1)\n```\n\n![Logo](logo.png)\n";
+    let input = InputDocument::from_bytes(
+        source.as_bytes().to_vec(),
+        "guide.md",
+        InputFormat::Markdown,
+    );
+    let doc = MarkdownBackend::new().convert(&input).unwrap();
+    let code = doc
+        .nodes()
+        .iter()
+        .find(|n| n.node_type() == NodeType::CodeBlock)
+        .unwrap();
+    assert_eq!(code.code_language(), Some("python"));
+    assert_eq!(code.language(), None);
+
+    let markdown = output::to_markdown(&doc);
+    assert!(markdown.contains("- one\n- two\n\n"));
+    assert!(markdown.contains("```python\nprint(1)\n```"));
+    assert!(markdown.contains("![Logo](logo.png)"));
+}
+
+#[test]
+fn test_markdown_export_source_code_language() {
+    let input = InputDocument::from_bytes(b"fn main() {}\n".to_vec(), "main.rs", InputFormat::Code);
+    let doc = CodeBackend::new().convert(&input).unwrap();
+    assert!(output::to_markdown(&doc).contains("```rust\n"));
+}
+
+#[test]
+fn test_markdown_export_options() {
+    let table = TableData::new()
+        .with_row(TableRow::new(vec![
+            TableCell::new("a"),
+            TableCell::new("b"),
+        ]))
+        .with_row(TableRow::new(vec![
+            TableCell::new("1"),
+            TableCell::new("2"),
+        ]));
+    let mut doc = DoclingDocument::new("report.md");
+    let heading = doc.add_node(DocumentNode::new(NodeType::Heading, "Results").with_level(1));
+    doc.add_child(
+        heading,
+        DocumentNode::new(
+            NodeType::Paragraph,
+            "Use *args and snake_case names, see call - 1 for code_here.",
+        )
+        .with_span(TextSpan::new(49, 58, Formatting::code())),
+    );
+    let list = doc
+        .add_child(heading, DocumentNode::new(NodeType::List, ""))
+        .unwrap();
+    doc.add_child(
+        list,
+        DocumentNode::new(NodeType::ListItem, "- a list item long enough to wrap"),
+    );
+    doc.add_child(
+        heading,
+        DocumentNode::new(NodeType::Table, "").with_table(table),
+    );
+
+    let options = MarkdownExportOptions::new()
+        .with_heading_style(HeadingStyle::Setext)
+        .with_escape(true)
+        .with_bullet('*')
+        .with_table_alignment(ColumnAlignment::Right)
+        .with_max_width(Some(24));
+    assert_eq!(
+        output::to_markdown_with(&doc, &options),
+        "report.md\n=========\n\n\
+         Results\n-------\n\n\
+         Use \\*args and\nsnake\\_case names, see\ncall - 1 for\n`code_here`.\n\n\
+         * a list item long\n  enough to wrap\n\n\
+         | a | b |\n|----:|----:|\n| 1 | 2 |\n\n"
+    );
+    assert_eq!(
+        output::to_markdown_with(&doc, &MarkdownExportOptions::default()),
+        output::to_markdown(&doc)
+    );
+}
+
+fn paged() -> DoclingDocument {
+    let mut doc = DoclingDocument::new("scan.pdf");
+    doc.add_node(DocumentNode::new(NodeType::Title, "Scan").with_page(1));
+    doc.add_node(DocumentNode::new(NodeType::Paragraph, "First page.").with_page(1));
+    doc.add_node(DocumentNode::new(NodeType::PageBreak, "").with_page(2));
+    doc.add_node(DocumentNode::new(NodeType::Paragraph, "Second page.").with_page(2));
+    doc.add_node(DocumentNode::new(NodeType::Paragraph, "Still second.").with_page(2));
+    doc.add_node(DocumentNode::new(NodeType::Paragraph, "Third page.").with_page(3));
+    doc
+}
+
+#[test]
+fn test_markdown_page_markers() {
+    let doc = paged();
+    assert!(!output::to_markdown(&doc).contains("<!--"));
+
+    let markdown =
+        output::to_markdown_with(&doc, &MarkdownExportOptions::new().with_page_markers(true));
+    assert_eq!(
+        markdown,
+        "<!-- page: 1 -->\n\n# Scan\n\nFirst page.\n\n<!-- page: 2 -->\n\n\
+         Second page.\n\nStill second.\n\n<!-- page: 3 -->\n\nThird page.\n\n"
+    );
+}
+
+#[test]
+fn test_text_page_markers() {
+    let doc = paged();
+    assert!(!output::to_text(&doc).contains('\u{c}'));
+
+    let text = output::to_text_with(&doc, &TextExportOptions::new().with_page_markers(true));
+    let pages: Vec<_> = text.split('\u{c}').collect();
+    assert_eq!(pages.len(), 3);
+    assert!(pages[0].contains("First page."));
+    assert_eq!(pages[1], "Second page.\n\nStill second.\n\n");
+    assert_eq!(pages[2], "Third page.\n\n");
+}
@@ -34,3 +34,27 @@ fn test_source_position() {
     assert_eq!(pos.start_line(), 1);
     assert_eq!(pos.end_line(), 5);
 }
+
+#[test]
+fn test_node_metadata_provenance() {
+    use docling_rs::datamodel::{BoundingBox, DocumentNode, NodeMetadata};
+
+    let bbox = BoundingBox::new(72.0, 720.0, 540.0, 700.0);
+    let metadata = NodeMetadata::new()
+        .with_position(SourcePosition::new(100, 120, 4, 4))
+        .with_page(2)
+        .with_bbox(bbox);
+    let node = DocumentNode::new(NodeType::Text, "text").with_metadata(metadata.clone());
+
+    assert_eq!(node.metadata(), &metadata);
+    assert_eq!(node.page(), Some(2));
+    assert_eq!(node.bbox(), Some(&bbox));
+    assert_eq!(node.position().map(|p| p.start_offset()), Some(100));
+    assert!(NodeMetadata::new().is_empty());
+
+    // Provenance fields serialize alongside the node content
+    let json = serde_json::to_value(NodeItem::new(NodeType::Text, "t").with_page(3)).unwrap();
+    assert_eq!(json["page"], 3);
+    let item: NodeItem = serde_json::from_value(json).unwrap();
+    assert_eq!(item.metadata().page(), Some(3));
+}
@@ -0,0 +1,49 @@
+//! Contract tests for the JSON Lines node stream
+
+use docling_rs::datamodel::jsonl::{self, NodeRecord};
+use docling_rs::datamodel::{BoundingBox, DocumentNode, NodeType};
+use docling_rs::DoclingDocument;
+
+#[test]
+fn test_node_stream_records() {
+    let mut doc = DoclingDocument::new("report.pdf");
+    doc.add_node(DocumentNode::new(NodeType::Heading, "Methods").with_level(1));
+    let results = doc.add_node(DocumentNode::new(NodeType::Heading, "Results").with_level(2));
+    doc.add_child(
+        results,
+        DocumentNode::new(NodeType::Paragraph, "Line one\nline two")
+            .with_page(2)
+            .with_bbox(BoundingBox::new(1.0, 2.0, 3.0, 4.0)),
+    );
+    doc.add_node(DocumentNode::new(NodeType::Heading, "Discussion").with_level(1));
+
+    let text = jsonl::to_string(&doc);
+    assert_eq!(text.lines().count(), 4, "one line per node");
+    let records: Vec<NodeRecord> = text
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+
+    let paragraph = &records[2];
+    assert_eq!(paragraph.document, "report.pdf");
+    assert_eq!(paragraph.id.index(), 2);
+    assert_eq!(paragraph.parent, Some(results));
+    assert_eq!(paragraph.node_type, NodeType::Paragraph);
+    assert_eq!(paragraph.text, "Line one\nline two");
+    assert_eq!(paragraph.headings, ["Methods", "Results"]);
+    assert_eq!(paragraph.page, Some(2));
+    assert_eq!(paragraph.bbox, Some(BoundingBox::new(1.0, 2.0, 3.0, 4.0)));
+
+    assert_eq!(
+        records[1].headings,
+        ["Methods"],
+        "a heading is not in its own path"
+    );
+    assert!(records[3].headings.is_empty());
+    assert_eq!(records[3].level, Some(1));
+
+    let first: serde_json::Value = serde_json::from_str(text.lines().next().unwrap()).unwrap();
+    assert_eq!(first["type"], "Heading");
+    assert!(first.get("parent").is_none());
+    assert!(first.get("page").is_none());
+}
@@ -0,0 +1,177 @@
+//! Contract tests for mapping node and chunk offsets back to source bytes
+
+use docling_rs::backend::{Backend, CodeBackend, MarkdownBackend, TextBackend};
+use docling_rs::chunking::{BaseChunker, HierarchicalChunker, RecursiveCharacterChunker};
+use docling_rs::datamodel::{DocumentNode, InputDocument, NodeType, OffsetMap, SourcePosition};
+use docling_rs::{DoclingDocument, InputFormat};
+
+fn convert(
+    backend: &dyn Backend,
+    content: &str,
+    name: &str,
+    format: InputFormat,
+) -> DoclingDocument {
+    let input = InputDocument::from_bytes(content.as_bytes().to_vec(), name, format);
+    backend.convert(&input).unwrap()
+}
+
+/// Check that every text byte range maps to the same bytes in the source
+fn assert_maps_back(source: &str, node: &DocumentNode) {
+    let text = node.text_content().unwrap();
+    for (start, word) in text.split_whitespace().map(|w| {
+        let start = w.as_ptr() as usize - text.as_ptr() as usize;
+        (start, w)
+    }) {
+        let (from, to) = node.source_range(start, start + word.len()).unwrap();
+        assert_eq!(&source[from..to], word, "word {:?} of {:?}", word, text);
+    }
+}
+
+#[test]
+fn test_offset_map_lookup() {
+    // "one\ntwo" built from "  one\r\n  two"
+    let map = OffsetMap::from_lines([(2, 3), (9, 3)], 1);
+    assert_eq!(map.segments().len(), 2);
+    assert!(!map.is_identity(2, 7));
+    assert_eq!(map.to_source(0), Some(2));
+    assert_eq!(map.to_source(4), Some(9));
+    assert_eq!(map.to_source(7), Some(12));
+    // The inserted separator maps to the end of the first line
+    assert_eq!(map.to_source(3), Some(5));
+    assert_eq!(map.source_range(3, 7), Some((9, 12)));
+    assert_eq!(map.source_range(0, 3), Some((2, 5)));
+
+    let slice = map.slice(4, 7);
+    assert_eq!(slice.to_source(0), Some(9));
+    assert!(slice.is_identity(9, 3));
+
+    // Contiguous lines collapse into one run
+    let map = OffsetMap::from_lines([(0, 3), (4, 3)], 1);
+    assert!(map.is_identity(0, 7));
+    assert!(!map.is_identity(2, 7));
+    assert_eq!(OffsetMap::new().to_source(3), None);
+}
+
+#[test]
+fn test_verbatim_nodes_use_position() {
+    let source = "# Title\n\nSome text here.\n";
+    let doc = convert(
+        &MarkdownBackend::new(),
+        source,
+        "a.md",
+        InputFormat::Markdown,
+    );
+    for node in doc.nodes() {
+        assert!(node.offsets().is_none(), "verbatim text needs no map");
+        assert_maps_back(source, node);
+    }
+
+    // Text with its inline markup removed maps around the markup
+    let source = "Some *text* here.\n";
+    let doc = convert(
+        &MarkdownBackend::new(),
+        source,
+        "a.md",
+        InputFormat::Markdown,
+    );
+    assert_eq!(doc.nodes()[0].text_content(), Some("Some text here."));
+    assert_maps_back(source, &doc.nodes()[0]);
+
+    // Text that does not match its position cannot be mapped
+    let node = DocumentNode::new(NodeType::Paragraph, "changed")
+        .with_position(SourcePosition::new(0, 3, 1, 1));
+    assert_eq!(node.source_offset(1), None);
+    assert_eq!(
+        DocumentNode::new(NodeType::Paragraph, "x").source_offset(0),
+        None
+    );
+}
+
+#[test]
+fn test_text_backend_maps_trimmed_lines() {
+    let source = "Intro\n\n    An indented paragraph\r\n    spanning two lines.\r\n";
+    let doc = convert(&TextBackend::new(), source, "a.txt", InputFormat::Text);
+    let paragraph = doc
+        .nodes()
+        .iter()
+        .find(|n| n.text_content().unwrap().starts_with("An"))
+        .unwrap();
+
+    assert!(paragraph.offsets().is_some());
+    assert_maps_back(source, paragraph);
+}
+
+#[test]
+fn test_text_backend_maps_indented_line() {
+    let source = "  Hello";
+    let doc = convert(&TextBackend::new(), source, "a.txt", InputFormat::Text);
+    let node = &doc.nodes()[0];
+
+    // One verbatim run, but not from the start of the node position
+    assert_eq!(node.text_content(), Some("Hello"));
+    assert!(node.offsets().is_some());
+    assert_eq!(node.source_offset(0), Some(2));
+    assert_maps_back(source, node);
+}
+
+#[test]
+fn test_markdown_code_block_crlf() {
+    let source = "# Code\r\n\r\n```\r\nfn main() {\r\n    run();\r\n}\r\n```\r\n";
+    let doc = convert(
+        &MarkdownBackend::new(),
+        source,
+        "a.md",
+        InputFormat::Markdown,
+    );
+    let code = doc
+        .nodes()
+        .iter()
+        .find(|n| n.node_type() == NodeType::CodeBlock)
+        .unwrap();
+
+    assert_eq!(code.text_content(), Some("fn main() {\n    run();\n}"));
+    assert_maps_back(source, code);
+}
+
+#[test]
+fn test_code_backend_crlf() {
+    let source = "fn a() {\r\n    1\r\n}\r\n\r\nfn b() {\r\n    2\r\n}\r\n";
+    let doc = convert(&CodeBackend::new(), source, "a.rs", InputFormat::Code);
+    let code: Vec<_> = doc
+        .nodes()
+        .iter()
+        .filter(|n| n.node_type() == NodeType::CodeBlock)
+        .collect();
+    assert_eq!(code.len(), 2);
+    for node in code {
+        assert_maps_back(source, node);
+    }
+}
+
+#[test]
+fn test_chunk_offsets_trace_back_to_source() {
+    let source = "  First line of a long paragraph\r\n  second line of the same paragraph\r\n";
+    let doc = convert(&TextBackend::new(), source, "a.txt", InputFormat::Text);
+
+    let chunk = HierarchicalChunker::new().chunk(&doc).next().unwrap();
+    assert!(chunk.meta.offsets.is_some());
+    let second = chunk.text.find("second").unwrap();
+    let from = chunk.meta.source_offset(second);
+    assert_eq!(&source[from..from + 6], "second");
+
+    // Pieces of a split element keep exact offsets
+    let chunker = RecursiveCharacterChunker::builder()
+        .chunk_size(40)
+        .build()
+        .unwrap();
+    for chunk in chunker.chunk(&doc) {
+        let first = chunk.text.split_whitespace().next().unwrap();
+        let from = chunk.meta.start_offset;
+        assert_eq!(&source[from..from + first.len()], first);
+        let last = chunk.text.split_whitespace().last().unwrap();
+        assert_eq!(
+            &source[chunk.meta.end_offset - last.len()..chunk.meta.end_offset],
+            last
+        );
+    }
+}
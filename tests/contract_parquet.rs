@@ -0,0 +1,126 @@
+//! Contract tests for Parquet export of chunks and nodes
+
+#[cfg(feature = "parquet")]
+mod parquet_export {
+    use std::fs::File;
+
+    use ::parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+    use arrow_array::{Array, ListArray, RecordBatch, StringArray, UInt32Array, UInt64Array};
+    use docling_rs::chunking::{parquet, ApproxTokenizer, BaseChunker, HierarchicalChunker};
+    use docling_rs::datamodel::{DocumentNode, NodeType};
+    use docling_rs::DoclingDocument;
+
+    fn sample() -> DoclingDocument {
+        let mut doc = DoclingDocument::new("report.pdf");
+        let heading = doc.add_node(
+            DocumentNode::new(NodeType::Heading, "Results")
+                .with_level(1)
+                .with_page(1),
+        );
+        doc.add_child(
+            heading,
+            DocumentNode::new(NodeType::Paragraph, "Scores improved a lot.").with_page(2),
+        );
+        doc.add_node(DocumentNode::new(NodeType::List, ""));
+        doc
+    }
+
+    fn read(bytes: Vec<u8>) -> RecordBatch {
+        let mut file = tempfile::tempfile().unwrap();
+        std::io::Write::write_all(&mut file, &bytes).unwrap();
+        let reader = ParquetRecordBatchReaderBuilder::try_new(file as File)
+            .unwrap()
+            .build()
+            .unwrap();
+        let mut batches: Vec<RecordBatch> = reader.map(Result::unwrap).collect();
+        assert_eq!(batches.len(), 1);
+        batches.remove(0)
+    }
+
+    fn strings<'a>(batch: &'a RecordBatch, name: &str) -> &'a StringArray {
+        batch
+            .column_by_name(name)
+            .unwrap()
+            .as_any()
+            .downcast_ref()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_nodes_to_parquet() {
+        let batch = read(parquet::nodes_to_bytes(&sample(), &ApproxTokenizer::new()).unwrap());
+        let names: Vec<_> = batch
+            .schema()
+            .fields()
+            .iter()
+            .map(|f| f.name().clone())
+            .collect();
+        assert_eq!(
+            names,
+            ["id", "document", "text", "headings", "page", "token_count"]
+        );
+        assert_eq!(batch.num_rows(), 2, "the text-less list is left out");
+
+        assert_eq!(strings(&batch, "id").value(1), "#/nodes/1");
+        assert_eq!(strings(&batch, "document").value(1), "report.pdf");
+        assert_eq!(strings(&batch, "text").value(1), "Scores improved a lot.");
+        let headings: &ListArray = batch
+            .column_by_name("headings")
+            .unwrap()
+            .as_any()
+            .downcast_ref()
+            .unwrap();
+        assert_eq!(headings.value(0).len(), 0);
+        let path = headings.value(1);
+        let path: &StringArray = path.as_any().downcast_ref().unwrap();
+        assert_eq!(path.value(0), "Results");
+        let pages: &UInt32Array = batch
+            .column_by_name("page")
+            .unwrap()
+            .as_any()
+            .downcast_ref()
+            .unwrap();
+        assert_eq!(pages.value(1), 2);
+        let tokens: &UInt64Array = batch
+            .column_by_name("token_count")
+            .unwrap()
+            .as_any()
+            .downcast_ref()
+            .unwrap();
+        assert!(tokens.value(1) > 0);
+    }
+
+    #[test]
+    fn test_chunks_to_parquet() {
+        let doc = sample();
+        let chunker = HierarchicalChunker::new();
+        let chunks: Vec<_> = chunker.chunk(&doc).collect();
+        let expected_id = chunks[0].meta.chunk_id.clone();
+        let batch = read(
+            parquet::chunks_to_bytes(chunks.clone(), &chunker, &ApproxTokenizer::new()).unwrap(),
+        );
+        assert_eq!(batch.num_rows(), chunks.len());
+        assert_eq!(strings(&batch, "id").value(0), expected_id);
+        assert_eq!(strings(&batch, "text").value(0), chunks[0].text);
+    }
+}
+
+#[cfg(not(feature = "parquet"))]
+#[test]
+fn test_parquet_output_requires_feature() {
+    use assert_cmd::Command;
+    use predicates::prelude::*;
+
+    let temp = tempfile::TempDir::new().unwrap();
+    let input = temp.path().join("doc.md");
+    std::fs::write(&input, "# Test\n\nBody.\n").unwrap();
+
+    Command::cargo_bin("docling-rs")
+        .unwrap()
+        .arg(&input)
+        .arg("--to")
+        .arg("parquet")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("`parquet` feature"));
+}
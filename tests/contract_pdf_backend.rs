@@ -56,3 +56,31 @@ fn test_pdf_backend_convert_simple_pdf() {
 }
 
 // Helper functions now imported from helpers::pdf_fixtures
+
+#[test]
+fn test_pdf_backends_convert_concurrently() {
+    // Backends share one pdfium instance, so several can exist at once and
+    // convert from worker threads
+    let paths: Vec<_> = (0..4)
+        .map(|i| create_simple_text_pdf(&format!("Concurrent PDF {}", i)))
+        .collect();
+
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = paths
+            .iter()
+            .map(|path| {
+                scope.spawn(move || {
+                    let backend = PdfBackend::new();
+                    backend.convert(&InputDocument::from_path(path.clone(), InputFormat::PDF))
+                })
+            })
+            .collect();
+        for handle in handles {
+            let doc = handle
+                .join()
+                .unwrap()
+                .expect("PDF conversion should succeed");
+            assert!(!doc.nodes().is_empty(), "Document should have nodes");
+        }
+    });
+}
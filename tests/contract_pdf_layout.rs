@@ -105,11 +105,11 @@ fn test_column_bounds() {
     let right_col = &layout.columns[1];
 
     assert!(
-        left_col.bbox.x < 300.0,
+        left_col.bbox.left < 300.0,
         "Left column should be on left side"
     );
     assert!(
-        right_col.bbox.x > 300.0,
+        right_col.bbox.left > 300.0,
         "Right column should be on right side"
     );
 }
@@ -137,13 +137,14 @@ fn test_empty_input() {
 }
 
 // Helper function
+/// Block at `y` points from the top of an 800 points high page
 fn create_text_block(text: &str, x: f64, y: f64, width: f64, height: f64) -> TextBlock {
     use docling_rs::backend::pdf::page::TextBlockType;
     use docling_rs::backend::pdf::types::FontInfo;
 
     TextBlock {
         text: text.to_string(),
-        bbox: BoundingBox::new(x, y, width, height),
+        bbox: BoundingBox::new(x, 800.0 - y, x + width, 800.0 - y - height),
         font_info: FontInfo {
             name: "Arial".to_string(),
             size: 12.0,
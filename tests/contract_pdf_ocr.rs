@@ -31,13 +31,13 @@ fn test_ocr_result_structure() {
 fn test_ocr_word_structure() {
     // Contract: OcrWord must contain text, confidence, and bounding box
 
-    let bbox = BoundingBox::new(10.0, 20.0, 100.0, 30.0);
+    let bbox = BoundingBox::new(10.0, 50.0, 110.0, 20.0);
     let word = OcrWord::new("hello".to_string(), 92.5, bbox);
 
     assert_eq!(word.text, "hello");
     assert_eq!(word.confidence, 92.5);
-    assert_eq!(word.bbox.x, 10.0);
-    assert_eq!(word.bbox.y, 20.0);
+    assert_eq!(word.bbox.left, 10.0);
+    assert_eq!(word.bbox.top, 50.0);
 }
 
 #[test]
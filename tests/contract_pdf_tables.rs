@@ -158,13 +158,14 @@ fn create_test_detector() -> impl TableDetector {
     GridBasedTableDetector::new()
 }
 
+/// Block at `y` points from the top of an 800 points high page
 fn create_text_block(text: &str, x: f64, y: f64, width: f64, height: f64) -> TextBlock {
     use docling_rs::backend::pdf::page::TextBlockType;
     use docling_rs::backend::pdf::types::FontInfo;
 
     TextBlock {
         text: text.to_string(),
-        bbox: BoundingBox::new(x, y, width, height),
+        bbox: BoundingBox::new(x, 800.0 - y, x + width, 800.0 - y - height),
         font_info: FontInfo {
             name: "Arial".to_string(),
             size: 12.0,
@@ -0,0 +1,95 @@
+//! Contract tests for NodeQuery selection
+
+use docling_rs::backend::{Backend, MarkdownBackend};
+use docling_rs::datamodel::{DoclingDocument, DocumentNode, InputDocument, NodeQuery, NodeType};
+use docling_rs::InputFormat;
+
+fn markdown(content: &str) -> DoclingDocument {
+    let input = InputDocument::from_bytes(
+        content.as_bytes().to_vec(),
+        "test.md",
+        InputFormat::Markdown,
+    );
+    MarkdownBackend::new().convert(&input).unwrap()
+}
+
+fn texts(selected: Vec<(docling_rs::datamodel::NodeId, &DocumentNode)>) -> Vec<&str> {
+    selected
+        .into_iter()
+        .map(|(_, node)| node.text_content().unwrap())
+        .collect()
+}
+
+const CONTENT: &str = "# Introduction\n\nOpening.\n\n## Background\n\nHistory.\n\n\
+                       ### Details\n\nFine print.\n\n## Goals\n\nAims.\n\n# Results\n\nNumbers.\n";
+
+#[test]
+fn test_select_by_type_and_level() {
+    let doc = markdown(CONTENT);
+
+    let headings = doc.select(&NodeQuery::type_(NodeType::Heading).level(2));
+    assert_eq!(texts(headings), vec!["Background", "Goals"]);
+
+    let all_headings = doc.select(&NodeQuery::type_(NodeType::Heading));
+    assert_eq!(all_headings.len(), 5);
+
+    let mixed = doc.select(
+        &NodeQuery::type_(NodeType::Heading)
+            .or_type(NodeType::Paragraph)
+            .contains("s."),
+    );
+    assert_eq!(texts(mixed), vec!["Aims.", "Numbers."]);
+
+    assert_eq!(doc.select(&NodeQuery::new()).len(), doc.nodes().len());
+}
+
+#[test]
+fn test_select_by_heading_path() {
+    let doc = markdown(CONTENT);
+
+    let heading = doc.select(&NodeQuery::heading_path("Introduction > Background"));
+    assert_eq!(heading.len(), 1);
+    let (id, node) = heading[0];
+    assert_eq!(node.text_content(), Some("Background"));
+    assert_eq!(doc.node(id).map(|n| n.level()), Some(Some(2)));
+
+    // Paths must start at the outermost section
+    assert!(doc
+        .select(&NodeQuery::heading_path("Background"))
+        .is_empty());
+    assert!(doc
+        .select(&NodeQuery::heading_path("Results > Background"))
+        .is_empty());
+}
+
+#[test]
+fn test_select_within_section() {
+    let doc = markdown(CONTENT);
+
+    let section = doc.select(&NodeQuery::new().within("Introduction > Background"));
+    assert_eq!(texts(section), vec!["History.", "Details", "Fine print."]);
+
+    let paragraphs = doc.select(&NodeQuery::type_(NodeType::Paragraph).within(" Introduction "));
+    assert_eq!(
+        texts(paragraphs),
+        vec!["Opening.", "History.", "Fine print.", "Aims."]
+    );
+}
+
+#[test]
+fn test_select_flat_document_and_pages() {
+    // Flat documents (no tree) get their heading paths from heading levels
+    let doc = DoclingDocument::new("report.pdf").with_nodes(vec![
+        DocumentNode::new(NodeType::Title, "Annual Report"),
+        DocumentNode::new(NodeType::Heading, "Summary").with_level(1),
+        DocumentNode::new(NodeType::Table, "Revenue").with_page(1),
+        DocumentNode::new(NodeType::Heading, "Appendix").with_level(1),
+        DocumentNode::new(NodeType::Table, "Costs").with_page(3),
+    ]);
+
+    let summary = doc.select(&NodeQuery::type_(NodeType::Table).within("Summary"));
+    assert_eq!(texts(summary), vec!["Revenue"]);
+
+    let page_three = doc.select(&NodeQuery::new().page(3));
+    assert_eq!(texts(page_three), vec!["Costs"]);
+}
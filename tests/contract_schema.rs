@@ -0,0 +1,176 @@
+//! Contract tests for the versioned document schema
+
+use docling_rs::datamodel::schema::{self, SchemaError};
+use docling_rs::datamodel::{
+    ConversionResult, ConversionStatus, DoclingDocument, DocumentNode, NodeType,
+};
+use serde_json::json;
+use std::path::Path;
+
+fn sample() -> DoclingDocument {
+    let mut doc = DoclingDocument::new("sample");
+    let heading = doc.add_node(DocumentNode::new(NodeType::Heading, "Intro").with_level(1));
+    doc.add_child(heading, DocumentNode::new(NodeType::Paragraph, "Body"));
+    doc
+}
+
+/// Compare a generated schema with its checked-in artifact
+///
+/// Run with `UPDATE_SCHEMA=1` to regenerate the artifacts after changing the
+/// data model.
+fn check_artifact(file: &str, generated: serde_json::Value) {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("schema")
+        .join(file);
+    let generated = serde_json::to_string_pretty(&generated).unwrap() + "\n";
+    if std::env::var_os("UPDATE_SCHEMA").is_some() {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, &generated).unwrap();
+    }
+    let stored = std::fs::read_to_string(&path).unwrap();
+    assert_eq!(
+        stored, generated,
+        "{} is out of date; rerun with UPDATE_SCHEMA=1",
+        file
+    );
+}
+
+#[test]
+fn test_schema_artifacts_up_to_date() {
+    check_artifact("docling-document.schema.json", schema::document_schema());
+    check_artifact("conversion-result.schema.json", schema::result_schema());
+}
+
+#[test]
+fn test_schema_describes_document() {
+    let schema = schema::document_schema();
+    assert_eq!(schema["title"], "DoclingDocument");
+    let properties = &schema["properties"];
+    assert!(properties.get("schema_version").is_some());
+    assert!(properties.get("nodes").is_some());
+    assert!(properties.get("metadata").is_some());
+
+    let schema = schema::result_schema();
+    assert_eq!(schema["title"], "ConversionResult");
+    assert!(schema["properties"].get("document").is_some());
+}
+
+#[test]
+fn test_schema_version_serialized() {
+    let doc = sample();
+    assert_eq!(doc.schema_version(), schema::SCHEMA_VERSION);
+
+    let json = serde_json::to_value(&doc).unwrap();
+    assert_eq!(json["schema_version"], schema::SCHEMA_VERSION);
+
+    let result = ConversionResult::new(doc, ConversionStatus::Success);
+    let json = serde_json::to_value(&result).unwrap();
+    assert_eq!(json["schema_version"], schema::SCHEMA_VERSION);
+    assert_eq!(json["document"]["schema_version"], schema::SCHEMA_VERSION);
+}
+
+#[test]
+fn test_schema_roundtrip_current_version() {
+    let doc = sample();
+    let json = serde_json::to_value(&doc).unwrap();
+    assert_eq!(schema::migrate_document(json.clone()).unwrap(), json);
+
+    let loaded = schema::document_from_value(json).unwrap();
+    assert_eq!(loaded.nodes().len(), 2);
+    assert_eq!(loaded.roots().count(), 1);
+
+    let result = ConversionResult::new(sample(), ConversionStatus::Success);
+    let loaded = schema::result_from_value(serde_json::to_value(&result).unwrap()).unwrap();
+    assert_eq!(loaded.document().name(), "sample");
+}
+
+#[test]
+fn test_schema_migrates_unversioned_document() {
+    // Layout written before documents were versioned
+    let legacy = json!({
+        "name": "old.pdf",
+        "nodes": [
+            {"item": {"node_type": "Paragraph", "text_content": "First", "position": null}},
+            {"item": {"node_type": "Paragraph", "text_content": "Second", "position": null}}
+        ],
+        "metadata": {"image_count": 3, "page_count": 2}
+    });
+
+    let migrated = schema::migrate_document(legacy.clone()).unwrap();
+    assert_eq!(migrated["schema_version"], schema::SCHEMA_VERSION);
+    assert_eq!(migrated["nodes"][1]["id"], 1);
+
+    let doc = schema::document_from_value(legacy).unwrap();
+    assert_eq!(doc.schema_version(), schema::SCHEMA_VERSION);
+    assert_eq!(doc.metadata().page_count(), Some(2));
+    assert_eq!(doc.metadata().extra().get("image_count"), Some(&json!(3)));
+    let second = doc.nodes()[1].id().unwrap();
+    assert_eq!(doc.node(second).unwrap().text_content(), Some("Second"));
+}
+
+#[test]
+fn test_schema_migrates_unversioned_result() {
+    let legacy = json!({
+        "document": {"name": "old.md", "nodes": [], "metadata": {}},
+        "status": "Success",
+        "metrics": {"total_pages": null, "processing_time_ms": 5}
+    });
+
+    let result = schema::result_from_value(legacy).unwrap();
+    assert_eq!(result.schema_version(), schema::SCHEMA_VERSION);
+    assert_eq!(result.document().schema_version(), schema::SCHEMA_VERSION);
+}
+
+#[test]
+fn test_schema_rejects_newer_major_version() {
+    let mut json = serde_json::to_value(sample()).unwrap();
+    json["schema_version"] = json!("2.0.0");
+
+    assert!(matches!(
+        schema::document_from_value(json),
+        Err(SchemaError::UnsupportedVersion(version)) if version == "2.0.0"
+    ));
+}
+
+#[test]
+fn test_schema_validation_errors() {
+    let json = serde_json::to_value(sample()).unwrap();
+
+    // Child pointing past the end of the node list
+    let mut broken = json.clone();
+    broken["nodes"][0]["children"] = json!([7]);
+    assert!(matches!(
+        schema::document_from_value(broken),
+        Err(SchemaError::Invalid(_))
+    ));
+
+    // Parent that does not list the node as a child
+    let mut broken = json.clone();
+    broken["nodes"][0]["children"] = json!([]);
+    assert!(matches!(
+        schema::document_from_value(broken),
+        Err(SchemaError::Invalid(_))
+    ));
+
+    // Span beyond the end of the text
+    let mut broken = json.clone();
+    broken["nodes"][1]["item"]["spans"] = json!([
+        {"start": 0, "end": 40, "formatting": "Bold"}
+    ]);
+    let err = schema::document_from_value(broken).unwrap_err();
+    assert!(err.to_string().contains("span"));
+
+    // Picture referencing a missing stored image
+    let mut broken = json;
+    broken["nodes"][1]["item"]["picture"] = json!({"kind": "stored", "digest": "abc"});
+    assert!(matches!(
+        schema::document_from_value(broken),
+        Err(SchemaError::Invalid(_))
+    ));
+
+    // Wrong shape
+    assert!(matches!(
+        schema::document_from_value(json!({"name": 3})),
+        Err(SchemaError::Serialization(_))
+    ));
+}
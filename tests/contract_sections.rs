@@ -0,0 +1,270 @@
+//! Contract tests for grouping document nodes into sections
+
+use docling_rs::backend::{Backend, MarkdownBackend};
+use docling_rs::datamodel::{
+    Annotation, DocumentNode, InputDocument, NodeQuery, NodeType, RefItem,
+};
+use docling_rs::pipeline::{Pipeline, SimplePipeline};
+use docling_rs::{DoclingDocument, InputFormat};
+
+const REPORT: &str = "\
+Preamble.
+
+## 1 Introduction
+
+Why this matters.
+
+## 3 Methods
+
+### 3.1 Data
+
+We collected data.
+
+- surveys
+- interviews
+
+### 3.2. Results
+
+It worked.
+
+#### Caveats
+
+Small sample.
+
+## 4 Conclusion
+
+Done.
+";
+
+fn report() -> DoclingDocument {
+    let input = InputDocument::from_bytes(
+        REPORT.as_bytes().to_vec(),
+        "report.md",
+        InputFormat::Markdown,
+    );
+    MarkdownBackend::new().convert(&input).unwrap()
+}
+
+/// Text of a node's children, for checking the tree shape
+fn child_texts(doc: &DoclingDocument, id: docling_rs::datamodel::NodeId) -> Vec<String> {
+    doc.children(id)
+        .map(|(_, node)| match node.node_type() {
+            NodeType::Section => format!("<section {}>", node.level().unwrap_or(0)),
+            _ => node.text_content().unwrap_or_default().to_string(),
+        })
+        .collect()
+}
+
+#[test]
+fn test_group_sections_nests_by_level() {
+    let doc = report().group_sections();
+
+    let roots: Vec<_> = doc.roots().map(|(id, _)| id).collect();
+    assert_eq!(roots.len(), 4);
+    assert_eq!(
+        doc.node(roots[0]).unwrap().text_content(),
+        Some("Preamble.")
+    );
+
+    let methods = roots[2];
+    assert_eq!(
+        child_texts(&doc, methods),
+        ["3 Methods", "<section 3>", "<section 3>"]
+    );
+    let data = doc.children(methods).nth(1).unwrap().0;
+    // Lists keep their items
+    assert_eq!(
+        child_texts(&doc, data),
+        ["3.1 Data", "We collected data.", ""]
+    );
+    let list = doc.children(data).nth(2).unwrap().0;
+    assert_eq!(child_texts(&doc, list), ["surveys", "interviews"]);
+
+    // Reading order and parent links are consistent
+    assert!(doc
+        .depth_first()
+        .map(|entry| entry.id.index())
+        .eq(0..doc.nodes().len()));
+    assert!(doc
+        .nodes()
+        .iter()
+        .all(|node| node
+            .children()
+            .iter()
+            .all(|&child| doc.node(child).unwrap().parent() == node.id())));
+}
+
+#[test]
+fn test_section_text_by_number_and_path() {
+    let doc = report().group_sections();
+
+    assert_eq!(
+        doc.section_text("3.2").as_deref(),
+        Some("3.2. Results\n\nIt worked.\n\nCaveats\n\nSmall sample.")
+    );
+    assert_eq!(
+        doc.section_text("3 Methods > 3.1").as_deref(),
+        Some("3.1 Data\n\nWe collected data.\n\nsurveys\n\ninterviews")
+    );
+    assert_eq!(
+        doc.section_text("Caveats"),
+        doc.section_text("3 > 3.2 > Caveats")
+    );
+    assert_eq!(doc.section("4 > 3.2"), None);
+    assert_eq!(doc.section("2"), None);
+    assert_eq!(doc.section(""), None);
+
+    // Documents that were not grouped have no sections
+    assert_eq!(report().section("3.2"), None);
+}
+
+#[test]
+fn test_group_sections_is_idempotent() {
+    let grouped = report().group_sections();
+    assert_eq!(grouped.group_sections(), grouped);
+
+    // Headings already nesting their content are regrouped the same way as flat nodes
+    let mut flat = DoclingDocument::new("report.md");
+    for node in report().nodes() {
+        let mut copy = DocumentNode::new(node.node_type(), node.text_content().unwrap_or_default());
+        if let Some(level) = node.level() {
+            copy = copy.with_level(level);
+        }
+        if node.node_type() != NodeType::ListItem {
+            flat.add_node(copy);
+        }
+    }
+    let flat = flat.group_sections();
+    assert_eq!(flat.section_text("3.2"), grouped.section_text("3.2"));
+}
+
+#[test]
+fn test_group_sections_remaps_ids() {
+    let mut doc = DoclingDocument::new("doc");
+    doc.add_node(DocumentNode::new(NodeType::Title, "Title"));
+    doc.add_node(DocumentNode::new(NodeType::Heading, "A").with_level(1));
+    let table = doc.add_node(DocumentNode::new(NodeType::Table, "| x |"));
+    doc.add_node(DocumentNode::new(NodeType::Heading, "B").with_level(1));
+    let note =
+        doc.add_node(DocumentNode::new(NodeType::Paragraph, "See the table").with_reference(table));
+    doc.annotate(Annotation::new(note, "xref").with_range(8, 13));
+
+    let grouped = doc.group_sections();
+    assert_eq!(grouped.nodes().len(), 7);
+    let (note, node) = grouped
+        .select(&NodeQuery::new().contains("See the"))
+        .pop()
+        .unwrap();
+    let target = grouped.resolve(&node.references()[0]).unwrap();
+    assert_eq!(target.node_type(), NodeType::Table);
+    assert_eq!(grouped.annotations()[0].node(), note);
+    assert_eq!(
+        grouped.annotated_text(&grouped.annotations()[0]),
+        Some("table")
+    );
+    assert_ne!(node.references()[0], RefItem::new(table));
+
+    // The title stays outside the sections
+    let sections = grouped.select(&NodeQuery::type_(NodeType::Section));
+    assert_eq!(sections.len(), 2);
+    assert!(sections.iter().all(|(_, s)| s.parent().is_none()));
+
+    // Grouped documents round-trip through JSON
+    let restored = DoclingDocument::from_json(&grouped.to_json().unwrap()).unwrap();
+    assert_eq!(restored, grouped);
+}
+
+#[test]
+fn test_pipeline_section_grouping() {
+    let input = InputDocument::from_bytes(
+        REPORT.as_bytes().to_vec(),
+        "report.md",
+        InputFormat::Markdown,
+    );
+    let result = SimplePipeline::new()
+        .with_section_grouping(true)
+        .execute(&input)
+        .unwrap();
+    assert_eq!(
+        result.document().section_text("4").as_deref(),
+        Some("4 Conclusion\n\nDone.")
+    );
+}
+
+#[test]
+fn test_split_sections_at_level() {
+    let parts = report().split_sections(2);
+    let firsts: Vec<_> = parts
+        .iter()
+        .map(|part| part.nodes()[0].text_content().unwrap_or_default())
+        .collect();
+    assert_eq!(
+        firsts,
+        ["Preamble.", "1 Introduction", "3 Methods", "4 Conclusion"]
+    );
+    assert!(parts.iter().all(|part| part.name() == "report.md"));
+    assert_eq!(parts[2].roots().count(), 1);
+    assert_eq!(
+        parts[2].subtree_text(parts[2].roots().next().unwrap().0),
+        "3 Methods\n\n3.1 Data\n\nWe collected data.\n\nsurveys\n\ninterviews\n\n\
+         3.2. Results\n\nIt worked.\n\nCaveats\n\nSmall sample."
+    );
+
+    // Deeper levels split subsections too
+    assert_eq!(report().split_sections(3).len(), 6);
+    // Documents without headings stay whole
+    assert_eq!(report().split_sections(1).len(), 1);
+}
+
+#[test]
+fn test_split_grouped_sections() {
+    let parts = report().group_sections().split_sections(2);
+    assert_eq!(parts.len(), 4);
+
+    let methods = &parts[2];
+    let roots: Vec<_> = methods.roots().collect();
+    assert_eq!(roots.len(), 1, "the section holds the whole part");
+    assert_eq!(roots[0].1.node_type(), NodeType::Section);
+    assert_eq!(
+        methods.section_text("3.2").as_deref(),
+        Some("3.2. Results\n\nIt worked.\n\nCaveats\n\nSmall sample.")
+    );
+    assert!(methods
+        .nodes()
+        .iter()
+        .all(|node| node
+            .children()
+            .iter()
+            .all(|&child| methods.node(child).unwrap().parent() == node.id())));
+}
+
+#[test]
+fn test_split_sections_remaps_ids() {
+    let mut doc = DoclingDocument::new("doc");
+    doc.add_node(DocumentNode::new(NodeType::Heading, "A").with_level(1));
+    let table = doc.add_node(DocumentNode::new(NodeType::Table, "| x |"));
+    doc.add_node(DocumentNode::new(NodeType::Heading, "B").with_level(1));
+    let note =
+        doc.add_node(DocumentNode::new(NodeType::Paragraph, "See the table").with_reference(table));
+    doc.add_node(DocumentNode::new(NodeType::Paragraph, "Above").with_reference(note));
+    doc.annotate(Annotation::new(note, "xref").with_range(8, 13));
+
+    let parts = doc.split_sections(1);
+    assert_eq!(parts.len(), 2);
+    let second = &parts[1];
+    // References into another part are dropped, those within the part kept
+    assert!(second.nodes()[1].references().is_empty());
+    let above = &second.nodes()[2];
+    assert_eq!(
+        second
+            .resolve(&above.references()[0])
+            .unwrap()
+            .text_content(),
+        Some("See the table")
+    );
+    assert_eq!(
+        second.annotated_text(&second.annotations()[0]),
+        Some("table")
+    );
+    assert!(parts[0].annotations().is_empty());
+}
@@ -1,5 +1,6 @@
 //! Contract tests for Table types
 
+use docling_rs::datamodel::table::{MAX_COL_SPAN, MAX_ROW_SPAN};
 use docling_rs::datamodel::{TableCell, TableData, TableRow};
 
 #[test]
@@ -38,9 +39,139 @@ fn test_tablecell_with_span() {
     assert_eq!(cell.row_span(), 3);
 }
 
+#[test]
+fn test_tablecell_clamps_spans() {
+    let cell = TableCell::new("Merged")
+        .with_col_span(usize::MAX)
+        .with_row_span(usize::MAX);
+    assert_eq!(cell.col_span(), MAX_COL_SPAN);
+    assert_eq!(cell.row_span(), MAX_ROW_SPAN);
+
+    let cell = TableCell::new("Empty").with_col_span(0).with_row_span(0);
+    assert_eq!((cell.col_span(), cell.row_span()), (1, 1));
+
+    let json = format!(
+        r#"{{"content":"x","col_span":{},"row_span":0}}"#,
+        usize::MAX
+    );
+    let cell: TableCell = serde_json::from_str(&json).unwrap();
+    assert_eq!((cell.col_span(), cell.row_span()), (MAX_COL_SPAN, 1));
+}
+
+#[test]
+fn test_tabledata_counts_without_grid() {
+    let table = TableData::new().with_row(TableRow::new(vec![
+        TableCell::new("Wide").with_col_span(MAX_COL_SPAN),
+        TableCell::new("Tall").with_row_span(MAX_ROW_SPAN),
+    ]));
+
+    assert_eq!(table.num_cols(), MAX_COL_SPAN + 1);
+    assert_eq!(table.num_rows(), MAX_ROW_SPAN);
+}
+
 #[test]
 fn test_table_serialization() {
     let table = TableData::new().with_row(TableRow::new(vec![TableCell::new("Test")]));
     let json = serde_json::to_string(&table).expect("Should serialize");
     let _deserialized: TableData = serde_json::from_str(&json).expect("Should deserialize");
 }
+
+/// Header row with a cell spanning two columns, and a body cell spanning two rows
+fn merged_table() -> TableData {
+    TableData::new()
+        .with_header_rows(1)
+        .with_row(TableRow::new(vec![
+            TableCell::new("Name"),
+            TableCell::new("Score").with_col_span(2),
+        ]))
+        .with_row(TableRow::new(vec![
+            TableCell::new("Ann").with_row_span(2),
+            TableCell::new("1"),
+            TableCell::new("2"),
+        ]))
+        .with_row(TableRow::new(vec![
+            TableCell::new("3"),
+            TableCell::new("4"),
+        ]))
+}
+
+#[test]
+fn test_tabledata_resolves_merged_cells() {
+    let table = merged_table();
+
+    assert_eq!(table.num_cols(), 3);
+    assert_eq!(table.num_rows(), 3);
+    assert_eq!(
+        table.to_vec(),
+        vec![
+            vec!["Name", "Score", "Score"],
+            vec!["Ann", "1", "2"],
+            vec!["Ann", "3", "4"],
+        ]
+    );
+
+    let columns: Vec<Vec<&str>> = table.logical_columns().collect();
+    assert_eq!(columns[0], vec!["Name", "Ann", "Ann"]);
+    assert_eq!(columns[2], vec!["Score", "2", "4"]);
+}
+
+#[test]
+fn test_tabledata_header_rows() {
+    let table = merged_table();
+
+    assert_eq!(table.num_header_rows(), 1);
+    assert_eq!(table.header_rows()[0].cells()[0].content(), "Name");
+    assert_eq!(table.body_rows().len(), 2);
+
+    // Header count is clamped to the number of rows
+    let table = TableData::new()
+        .with_row(TableRow::new(vec![TableCell::new("A")]))
+        .with_header_rows(5);
+    assert_eq!(table.header_rows().len(), 1);
+    assert!(table.body_rows().is_empty());
+}
+
+#[test]
+fn test_tabledata_ragged_rows_are_padded() {
+    let table = TableData::new()
+        .with_row(TableRow::new(vec![
+            TableCell::new("A"),
+            TableCell::new("B"),
+        ]))
+        .with_row(TableRow::new(vec![TableCell::new("C")]));
+
+    assert_eq!(table.to_vec(), vec![vec!["A", "B"], vec!["C", ""]]);
+    assert!(table.grid()[1][1].is_none());
+}
+
+#[test]
+fn test_tabledata_to_markdown() {
+    let table = TableData::new()
+        .with_row(TableRow::new(vec![
+            TableCell::new("Key"),
+            TableCell::new("Value"),
+        ]))
+        .with_row(TableRow::new(vec![
+            TableCell::new("a|b"),
+            TableCell::new("line\nbreak"),
+        ]));
+
+    assert_eq!(
+        table.to_markdown(),
+        "| Key | Value |\n|-----|-----|\n| a\\|b | line<br>break |\n"
+    );
+    assert_eq!(TableData::new().to_markdown(), "");
+}
+
+#[test]
+fn test_tabledata_to_csv() {
+    let table = merged_table();
+    assert_eq!(table.to_csv(), "Name,Score,Score\nAnn,1,2\nAnn,3,4\n");
+
+    let table = TableData::new().with_row(TableRow::new(vec![
+        TableCell::new("plain"),
+        TableCell::new("a,b"),
+        TableCell::new("say \"hi\""),
+    ]));
+    assert_eq!(table.to_csv(), "plain,\"a,b\",\"say \"\"hi\"\"\"\n");
+}
@@ -0,0 +1,81 @@
+//! Contract tests for exporting tables to CSV and XLSX files
+
+use std::io::Read;
+
+use docling_rs::cli::output;
+use docling_rs::cli::TableFormat;
+use docling_rs::datamodel::{DocumentNode, NodeType, TableCell, TableData, TableRow};
+use docling_rs::DoclingDocument;
+
+fn invoice_table() -> TableData {
+    TableData::new()
+        .with_row(TableRow::new(vec![
+            TableCell::new("Item"),
+            TableCell::new("Amount").with_col_span(2),
+        ]))
+        .with_row(TableRow::new(vec![
+            TableCell::new("Widgets & bolts"),
+            TableCell::new("12.50"),
+            TableCell::new("007"),
+        ]))
+        .with_header_rows(1)
+}
+
+fn sheet_xml(bytes: Vec<u8>) -> String {
+    let mut archive = zip::ZipArchive::new(std::io::Cursor::new(bytes)).unwrap();
+    let mut xml = String::new();
+    archive
+        .by_name("xl/worksheets/sheet1.xml")
+        .unwrap()
+        .read_to_string(&mut xml)
+        .unwrap();
+    xml
+}
+
+#[test]
+fn test_table_to_xlsx() {
+    let xml = sheet_xml(invoice_table().to_xlsx().unwrap());
+    assert!(xml.contains(
+        "<c r=\"A2\" t=\"inlineStr\"><is><t xml:space=\"preserve\">Widgets &amp; bolts</t></is></c>"
+    ));
+    assert!(xml.contains("<c r=\"B2\"><v>12.50</v></c>"));
+    assert!(
+        xml.contains("<c r=\"C2\" t=\"inlineStr\">"),
+        "leading zeros stay text"
+    );
+    assert!(xml.contains("<mergeCells count=\"1\"><mergeCell ref=\"B1:C1\"/></mergeCells>"));
+}
+
+#[test]
+fn test_export_tables_names_and_contents() {
+    let mut doc = DoclingDocument::new("invoice.pdf");
+    doc.add_node(DocumentNode::new(NodeType::Paragraph, "Invoice 42"));
+    doc.add_node(
+        DocumentNode::new(NodeType::Table, "")
+            .with_table(invoice_table())
+            .with_page(3)
+            .with_caption("Table 1: Line items (EUR)"),
+    );
+    doc.add_node(DocumentNode::new(NodeType::Table, "no cell data"));
+    doc.add_node(DocumentNode::new(NodeType::Table, "").with_table(invoice_table()));
+
+    let dir = tempfile::tempdir().unwrap();
+    let paths = output::export_tables(&doc, dir.path(), "invoice", TableFormat::Csv).unwrap();
+    let names: Vec<_> = paths
+        .iter()
+        .map(|p| p.file_name().unwrap().to_str().unwrap())
+        .collect();
+    assert_eq!(
+        names,
+        [
+            "invoice-table-1-p3-table-1-line-items-eur.csv",
+            "invoice-table-2.csv"
+        ]
+    );
+    let csv = std::fs::read_to_string(&paths[0]).unwrap();
+    assert_eq!(csv, invoice_table().to_csv());
+
+    let paths = output::export_tables(&doc, dir.path(), "invoice", TableFormat::Xlsx).unwrap();
+    assert_eq!(paths.len(), 2);
+    assert!(sheet_xml(std::fs::read(&paths[1]).unwrap()).contains("B1:C1"));
+}
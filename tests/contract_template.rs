@@ -0,0 +1,137 @@
+//! Contract tests for template-based output
+
+#[cfg(feature = "templates")]
+mod templates {
+    use assert_cmd::Command;
+    use docling_rs::chunking::{ApproxTokenizer, BaseChunker, ChunkRecord, HierarchicalChunker};
+    use docling_rs::cli::template::TemplateRenderer;
+    use docling_rs::datamodel::{DocumentNode, NodeType};
+    use docling_rs::DoclingDocument;
+
+    fn sample() -> DoclingDocument {
+        let mut doc = DoclingDocument::new("post.md");
+        doc.add_node(DocumentNode::new(NodeType::Title, "Release notes"));
+        let heading = doc.add_node(DocumentNode::new(NodeType::Heading, "Fixes").with_level(2));
+        doc.add_child(
+            heading,
+            DocumentNode::new(NodeType::Paragraph, "Tables <b> & images."),
+        );
+        doc
+    }
+
+    #[test]
+    fn test_front_matter_template() {
+        let renderer = TemplateRenderer::new(
+            "---\ntitle: {{ title }}\nsource: {{ name }}\n---\n{{ markdown }}",
+        )
+        .unwrap();
+        let out = renderer.render(&sample()).unwrap();
+        assert!(out.starts_with("---\ntitle: Release notes\nsource: post.md\n---\n"));
+        assert!(out.contains("## Fixes"));
+        assert!(out.contains("Tables <b> & images."), "no escaping: {}", out);
+    }
+
+    #[test]
+    fn test_template_iterates_nodes() {
+        let renderer = TemplateRenderer::new(
+            "{% for node in nodes %}{{ node.type }}|{{ node.headings | join(sep=\">\") }}|{{ node.text }}\n{% endfor %}",
+        )
+        .unwrap();
+        assert_eq!(
+            renderer.render(&sample()).unwrap(),
+            "Title||Release notes\nHeading||Fixes\nParagraph|Fixes|Tables <b> & images.\n"
+        );
+    }
+
+    #[test]
+    fn test_template_receives_chunks() {
+        let doc = sample();
+        let chunker = HierarchicalChunker::new();
+        let tokenizer = ApproxTokenizer::new();
+        let chunks: Vec<_> = chunker
+            .chunk(&doc)
+            .map(|chunk| ChunkRecord::new(chunk, &chunker, &tokenizer))
+            .collect();
+        let renderer = TemplateRenderer::new(
+            "{{ chunks | length }} chunks{% for c in chunks %}; {{ c.text }}{% endfor %}",
+        )
+        .unwrap();
+        let out = renderer.render_chunks(&doc, &chunks).unwrap();
+        assert!(out.starts_with(&format!("{} chunks", chunks.len())));
+        assert!(out.contains("Tables <b> & images."));
+        assert_eq!(renderer.render(&doc).unwrap(), "0 chunks");
+    }
+
+    #[test]
+    fn test_invalid_template_is_an_error() {
+        assert!(TemplateRenderer::new("{% for x in %}").is_err());
+        let renderer = TemplateRenderer::new("{{ missing.field }}").unwrap();
+        assert!(renderer.render(&sample()).is_err());
+    }
+
+    #[test]
+    fn test_xml_template_escapes() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = temp.path().join("feed.xml");
+        std::fs::write(&path, "<p>{{ nodes[2].text }}</p>").unwrap();
+        let out = TemplateRenderer::from_file(&path)
+            .unwrap()
+            .render(&sample())
+            .unwrap();
+        assert_eq!(out, "<p>Tables &lt;b&gt; &amp; images.</p>");
+    }
+
+    #[test]
+    fn test_cli_template_output() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let input = temp.path().join("doc.md");
+        std::fs::write(&input, "# Guide\n\nBody text.\n").unwrap();
+        let template = temp.path().join("page.md.tera");
+        std::fs::write(
+            &template,
+            "+++\ntitle = \"{{ title }}\"\n+++\n{{ markdown }}",
+        )
+        .unwrap();
+        let out_dir = temp.path().join("out");
+
+        Command::cargo_bin("docling-rs")
+            .unwrap()
+            .arg(&input)
+            .arg("--template")
+            .arg(&template)
+            .arg("--output-dir")
+            .arg(&out_dir)
+            .assert()
+            .success();
+
+        let output = std::fs::read_to_string(out_dir.join("doc.md")).unwrap();
+        assert!(
+            output.starts_with("+++\ntitle = \"doc.md\"\n+++\n"),
+            "{}",
+            output
+        );
+        assert!(output.contains("Body text."));
+    }
+}
+
+#[cfg(not(feature = "templates"))]
+#[test]
+fn test_template_requires_feature() {
+    use assert_cmd::Command;
+    use predicates::prelude::*;
+
+    let temp = tempfile::TempDir::new().unwrap();
+    let input = temp.path().join("doc.md");
+    std::fs::write(&input, "# Test\n\nBody.\n").unwrap();
+    let template = temp.path().join("page.tera");
+    std::fs::write(&template, "{{ markdown }}").unwrap();
+
+    Command::cargo_bin("docling-rs")
+        .unwrap()
+        .arg(&input)
+        .arg("--template")
+        .arg(&template)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("`templates` feature"));
+}
@@ -32,3 +32,39 @@ fn test_textitem_serialization() {
     let json = serde_json::to_string(&text).expect("Should serialize");
     let _deserialized: TextItem = serde_json::from_str(&json).expect("Should deserialize");
 }
+
+#[test]
+fn test_textitem_spans_to_markdown() {
+    use docling_rs::datamodel::TextSpan;
+
+    let text = TextItem::new("Read the docs now, not later")
+        .with_span(TextSpan::new(0, 4, Formatting::bold()))
+        .with_span(TextSpan::new(9, 13, Formatting::link("https://docs.rs")))
+        .with_span(TextSpan::new(9, 13, Formatting::code()))
+        .with_span(TextSpan::new(19, 28, Formatting::strikethrough()));
+
+    assert_eq!(
+        text.to_markdown(),
+        "**Read** the [`docs`](https://docs.rs) now, ~~not later~~"
+    );
+    assert_eq!(
+        Formatting::link("https://docs.rs").url(),
+        Some("https://docs.rs")
+    );
+}
+
+#[test]
+fn test_textitem_nested_spans_and_whole_formatting() {
+    use docling_rs::datamodel::TextSpan;
+
+    let text = TextItem::new("very important")
+        .with_formatting(Formatting::italic())
+        .with_span(TextSpan::new(5, 14, Formatting::bold()));
+    assert_eq!(text.to_markdown(), "*very **important***");
+
+    // Spans outside the text or off character boundaries are ignored
+    let text = TextItem::new("héllo")
+        .with_span(TextSpan::new(0, 2, Formatting::bold()))
+        .with_span(TextSpan::new(3, 99, Formatting::italic()));
+    assert_eq!(text.to_markdown(), "héllo");
+}
@@ -0,0 +1,136 @@
+//! Contract tests for the document Visitor API
+
+use docling_rs::datamodel::{
+    walk, walk_node, DoclingDocument, DocumentNode, Flow, NodeType, TreeEntry, Visitor,
+};
+
+/// Intro / Background heading with a paragraph, a table and a picture
+fn sample() -> DoclingDocument {
+    let mut doc = DoclingDocument::new("test.md");
+    doc.add_node(DocumentNode::new(NodeType::Paragraph, "Preface"));
+    let intro = doc.add_node(DocumentNode::new(NodeType::Heading, "Intro").with_level(1));
+    let background = doc
+        .add_child(
+            intro,
+            DocumentNode::new(NodeType::Heading, "Background").with_level(2),
+        )
+        .unwrap();
+    doc.add_child(background, DocumentNode::new(NodeType::Paragraph, "Text"));
+    doc.add_child(background, DocumentNode::new(NodeType::Table, "A | B"));
+    doc.add_child(intro, DocumentNode::new(NodeType::Picture, "Figure"));
+    doc
+}
+
+/// Records every callback as a string
+#[derive(Default)]
+struct Recorder {
+    events: Vec<String>,
+    skip: Option<&'static str>,
+    stop: Option<&'static str>,
+}
+
+impl Recorder {
+    fn record(&mut self, kind: &str, entry: TreeEntry<'_>) {
+        let text = entry.node.text_content().unwrap_or_default();
+        self.events
+            .push(format!("{}:{}:{}", kind, entry.depth, text));
+    }
+}
+
+impl Visitor for Recorder {
+    fn enter_node(&mut self, entry: TreeEntry<'_>) -> Flow {
+        match entry.node.text_content() {
+            text if text == self.stop => Flow::Stop,
+            text if text == self.skip => Flow::SkipChildren,
+            _ => Flow::Continue,
+        }
+    }
+
+    fn enter_section(&mut self, entry: TreeEntry<'_>) {
+        self.record("enter", entry);
+    }
+
+    fn leave_section(&mut self, entry: TreeEntry<'_>) {
+        self.record("leave", entry);
+    }
+
+    fn visit_text(&mut self, entry: TreeEntry<'_>) {
+        self.record("text", entry);
+    }
+
+    fn visit_table(&mut self, entry: TreeEntry<'_>) {
+        self.record("table", entry);
+    }
+
+    fn visit_picture(&mut self, entry: TreeEntry<'_>) {
+        self.record("picture", entry);
+    }
+}
+
+#[test]
+fn test_walk_visits_tree_in_order() {
+    let mut recorder = Recorder::default();
+    walk(&sample(), &mut recorder);
+
+    assert_eq!(
+        recorder.events,
+        vec![
+            "text:0:Preface",
+            "enter:0:Intro",
+            "enter:1:Background",
+            "text:2:Text",
+            "table:2:A | B",
+            "leave:1:Background",
+            "picture:1:Figure",
+            "leave:0:Intro",
+        ]
+    );
+}
+
+#[test]
+fn test_walk_skip_children_and_stop() {
+    let mut recorder = Recorder {
+        skip: Some("Background"),
+        ..Default::default()
+    };
+    walk(&sample(), &mut recorder);
+    assert!(recorder.events.contains(&"leave:1:Background".to_string()));
+    assert!(!recorder.events.iter().any(|e| e.starts_with("text:2")));
+
+    let mut recorder = Recorder {
+        stop: Some("A | B"),
+        ..Default::default()
+    };
+    walk(&sample(), &mut recorder);
+    assert_eq!(
+        recorder.events,
+        vec![
+            "text:0:Preface",
+            "enter:0:Intro",
+            "enter:1:Background",
+            "text:2:Text"
+        ]
+    );
+}
+
+#[test]
+fn test_walk_node_visits_subtree() {
+    let doc = sample();
+    let (background, _) = doc
+        .depth_first()
+        .find(|e| e.node.text_content() == Some("Background"))
+        .map(|e| (e.id, e.node))
+        .unwrap();
+
+    let mut recorder = Recorder::default();
+    walk_node(&doc, background, &mut recorder);
+    assert_eq!(
+        recorder.events,
+        vec![
+            "enter:0:Background",
+            "text:1:Text",
+            "table:1:A | B",
+            "leave:0:Background",
+        ]
+    );
+}
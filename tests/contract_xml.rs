@@ -0,0 +1,77 @@
+//! Contract tests for XML export
+
+use docling_rs::cli::output;
+use docling_rs::datamodel::{
+    BoundingBox, DocumentNode, NodeType, PictureItem, SourcePosition, TableCell, TableData,
+    TableRow,
+};
+use docling_rs::DoclingDocument;
+
+#[test]
+fn test_xml_export_tree_and_provenance() {
+    let mut doc = DoclingDocument::new("report \"Q3\".pdf");
+    let heading = doc.add_node(
+        DocumentNode::new(NodeType::Heading, "Results")
+            .with_level(1)
+            .with_page(1)
+            .with_bbox(BoundingBox::new(72.0, 720.0, 300.5, 700.0)),
+    );
+    doc.add_child(
+        heading,
+        DocumentNode::new(NodeType::Paragraph, "a < b & c")
+            .with_position(SourcePosition::new(10, 19, 3, 3)),
+    );
+    doc.add_node(
+        DocumentNode::new(NodeType::Picture, "A chart")
+            .with_picture(PictureItem::file("chart.png"))
+            .with_caption("Figure 1"),
+    );
+    doc.add_node(DocumentNode::new(NodeType::PageBreak, ""));
+
+    assert_eq!(
+        output::to_xml(&doc),
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <document name=\"report &quot;Q3&quot;.pdf\">\n  \
+           <heading id=\"0\" level=\"1\" page=\"1\" bbox=\"72,720,300.5,700\">\n    \
+             <content>Results</content>\n    \
+             <paragraph id=\"1\" lines=\"3-3\" offsets=\"10-19\">a &lt; b &amp; c</paragraph>\n  \
+           </heading>\n  \
+           <picture id=\"2\">\n    \
+             <content>A chart</content>\n    \
+             <caption>Figure 1</caption>\n    \
+             <image src=\"chart.png\"/>\n  \
+           </picture>\n  \
+           <page_break id=\"3\"/>\n\
+         </document>\n"
+    );
+}
+
+#[test]
+fn test_xml_export_tables() {
+    let table = TableData::new()
+        .with_row(TableRow::new(vec![
+            TableCell::new("Name"),
+            TableCell::new("Score").with_col_span(2),
+        ]))
+        .with_row(TableRow::new(vec![
+            TableCell::new("Ann"),
+            TableCell::new("1"),
+            TableCell::new("2"),
+        ]))
+        .with_header_rows(1);
+    let mut doc = DoclingDocument::new("t");
+    doc.add_node(
+        DocumentNode::new(NodeType::Table, table.to_markdown())
+            .with_table(table)
+            .with_caption("Scores"),
+    );
+
+    let xml = output::to_xml(&doc);
+    assert!(xml.contains(
+        "  <table id=\"0\">\n    \
+           <caption>Scores</caption>\n    \
+           <row header=\"true\"><cell>Name</cell><cell col-span=\"2\">Score</cell></row>\n    \
+           <row><cell>Ann</cell><cell>1</cell><cell>2</cell></row>\n  \
+         </table>\n"
+    ));
+}
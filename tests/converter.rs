@@ -51,6 +51,25 @@ fn test_converter_convert_csv_bytes() {
     assert_eq!(result.unwrap().status(), ConversionStatus::Success);
 }
 
+#[cfg(not(feature = "pdf"))]
+#[test]
+fn test_converter_pdf_needs_pdf_feature() {
+    let converter = DocumentConverter::new();
+    let result = converter.convert_bytes(
+        b"%PDF-1.4\n%%EOF\n".to_vec(),
+        "test.pdf".to_string(),
+        docling_rs::InputFormat::PDF,
+    );
+
+    let error = result.unwrap_err();
+    assert!(
+        matches!(error, docling_rs::ConversionError::UnsupportedFormat(_)),
+        "{:?}",
+        error
+    );
+    assert!(error.to_string().contains("`pdf` feature"));
+}
+
 #[test]
 fn test_converter_convert_markdown_file() {
     let converter = DocumentConverter::new();
@@ -76,3 +95,265 @@ fn test_converter_convert_file_not_found() {
 
     assert!(result.is_err());
 }
+
+#[test]
+fn test_converter_convert_many() {
+    use docling_rs::ParallelOptions;
+
+    let dir = tempfile::TempDir::new().unwrap();
+    let mut paths = Vec::new();
+    for i in 0..6 {
+        let path = dir.path().join(format!("doc{}.md", i));
+        std::fs::write(&path, format!("# Doc {}\n\nBody {}.\n", i, i)).unwrap();
+        paths.push(path);
+    }
+    paths.insert(2, dir.path().join("missing.md"));
+    paths.push(dir.path().join("notes.unknown"));
+
+    let converter = DocumentConverter::new();
+    let results = converter.convert_many(&paths, &ParallelOptions::new().with_max_workers(2));
+
+    assert_eq!(results.len(), paths.len());
+    for ((path, result), expected) in results.iter().zip(&paths) {
+        assert_eq!(path, expected, "results keep the input order");
+        let name = path.file_name().unwrap().to_str().unwrap();
+        match name {
+            "missing.md" | "notes.unknown" => assert!(result.is_err()),
+            _ => assert_eq!(result.as_ref().unwrap().document().name(), name),
+        }
+    }
+
+    let empty: Vec<std::path::PathBuf> = Vec::new();
+    assert!(converter
+        .convert_many(&empty, &ParallelOptions::default())
+        .is_empty());
+}
+
+#[test]
+fn test_converter_builder_registers_backends() {
+    use docling_rs::backend::Backend;
+    use docling_rs::datamodel::{DocumentNode, DocumentSource, InputDocument, NodeType};
+    use docling_rs::{ConversionError, DoclingDocument, InputFormat};
+
+    /// Reads `key = value` lines as one paragraph per entry
+    struct IniBackend;
+
+    impl Backend for IniBackend {
+        fn convert(&self, input: &InputDocument) -> Result<DoclingDocument, ConversionError> {
+            let text = match input.source() {
+                DocumentSource::FilePath(path) | DocumentSource::Remote { path, .. } => {
+                    std::fs::read_to_string(path)?
+                }
+                DocumentSource::Bytes { data, .. } => String::from_utf8_lossy(data).into_owned(),
+            };
+            let nodes = text
+                .lines()
+                .filter_map(|line| line.split_once('='))
+                .map(|(key, value)| {
+                    DocumentNode::new(
+                        NodeType::Paragraph,
+                        format!("{}: {}", key.trim(), value.trim()),
+                    )
+                })
+                .collect();
+            Ok(DoclingDocument::new(input.name()).with_nodes(nodes))
+        }
+
+        fn supports_format(&self, format: InputFormat) -> bool {
+            format == InputFormat::Text
+        }
+    }
+
+    let dir = tempfile::TempDir::new().unwrap();
+    let ini = dir.path().join("settings.INI");
+    std::fs::write(&ini, "name = docling\nthreads=4\n").unwrap();
+
+    // Unknown extensions are rejected by default
+    assert!(matches!(
+        DocumentConverter::new().convert_file(&ini),
+        Err(ConversionError::UnsupportedFormat(_))
+    ));
+
+    let converter = DocumentConverter::builder()
+        .register_backend(InputFormat::Text, Box::new(IniBackend))
+        .register_extension(".ini", InputFormat::Text)
+        .build();
+    let result = converter.convert_file(&ini).unwrap();
+    let texts: Vec<_> = result
+        .document()
+        .nodes()
+        .iter()
+        .filter_map(|node| node.text_content())
+        .collect();
+    assert_eq!(texts, ["name: docling", "threads: 4"]);
+
+    // Other formats keep their built-in backends
+    let markdown = converter
+        .convert_bytes(
+            b"# Title".to_vec(),
+            "doc.md".to_string(),
+            InputFormat::Markdown,
+        )
+        .unwrap();
+    assert_eq!(
+        markdown.document().nodes()[0].node_type(),
+        NodeType::Heading
+    );
+}
+
+#[test]
+fn test_converter_hooks() {
+    use docling_rs::{ConversionError, DoclingDocument, InputFormat};
+
+    let converter = DocumentConverter::new()
+        .with_before_parse(|_: &str, format: InputFormat, bytes: &mut Vec<u8>| {
+            assert_eq!(format, InputFormat::Markdown);
+            let text = String::from_utf8_lossy(bytes).replace("![pixel](track.gif)\n", "");
+            *bytes = text.into_bytes();
+            Ok(())
+        })
+        .with_after_convert(|doc: &mut DoclingDocument| {
+            doc.metadata_mut().insert("team", "search");
+            Ok(())
+        });
+
+    let mut file = tempfile::Builder::new().suffix(".md").tempfile().unwrap();
+    file.write_all(b"# Title\n\n![pixel](track.gif)\nText.\n")
+        .unwrap();
+    let result = converter.convert_file(file.path()).unwrap();
+    let doc = result.document();
+    assert!(doc
+        .nodes()
+        .iter()
+        .all(|node| node.text_content() != Some("pixel")));
+    assert_eq!(doc.metadata().get("team").unwrap(), "search");
+    // The document still points at the file, not the rewritten bytes
+    assert_eq!(
+        doc.metadata().source_uri(),
+        Some(file.path().display().to_string().as_str())
+    );
+
+    let failing = DocumentConverter::new().with_after_convert(|_: &mut DoclingDocument| {
+        Err(ConversionError::InvalidFile("rejected".to_string()))
+    });
+    let error = failing
+        .convert_bytes(
+            b"# Title".to_vec(),
+            "doc.md".to_string(),
+            InputFormat::Markdown,
+        )
+        .unwrap_err();
+    assert!(matches!(error, ConversionError::InvalidFile(_)));
+}
+
+#[test]
+fn test_converter_convert_streaming() {
+    use docling_rs::pipeline::{ConversionLimits, ExceededLimit};
+    use docling_rs::ConversionError;
+
+    let mut file = tempfile::Builder::new().suffix(".md").tempfile().unwrap();
+    file.write_all(b"# One\n\nFirst part.\n\n# Two\n\nSecond part.\n")
+        .unwrap();
+
+    let converter = DocumentConverter::new();
+    let mut parts = Vec::new();
+    let summary = converter
+        .convert_streaming(file.path(), |part| {
+            parts.push(part);
+            Ok(())
+        })
+        .unwrap();
+    assert_eq!(summary.parts(), 2);
+    assert_eq!(summary.status(), ConversionStatus::Success);
+    let whole = converter.convert_file(file.path()).unwrap();
+    assert_eq!(summary.nodes(), whole.document().nodes().len());
+    assert_eq!(parts[1].nodes()[0].text_content(), Some("Two"));
+    assert_eq!(parts[1].name(), whole.document().name());
+
+    // An error from the consumer stops the conversion
+    let mut seen = 0;
+    let error = converter
+        .convert_streaming(file.path(), |_| {
+            seen += 1;
+            Err(ConversionError::InvalidFile("full".to_string()))
+        })
+        .unwrap_err();
+    assert!(matches!(error, ConversionError::InvalidFile(_)));
+    assert_eq!(seen, 1);
+
+    // The output size limit counts all parts
+    let limited =
+        DocumentConverter::new().with_limits(ConversionLimits::new().with_max_output_bytes(20));
+    let error = limited
+        .convert_streaming(file.path(), |_| Ok(()))
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        ConversionError::LimitExceeded(ExceededLimit::OutputSize { .. })
+    ));
+}
+
+#[test]
+fn test_converter_builder_format_options() {
+    use docling_rs::backend::pdf::PdfConfig;
+    use docling_rs::backend::HtmlConfig;
+    use docling_rs::InputFormat;
+
+    let html = b"<html><head><title>Report</title></head><body></body></html>".to_vec();
+    let convert = |converter: &DocumentConverter| {
+        converter
+            .convert_bytes(html.clone(), "report.html".to_string(), InputFormat::Html)
+            .unwrap()
+    };
+
+    let result = convert(&DocumentConverter::new());
+    assert_eq!(result.document().metadata().title(), Some("Report"));
+
+    let converter = DocumentConverter::builder()
+        .pdf_config(PdfConfig::default().enable_images(false))
+        .html_config(HtmlConfig::default().extract_metadata(false))
+        .build();
+    let result = convert(&converter);
+    assert_eq!(result.document().metadata().title(), None);
+}
+
+#[test]
+fn test_converter_chunker_selection() {
+    use docling_rs::chunking::{ChunkerOptions, ChunkerRegistry};
+
+    let markdown = b"# Notes\n\nFirst paragraph.\n\nSecond paragraph.\n".to_vec();
+    let converter = DocumentConverter::new();
+    let result = converter
+        .convert_bytes(
+            markdown.clone(),
+            "notes.md".to_string(),
+            docling_rs::InputFormat::Markdown,
+        )
+        .unwrap();
+    // Hierarchical by default: one chunk per paragraph
+    assert_eq!(converter.chunk(result.document()).count(), 2);
+
+    let chunker = ChunkerRegistry::new()
+        .create(
+            "hybrid",
+            ChunkerOptions {
+                max_tokens: Some(64),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    let converter = DocumentConverter::new().with_chunker(chunker);
+    let result = converter
+        .convert_bytes(
+            markdown,
+            "notes.md".to_string(),
+            docling_rs::InputFormat::Markdown,
+        )
+        .unwrap();
+    let chunks: Vec<_> = converter.chunk(result.document()).collect();
+    assert_eq!(chunks.len(), 1);
+    assert!(converter
+        .chunker()
+        .contextualize(&chunks[0])
+        .starts_with("Notes\n"));
+}
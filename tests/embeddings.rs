@@ -0,0 +1,285 @@
+//! Tests for chunk embeddings
+
+use docling_rs::chunking::{ApproxTokenizer, BaseChunker, ChunkRecord, HierarchicalChunker};
+use docling_rs::embeddings::{self, Embedder, HashEmbedder};
+use docling_rs::DocumentConverter;
+
+fn cosine(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+#[test]
+fn test_hash_embedder() {
+    let embedder = HashEmbedder::new(64);
+    assert_eq!(embedder.model(), "hash:64");
+
+    let vectors = embedder
+        .embed(&[
+            "Rust document parsing",
+            "rust DOCUMENT parsing!",
+            "Cooking pasta",
+            "",
+        ])
+        .unwrap();
+    assert_eq!(vectors.len(), 4);
+    assert!(vectors.iter().all(|vector| vector.len() == 64));
+
+    // Unit length, case and punctuation insensitive
+    assert!((cosine(&vectors[0], &vectors[0]) - 1.0).abs() < 1e-5);
+    assert!((cosine(&vectors[0], &vectors[1]) - 1.0).abs() < 1e-5);
+    assert!(cosine(&vectors[0], &vectors[2]) < 0.5);
+    assert!(vectors[3].iter().all(|x| *x == 0.0));
+
+    // The same on every run
+    assert_eq!(
+        embedder.embed(&["Rust document parsing"]).unwrap()[0],
+        vectors[0]
+    );
+}
+
+#[test]
+fn test_from_spec() {
+    assert_eq!(embeddings::from_spec("hash").unwrap().model(), "hash:256");
+    assert_eq!(embeddings::from_spec("hash:32").unwrap().model(), "hash:32");
+    assert!(embeddings::from_spec("hash:0").is_err());
+    assert!(embeddings::from_spec("hash:many").is_err());
+    assert!(embeddings::from_spec("bogus").is_err());
+    #[cfg(not(feature = "openai"))]
+    assert!(embeddings::from_spec("openai:text-embedding-3-small").is_err());
+    #[cfg(not(feature = "onnx"))]
+    assert!(embeddings::from_spec("onnx:all-MiniLM-L6-v2")
+        .err()
+        .unwrap()
+        .to_string()
+        .contains("`onnx` feature"));
+}
+
+#[test]
+fn test_embed_records() {
+    let doc = DocumentConverter::new()
+        .convert_bytes(
+            b"# Title\n\nFirst paragraph.\n\nSecond paragraph.\n".to_vec(),
+            "doc.md".to_string(),
+            docling_rs::InputFormat::Markdown,
+        )
+        .unwrap()
+        .document()
+        .clone();
+    let chunker = HierarchicalChunker::new();
+    let mut records: Vec<ChunkRecord> = chunker
+        .chunk(&doc)
+        .map(|chunk| ChunkRecord::new(chunk, &chunker, &ApproxTokenizer::new()))
+        .collect();
+    assert!(records.iter().all(|record| record.embedding.is_none()));
+
+    let embedder = HashEmbedder::new(16);
+    embeddings::embed_records(&mut records, &embedder).unwrap();
+    for record in &records {
+        let expected = embedder.embed(&[&record.contextualized_text]).unwrap();
+        assert_eq!(record.embedding.as_ref(), Some(&expected[0]));
+    }
+
+    // Records round-trip with their embedding
+    let json = serde_json::to_string(&records[0]).unwrap();
+    let parsed: ChunkRecord = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed.embedding, records[0].embedding);
+}
+
+#[cfg(feature = "openai")]
+#[test]
+fn test_openai_embedder() {
+    use docling_rs::embeddings::OpenAiEmbedder;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    let server = std::thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream);
+        let mut length = 0;
+        let mut authorized = false;
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            let lower = line.to_ascii_lowercase();
+            if let Some(value) = lower.strip_prefix("content-length:") {
+                length = value.trim().parse().unwrap();
+            }
+            authorized |= lower.starts_with("authorization: bearer secret");
+            if line == "\r\n" {
+                break;
+            }
+        }
+        let mut body = vec![0; length];
+        reader.read_exact(&mut body).unwrap();
+        let request: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+        // Vectors come back out of order, as the API allows
+        let response =
+            r#"{"data":[{"index":1,"embedding":[0.0,1.0]},{"index":0,"embedding":[1.0,0.0]}]}"#;
+        write!(
+            reader.get_mut(),
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+            response.len(),
+            response
+        )
+        .unwrap();
+        (request, authorized)
+    });
+
+    let embedder = OpenAiEmbedder::new("test-model")
+        .with_base_url(format!("http://{}/v1", address))
+        .with_api_key("secret");
+    let vectors = embedder.embed(&["first", "second"]).unwrap();
+    assert_eq!(vectors, vec![vec![1.0, 0.0], vec![0.0, 1.0]]);
+
+    let (request, authorized) = server.join().unwrap();
+    assert!(authorized);
+    assert_eq!(request["model"], "test-model");
+    assert_eq!(request["input"], serde_json::json!(["first", "second"]));
+}
+
+#[cfg(feature = "openai")]
+#[test]
+fn test_openai_embedder_batches_and_retries() {
+    use docling_rs::embeddings::OpenAiEmbedder;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::time::{Duration, Instant};
+
+    // Rate limited once, asking for a short wait, then two batches
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    let server = std::thread::spawn(move || {
+        let mut inputs = Vec::new();
+        for status in [429, 200, 200] {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(value) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                    length = value.trim().parse().unwrap();
+                }
+                if line == "\r\n" {
+                    break;
+                }
+            }
+            let mut body = vec![0; length];
+            reader.read_exact(&mut body).unwrap();
+            let request: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            let input = request["input"].clone();
+
+            let response = if status == 429 {
+                r#"{"error":{"message":"Rate limit reached"}}"#.to_string()
+            } else {
+                let data: Vec<_> = (0..input.as_array().unwrap().len())
+                    .map(|index| serde_json::json!({ "index": index, "embedding": [inputs.len() as f32, index as f32] }))
+                    .collect();
+                serde_json::json!({ "data": data }).to_string()
+            };
+            write!(
+                reader.get_mut(),
+                "HTTP/1.1 {} Status\r\nContent-Type: application/json\r\nretry-after-ms: 50\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                response.len(),
+                response
+            )
+            .unwrap();
+            inputs.push(input);
+        }
+        inputs
+    });
+
+    let embedder = OpenAiEmbedder::new("test-model")
+        .with_base_url(format!("http://{}/v1", address))
+        .with_batch_size(2)
+        .with_retry_delay(Duration::from_secs(60));
+    let started = Instant::now();
+    let vectors = embedder.embed(&["first", "second", "third"]).unwrap();
+    // The server's delay is used, not the (long) default one
+    assert!(started.elapsed() < Duration::from_secs(30));
+    assert!(started.elapsed() >= Duration::from_millis(50));
+    assert_eq!(
+        vectors,
+        vec![vec![1.0, 0.0], vec![1.0, 1.0], vec![2.0, 0.0]]
+    );
+
+    let inputs = server.join().unwrap();
+    assert_eq!(inputs[0], inputs[1]);
+    assert_eq!(inputs[1], serde_json::json!(["first", "second"]));
+    assert_eq!(inputs[2], serde_json::json!(["third"]));
+}
+
+#[cfg(feature = "openai")]
+#[test]
+fn test_openai_embedder_errors() {
+    use docling_rs::embeddings::OpenAiEmbedder;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::time::Duration;
+
+    // Client errors are not retried
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    let server = std::thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream);
+        let mut line = String::new();
+        while line != "\r\n" {
+            line.clear();
+            reader.read_line(&mut line).unwrap();
+        }
+        let response = r#"{"error":{"message":"Incorrect API key"}}"#;
+        write!(
+            reader.get_mut(),
+            "HTTP/1.1 401 Unauthorized\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            response.len(),
+            response
+        )
+        .unwrap();
+    });
+    let embedder = OpenAiEmbedder::new("test-model")
+        .with_base_url(format!("http://{}/v1", address))
+        .with_retry_delay(Duration::from_millis(1));
+    let error = embedder.embed(&["text"]).unwrap_err().to_string();
+    assert!(error.contains("401"), "{}", error);
+    assert!(error.contains("Incorrect API key"), "{}", error);
+    server.join().unwrap();
+
+    // Nothing to embed, nothing sent
+    assert!(embedder.embed(&[]).unwrap().is_empty());
+}
+
+#[cfg(feature = "onnx")]
+#[test]
+fn test_onnx_embedder_loading_errors() {
+    use docling_rs::embeddings::OnnxEmbedder;
+
+    let temp = tempfile::TempDir::new().unwrap();
+    let error = OnnxEmbedder::from_dir(temp.path())
+        .err()
+        .unwrap()
+        .to_string();
+    assert!(error.contains("model.onnx"), "{}", error);
+
+    // Not a model: refused whether or not ONNX Runtime is installed
+    std::fs::create_dir(temp.path().join("onnx")).unwrap();
+    std::fs::write(temp.path().join("onnx").join("model.onnx"), b"not a model").unwrap();
+    let error = OnnxEmbedder::from_dir(temp.path())
+        .err()
+        .unwrap()
+        .to_string();
+    assert!(error.contains("tokenizer.json"), "{}", error);
+    std::fs::write(
+        temp.path().join("tokenizer.json"),
+        r#"{"version":"1.0","truncation":null,"padding":null,"added_tokens":[],
+        "normalizer":null,"pre_tokenizer":{"type":"Whitespace"},"post_processor":null,
+        "decoder":null,"model":{"type":"WordLevel","vocab":{"[UNK]":0},"unk_token":"[UNK]"}}"#,
+    )
+    .unwrap();
+    assert!(OnnxEmbedder::from_dir(temp.path()).is_err());
+    assert!(embeddings::from_spec(&format!("onnx:{}", temp.path().display())).is_err());
+}
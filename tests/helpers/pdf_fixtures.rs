@@ -116,6 +116,64 @@ pub fn create_multipage_pdf(page_count: usize) -> PathBuf {
     path
 }
 
+/// Create a PDF with a caption and a 200x200 RGB image below it.
+#[allow(dead_code)]
+pub fn create_pdf_with_image() -> PathBuf {
+    // Use tempfile for cross-platform compatibility
+    let temp_file = tempfile::Builder::new()
+        .prefix("test_image_")
+        .suffix(".pdf")
+        .tempfile()
+        .expect("Failed to create temp file");
+
+    let path = temp_file.path().to_path_buf();
+
+    let (doc, page1, layer1) = PdfDocument::new("Image PDF", Mm(210.0), Mm(297.0), "Layer 1");
+    let font = doc.add_builtin_font(BuiltinFont::TimesRoman).unwrap();
+    let current_layer = doc.get_page(page1).get_layer(layer1);
+    current_layer.use_text("Figure 1", 12.0, Mm(10.0), Mm(280.0), &font);
+
+    // Red to blue gradient, uncompressed
+    let mut pixels = Vec::with_capacity(200 * 200 * 3);
+    for _ in 0..200 {
+        for x in 0..200u32 {
+            pixels.extend_from_slice(&[(255 - x) as u8, 0, x as u8]);
+        }
+    }
+    let image = Image::from(ImageXObject {
+        width: Px(200),
+        height: Px(200),
+        color_space: ColorSpace::Rgb,
+        bits_per_component: ColorBits::Bit8,
+        interpolate: false,
+        image_data: pixels,
+        image_filter: None,
+        smask: None,
+        clipping_bbox: None,
+    });
+    image.add_to_layer(
+        current_layer,
+        ImageTransform {
+            translate_x: Some(Mm(10.0)),
+            translate_y: Some(Mm(150.0)),
+            dpi: Some(72.0),
+            ..Default::default()
+        },
+    );
+
+    // Save PDF
+    {
+        let mut writer = BufWriter::new(temp_file.as_file());
+        doc.save(&mut writer).unwrap();
+        writer.flush().unwrap();
+    } // writer is dropped here
+
+    // Persist the temp file
+    temp_file.keep().unwrap();
+
+    path
+}
+
 /// Create a PDF with specific text on each page (for testing reading order).
 #[allow(dead_code)]
 pub fn create_pdf_with_page_texts(texts: &[&str]) -> PathBuf {
@@ -0,0 +1,117 @@
+//! Integration tests for archive inputs (.zip, .tar.gz)
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::fs::{self, File};
+use std::io::Write;
+use tempfile::TempDir;
+
+#[test]
+fn test_zip_archive_preserves_structure() {
+    let temp = TempDir::new().unwrap();
+    let archive_path = temp.path().join("docs.zip");
+    let output_dir = temp.path().join("out");
+
+    let mut zip = zip::ZipWriter::new(File::create(&archive_path).unwrap());
+    let options = zip::write::SimpleFileOptions::default();
+    zip.start_file("root.md", options).unwrap();
+    zip.write_all(b"# Root").unwrap();
+    zip.start_file("nested/inner.md", options).unwrap();
+    zip.write_all(b"# Inner").unwrap();
+    zip.finish().unwrap();
+
+    let mut cmd = Command::cargo_bin("docling-rs").unwrap();
+    cmd.arg(&archive_path)
+        .arg("--output-dir")
+        .arg(&output_dir)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("root.md"))
+        .stdout(predicate::str::contains("inner.md"));
+
+    assert!(output_dir.join("root.md").exists());
+    assert!(output_dir.join("nested").join("inner.md").exists());
+}
+
+#[test]
+fn test_tar_gz_archive() {
+    let temp = TempDir::new().unwrap();
+    let archive_path = temp.path().join("docs.tar.gz");
+    let output_dir = temp.path().join("out");
+
+    let encoder = flate2::write::GzEncoder::new(
+        File::create(&archive_path).unwrap(),
+        flate2::Compression::default(),
+    );
+    let mut builder = tar::Builder::new(encoder);
+    let content = b"# Archived";
+    let mut header = tar::Header::new_gnu();
+    header.set_size(content.len() as u64);
+    header.set_mode(0o644);
+    header.set_cksum();
+    builder
+        .append_data(&mut header, "sub/doc.md", &content[..])
+        .unwrap();
+    builder.into_inner().unwrap().finish().unwrap();
+
+    let mut cmd = Command::cargo_bin("docling-rs").unwrap();
+    cmd.arg(&archive_path)
+        .arg("--to")
+        .arg("json")
+        .arg("--output-dir")
+        .arg(&output_dir)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("doc.md"));
+
+    assert!(output_dir.join("sub").join("doc.json").exists());
+}
+
+#[test]
+fn test_corrupt_archive_fails() {
+    let temp = TempDir::new().unwrap();
+    let archive_path = temp.path().join("broken.zip");
+    fs::write(&archive_path, "not a zip file").unwrap();
+
+    let mut cmd = Command::cargo_bin("docling-rs").unwrap();
+    cmd.arg(&archive_path)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("archive"));
+}
+
+#[test]
+fn test_archive_guardrails() {
+    let temp = TempDir::new().unwrap();
+    let archive_path = temp.path().join("docs.zip");
+
+    let mut zip = zip::ZipWriter::new(File::create(&archive_path).unwrap());
+    let options = zip::write::SimpleFileOptions::default();
+    zip.start_file("a/b/c/deep.md", options).unwrap();
+    zip.write_all(b"# Deep").unwrap();
+    zip.start_file("big.md", options).unwrap();
+    zip.write_all(&vec![b'#'; 1 << 20]).unwrap();
+    zip.finish().unwrap();
+
+    Command::cargo_bin("docling-rs")
+        .unwrap()
+        .arg(&archive_path)
+        .arg("--output-dir")
+        .arg(temp.path().join("out"))
+        .arg("--max-depth")
+        .arg("3")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("nested 4 levels deep"));
+
+    Command::cargo_bin("docling-rs")
+        .unwrap()
+        .arg(&archive_path)
+        .arg("--output-dir")
+        .arg(temp.path().join("out"))
+        .arg("--max-decompressed-bytes")
+        .arg("4096")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("the limit is 4096"));
+}
@@ -65,11 +65,13 @@ fn test_cli_html_to_markdown() {
 #[test]
 fn test_cli_csv_conversion() {
     let temp = TempDir::new().unwrap();
+    let work = TempDir::new().unwrap();
     let input = temp.path().join("data.csv");
 
     fs::write(&input, "name,value\nAlice,100\nBob,200").unwrap();
 
     let mut cmd = Command::cargo_bin("docling-rs").unwrap();
+    cmd.current_dir(work.path());
     cmd.arg(&input)
         .arg("--to")
         .arg("markdown")
@@ -89,10 +91,12 @@ fn test_cli_version_flag() {
 #[test]
 fn test_cli_verbose_output() {
     let temp = TempDir::new().unwrap();
+    let work = TempDir::new().unwrap();
     let input = temp.path().join("test.md");
     fs::write(&input, "# Test").unwrap();
 
     let mut cmd = Command::cargo_bin("docling-rs").unwrap();
+    cmd.current_dir(work.path());
     cmd.arg(&input)
         .arg("--verbose")
         .assert()
@@ -103,13 +107,476 @@ fn test_cli_verbose_output() {
 #[test]
 fn test_cli_quiet_mode() {
     let temp = TempDir::new().unwrap();
+    let work = TempDir::new().unwrap();
     let input = temp.path().join("test.md");
     fs::write(&input, "# Test").unwrap();
 
     let mut cmd = Command::cargo_bin("docling-rs").unwrap();
+    cmd.current_dir(work.path());
     cmd.arg(&input)
         .arg("--quiet")
         .assert()
         .success()
         .stdout(predicate::str::is_empty());
 }
+
+#[test]
+fn test_cli_markdown_to_docling_json() {
+    let temp = TempDir::new().unwrap();
+    let input = temp.path().join("input.md");
+    let output_dir = temp.path().join("output");
+
+    fs::write(&input, "# Heading\n\nParagraph text.").unwrap();
+
+    let mut cmd = Command::cargo_bin("docling-rs").unwrap();
+    cmd.arg(&input)
+        .arg("--to")
+        .arg("docling-json")
+        .arg("--output-dir")
+        .arg(&output_dir)
+        .assert()
+        .success();
+
+    let content = fs::read_to_string(output_dir.join("input.json")).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&content).unwrap();
+    assert_eq!(json["schema_name"], "DoclingDocument");
+    assert_eq!(json["texts"][0]["label"], "section_header");
+}
+
+#[test]
+fn test_cli_markdown_to_html() {
+    let temp = TempDir::new().unwrap();
+    let input = temp.path().join("input.md");
+    let output_dir = temp.path().join("output");
+
+    fs::write(&input, "# Heading\n\nParagraph text.").unwrap();
+
+    let mut cmd = Command::cargo_bin("docling-rs").unwrap();
+    cmd.arg(&input)
+        .arg("--to")
+        .arg("html")
+        .arg("--output-dir")
+        .arg(&output_dir)
+        .assert()
+        .success();
+
+    let content = fs::read_to_string(output_dir.join("input.html")).unwrap();
+    assert!(content.contains("<h2>Heading</h2>"));
+    assert!(content.contains("<p>Paragraph text.</p>"));
+}
+
+#[test]
+fn test_cli_split_by_heading() {
+    let temp = TempDir::new().unwrap();
+    let input = temp.path().join("manual.md");
+    let output_dir = temp.path().join("output");
+
+    fs::write(
+        &input,
+        "Intro text.\n\n# Getting Started\n\nInstall it.\n\n## Details\n\nMore.\n\n# FAQ / Help?\n\nAsk.\n",
+    )
+    .unwrap();
+
+    Command::cargo_bin("docling-rs")
+        .unwrap()
+        .arg(&input)
+        .arg("--split-by")
+        .arg("heading1")
+        .arg("--output-dir")
+        .arg(&output_dir)
+        .assert()
+        .success();
+
+    let dir = output_dir.join("manual");
+    let chapter = fs::read_to_string(dir.join("02-getting-started.md")).unwrap();
+    assert!(chapter.contains("Install it."));
+    assert!(chapter.contains("Details"));
+    assert!(!chapter.contains("Ask."));
+    assert!(fs::read_to_string(dir.join("03-faq-help.md"))
+        .unwrap()
+        .contains("Ask."));
+    assert!(dir.join("01-manual-md.md").exists());
+
+    let index = fs::read_to_string(dir.join("index.md")).unwrap();
+    assert!(index.contains("2. [Getting Started](02-getting-started.md)"));
+    assert!(index.contains("3. [FAQ / Help?](03-faq-help.md)"));
+    assert!(!output_dir.join("manual.md").exists());
+}
+
+#[test]
+fn test_cli_split_by_json_index() {
+    let temp = TempDir::new().unwrap();
+    let input = temp.path().join("guide.md");
+    fs::write(&input, "# One\n\nA.\n\n# Two\n\nB.\n").unwrap();
+
+    Command::cargo_bin("docling-rs")
+        .unwrap()
+        .arg(&input)
+        .arg("--split-by")
+        .arg("heading1")
+        .arg("--to")
+        .arg("json")
+        .arg("--output-dir")
+        .arg(temp.path().join("out"))
+        .assert()
+        .success();
+
+    let dir = temp.path().join("out").join("guide");
+    let index: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(dir.join("index.json")).unwrap()).unwrap();
+    assert_eq!(index["sections"][0]["file"], "01-one.json");
+    assert_eq!(index["sections"][1]["title"], "Two");
+    assert!(dir.join("02-two.json").exists());
+}
+
+#[test]
+fn test_cli_max_output_bytes() {
+    let temp = TempDir::new().unwrap();
+    let input = temp.path().join("doc.md");
+    fs::write(&input, "# Title\n\nA paragraph of text.\n").unwrap();
+
+    Command::cargo_bin("docling-rs")
+        .unwrap()
+        .arg(&input)
+        .arg("--max-output-bytes")
+        .arg("8")
+        .arg("--output-dir")
+        .arg(temp.path().join("out"))
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Limit exceeded"));
+
+    assert!(!temp.path().join("out").join("doc.md").exists());
+}
+
+#[test]
+fn test_cli_reports_conversion_warnings() {
+    let temp = TempDir::new().unwrap();
+    let input = temp.path().join("doc.md");
+    fs::write(&input, "# Title\n\n```\nunclosed\n").unwrap();
+
+    Command::cargo_bin("docling-rs")
+        .unwrap()
+        .arg(&input)
+        .arg("--output-dir")
+        .arg(temp.path().join("out"))
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("WARN"))
+        .stderr(predicate::str::contains("doc.md"))
+        .stderr(predicate::str::contains("is never closed"));
+
+    assert!(temp.path().join("out").join("doc.md").exists());
+}
+
+#[test]
+fn test_cli_config_profile() {
+    let temp = TempDir::new().unwrap();
+    let input = temp.path().join("doc.md");
+    fs::write(&input, "# Title\n\nSome text.\n").unwrap();
+    let profile = temp.path().join("docling.yaml");
+    fs::write(&profile, "output:\n  format: json\n  json_compact: true\n").unwrap();
+
+    // The profile sets the format and output directory defaults
+    Command::cargo_bin("docling-rs")
+        .unwrap()
+        .arg(&input)
+        .arg("--config")
+        .arg(&profile)
+        .arg("--output-dir")
+        .arg(temp.path().join("out"))
+        .assert()
+        .success();
+    let json = fs::read_to_string(temp.path().join("out").join("doc.json")).unwrap();
+    assert!(!json.contains('\n'));
+
+    // Flags given on the command line win
+    Command::cargo_bin("docling-rs")
+        .unwrap()
+        .arg(&input)
+        .arg("--config")
+        .arg(&profile)
+        .arg("--to")
+        .arg("text")
+        .arg("--output-dir")
+        .arg(temp.path().join("text"))
+        .assert()
+        .success();
+    assert!(temp.path().join("text").join("doc.txt").exists());
+
+    fs::write(&profile, "output:\n  format: pdf\n").unwrap();
+    Command::cargo_bin("docling-rs")
+        .unwrap()
+        .arg(&input)
+        .arg("--config")
+        .arg(&profile)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("output.format"));
+}
+
+#[test]
+fn test_cli_user_and_project_config_files() {
+    let temp = TempDir::new().unwrap();
+    let config_home = temp.path().join("xdg");
+    fs::create_dir_all(config_home.join("docling-rs")).unwrap();
+    fs::write(
+        config_home.join("docling-rs").join("config.toml"),
+        "[output]\nformat = \"json\"\ndir = \"from-user\"\n",
+    )
+    .unwrap();
+    let project = temp.path().join("project");
+    let work_dir = project.join("docs");
+    fs::create_dir_all(&work_dir).unwrap();
+    fs::write(
+        project.join(".docling.toml"),
+        "[output]\nformat = \"text\"\n",
+    )
+    .unwrap();
+    fs::write(work_dir.join("doc.md"), "# Title\n\nSome text.\n").unwrap();
+
+    let run = |extra: &[&str]| {
+        Command::cargo_bin("docling-rs")
+            .unwrap()
+            .current_dir(&work_dir)
+            .env("XDG_CONFIG_HOME", &config_home)
+            .arg("doc.md")
+            .args(extra)
+            .assert()
+            .success();
+    };
+
+    // The project file overrides the user file, which still sets the directory
+    run(&[]);
+    assert!(work_dir.join("from-user").join("doc.txt").exists());
+
+    // Flags given on the command line win over both
+    run(&["--to", "markdown", "--output-dir", "flags"]);
+    assert!(work_dir.join("flags").join("doc.md").exists());
+
+    run(&["--no-config", "--to", "json"]);
+    assert!(work_dir.join("doc.json").exists());
+}
+
+#[test]
+fn test_cli_verbose_metrics() {
+    let temp = TempDir::new().unwrap();
+    let input = temp.path().join("test.md");
+    fs::write(&input, "# Test\n\nSome text.").unwrap();
+
+    let mut cmd = Command::cargo_bin("docling-rs").unwrap();
+    cmd.arg(&input)
+        .arg("--verbose")
+        .arg("--chunk")
+        .arg("--output-dir")
+        .arg(temp.path().join("out"))
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("metrics: nodes: 2,"))
+        .stderr(predicate::str::contains("chunking:"));
+}
+
+#[test]
+fn test_cli_log_level() {
+    let temp = TempDir::new().unwrap();
+    let input = temp.path().join("doc.md");
+    fs::write(&input, "# Title\n\n```\nunclosed\n").unwrap();
+
+    // Warnings are logged by default, not below
+    for args in [&["--log-level", "error"][..], &["--quiet"][..]] {
+        Command::cargo_bin("docling-rs")
+            .unwrap()
+            .arg(&input)
+            .args(args)
+            .arg("--output-dir")
+            .arg(temp.path().join("out"))
+            .assert()
+            .success()
+            .stderr(predicate::str::is_empty());
+    }
+
+    Command::cargo_bin("docling-rs")
+        .unwrap()
+        .arg(&input)
+        .arg("--log-level")
+        .arg("verbose")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("debug"));
+}
+
+#[test]
+fn test_cli_json_logs() {
+    let temp = TempDir::new().unwrap();
+    let input_dir = temp.path().join("docs");
+    fs::create_dir(&input_dir).unwrap();
+    fs::write(input_dir.join("doc.md"), "# Title\n\n```\nunclosed\n").unwrap();
+
+    let output = Command::cargo_bin("docling-rs")
+        .unwrap()
+        .arg(&input_dir)
+        .arg("--log-level")
+        .arg("debug")
+        .arg("--log-format")
+        .arg("json")
+        .arg("--output-dir")
+        .arg(temp.path().join("out"))
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let logs: Vec<serde_json::Value> = String::from_utf8_lossy(&output.stderr)
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+
+    // The warning, in the span of the file it is about
+    let warning = logs.iter().find(|log| log["level"] == "WARN").unwrap();
+    assert!(warning["fields"]["message"]
+        .as_str()
+        .unwrap()
+        .contains("is never closed"));
+    assert_eq!(warning["spans"][0]["name"], "convert_file");
+    assert!(warning["spans"][0]["input"]
+        .as_str()
+        .unwrap()
+        .ends_with("doc.md"));
+
+    // Closed spans, with their durations
+    let closed: Vec<&str> = logs
+        .iter()
+        .filter(|log| log["fields"]["message"] == "close")
+        .map(|log| log["span"]["name"].as_str().unwrap())
+        .collect();
+    for span in ["parse", "enrich", "pipeline", "convert_file"] {
+        assert!(closed.contains(&span), "no {} span in {:?}", span, closed);
+    }
+    assert!(logs
+        .iter()
+        .any(|log| log["fields"]["time.busy"].is_string()));
+}
+
+#[test]
+fn test_cli_stdin_to_stdout() {
+    let temp = TempDir::new().unwrap();
+    let work = TempDir::new().unwrap();
+
+    Command::cargo_bin("docling-rs")
+        .unwrap()
+        .current_dir(work.path())
+        .current_dir(work.path())
+        .arg("-")
+        .arg("--from")
+        .arg("html")
+        .arg("--to")
+        .arg("json")
+        .write_stdin("<html><head><title>Piped</title></head><body></body></html>")
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("{"))
+        .stdout(predicate::str::contains("Piped"))
+        .stdout(predicate::str::contains("stdin.html"));
+
+    Command::cargo_bin("docling-rs")
+        .unwrap()
+        .current_dir(work.path())
+        .current_dir(work.path())
+        .args(["-", "--from", "markdown", "--to", "text"])
+        .write_stdin("# Title\n\nFrom a pipe.")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("From a pipe."));
+    // Nothing was written next to the working directory
+    assert_eq!(fs::read_dir(temp.path()).unwrap().count(), 0);
+
+    Command::cargo_bin("docling-rs")
+        .unwrap()
+        .current_dir(work.path())
+        .arg("-")
+        .write_stdin("# Title")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--from"));
+
+    Command::cargo_bin("docling-rs")
+        .unwrap()
+        .current_dir(work.path())
+        .args(["-", "--from", "markdown", "--split-by", "heading1"])
+        .write_stdin("# Title")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--split-by"));
+}
+
+#[test]
+fn test_cli_completions() {
+    for (shell, expected) in [
+        ("bash", "--output-dir"),
+        ("zsh", "#compdef docling-rs"),
+        ("fish", "complete -c docling-rs"),
+        ("powershell", "Register-ArgumentCompleter"),
+    ] {
+        Command::cargo_bin("docling-rs")
+            .unwrap()
+            .arg("completions")
+            .arg(shell)
+            .assert()
+            .success()
+            .stdout(predicate::str::contains(expected))
+            .stdout(predicate::str::contains("combine"));
+    }
+
+    Command::cargo_bin("docling-rs")
+        .unwrap()
+        .arg("completions")
+        .arg("tcsh")
+        .assert()
+        .failure();
+
+    // An input is still needed without the subcommand
+    Command::cargo_bin("docling-rs")
+        .unwrap()
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("<INPUT>"));
+}
+
+#[test]
+fn test_cli_export_tables() {
+    let temp = TempDir::new().unwrap();
+    let markdown = temp.path().join("report.md");
+    let csv = temp.path().join("scores.csv");
+    let output_dir = temp.path().join("output");
+
+    fs::write(
+        &markdown,
+        "# Results\n\n| Model | Score |\n|:------|------:|\n| a | 1 |\n| b \\| c | 2 |\n\nDone.\n",
+    )
+    .unwrap();
+    fs::write(&csv, "name,score\nann,3\n").unwrap();
+
+    Command::cargo_bin("docling-rs")
+        .unwrap()
+        .arg(&markdown)
+        .arg("--output-dir")
+        .arg(&output_dir)
+        .arg("--export-tables")
+        .assert()
+        .success();
+    assert_eq!(
+        fs::read_to_string(output_dir.join("report-table-1.csv")).unwrap(),
+        "Model,Score\na,1\nb | c,2\n"
+    );
+
+    Command::cargo_bin("docling-rs")
+        .unwrap()
+        .arg(&csv)
+        .arg("--output-dir")
+        .arg(&output_dir)
+        .arg("--export-tables")
+        .arg("xlsx")
+        .assert()
+        .success();
+    let xlsx = fs::read(output_dir.join("scores-table-1.xlsx")).unwrap();
+    assert!(xlsx.starts_with(b"PK"), "xlsx files are zip archives");
+}
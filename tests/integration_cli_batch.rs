@@ -10,6 +10,7 @@ use tempfile::TempDir;
 #[test]
 fn test_batch_mixed_formats() {
     let temp = TempDir::new().unwrap();
+    let work = TempDir::new().unwrap();
     let input_dir = temp.path().join("docs");
     fs::create_dir(&input_dir).unwrap();
 
@@ -18,6 +19,7 @@ fn test_batch_mixed_formats() {
     fs::write(input_dir.join("doc3.csv"), "a,b\n1,2").unwrap();
 
     let mut cmd = Command::cargo_bin("docling-rs").unwrap();
+    cmd.current_dir(work.path());
     cmd.arg(&input_dir)
         .arg("--to")
         .arg("json")
@@ -31,6 +33,7 @@ fn test_batch_mixed_formats() {
 #[test]
 fn test_batch_with_subdirectories() {
     let temp = TempDir::new().unwrap();
+    let work = TempDir::new().unwrap();
     let input_dir = temp.path().join("docs");
     let subdir = input_dir.join("subdir");
     fs::create_dir_all(&subdir).unwrap();
@@ -39,6 +42,7 @@ fn test_batch_with_subdirectories() {
     fs::write(subdir.join("nested.md"), "# Nested").unwrap();
 
     let mut cmd = Command::cargo_bin("docling-rs").unwrap();
+    cmd.current_dir(work.path());
     cmd.arg(&input_dir)
         .assert()
         .success()
@@ -49,6 +53,7 @@ fn test_batch_with_subdirectories() {
 #[test]
 fn test_batch_partial_failure() {
     let temp = TempDir::new().unwrap();
+    let work = TempDir::new().unwrap();
     let input_dir = temp.path().join("docs");
     fs::create_dir(&input_dir).unwrap();
 
@@ -57,6 +62,7 @@ fn test_batch_partial_failure() {
     fs::write(input_dir.join("good2.md"), "# Also Good").unwrap();
 
     let mut cmd = Command::cargo_bin("docling-rs").unwrap();
+    cmd.current_dir(work.path());
     cmd.arg(&input_dir)
         .arg("--continue-on-error")
         .assert()
@@ -92,6 +98,7 @@ fn test_batch_output_directory_structure() {
 #[test]
 fn test_batch_format_filter_multiple() {
     let temp = TempDir::new().unwrap();
+    let work = TempDir::new().unwrap();
     let input_dir = temp.path().join("docs");
     fs::create_dir(&input_dir).unwrap();
 
@@ -101,6 +108,7 @@ fn test_batch_format_filter_multiple() {
     fs::write(input_dir.join("doc.txt"), "text").unwrap();
 
     let mut cmd = Command::cargo_bin("docling-rs").unwrap();
+    cmd.current_dir(work.path());
     cmd.arg(&input_dir)
         .arg("--from")
         .arg("markdown,html")
@@ -111,3 +119,433 @@ fn test_batch_format_filter_multiple() {
         .stdout(predicate::str::contains("doc.csv").not())
         .stdout(predicate::str::contains("doc.txt").not());
 }
+
+#[test]
+fn test_batch_exclude_patterns() {
+    let temp = TempDir::new().unwrap();
+    let input_dir = temp.path().join("docs");
+    let output_dir = temp.path().join("out");
+    fs::create_dir_all(input_dir.join("node_modules/pkg")).unwrap();
+    fs::create_dir_all(input_dir.join("drafts")).unwrap();
+    fs::create_dir_all(input_dir.join("guide/drafts")).unwrap();
+
+    fs::write(input_dir.join("readme.md"), "# Readme").unwrap();
+    fs::write(input_dir.join("notes.tmp.md"), "# Notes").unwrap();
+    fs::write(input_dir.join("node_modules/pkg/readme.md"), "# Package").unwrap();
+    fs::write(input_dir.join("drafts/idea.md"), "# Idea").unwrap();
+    fs::write(input_dir.join("guide/intro.md"), "# Intro").unwrap();
+    fs::write(input_dir.join("guide/drafts/wip.md"), "# Wip").unwrap();
+
+    Command::cargo_bin("docling-rs")
+        .unwrap()
+        .arg(&input_dir)
+        .arg("--exclude")
+        .arg("node_modules")
+        .arg("--exclude")
+        .arg("*.tmp.md")
+        .arg("--exclude")
+        .arg("/drafts/**")
+        .arg("--output-dir")
+        .arg(&output_dir)
+        .assert()
+        .success();
+
+    assert!(output_dir.join("readme.md").exists());
+    assert!(output_dir.join("guide/intro.md").exists());
+    // Anchored patterns only match at the root
+    assert!(output_dir.join("guide/drafts/wip.md").exists());
+    assert!(!output_dir.join("notes.tmp.md").exists());
+    assert!(!output_dir.join("node_modules").exists());
+    assert!(!output_dir.join("drafts").exists());
+
+    Command::cargo_bin("docling-rs")
+        .unwrap()
+        .arg(&input_dir)
+        .arg("--exclude")
+        .arg("[a-")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("[a-"));
+}
+
+#[test]
+fn test_batch_no_recursive() {
+    let temp = TempDir::new().unwrap();
+    let input_dir = temp.path().join("docs");
+    let output_dir = temp.path().join("out");
+    fs::create_dir_all(input_dir.join("subdir")).unwrap();
+    fs::write(input_dir.join("root.md"), "# Root").unwrap();
+    fs::write(input_dir.join("subdir/nested.md"), "# Nested").unwrap();
+
+    Command::cargo_bin("docling-rs")
+        .unwrap()
+        .arg(&input_dir)
+        .arg("--no-recursive")
+        .arg("--output-dir")
+        .arg(&output_dir)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("root.md"))
+        .stdout(predicate::str::contains("nested.md").not());
+
+    assert!(output_dir.join("root.md").exists());
+    assert!(!output_dir.join("subdir").exists());
+}
+
+#[test]
+fn test_batch_resume_skips_converted_files() {
+    use docling_rs::cli::journal::{Journal, JOURNAL_FILE};
+
+    let temp = TempDir::new().unwrap();
+    let input_dir = temp.path().join("docs");
+    let output_dir = temp.path().join("output");
+    fs::create_dir(&input_dir).unwrap();
+    fs::write(input_dir.join("done.md"), "# Done").unwrap();
+    fs::write(input_dir.join("edited.md"), "# Before").unwrap();
+    fs::write(input_dir.join("todo.md"), "# Todo").unwrap();
+
+    // An interrupted run converted two files, one of which changed since
+    let journal_path = output_dir.join(JOURNAL_FILE);
+    let mut journal = Journal::open(&journal_path).unwrap();
+    journal
+        .record(&input_dir, &input_dir.join("done.md"))
+        .unwrap();
+    journal
+        .record(&input_dir, &input_dir.join("edited.md"))
+        .unwrap();
+    drop(journal);
+    fs::write(input_dir.join("edited.md"), "# After").unwrap();
+
+    Command::cargo_bin("docling-rs")
+        .unwrap()
+        .arg(&input_dir)
+        .arg("--output-dir")
+        .arg(&output_dir)
+        .arg("--resume")
+        .assert()
+        .success();
+
+    assert!(!output_dir.join("done.md").exists());
+    assert!(output_dir.join("edited.md").exists());
+    assert!(output_dir.join("todo.md").exists());
+    assert!(!journal_path.exists(), "complete run removes the journal");
+}
+
+#[test]
+fn test_batch_resume_keeps_journal_on_failure() {
+    use docling_rs::cli::journal::{Journal, JOURNAL_FILE};
+
+    let temp = TempDir::new().unwrap();
+    let input_dir = temp.path().join("docs");
+    let output_dir = temp.path().join("output");
+    fs::create_dir(&input_dir).unwrap();
+    fs::write(input_dir.join("good.md"), "# Good").unwrap();
+    fs::write(input_dir.join("bad.docx"), "not a docx").unwrap();
+
+    Command::cargo_bin("docling-rs")
+        .unwrap()
+        .arg(&input_dir)
+        .arg("--output-dir")
+        .arg(&output_dir)
+        .arg("--resume")
+        .arg("--continue-on-error")
+        .assert()
+        .success();
+
+    let journal = Journal::open(&output_dir.join(JOURNAL_FILE)).unwrap();
+    assert_eq!(journal.len(), 1);
+    assert!(journal.is_done(&input_dir, &input_dir.join("good.md")));
+    assert!(!journal.is_done(&input_dir, &input_dir.join("bad.docx")));
+}
+
+#[test]
+fn test_batch_report() {
+    let temp = TempDir::new().unwrap();
+    let input_dir = temp.path().join("docs");
+    let output_dir = temp.path().join("output");
+    let report_path = temp.path().join("audit").join("report.json");
+    fs::create_dir(&input_dir).unwrap();
+    fs::write(input_dir.join("good.md"), "# Good\n\nText.").unwrap();
+    fs::write(input_dir.join("bad.docx"), "not a docx").unwrap();
+
+    Command::cargo_bin("docling-rs")
+        .unwrap()
+        .arg(&input_dir)
+        .arg("--output-dir")
+        .arg(&output_dir)
+        .arg("--report")
+        .arg(&report_path)
+        .arg("--continue-on-error")
+        .assert()
+        .success();
+
+    let report: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&report_path).unwrap()).unwrap();
+    assert_eq!(report["total"], 2);
+    assert_eq!(report["converted"], 1);
+    assert_eq!(report["failed"], 1);
+    assert_eq!(report["aborted"], false);
+    let files = report["files"].as_array().unwrap();
+    let entry = |name: &str| {
+        files
+            .iter()
+            .find(|file| file["input"] == name)
+            .unwrap()
+            .clone()
+    };
+    let good = entry("good.md");
+    assert_eq!(good["status"], "converted");
+    assert_eq!(good["output"], output_dir.join("good.md").to_str().unwrap());
+    assert_eq!(good["metrics"]["node_count"], 2);
+    assert!(good["duration_ms"].is_u64());
+    assert!(good.get("error").is_none());
+    let bad = entry("bad.docx");
+    assert_eq!(bad["status"], "failed");
+    assert!(bad["error"].as_str().unwrap().contains("bad.docx"));
+    assert!(bad.get("metrics").is_none());
+
+    // An aborted run still writes its report
+    Command::cargo_bin("docling-rs")
+        .unwrap()
+        .arg(&input_dir)
+        .arg("--output-dir")
+        .arg(&output_dir)
+        .arg("--report")
+        .arg(&report_path)
+        .arg("--abort-on-error")
+        .assert()
+        .failure();
+    let report: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&report_path).unwrap()).unwrap();
+    assert_eq!(report["aborted"], true);
+    assert_eq!(report["failed"], 1);
+}
+
+#[test]
+fn test_batch_parallel_jobs() {
+    let temp = TempDir::new().unwrap();
+    let input_dir = temp.path().join("docs");
+    let output_dir = temp.path().join("out");
+    fs::create_dir(&input_dir).unwrap();
+    for i in 0..12 {
+        fs::write(
+            input_dir.join(format!("doc{}.md", i)),
+            format!("# Doc {}", i),
+        )
+        .unwrap();
+    }
+    fs::write(input_dir.join("bad.docx"), "not a docx").unwrap();
+
+    let output = Command::cargo_bin("docling-rs")
+        .unwrap()
+        .arg(&input_dir)
+        .arg("--output-dir")
+        .arg(&output_dir)
+        .arg("--jobs")
+        .arg("4")
+        .arg("--continue-on-error")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut converted: Vec<&str> = stdout.lines().collect();
+    converted.sort_unstable();
+    let mut expected: Vec<String> = (0..12).map(|i| format!("doc{}.md", i)).collect();
+    expected.sort_unstable();
+    assert_eq!(converted, expected);
+    for i in 0..12 {
+        let markdown = fs::read_to_string(output_dir.join(format!("doc{}.md", i))).unwrap();
+        assert!(markdown.contains(&format!("Doc {}", i)));
+    }
+
+    // Failures are summed up with their errors once all jobs are done
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let summary = stderr.split("Failed to convert 1 files:").nth(1).unwrap();
+    assert!(summary.contains("bad.docx: "));
+}
+
+#[test]
+fn test_batch_jobs_must_be_positive() {
+    let temp = TempDir::new().unwrap();
+    let work = TempDir::new().unwrap();
+    Command::cargo_bin("docling-rs")
+        .unwrap()
+        .current_dir(work.path())
+        .arg(temp.path())
+        .arg("--jobs")
+        .arg("0")
+        .assert()
+        .failure();
+}
+
+#[test]
+fn test_batch_combine() {
+    let temp = TempDir::new().unwrap();
+    let input_dir = temp.path().join("docs");
+    fs::create_dir_all(input_dir.join("sub")).unwrap();
+    fs::write(input_dir.join("b.md"), "# Beta\n\nSecond.").unwrap();
+    fs::write(input_dir.join("sub").join("a.md"), "# Alpha\n\nFirst.").unwrap();
+    fs::write(input_dir.join("c.html"), "<h1>Gamma</h1><p>Third.</p>").unwrap();
+    let combined = temp.path().join("out").join("corpus.md");
+
+    Command::cargo_bin("docling-rs")
+        .unwrap()
+        .arg(&input_dir)
+        .arg("--combine")
+        .arg(&combined)
+        .arg("--output-dir")
+        .arg(temp.path().join("per-file"))
+        .arg("--jobs")
+        .arg("2")
+        .assert()
+        .success();
+
+    let markdown = fs::read_to_string(&combined).unwrap();
+    assert!(markdown
+        .starts_with("# Index\n\n1. [b.md](#doc-1)\n2. [c.html](#doc-2)\n3. [sub/a.md](#doc-3)\n"));
+    let beta = markdown.find("<a id=\"doc-1\"></a>\n\n# b.md\n").unwrap();
+    let gamma = markdown.find("# c.html\n").unwrap();
+    let alpha = markdown.find("# sub/a.md\n").unwrap();
+    assert!(beta < gamma && gamma < alpha);
+    assert!(markdown[alpha..].contains("First."));
+    // Nothing is written per file
+    assert!(!temp.path().join("per-file").exists());
+
+    let json_path = temp.path().join("corpus.json");
+    Command::cargo_bin("docling-rs")
+        .unwrap()
+        .arg(&input_dir)
+        .arg("--to")
+        .arg("json")
+        .arg("--combine")
+        .arg(&json_path)
+        .assert()
+        .success();
+    let json: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&json_path).unwrap()).unwrap();
+    assert_eq!(
+        json["index"],
+        serde_json::json!(["b.md", "c.html", "sub/a.md"])
+    );
+    assert_eq!(json["documents"][2]["source"], "sub/a.md");
+    assert!(json["documents"][2]["document"].is_object());
+
+    Command::cargo_bin("docling-rs")
+        .unwrap()
+        .arg(&input_dir)
+        .arg("--to")
+        .arg("html")
+        .arg("--combine")
+        .arg(temp.path().join("corpus.html"))
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--combine"));
+}
+
+#[test]
+fn test_batch_progress_display() {
+    use docling_rs::cli::progress::ProgressDisplay;
+    use docling_rs::pipeline::{ProgressEvent, ProgressObserver};
+
+    let display = ProgressDisplay::hidden();
+    display.start_files(2);
+    display.file_started("a.pdf");
+    display.file_started("sub/b.md");
+    display.on_progress(
+        "a.pdf",
+        &ProgressEvent::Page {
+            page: 1,
+            done: 1,
+            total: 3,
+        },
+    );
+    display.on_progress(
+        "a.pdf",
+        &ProgressEvent::Page {
+            page: 2,
+            done: 2,
+            total: 3,
+        },
+    );
+    assert_eq!(display.pages("a.pdf"), Some((2, 3)));
+    assert_eq!(display.files_done(), 0);
+
+    display.on_progress("a.pdf", &ProgressEvent::Finished { nodes: 4 });
+    display.file_done("a.pdf");
+    assert_eq!(display.pages("a.pdf"), None);
+    assert_eq!(display.files_done(), 1);
+    display.file_done("sub/b.md");
+    assert_eq!(display.files_done(), 2);
+    display.finish();
+
+    // Not on a terminal (or with --quiet), no bars are drawn
+    assert!(!ProgressDisplay::enabled(true));
+}
+
+#[test]
+fn test_batch_no_progress_bar_when_piped() {
+    let temp = TempDir::new().unwrap();
+    let input_dir = temp.path().join("docs");
+    fs::create_dir(&input_dir).unwrap();
+    fs::write(input_dir.join("doc.md"), "# Doc").unwrap();
+
+    Command::cargo_bin("docling-rs")
+        .unwrap()
+        .arg(&input_dir)
+        .arg("--output-dir")
+        .arg(temp.path().join("out"))
+        .assert()
+        .success()
+        .stdout("doc.md\n")
+        .stderr("");
+}
+
+/// Polls `check` for up to ten seconds
+fn wait_for(check: impl Fn() -> bool) -> bool {
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(10);
+    while std::time::Instant::now() < deadline {
+        if check() {
+            return true;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(50));
+    }
+    false
+}
+
+#[test]
+fn test_batch_watch_converts_new_and_modified_files() {
+    let temp = TempDir::new().unwrap();
+    let inbox = temp.path().join("inbox");
+    let output_dir = temp.path().join("out");
+    fs::create_dir(&inbox).unwrap();
+    fs::write(inbox.join("a.md"), "# First").unwrap();
+
+    let mut child = std::process::Command::new(assert_cmd::cargo::cargo_bin("docling-rs"))
+        .arg(&inbox)
+        .arg("--watch")
+        .arg("--debounce")
+        .arg("100")
+        .arg("--output-dir")
+        .arg(&output_dir)
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .spawn()
+        .unwrap();
+
+    let read = |name: &str| fs::read_to_string(output_dir.join(name)).unwrap_or_default();
+    let existing = wait_for(|| read("a.md").contains("First"));
+
+    fs::create_dir(inbox.join("sub")).unwrap();
+    fs::write(inbox.join("sub").join("b.md"), "# Dropped").unwrap();
+    let dropped = wait_for(|| read("sub/b.md").contains("Dropped"));
+
+    fs::write(inbox.join("a.md"), "# Second version").unwrap();
+    let modified = wait_for(|| read("a.md").contains("Second version"));
+
+    child.kill().unwrap();
+    child.wait().unwrap();
+    assert!(existing, "existing file not converted");
+    assert!(dropped, "new file not converted");
+    assert!(modified, "modified file not converted again");
+}
@@ -0,0 +1,154 @@
+//! Integration tests for the benchmark subcommand (`docling-rs bench`)
+
+use assert_cmd::Command;
+use docling_rs::cli::bench::{percentile, BenchReport, Sample};
+use docling_rs::cli::InputFormat;
+use predicates::prelude::*;
+use serde_json::Value;
+use std::fs;
+use std::time::Duration;
+use tempfile::TempDir;
+
+fn sample(format: InputFormat, ms: u64, success: bool) -> Sample {
+    Sample {
+        format,
+        bytes: 500_000,
+        pages: if format == InputFormat::Pdf { 4 } else { 0 },
+        duration: Duration::from_millis(ms),
+        success,
+    }
+}
+
+/// A corpus of Markdown, CSV and text files, with one under `drafts/`
+fn corpus() -> TempDir {
+    let temp = TempDir::new().unwrap();
+    let dir = temp.path();
+    fs::write(dir.join("guide.md"), "# Guide\n\nSome text.\n").unwrap();
+    fs::write(dir.join("table.csv"), "name,score\nada,3\nbob,5\n").unwrap();
+    fs::write(dir.join("notes.txt"), "Plain notes.\n").unwrap();
+    fs::write(dir.join("image.png"), [0u8; 8]).unwrap();
+    fs::create_dir(dir.join("drafts")).unwrap();
+    fs::write(dir.join("drafts/draft.md"), "# Draft\n").unwrap();
+    temp
+}
+
+#[test]
+fn test_bench_percentile() {
+    let values: Vec<_> = (1..=10).map(Duration::from_millis).collect();
+    assert_eq!(percentile(&values, 50), Some(Duration::from_millis(5)));
+    assert_eq!(percentile(&values, 90), Some(Duration::from_millis(9)));
+    assert_eq!(percentile(&values, 99), Some(Duration::from_millis(10)));
+    assert_eq!(percentile(&values[..1], 50), Some(Duration::from_millis(1)));
+    assert_eq!(percentile(&[], 50), None);
+}
+
+#[test]
+fn test_bench_report() {
+    let samples = vec![
+        sample(InputFormat::Pdf, 100, true),
+        sample(InputFormat::Pdf, 300, true),
+        sample(InputFormat::Markdown, 2, true),
+        sample(InputFormat::Docx, 50, false),
+    ];
+    let report = BenchReport::new(&samples, Duration::from_secs(2));
+
+    assert_eq!(report.conversions, 4);
+    assert_eq!(report.failed, 1);
+    // Failed conversions count in neither bytes nor pages
+    assert_eq!(report.bytes, 1_500_000);
+    assert_eq!(report.pages, 8);
+    assert!((report.documents_per_sec - 1.5).abs() < 1e-9);
+    assert!((report.pages_per_sec - 4.0).abs() < 1e-9);
+    assert!((report.mb_per_sec - 0.75).abs() < 1e-9);
+
+    let formats: Vec<_> = report.formats.iter().map(|f| f.format.as_str()).collect();
+    assert_eq!(formats, ["docx", "markdown", "pdf"]);
+    let pdf = &report.formats[2];
+    assert_eq!(pdf.conversions, 2);
+    assert_eq!(pdf.p50_ms, Some(100.0));
+    assert_eq!(pdf.max_ms, Some(300.0));
+    let docx = &report.formats[0];
+    assert_eq!(docx.failed, 1);
+    assert_eq!(docx.p50_ms, None);
+
+    let text = report.to_string();
+    assert!(text.contains("Throughput: 1.50 docs/s, 4.00 pages/s, 0.75 MB/s"));
+    assert!(text
+        .lines()
+        .any(|line| line.starts_with("docx") && line.ends_with('-')));
+}
+
+#[test]
+fn test_bench_corpus() {
+    let corpus = corpus();
+    Command::cargo_bin("docling-rs")
+        .unwrap()
+        .arg("bench")
+        .arg(corpus.path())
+        .args(["--iterations", "2", "--warmup", "0"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Corpus: 4 files"))
+        .stdout(predicate::str::contains("Conversions: 8 (0 failed)"))
+        .stdout(predicate::str::contains("Throughput:"))
+        .stdout(predicate::str::contains("Peak memory:"))
+        .stdout(predicate::str::is_match(r"(?m)^markdown\s+4\s+0\s").unwrap());
+}
+
+#[test]
+fn test_bench_json() {
+    let corpus = corpus();
+    let output = Command::cargo_bin("docling-rs")
+        .unwrap()
+        .arg("bench")
+        .arg(corpus.path())
+        .args(["-n", "1", "--jobs", "2", "--chunker", "hybrid"])
+        .args(["--exclude", "drafts", "--json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let report: Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(report["files"], 3);
+    assert_eq!(report["conversions"], 3);
+    assert_eq!(report["jobs"], 2);
+    assert_eq!(report["chunker"], "hybrid");
+    assert_eq!(report["formats"].as_array().unwrap().len(), 3);
+    #[cfg(target_os = "linux")]
+    assert!(report["peak_memory_bytes"].as_u64().unwrap() > 0);
+}
+
+#[test]
+fn test_bench_errors() {
+    let temp = TempDir::new().unwrap();
+    Command::cargo_bin("docling-rs")
+        .unwrap()
+        .arg("bench")
+        .arg(temp.path().join("missing"))
+        .assert()
+        .failure()
+        .code(3);
+
+    // No supported files
+    fs::write(temp.path().join("image.png"), [0u8; 8]).unwrap();
+    Command::cargo_bin("docling-rs")
+        .unwrap()
+        .arg("bench")
+        .arg(temp.path())
+        .assert()
+        .failure()
+        .code(3)
+        .stderr(predicate::str::contains("No files to convert"));
+
+    // Every conversion fails, and each failing file is logged once
+    fs::write(temp.path().join("broken.docx"), "not a zip").unwrap();
+    Command::cargo_bin("docling-rs")
+        .unwrap()
+        .arg("bench")
+        .arg(temp.path())
+        .assert()
+        .failure()
+        .code(1)
+        .stderr(predicate::str::contains("broken.docx").count(1));
+}
@@ -0,0 +1,437 @@
+//! Integration tests for CLI chunking options
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+#[test]
+fn test_fixed_chunker_respects_chunk_size() {
+    let temp = TempDir::new().unwrap();
+    let input = temp.path().join("doc.md");
+    let output_dir = temp.path().join("out");
+    fs::write(
+        &input,
+        "alpha beta gamma delta epsilon zeta eta theta iota kappa lambda mu",
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("docling-rs").unwrap();
+    cmd.arg(&input)
+        .arg("--chunk")
+        .arg("--chunker")
+        .arg("fixed")
+        .arg("--chunk-size")
+        .arg("20")
+        .arg("--to")
+        .arg("json")
+        .arg("--output-dir")
+        .arg(&output_dir)
+        .assert()
+        .success();
+
+    let json = fs::read_to_string(output_dir.join("doc.json")).unwrap();
+    let chunks: Vec<serde_json::Value> = serde_json::from_str(&json).unwrap();
+    assert!(chunks.len() > 1);
+    for chunk in &chunks {
+        assert!(chunk["text"].as_str().unwrap().chars().count() <= 20);
+    }
+}
+
+#[test]
+fn test_semantic_chunker() {
+    let temp = TempDir::new().unwrap();
+    let input = temp.path().join("doc.md");
+    let output_dir = temp.path().join("out");
+    fs::write(
+        &input,
+        "# Pets\n\nThe cat sleeps all day.\n\nThe cat purrs all day.\n\n\
+         Ships sail across the sea.\n",
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("docling-rs").unwrap();
+    cmd.arg(&input)
+        .arg("--chunk")
+        .arg("--chunker")
+        .arg("semantic")
+        .arg("--embedding-model")
+        .arg("hash:64")
+        .arg("--to")
+        .arg("json")
+        .arg("--output-dir")
+        .arg(&output_dir)
+        .assert()
+        .success();
+
+    let json = fs::read_to_string(output_dir.join("doc.json")).unwrap();
+    let chunks: Vec<serde_json::Value> = serde_json::from_str(&json).unwrap();
+    assert_eq!(chunks.len(), 2);
+    assert_eq!(
+        chunks[0]["text"],
+        "The cat sleeps all day. The cat purrs all day."
+    );
+    assert_eq!(chunks[1]["text"], "Ships sail across the sea.");
+}
+
+#[test]
+fn test_unknown_chunker_rejected() {
+    let temp = TempDir::new().unwrap();
+    let input = temp.path().join("doc.md");
+    fs::write(&input, "# Test").unwrap();
+
+    let mut cmd = Command::cargo_bin("docling-rs").unwrap();
+    cmd.arg(&input)
+        .arg("--chunk")
+        .arg("--chunker")
+        .arg("bogus")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("hierarchical"));
+}
+
+#[test]
+fn test_jsonl_chunk_export() {
+    let temp = TempDir::new().unwrap();
+    let input = temp.path().join("doc.md");
+    let output_dir = temp.path().join("out");
+    fs::write(&input, "# Title\n\nFirst paragraph.\n\nSecond paragraph.\n").unwrap();
+
+    let mut cmd = Command::cargo_bin("docling-rs").unwrap();
+    cmd.arg(&input)
+        .arg("--chunk")
+        .arg("--to")
+        .arg("jsonl")
+        .arg("--output-dir")
+        .arg(&output_dir)
+        .assert()
+        .success();
+
+    let jsonl = fs::read_to_string(output_dir.join("doc.jsonl")).unwrap();
+    let records: Vec<serde_json::Value> = jsonl
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0]["text"], "First paragraph.");
+    assert_eq!(records[0]["contextualized_text"], "Title\nFirst paragraph.");
+    assert!(records[0]["token_count"].as_u64().unwrap() > 0);
+    assert_eq!(records[1]["meta"]["index"], 1);
+}
+
+#[test]
+fn test_jsonl_without_chunk_streams_nodes() {
+    let temp = TempDir::new().unwrap();
+    let input = temp.path().join("doc.md");
+    fs::write(&input, "# Test\n\nBody text.\n").unwrap();
+    let output_dir = temp.path().join("out");
+
+    let mut cmd = Command::cargo_bin("docling-rs").unwrap();
+    cmd.arg(&input)
+        .arg("--to")
+        .arg("jsonl")
+        .arg("--output-dir")
+        .arg(&output_dir)
+        .assert()
+        .success();
+
+    let jsonl = fs::read_to_string(output_dir.join("doc.jsonl")).unwrap();
+    let records: Vec<serde_json::Value> = jsonl
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    let body = records.iter().find(|r| r["text"] == "Body text.").unwrap();
+    assert_eq!(body["type"], "Paragraph");
+    assert_eq!(body["headings"], serde_json::json!(["Test"]));
+}
+
+#[test]
+fn test_stats_report() {
+    let temp = TempDir::new().unwrap();
+    let input = temp.path().join("doc.md");
+    fs::write(&input, "# Title\n\nFirst paragraph.\n\nSecond paragraph.\n").unwrap();
+
+    let mut cmd = Command::cargo_bin("docling-rs").unwrap();
+    cmd.arg(&input)
+        .arg("--chunk")
+        .arg("--stats")
+        .arg("--output-dir")
+        .arg(temp.path().join("out"))
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Chunks: 2"))
+        .stderr(predicate::str::contains("p95"));
+}
+
+#[cfg(feature = "huggingface")]
+#[test]
+fn test_hybrid_chunker_with_tokenizer_file() {
+    let temp = TempDir::new().unwrap();
+    let input = temp.path().join("doc.md");
+    let output_dir = temp.path().join("out");
+    fs::write(
+        &input,
+        "# Title\n\none two three four five six seven eight nine ten eleven twelve\n",
+    )
+    .unwrap();
+    // Word-level tokenizer: every word is one token
+    let tokenizer = temp.path().join("tokenizer.json");
+    fs::write(
+        &tokenizer,
+        r#"{"version":"1.0","truncation":null,"padding":null,"added_tokens":[],
+        "normalizer":null,"pre_tokenizer":{"type":"Whitespace"},"post_processor":null,
+        "decoder":null,"model":{"type":"WordLevel","vocab":{"[UNK]":0},"unk_token":"[UNK]"}}"#,
+    )
+    .unwrap();
+
+    Command::cargo_bin("docling-rs")
+        .unwrap()
+        .arg(&input)
+        .arg("--chunk")
+        .arg("--chunker")
+        .arg("hybrid")
+        .arg("--max-tokens")
+        .arg("5")
+        .arg("--tokenizer")
+        .arg(&tokenizer)
+        .arg("--to")
+        .arg("jsonl")
+        .arg("--output-dir")
+        .arg(&output_dir)
+        .assert()
+        .success();
+
+    let jsonl = fs::read_to_string(output_dir.join("doc.jsonl")).unwrap();
+    let records: Vec<serde_json::Value> = jsonl
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert!(records.len() > 1);
+    for record in &records {
+        assert!(record["token_count"].as_u64().unwrap() <= 5);
+    }
+
+    Command::cargo_bin("docling-rs")
+        .unwrap()
+        .arg(&input)
+        .arg("--chunk")
+        .arg("--chunker")
+        .arg("hybrid")
+        .arg("--tokenizer")
+        .arg(temp.path().join("missing.json"))
+        .arg("--output-dir")
+        .arg(&output_dir)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("missing.json"));
+}
+
+#[test]
+fn test_embed_chunks() {
+    let temp = TempDir::new().unwrap();
+    let input = temp.path().join("doc.md");
+    let output_dir = temp.path().join("out");
+    fs::write(&input, "# Title\n\nFirst paragraph.\n\nSecond paragraph.\n").unwrap();
+
+    for format in ["jsonl", "json"] {
+        Command::cargo_bin("docling-rs")
+            .unwrap()
+            .arg(&input)
+            .arg("--embed")
+            .arg("--embedding-model")
+            .arg("hash:16")
+            .arg("--to")
+            .arg(format)
+            .arg("--output-dir")
+            .arg(&output_dir)
+            .assert()
+            .success();
+
+        let output = fs::read_to_string(output_dir.join(format!("doc.{}", format))).unwrap();
+        let chunks: Vec<serde_json::Value> = if format == "jsonl" {
+            output
+                .lines()
+                .map(|line| serde_json::from_str(line).unwrap())
+                .collect()
+        } else {
+            serde_json::from_str(&output).unwrap()
+        };
+        assert!(!chunks.is_empty());
+        for chunk in &chunks {
+            assert_eq!(chunk["embedding"].as_array().unwrap().len(), 16);
+        }
+    }
+
+    // Vectors only fit structured output
+    Command::cargo_bin("docling-rs")
+        .unwrap()
+        .arg(&input)
+        .arg("--embed")
+        .arg("--embedding-model")
+        .arg("hash")
+        .arg("--to")
+        .arg("markdown")
+        .arg("--output-dir")
+        .arg(&output_dir)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--embed"));
+
+    Command::cargo_bin("docling-rs")
+        .unwrap()
+        .arg(&input)
+        .arg("--embed")
+        .arg("--embedding-model")
+        .arg("bogus")
+        .arg("--to")
+        .arg("jsonl")
+        .arg("--output-dir")
+        .arg(&output_dir)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("bogus"));
+}
+
+#[test]
+fn test_embed_chunks_into_store() {
+    let temp = TempDir::new().unwrap();
+    let docs = temp.path().join("docs");
+    let index = temp.path().join("index");
+    fs::create_dir(&docs).unwrap();
+    fs::write(
+        docs.join("a.md"),
+        "# Alpha\n\nFirst paragraph.\n\nSecond paragraph.\n",
+    )
+    .unwrap();
+    fs::write(docs.join("b.md"), "# Beta\n\nOnly paragraph.\n").unwrap();
+
+    let store = |input: &std::path::Path| {
+        let mut cmd = Command::cargo_bin("docling-rs").unwrap();
+        cmd.arg(input)
+            .arg("--embed")
+            .arg("--embedding-model")
+            .arg("hash:32")
+            .arg("--store")
+            .arg(&index)
+            .arg("--output-dir")
+            .arg(temp.path().join("out"));
+        cmd
+    };
+    store(&docs).assert().success();
+
+    let manifest: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(index.join("index.json")).unwrap()).unwrap();
+    assert_eq!(manifest["model"], "hash:32");
+    assert_eq!(manifest["dimensions"], 32);
+    let records: Vec<serde_json::Value> = fs::read_dir(&index)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|e| e == "jsonl"))
+        .flat_map(|path| {
+            fs::read_to_string(path)
+                .unwrap()
+                .lines()
+                .map(|line| serde_json::from_str(line).unwrap())
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    assert_eq!(records.len(), 3);
+    assert!(records
+        .iter()
+        .all(|record| record["embedding"].as_array().unwrap().len() == 32));
+    // No output files
+    assert!(!temp.path().join("out").join("a.json").exists());
+
+    // Storing a changed document replaces its chunks
+    fs::write(docs.join("b.md"), "# Beta\n\nRewritten.\n\nAnd extended.\n").unwrap();
+    store(&docs.join("b.md")).assert().success();
+    let count: usize = fs::read_dir(&index)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|e| e == "jsonl"))
+        .map(|path| fs::read_to_string(path).unwrap().lines().count())
+        .sum();
+    assert_eq!(count, 4);
+
+    // Another model, or a directory that is not an index, is refused
+    Command::cargo_bin("docling-rs")
+        .unwrap()
+        .arg(&docs)
+        .arg("--embed")
+        .arg("--embedding-model")
+        .arg("hash:64")
+        .arg("--store")
+        .arg(&index)
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("hash:32"));
+    Command::cargo_bin("docling-rs")
+        .unwrap()
+        .arg(docs.join("a.md"))
+        .arg("--embed")
+        .arg("--embedding-model")
+        .arg("hash:32")
+        .arg("--store")
+        .arg(&docs)
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("not a docling-rs index"));
+}
+
+#[test]
+fn test_langchain_and_llamaindex_chunk_export() {
+    let temp = TempDir::new().unwrap();
+    let input = temp.path().join("doc.md");
+    let output_dir = temp.path().join("out");
+    fs::write(&input, "# Title\n\nFirst paragraph.\n\nSecond paragraph.\n").unwrap();
+
+    Command::cargo_bin("docling-rs")
+        .unwrap()
+        .arg(&input)
+        .arg("--chunk")
+        .arg("--to")
+        .arg("langchain")
+        .arg("--output-dir")
+        .arg(&output_dir)
+        .assert()
+        .success();
+
+    let json = fs::read_to_string(output_dir.join("doc.json")).unwrap();
+    let documents: Vec<serde_json::Value> = serde_json::from_str(&json).unwrap();
+    assert_eq!(documents.len(), 2);
+    assert_eq!(documents[0]["page_content"], "Title\nFirst paragraph.");
+    assert_eq!(documents[0]["metadata"]["source"], "doc.md");
+    assert_eq!(documents[1]["metadata"]["chunk_index"], 1);
+
+    Command::cargo_bin("docling-rs")
+        .unwrap()
+        .arg(&input)
+        .arg("--chunk")
+        .arg("--to")
+        .arg("llamaindex")
+        .arg("--output-dir")
+        .arg(&output_dir)
+        .assert()
+        .success();
+
+    let json = fs::read_to_string(output_dir.join("doc.json")).unwrap();
+    let nodes: Vec<serde_json::Value> = serde_json::from_str(&json).unwrap();
+    assert_eq!(nodes.len(), 2);
+    assert_eq!(nodes[0]["text"], "First paragraph.");
+    assert_eq!(nodes[0]["metadata"]["headings"][0], "Title");
+    assert_eq!(nodes[0]["relationships"]["3"]["node_id"], nodes[1]["id_"]);
+
+    // Whole documents are not LangChain documents
+    Command::cargo_bin("docling-rs")
+        .unwrap()
+        .arg(&input)
+        .arg("--to")
+        .arg("langchain")
+        .arg("--output-dir")
+        .arg(&output_dir)
+        .assert()
+        .failure()
+        .code(2)
+        .stderr(predicate::str::contains("needs --chunk"));
+}
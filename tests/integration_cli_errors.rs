@@ -10,16 +10,18 @@ use tempfile::TempDir;
 #[test]
 fn test_conflicting_flags_quiet_verbose() {
     let temp = TempDir::new().unwrap();
+    let work = TempDir::new().unwrap();
     let input = temp.path().join("test.md");
     fs::write(&input, "# Test").unwrap();
 
     let mut cmd = Command::cargo_bin("docling-rs").unwrap();
+    cmd.current_dir(work.path());
     cmd.arg(&input)
         .arg("--quiet")
         .arg("--verbose")
         .assert()
         .failure()
-        .code(1)
+        .code(2)
         .stderr(
             predicate::str::contains("conflict").or(predicate::str::contains("cannot be used")),
         );
@@ -28,17 +30,19 @@ fn test_conflicting_flags_quiet_verbose() {
 #[test]
 fn test_conflicting_error_handling_flags() {
     let temp = TempDir::new().unwrap();
+    let work = TempDir::new().unwrap();
     let input_dir = temp.path().join("docs");
     fs::create_dir(&input_dir).unwrap();
     fs::write(input_dir.join("test.md"), "# Test").unwrap();
 
     let mut cmd = Command::cargo_bin("docling-rs").unwrap();
+    cmd.current_dir(work.path());
     cmd.arg(&input_dir)
         .arg("--continue-on-error")
         .arg("--abort-on-error")
         .assert()
         .failure()
-        .code(1)
+        .code(2)
         .stderr(
             predicate::str::contains("conflict").or(predicate::str::contains("cannot be used")),
         );
@@ -47,46 +51,52 @@ fn test_conflicting_error_handling_flags() {
 #[test]
 fn test_invalid_output_format() {
     let temp = TempDir::new().unwrap();
+    let work = TempDir::new().unwrap();
     let input = temp.path().join("test.md");
     fs::write(&input, "# Test").unwrap();
 
     let mut cmd = Command::cargo_bin("docling-rs").unwrap();
+    cmd.current_dir(work.path());
     cmd.arg(&input)
         .arg("--to")
         .arg("invalid-format")
         .assert()
         .failure()
-        .code(1)
+        .code(2)
         .stderr(predicate::str::contains("invalid").or(predicate::str::contains("Invalid")));
 }
 
 #[test]
 fn test_invalid_chunk_size() {
     let temp = TempDir::new().unwrap();
+    let work = TempDir::new().unwrap();
     let input = temp.path().join("test.md");
     fs::write(&input, "# Test").unwrap();
 
     let mut cmd = Command::cargo_bin("docling-rs").unwrap();
+    cmd.current_dir(work.path());
     cmd.arg(&input)
         .arg("--chunk-size")
         .arg("0")
         .assert()
         .failure()
-        .code(1)
+        .code(2)
         .stderr(predicate::str::contains("invalid").or(predicate::str::contains("must be")));
 }
 
 #[test]
 fn test_empty_input_directory() {
     let temp = TempDir::new().unwrap();
+    let work = TempDir::new().unwrap();
     let input_dir = temp.path().join("empty");
     fs::create_dir(&input_dir).unwrap();
 
     let mut cmd = Command::cargo_bin("docling-rs").unwrap();
+    cmd.current_dir(work.path());
     cmd.arg(&input_dir)
         .assert()
         .failure()
-        .code(1)
+        .code(3)
         .stderr(predicate::str::contains("No").or(predicate::str::contains("empty")));
 }
 
@@ -126,11 +136,13 @@ fn test_output_permission_error() {
 #[test]
 fn test_malformed_input_file() {
     let temp = TempDir::new().unwrap();
+    let work = TempDir::new().unwrap();
     let input = temp.path().join("malformed.html");
     // Write intentionally malformed HTML
     fs::write(&input, "<html><body><unclosed>").unwrap();
 
     let mut cmd = Command::cargo_bin("docling-rs").unwrap();
+    cmd.current_dir(work.path());
     cmd.arg(&input).assert().success(); // Should still succeed with best-effort parsing
 }
 
@@ -139,16 +151,105 @@ fn test_missing_required_argument() {
     let mut cmd = Command::cargo_bin("docling-rs").unwrap();
     cmd.assert()
         .failure()
-        .code(1)
+        .code(2)
         .stderr(predicate::str::contains("required").or(predicate::str::contains("INPUT")));
 }
 
 #[test]
 fn test_chunk_without_size() {
     let temp = TempDir::new().unwrap();
+    let work = TempDir::new().unwrap();
     let input = temp.path().join("test.md");
     fs::write(&input, "# Test").unwrap();
 
     let mut cmd = Command::cargo_bin("docling-rs").unwrap();
+    cmd.current_dir(work.path());
     cmd.arg(&input).arg("--chunk").assert().success(); // Should use default chunk size
 }
+
+#[test]
+fn test_exit_code_classes() {
+    let temp = TempDir::new().unwrap();
+    let input = temp.path().join("test.md");
+    fs::write(&input, "# Test").unwrap();
+
+    // Options that cannot be used together
+    Command::cargo_bin("docling-rs")
+        .unwrap()
+        .arg(&input)
+        .arg("--combine")
+        .arg(temp.path().join("all.md"))
+        .assert()
+        .failure()
+        .code(2);
+
+    // Formats unknown on stdin
+    Command::cargo_bin("docling-rs")
+        .unwrap()
+        .arg("-")
+        .arg("--from")
+        .arg("xyz")
+        .write_stdin("content")
+        .assert()
+        .failure()
+        .code(4);
+
+    // Documents that cannot be read
+    let bad = temp.path().join("bad.docx");
+    fs::write(&bad, "not a docx").unwrap();
+    Command::cargo_bin("docling-rs")
+        .unwrap()
+        .arg(&bad)
+        .arg("--output-dir")
+        .arg(temp.path().join("out"))
+        .assert()
+        .failure()
+        .code(1);
+}
+
+#[test]
+fn test_partial_batch_failure_exit_code() {
+    let temp = TempDir::new().unwrap();
+    let input_dir = temp.path().join("docs");
+    fs::create_dir(&input_dir).unwrap();
+    fs::write(input_dir.join("good.md"), "# Good").unwrap();
+    fs::write(input_dir.join("bad.docx"), "not a docx").unwrap();
+
+    Command::cargo_bin("docling-rs")
+        .unwrap()
+        .arg(&input_dir)
+        .arg("--output-dir")
+        .arg(temp.path().join("out"))
+        .assert()
+        .failure()
+        .code(5)
+        .stderr(predicate::str::contains("1 files failed to convert"));
+
+    // Failures are accepted with --continue-on-error
+    Command::cargo_bin("docling-rs")
+        .unwrap()
+        .arg(&input_dir)
+        .arg("--continue-on-error")
+        .arg("--output-dir")
+        .arg(temp.path().join("out"))
+        .assert()
+        .success();
+}
+
+#[test]
+fn test_exit_code_of_errors() {
+    use anyhow::Context;
+    use docling_rs::cli::exit::{failure, ExitCode};
+    use docling_rs::ConversionError;
+
+    let error = Err::<(), _>(failure(ExitCode::InputNotFound, "missing"))
+        .context("while starting")
+        .unwrap_err();
+    assert_eq!(ExitCode::of(&error), ExitCode::InputNotFound);
+    assert_eq!(ExitCode::InputNotFound.code(), 3);
+
+    let error = anyhow::Error::from(ConversionError::UnsupportedFormat("xyz".to_string()));
+    assert_eq!(ExitCode::of(&error), ExitCode::UnsupportedFormat);
+    let error = anyhow::Error::from(ConversionError::ParseError("bad".to_string()));
+    assert_eq!(ExitCode::of(&error), ExitCode::ConversionFailed);
+}
@@ -0,0 +1,260 @@
+//! Integration tests for the MCP server (`docling-rs mcp`)
+
+use assert_cmd::Command;
+use docling_rs::cli::mcp::McpServer;
+use docling_rs::pipeline::ConversionLimits;
+use serde_json::{json, Value};
+use std::fs;
+use tempfile::TempDir;
+
+const MARKDOWN: &str =
+    "# Field Notes\n\nThe river rose overnight.\n\n## Birds\n\n- heron\n- kingfisher\n";
+
+fn server() -> McpServer {
+    McpServer::new(ConversionLimits::new())
+}
+
+/// Result of the request `method` with `params`, which must succeed
+fn request(server: &McpServer, method: &str, params: Value) -> Value {
+    let message = json!({ "jsonrpc": "2.0", "id": 7, "method": method, "params": params });
+    let response = server.handle(&message.to_string()).unwrap();
+    assert_eq!(response["id"], 7);
+    assert!(response.get("error").is_none(), "{}", response);
+    response["result"].clone()
+}
+
+/// Text returned by the tool `name`, and whether it is an error
+fn call(server: &McpServer, name: &str, arguments: Value) -> (String, bool) {
+    let result = request(
+        server,
+        "tools/call",
+        json!({ "name": name, "arguments": arguments }),
+    );
+    let text = result["content"][0]["text"].as_str().unwrap().to_string();
+    (text, result["isError"].as_bool().unwrap())
+}
+
+#[test]
+fn test_mcp_initialize() {
+    let server = server();
+    let result = request(
+        &server,
+        "initialize",
+        json!({
+            "protocolVersion": "2024-11-05",
+            "capabilities": {},
+            "clientInfo": { "name": "test", "version": "1.0" },
+        }),
+    );
+    assert_eq!(result["protocolVersion"], "2024-11-05");
+    assert_eq!(result["serverInfo"]["name"], "docling-rs");
+    assert!(result["capabilities"]["tools"].is_object());
+
+    // A version from the future gets the newest one understood
+    let result = request(
+        &server,
+        "initialize",
+        json!({ "protocolVersion": "2999-01-01" }),
+    );
+    assert_eq!(result["protocolVersion"], "2025-06-18");
+
+    // Notifications are not answered
+    let notification = json!({ "jsonrpc": "2.0", "method": "notifications/initialized" });
+    assert!(server.handle(&notification.to_string()).is_none());
+    assert_eq!(request(&server, "ping", Value::Null), json!({}));
+}
+
+#[test]
+fn test_mcp_tools_list() {
+    let result = request(&server(), "tools/list", json!({}));
+    let tools = result["tools"].as_array().unwrap();
+    let names: Vec<_> = tools
+        .iter()
+        .map(|tool| tool["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, ["convert_document", "chunk_document"]);
+
+    let convert = &tools[0]["inputSchema"];
+    assert_eq!(convert["type"], "object");
+    let formats = convert["properties"]["to"]["enum"].as_array().unwrap();
+    assert!(formats.contains(&json!("markdown")));
+    assert!(!formats.contains(&json!("parquet")));
+    assert!(convert["properties"]["from"]["enum"]
+        .as_array()
+        .unwrap()
+        .contains(&json!("pdf")));
+
+    let chunk = &tools[1]["inputSchema"]["properties"];
+    assert!(chunk["chunker"]["enum"]
+        .as_array()
+        .unwrap()
+        .contains(&json!("hybrid")));
+    assert!(chunk["path"].is_object());
+}
+
+#[test]
+fn test_mcp_convert_document() {
+    let temp = TempDir::new().unwrap();
+    let path = temp.path().join("notes.md");
+    fs::write(&path, MARKDOWN).unwrap();
+    let server = server();
+
+    let (text, is_error) = call(&server, "convert_document", json!({ "path": path }));
+    assert!(!is_error, "{}", text);
+    assert!(text.contains("# Field Notes"));
+    assert!(text.contains("- heron"));
+
+    let (text, is_error) = call(
+        &server,
+        "convert_document",
+        json!({ "path": path, "to": "json" }),
+    );
+    assert!(!is_error, "{}", text);
+    let document: Value = serde_json::from_str(&text).unwrap();
+    assert!(document["nodes"].is_array());
+
+    // Inline content, Markdown by default
+    let (text, is_error) = call(
+        &server,
+        "convert_document",
+        json!({ "content": MARKDOWN, "to": "text" }),
+    );
+    assert!(!is_error, "{}", text);
+    assert!(text.contains("The river rose overnight."));
+    assert!(!text.contains('#'));
+
+    // Format given for a file without extension
+    let bare = temp.path().join("notes");
+    fs::write(&bare, MARKDOWN).unwrap();
+    let (text, is_error) = call(
+        &server,
+        "convert_document",
+        json!({ "path": bare, "from": "markdown" }),
+    );
+    assert!(!is_error, "{}", text);
+    assert!(text.contains("## Birds"));
+}
+
+#[test]
+fn test_mcp_chunk_document() {
+    let server = server();
+    let (text, is_error) = call(&server, "chunk_document", json!({ "content": MARKDOWN }));
+    assert!(!is_error, "{}", text);
+    let chunks: Vec<Value> = serde_json::from_str(&text).unwrap();
+    assert!(chunks
+        .iter()
+        .any(|chunk| chunk["text"] == "The river rose overnight."));
+
+    let (text, is_error) = call(
+        &server,
+        "chunk_document",
+        json!({ "content": MARKDOWN, "chunker": "fixed", "chunk_size": 12 }),
+    );
+    assert!(!is_error, "{}", text);
+    let chunks: Vec<Value> = serde_json::from_str(&text).unwrap();
+    assert!(chunks.len() > 3);
+}
+
+#[test]
+fn test_mcp_tool_errors() {
+    let temp = TempDir::new().unwrap();
+    let server = server();
+
+    let missing = temp.path().join("missing.md");
+    let (text, is_error) = call(&server, "convert_document", json!({ "path": missing }));
+    assert!(is_error);
+    assert!(text.contains("File not found"), "{}", text);
+
+    let (text, is_error) = call(&server, "convert_document", json!({}));
+    assert!(is_error);
+    assert!(text.contains("path or the content"), "{}", text);
+
+    let (_, is_error) = call(
+        &server,
+        "convert_document",
+        json!({ "content": MARKDOWN, "to": "parquet" }),
+    );
+    assert!(is_error);
+
+    let (text, is_error) = call(
+        &server,
+        "chunk_document",
+        json!({ "content": MARKDOWN, "chunk_size": 0 }),
+    );
+    assert!(is_error);
+    assert!(text.contains("positive integer"), "{}", text);
+
+    let limited = McpServer::new(ConversionLimits::new().with_max_input_bytes(8));
+    let (text, is_error) = call(&limited, "convert_document", json!({ "content": MARKDOWN }));
+    assert!(is_error);
+    assert!(text.contains("the limit is 8"), "{}", text);
+}
+
+#[test]
+fn test_mcp_protocol_errors() {
+    let server = server();
+
+    let response = server.handle("{not json").unwrap();
+    assert_eq!(response["error"]["code"], -32700);
+    assert_eq!(response["id"], Value::Null);
+
+    let unknown_method = json!({ "jsonrpc": "2.0", "id": 1, "method": "resources/list" });
+    let response = server.handle(&unknown_method.to_string()).unwrap();
+    assert_eq!(response["error"]["code"], -32601);
+
+    let unknown_tool = json!({
+        "jsonrpc": "2.0",
+        "id": 2,
+        "method": "tools/call",
+        "params": { "name": "summarize", "arguments": {} },
+    });
+    let response = server.handle(&unknown_tool.to_string()).unwrap();
+    assert_eq!(response["error"]["code"], -32602);
+
+    let response = server.handle(&json!({ "id": 3 }).to_string()).unwrap();
+    assert_eq!(response["error"]["code"], -32600);
+
+    // A batch gets the responses of its requests only
+    let batch = json!([
+        { "jsonrpc": "2.0", "id": 4, "method": "ping" },
+        { "jsonrpc": "2.0", "method": "notifications/initialized" },
+    ]);
+    let response = server.handle(&batch.to_string()).unwrap();
+    assert_eq!(response.as_array().unwrap().len(), 1);
+    assert_eq!(response[0]["id"], 4);
+}
+
+#[test]
+fn test_mcp_over_stdio() {
+    let messages = [
+        json!({ "jsonrpc": "2.0", "id": 1, "method": "initialize", "params": { "protocolVersion": "2025-06-18" } }),
+        json!({ "jsonrpc": "2.0", "method": "notifications/initialized" }),
+        json!({ "jsonrpc": "2.0", "id": 2, "method": "tools/call", "params": { "name": "convert_document", "arguments": { "content": MARKDOWN } } }),
+    ];
+    let input: String = messages
+        .iter()
+        .map(|message| format!("{}\n", message))
+        .collect();
+
+    let output = Command::cargo_bin("docling-rs")
+        .unwrap()
+        .arg("mcp")
+        .write_stdin(input)
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let responses: Vec<Value> = String::from_utf8(output)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(responses.len(), 2);
+    assert_eq!(responses[0]["result"]["protocolVersion"], "2025-06-18");
+    assert_eq!(responses[1]["id"], 2);
+    assert!(responses[1]["result"]["content"][0]["text"]
+        .as_str()
+        .unwrap()
+        .contains("# Field Notes"));
+}
@@ -0,0 +1,324 @@
+//! Integration tests for the HTTP server (`docling-rs serve`)
+
+use assert_cmd::Command;
+use docling_rs::cli::serve::{Response, Service};
+use docling_rs::pipeline::ConversionLimits;
+use predicates::prelude::*;
+
+const BOUNDARY: &str = "----docling-test-boundary";
+
+const MARKDOWN: &str = "# Quarterly Report\n\nRevenue grew in every region.\n\n## Outlook\n\nThe outlook remains stable for the next quarter.\n";
+
+/// Content type and body of a multipart upload of `content` as `filename`
+fn upload(filename: &str, content: &str) -> (String, Vec<u8>) {
+    let body = format!(
+        "--{b}\r\nContent-Disposition: form-data; name=\"note\"\r\n\r\nignored\r\n\
+         --{b}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"{f}\"\r\n\
+         Content-Type: application/octet-stream\r\n\r\n{c}\r\n--{b}--\r\n",
+        b = BOUNDARY,
+        f = filename,
+        c = content
+    );
+    (
+        format!("multipart/form-data; boundary={}", BOUNDARY),
+        body.into_bytes(),
+    )
+}
+
+fn post(service: &Service, url: &str, filename: &str, content: &str) -> Response {
+    let (content_type, body) = upload(filename, content);
+    service.handle("POST", url, Some(&content_type), &body)
+}
+
+#[test]
+fn test_serve_health() {
+    let response = Service::new(ConversionLimits::new()).handle("GET", "/health", None, b"");
+    assert_eq!(response.status, 200);
+    assert_eq!(response.body["status"], "ok");
+}
+
+#[test]
+fn test_serve_convert() {
+    let service = Service::new(ConversionLimits::new());
+    let response = post(&service, "/convert", "report.md", MARKDOWN);
+    assert_eq!(response.status, 200, "{}", response.body);
+    assert_eq!(response.body["name"], "report.md");
+    assert!(response.body["warnings"].as_array().unwrap().is_empty());
+    let document = response.body["document"].to_string();
+    assert!(document.contains("Quarterly Report"));
+    assert!(document.contains("Revenue grew in every region."));
+    assert!(response.body.get("content").is_none());
+}
+
+#[test]
+fn test_serve_convert_to_markdown() {
+    let service = Service::new(ConversionLimits::new());
+    let response = post(&service, "/convert?to=markdown", "report.md", MARKDOWN);
+    assert_eq!(response.status, 200, "{}", response.body);
+    let content = response.body["content"].as_str().unwrap();
+    assert!(content.contains("# Quarterly Report"));
+    assert!(content.contains("## Outlook"));
+    assert!(response.body.get("document").is_none());
+
+    let response = post(&service, "/convert?to=parquet", "report.md", MARKDOWN);
+    assert_eq!(response.status, 400);
+}
+
+#[test]
+fn test_serve_convert_from_param() {
+    let service = Service::new(ConversionLimits::new());
+    // Without an extension, the format is unknown
+    let response = post(&service, "/convert", "upload", MARKDOWN);
+    assert_eq!(response.status, 415);
+    assert!(response.body["error"]
+        .as_str()
+        .unwrap()
+        .contains("Unsupported file format"));
+
+    let response = post(
+        &service,
+        "/convert?from=markdown&to=text",
+        "upload",
+        MARKDOWN,
+    );
+    assert_eq!(response.status, 200, "{}", response.body);
+    assert!(response.body["content"]
+        .as_str()
+        .unwrap()
+        .contains("Quarterly Report"));
+
+    let response = post(&service, "/convert?from=spreadsheet", "upload", MARKDOWN);
+    assert_eq!(response.status, 415);
+}
+
+#[test]
+fn test_serve_chunk() {
+    let service = Service::new(ConversionLimits::new());
+    let response = post(&service, "/chunk", "report.md", MARKDOWN);
+    assert_eq!(response.status, 200, "{}", response.body);
+    assert_eq!(response.body["chunker"], "hierarchical");
+    let chunks = response.body["chunks"].as_array().unwrap();
+    assert!(!chunks.is_empty());
+    assert!(chunks
+        .iter()
+        .any(|chunk| chunk["text"] == "Revenue grew in every region."));
+}
+
+#[test]
+fn test_serve_chunk_options() {
+    let service = Service::new(ConversionLimits::new());
+    let response = post(
+        &service,
+        "/chunk?chunker=fixed&chunk_size=20",
+        "report.md",
+        MARKDOWN,
+    );
+    assert_eq!(response.status, 200, "{}", response.body);
+    assert_eq!(response.body["chunker"], "fixed");
+    let chunks = response.body["chunks"].as_array().unwrap();
+    assert!(chunks.len() > 3);
+
+    let response = post(&service, "/chunk?chunk_size=0", "report.md", MARKDOWN);
+    assert_eq!(response.status, 400);
+    let response = post(&service, "/chunk?chunker=sentences", "report.md", MARKDOWN);
+    assert_eq!(response.status, 400);
+}
+
+#[test]
+fn test_serve_request_errors() {
+    let service = Service::new(ConversionLimits::new());
+
+    assert_eq!(service.handle("GET", "/nowhere", None, b"").status, 404);
+    assert_eq!(service.handle("GET", "/convert", None, b"").status, 405);
+
+    // No multipart body, or no `file` field in it
+    let response = service.handle("POST", "/convert", Some("text/markdown"), b"# Title");
+    assert_eq!(response.status, 400);
+    assert!(response.body["error"].as_str().unwrap().contains("`file`"));
+    let (content_type, _) = upload("report.md", MARKDOWN);
+    let body = format!(
+        "--{b}\r\nContent-Disposition: form-data; name=\"other\"\r\n\r\nvalue\r\n--{b}--\r\n",
+        b = BOUNDARY
+    );
+    let response = service.handle("POST", "/convert", Some(&content_type), body.as_bytes());
+    assert_eq!(response.status, 400);
+
+    // Cut off before its closing boundary
+    let response = service.handle(
+        "POST",
+        "/convert",
+        Some(&content_type),
+        format!(
+            "--{}\r\nContent-Disposition: form-data; name=\"file\"\r\n\r\n# Ti",
+            BOUNDARY
+        )
+        .as_bytes(),
+    );
+    assert_eq!(response.status, 400);
+    assert!(response.body["error"]
+        .as_str()
+        .unwrap()
+        .contains("Malformed multipart body"));
+}
+
+#[test]
+fn test_serve_quoted_file_name() {
+    let service = Service::new(ConversionLimits::new());
+    let response = post(&service, "/convert", "C:\\uploads\\q3; final.md", MARKDOWN);
+    assert_eq!(response.status, 200, "{}", response.body);
+    assert_eq!(response.body["name"], "q3; final.md");
+}
+
+#[test]
+fn test_serve_input_size_limit() {
+    let service = Service::new(ConversionLimits::new().with_max_input_bytes(16));
+    let response = post(&service, "/convert", "report.md", MARKDOWN);
+    assert_eq!(response.status, 413);
+    assert!(response.body["error"]
+        .as_str()
+        .unwrap()
+        .contains("the limit is 16"));
+}
+
+#[cfg(not(feature = "serve"))]
+#[test]
+fn test_serve_needs_serve_feature() {
+    Command::cargo_bin("docling-rs")
+        .unwrap()
+        .arg("serve")
+        .arg("--port")
+        .arg("0")
+        .assert()
+        .failure()
+        .code(2)
+        .stderr(predicate::str::contains("`serve` feature"));
+}
+
+#[test]
+fn test_serve_limits() {
+    use clap::FromArgMatches;
+    use docling_rs::cli::serve::{subcommand, ServeArgs};
+
+    let limits = |args: &[&str]| {
+        let matches = subcommand().get_matches_from(args);
+        ServeArgs::from_arg_matches(&matches).unwrap().limits()
+    };
+
+    // Hostile inputs are bounded by default
+    let defaults = limits(&["serve"]);
+    assert_eq!(defaults.max_input_bytes(), Some(100 * 1024 * 1024));
+    assert_eq!(defaults.max_decompressed_bytes(), Some(1024 * 1024 * 1024));
+    assert_eq!(defaults.max_depth(), Some(256));
+
+    let custom = limits(&[
+        "serve",
+        "--max-decompressed-bytes",
+        "1000",
+        "--max-depth",
+        "8",
+    ]);
+    assert_eq!(custom.max_decompressed_bytes(), Some(1000));
+    assert_eq!(custom.max_depth(), Some(8));
+}
+
+#[test]
+fn test_serve_rejects_conversion_flags() {
+    Command::cargo_bin("docling-rs")
+        .unwrap()
+        .arg("serve")
+        .arg("--chunk")
+        .assert()
+        .failure()
+        .code(2)
+        .stderr(predicate::str::contains("--chunk"));
+}
+
+#[cfg(feature = "serve")]
+mod server {
+    use super::*;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpStream;
+    use std::process::{Child, Command as Process, Stdio};
+
+    /// The server, killed when dropped
+    struct Server {
+        child: Child,
+        address: String,
+    }
+
+    impl Server {
+        fn start(args: &[&str]) -> Self {
+            let mut child = Process::new(assert_cmd::cargo::cargo_bin("docling-rs"))
+                .arg("serve")
+                .arg("--port")
+                .arg("0")
+                .args(args)
+                .stderr(Stdio::piped())
+                .spawn()
+                .unwrap();
+            let mut stderr = BufReader::new(child.stderr.take().unwrap());
+            let mut line = String::new();
+            stderr.read_line(&mut line).unwrap();
+            let address = line
+                .trim()
+                .strip_prefix("Listening on http://")
+                .expect("the server announces its address first")
+                .to_string();
+            Self { child, address }
+        }
+
+        /// Status line and body of the answer to a raw HTTP request
+        fn request(&self, method: &str, path: &str, content_type: &str, body: &[u8]) -> String {
+            let mut stream = TcpStream::connect(&self.address).unwrap();
+            write!(
+                stream,
+                "{} {} HTTP/1.1\r\nHost: {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                method,
+                path,
+                self.address,
+                content_type,
+                body.len()
+            )
+            .unwrap();
+            stream.write_all(body).unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).unwrap();
+            response
+        }
+    }
+
+    impl Drop for Server {
+        fn drop(&mut self) {
+            let _ = self.child.kill();
+            let _ = self.child.wait();
+        }
+    }
+
+    #[test]
+    fn test_serve_over_http() {
+        let server = Server::start(&[]);
+
+        let response = server.request("GET", "/health", "text/plain", b"");
+        assert!(response.starts_with("HTTP/1.1 200"), "{}", response);
+        assert!(response
+            .to_ascii_lowercase()
+            .contains("content-type: application/json"));
+
+        let (content_type, body) = upload("report.md", MARKDOWN);
+        let response = server.request("POST", "/convert?to=markdown", &content_type, &body);
+        assert!(response.starts_with("HTTP/1.1 200"), "{}", response);
+        assert!(response.contains("# Quarterly Report"));
+
+        let response = server.request("POST", "/chunk", &content_type, &body);
+        assert!(response.starts_with("HTTP/1.1 200"), "{}", response);
+        assert!(response.contains("\"chunks\""));
+    }
+
+    #[test]
+    fn test_serve_body_limit() {
+        let server = Server::start(&["--max-input-bytes", "0"]);
+        let body = vec![b'x'; 128 * 1024];
+        let response = server.request("POST", "/convert", "multipart/form-data; boundary=x", &body);
+        assert!(response.starts_with("HTTP/1.1 413"), "{}", response);
+    }
+}
@@ -0,0 +1,173 @@
+//! Integration tests for URL inputs
+
+use assert_cmd::Command;
+use docling_rs::cli::remote;
+use docling_rs::cli::InputFormat;
+use predicates::prelude::*;
+use std::path::Path;
+
+#[test]
+fn test_url_detection() {
+    assert!(remote::is_url(Path::new("https://example.com/page.html")));
+    assert!(remote::is_url(Path::new("HTTP://example.com")));
+    assert!(!remote::is_url(Path::new("page.html")));
+    assert!(!remote::is_url(Path::new("ftp://example.com/page.html")));
+}
+
+#[test]
+fn test_url_slug() {
+    assert_eq!(remote::slug("https://example.com/page.html"), "page");
+    assert_eq!(
+        remote::slug("https://example.com/docs/getting-started.html?lang=en#intro"),
+        "getting-started"
+    );
+    assert_eq!(remote::slug("https://example.com/docs/guide/"), "guide");
+    assert_eq!(
+        remote::slug("https://user@example.com:8080/"),
+        "example.com"
+    );
+    assert_eq!(remote::slug("https://example.com/a%20b.pdf"), "a-20b");
+}
+
+#[test]
+fn test_url_format() {
+    let url = "https://example.com/paper";
+    assert_eq!(
+        remote::detect_format(url, Some("application/pdf")),
+        Some(InputFormat::Pdf)
+    );
+    assert_eq!(
+        remote::detect_format(url, Some("text/html; charset=utf-8")),
+        Some(InputFormat::Html)
+    );
+    // Plain text and unknown types defer to the extension
+    assert_eq!(
+        remote::detect_format("https://example.com/notes.md", Some("text/plain")),
+        Some(InputFormat::Markdown)
+    );
+    assert_eq!(
+        remote::detect_format("https://example.com/notes", Some("text/plain")),
+        Some(InputFormat::Text)
+    );
+    assert_eq!(
+        remote::detect_format(
+            "https://example.com/report.pdf",
+            Some("application/octet-stream")
+        ),
+        Some(InputFormat::Pdf)
+    );
+    assert_eq!(remote::detect_format(url, Some("image/png")), None);
+}
+
+#[cfg(not(feature = "http"))]
+#[test]
+fn test_url_input_needs_http_feature() {
+    Command::cargo_bin("docling-rs")
+        .unwrap()
+        .arg("https://example.com/page.html")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("`http` feature"));
+}
+
+#[cfg(feature = "http")]
+mod download {
+    use super::*;
+    use std::fs;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::thread::JoinHandle;
+    use tempfile::TempDir;
+
+    /// Serve one response, returning the server address and the request
+    /// headers received
+    fn serve_once(status: &str, content_type: &str, body: &str) -> (String, JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        let response = format!(
+            "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status,
+            content_type,
+            body.len(),
+            body
+        );
+        let server = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut headers = String::new();
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line == "\r\n" || line.is_empty() {
+                    break;
+                }
+                headers.push_str(&line);
+            }
+            reader.get_mut().write_all(response.as_bytes()).unwrap();
+            headers
+        });
+        (address, server)
+    }
+
+    #[test]
+    fn test_url_input_named_after_slug() {
+        let temp = TempDir::new().unwrap();
+        let output_dir = temp.path().join("out");
+        let (address, server) = serve_once(
+            "200 OK",
+            "text/markdown; charset=utf-8",
+            "# Remote Page\n\nFetched over HTTP.\n",
+        );
+        let url = format!("http://{}/docs/remote-page?version=2", address);
+
+        Command::cargo_bin("docling-rs")
+            .unwrap()
+            .arg(&url)
+            .arg("--output-dir")
+            .arg(&output_dir)
+            .assert()
+            .success()
+            .stdout(predicate::str::contains(url.as_str()));
+
+        let markdown = fs::read_to_string(output_dir.join("remote-page.md")).unwrap();
+        assert!(markdown.contains("Remote Page"));
+        assert!(markdown.contains("Fetched over HTTP."));
+
+        let headers = server.join().unwrap().to_ascii_lowercase();
+        assert!(headers.contains("user-agent: docling-rs/"));
+    }
+
+    #[test]
+    fn test_url_input_http_error() {
+        let temp = TempDir::new().unwrap();
+        let (address, server) = serve_once("404 Not Found", "text/plain", "missing");
+
+        Command::cargo_bin("docling-rs")
+            .unwrap()
+            .arg(format!("http://{}/missing.html", address))
+            .arg("--output-dir")
+            .arg(temp.path())
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains("404"));
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn test_url_input_size_limit() {
+        let temp = TempDir::new().unwrap();
+        let (address, server) = serve_once("200 OK", "text/markdown", &"word ".repeat(100));
+
+        Command::cargo_bin("docling-rs")
+            .unwrap()
+            .arg(format!("http://{}/big.md", address))
+            .arg("--max-input-bytes")
+            .arg("100")
+            .arg("--output-dir")
+            .arg(temp.path())
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains("the limit is 100"));
+        server.join().unwrap();
+    }
+}
@@ -2,32 +2,42 @@
 //!
 //! Tests the complete workflow of extracting images from PDFs.
 
+mod helpers;
+
 use docling_rs::backend::{Backend, PdfBackend};
-use docling_rs::datamodel::InputDocument;
+use docling_rs::datamodel::{ImageRef, InputDocument, NodeType};
 use docling_rs::InputFormat;
 
 #[test]
-#[ignore = "Requires PDF implementation with image extraction"]
 fn test_extract_images_from_pdf() {
-    // This test verifies extraction of images from a PDF
-
-    // Arrange: Create a PDF with images
-    let pdf_path = create_pdf_with_images();
+    // Arrange: Create a PDF with a caption and one image
+    let pdf_path = helpers::pdf_fixtures::create_pdf_with_image();
     let backend = PdfBackend::new();
     let input = InputDocument::from_path(pdf_path, InputFormat::PDF);
 
     // Act: Convert PDF
-    let result = backend.convert(&input);
-
-    // Assert: Images are detected and extracted
-    assert!(
-        result.is_ok(),
-        "PDF with images should convert successfully"
-    );
-
-    let _doc = result.unwrap();
-    // Verify images were detected
-    // (This assumes DoclingDocument can represent images)
+    let doc = backend
+        .convert(&input)
+        .expect("PDF with images should convert successfully");
+
+    // Assert: The image is a picture node after the text, with its bytes stored
+    let pictures: Vec<_> = doc
+        .nodes()
+        .iter()
+        .filter(|node| node.node_type() == NodeType::Picture)
+        .collect();
+    assert_eq!(pictures.len(), 1);
+    let node = pictures[0];
+    assert_eq!(node.page(), Some(1));
+    let bbox = node.bbox().expect("picture should have a bounding box");
+    assert!((bbox.width() - 200.0).abs() < 1.0);
+
+    let picture = node.picture().expect("picture should reference its image");
+    assert_eq!(picture.mimetype(), Some("image/png"));
+    assert_eq!((picture.width(), picture.height()), (Some(200), Some(200)));
+    assert!(matches!(picture.image(), ImageRef::Stored { .. }));
+    assert_eq!(doc.images().len(), 1);
+    assert!(doc.metadata().extra().get("image_count").is_none());
 }
 
 #[test]
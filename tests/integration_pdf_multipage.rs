@@ -94,3 +94,31 @@ fn test_multipage_pdf_reading_order() {
 }
 
 // Helper functions now imported from helpers::pdf_fixtures
+
+#[test]
+fn test_multipage_pdf_reports_page_progress() {
+    use docling_rs::pipeline::{ProgressEvent, ProgressObserver};
+    use std::sync::Mutex;
+
+    struct Pages(Mutex<Vec<(usize, usize, usize)>>);
+
+    impl ProgressObserver for Pages {
+        fn on_progress(&self, _document: &str, event: &ProgressEvent) {
+            if let ProgressEvent::Page { page, done, total } = *event {
+                self.0.lock().unwrap().push((page, done, total));
+            }
+        }
+    }
+
+    let pdf_path = create_multipage_pdf(3);
+    let input = InputDocument::from_path(pdf_path, InputFormat::PDF);
+    let pages = Pages(Mutex::new(Vec::new()));
+    PdfBackend::new()
+        .convert_with_progress(&input, &pages)
+        .unwrap();
+
+    assert_eq!(
+        pages.0.into_inner().unwrap(),
+        [(1, 1, 3), (2, 2, 3), (3, 3, 3)]
+    );
+}
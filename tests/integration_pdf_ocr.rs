@@ -98,13 +98,13 @@ fn test_ocr_result_creation() {
     let word1 = OcrWord::new(
         "Hello".to_string(),
         95.5,
-        BoundingBox::new(10.0, 20.0, 50.0, 15.0),
+        BoundingBox::new(10.0, 35.0, 60.0, 20.0),
     );
 
     let word2 = OcrWord::new(
         "World".to_string(),
         92.3,
-        BoundingBox::new(65.0, 20.0, 55.0, 15.0),
+        BoundingBox::new(65.0, 35.0, 120.0, 20.0),
     );
 
     let result = OcrResult::new("Hello World".to_string(), 93.9, vec![word1, word2]);
@@ -10,7 +10,7 @@ use helpers::pdf_fixtures::*;
 
 use docling_rs::backend::{Backend, PdfBackend};
 use docling_rs::cli::output;
-use docling_rs::datamodel::InputDocument;
+use docling_rs::datamodel::{InputDocument, NodeType};
 use docling_rs::InputFormat;
 
 #[test]
@@ -69,9 +69,44 @@ fn test_extract_text_with_positions() {
     // Verify that both lines of text were extracted
     assert!(text.contains("Top text"), "Should extract 'Top text'");
     assert!(text.contains("Bottom text"), "Should extract 'Bottom text'");
+}
+
+#[test]
+fn test_extract_text_blocks_with_bounding_boxes() {
+    // This test verifies that each block of text is a node with its own box
+
+    // Arrange: two blocks of text, three blank lines apart
+    let pdf_path = create_simple_text_pdf("First block\n\n\n\nSecond block");
+
+    let backend = PdfBackend::new();
+    let input = InputDocument::from_path(pdf_path, InputFormat::PDF);
+
+    // Act
+    let doc = backend
+        .convert(&input)
+        .expect("PDF conversion should succeed");
+
+    // Assert: one node per block, top to bottom, each with page and bbox
+    let blocks: Vec<_> = doc
+        .nodes()
+        .iter()
+        .filter(|node| node.node_type() == NodeType::Text)
+        .collect();
+    assert_eq!(blocks.len(), 2);
+    assert_eq!(blocks[0].text_content(), Some("First block"));
+    assert_eq!(blocks[1].text_content(), Some("Second block"));
+
+    let first = blocks[0].bbox().expect("block bbox");
+    let second = blocks[1].bbox().expect("block bbox");
+    assert!(first.bottom > second.top, "First block should be above");
+    assert!(
+        first.width() < 300.0,
+        "Box should fit the text, not the page"
+    );
+    assert_eq!(blocks[1].page(), Some(1));
 
-    // Note: Position metadata will be added in future phases (Phase 2: Layout Analysis)
-    // For now, we just verify basic text extraction works
+    let position = blocks[1].position().expect("block position");
+    assert_eq!(position.end_offset() - position.start_offset(), 12);
 }
 
 #[test]
@@ -0,0 +1,202 @@
+//! Integration tests for object storage sources (`s3://`, `gs://`)
+
+use docling_rs::cli::remote;
+use docling_rs::remote::{ObjectUrl, Scheme};
+use std::path::Path;
+
+#[test]
+fn test_object_url_parse() {
+    let url = ObjectUrl::parse("s3://reports/2024/annual.pdf").unwrap();
+    assert_eq!(url.scheme, Scheme::S3);
+    assert_eq!(url.bucket, "reports");
+    assert_eq!(url.key, "2024/annual.pdf");
+    assert_eq!(url.file_name(), "annual.pdf");
+    assert_eq!(url.to_string(), "s3://reports/2024/annual.pdf");
+
+    let url = ObjectUrl::parse("gs://data/notes/").unwrap();
+    assert_eq!(url.scheme, Scheme::Gcs);
+    assert_eq!(url.file_name(), "notes");
+
+    assert!(ObjectUrl::parse("s3://bucket-only").is_err());
+    assert!(ObjectUrl::parse("s3://bucket/").is_err());
+    assert!(ObjectUrl::parse("azure://container/blob.pdf").is_err());
+}
+
+#[test]
+fn test_object_url_detection() {
+    assert!(remote::is_url(Path::new("s3://bucket/doc.pdf")));
+    assert!(remote::is_url(Path::new("GS://bucket/doc.pdf")));
+    assert!(!remote::is_url(Path::new("s3/bucket/doc.pdf")));
+}
+
+#[cfg(not(feature = "s3"))]
+#[test]
+fn test_s3_needs_feature() {
+    use docling_rs::{ConversionError, DocumentConverter};
+
+    let result = DocumentConverter::new().convert_url("s3://bucket/doc.md");
+    assert!(matches!(
+        result,
+        Err(ConversionError::Config(message)) if message.contains("`s3` feature")
+    ));
+}
+
+#[cfg(any(feature = "s3", feature = "gcs"))]
+mod download {
+    use assert_cmd::Command;
+    use std::fs;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::thread::JoinHandle;
+    use tempfile::TempDir;
+
+    /// Serve one response, returning the server address and the request
+    /// line and headers received
+    pub fn serve_once(status: &str, body: &str) -> (String, JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        let response = format!(
+            "HTTP/1.1 {}\r\nContent-Type: application/octet-stream\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status,
+            body.len(),
+            body
+        );
+        let server = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut headers = String::new();
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line == "\r\n" || line.is_empty() {
+                    break;
+                }
+                headers.push_str(&line);
+            }
+            reader.get_mut().write_all(response.as_bytes()).unwrap();
+            headers
+        });
+        (address, server)
+    }
+
+    /// The command line tool, with no credentials of the environment
+    pub fn command() -> Command {
+        let mut command = Command::cargo_bin("docling-rs").unwrap();
+        for name in [
+            "AWS_PROFILE",
+            "AWS_REGION",
+            "AWS_DEFAULT_REGION",
+            "AWS_ENDPOINT_URL_S3",
+            "AWS_SESSION_TOKEN",
+            "AWS_CONFIG_FILE",
+            "AWS_SHARED_CREDENTIALS_FILE",
+            "AWS_WEB_IDENTITY_TOKEN_FILE",
+            "AWS_CONTAINER_CREDENTIALS_RELATIVE_URI",
+            "AWS_CONTAINER_CREDENTIALS_FULL_URI",
+            "GOOGLE_APPLICATION_CREDENTIALS",
+        ] {
+            command.env_remove(name);
+        }
+        command
+    }
+
+    #[cfg(feature = "s3")]
+    #[test]
+    fn test_s3_input() {
+        let temp = TempDir::new().unwrap();
+        let (address, server) = serve_once("200 OK", "# Annual Report\n\nRead from S3.\n");
+
+        command()
+            .arg("s3://reports/2024/annual.md")
+            .arg("--output-dir")
+            .arg(temp.path())
+            .env("AWS_ENDPOINT_URL", format!("http://{}", address))
+            .env("AWS_REGION", "eu-west-1")
+            .env("AWS_ACCESS_KEY_ID", "AKIDEXAMPLE")
+            .env("AWS_SECRET_ACCESS_KEY", "secret")
+            .env("AWS_EC2_METADATA_DISABLED", "true")
+            .env("HOME", temp.path())
+            .assert()
+            .success();
+
+        let markdown = fs::read_to_string(temp.path().join("annual.md")).unwrap();
+        assert!(markdown.contains("Read from S3."));
+
+        let headers = server.join().unwrap();
+        assert!(headers.starts_with("GET /reports/2024/annual.md HTTP/1.1"));
+        let headers = headers.to_ascii_lowercase();
+        assert!(headers.contains("authorization: aws4-hmac-sha256 credential=akidexample/"));
+        assert!(headers.contains("/eu-west-1/s3/aws4_request"));
+        assert!(headers.contains("x-amz-content-sha256: unsigned-payload"));
+    }
+
+    #[cfg(feature = "gcs")]
+    #[test]
+    fn test_gcs_input() {
+        let temp = TempDir::new().unwrap();
+        let (address, server) = serve_once("200 OK", "# Field Notes\n\nRead from GCS.\n");
+
+        command()
+            .arg("gs://archive/docs/field notes.md")
+            .arg("--output-dir")
+            .arg(temp.path())
+            .env("STORAGE_EMULATOR_HOST", format!("http://{}", address))
+            .env("GOOGLE_OAUTH_ACCESS_TOKEN", "ya29.token")
+            .assert()
+            .success();
+
+        let markdown = fs::read_to_string(temp.path().join("field notes.md")).unwrap();
+        assert!(markdown.contains("Read from GCS."));
+
+        let headers = server.join().unwrap();
+        assert!(headers
+            .starts_with("GET /storage/v1/b/archive/o/docs%2Ffield%20notes.md?alt=media HTTP/1.1"));
+        assert!(headers
+            .to_ascii_lowercase()
+            .contains("authorization: bearer ya29.token"));
+    }
+}
+
+#[cfg(feature = "s3")]
+mod library {
+    use super::download::serve_once;
+    use docling_rs::remote::{Credentials, ObjectUrl, S3Reader};
+    use docling_rs::{ConversionError, DocumentConverter};
+
+    fn reader(address: &str) -> S3Reader {
+        S3Reader::new("us-east-1")
+            .with_endpoint(format!("http://{}", address))
+            .with_credentials(Credentials::new("AKIDEXAMPLE", "secret"))
+    }
+
+    #[test]
+    fn test_convert_object() {
+        let (address, server) = serve_once("200 OK", "# Minutes\n\nApproved.\n");
+        let url = ObjectUrl::parse("s3://board/minutes.md").unwrap();
+
+        let result = DocumentConverter::new()
+            .convert_object(&url, &reader(&address))
+            .unwrap();
+        let document = result.document();
+        assert!(document
+            .nodes()
+            .iter()
+            .any(|node| node.text_content() == Some("Approved.")));
+        assert_eq!(
+            document.metadata().source_uri(),
+            Some("s3://board/minutes.md")
+        );
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn test_missing_object() {
+        let (address, server) =
+            serve_once("404 Not Found", "<Error><Code>NoSuchKey</Code></Error>");
+        let url = ObjectUrl::parse("s3://board/missing.md").unwrap();
+
+        let result = DocumentConverter::new().convert_object(&url, &reader(&address));
+        assert!(matches!(result, Err(ConversionError::FileNotFound(_))));
+        server.join().unwrap();
+    }
+}
@@ -0,0 +1,253 @@
+//! Integration tests for the embeddable REST server (`docling_rs::server`)
+
+#![cfg(feature = "server")]
+
+use docling_rs::pipeline::ConversionLimits;
+use docling_rs::server::{
+    openapi, router, ChunkRequest, ChunkResponse, ConvertRequest, ConvertResponse, ErrorResponse,
+    HealthResponse,
+};
+use serde_json::Value;
+use std::io::{Read, Write};
+use std::net::TcpStream;
+
+const MARKDOWN: &str = "# Quarterly Report\n\nRevenue grew in every region.\n\n## Outlook\n\nThe outlook remains stable for the next quarter.\n";
+
+/// Serve the router on a free port in the background, returning its address
+fn start(limits: ConversionLimits) -> String {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    listener.set_nonblocking(true).unwrap();
+    let address = listener.local_addr().unwrap().to_string();
+    let app = router(limits);
+    std::thread::spawn(move || {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        runtime.block_on(async move {
+            let listener = tokio::net::TcpListener::from_std(listener).unwrap();
+            axum::serve(listener, app).await.unwrap();
+        });
+    });
+    address
+}
+
+/// Status and JSON body of the answer to a request with `body`
+fn request(address: &str, method: &str, path: &str, body: &[u8]) -> (u16, Value) {
+    let mut stream = TcpStream::connect(address).unwrap();
+    write!(
+        stream,
+        "{} {} HTTP/1.1\r\nHost: {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        method,
+        path,
+        address,
+        body.len()
+    )
+    .unwrap();
+    stream.write_all(body).unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+
+    let status = response.split(' ').nth(1).unwrap().parse().unwrap();
+    let (_, body) = response.split_once("\r\n\r\n").unwrap();
+    (status, serde_json::from_str(body).unwrap_or(Value::Null))
+}
+
+fn post(address: &str, path: &str, body: &impl serde::Serialize) -> (u16, Value) {
+    request(address, "POST", path, &serde_json::to_vec(body).unwrap())
+}
+
+fn error(body: Value) -> String {
+    serde_json::from_value::<ErrorResponse>(body).unwrap().error
+}
+
+#[test]
+fn test_server_health() {
+    let address = start(ConversionLimits::new());
+    let (status, body) = request(&address, "GET", "/health", b"");
+    assert_eq!(status, 200);
+    let health: HealthResponse = serde_json::from_value(body).unwrap();
+    assert_eq!(health.status, "ok");
+    assert_eq!(health.version, env!("CARGO_PKG_VERSION"));
+}
+
+#[test]
+fn test_server_convert() {
+    let address = start(ConversionLimits::new());
+    let (status, body) = post(
+        &address,
+        "/convert",
+        &ConvertRequest::new("report.md", MARKDOWN.as_bytes()),
+    );
+    assert_eq!(status, 200, "{}", body);
+    let response: ConvertResponse = serde_json::from_value(body).unwrap();
+    assert_eq!(response.name, "report.md");
+    assert!(response.warnings.is_empty());
+    assert!(response.content.is_none());
+    let document = response.document.unwrap();
+    assert!(document
+        .nodes()
+        .iter()
+        .any(|node| node.text_content() == Some("Revenue grew in every region.")));
+
+    let (status, body) = post(
+        &address,
+        "/convert",
+        &ConvertRequest::new("upload", MARKDOWN.as_bytes())
+            .with_from("markdown")
+            .with_to("markdown"),
+    );
+    assert_eq!(status, 200, "{}", body);
+    let response: ConvertResponse = serde_json::from_value(body).unwrap();
+    assert!(response.document.is_none());
+    let content = response.content.unwrap();
+    assert!(content.contains("# Quarterly Report"));
+    assert!(content.contains("## Outlook"));
+}
+
+#[test]
+fn test_server_chunk() {
+    let address = start(ConversionLimits::new());
+    let (status, body) = post(
+        &address,
+        "/chunk",
+        &ChunkRequest::new("report.md", MARKDOWN.as_bytes()),
+    );
+    assert_eq!(status, 200, "{}", body);
+    let response: ChunkResponse = serde_json::from_value(body).unwrap();
+    assert_eq!(response.chunker, "hierarchical");
+    assert!(response
+        .chunks
+        .iter()
+        .any(|chunk| chunk.text == "Revenue grew in every region."));
+
+    let (status, body) = post(
+        &address,
+        "/chunk",
+        &ChunkRequest::new("report.md", MARKDOWN.as_bytes())
+            .with_chunker("fixed")
+            .with_chunk_size(20),
+    );
+    assert_eq!(status, 200, "{}", body);
+    let response: ChunkResponse = serde_json::from_value(body).unwrap();
+    assert_eq!(response.chunker, "fixed");
+    assert!(response.chunks.len() > 3);
+
+    let (status, _) = post(
+        &address,
+        "/chunk",
+        &ChunkRequest::new("report.md", MARKDOWN.as_bytes()).with_chunk_size(0),
+    );
+    assert_eq!(status, 400);
+    let (status, body) = post(
+        &address,
+        "/chunk",
+        &ChunkRequest::new("report.md", MARKDOWN.as_bytes()).with_chunker("semantic"),
+    );
+    assert_eq!(status, 200, "{}", body);
+    assert_eq!(body["chunker"], "semantic");
+    let (status, _) = post(
+        &address,
+        "/chunk",
+        &ChunkRequest::new("report.md", MARKDOWN.as_bytes()).with_chunker("unknown"),
+    );
+    assert_eq!(status, 400);
+}
+
+#[test]
+fn test_server_request_errors() {
+    let address = start(ConversionLimits::new());
+
+    let (status, body) = post(
+        &address,
+        "/convert",
+        &ConvertRequest::new("upload", MARKDOWN.as_bytes()),
+    );
+    assert_eq!(status, 415);
+    assert!(error(body).contains("Unsupported file format"));
+
+    let (status, _) = post(
+        &address,
+        "/convert",
+        &ConvertRequest::new("report.md", MARKDOWN.as_bytes()).with_to("parquet"),
+    );
+    assert_eq!(status, 400);
+
+    let mut request_body = ConvertRequest::new("report.md", b"");
+    request_body.data = "not base64!".to_string();
+    let (status, body) = post(&address, "/convert", &request_body);
+    assert_eq!(status, 400);
+    assert!(error(body).contains("base64"));
+
+    // JSON of the wrong shape, and no JSON at all
+    let (status, body) = request(&address, "POST", "/convert", br#"{"name": "report.md"}"#);
+    assert_eq!(status, 400);
+    assert!(error(body).contains("data"));
+    let (status, _) = request(&address, "POST", "/chunk", b"# Title");
+    assert_eq!(status, 400);
+}
+
+#[test]
+fn test_server_input_size_limit() {
+    let address = start(ConversionLimits::new().with_max_input_bytes(16));
+    let (status, body) = post(
+        &address,
+        "/convert",
+        &ConvertRequest::new("report.md", MARKDOWN.as_bytes()),
+    );
+    assert_eq!(status, 413);
+    assert!(error(body).contains("the limit is 16"));
+
+    // Bodies far over the limit are refused before being read
+    let address = start(ConversionLimits::new().with_max_input_bytes(0));
+    let (status, _) = request(&address, "POST", "/convert", &vec![b' '; 128 * 1024]);
+    assert_eq!(status, 413);
+}
+
+#[test]
+fn test_server_openapi() {
+    let spec = openapi();
+    assert_eq!(spec["openapi"], "3.0.3");
+    for path in ["/convert", "/chunk", "/health", "/openapi.json"] {
+        assert!(spec["paths"][path].is_object(), "no {}", path);
+    }
+
+    // Every schema reference resolves to a component
+    let schemas = spec["components"]["schemas"].as_object().unwrap();
+    for name in [
+        "ConvertRequest",
+        "ConvertResponse",
+        "ChunkRequest",
+        "ChunkResponse",
+        "ErrorResponse",
+        "DoclingDocument",
+        "BaseChunk",
+    ] {
+        assert!(schemas.contains_key(name), "no {} schema", name);
+    }
+    // Every registered chunker is documented
+    let chunker = schemas["ChunkRequest"]["properties"]["chunker"]["description"]
+        .as_str()
+        .unwrap();
+    assert!(chunker.contains("`semantic`"), "{}", chunker);
+    let text = spec.to_string();
+    for reference in text.split("\"$ref\":\"").skip(1) {
+        let name = reference
+            .split('"')
+            .next()
+            .unwrap()
+            .strip_prefix("#/components/schemas/")
+            .unwrap();
+        assert!(schemas.contains_key(name), "dangling reference to {}", name);
+    }
+    assert_eq!(
+        spec["paths"]["/convert"]["post"]["requestBody"]["content"]["application/json"]["schema"]
+            ["$ref"],
+        "#/components/schemas/ConvertRequest"
+    );
+
+    let address = start(ConversionLimits::new());
+    let (status, body) = request(&address, "GET", "/openapi.json", b"");
+    assert_eq!(status, 200);
+    assert_eq!(body, spec);
+}
@@ -0,0 +1,139 @@
+//! Language detection tests
+
+use docling_rs::chunking::{BaseChunker, HierarchicalChunker, WindowChunker};
+use docling_rs::datamodel::{DocumentNode, InputDocument, NodeType};
+use docling_rs::pipeline::{LanguageDetector, Pipeline, SimplePipeline};
+use docling_rs::{DoclingDocument, InputFormat};
+
+const ENGLISH: &str = "The quarterly report shows that revenue grew steadily across all regions, \
+                       driven by strong demand for the new product line.";
+const SPANISH: &str =
+    "El informe trimestral muestra que los ingresos crecieron de manera constante \
+                       en todas las regiones gracias a la fuerte demanda.";
+
+fn mixed() -> DoclingDocument {
+    let mut doc = DoclingDocument::new("mixed.md");
+    let heading = doc.add_node(DocumentNode::new(NodeType::Heading, "Summary").with_level(1));
+    doc.add_child(heading, DocumentNode::new(NodeType::Paragraph, ENGLISH));
+    doc.add_child(heading, DocumentNode::new(NodeType::Paragraph, SPANISH));
+    doc.add_child(
+        heading,
+        DocumentNode::new(NodeType::CodeBlock, "let revenue = 42;"),
+    );
+    let list = doc.add_node(DocumentNode::new(NodeType::List, ""));
+    doc.add_node(DocumentNode::new(NodeType::Paragraph, ENGLISH).with_language("deu"));
+    doc.add_child(list, DocumentNode::new(NodeType::ListItem, "Revenue"));
+    doc
+}
+
+#[test]
+fn test_detect_language_of_text() {
+    let detector = LanguageDetector::new();
+    assert_eq!(detector.detect(ENGLISH), Some("eng"));
+    assert_eq!(detector.detect(SPANISH), Some("spa"));
+    assert_eq!(detector.detect("Hi"), None, "too short to guess");
+
+    let detector = LanguageDetector::new()
+        .with_min_chars(0)
+        .with_min_confidence(0.0);
+    assert!(detector.detect("Hola, buenos días").is_some());
+}
+
+#[test]
+fn test_annotate_mixed_document() {
+    let mut doc = mixed();
+    let tagged = LanguageDetector::new().annotate(&mut doc);
+
+    let language = |i: usize| doc.nodes()[i].language();
+    // The heading is too short and has no parent to inherit from
+    assert_eq!(language(0), None);
+    assert_eq!(language(1), Some("eng"));
+    assert_eq!(language(2), Some("spa"));
+    assert_eq!(language(3), None, "code is not natural language");
+    // Existing tags are kept
+    assert_eq!(language(5), Some("deu"));
+    assert_eq!(tagged, 2);
+
+    // Spanish has the most text, the pre-tagged English text counting as German
+    assert_eq!(doc.metadata().language(), Some("spa"));
+}
+
+#[test]
+fn test_short_nodes_inherit_parent_language() {
+    let mut doc = DoclingDocument::new("list.md");
+    let list = doc.add_node(DocumentNode::new(NodeType::List, SPANISH));
+    doc.add_child(list, DocumentNode::new(NodeType::ListItem, "Ingresos"));
+
+    LanguageDetector::new().annotate(&mut doc);
+    assert_eq!(doc.nodes()[1].language(), Some("spa"));
+}
+
+#[test]
+fn test_allowlist_restricts_languages() {
+    let detector =
+        LanguageDetector::new()
+            .with_min_chars(0)
+            .with_languages(&["eng", "spa", "not-a-code"]);
+    let detected = detector.detect("Buenos días a todos").unwrap();
+    assert!(["eng", "spa"].contains(&detected));
+}
+
+#[test]
+fn test_document_language_not_overwritten() {
+    let mut doc = mixed();
+    let metadata = doc.metadata().clone().with_language("en");
+    *doc.metadata_mut() = metadata;
+
+    LanguageDetector::new().annotate(&mut doc);
+    assert_eq!(doc.metadata().language(), Some("en"));
+}
+
+#[test]
+fn test_language_serialized_with_node() {
+    let node = DocumentNode::new(NodeType::Paragraph, ENGLISH).with_language("eng");
+    let json = serde_json::to_value(&node).unwrap();
+    assert_eq!(json["item"]["language"], "eng");
+
+    let json = serde_json::to_value(DocumentNode::new(NodeType::Paragraph, "x")).unwrap();
+    assert!(json["item"].get("language").is_none());
+}
+
+#[test]
+fn test_chunks_carry_languages() {
+    let mut doc = mixed();
+    LanguageDetector::new().annotate(&mut doc);
+
+    let chunks: Vec<_> = HierarchicalChunker::new().chunk(&doc).collect();
+    let english = chunks.iter().find(|c| c.text == ENGLISH).unwrap();
+    assert_eq!(english.meta.languages, ["eng"]);
+    assert_eq!(english.meta.language(), Some("eng"));
+
+    // A window spanning both paragraphs has two languages and no single one
+    let chunks: Vec<_> = WindowChunker::new().chunk(&doc).collect();
+    assert_eq!(chunks.len(), 1);
+    assert_eq!(chunks[0].meta.languages, ["eng", "spa", "deu"]);
+    assert_eq!(chunks[0].meta.language(), None);
+}
+
+#[test]
+fn test_pipeline_language_detection() {
+    let content = format!("# Informe\n\n{}\n\n{}\n", SPANISH, SPANISH);
+    let input = InputDocument::from_bytes(content.into_bytes(), "es.md", InputFormat::Markdown);
+
+    let result = SimplePipeline::new().execute(&input).unwrap();
+    assert!(result
+        .document()
+        .nodes()
+        .iter()
+        .all(|n| n.language().is_none()));
+
+    let pipeline = SimplePipeline::new().with_language_detection(LanguageDetector::new());
+    let result = pipeline.execute(&input).unwrap();
+    let doc = result.document();
+    assert_eq!(doc.metadata().language(), Some("spa"));
+    assert!(doc
+        .nodes()
+        .iter()
+        .filter(|n| n.node_type() == NodeType::Paragraph)
+        .all(|n| n.language() == Some("spa")));
+}
@@ -64,3 +64,315 @@ fn test_simple_pipeline_unsupported_format() {
     let result = pipeline.execute(&input);
     assert!(result.is_err());
 }
+
+#[test]
+fn test_simple_pipeline_reports_progress() {
+    use docling_rs::datamodel::TimedStage;
+    use docling_rs::pipeline::{ConversionStage, ProgressEvent};
+    use std::sync::{Arc, Mutex};
+
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let recorded = Arc::clone(&events);
+    let pipeline = SimplePipeline::new()
+        .with_section_grouping(true)
+        .with_progress(move |name: &str, event: &ProgressEvent| {
+            recorded
+                .lock()
+                .unwrap()
+                .push((name.to_string(), event.clone()));
+        });
+    let input = InputDocument::from_bytes(
+        b"# Hello\n\nWorld.".to_vec(),
+        "test.md",
+        InputFormat::Markdown,
+    );
+    let result = pipeline.execute(&input).unwrap();
+
+    let events = events.lock().unwrap();
+    assert!(events.iter().all(|(name, _)| name == "test.md"));
+    // Timings vary from run to run, only their stages are checked
+    let (timings, events): (Vec<_>, Vec<_>) = events
+        .iter()
+        .map(|(_, event)| event.clone())
+        .partition(|event| matches!(event, ProgressEvent::Timing { .. }));
+    assert!(matches!(
+        timings[..],
+        [ProgressEvent::Timing {
+            stage: TimedStage::Parse,
+            ..
+        }]
+    ));
+    assert_eq!(
+        events,
+        [
+            ProgressEvent::Started { bytes: Some(15) },
+            ProgressEvent::Stage(ConversionStage::Parse),
+            ProgressEvent::Stage(ConversionStage::Enrich),
+            ProgressEvent::Finished {
+                nodes: result.document().nodes().len()
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_simple_pipeline_runs_stages_in_order() {
+    use docling_rs::datamodel::{Annotation, DoclingDocument};
+    use docling_rs::pipeline::PipelineStage;
+    use docling_rs::ConversionError;
+
+    struct Label(&'static str);
+
+    impl PipelineStage for Label {
+        fn name(&self) -> &str {
+            self.0
+        }
+
+        fn process(&self, doc: &mut DoclingDocument) -> Result<(), ConversionError> {
+            let id = doc.nodes()[0].id().unwrap();
+            doc.annotate(Annotation::new(id, self.0));
+            Ok(())
+        }
+    }
+
+    struct Reject;
+
+    impl PipelineStage for Reject {
+        fn name(&self) -> &str {
+            "reject"
+        }
+
+        fn process(&self, _doc: &mut DoclingDocument) -> Result<(), ConversionError> {
+            Err(ConversionError::InvalidFile("not allowed".to_string()))
+        }
+    }
+
+    let input = InputDocument::from_bytes(
+        b"# Hello\n\nWorld.".to_vec(),
+        "test.md",
+        InputFormat::Markdown,
+    );
+
+    let pipeline = SimplePipeline::new()
+        .with_stage(Label("first"))
+        .with_stage(Label("second"))
+        .with_section_grouping(true);
+    let result = pipeline.execute(&input).unwrap();
+    let doc = result.document();
+    let labels: Vec<_> = doc.annotations().iter().map(|a| a.label()).collect();
+    assert_eq!(labels, ["first", "second"]);
+    // Grouping runs after the stages and keeps their annotations
+    let annotated = doc.node(doc.annotations()[0].node()).unwrap();
+    assert_eq!(annotated.text_content(), Some("Hello"));
+
+    let error = SimplePipeline::new()
+        .with_stage(Label("first"))
+        .with_stage(Reject)
+        .execute(&input)
+        .unwrap_err();
+    assert!(matches!(&error, ConversionError::Stage { stage, .. } if stage == "reject"));
+    assert!(error.to_string().contains("not allowed"));
+}
+
+#[test]
+fn test_simple_pipeline_enforces_limits() {
+    use docling_rs::datamodel::DoclingDocument;
+    use docling_rs::pipeline::{ConversionLimits, ExceededLimit, PipelineStage};
+    use docling_rs::ConversionError;
+    use std::time::Duration;
+
+    struct Slow;
+
+    impl PipelineStage for Slow {
+        fn name(&self) -> &str {
+            "slow"
+        }
+
+        fn process(&self, _doc: &mut DoclingDocument) -> Result<(), ConversionError> {
+            std::thread::sleep(Duration::from_millis(50));
+            Ok(())
+        }
+    }
+
+    let input = InputDocument::from_bytes(
+        b"# Hello\n\nWorld.".to_vec(),
+        "test.md",
+        InputFormat::Markdown,
+    );
+
+    let generous = ConversionLimits::new()
+        .with_timeout(Duration::from_secs(60))
+        .with_max_pages(1)
+        .with_max_output_bytes(1024);
+    assert!(SimplePipeline::new()
+        .with_limits(generous)
+        .execute(&input)
+        .is_ok());
+
+    let error = SimplePipeline::new()
+        .with_limits(ConversionLimits::new().with_max_output_bytes(5))
+        .execute(&input)
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        ConversionError::LimitExceeded(ExceededLimit::OutputSize { bytes: 11, max: 5 })
+    ));
+
+    let timeout = Duration::from_millis(10);
+    let error = SimplePipeline::new()
+        .with_stage(Slow)
+        .with_limits(ConversionLimits::new().with_timeout(timeout))
+        .execute(&input)
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        ConversionError::LimitExceeded(ExceededLimit::Timeout(t)) if t == timeout
+    ));
+    assert!(error.to_string().starts_with("Limit exceeded"));
+}
+
+#[test]
+fn test_simple_pipeline_input_guardrails() {
+    use docling_rs::pipeline::{ConversionLimits, ExceededLimit};
+    use docling_rs::ConversionError;
+    use std::io::Write;
+
+    let limited = |limits| SimplePipeline::new().with_limits(limits);
+
+    // Oversized sources are refused before parsing
+    let input = InputDocument::from_bytes(
+        b"# Hello\n\nWorld.".to_vec(),
+        "test.md",
+        InputFormat::Markdown,
+    );
+    let error = limited(ConversionLimits::new().with_max_input_bytes(8))
+        .execute(&input)
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        ConversionError::LimitExceeded(ExceededLimit::InputSize { bytes: 15, max: 8 })
+    ));
+
+    // Deeply nested HTML
+    let html = format!(
+        "<html><body>{}deep{}</body></html>",
+        "<div>".repeat(100),
+        "</div>".repeat(100)
+    );
+    let input = InputDocument::from_bytes(html.into_bytes(), "deep.html", InputFormat::Html);
+    assert!(limited(ConversionLimits::new().with_max_depth(200))
+        .execute(&input)
+        .is_ok());
+    let error = limited(ConversionLimits::new().with_max_depth(50))
+        .execute(&input)
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        ConversionError::LimitExceeded(ExceededLimit::Depth { max: 50, .. })
+    ));
+
+    // A DOCX (zip) that expands far beyond its size
+    let mut bomb = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    bomb.start_file(
+        "word/document.xml",
+        zip::write::SimpleFileOptions::default(),
+    )
+    .unwrap();
+    bomb.write_all(&vec![b' '; 1 << 20]).unwrap();
+    let bytes = bomb.finish().unwrap().into_inner();
+    assert!(bytes.len() < 10_000);
+    let input = InputDocument::from_bytes(bytes, "bomb.docx", InputFormat::Docx);
+    let error = limited(ConversionLimits::new().with_max_decompressed_bytes(64 * 1024))
+        .execute(&input)
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        ConversionError::LimitExceeded(ExceededLimit::DecompressedSize { max: 65536, .. })
+    ));
+    assert!(error.to_string().contains("expands"));
+}
+
+#[test]
+fn test_simple_pipeline_partial_success_with_warnings() {
+    use docling_rs::datamodel::{ConversionResult, NodeType};
+    use docling_rs::pipeline::ProgressEvent;
+    use std::sync::{Arc, Mutex};
+
+    let input = InputDocument::from_bytes(
+        b"# Setup\n\n```sh\ncargo build\n".to_vec(),
+        "test.md",
+        InputFormat::Markdown,
+    );
+
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let seen = Arc::clone(&events);
+    let result = SimplePipeline::new()
+        .with_progress(move |_: &str, event: &ProgressEvent| {
+            if let ProgressEvent::Warning(warning) = event {
+                seen.lock().unwrap().push(warning.clone());
+            }
+        })
+        .execute(&input)
+        .unwrap();
+
+    assert_eq!(result.status(), ConversionStatus::PartialSuccess);
+    let warnings = result.warnings();
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(
+        warnings[0].to_string(),
+        "Code block opened on line 3 is never closed"
+    );
+    assert_eq!(warnings[0].page(), None);
+    assert_eq!(*events.lock().unwrap(), warnings);
+    // The unterminated block is still converted
+    assert!(result
+        .document()
+        .nodes()
+        .iter()
+        .any(|node| node.node_type() == NodeType::CodeBlock));
+
+    let json = serde_json::to_string(&result).unwrap();
+    let loaded: ConversionResult = serde_json::from_str(&json).unwrap();
+    assert_eq!(loaded.warnings(), warnings);
+
+    let clean = InputDocument::from_bytes(b"# Setup".to_vec(), "ok.md", InputFormat::Markdown);
+    let result = SimplePipeline::new().execute(&clean).unwrap();
+    assert_eq!(result.status(), ConversionStatus::Success);
+    assert!(result.warnings().is_empty());
+    assert!(!serde_json::to_string(&result).unwrap().contains("warnings"));
+}
+
+#[test]
+fn test_simple_pipeline_metrics() {
+    use docling_rs::datamodel::{ConversionResult, TimedStage};
+
+    let input = InputDocument::from_bytes(
+        b"# Setup\n\nInstall it.\n\n```sh\ncargo build\n".to_vec(),
+        "test.md",
+        InputFormat::Markdown,
+    );
+    let result = SimplePipeline::new().execute(&input).unwrap();
+
+    let metrics = result.metrics();
+    assert_eq!(metrics.node_count(), result.document().nodes().len());
+    let text: usize = result
+        .document()
+        .nodes()
+        .iter()
+        .filter_map(|node| node.text_content())
+        .map(|text| text.chars().count())
+        .sum();
+    assert!(text > 0);
+    assert_eq!(metrics.characters(), text);
+    assert_eq!(metrics.warning_count(), 1);
+    assert_eq!(metrics.total_pages(), 0);
+    assert!(metrics.stages().get(TimedStage::Parse).is_some());
+    assert_eq!(metrics.stages().get(TimedStage::Ocr), None);
+    assert!(metrics.to_string().contains("warnings: 1"));
+
+    let json = serde_json::to_value(&result).unwrap();
+    assert_eq!(json["metrics"]["node_count"], metrics.node_count());
+    assert!(json["metrics"]["stages"]["parse_ms"].is_u64());
+    let loaded: ConversionResult = serde_json::from_value(json).unwrap();
+    assert_eq!(loaded.metrics(), metrics);
+}